@@ -1,8 +1,60 @@
+use std::io::Write;
+use std::path::Path;
+
+use flate2::write::GzEncoder;
+use flate2::Compression;
 use vergen::{vergen, Config, ShaKind};
 
+const RELEASES_SNAPSHOT: &str = "resources/stable-releases.txt";
+const SNAPSHOT_DATE_KEY: &str = "# snapshot-date:";
+
 fn main() {
     // generate build info
     let mut config = Config::default();
     *config.git_mut().sha_kind_mut() = ShaKind::Short;
     vergen(config).expect("Unable to generate build info with 'vergen'");
+
+    embed_releases_snapshot();
+}
+
+/// Compresses the snapshot of the Rust release list, so it can be embedded in the binary and
+/// used as a fallback release source.
+fn embed_releases_snapshot() {
+    println!("cargo:rerun-if-changed={}", RELEASES_SNAPSHOT);
+
+    let contents = std::fs::read_to_string(RELEASES_SNAPSHOT)
+        .expect("Unable to read the Rust releases snapshot");
+
+    let snapshot_date = contents
+        .lines()
+        .find_map(|line| line.strip_prefix(SNAPSHOT_DATE_KEY))
+        .map(str::trim)
+        .expect("Rust releases snapshot is missing its snapshot date");
+
+    let releases = contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+    encoder
+        .write_all(releases.as_bytes())
+        .expect("Unable to compress the Rust releases snapshot");
+    let compressed = encoder
+        .finish()
+        .expect("Unable to compress the Rust releases snapshot");
+
+    let out_dir = std::env::var("OUT_DIR").expect("OUT_DIR is set by cargo");
+    std::fs::write(
+        Path::new(&out_dir).join("stable-releases.snapshot.gz"),
+        compressed,
+    )
+    .expect("Unable to write the compressed Rust releases snapshot");
+
+    println!(
+        "cargo:rustc-env=CARGO_MSRV_RELEASES_SNAPSHOT_DATE={}",
+        snapshot_date
+    );
 }
@@ -5,22 +5,29 @@ use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use storyteller::{EventHandler, EventListener, FinishProcessing};
-use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
+use tracing_subscriber::layer::SubscriberExt;
 
 use cargo_msrv::cli::CargoCli;
-use cargo_msrv::config::{Config, OutputFormat, TracingOptions, TracingTargetOption};
+use cargo_msrv::config::{
+    ColorChoice, Config, EventFilter, Locale, LogRotation, OutputFormat, TracingOptions,
+    TracingTargetOption,
+};
 use cargo_msrv::error::CargoMSRVError;
 use cargo_msrv::exit_code::ExitCode;
+use cargo_msrv::log_file::{log_folder, LogFile};
 use cargo_msrv::reporter::{
-    DiscardOutputHandler, HumanProgressHandler, JsonHandler, ReporterSetup,
+    CiHandler, DiscardOutputHandler, GitHubCheckHandler, HumanProgressHandler, InvalidEvents,
+    JsonHandler, OtelHandler, ReporterSetup, SchemaValidationHandler, WebhookHandler,
 };
-use cargo_msrv::reporter::{Event, Reporter, TerminateWithFailure};
+use cargo_msrv::reporter::{Event, LogFileUnavailable, Reporter, TerminateWithFailure};
 use cargo_msrv::run_app;
 
 fn main() {
     std::process::exit(
         match _main(std::env::args_os) {
-            Ok((_guard, exit_code)) => exit_code,
+            Ok((_logging, exit_code)) => exit_code,
             Err(err) => {
                 tracing::error!("{}", err);
                 ExitCode::Failure
@@ -32,25 +39,32 @@ fn main() {
 
 fn _main<I: IntoIterator<Item = OsString>, F: FnOnce() -> I + Clone>(
     args: F,
-) -> Result<(Option<TracingGuard>, ExitCode), InstanceError> {
+) -> Result<(Option<Tracing>, ExitCode), InstanceError> {
     let matches = CargoCli::parse_args(args());
-    let config = Config::try_from(&matches).map_err(InstanceError::CargoMsrv)?;
+    let config = match Config::try_from(&matches) {
+        Ok(config) => config,
+        // The user output isn't set up before the configuration is resolved, so the failure is
+        // written directly, for consumers of the json output which can't parse the prose
+        Err(err) if matches.output_format() == OutputFormat::Json => {
+            JsonHandler::stderr().handle(TerminateWithFailure::new(err).into());
+            return Ok((None, ExitCode::Failure));
+        }
+        Err(err) => return Err(InstanceError::CargoMsrv(err)),
+    };
 
     // NB: We must collect the guard of the non-blocking tracing appender, since it will only live as
     // long as the lifetime of the worker guard. If we don't do this, the guard would be dropped after
     // the scope of `if !config.no_tracing() { ... }` ended, and as a result, anything logged in
     // `init_and_run` would not be logged.
-    let mut guard = None;
+    let logging = config
+        .tracing()
+        .map(|options| init_tracing(options, config.scratch_dir()))
+        .transpose()?;
 
-    if let Some(options) = config.tracing() {
-        let tracing_config = TracingConfig::try_from_options(options)?;
-        guard = Some(init_tracing(&tracing_config)?);
-    }
-
-    init_and_run(&config).map(|exit_code| (guard, exit_code))
+    init_and_run(&config, logging.as_ref()).map(|exit_code| (logging, exit_code))
 }
 
-fn init_and_run(config: &Config) -> Result<ExitCode, InstanceError> {
+fn init_and_run(config: &Config, logging: Option<&Tracing>) -> Result<ExitCode, InstanceError> {
     tracing::info!(
         cargo_msrv_version = env!("CARGO_PKG_VERSION"),
         "initializing"
@@ -61,32 +75,69 @@ fn init_and_run(config: &Config) -> Result<ExitCode, InstanceError> {
 
     tracing::info!("storyteller channel created");
 
-    let handler = WrappingHandler::from(config.output_format());
+    let validation = config
+        .validate_output()
+        .then(SchemaValidationHandler::stderr);
+    let invalid_events = validation
+        .as_ref()
+        .map(SchemaValidationHandler::invalid_events);
+
+    let handler = NotifyingHandler {
+        output: WrappingHandler::new(
+            config.output_format(),
+            config.locale(),
+            config.color(),
+            config.event_filter().cloned(),
+        ),
+        webhook: config.webhook().cloned().map(WebhookHandler::new),
+        github_check: config.github_check().cloned().map(GitHubCheckHandler::new),
+        otel: config.otel_endpoint().map(OtelHandler::new),
+        validation,
+    };
     let finalizer = listener.run_handler(Arc::new(handler));
     tracing::info!("storyteller started handler");
+
+    // Only now the user can be told that logs are not written to a file, since the user output
+    // was not available yet while tracing was initialized
+    if let Some(unavailable) = logging.and_then(|logging| logging.log_file_unavailable.clone()) {
+        reporter
+            .report_event(unavailable)
+            .map_err(|_| InstanceError::StorytellerSend)?;
+    }
+
     tracing::info!("start run_app");
 
     let res = run_app(config, &reporter);
 
     tracing::info!("finished run_app");
 
-    let exit_code = get_exit_code(res, &reporter)?;
+    let log_file = logging.and_then(|logging| logging.log_file.as_deref());
+    let exit_code = get_exit_code(res, &reporter, log_file)?;
     disconnect_reporter(reporter)?;
     wait_for_user_output(finalizer)?;
 
-    Ok(exit_code)
+    Ok(fail_on_invalid_events(exit_code, invalid_events.as_ref()))
 }
 
 /// Get the exit code from the result of the program's main work unit.
+///
+/// On failure, the reported termination points to the `log_file`, if logs are written to a file.
 fn get_exit_code(
     result: Result<(), CargoMSRVError>,
     reporter: &impl Reporter,
+    log_file: Option<&Path>,
 ) -> Result<ExitCode, InstanceError> {
     Ok(match result {
         Ok(_) => ExitCode::Success,
         Err(err) => {
+            let termination = TerminateWithFailure::new(err);
+            let termination = match log_file {
+                Some(path) => termination.with_log_file(path),
+                None => termination,
+            };
+
             reporter
-                .report_event(TerminateWithFailure::new(err))
+                .report_event(termination)
                 .map_err(|_| InstanceError::StorytellerSend)?;
 
             ExitCode::Failure
@@ -94,11 +145,24 @@ fn get_exit_code(
     })
 }
 
+/// With `--validate-output`, the run fails when an event did not match the schema of the events,
+/// even if the program's main work unit succeeded.
+fn fail_on_invalid_events(exit_code: ExitCode, invalid_events: Option<&InvalidEvents>) -> ExitCode {
+    match invalid_events.map(InvalidEvents::count) {
+        Some(count) if count > 0 => {
+            tracing::error!(count, "events did not match the schema");
+            ExitCode::Failure
+        }
+        _ => exit_code,
+    }
+}
+
 /// Enumerates the in our program available output handlers, and implements EventHandler which
 /// directly delegates the implementation to the wrapped handlers.
 enum WrappingHandler {
     HumanProgress(HumanProgressHandler),
     Json(JsonHandler<io::Stderr>),
+    Ci(CiHandler<io::Stderr>),
     DiscardOutput(DiscardOutputHandler),
 }
 
@@ -109,6 +173,7 @@ impl EventHandler for WrappingHandler {
         match self {
             WrappingHandler::HumanProgress(inner) => inner.handle(event),
             WrappingHandler::Json(inner) => inner.handle(event),
+            WrappingHandler::Ci(inner) => inner.handle(event),
             WrappingHandler::DiscardOutput(inner) => inner.handle(event),
         }
     }
@@ -117,16 +182,23 @@ impl EventHandler for WrappingHandler {
         match self {
             WrappingHandler::HumanProgress(inner) => inner.finish(),
             WrappingHandler::Json(inner) => inner.finish(),
+            WrappingHandler::Ci(inner) => inner.finish(),
             WrappingHandler::DiscardOutput(inner) => inner.finish(),
         }
     }
 }
 
-impl From<OutputFormat> for WrappingHandler {
-    fn from(output_format: OutputFormat) -> Self {
+impl WrappingHandler {
+    fn new(
+        output_format: OutputFormat,
+        locale: Locale,
+        color: ColorChoice,
+        event_filter: Option<EventFilter>,
+    ) -> Self {
         match output_format {
-            OutputFormat::Human => Self::HumanProgress(HumanProgressHandler::default()),
-            OutputFormat::Json => Self::Json(JsonHandler::stderr()),
+            OutputFormat::Human => Self::HumanProgress(HumanProgressHandler::new(locale, color)),
+            OutputFormat::Json => Self::Json(JsonHandler::stderr().with_filter(event_filter)),
+            OutputFormat::Ci => Self::Ci(CiHandler::stderr(locale)),
             OutputFormat::None => {
                 // To disable regular output. Useful when outputting logs to stdout, as the
                 //   regular output and the log output may otherwise interfere with each other.
@@ -136,6 +208,62 @@ impl From<OutputFormat> for WrappingHandler {
     }
 }
 
+/// Delegates to the user output handler, to the webhook handler if a webhook should be notified,
+/// to the GitHub check handler if the outcome should be reported as a check run, to the
+/// OpenTelemetry handler if the checks should be exported as spans, and to the schema validation
+/// handler if the events should be validated.
+struct NotifyingHandler {
+    output: WrappingHandler,
+    webhook: Option<WebhookHandler>,
+    github_check: Option<GitHubCheckHandler>,
+    otel: Option<OtelHandler>,
+    validation: Option<SchemaValidationHandler<io::Stderr>>,
+}
+
+impl EventHandler for NotifyingHandler {
+    type Event = Event;
+
+    fn handle(&self, event: Self::Event) {
+        if let Some(webhook) = &self.webhook {
+            webhook.handle(event.clone());
+        }
+
+        if let Some(github_check) = &self.github_check {
+            github_check.handle(event.clone());
+        }
+
+        if let Some(otel) = &self.otel {
+            otel.handle(event.clone());
+        }
+
+        if let Some(validation) = &self.validation {
+            validation.handle(event.clone());
+        }
+
+        self.output.handle(event);
+    }
+
+    fn finish(&self) {
+        if let Some(webhook) = &self.webhook {
+            webhook.finish();
+        }
+
+        if let Some(github_check) = &self.github_check {
+            github_check.finish();
+        }
+
+        if let Some(otel) = &self.otel {
+            otel.finish();
+        }
+
+        if let Some(validation) = &self.validation {
+            validation.finish();
+        }
+
+        self.output.finish();
+    }
+}
+
 /// Disconnect the reporter, signalling that the program is finished, and we can now finish
 /// up processing the last user output events.
 fn disconnect_reporter(reporter: impl Reporter) -> Result<(), InstanceError> {
@@ -159,94 +287,86 @@ fn wait_for_user_output(finalizer: impl FinishProcessing) -> Result<(), Instance
     Ok(())
 }
 
-fn init_tracing(tracing_config: &TracingConfig) -> Result<TracingGuard, InstanceError> {
-    let level = tracing_config.level;
-
-    match &tracing_config.target {
-        // Log (non-blocking) to disk
-        TracingTarget::ToDisk(path) => {
-            let guard = init_tracing_to_file(path, level);
-
-            let folder = format!("{}", path.display());
-            tracing::debug!(log_folder = folder.as_str());
-
-            guard
-        }
-        // Log to stdout
-        TracingTarget::Stdout => init_tracing_to_stdout(level),
-    }
+/// The state of tracing, once initialized.
+struct Tracing {
+    // Dropping the guard of the non-blocking log file writer stops the writer
+    _guard: Option<tracing_appender::non_blocking::WorkerGuard>,
+    // The path of the log file, if logs are written to a file
+    log_file: Option<PathBuf>,
+    // Set when logs should have been written to a file, but the log file could not be opened
+    log_file_unavailable: Option<LogFileUnavailable>,
 }
 
-fn init_tracing_to_file(
-    log_folder: impl AsRef<Path>,
-    level: tracing::Level,
-) -> Result<TracingGuard, InstanceError> {
-    let file_appender = RollingFileAppender::new(Rotation::DAILY, log_folder, "cargo-msrv-log");
-    let (non_blocking, guard) = tracing_appender::non_blocking(file_appender);
+fn init_tracing(
+    options: &TracingOptions,
+    scratch_dir: Option<&Path>,
+) -> Result<Tracing, InstanceError> {
+    let level: tracing::Level = (*options.level()).into();
+    let target = options.target();
+
+    // A log file which can't be opened is not fatal: the program continues without it
+    let (log_file, log_file_unavailable) = if target.writes_to_file() {
+        match open_log_file(options.rotation(), scratch_dir) {
+            Ok(log_file) => (Some(log_file), None),
+            Err(unavailable) => (None, Some(unavailable)),
+        }
+    } else {
+        (None, None)
+    };
 
-    let subscriber = tracing_subscriber::fmt()
-        .json()
-        .with_max_level(level)
-        .with_writer(non_blocking)
-        .finish();
+    let log_file_path = log_file.as_ref().map(|file| file.path().to_path_buf());
 
-    tracing::subscriber::set_global_default(subscriber)
-        .map_err(|_| InstanceError::UnableToInitTracing)?;
+    let (file_layer, guard) = match log_file {
+        Some(log_file) => {
+            let (non_blocking, guard) = tracing_appender::non_blocking(log_file);
+            let layer = tracing_subscriber::fmt::layer()
+                .json()
+                .with_writer(non_blocking);
 
-    Ok(TracingGuard::NonBlockingGuard(guard))
-}
+            (Some(layer), Some(guard))
+        }
+        None => (None, None),
+    };
+
+    let console_layer = match target {
+        TracingTargetOption::File => None,
+        TracingTargetOption::Stdout => Some(BoxMakeWriter::new(io::stdout)),
+        TracingTargetOption::Stderr | TracingTargetOption::Both => {
+            Some(BoxMakeWriter::new(io::stderr))
+        }
+    }
+    .map(|writer| tracing_subscriber::fmt::layer().with_writer(writer));
 
-fn init_tracing_to_stdout(level: tracing::Level) -> Result<TracingGuard, InstanceError> {
-    let subscriber = tracing_subscriber::fmt().with_max_level(level).finish();
+    let subscriber = tracing_subscriber::registry()
+        .with(LevelFilter::from_level(level))
+        .with(file_layer)
+        .with(console_layer);
 
     tracing::subscriber::set_global_default(subscriber)
         .map_err(|_| InstanceError::UnableToInitTracing)?;
 
-    Ok(TracingGuard::None)
-}
-
-struct TracingConfig {
-    level: tracing::Level,
-    target: TracingTarget,
-}
-
-impl TracingConfig {
-    fn try_from_options(config: &TracingOptions) -> Result<Self, InstanceError> {
-        let target = TracingTarget::try_from_option(config.target())?;
-
-        Ok(Self {
-            level: (*config.level()).into(),
-            target,
-        })
+    if let Some(path) = &log_file_path {
+        let path = format!("{}", path.display());
+        tracing::debug!(log_file = path.as_str());
     }
-}
 
-enum TracingTarget {
-    ToDisk(PathBuf),
-    Stdout,
-}
-
-impl TracingTarget {
-    fn try_from_option(option: &TracingTargetOption) -> Result<Self, InstanceError> {
-        match option {
-            TracingTargetOption::File => {
-                let folder = log_folder()?;
-                Ok(Self::ToDisk(folder))
-            }
-            TracingTargetOption::Stdout => Ok(Self::Stdout),
-        }
-    }
+    Ok(Tracing {
+        _guard: guard,
+        log_file: log_file_path,
+        log_file_unavailable,
+    })
 }
 
-enum TracingGuard {
-    NonBlockingGuard(tracing_appender::non_blocking::WorkerGuard),
-    None,
-}
+fn open_log_file(
+    rotation: &LogRotation,
+    scratch_dir: Option<&Path>,
+) -> Result<LogFile, LogFileUnavailable> {
+    let folder = log_folder(scratch_dir).ok_or_else(|| {
+        LogFileUnavailable::new(None, "unable to determine the local data directory")
+    })?;
 
-fn log_folder() -> Result<PathBuf, InstanceError> {
-    dirs::data_local_dir()
-        .map(|path| path.join("cargo-msrv"))
-        .ok_or(InstanceError::UnableToAccessLogFolder)
+    LogFile::open(&folder, rotation)
+        .map_err(|error| LogFileUnavailable::new(Some(folder), error.to_string()))
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -261,9 +381,6 @@ enum InstanceError {
     #[error("Unable to init logger, run with --no-log to try again without logging.")]
     UnableToInitTracing,
 
-    #[error("Unable to access log folder, run with --no-log to try again without logging.")]
-    UnableToAccessLogFolder,
-
     #[error("Failed to disconnect user output channel (storyteller)")]
     StorytellerDisconnect,
 
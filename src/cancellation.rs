@@ -0,0 +1,105 @@
+//! Cancellation of a running cargo-msrv action, for programs which use cargo-msrv as a library.
+//!
+//! A [`CancellationToken`] is given to the [`ConfigBuilder`](crate::config::ConfigBuilder). When it
+//! is cancelled, e.g. from another thread, the toolchain which is currently installed or checked
+//! is stopped by killing its child process. The lockfile is then moved back in place, and the
+//! action ends with [`CargoMSRVError::Cancelled`].
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::error::{CargoMSRVError, TResult};
+
+/// How often a blocking operation, like a running process, checks whether it should be cancelled.
+pub(crate) const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// A handle by which a running action can be cancelled. Clones share their cancellation state,
+/// so a clone can be kept to cancel the action which was given the original token.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+    /// The cancellation state of the tokens this token was derived from, with
+    /// [`CancellationToken::child`]
+    parents: Vec<Arc<AtomicBool>>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request the action to stop as soon as possible.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        std::iter::once(&self.cancelled)
+            .chain(&self.parents)
+            .any(|cancelled| cancelled.load(Ordering::SeqCst))
+    }
+
+    /// A token which is cancelled along with this token, but which can also be cancelled by
+    /// itself, without cancelling this token, e.g. to stop work which runs in the background.
+    pub(crate) fn child(&self) -> Self {
+        let mut parents = self.parents.clone();
+        parents.push(Arc::clone(&self.cancelled));
+
+        Self {
+            cancelled: Arc::new(AtomicBool::new(false)),
+            parents,
+        }
+    }
+
+    /// Returns [`CargoMSRVError::Cancelled`] if cancellation was requested, so the action can be
+    /// unwound with `?`.
+    pub(crate) fn check(&self) -> TResult<()> {
+        if self.is_cancelled() {
+            Err(CargoMSRVError::Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clones_share_cancellation() {
+        let token = CancellationToken::new();
+        let clone = token.clone();
+
+        assert!(token.check().is_ok());
+
+        clone.cancel();
+
+        assert!(token.is_cancelled());
+        assert!(matches!(token.check(), Err(CargoMSRVError::Cancelled)));
+    }
+
+    #[test]
+    fn child_is_cancelled_with_parent() {
+        let token = CancellationToken::new();
+        let child = token.child();
+        let grandchild = child.child();
+
+        token.cancel();
+
+        assert!(child.is_cancelled());
+        assert!(grandchild.is_cancelled());
+    }
+
+    #[test]
+    fn parent_is_not_cancelled_with_child() {
+        let token = CancellationToken::new();
+        let child = token.child();
+
+        child.cancel();
+
+        assert!(child.is_cancelled());
+        assert!(!token.is_cancelled());
+    }
+}
@@ -0,0 +1,267 @@
+//! Which components, like `clippy` and `rustfmt`, were shipped with a Rust release, as listed by
+//! its channel manifest on the Rust distribution server, e.g. `dist/channel-rust-1.56.0.toml`.
+//!
+//! A component is not shipped with each release, nor for each target: `clippy` is shipped since
+//! Rust 1.29 only, and `rustfmt` was missing from some releases for some targets. When the check
+//! requires such a component, a release which doesn't ship it can be rejected right away, instead
+//! of failing while the component is installed.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use toml_edit::Document;
+
+use crate::config::Config;
+use crate::error::{CargoMSRVError, TResult};
+use crate::semver;
+
+const DIST_URL: &str = "https://static.rust-lang.org/dist";
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The target of the packages which are shipped for each target, like `rust-src`.
+const ANY_TARGET: &str = "*";
+
+/// Provides the components which were shipped with a release.
+pub(crate) trait ComponentSource {
+    /// The components which were shipped with the given release, for the given host.
+    fn components(&self, version: &semver::Version, host: &str) -> TResult<Components>;
+}
+
+/// The components which were shipped with a release, for one host.
+#[derive(Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub(crate) struct Components {
+    /// The packages which are available for the host
+    available: BTreeSet<String>,
+    /// The package of a component, when the component was renamed, e.g. `clippy` was shipped as
+    /// the `clippy-preview` package
+    renames: BTreeMap<String, String>,
+}
+
+impl Components {
+    /// Read the components which are available for the host from a channel manifest.
+    pub(crate) fn parse(manifest: &str, host: &str) -> Result<Self, String> {
+        let document = manifest
+            .parse::<Document>()
+            .map_err(|err| err.to_string())?;
+
+        let available = document
+            .get("pkg")
+            .and_then(|pkg| pkg.as_table_like())
+            .into_iter()
+            .flat_map(|pkg| pkg.iter())
+            .filter(|(_, package)| {
+                [host, ANY_TARGET].iter().any(|target| {
+                    package
+                        .get("target")
+                        .and_then(|targets| targets.get(*target))
+                        .and_then(|target| target.get("available"))
+                        .and_then(|available| available.as_bool())
+                        .unwrap_or(false)
+                })
+            })
+            .map(|(name, _)| name.to_string())
+            .collect();
+
+        let renames = document
+            .get("renames")
+            .and_then(|renames| renames.as_table_like())
+            .into_iter()
+            .flat_map(|renames| renames.iter())
+            .filter_map(|(name, rename)| {
+                let to = rename.get("to").and_then(|to| to.as_str())?;
+                Some((name.to_string(), to.to_string()))
+            })
+            .collect();
+
+        Ok(Self { available, renames })
+    }
+
+    /// Whether the component is available, under its own name, its new name, or, for releases
+    /// which predate the renames, as a preview, like `rustfmt-preview`.
+    pub(crate) fn is_available(&self, component: &str) -> bool {
+        let renamed = self.renames.get(component).map(String::as_str);
+        let preview = format!("{}-preview", component);
+
+        renamed
+            .into_iter()
+            .chain([component, preview.as_str()])
+            .any(|package| self.available.contains(package))
+    }
+}
+
+/// Fetches the channel manifests from the Rust distribution server.
+///
+/// Since a channel manifest doesn't change once a release is published, the components which are
+/// read from it are cached on disk, so each manifest is fetched once only.
+pub(crate) struct ChannelManifests {
+    cache: Option<PathBuf>,
+}
+
+impl ChannelManifests {
+    pub(crate) fn new(config: &Config) -> Self {
+        Self {
+            cache: cache_dir(config.scratch_dir()),
+        }
+    }
+
+    fn read(&self, version: &semver::Version, host: &str) -> Option<Components> {
+        let path = self.cache.as_ref()?.join(file_name(version, host));
+        let contents = std::fs::read_to_string(path).ok()?;
+
+        serde_json::from_str(&contents)
+            .map_err(|error| warn!(%version, %error, "ignoring unreadable cached components"))
+            .ok()
+    }
+
+    /// Write the cached components. Failing to do so is not fatal, since the manifest will be
+    /// fetched again on the next run.
+    fn write(&self, version: &semver::Version, host: &str, components: &Components) {
+        let dir = match &self.cache {
+            Some(dir) => dir,
+            None => return,
+        };
+
+        let path = dir.join(file_name(version, host));
+
+        let result = std::fs::create_dir_all(dir).and_then(|_| {
+            let contents = serde_json::to_string(components)?;
+            std::fs::write(&path, contents)
+        });
+
+        if let Err(error) = result {
+            warn!(path = %path.display(), %error, "unable to write cached components");
+        }
+    }
+}
+
+impl ComponentSource for ChannelManifests {
+    fn components(&self, version: &semver::Version, host: &str) -> TResult<Components> {
+        if let Some(components) = self.read(version, host) {
+            return Ok(components);
+        }
+
+        let url = format!("{}/channel-rust-{}.toml", DIST_URL, version);
+        let components = fetch(&url, host)?;
+        self.write(version, host, &components);
+
+        Ok(components)
+    }
+}
+
+fn fetch(url: &str, host: &str) -> TResult<Components> {
+    let fetch_error = |message: String| CargoMSRVError::FetchChannelManifest {
+        url: url.to_string(),
+        message,
+    };
+
+    let response = attohttpc::get(url)
+        .header("User-Agent", "cargo-msrv")
+        .timeout(TIMEOUT)
+        .send()
+        .map_err(|err| fetch_error(err.to_string()))?;
+
+    if !response.is_success() {
+        return Err(fetch_error(format!(
+            "unexpected status code {}",
+            response.status().as_u16()
+        )));
+    }
+
+    let manifest = response
+        .text()
+        .map_err(|err| fetch_error(err.to_string()))?;
+
+    Components::parse(&manifest, host).map_err(fetch_error)
+}
+
+/// The directory in which the components of fetched channel manifests are cached, if it can be
+/// determined.
+fn cache_dir(scratch_dir: Option<&Path>) -> Option<PathBuf> {
+    if let Some(dir) = scratch_dir {
+        return Some(dir.join("channel-manifests"));
+    }
+
+    dirs::cache_dir().map(|path| path.join("cargo-msrv").join("channel-manifests"))
+}
+
+fn file_name(version: &semver::Version, host: &str) -> String {
+    format!("{}-{}.json", version, host)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_dir::{DirBuilder, TestDir};
+
+    const MANIFEST: &str = r#"
+manifest-version = "2"
+date = "2021-10-21"
+
+[pkg.rust-src.target."*"]
+available = true
+
+[pkg.clippy-preview.target.x86_64-unknown-linux-gnu]
+available = true
+
+[pkg.clippy-preview.target.riscv64gc-unknown-linux-gnu]
+available = false
+
+[pkg.rustfmt-preview.target.x86_64-unknown-linux-gnu]
+available = true
+
+[pkg.miri-preview.target.x86_64-unknown-linux-gnu]
+available = false
+
+[renames.clippy]
+to = "clippy-preview"
+
+[renames.rustfmt]
+to = "rustfmt-preview"
+"#;
+
+    #[yare::parameterized(
+        renamed = { "x86_64-unknown-linux-gnu", "clippy", true },
+        not_shipped_for_host = { "riscv64gc-unknown-linux-gnu", "clippy", false },
+        any_target = { "riscv64gc-unknown-linux-gnu", "rust-src", true },
+        not_available = { "x86_64-unknown-linux-gnu", "miri", false },
+        unknown_component = { "x86_64-unknown-linux-gnu", "llvm-tools", false },
+    )]
+    fn available(host: &str, component: &str, expected: bool) {
+        let components = Components::parse(MANIFEST, host).unwrap();
+
+        assert_eq!(components.is_available(component), expected);
+    }
+
+    #[test]
+    fn preview_without_renames() {
+        let manifest = MANIFEST.replace("[renames.rustfmt]\nto = \"rustfmt-preview\"", "");
+        let components = Components::parse(&manifest, "x86_64-unknown-linux-gnu").unwrap();
+
+        assert!(components.is_available("rustfmt"));
+    }
+
+    #[test]
+    fn invalid_manifest() {
+        assert!(Components::parse("[pkg", "x86_64-unknown-linux-gnu").is_err());
+    }
+
+    #[test]
+    fn cached_components() {
+        let dir = TestDir::temp();
+        let manifests = ChannelManifests {
+            cache: Some(dir.root().join("channel-manifests")),
+        };
+
+        let version = semver::Version::new(1, 56, 0);
+        let components = Components::parse(MANIFEST, "x86_64-unknown-linux-gnu").unwrap();
+        manifests.write(&version, "x86_64-unknown-linux-gnu", &components);
+
+        assert_eq!(
+            manifests
+                .components(&version, "x86_64-unknown-linux-gnu")
+                .unwrap(),
+            components
+        );
+    }
+}
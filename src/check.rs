@@ -1,15 +1,79 @@
 use crate::config::Config;
+use crate::reporter::event::DependencyCache;
 use crate::toolchain::ToolchainSpec;
 
+mod budgeted_check;
+mod chain;
+mod component_availability;
+mod fetched_dependencies;
+mod hooks;
+mod ignored_lockfile;
+mod remote_check;
+mod required_rust_version;
+mod retried_check;
+mod rewritten_manifest;
+mod rustc_wrapper;
 mod rustup_toolchain_check;
+mod server_check;
 #[cfg(test)]
 mod testing;
+mod unit_graph;
 
 use crate::{Outcome, TResult};
+pub use budgeted_check::BudgetedCheck;
+pub use chain::{CheckChain, CheckLayer};
+pub(crate) use component_availability::ComponentAvailabilityCheck;
+pub(crate) use fetched_dependencies::FetchedDependenciesCheck;
+pub(crate) use ignored_lockfile::IgnoredLockfileCheck;
+pub use remote_check::RemoteCheck;
+pub use required_rust_version::RequiredRustVersionCheck;
+pub(crate) use retried_check::RetriedCheck;
+pub(crate) use rewritten_manifest::{PatchedManifestCheck, PrunedManifestCheck};
+pub(crate) use rustc_wrapper::is_sccache;
 pub use rustup_toolchain_check::RustupToolchainCheck;
+pub use server_check::ServerCheck;
 #[cfg(test)]
 pub use testing::TestRunner;
+pub(crate) use unit_graph::UnitGraphReuseCheck;
 
 pub trait Check {
     fn check(&self, config: &Config, toolchain: &ToolchainSpec) -> TResult<Outcome>;
+
+    /// How the dependencies were shared between the checks so far, if they were fetched once for
+    /// all checks.
+    fn dependency_cache(&self) -> Option<DependencyCache> {
+        None
+    }
+
+    /// Start to prepare the given toolchains, which may be checked next, in the background, e.g.
+    /// by installing them, with `--prefetch-toolchains`. Does nothing by default.
+    fn prefetch(&self, _config: &Config, _toolchains: &[ToolchainSpec]) {}
+}
+
+impl<C: Check> Check for &C {
+    fn check(&self, config: &Config, toolchain: &ToolchainSpec) -> TResult<Outcome> {
+        (*self).check(config, toolchain)
+    }
+
+    fn dependency_cache(&self) -> Option<DependencyCache> {
+        (*self).dependency_cache()
+    }
+
+    fn prefetch(&self, config: &Config, toolchains: &[ToolchainSpec]) {
+        (*self).prefetch(config, toolchains)
+    }
+}
+
+impl<C: Check + ?Sized> Check for Box<C> {
+    fn check(&self, config: &Config, toolchain: &ToolchainSpec) -> TResult<Outcome> {
+        (**self).check(config, toolchain)
+    }
+
+    fn dependency_cache(&self) -> Option<DependencyCache> {
+        (**self).dependency_cache()
+    }
+
+    fn prefetch(&self, config: &Config, toolchains: &[ToolchainSpec]) {
+        (**self).prefetch(config, toolchains)
+    }
 }
@@ -0,0 +1,144 @@
+use std::cell::{Cell, RefCell};
+
+use crate::check::{Check, CheckLayer};
+use crate::error::CargoMSRVError;
+use crate::outcome::Outcome;
+use crate::semver;
+use crate::toolchain::ToolchainSpec;
+use crate::{Config, TResult};
+
+/// A [`CheckLayer`] which refuses to run more checks than the budget given with `--max-checks`
+/// allows.
+///
+/// Once the budget is exhausted, the next check fails with
+/// [`CargoMSRVError::CheckBudgetExhausted`], which stops the search. The outcomes of the checks
+/// which did run are remembered, so the range of Rust versions in which the MSRV must lie can
+/// still be reported.
+pub struct BudgetedCheck {
+    max_checks: Option<u64>,
+    checks: Cell<u64>,
+    least_recent_accepted: RefCell<Option<semver::Version>>,
+    most_recent_rejected: RefCell<Option<semver::Version>>,
+}
+
+impl BudgetedCheck {
+    /// Without a maximum, the checks are not bounded.
+    pub fn new(max_checks: Option<u64>) -> Self {
+        Self {
+            max_checks,
+            checks: Cell::new(0),
+            least_recent_accepted: RefCell::new(None),
+            most_recent_rejected: RefCell::new(None),
+        }
+    }
+
+    /// The least recent Rust version whose check was accepted, if any. The MSRV is at most this
+    /// version.
+    pub fn least_recent_accepted(&self) -> Option<semver::Version> {
+        self.least_recent_accepted.borrow().clone()
+    }
+
+    /// The most recent Rust version whose check was rejected, if any. The MSRV is more recent
+    /// than this version.
+    pub fn most_recent_rejected(&self) -> Option<semver::Version> {
+        self.most_recent_rejected.borrow().clone()
+    }
+
+    fn record(&self, version: &semver::Version, accepted: bool) {
+        if accepted {
+            let mut accepted = self.least_recent_accepted.borrow_mut();
+
+            if accepted.as_ref().map_or(true, |current| version < current) {
+                *accepted = Some(version.clone());
+            }
+        } else {
+            let mut rejected = self.most_recent_rejected.borrow_mut();
+
+            if rejected.as_ref().map_or(true, |current| version > current) {
+                *rejected = Some(version.clone());
+            }
+        }
+    }
+}
+
+impl CheckLayer for BudgetedCheck {
+    fn check(
+        &self,
+        config: &Config,
+        toolchain: &ToolchainSpec,
+        inner: &dyn Check,
+    ) -> TResult<Outcome> {
+        if let Some(max_checks) = self.max_checks {
+            if self.checks.get() >= max_checks {
+                info!(max_checks, %toolchain, "the check budget is exhausted");
+                return Err(CargoMSRVError::CheckBudgetExhausted { max_checks });
+            }
+        }
+
+        let outcome = inner.check(config, toolchain)?;
+
+        self.checks.set(self.checks.get() + 1);
+        self.record(toolchain.version(), config.polarity().accepts(&outcome));
+
+        Ok(outcome)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::{CheckChain, TestRunner};
+    use crate::Action;
+
+    fn check(chain: &CheckChain<TestRunner>, minor: u64) -> TResult<Outcome> {
+        let config = Config::new(Action::Find, "x");
+        let version = semver::Version::new(1, minor, 0);
+
+        chain.check(&config, &ToolchainSpec::new(&version, "x"))
+    }
+
+    #[test]
+    fn exhausted() {
+        let runner = TestRunner::with_ok(&[
+            semver::Version::new(1, 60, 0),
+            semver::Version::new(1, 58, 0),
+        ]);
+        let budget = BudgetedCheck::new(Some(3));
+        let chain = CheckChain::new(runner).layer(&budget);
+
+        assert!(check(&chain, 60).unwrap().is_success());
+        assert!(!check(&chain, 50).unwrap().is_success());
+        assert!(check(&chain, 58).unwrap().is_success());
+
+        assert!(matches!(
+            check(&chain, 55),
+            Err(CargoMSRVError::CheckBudgetExhausted { max_checks: 3 })
+        ));
+
+        assert_eq!(
+            budget.least_recent_accepted(),
+            Some(semver::Version::new(1, 58, 0))
+        );
+        assert_eq!(
+            budget.most_recent_rejected(),
+            Some(semver::Version::new(1, 50, 0))
+        );
+    }
+
+    #[test]
+    fn unbounded() {
+        let runner = TestRunner::with_ok(&[]);
+        let budget = BudgetedCheck::new(None);
+        let chain = CheckChain::new(runner).layer(&budget);
+
+        for minor in 40..60 {
+            assert!(check(&chain, minor).is_ok());
+        }
+
+        assert_eq!(budget.least_recent_accepted(), None);
+        assert_eq!(
+            budget.most_recent_rejected(),
+            Some(semver::Version::new(1, 59, 0))
+        );
+    }
+}
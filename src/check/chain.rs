@@ -0,0 +1,216 @@
+use crate::channel_manifest::ChannelManifests;
+use crate::check::{Check, ComponentAvailabilityCheck, RequiredRustVersionCheck};
+use crate::config::CheckWith;
+use crate::outcome::Outcome;
+use crate::reporter::event::DependencyCache;
+use crate::reporter::Reporter;
+use crate::toolchain::ToolchainSpec;
+use crate::{Config, TResult};
+
+/// A layer of a [`CheckChain`], which wraps the check of the layers below it.
+///
+/// A layer may run code before and after the inner check, e.g. to record its outcome, decide the
+/// outcome without running the inner check at all, or give the inner check another
+/// configuration.
+pub trait CheckLayer {
+    /// Check the toolchain, where `inner` runs the check of the layers below this one, and
+    /// finally the runner of the chain.
+    fn check(
+        &self,
+        config: &Config,
+        toolchain: &ToolchainSpec,
+        inner: &dyn Check,
+    ) -> TResult<Outcome>;
+}
+
+impl<L: CheckLayer + ?Sized> CheckLayer for &L {
+    fn check(
+        &self,
+        config: &Config,
+        toolchain: &ToolchainSpec,
+        inner: &dyn Check,
+    ) -> TResult<Outcome> {
+        (**self).check(config, toolchain, inner)
+    }
+}
+
+/// A runner, like [`RustupToolchainCheck`](crate::check::RustupToolchainCheck), wrapped by a
+/// stack of [`CheckLayer`]s, so concerns like a budget of checks can be combined freely, without
+/// an implementation of [`Check`] for each combination.
+///
+/// ```
+/// # use cargo_msrv::check::{BudgetedCheck, Check, CheckChain};
+/// # fn chain(runner: impl Check) {
+/// // the search stops after 10 checks
+/// let budget = BudgetedCheck::new(Some(10));
+/// let runner = CheckChain::new(runner).layer(&budget);
+/// # }
+/// ```
+pub struct CheckChain<'a, C: Check> {
+    runner: C,
+    // from the innermost to the outermost layer
+    layers: Vec<Box<dyn CheckLayer + 'a>>,
+}
+
+impl<'a, C: Check> CheckChain<'a, C> {
+    /// A chain without layers, which checks with the runner only.
+    pub fn new(runner: C) -> Self {
+        Self {
+            runner,
+            layers: Vec::new(),
+        }
+    }
+
+    /// The chain with the layers which the configuration calls for, like rejecting the
+    /// toolchains which are less recent than the Rust version which cargo requires, or which
+    /// didn't ship a component which the check requires.
+    pub fn from_config<R: Reporter>(runner: C, config: &Config, reporter: &'a R) -> Self {
+        let chain = Self::new(runner).layer(ComponentAvailabilityCheck::new(
+            reporter,
+            ChannelManifests::new(config),
+        ));
+
+        // a file compiled with rustc has no dependencies which require a Rust version
+        if *config.check_with() != CheckWith::Cargo {
+            return chain;
+        }
+
+        chain.layer(RequiredRustVersionCheck::new(reporter))
+    }
+
+    /// Wrap the layers added so far with the given layer, which is run first.
+    pub fn layer(mut self, layer: impl CheckLayer + 'a) -> Self {
+        self.layers.push(Box::new(layer));
+        self
+    }
+}
+
+impl<'a, C: Check> Check for CheckChain<'a, C> {
+    fn check(&self, config: &Config, toolchain: &ToolchainSpec) -> TResult<Outcome> {
+        let inner = Inner {
+            layers: &self.layers,
+            runner: &self.runner,
+        };
+
+        inner.check(config, toolchain)
+    }
+
+    fn dependency_cache(&self) -> Option<DependencyCache> {
+        self.runner.dependency_cache()
+    }
+
+    fn prefetch(&self, config: &Config, toolchains: &[ToolchainSpec]) {
+        self.runner.prefetch(config, toolchains)
+    }
+}
+
+/// The layers below the layer which is being run, and the runner.
+struct Inner<'c> {
+    layers: &'c [Box<dyn CheckLayer + 'c>],
+    runner: &'c dyn Check,
+}
+
+impl<'c> Check for Inner<'c> {
+    fn check(&self, config: &Config, toolchain: &ToolchainSpec) -> TResult<Outcome> {
+        match self.layers.split_last() {
+            Some((outermost, layers)) => {
+                let inner = Inner {
+                    layers,
+                    runner: self.runner,
+                };
+
+                outermost.check(config, toolchain, &inner)
+            }
+            None => self.runner.check(config, toolchain),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::TestRunner;
+    use crate::semver;
+    use crate::Action;
+    use std::cell::RefCell;
+
+    /// Records the order in which the layers are run.
+    struct Record<'a> {
+        name: &'static str,
+        log: &'a RefCell<Vec<&'static str>>,
+    }
+
+    impl<'a> CheckLayer for Record<'a> {
+        fn check(
+            &self,
+            config: &Config,
+            toolchain: &ToolchainSpec,
+            inner: &dyn Check,
+        ) -> TResult<Outcome> {
+            self.log.borrow_mut().push(self.name);
+            inner.check(config, toolchain)
+        }
+    }
+
+    /// Rejects each toolchain, without running the inner check.
+    struct Reject;
+
+    impl CheckLayer for Reject {
+        fn check(
+            &self,
+            config: &Config,
+            toolchain: &ToolchainSpec,
+            _inner: &dyn Check,
+        ) -> TResult<Outcome> {
+            Ok(Outcome::new_failure(
+                toolchain.to_owned(),
+                format!("rejected for {}", config.target()),
+            ))
+        }
+    }
+
+    #[test]
+    fn outermost_layer_runs_first() {
+        let version = semver::Version::new(1, 56, 0);
+        let log = RefCell::new(Vec::new());
+
+        let chain = CheckChain::new(TestRunner::with_ok(&[version.clone()]))
+            .layer(Record {
+                name: "inner",
+                log: &log,
+            })
+            .layer(Record {
+                name: "outer",
+                log: &log,
+            });
+
+        let config = Config::new(Action::Find, "x");
+        let outcome = chain
+            .check(&config, &ToolchainSpec::new(&version, "x"))
+            .unwrap();
+
+        assert!(outcome.is_success());
+        assert_eq!(*log.borrow(), vec!["outer", "inner"]);
+    }
+
+    #[test]
+    fn layer_decides_without_inner_check() {
+        let version = semver::Version::new(1, 56, 0);
+        let log = RefCell::new(Vec::new());
+
+        let chain = CheckChain::new(TestRunner::with_ok(&[version.clone()]))
+            .layer(Record {
+                name: "inner",
+                log: &log,
+            })
+            .layer(Reject);
+
+        let config = Config::new(Action::Find, "x");
+        let outcome = chain
+            .check(&config, &ToolchainSpec::new(&version, "x"))
+            .unwrap();
+
+        assert!(!outcome.is_success());
+        assert!(log.borrow().is_empty());
+    }
+}
@@ -0,0 +1,166 @@
+use crate::channel_manifest::ComponentSource;
+use crate::check::{Check, CheckLayer};
+use crate::outcome::Outcome;
+use crate::reporter::event::{Compatibility, ComponentUnavailable};
+use crate::reporter::Reporter;
+use crate::toolchain::ToolchainSpec;
+use crate::{Config, TResult};
+
+/// A [`CheckLayer`] which rejects toolchains whose release didn't ship a component which the
+/// check requires, like `clippy` for `cargo clippy`, without checking them.
+///
+/// Whether a component was shipped is read from the channel manifest of the release. Without
+/// this layer, such a toolchain fails while the component is installed, with an error which
+/// doesn't say much about the compatibility of the crate.
+pub(crate) struct ComponentAvailabilityCheck<'a, R: Reporter, S: ComponentSource> {
+    reporter: &'a R,
+    source: S,
+}
+
+impl<'a, R: Reporter, S: ComponentSource> ComponentAvailabilityCheck<'a, R, S> {
+    pub(crate) fn new(reporter: &'a R, source: S) -> Self {
+        Self { reporter, source }
+    }
+}
+
+impl<'a, R: Reporter, S: ComponentSource> CheckLayer for ComponentAvailabilityCheck<'a, R, S> {
+    fn check(
+        &self,
+        config: &Config,
+        toolchain: &ToolchainSpec,
+        inner: &dyn Check,
+    ) -> TResult<Outcome> {
+        let required = config.required_components();
+
+        if required.is_empty() {
+            return inner.check(config, toolchain);
+        }
+
+        // without the channel manifest, the toolchain is checked as before
+        let components = match self
+            .source
+            .components(toolchain.version(), toolchain.host())
+        {
+            Ok(components) => components,
+            Err(error) => {
+                warn!(
+                    version = %toolchain.version(),
+                    %error,
+                    "unable to determine the shipped components"
+                );
+                return inner.check(config, toolchain);
+            }
+        };
+
+        let unavailable = required
+            .into_iter()
+            .filter(|component| !components.is_available(component))
+            .map(String::from)
+            .collect::<Vec<_>>();
+
+        if unavailable.is_empty() {
+            return inner.check(config, toolchain);
+        }
+
+        let reason = format!(
+            "Rust {} didn't ship {} for {}",
+            toolchain.version(),
+            unavailable.join(", "),
+            toolchain.host()
+        );
+
+        self.reporter
+            .report_event(ComponentUnavailable::new(toolchain.to_owned(), unavailable))?;
+
+        let error = (!config.no_check_feedback()).then(|| reason.clone());
+        self.reporter
+            .report_event(Compatibility::incompatible(toolchain.to_owned(), error))?;
+
+        Ok(Outcome::new_failure(toolchain.to_owned(), reason))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::channel_manifest::Components;
+    use crate::check::TestRunner;
+    use crate::config::ConfigBuilder;
+    use crate::reporter::event::Message;
+    use crate::reporter::TestReporter;
+    use crate::semver;
+    use crate::toolchain::OwnedToolchainSpec;
+    use crate::{Action, CargoMSRVError, Event};
+
+    /// Ships clippy since Rust 1.29, and fails for releases before Rust 1.8.
+    struct Manifests;
+
+    impl ComponentSource for Manifests {
+        fn components(&self, version: &semver::Version, host: &str) -> TResult<Components> {
+            if version.minor < 8 {
+                return Err(CargoMSRVError::FetchChannelManifest {
+                    url: "https://static.rust-lang.org/dist".to_string(),
+                    message: "unexpected status code 404".to_string(),
+                });
+            }
+
+            let clippy = if version.minor >= 29 {
+                format!("[pkg.clippy-preview.target.{}]\navailable = true\n", host)
+            } else {
+                String::new()
+            };
+
+            let manifest = format!("[pkg.rustc.target.{}]\navailable = true\n{}", host, clippy);
+
+            Ok(Components::parse(&manifest, host).unwrap())
+        }
+    }
+
+    fn config(command: Vec<&'static str>) -> Config<'static> {
+        ConfigBuilder::new(Action::Find, "x")
+            .check_command(command)
+            .build()
+    }
+
+    #[test]
+    fn rejects_toolchain_without_component() {
+        let config = config(vec!["cargo", "clippy"]);
+        let reporter = TestReporter::default();
+        let version = semver::Version::new(1, 28, 0);
+        let runner = TestRunner::with_ok(&[version.clone()]);
+        let check = ComponentAvailabilityCheck::new(reporter.reporter(), Manifests);
+
+        let outcome = check
+            .check(&config, &ToolchainSpec::new(&version, "x"), &runner)
+            .unwrap();
+        assert!(!outcome.is_success());
+
+        let events = reporter.wait_for_events();
+        assert_eq!(
+            events[0],
+            Event::new(Message::ComponentUnavailable(ComponentUnavailable::new(
+                OwnedToolchainSpec::new(&version, "x"),
+                vec!["clippy".to_string()],
+            )))
+        );
+    }
+
+    #[yare::parameterized(
+        shipped = { vec!["cargo", "clippy"], 29 },
+        not_required = { vec!["cargo", "check"], 28 },
+        unknown = { vec!["cargo", "clippy"], 7 },
+    )]
+    fn checks_toolchain(command: Vec<&'static str>, minor: u64) {
+        let config = config(command);
+        let reporter = TestReporter::default();
+        let version = semver::Version::new(1, minor, 0);
+        let runner = TestRunner::with_ok(&[version.clone()]);
+        let check = ComponentAvailabilityCheck::new(reporter.reporter(), Manifests);
+
+        let outcome = check
+            .check(&config, &ToolchainSpec::new(&version, "x"), &runner)
+            .unwrap();
+
+        assert!(outcome.is_success());
+    }
+}
@@ -0,0 +1,154 @@
+use std::cell::Cell;
+use std::time::{Duration, Instant};
+
+use once_cell::unsync::OnceCell;
+
+use crate::check::rustup_toolchain_check::{
+    current_dir_crate_path, lockfile_path, remove_lockfile, runner_toolchain,
+};
+use crate::check::{Check, CheckLayer};
+use crate::command::RustupCommand;
+use crate::config::{CargoFlag, CheckWith, ConfigBuilder};
+use crate::error::CargoMSRVError;
+use crate::outcome::Outcome;
+use crate::reporter::event::DependencyCache;
+use crate::toolchain::ToolchainSpec;
+use crate::{Config, TResult};
+
+/// A [`CheckLayer`] which fetches the dependencies once, with the first checked toolchain, when
+/// only the current package is compiled. Each check then runs offline, so cargo doesn't update
+/// the registry index, or download the sources, again.
+///
+/// A toolchain which is unable to fetch them, e.g. because its cargo can't read the lockfile, is
+/// not fatal: the checks then fetch the dependencies themselves, as usual.
+#[derive(Debug, Default)]
+pub(crate) struct FetchedDependenciesCheck {
+    // The time it took to fetch the dependencies, or `None` if they could not be fetched
+    fetched: OnceCell<Option<Duration>>,
+    offline_checks: Cell<u32>,
+}
+
+impl FetchedDependenciesCheck {
+    /// How the dependencies were shared between the checks so far, if they were fetched.
+    pub(crate) fn dependency_cache(&self) -> Option<DependencyCache> {
+        let fetch = (*self.fetched.get()?)?;
+
+        Some(DependencyCache::new(fetch, self.offline_checks.get()))
+    }
+
+    /// Fetch the dependencies with the given toolchain, if enabled and not done before. Returns
+    /// whether the dependencies were fetched, so the check can run offline.
+    fn fetch(&self, config: &Config, toolchain: &ToolchainSpec) -> TResult<bool> {
+        // a file compiled with rustc has no dependencies
+        if !config.fetch_once() || *config.check_with() != CheckWith::Cargo {
+            return Ok(false);
+        }
+
+        let fetched = self.fetched.get_or_try_init(|| {
+            let runner = runner_toolchain(config, toolchain);
+            let dir = current_dir_crate_path(config)?;
+
+            // `cargo fetch` writes a lockfile, which should not be left behind if the crate had none
+            let had_lockfile = lockfile_path(config)?.is_file();
+
+            let start = Instant::now();
+
+            let output = RustupCommand::new()
+                .with_args([runner.spec(), "cargo", "fetch"])
+                .with_optional_dir(dir)
+                .with_stderr()
+                .with_optional_cancellation(config.cancellation_token())
+                .run()?;
+
+            let elapsed = start.elapsed();
+
+            if !had_lockfile {
+                remove_lockfile(config)?;
+            }
+
+            if output.exit_status().success() {
+                info!(?toolchain, ?elapsed, "fetched dependencies");
+                Ok::<_, CargoMSRVError>(Some(elapsed))
+            } else {
+                warn!(
+                    ?toolchain,
+                    stderr = output.stderr(),
+                    "unable to fetch dependencies, checking online instead"
+                );
+                Ok(None)
+            }
+        })?;
+
+        Ok(fetched.is_some())
+    }
+}
+
+impl CheckLayer for FetchedDependenciesCheck {
+    fn check(
+        &self,
+        config: &Config,
+        toolchain: &ToolchainSpec,
+        inner: &dyn Check,
+    ) -> TResult<Outcome> {
+        if !self.fetch(config, toolchain)? {
+            return inner.check(config, toolchain);
+        }
+
+        self.offline_checks.set(self.offline_checks.get() + 1);
+
+        inner.check(&with_fetched_dependencies(config), toolchain)
+    }
+}
+
+/// The configuration for a check with dependencies which were fetched up front: cargo doesn't
+/// need to access the network, e.g. to update the registry index.
+fn with_fetched_dependencies<'c>(config: &'c Config) -> Config<'c> {
+    let mut cargo_flags = config.cargo_flags().to_vec();
+
+    // `--frozen` implies `--offline`
+    if !cargo_flags.contains(&CargoFlag::Offline) && !cargo_flags.contains(&CargoFlag::Frozen) {
+        cargo_flags.push(CargoFlag::Offline);
+    }
+
+    ConfigBuilder::from_config(config)
+        .cargo_flags(cargo_flags)
+        .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::{CheckChain, TestRunner};
+    use crate::{semver, Action};
+
+    fn config(flags: Vec<CargoFlag>) -> Config<'static> {
+        ConfigBuilder::new(Action::Find, "x86_64-unknown-linux-gnu")
+            .cargo_flags(flags)
+            .build()
+    }
+
+    #[yare::parameterized(
+        none = { vec![], vec![CargoFlag::Offline] },
+        offline = { vec![CargoFlag::Offline], vec![CargoFlag::Offline] },
+        frozen = { vec![CargoFlag::Frozen], vec![CargoFlag::Frozen] },
+    )]
+    fn fetched_dependencies(flags: Vec<CargoFlag>, expected: Vec<CargoFlag>) {
+        let config = config(flags);
+
+        assert_eq!(with_fetched_dependencies(&config).cargo_flags(), expected);
+    }
+
+    #[test]
+    fn not_fetched_unless_only_the_current_package_is_compiled() {
+        let version = semver::Version::new(1, 56, 0);
+        let fetched = FetchedDependenciesCheck::default();
+        let chain = CheckChain::new(TestRunner::with_ok(&[version.clone()])).layer(&fetched);
+
+        let outcome = chain
+            .check(&config(vec![]), &ToolchainSpec::new(&version, "x"))
+            .unwrap();
+
+        assert!(outcome.is_success());
+        assert!(fetched.dependency_cache().is_none());
+    }
+}
@@ -0,0 +1,149 @@
+//! Commands which are run before and after each toolchain check, as given by `--before-check` and
+//! `--after-check`.
+//!
+//! A hook is run by the shell of the platform, in the directory of the crate. It receives the
+//! toolchain which is checked through environment variables.
+
+use crate::config::CheckHook;
+use crate::error::{CargoMSRVError, IoErrorSource, TResult};
+use crate::toolchain::ToolchainSpec;
+use crate::Outcome;
+use std::ffi::OsString;
+use std::path::Path;
+use std::process::Command;
+
+/// The toolchain which is checked, e.g. `1.56.0-x86_64-unknown-linux-gnu`
+pub const MSRV_TOOLCHAIN: &str = "MSRV_TOOLCHAIN";
+/// The target of the toolchain which is checked, e.g. `x86_64-unknown-linux-gnu`
+pub const MSRV_TARGET: &str = "MSRV_TARGET";
+/// The outcome of the check, either `compatible` or `incompatible`. Only given to the after hook.
+pub const MSRV_OUTCOME: &str = "MSRV_OUTCOME";
+
+/// Run the `command` given to `--before-check`, before the given toolchain is checked.
+pub(crate) fn run_before_check(
+    command: &str,
+    toolchain: &ToolchainSpec,
+    dir: Option<&Path>,
+) -> TResult<()> {
+    let env = [
+        (MSRV_TOOLCHAIN, toolchain.spec()),
+        (MSRV_TARGET, toolchain.target()),
+    ];
+
+    run_hook(CheckHook::Before, command, dir, &env)
+}
+
+/// Run the `command` given to `--after-check`, once a toolchain has been checked.
+pub(crate) fn run_after_check(command: &str, outcome: &Outcome, dir: Option<&Path>) -> TResult<()> {
+    let toolchain = outcome.toolchain_spec();
+    let result = if outcome.is_success() {
+        "compatible"
+    } else {
+        "incompatible"
+    };
+
+    let env = [
+        (MSRV_TOOLCHAIN, toolchain.spec()),
+        (MSRV_TARGET, toolchain.target()),
+        (MSRV_OUTCOME, result),
+    ];
+
+    run_hook(CheckHook::After, command, dir, &env)
+}
+
+fn run_hook(
+    hook: CheckHook,
+    command: &str,
+    dir: Option<&Path>,
+    env: &[(&str, &str)],
+) -> TResult<()> {
+    info!(%hook, command, ?env, "running check hook");
+
+    let mut shell = shell(command);
+    shell.envs(env.iter().copied());
+
+    if let Some(dir) = dir {
+        shell.current_dir(dir);
+    }
+
+    // The output is captured, so it won't interfere with the user output
+    let output = shell.output().map_err(|error| CargoMSRVError::Io {
+        error,
+        source: IoErrorSource::SpawnProcess(OsString::from(command)),
+    })?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    info!(%hook, stdout = %stdout, stderr = %stderr, "finished check hook");
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(CargoMSRVError::CheckHookFailed {
+            hook,
+            command: command.to_string(),
+            status: output.status,
+        })
+    }
+}
+
+#[cfg(windows)]
+fn shell(command: &str) -> Command {
+    let mut shell = Command::new("cmd");
+    shell.args(&["/C", command]);
+    shell
+}
+
+#[cfg(not(windows))]
+fn shell(command: &str) -> Command {
+    let mut shell = Command::new("sh");
+    shell.args(&["-c", command]);
+    shell
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::semver;
+    use crate::toolchain::OwnedToolchainSpec;
+
+    #[test]
+    fn before_check_receives_toolchain() {
+        let version = semver::Version::new(1, 56, 0);
+        let toolchain = ToolchainSpec::new(&version, "x86_64-unknown-linux-gnu");
+
+        let result = run_before_check(
+            r#"test "$MSRV_TOOLCHAIN" = "1.56.0-x86_64-unknown-linux-gnu" && test "$MSRV_TARGET" = "x86_64-unknown-linux-gnu""#,
+            &toolchain,
+            None,
+        );
+
+        assert!(result.is_ok());
+    }
+
+    #[yare::parameterized(
+        compatible = { Outcome::new_success(OwnedToolchainSpec::new(&semver::Version::new(1, 56, 0), "x")), "compatible" },
+        incompatible = { Outcome::new_failure(OwnedToolchainSpec::new(&semver::Version::new(1, 56, 0), "x"), "f".to_string()), "incompatible" },
+    )]
+    fn after_check_receives_outcome(outcome: Outcome, expected: &str) {
+        let command = format!(r#"test "$MSRV_OUTCOME" = "{}""#, expected);
+
+        assert!(run_after_check(&command, &outcome, None).is_ok());
+    }
+
+    #[test]
+    fn failing_hook_fails_check() {
+        let version = semver::Version::new(1, 56, 0);
+        let toolchain = ToolchainSpec::new(&version, "x86_64-unknown-linux-gnu");
+
+        let result = run_before_check("exit 3", &toolchain, None);
+
+        assert!(matches!(
+            result,
+            Err(CargoMSRVError::CheckHookFailed {
+                hook: CheckHook::Before,
+                ..
+            })
+        ));
+    }
+}
@@ -0,0 +1,112 @@
+use crate::check::rustup_toolchain_check::{lockfile_path, remove_lockfile};
+use crate::check::{Check, CheckLayer};
+use crate::lockfile::LockfileHandler;
+use crate::outcome::Outcome;
+use crate::toolchain::ToolchainSpec;
+use crate::{Config, TResult};
+
+/// A [`CheckLayer`] which moves the lockfile out of the way while a toolchain is checked, when the
+/// lockfile is ignored, e.g. with `--ignore-lockfile`. The lockfile is moved back after the check,
+/// also when the check could not be run, e.g. because it was cancelled.
+///
+/// With `--scratch-dir`, the lockfile is moved into the scratch directory, instead of next to
+/// itself.
+#[derive(Debug, Default)]
+pub(crate) struct IgnoredLockfileCheck;
+
+impl CheckLayer for IgnoredLockfileCheck {
+    fn check(
+        &self,
+        config: &Config,
+        toolchain: &ToolchainSpec,
+        inner: &dyn Check,
+    ) -> TResult<Outcome> {
+        if !config.ignore_lockfile() {
+            return inner.check(config, toolchain);
+        }
+
+        let cargo_lock = lockfile_path(config)?;
+
+        let moved = if cargo_lock.is_file() {
+            let handle = match config.scratch_dir() {
+                Some(dir) => LockfileHandler::new(&cargo_lock).with_replacement_dir(dir),
+                None => LockfileHandler::new(&cargo_lock),
+            };
+
+            Some(handle.move_lockfile()?)
+        } else {
+            None
+        };
+
+        let result = remove_lockfile(config).and_then(|()| inner.check(config, toolchain));
+
+        if let Some(handle) = moved {
+            handle.move_lockfile_back()?;
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::CheckChain;
+    use crate::config::ConfigBuilder;
+    use crate::{semver, Action};
+    use std::path::{Path, PathBuf};
+    use test_dir::{DirBuilder, FileType, TestDir};
+
+    /// Records whether the lockfile existed while the toolchain was checked.
+    struct LockfilePresence {
+        lockfile: PathBuf,
+    }
+
+    impl Check for LockfilePresence {
+        fn check(&self, _config: &Config, toolchain: &ToolchainSpec) -> TResult<Outcome> {
+            if self.lockfile.exists() {
+                let reason = "the lockfile is present".to_string();
+                Ok(Outcome::new_failure(toolchain.to_owned(), reason))
+            } else {
+                Ok(Outcome::new_success(toolchain.to_owned()))
+            }
+        }
+    }
+
+    fn check(crate_dir: &Path, ignore_lockfile: bool) -> Outcome {
+        let config = ConfigBuilder::new(Action::Find, "x")
+            .crate_path(Some(crate_dir))
+            .ignore_lockfile(ignore_lockfile)
+            .build();
+
+        let chain = CheckChain::new(LockfilePresence {
+            lockfile: crate_dir.join("Cargo.lock"),
+        })
+        .layer(IgnoredLockfileCheck);
+
+        let version = semver::Version::new(1, 56, 0);
+
+        chain
+            .check(&config, &ToolchainSpec::new(&version, "x"))
+            .unwrap()
+    }
+
+    #[test]
+    fn lockfile_is_moved_aside_while_checking() {
+        let tmp = TestDir::temp()
+            .create("Cargo.toml", FileType::EmptyFile)
+            .create("Cargo.lock", FileType::EmptyFile);
+
+        assert!(check(tmp.root(), true).is_success());
+        assert!(tmp.path("Cargo.lock").is_file());
+    }
+
+    #[test]
+    fn lockfile_is_kept_unless_ignored() {
+        let tmp = TestDir::temp()
+            .create("Cargo.toml", FileType::EmptyFile)
+            .create("Cargo.lock", FileType::EmptyFile);
+
+        assert!(!check(tmp.root(), false).is_success());
+    }
+}
@@ -0,0 +1,293 @@
+use crate::check::Check;
+use crate::error::IoErrorSource;
+use crate::long_path::Sandbox;
+use crate::reporter::event::{CheckTiming, CheckToolchain, Compatibility};
+use crate::toolchain::{OwnedToolchainSpec, ToolchainSpec};
+use crate::{CargoMSRVError, Config, Outcome, Reporter, TResult};
+use std::cell::RefCell;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+/// Separates the parts of the `multipart/form-data` body of a remote check.
+const BOUNDARY: &str = "cargo-msrv-remote-check";
+
+/// Runs each check on a remote execution service, like a build farm, while the search is driven
+/// by the local process.
+///
+/// The crate is packaged once, with `cargo package`. For each check, the toolchain, the check
+/// command, and the crate archive are POSTed to the service as a `multipart/form-data` request.
+/// The service replies with a JSON object, which tells whether the crate is compatible with the
+/// toolchain, e.g. `{ "compatible": false, "error": "..." }`.
+pub struct RemoteCheck<'reporter, R: Reporter> {
+    reporter: &'reporter R,
+    url: String,
+    // The archive of the crate which was packaged last, by the path of its manifest
+    archive: RefCell<Option<(PathBuf, Rc<Vec<u8>>)>>,
+}
+
+impl<'reporter, R: Reporter> Check for RemoteCheck<'reporter, R> {
+    fn check(&self, config: &Config, toolchain: &ToolchainSpec) -> TResult<Outcome> {
+        self.reporter
+            .run_scoped_event(CheckToolchain::new(toolchain.to_owned()), || {
+                if let Some(token) = config.cancellation_token() {
+                    token.check()?;
+                }
+
+                let archive = self.archive(config)?;
+
+                let start = Instant::now();
+                let response = self.submit(config, toolchain, &archive)?;
+                let timing = CheckTiming::new(start.elapsed(), None);
+
+                let outcome = response.into_outcome(toolchain.to_owned());
+
+                let event = match &outcome {
+                    Outcome::Success(outcome) => {
+                        Compatibility::compatible(outcome.toolchain_spec.to_owned())
+                    }
+                    Outcome::Failure(outcome) => Compatibility::incompatible(
+                        outcome.toolchain_spec.to_owned(),
+                        Some(outcome.error_message.clone()),
+                    ),
+                };
+
+                self.reporter.report_event(event.with_timing(timing))?;
+
+                Ok(outcome)
+            })
+    }
+}
+
+impl<'reporter, R: Reporter> RemoteCheck<'reporter, R> {
+    /// A check may take as long as a build on the remote service, including its queue time.
+    const TIMEOUT: Duration = Duration::from_secs(60 * 60);
+
+    pub fn new(reporter: &'reporter R, url: impl Into<String>) -> Self {
+        Self {
+            reporter,
+            url: url.into(),
+            archive: RefCell::new(None),
+        }
+    }
+
+    /// The crate is packaged once, and the same archive is submitted for each check. In batch
+    /// mode, each crate is packaged when its first check is run.
+    fn archive(&self, config: &Config) -> TResult<Rc<Vec<u8>>> {
+        let manifest_path = config.context().manifest_path()?;
+        let mut archive = self.archive.borrow_mut();
+
+        if let Some((path, bytes)) = archive.as_ref() {
+            if path == manifest_path {
+                return Ok(Rc::clone(bytes));
+            }
+        }
+
+        let bytes = Rc::new(package_crate(config, manifest_path)?);
+        *archive = Some((manifest_path.to_path_buf(), Rc::clone(&bytes)));
+
+        Ok(bytes)
+    }
+
+    fn submit(
+        &self,
+        config: &Config,
+        toolchain: &ToolchainSpec,
+        archive: &[u8],
+    ) -> TResult<RemoteOutcome> {
+        let request = RemoteCheckRequest {
+            toolchain: toolchain.to_owned(),
+            check_command: config.check_command_for_version(toolchain.version()),
+        };
+
+        let request = serde_json::to_string(&request).map_err(|err| self.error(err.to_string()))?;
+
+        let response = attohttpc::post(&self.url)
+            .header(
+                "Content-Type",
+                format!("multipart/form-data; boundary={}", BOUNDARY),
+            )
+            .header("User-Agent", "cargo-msrv")
+            .timeout(Self::TIMEOUT)
+            .bytes(multipart_body(&request, archive))
+            .send()
+            .map_err(|err| self.error(err.to_string()))?;
+
+        if !response.is_success() {
+            return Err(self.error(format!(
+                "unexpected status code {}",
+                response.status().as_u16()
+            )));
+        }
+
+        let body = response
+            .bytes()
+            .map_err(|err| self.error(err.to_string()))?;
+
+        serde_json::from_slice(&body).map_err(|err| self.error(err.to_string()))
+    }
+
+    fn error(&self, message: String) -> CargoMSRVError {
+        CargoMSRVError::RemoteCheck {
+            url: self.url.clone(),
+            message,
+        }
+    }
+}
+
+/// The part of a remote check which describes what to check.
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+struct RemoteCheckRequest<'config> {
+    toolchain: OwnedToolchainSpec,
+    /// The check command, as it would be run by `rustup run <toolchain>`
+    check_command: Vec<&'config str>,
+}
+
+/// The reply of the remote execution service to a check.
+#[derive(Debug, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+struct RemoteOutcome {
+    compatible: bool,
+    /// The output of a failed check
+    #[serde(default)]
+    error: Option<String>,
+}
+
+impl RemoteOutcome {
+    fn into_outcome(self, toolchain: OwnedToolchainSpec) -> Outcome {
+        if self.compatible {
+            Outcome::new_success(toolchain)
+        } else {
+            Outcome::new_failure(toolchain, self.error.unwrap_or_default())
+        }
+    }
+}
+
+/// The body of a remote check: the check as JSON, followed by the crate archive.
+fn multipart_body(request: &str, archive: &[u8]) -> Vec<u8> {
+    let mut body = Vec::with_capacity(request.len() + archive.len() + 256);
+
+    body.extend_from_slice(
+        format!(
+            "--{}\r\nContent-Disposition: form-data; name=\"check\"\r\nContent-Type: application/json\r\n\r\n{}\r\n",
+            BOUNDARY, request
+        )
+        .as_bytes(),
+    );
+    body.extend_from_slice(
+        format!(
+            "--{}\r\nContent-Disposition: form-data; name=\"crate\"; filename=\"package.crate\"\r\nContent-Type: application/gzip\r\n\r\n",
+            BOUNDARY
+        )
+        .as_bytes(),
+    );
+    body.extend_from_slice(archive);
+    body.extend_from_slice(format!("\r\n--{}--\r\n", BOUNDARY).as_bytes());
+
+    body
+}
+
+/// Package the crate with `cargo package`, and read the resulting `.crate` archive. The crate is
+/// packaged as is, without verifying that it builds, and including uncommitted changes.
+fn package_crate(config: &Config, manifest_path: &Path) -> TResult<Vec<u8>> {
+    // A fresh target directory, so it holds a single archive
+    let sandbox = Sandbox::new(config.sandbox_root(), "cargo-msrv-remote-check");
+    let target_dir = sandbox.path();
+
+    let output = Command::new("cargo")
+        .args(["package", "--no-verify", "--allow-dirty", "--manifest-path"])
+        .arg(manifest_path)
+        .arg("--target-dir")
+        .arg(target_dir)
+        .output()
+        .map_err(|error| CargoMSRVError::Io {
+            error,
+            source: IoErrorSource::SpawnProcess("cargo".into()),
+        })?;
+
+    if !output.status.success() {
+        return Err(CargoMSRVError::PackageCrate(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+
+    let archive = find_archive(&target_dir.join("package"))?;
+
+    std::fs::read(&archive).map_err(|error| CargoMSRVError::Io {
+        error,
+        source: IoErrorSource::ReadFile(archive),
+    })
+}
+
+/// The `.crate` archive written by `cargo package` to the given directory.
+fn find_archive(dir: &Path) -> TResult<PathBuf> {
+    let entries = std::fs::read_dir(dir).map_err(|error| CargoMSRVError::Io {
+        error,
+        source: IoErrorSource::ReadDir(dir.to_path_buf()),
+    })?;
+
+    entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .find(|path| path.extension().map_or(false, |ext| ext == "crate"))
+        .ok_or_else(|| {
+            CargoMSRVError::PackageCrate(format!("no archive was written to '{}'", dir.display()))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::semver;
+
+    fn toolchain() -> OwnedToolchainSpec {
+        OwnedToolchainSpec::new(&semver::Version::new(1, 56, 0), "x86_64-unknown-linux-gnu")
+    }
+
+    #[yare::parameterized(
+        compatible = { r#"{ "compatible": true }"#, Outcome::new_success(toolchain()) },
+        incompatible = { r#"{ "compatible": false, "error": "error[E0658]" }"#, Outcome::new_failure(toolchain(), "error[E0658]".to_string()) },
+        incompatible_without_error = { r#"{ "compatible": false }"#, Outcome::new_failure(toolchain(), String::new()) },
+    )]
+    fn outcome_of_response(response: &str, expected: Outcome) {
+        let response = serde_json::from_str::<RemoteOutcome>(response).unwrap();
+
+        assert_eq!(response.into_outcome(toolchain()), expected);
+    }
+
+    #[test]
+    fn serialized_request() {
+        let request = RemoteCheckRequest {
+            toolchain: toolchain(),
+            check_command: vec!["cargo", "check", "--locked"],
+        };
+
+        assert_eq!(
+            serde_json::to_value(&request).unwrap(),
+            serde_json::json!({
+                "toolchain": { "version": "1.56.0", "target": "x86_64-unknown-linux-gnu" },
+                "check_command": ["cargo", "check", "--locked"],
+            })
+        );
+    }
+
+    #[test]
+    fn body_holds_check_and_archive() {
+        let body = multipart_body(r#"{"check":1}"#, &[0x1f, 0x8b]);
+
+        let expected = [
+            b"--cargo-msrv-remote-check\r\n".as_ref(),
+            b"Content-Disposition: form-data; name=\"check\"\r\nContent-Type: application/json\r\n\r\n",
+            b"{\"check\":1}\r\n",
+            b"--cargo-msrv-remote-check\r\n",
+            b"Content-Disposition: form-data; name=\"crate\"; filename=\"package.crate\"\r\nContent-Type: application/gzip\r\n\r\n",
+            &[0x1f, 0x8b],
+            b"\r\n--cargo-msrv-remote-check--\r\n",
+        ]
+        .concat();
+
+        assert_eq!(body, expected);
+    }
+}
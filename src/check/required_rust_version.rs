@@ -0,0 +1,193 @@
+use std::cell::RefCell;
+
+use crate::check::{Check, CheckLayer};
+use crate::manifest::bare_version::BareVersion;
+use crate::outcome::Outcome;
+use crate::reporter::event::{Compatibility, RequiredRustVersion};
+use crate::reporter::Reporter;
+use crate::semver;
+use crate::toolchain::ToolchainSpec;
+use crate::{Config, TResult};
+
+/// A [`CheckLayer`] which rejects toolchains which are less recent than the Rust version which
+/// cargo requires, without checking them.
+///
+/// Since Rust 1.56, cargo refuses to build a crate with a toolchain which is older than the
+/// `rust-version` of the crate, or of one of its dependencies. Once a check failed because of it,
+/// the toolchains which are less recent than the required Rust version are known to fail the
+/// same way, so they are rejected right away, which shrinks the remaining search space.
+pub struct RequiredRustVersionCheck<'a, R: Reporter> {
+    reporter: &'a R,
+    required: RefCell<Option<semver::Version>>,
+}
+
+impl<'a, R: Reporter> RequiredRustVersionCheck<'a, R> {
+    pub fn new(reporter: &'a R) -> Self {
+        Self {
+            reporter,
+            required: RefCell::new(None),
+        }
+    }
+}
+
+impl<'a, R: Reporter> CheckLayer for RequiredRustVersionCheck<'a, R> {
+    fn check(
+        &self,
+        config: &Config,
+        toolchain: &ToolchainSpec,
+        inner: &dyn Check,
+    ) -> TResult<Outcome> {
+        if let Some(required) = self.required.borrow().as_ref() {
+            if toolchain.version() < required {
+                let reason = format!(
+                    "Rust {} is less recent than Rust {}, which is required by cargo",
+                    toolchain.version(),
+                    required
+                );
+
+                let error = (!config.no_check_feedback()).then(|| reason.clone());
+                self.reporter
+                    .report_event(Compatibility::incompatible(toolchain.to_owned(), error))?;
+
+                return Ok(Outcome::new_failure(toolchain.to_owned(), reason));
+            }
+        }
+
+        let outcome = inner.check(config, toolchain)?;
+
+        if let Outcome::Failure(failure) = &outcome {
+            let required = required_rust_version(&failure.error_message)
+                .filter(|required| required > toolchain.version());
+
+            if let Some(required) = required {
+                let is_raised = self
+                    .required
+                    .borrow()
+                    .as_ref()
+                    .map_or(true, |current| &required > current);
+
+                if is_raised {
+                    self.reporter.report_event(RequiredRustVersion::new(
+                        toolchain.to_owned(),
+                        required.clone(),
+                    ))?;
+                    self.required.replace(Some(required));
+                }
+            }
+        }
+
+        Ok(outcome)
+    }
+}
+
+/// Parse the Rust version which cargo requires from the output of a check, e.g.
+/// `package `a v0.1.0` cannot be built because it requires rustc 1.60 or newer` (Rust 1.56 up
+/// to 1.72), or `a@0.1.0 requires rustc 1.60` (later releases). When several packages require
+/// a Rust version, the most recent is returned.
+fn required_rust_version(output: &str) -> Option<semver::Version> {
+    output
+        .match_indices("requires rustc ")
+        .filter_map(|(start, needle)| {
+            let version = output[start + needle.len()..]
+                .split(|c: char| !(c.is_ascii_digit() || c == '.'))
+                .next()?
+                .trim_end_matches('.');
+
+            version.parse::<BareVersion>().ok()
+        })
+        .map(|version| version.to_semver_version())
+        .max()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::TestRunner;
+    use crate::reporter::event::Message;
+    use crate::reporter::TestReporter;
+    use crate::toolchain::OwnedToolchainSpec;
+    use crate::{Action, CargoMSRVError, Event};
+
+    #[yare::parameterized(
+        cargo_1_56 = { "error: package `a v0.1.0 (/tmp/a)` cannot be built because it requires rustc 1.60 or newer, while the currently active rustc version is 1.56.0", Some(semver::Version::new(1, 60, 0)) },
+        three_components = { "error: package `a v0.1.0` cannot be built because it requires rustc 1.60.1 or newer, while the currently active rustc version is 1.56.0", Some(semver::Version::new(1, 60, 1)) },
+        trailing_period = { "error: package `a v0.1.0` cannot be built because it requires rustc 1.70.\n", Some(semver::Version::new(1, 70, 0)) },
+        several_packages = { "error: rustc 1.56.0 is not supported by the following packages:\n  a@0.1.0 requires rustc 1.60\n  b@0.2.0 requires rustc 1.65.0", Some(semver::Version::new(1, 65, 0)) },
+        other_error = { "error[E0658]: use of unstable library feature 'let_else'", None },
+        empty = { "", None },
+    )]
+    fn parse(output: &str, expected: Option<semver::Version>) {
+        assert_eq!(required_rust_version(output), expected);
+    }
+
+    /// Fails each toolchain with the given output.
+    struct Refuse(&'static str);
+
+    impl Check for Refuse {
+        fn check(&self, config: &Config, toolchain: &ToolchainSpec) -> TResult<Outcome> {
+            Ok(Outcome::new_failure(
+                OwnedToolchainSpec::new(toolchain.version(), config.target()),
+                self.0.to_string(),
+            ))
+        }
+    }
+
+    /// Errors for each toolchain, so a toolchain which is checked fails the test.
+    struct Unreachable;
+
+    impl Check for Unreachable {
+        fn check(&self, _config: &Config, _toolchain: &ToolchainSpec) -> TResult<Outcome> {
+            Err(CargoMSRVError::UnableToRunCheck)
+        }
+    }
+
+    #[test]
+    fn rejects_toolchains_below_required_version() {
+        let config = Config::new(Action::Find, "x");
+        let reporter = TestReporter::default();
+        let runner = Refuse("error: package `a v0.1.0` cannot be built because it requires rustc 1.60 or newer, while the currently active rustc version is 1.56.0");
+        let check = RequiredRustVersionCheck::new(reporter.reporter());
+
+        let refused = semver::Version::new(1, 56, 0);
+        let outcome = check
+            .check(&config, &ToolchainSpec::new(&refused, "x"), &runner)
+            .unwrap();
+        assert!(!outcome.is_success());
+
+        // from now on, older toolchains are rejected without running the inner check
+        let older = semver::Version::new(1, 58, 0);
+        let outcome = check
+            .check(&config, &ToolchainSpec::new(&older, "x"), &Unreachable)
+            .unwrap();
+        assert!(!outcome.is_success());
+
+        let required = semver::Version::new(1, 60, 0);
+        let result = check.check(&config, &ToolchainSpec::new(&required, "x"), &Unreachable);
+        assert!(result.is_err());
+
+        let events = reporter.wait_for_events();
+        assert_eq!(
+            events[0],
+            Event::new(Message::RequiredRustVersion(RequiredRustVersion::new(
+                OwnedToolchainSpec::new(&refused, "x"),
+                semver::Version::new(1, 60, 0),
+            )))
+        );
+    }
+
+    #[test]
+    fn passes_through_other_outcomes() {
+        let config = Config::new(Action::Find, "x");
+        let reporter = TestReporter::default();
+        let version = semver::Version::new(1, 56, 0);
+        let runner = TestRunner::with_ok(&[version.clone()]);
+        let check = RequiredRustVersionCheck::new(reporter.reporter());
+
+        let outcome = check
+            .check(&config, &ToolchainSpec::new(&version, "x"), &runner)
+            .unwrap();
+
+        assert!(outcome.is_success());
+        assert!(check.required.borrow().is_none());
+    }
+}
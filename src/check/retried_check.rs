@@ -0,0 +1,142 @@
+use crate::check::rustup_toolchain_check::clean_target_dir;
+use crate::check::{Check, CheckLayer};
+use crate::outcome::Outcome;
+use crate::reporter::event::RetryCheck;
+use crate::reporter::Reporter;
+use crate::toolchain::ToolchainSpec;
+use crate::{Config, TResult};
+
+/// A [`CheckLayer`] which retries a failed check from scratch, up to the amount of retries given
+/// with `--retries-on-failure`, before the toolchain is rejected. A failed check may be spurious,
+/// e.g. because a build script depends on the network.
+///
+/// The timing of the outcome covers all attempts.
+pub(crate) struct RetriedCheck<'reporter, R: Reporter> {
+    reporter: &'reporter R,
+}
+
+impl<'reporter, R: Reporter> RetriedCheck<'reporter, R> {
+    pub(crate) fn new(reporter: &'reporter R) -> Self {
+        Self { reporter }
+    }
+}
+
+impl<'reporter, R: Reporter> CheckLayer for RetriedCheck<'reporter, R> {
+    fn check(
+        &self,
+        config: &Config,
+        toolchain: &ToolchainSpec,
+        inner: &dyn Check,
+    ) -> TResult<Outcome> {
+        let mut outcome = inner.check(config, toolchain)?;
+        let retries = config.retries_on_failure();
+
+        for attempt in 1..=retries {
+            let error_message = match &outcome {
+                Outcome::Success(_) => break,
+                Outcome::Failure(failure) => failure.error_message.clone(),
+            };
+
+            self.reporter.report_event(RetryCheck::new(
+                toolchain.to_owned(),
+                attempt,
+                retries,
+                error_message,
+            ))?;
+
+            clean_target_dir(config, toolchain);
+
+            let timing = outcome.timing().cloned();
+            let retry = inner.check(config, toolchain)?;
+
+            let timing = match (timing, retry.timing().cloned()) {
+                (Some(timing), Some(next)) => Some(timing.followed_by(next)),
+                (timing, next) => timing.or(next),
+            };
+
+            outcome = retry.with_timing(timing);
+        }
+
+        Ok(outcome)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ConfigBuilder;
+    use crate::reporter::event::{CheckTiming, Message};
+    use crate::reporter::TestReporter;
+    use crate::toolchain::OwnedToolchainSpec;
+    use crate::{semver, Action, Event};
+    use std::cell::Cell;
+    use std::path::Path;
+    use std::time::Duration;
+
+    /// Fails the given amount of times, before it succeeds. Each attempt takes a second.
+    struct Flaky {
+        failures: Cell<u32>,
+    }
+
+    impl Check for Flaky {
+        fn check(&self, _config: &Config, toolchain: &ToolchainSpec) -> TResult<Outcome> {
+            let outcome = match self.failures.get() {
+                0 => Outcome::new_success(toolchain.to_owned()),
+                n => {
+                    self.failures.set(n - 1);
+                    Outcome::new_failure(toolchain.to_owned(), "spurious".to_string())
+                }
+            };
+
+            Ok(outcome.with_timing(Some(CheckTiming::new(Duration::from_secs(1), None))))
+        }
+    }
+
+    fn check(failures: u32, retries: u32) -> (Outcome, Vec<Event>) {
+        // a file compiled with rustc has no target directory to clean between the attempts
+        let config = ConfigBuilder::new(Action::Find, "x")
+            .check_with_rustc(Path::new("main.rs"))
+            .retries_on_failure(retries)
+            .build();
+
+        let reporter = TestReporter::default();
+        let runner = Flaky {
+            failures: Cell::new(failures),
+        };
+
+        let version = semver::Version::new(1, 56, 0);
+        let outcome = RetriedCheck::new(reporter.reporter())
+            .check(&config, &ToolchainSpec::new(&version, "x"), &runner)
+            .unwrap();
+
+        (outcome, reporter.wait_for_events())
+    }
+
+    #[test]
+    fn spurious_failure_is_retried() {
+        let (outcome, events) = check(1, 2);
+
+        assert!(outcome.is_success());
+        assert_eq!(
+            outcome.timing(),
+            Some(&CheckTiming::new(Duration::from_secs(2), None))
+        );
+        assert_eq!(
+            events,
+            vec![Event::new(Message::RetryCheck(RetryCheck::new(
+                OwnedToolchainSpec::new(&semver::Version::new(1, 56, 0), "x"),
+                1,
+                2,
+                "spurious".to_string(),
+            )))]
+        );
+    }
+
+    #[test]
+    fn rejected_once_out_of_retries() {
+        let (outcome, events) = check(3, 2);
+
+        assert!(!outcome.is_success());
+        assert_eq!(events.len(), 2);
+    }
+}
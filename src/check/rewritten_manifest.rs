@@ -0,0 +1,131 @@
+use crate::check::rustup_toolchain_check::lockfile_path;
+use crate::check::{Check, CheckLayer};
+use crate::config::CheckWith;
+use crate::manifest::pruned_manifest::PrunedManifest;
+use crate::outcome::Outcome;
+use crate::toolchain::ToolchainSpec;
+use crate::{Config, TResult};
+
+/// A [`CheckLayer`] which removes the dependency tables of the kinds which are left out of the
+/// check from the Cargo manifest, e.g. with `--dep-kinds normal,build`. The manifest is restored
+/// after the check, also when the check could not be run.
+#[derive(Debug, Default)]
+pub(crate) struct PrunedManifestCheck;
+
+impl CheckLayer for PrunedManifestCheck {
+    fn check(
+        &self,
+        config: &Config,
+        toolchain: &ToolchainSpec,
+        inner: &dyn Check,
+    ) -> TResult<Outcome> {
+        let excluded = config.excluded_dependency_kinds();
+
+        // a file compiled with rustc has no manifest
+        if excluded.is_empty() || *config.check_with() != CheckWith::Cargo {
+            return inner.check(config, toolchain);
+        }
+
+        let manifest = config.context().manifest_path()?;
+        let pruned = PrunedManifest::prune(manifest, &lockfile_path(config)?, &excluded)?;
+
+        restore_after(pruned, inner.check(config, toolchain))
+    }
+}
+
+/// A [`CheckLayer`] which merges the patch given with `--manifest-patch` into the Cargo manifest,
+/// for the checked toolchain. The manifest is restored after the check, also when the check could
+/// not be run.
+#[derive(Debug, Default)]
+pub(crate) struct PatchedManifestCheck;
+
+impl CheckLayer for PatchedManifestCheck {
+    fn check(
+        &self,
+        config: &Config,
+        toolchain: &ToolchainSpec,
+        inner: &dyn Check,
+    ) -> TResult<Outcome> {
+        let patch = match config.manifest_patch() {
+            Some(patch) if *config.check_with() == CheckWith::Cargo => patch,
+            _ => return inner.check(config, toolchain),
+        };
+
+        let manifest = config.context().manifest_path()?;
+        let patched = PrunedManifest::patch(
+            manifest,
+            &lockfile_path(config)?,
+            patch,
+            toolchain.version(),
+        )?;
+
+        if patched.is_some() {
+            info!(?toolchain, patch = %patch.path().display(), "patched manifest");
+        }
+
+        restore_after(patched, inner.check(config, toolchain))
+    }
+}
+
+/// Restore the rewritten manifest, if it was rewritten, after the check which gave the result.
+fn restore_after(rewritten: Option<PrunedManifest>, result: TResult<Outcome>) -> TResult<Outcome> {
+    match rewritten {
+        Some(rewritten) => rewritten.restore().and(result),
+        None => result,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::CheckChain;
+    use crate::config::{ConfigBuilder, DependencyKind};
+    use crate::{semver, Action};
+    use std::path::Path;
+    use test_dir::{DirBuilder, TestDir};
+
+    const MANIFEST: &str = "[package]\nname = \"a\"\n\n[dev-dependencies]\nb = \"1\"\n";
+
+    /// Records whether the manifest had dev-dependencies while the toolchain was checked.
+    struct DevDependencies<'a> {
+        manifest: &'a Path,
+    }
+
+    impl<'a> Check for DevDependencies<'a> {
+        fn check(&self, _config: &Config, toolchain: &ToolchainSpec) -> TResult<Outcome> {
+            let manifest = std::fs::read_to_string(self.manifest).unwrap();
+
+            if manifest.contains("[dev-dependencies]") {
+                let reason = "the dev-dependencies are present".to_string();
+                Ok(Outcome::new_failure(toolchain.to_owned(), reason))
+            } else {
+                Ok(Outcome::new_success(toolchain.to_owned()))
+            }
+        }
+    }
+
+    #[test]
+    fn pruned_while_checking() {
+        let tmp = TestDir::temp();
+        let manifest = tmp.path("Cargo.toml");
+        std::fs::write(&manifest, MANIFEST).unwrap();
+
+        let config = ConfigBuilder::new(Action::Find, "x")
+            .crate_path(Some(tmp.root()))
+            .dependency_kinds(vec![DependencyKind::Normal, DependencyKind::Build])
+            .build();
+
+        let chain = CheckChain::new(DevDependencies {
+            manifest: &manifest,
+        })
+        .layer(PrunedManifestCheck);
+
+        let version = semver::Version::new(1, 56, 0);
+        let outcome = chain
+            .check(&config, &ToolchainSpec::new(&version, "x"))
+            .unwrap();
+
+        assert!(outcome.is_success());
+        assert_eq!(std::fs::read_to_string(&manifest).unwrap(), MANIFEST);
+    }
+}
@@ -0,0 +1,120 @@
+//! The compiler wrapper given by `--rustc-wrapper`, like `sccache`, through which the compiler of
+//! each check is run.
+//!
+//! Since the wrapper caches compiled crates, repeated checks, e.g. by later runs or retries, take
+//! the dependencies from the cache, instead of compiling them again. The cache statistics are only
+//! available for `sccache`.
+
+use std::path::Path;
+use std::process::Command;
+
+/// The environment variable by which cargo is told to run the compiler through a wrapper.
+pub const RUSTC_WRAPPER: &str = "RUSTC_WRAPPER";
+
+/// The cache hits and misses counted by a compiler wrapper.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(crate) struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl CacheStats {
+    /// The hits and misses which were counted since the `earlier` statistics were queried.
+    pub fn since(self, earlier: CacheStats) -> CacheStats {
+        CacheStats {
+            hits: self.hits.saturating_sub(earlier.hits),
+            misses: self.misses.saturating_sub(earlier.misses),
+        }
+    }
+}
+
+/// The cache statistics of the wrapper, if it's `sccache`, and they can be queried.
+pub(crate) fn cache_stats(wrapper: &str) -> Option<CacheStats> {
+    if !is_sccache(wrapper) {
+        return None;
+    }
+
+    let output = match Command::new(wrapper).arg("--show-stats").output() {
+        Ok(output) if output.status.success() => output,
+        Ok(output) => {
+            info!(wrapper, status = %output.status, "unable to query cache statistics");
+            return None;
+        }
+        Err(error) => {
+            info!(wrapper, %error, "unable to query cache statistics");
+            return None;
+        }
+    };
+
+    parse_sccache_stats(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Whether the wrapper is `sccache`, e.g. `sccache` or `/usr/local/bin/sccache.exe`.
+pub(crate) fn is_sccache(wrapper: &str) -> bool {
+    Path::new(wrapper)
+        .file_stem()
+        .map_or(false, |stem| stem == "sccache")
+}
+
+/// Parse the totals from the output of `sccache --show-stats`, e.g. `Cache hits    42`. Lines
+/// which break the totals down, like `Cache hits (Rust)    42`, are skipped.
+fn parse_sccache_stats(output: &str) -> Option<CacheStats> {
+    let count = |label: &str| {
+        output.lines().find_map(|line| {
+            let value = line.strip_prefix(label)?.trim();
+
+            value.parse::<u64>().ok()
+        })
+    };
+
+    Some(CacheStats {
+        hits: count("Cache hits")?,
+        misses: count("Cache misses")?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCCACHE_STATS: &str = "Compile requests                      10
+Compile requests executed              8
+Cache hits                             5
+Cache hits (Rust)                      5
+Cache misses                           3
+Cache misses (Rust)                    3
+Cache hits rate                    62.50 %
+Cache timeouts                         0
+";
+
+    #[test]
+    fn parse_stats() {
+        assert_eq!(
+            parse_sccache_stats(SCCACHE_STATS),
+            Some(CacheStats { hits: 5, misses: 3 })
+        );
+    }
+
+    #[test]
+    fn unknown_stats() {
+        assert_eq!(parse_sccache_stats("sccache: error: no server"), None);
+    }
+
+    #[yare::parameterized(
+        name = { "sccache", true },
+        path = { "/usr/local/bin/sccache", true },
+        windows = { "sccache.exe", true },
+        ccache = { "ccache", false },
+    )]
+    fn sccache_wrapper(wrapper: &str, expected: bool) {
+        assert_eq!(is_sccache(wrapper), expected);
+    }
+
+    #[test]
+    fn stats_since() {
+        let earlier = CacheStats { hits: 5, misses: 3 };
+        let later = CacheStats { hits: 9, misses: 4 };
+
+        assert_eq!(later.since(earlier), CacheStats { hits: 4, misses: 1 });
+    }
+}
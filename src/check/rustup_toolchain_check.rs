@@ -1,17 +1,50 @@
-use crate::check::Check;
-use crate::command::RustupCommand;
-use crate::download::{DownloadToolchain, ToolchainDownloader};
+use crate::check::rustc_wrapper::{self, CacheStats, RUSTC_WRAPPER};
+use crate::check::{
+    hooks, Check, CheckChain, CheckLayer, FetchedDependenciesCheck, IgnoredLockfileCheck,
+    PatchedManifestCheck, PrunedManifestCheck, RetriedCheck, UnitGraphReuseCheck,
+};
+use crate::check_log::CheckLogs;
+use crate::command::{Pipe, RustupCommand};
+use crate::config::{BinPolicy, CargoFlag, CheckRunner, CheckWith, ConfigBuilder, LockfilePolicy};
+use crate::default_target::default_target;
+use crate::download::{
+    install_args, DownloadToolchain, InstalledToolchains, PrefetchedToolchains, ToolchainDownloader,
+};
 use crate::error::IoErrorSource;
-use crate::lockfile::{LockfileHandler, CARGO_LOCK};
-use crate::reporter::event::{CheckToolchain, Compatibility, CompatibilityCheckMethod, Method};
+use crate::lockfile::{LockfileFormat, CARGO_LOCK};
+use crate::reporter::event::{
+    CheckOutput, CheckTiming, CheckToolchain, Compatibility, CompatibilityCheckMethod,
+    DependencyCache, EncodingNote, ExcludedBinaries, IncompatibleLockfile, Method, OutputStream,
+    WrapperCacheStats,
+};
+use crate::required_features::CrateTargets;
 use crate::toolchain::ToolchainSpec;
 use crate::{CargoMSRVError, Config, Outcome, Reporter, TResult};
 use once_cell::unsync::OnceCell;
 use std::path::{Path, PathBuf};
-
+use std::time::{Duration, Instant};
+
+/// Checks a toolchain by running the check command with `rustup run`, after installing the
+/// toolchain.
+///
+/// The check command itself is wrapped by the layers which apply the configuration around it,
+/// from the outermost to the innermost:
+///
+/// * [`FetchedDependenciesCheck`]: fetch the dependencies once, and check offline
+/// * [`IgnoredLockfileCheck`]: move the lockfile aside, with `--ignore-lockfile`
+/// * [`PrunedManifestCheck`]: leave dependency kinds out, with `--dep-kinds`
+/// * [`PatchedManifestCheck`]: patch the manifest, with `--manifest-patch`
+/// * [`UnitGraphReuseCheck`]: reuse earlier outcomes, with `--reuse-unit-graph`
+/// * [`RetriedCheck`]: retry failed checks, with `--retries-on-failure`
 pub struct RustupToolchainCheck<'reporter, R: Reporter> {
     reporter: &'reporter R,
-    lockfile_path: OnceCell<PathBuf>,
+    lockfile_format: OnceCell<Option<LockfileFormat>>,
+    installed_toolchains: OnceCell<InstalledToolchains>,
+    bin_policy_args: OnceCell<Vec<String>>,
+    host_target: OnceCell<String>,
+    fetched_dependencies: FetchedDependenciesCheck,
+    reused_outcomes: UnitGraphReuseCheck<'reporter, R>,
+    prefetched: PrefetchedToolchains,
 }
 
 impl<'reporter, R: Reporter> Check for RustupToolchainCheck<'reporter, R> {
@@ -20,80 +53,372 @@ impl<'reporter, R: Reporter> Check for RustupToolchainCheck<'reporter, R> {
             .run_scoped_event(CheckToolchain::new(toolchain.to_owned()), || {
                 info!(ignore_lockfile_enabled = config.ignore_lockfile());
 
-                // temporarily move the lockfile if the user opted to ignore it, and it exists
-                let cargo_lock = self.lockfile_path(config)?;
-
-                let handle_wrap = if config.ignore_lockfile() && cargo_lock.is_file() {
-                    let handle = LockfileHandler::new(cargo_lock).move_lockfile()?;
+                if let Some(token) = config.cancellation_token() {
+                    token.check()?;
+                }
 
-                    Some(handle)
-                } else {
-                    None
+                // a toolchain whose cargo can't read the lockfile is either rejected before it's
+                // installed, or checked as if the lockfile was ignored
+                let regenerating;
+                let config = match self.incompatible_lockfile(config, toolchain)? {
+                    Some(format) => {
+                        let policy = config.lockfile_policy();
+
+                        self.reporter.report_event(IncompatibleLockfile::new(
+                            toolchain.to_owned(),
+                            format,
+                            policy,
+                        ))?;
+
+                        match policy {
+                            LockfilePolicy::Untestable => {
+                                return self.reject_untestable(toolchain, format);
+                            }
+                            LockfilePolicy::Regenerate => {
+                                regenerating = with_regenerated_lockfile(config);
+                                &regenerating
+                            }
+                        }
+                    }
+                    None => config,
                 };
 
-                self.prepare(toolchain, config)?;
+                let config = self.resolve(config)?;
 
-                let path = current_dir_crate_path(config)?;
-                let outcome =
-                    self.run_check_command_via_rustup(toolchain, path, config.check_command())?;
+                // install the toolchain before touching the lockfile, so the lockfile is left
+                // as is when the toolchain can't be installed
+                self.install_toolchain(&config, &runner_toolchain(&config, toolchain))?;
 
-                // report outcome to UI
-                self.report_outcome(&outcome, config.no_check_feedback())?;
+                let outcome = self.layers().check(&config, toolchain)?;
 
-                // move the lockfile back
-                if let Some(handle) = handle_wrap {
-                    handle.move_lockfile_back()?;
+                if let Some(command) = config.check_hooks().after() {
+                    let path = current_dir_crate_path(&config)?;
+                    hooks::run_after_check(command, &outcome, path)?;
                 }
 
                 Ok(outcome)
             })
     }
+
+    fn dependency_cache(&self) -> Option<DependencyCache> {
+        self.fetched_dependencies.dependency_cache()
+    }
+
+    fn prefetch(&self, config: &Config, toolchains: &[ToolchainSpec]) {
+        // the server installs each toolchain once it's checked
+        if !config.prefetch_toolchains() || config.server().is_some() {
+            return;
+        }
+
+        // with the cross runner, the toolchain of the host is installed, as in `resolve`
+        let with_host;
+        let config = if config.check_runner() == CheckRunner::Cross && config.host().is_none() {
+            match self.host_target.get_or_try_init(default_target) {
+                Ok(host) => {
+                    with_host = ConfigBuilder::from_config(config).host(host).build();
+                    &with_host
+                }
+                Err(error) => {
+                    warn!(%error, "unable to prefetch toolchains");
+                    return;
+                }
+            }
+        } else {
+            config
+        };
+
+        let installed = self
+            .installed_toolchains
+            .get_or_init(InstalledToolchains::query);
+
+        let toolchains = toolchains
+            .iter()
+            .filter(|toolchain| !self.is_untestable(config, toolchain))
+            .map(|toolchain| runner_toolchain(config, toolchain))
+            .filter(|toolchain| !installed.contains(toolchain) || toolchain.is_cross_compiling())
+            .map(|toolchain| {
+                let args = install_args(
+                    &toolchain,
+                    config.install_profile(),
+                    &config.required_components(),
+                );
+
+                (toolchain.spec().to_string(), args)
+            })
+            .collect();
+
+        self.prefetched
+            .prefetch(toolchains, config.cancellation_token());
+    }
 }
 
 impl<'reporter, R: Reporter> RustupToolchainCheck<'reporter, R> {
     pub fn new(reporter: &'reporter R) -> Self {
         Self {
             reporter,
-            lockfile_path: OnceCell::new(),
+            lockfile_format: OnceCell::new(),
+            installed_toolchains: OnceCell::new(),
+            bin_policy_args: OnceCell::new(),
+            host_target: OnceCell::new(),
+            fetched_dependencies: FetchedDependenciesCheck::default(),
+            reused_outcomes: UnitGraphReuseCheck::new(reporter),
+            prefetched: PrefetchedToolchains::default(),
         }
     }
 
-    fn prepare(&self, toolchain: &ToolchainSpec, config: &Config) -> TResult<()> {
-        let downloader = ToolchainDownloader::new(self.reporter);
-        downloader.download(toolchain)?;
+    /// The check command, wrapped by the layers which apply the configuration around it.
+    fn layers(&self) -> CheckChain<'_, Attempt<'reporter, R>> {
+        CheckChain::new(Attempt {
+            reporter: self.reporter,
+        })
+        .layer(RetriedCheck::new(self.reporter))
+        .layer(ReportedCheck {
+            reporter: self.reporter,
+        })
+        .layer(&self.reused_outcomes)
+        .layer(PatchedManifestCheck)
+        .layer(PrunedManifestCheck)
+        .layer(IgnoredLockfileCheck)
+        .layer(&self.fetched_dependencies)
+    }
+
+    /// The configuration with the settings which are only resolved once, for all checks: the
+    /// arguments which apply the binary policy, and, with the cross runner, the host, unless it
+    /// was given with `--host`.
+    fn resolve<'c>(&'c self, config: &'c Config) -> TResult<Config<'c>> {
+        let mut builder =
+            ConfigBuilder::from_config(config).bin_policy_args(self.bin_policy_args(config)?);
 
-        if config.ignore_lockfile() {
-            self.remove_lockfile(config)?;
+        if config.check_runner() == CheckRunner::Cross && config.host().is_none() {
+            builder = builder.host(self.host_target.get_or_try_init(default_target)?);
         }
 
-        Ok(())
+        Ok(builder.build())
+    }
+
+    fn install_toolchain(&self, config: &Config, toolchain: &ToolchainSpec) -> TResult<()> {
+        // The installed toolchains are listed once, and reused for each subsequent check
+        let installed = self
+            .installed_toolchains
+            .get_or_init(InstalledToolchains::query);
+
+        let downloader = ToolchainDownloader::new(self.reporter, installed)
+            .with_profile(config.install_profile())
+            .with_components(config.required_components())
+            .with_server(config.server())
+            .with_optional_cancellation(config.cancellation_token())
+            .with_prefetched(&self.prefetched);
+        downloader.download(toolchain)
     }
 
+    /// The format of the lockfile, if the cargo of the given toolchain can't read it. The
+    /// lockfile is only probed once, and not at all when it's ignored anyway.
+    fn incompatible_lockfile(
+        &self,
+        config: &Config,
+        toolchain: &ToolchainSpec,
+    ) -> TResult<Option<LockfileFormat>> {
+        // a file compiled with rustc doesn't use the lockfile
+        if config.ignore_lockfile() || *config.check_with() != CheckWith::Cargo {
+            return Ok(None);
+        }
+
+        let format = self
+            .lockfile_format
+            .get_or_try_init(|| LockfileFormat::probe(&lockfile_path(config)?))?;
+
+        Ok(format.filter(|format| !format.is_readable_by(toolchain.version())))
+    }
+
+    /// Whether the toolchain is rejected without being checked, or installed, since its cargo
+    /// can't read the lockfile.
+    fn is_untestable(&self, config: &Config, toolchain: &ToolchainSpec) -> bool {
+        config.lockfile_policy() == LockfilePolicy::Untestable
+            && matches!(self.incompatible_lockfile(config, toolchain), Ok(Some(_)))
+    }
+
+    /// Reject a toolchain which can't read the lockfile, without checking it.
+    fn reject_untestable(
+        &self,
+        toolchain: &ToolchainSpec,
+        format: LockfileFormat,
+    ) -> TResult<Outcome> {
+        let reason = format!(
+            "Rust {} can't read the lockfile, which uses format {} (readable since Rust {})",
+            toolchain.version(),
+            format,
+            format.readable_since()
+        );
+
+        self.reporter.report_event(Compatibility::incompatible(
+            toolchain.to_owned(),
+            Some(reason.clone()),
+        ))?;
+
+        Ok(Outcome::new_failure(toolchain.to_owned(), reason))
+    }
+
+    /// The arguments which apply the binary policy to the check command. The targets of the crate
+    /// are only resolved once, and only when a policy other than `ignore` is configured.
+    fn bin_policy_args(&self, config: &Config) -> TResult<&[String]> {
+        let args = self.bin_policy_args.get_or_try_init(|| {
+            let policy = config.bin_policy();
+
+            if policy == BinPolicy::Ignore {
+                return Ok(Vec::new());
+            }
+
+            let targets = CrateTargets::resolve(config)?;
+
+            if policy == BinPolicy::Exclude {
+                let excluded = targets
+                    .binaries_with_required_features()
+                    .cloned()
+                    .collect::<Vec<_>>();
+
+                if !excluded.is_empty() {
+                    self.reporter
+                        .report_event(ExcludedBinaries::new(excluded))?;
+                }
+            }
+
+            Ok::<_, CargoMSRVError>(targets.check_args(policy))
+        })?;
+
+        Ok(args)
+    }
+}
+
+/// The layer which runs the hooks around a check, and reports its outcome, once it's final, i.e.
+/// after the retries of the check.
+struct ReportedCheck<'reporter, R: Reporter> {
+    reporter: &'reporter R,
+}
+
+impl<'reporter, R: Reporter> CheckLayer for ReportedCheck<'reporter, R> {
+    fn check(
+        &self,
+        config: &Config,
+        toolchain: &ToolchainSpec,
+        inner: &dyn Check,
+    ) -> TResult<Outcome> {
+        if let Some(command) = config.check_hooks().before() {
+            hooks::run_before_check(command, toolchain, current_dir_crate_path(config)?)?;
+        }
+
+        let cache_stats = config.rustc_wrapper().and_then(rustc_wrapper::cache_stats);
+
+        let outcome = inner.check(config, toolchain)?;
+
+        report_cache_stats(self.reporter, config, toolchain, cache_stats)?;
+
+        if config.clean_after().applies_to(outcome.is_success()) {
+            clean_target_dir(config, toolchain);
+        }
+
+        // report outcome to UI
+        let timing = outcome
+            .timing()
+            .cloned()
+            .unwrap_or_else(|| CheckTiming::new(Duration::ZERO, None));
+        report_outcome(self.reporter, &outcome, timing, config.no_check_feedback())?;
+
+        Ok(outcome)
+    }
+}
+
+/// A single attempt of a check: the check command, followed by the doc test command, if doc tests
+/// are checked. The outcome notes how long it took to run them.
+struct Attempt<'reporter, R: Reporter> {
+    reporter: &'reporter R,
+}
+
+impl<'reporter, R: Reporter> Check for Attempt<'reporter, R> {
+    fn check(&self, config: &Config, toolchain: &ToolchainSpec) -> TResult<Outcome> {
+        let dir = current_dir_crate_path(config)?;
+        let check = config.check_command_for_version(toolchain.version());
+
+        let (outcome, timing) =
+            self.run_check_command_via_rustup(config, toolchain, dir, &check)?;
+
+        // the doc tests are only checked once the crate itself compiles
+        if !outcome.is_success() {
+            return Ok(outcome.with_timing(Some(timing)));
+        }
+
+        match config.doc_test_command_for_version(toolchain.version()) {
+            Some(doc_tests) => {
+                let (outcome, doc_timing) =
+                    self.run_check_command_via_rustup(config, toolchain, dir, &doc_tests)?;
+
+                Ok(outcome.with_timing(Some(timing.followed_by(doc_timing))))
+            }
+            None => Ok(outcome.with_timing(Some(timing))),
+        }
+    }
+}
+
+impl<'reporter, R: Reporter> Attempt<'reporter, R> {
     fn run_check_command_via_rustup(
         &self,
+        config: &Config,
         toolchain: &ToolchainSpec,
         dir: Option<&Path>,
         check: &[&str],
-    ) -> TResult<Outcome> {
-        let mut cmd: Vec<&str> = vec![toolchain.spec()];
-        cmd.extend_from_slice(check);
+    ) -> TResult<(Outcome, CheckTiming)> {
+        let runner = runner_toolchain(config, toolchain);
+        let cmd = runner_command(config, &runner, check)?;
 
         self.reporter.report_event(CompatibilityCheckMethod::new(
             toolchain.to_owned(),
             Method::rustup_run(&cmd, dir),
         ))?;
 
-        let rustup_output = RustupCommand::new()
+        let start = Instant::now();
+
+        let mut command = RustupCommand::new();
+
+        if let Some(wrapper) = config.rustc_wrapper() {
+            command = command.with_env(RUSTC_WRAPPER, wrapper);
+        }
+
+        let command = command
             .with_args(cmd.iter())
             .with_optional_dir(dir)
             .with_stderr()
-            .run()
-            .map_err(|_| CargoMSRVError::UnableToRunCheck)?;
+            .with_optional_cancellation(config.cancellation_token());
+
+        // with `--verbose`, the output is reported while the check runs, instead of afterwards
+        let rustup_output = if config.verbose() {
+            command.with_stdout().run_streamed(|pipe, line| {
+                let stream = match pipe {
+                    Pipe::Stdout => OutputStream::Stdout,
+                    Pipe::Stderr => OutputStream::Stderr,
+                };
+
+                self.reporter
+                    .report_event(CheckOutput::new(toolchain.to_owned(), stream, line))?;
+
+                Ok(())
+            })
+        } else {
+            command.run()
+        };
+
+        let rustup_output = rustup_output.map_err(|error| match error {
+            CargoMSRVError::Cancelled => error,
+            _ => CargoMSRVError::UnableToRunCheck,
+        })?;
+
+        let timing = CheckTiming::new(
+            start.elapsed(),
+            parse_cargo_build_duration(rustup_output.stderr()),
+        );
 
         let status = rustup_output.exit_status();
 
-        if status.success() {
-            Ok(Outcome::new_success(toolchain.to_owned()))
+        let outcome = if status.success() {
+            Outcome::new_success(toolchain.to_owned())
         } else {
             let stderr = rustup_output.stderr();
             let command = cmd.join(" ");
@@ -105,67 +430,214 @@ impl<'reporter, R: Reporter> RustupToolchainCheck<'reporter, R> {
                 "try_building run failed"
             );
 
-            Ok(Outcome::new_failure(
-                toolchain.to_owned(),
-                stderr.to_string(),
-            ))
-        }
-    }
-
-    fn report_outcome(&self, outcome: &Outcome, no_error_report: bool) -> TResult<()> {
-        match outcome {
-            Outcome::Success(outcome) => {
-                // report compatibility with this toolchain
-                self.reporter
-                    .report_event(Compatibility::compatible(outcome.toolchain_spec.to_owned()))?
-            }
-            Outcome::Failure(outcome) if no_error_report => {
-                // report incompatibility with this toolchain
-                self.reporter.report_event(Compatibility::incompatible(
-                    outcome.toolchain_spec.to_owned(),
-                    None,
-                ))?
+            // the raw output is kept in the log, since the reported error is decoded lossily
+            let encoding_note = EncodingNote::of(rustup_output.stderr_bytes());
+            if let Some(note) = encoding_note {
+                info!(
+                    ?toolchain,
+                    invalid_sequences = note.invalid_sequences(),
+                    raw_stderr = %rustup_output.stderr_bytes().escape_ascii(),
+                    "output of the check command is not valid UTF-8"
+                );
             }
-            Outcome::Failure(outcome) => {
-                // report incompatibility with this toolchain
-                self.reporter.report_event(Compatibility::incompatible(
-                    outcome.toolchain_spec.to_owned(),
-                    Some(outcome.error_message.clone()),
-                ))?
+
+            // like the log file, the check logs are not written with `--no-log`
+            if config.tracing().is_some() {
+                write_check_log(config, toolchain, &command, rustup_output.stderr_bytes());
             }
+
+            Outcome::new_failure(toolchain.to_owned(), stderr.to_string())
+                .with_encoding_note(encoding_note)
         };
 
-        Ok(())
+        Ok((outcome, timing))
     }
+}
 
-    fn lockfile_path(&self, config: &Config) -> TResult<&Path> {
-        let path = self.lockfile_path.get_or_try_init(|| {
-            config
-                .context()
-                .crate_root_path()
-                .map(|path| path.join(CARGO_LOCK))
-        })?;
+/// The toolchain by which the check is run. With the cross runner, this is the toolchain of the
+/// host, since cross builds for the configured target itself.
+pub(super) fn runner_toolchain<'t>(
+    config: &'t Config,
+    toolchain: &'t ToolchainSpec<'t>,
+) -> ToolchainSpec<'t> {
+    match (config.check_runner(), config.host()) {
+        (CheckRunner::Cross, Some(host)) => ToolchainSpec::new(toolchain.version(), host),
+        _ => toolchain.clone(),
+    }
+}
+
+pub(super) fn report_outcome(
+    reporter: &impl Reporter,
+    outcome: &Outcome,
+    timing: CheckTiming,
+    no_error_report: bool,
+) -> TResult<()> {
+    let event = match outcome {
+        // report compatibility with this toolchain
+        Outcome::Success(outcome) => Compatibility::compatible(outcome.toolchain_spec.to_owned()),
+        // report incompatibility with this toolchain
+        Outcome::Failure(outcome) if no_error_report => {
+            Compatibility::incompatible(outcome.toolchain_spec.to_owned(), None)
+        }
+        Outcome::Failure(outcome) => {
+            let event = Compatibility::incompatible(
+                outcome.toolchain_spec.to_owned(),
+                Some(outcome.error_message.clone()),
+            );
+
+            match outcome.encoding_note {
+                Some(note) => event.with_encoding_note(note),
+                None => event,
+            }
+        }
+    };
+
+    reporter.report_event(event.with_timing(timing))?;
+
+    Ok(())
+}
+
+/// Report the cache statistics of the compiler wrapper for the checks of the toolchain, given the
+/// statistics from before the checks, if the wrapper keeps them.
+fn report_cache_stats(
+    reporter: &impl Reporter,
+    config: &Config,
+    toolchain: &ToolchainSpec,
+    before: Option<CacheStats>,
+) -> TResult<()> {
+    let (wrapper, before) = match (config.rustc_wrapper(), before) {
+        (Some(wrapper), Some(before)) => (wrapper, before),
+        _ => return Ok(()),
+    };
+
+    if let Some(after) = rustc_wrapper::cache_stats(wrapper) {
+        let stats = after.since(before);
+
+        reporter.report_event(WrapperCacheStats::new(
+            toolchain.to_owned(),
+            wrapper,
+            stats.hits,
+            stats.misses,
+        ))?;
+    }
+
+    Ok(())
+}
+
+/// Remove the build artifacts of earlier checks, so a retried check starts from scratch, or, with
+/// `--clean-after`, once a toolchain has been checked. Failing to do so is not fatal, since the
+/// artifacts don't affect the outcome of the check.
+pub(super) fn clean_target_dir(config: &Config, toolchain: &ToolchainSpec) {
+    // a file compiled with rustc doesn't use a target directory
+    if *config.check_with() != CheckWith::Cargo {
+        return;
+    }
+
+    let dir = match current_dir_crate_path(config) {
+        Ok(dir) => dir,
+        Err(error) => {
+            warn!(?toolchain, %error, "unable to clean target directory");
+            return;
+        }
+    };
+
+    let runner = runner_toolchain(config, toolchain);
+
+    let result = RustupCommand::new()
+        .with_args([runner.spec(), "cargo", "clean"])
+        .with_args(config.target_dir_args())
+        .with_optional_dir(dir)
+        .with_optional_cancellation(config.cancellation_token())
+        .run();
+
+    match result {
+        Ok(output) if output.exit_status().success() => {}
+        Ok(output) => {
+            warn!(?toolchain, status = %output.exit_status(), "unable to clean target directory")
+        }
+        Err(error) => warn!(?toolchain, %error, "unable to clean target directory"),
+    }
+}
+
+/// The path of the lockfile of the crate, which may not exist.
+pub(super) fn lockfile_path(config: &Config) -> TResult<PathBuf> {
+    let root = config.context().crate_root_path()?;
+
+    Ok(root.join(CARGO_LOCK))
+}
+
+pub(super) fn remove_lockfile(config: &Config) -> TResult<()> {
+    let lock_file = lockfile_path(config)?;
 
-        Ok(path)
+    if lock_file.is_file() {
+        std::fs::remove_file(&lock_file).map_err(|error| CargoMSRVError::Io {
+            error,
+            source: IoErrorSource::RemoveFile(lock_file),
+        })?;
     }
 
-    fn remove_lockfile(&self, config: &Config) -> TResult<()> {
-        let lock_file = self.lockfile_path(config)?;
+    Ok(())
+}
+
+/// The configuration for a check with a lockfile which is regenerated by the cargo of the
+/// toolchain: the lockfile of the crate is ignored, and `--locked` would reject the new lockfile.
+/// `--frozen` is replaced by `--offline`, which it implies besides `--locked`.
+fn with_regenerated_lockfile<'c>(config: &'c Config) -> Config<'c> {
+    let mut cargo_flags = Vec::new();
+
+    for flag in config.cargo_flags() {
+        let flag = match flag {
+            CargoFlag::Locked => continue,
+            CargoFlag::Frozen => CargoFlag::Offline,
+            other => *other,
+        };
 
-        if lock_file.is_file() {
-            std::fs::remove_file(&lock_file).map_err(|error| CargoMSRVError::Io {
-                error,
-                source: IoErrorSource::RemoveFile(lock_file.to_path_buf()),
-            })?;
+        if !cargo_flags.contains(&flag) {
+            cargo_flags.push(flag);
         }
+    }
+
+    ConfigBuilder::from_config(config)
+        .ignore_lockfile(true)
+        .cargo_flags(cargo_flags)
+        .build()
+}
 
-        Ok(())
+/// The arguments to `rustup run`, which run the check command with the given toolchain. A cargo
+/// check command is given the target, when the toolchain runs on another host. With the cross
+/// runner, the cargo check command is run by cross instead, for the configured target.
+pub(super) fn runner_command<'a>(
+    config: &'a Config,
+    runner: &'a ToolchainSpec,
+    check: &[&'a str],
+) -> TResult<Vec<&'a str>> {
+    let mut cmd = vec![runner.spec()];
+
+    match (config.check_runner(), check) {
+        (CheckRunner::Rustup, ["cargo", ..]) if runner.is_cross_compiling() => {
+            cmd.extend_from_slice(check);
+            cmd.extend(["--target", runner.target()]);
+        }
+        (CheckRunner::Rustup, _) => cmd.extend_from_slice(check),
+        (CheckRunner::Cross, ["cargo", args @ ..]) => {
+            cmd.push("cross");
+            cmd.extend_from_slice(args);
+            cmd.extend(["--target", config.target().as_str()]);
+        }
+        (CheckRunner::Cross, _) => {
+            return Err(CargoMSRVError::InvalidConfig(format!(
+                "Unable to run '{}' with cross, only cargo commands can be run with cross",
+                check.join(" ")
+            )))
+        }
     }
+
+    Ok(cmd)
 }
 
 /// If we manually specify the path to a crate (e.g. with --manifest-path or --path),
 /// we must supply the custom directory to our Command runner.
-fn current_dir_crate_path<'c>(config: &'c Config<'c>) -> TResult<Option<&'c Path>> {
+pub(super) fn current_dir_crate_path<'c>(config: &'c Config<'c>) -> TResult<Option<&'c Path>> {
     if config.crate_path().is_some() || config.manifest_path().is_some() {
         config.context().crate_root_path().map(Some)
     } else {
@@ -173,6 +645,147 @@ fn current_dir_crate_path<'c>(config: &'c Config<'c>) -> TResult<Option<&'c Path
     }
 }
 
+/// Keep the output of a failed check in the log of the toolchain, for `cargo msrv last-failure`.
+/// Failing to do so is not fatal, since the output is also reported.
+fn write_check_log(config: &Config, toolchain: &ToolchainSpec, command: &str, output: &[u8]) {
+    let logs = match CheckLogs::of_config(config) {
+        Some(logs) => logs,
+        None => return,
+    };
+
+    match logs.write_failure(toolchain, command, output) {
+        Ok(path) => debug!(?toolchain, path = %path.display(), "wrote check log"),
+        Err(error) => warn!(?toolchain, %error, "unable to write check log"),
+    }
+}
+
+/// Parse the build duration from the `Finished ... in <duration>` line printed by cargo,
+/// e.g. `in 8.94s`, `in 1m 02s` or (older cargo versions) `in 0.45 secs`.
+fn parse_cargo_build_duration(stderr: &str) -> Option<Duration> {
+    let line = stderr
+        .lines()
+        .rev()
+        .map(str::trim)
+        .find(|line| line.starts_with("Finished"))?;
+
+    let (_, duration) = line.rsplit_once(" in ")?;
+
+    duration
+        .split_whitespace()
+        .filter(|part| *part != "secs")
+        .try_fold(Duration::ZERO, |total, part| {
+            let unit_start = part
+                .find(|c: char| c.is_ascii_alphabetic())
+                .unwrap_or(part.len());
+            let (value, unit) = part.split_at(unit_start);
+            let value = value.parse::<f64>().ok()?;
+
+            let secs = match unit {
+                "h" => value * 3600.0,
+                "m" => value * 60.0,
+                "s" | "" => value,
+                _ => return None,
+            };
+
+            Some(total + Duration::from_secs_f64(secs))
+        })
+}
+
+#[cfg(test)]
+mod parse_cargo_build_duration_tests {
+    use super::*;
+
+    #[yare::parameterized(
+        seconds = { "    Finished dev [unoptimized + debuginfo] target(s) in 8.94s", Some(Duration::from_millis(8940)) },
+        minutes = { "    Finished dev [unoptimized + debuginfo] target(s) in 1m 02s", Some(Duration::from_secs(62)) },
+        old_cargo = { "    Finished dev [unoptimized + debuginfo] target(s) in 0.45 secs", Some(Duration::from_millis(450)) },
+        after_other_output = { "   Compiling a v0.1.0\n    Finished release [optimized] target(s) in 2.00s\n", Some(Duration::from_secs(2)) },
+        not_finished = { "error[E0658]: use of unstable library feature", None },
+        empty = { "", None },
+    )]
+    fn parse(stderr: &str, expected: Option<Duration>) {
+        assert_eq!(parse_cargo_build_duration(stderr), expected);
+    }
+}
+
+#[cfg(test)]
+mod adjusted_cargo_flags_tests {
+    use super::*;
+    use crate::Action;
+
+    fn config(flags: Vec<CargoFlag>) -> Config<'static> {
+        ConfigBuilder::new(Action::Find, "x86_64-unknown-linux-gnu")
+            .cargo_flags(flags)
+            .build()
+    }
+
+    #[yare::parameterized(
+        locked = { vec![CargoFlag::Locked, CargoFlag::IgnoreRustVersion], vec![CargoFlag::IgnoreRustVersion] },
+        frozen = { vec![CargoFlag::Frozen], vec![CargoFlag::Offline] },
+        frozen_and_offline = { vec![CargoFlag::Frozen, CargoFlag::Offline], vec![CargoFlag::Offline] },
+    )]
+    fn regenerated_lockfile(flags: Vec<CargoFlag>, expected: Vec<CargoFlag>) {
+        let config = config(flags);
+
+        assert_eq!(with_regenerated_lockfile(&config).cargo_flags(), expected);
+    }
+}
+
+#[cfg(test)]
+mod runner_command_tests {
+    use super::*;
+    use crate::{semver, Action};
+
+    #[yare::parameterized(
+        rustup = { CheckRunner::Rustup, &["cargo", "check", "--locked"], &["1.56.0-x86_64-unknown-linux-gnu", "cargo", "check", "--locked"] },
+        cross = { CheckRunner::Cross, &["cargo", "check", "--locked"], &["1.56.0-x86_64-unknown-linux-gnu", "cross", "check", "--locked", "--target", "armv7-linux-androideabi"] },
+        cross_doc_tests = { CheckRunner::Cross, &["cargo", "test", "--doc"], &["1.56.0-x86_64-unknown-linux-gnu", "cross", "test", "--doc", "--target", "armv7-linux-androideabi"] },
+    )]
+    fn command(runner: CheckRunner, check: &[&str], expected: &[&str]) {
+        let config = ConfigBuilder::new(Action::Verify, "armv7-linux-androideabi")
+            .check_runner(runner)
+            .build();
+
+        let version = semver::Version::new(1, 56, 0);
+        let toolchain = ToolchainSpec::new(&version, "x86_64-unknown-linux-gnu");
+
+        let cmd = runner_command(&config, &toolchain, check).unwrap();
+
+        assert_eq!(cmd, expected);
+    }
+
+    #[yare::parameterized(
+        cargo = { &["cargo", "check"], &["1.56.0-x86_64-unknown-linux-gnu", "cargo", "check", "--target", "wasm32-unknown-unknown"] },
+        rustc = { &["rustc", "a.rs"], &["1.56.0-x86_64-unknown-linux-gnu", "rustc", "a.rs"] },
+    )]
+    fn command_on_other_host(check: &[&str], expected: &[&str]) {
+        let config = ConfigBuilder::new(Action::Verify, "wasm32-unknown-unknown")
+            .host("x86_64-unknown-linux-gnu")
+            .build();
+
+        let version = semver::Version::new(1, 56, 0);
+        let toolchain = config.toolchain_spec(&version);
+
+        let cmd = runner_command(&config, &toolchain, check).unwrap();
+
+        assert_eq!(cmd, expected);
+    }
+
+    #[test]
+    fn cross_requires_cargo_command() {
+        let config = ConfigBuilder::new(Action::Verify, "armv7-linux-androideabi")
+            .check_runner(CheckRunner::Cross)
+            .build();
+
+        let version = semver::Version::new(1, 56, 0);
+        let toolchain = ToolchainSpec::new(&version, "x86_64-unknown-linux-gnu");
+
+        let result = runner_command(&config, &toolchain, &["rustc", "a.rs"]);
+
+        assert!(matches!(result, Err(CargoMSRVError::InvalidConfig(_))));
+    }
+}
+
 #[cfg(test)]
 mod current_dir_crate_path_tests {
     use super::*;
@@ -0,0 +1,144 @@
+use crate::check::{Check, RustupToolchainCheck};
+use crate::error::IoErrorSource;
+use crate::reporter::event::{CheckTiming, CheckToolchain, Compatibility, DependencyCache};
+use crate::server::{Client, InstallRequest, Request, Response};
+use crate::toolchain::{OwnedToolchainSpec, ToolchainSpec};
+use crate::{download, CargoMSRVError, Config, Outcome, Reporter, TResult};
+use std::time::Instant;
+
+/// Runs each check on the server started with `cargo msrv serve`, which installs the toolchain,
+/// and runs the check command in the directory of the crate, on behalf of concurrent invocations.
+///
+/// When the server can't be reached, or refuses the check, e.g. because only cargo commands are
+/// checked by the server, the toolchain is checked locally instead.
+pub struct ServerCheck<'reporter, R: Reporter> {
+    reporter: &'reporter R,
+    address: String,
+    local: RustupToolchainCheck<'reporter, R>,
+}
+
+impl<'reporter, R: Reporter> Check for ServerCheck<'reporter, R> {
+    fn check(&self, config: &Config, toolchain: &ToolchainSpec) -> TResult<Outcome> {
+        let result =
+            self.reporter
+                .run_scoped_event(CheckToolchain::new(toolchain.to_owned()), || {
+                    if let Some(token) = config.cancellation_token() {
+                        token.check()?;
+                    }
+
+                    let start = Instant::now();
+                    let outcome = self.submit(config, toolchain)?;
+                    let timing = CheckTiming::new(start.elapsed(), None);
+
+                    let event = match &outcome {
+                        Outcome::Success(outcome) => {
+                            Compatibility::compatible(outcome.toolchain_spec.to_owned())
+                        }
+                        Outcome::Failure(outcome) => Compatibility::incompatible(
+                            outcome.toolchain_spec.to_owned(),
+                            Some(outcome.error_message.clone()),
+                        ),
+                    };
+
+                    self.reporter.report_event(event.with_timing(timing))?;
+
+                    Ok(outcome)
+                });
+
+        match result {
+            Err(CargoMSRVError::ServerUnreachable { address, message }) => {
+                warn!(
+                    %address,
+                    %message,
+                    "unable to reach the server, checking the toolchain locally"
+                );
+
+                self.local.check(config, toolchain)
+            }
+            result => result,
+        }
+    }
+
+    fn dependency_cache(&self) -> Option<DependencyCache> {
+        self.local.dependency_cache()
+    }
+}
+
+impl<'reporter, R: Reporter> ServerCheck<'reporter, R> {
+    pub fn new(reporter: &'reporter R, address: impl Into<String>) -> Self {
+        Self {
+            reporter,
+            address: address.into(),
+            local: RustupToolchainCheck::new(reporter),
+        }
+    }
+
+    fn submit(&self, config: &Config, toolchain: &ToolchainSpec) -> TResult<Outcome> {
+        // the server doesn't share the working directory of the client
+        let root = config.context().crate_root_path()?;
+        let dir = std::env::current_dir()
+            .map_err(|error| CargoMSRVError::Io {
+                error,
+                source: IoErrorSource::CurrentDir,
+            })?
+            .join(root);
+
+        let request = Request::Check {
+            install: InstallRequest::new(
+                toolchain,
+                config.install_profile(),
+                &config.required_components(),
+            ),
+            dir,
+            check_command: config
+                .check_command_for_version(toolchain.version())
+                .iter()
+                .map(|arg| arg.to_string())
+                .collect(),
+        };
+
+        let response = Client::new(&self.address).request(&request)?;
+
+        match response {
+            Response::Check { compatible, error } => {
+                Ok(outcome_of(toolchain.to_owned(), compatible, error))
+            }
+            // the toolchain could not be installed, or the server refused the check
+            response => {
+                download::installed_by_server(&self.address, toolchain, response)?;
+
+                Err(CargoMSRVError::ServerUnreachable {
+                    address: self.address.clone(),
+                    message: "unexpected response to a check request".to_string(),
+                })
+            }
+        }
+    }
+}
+
+fn outcome_of(toolchain: OwnedToolchainSpec, compatible: bool, error: Option<String>) -> Outcome {
+    if compatible {
+        Outcome::new_success(toolchain)
+    } else {
+        Outcome::new_failure(toolchain, error.unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::semver;
+
+    fn toolchain() -> OwnedToolchainSpec {
+        OwnedToolchainSpec::new(&semver::Version::new(1, 56, 0), "x86_64-unknown-linux-gnu")
+    }
+
+    #[yare::parameterized(
+        compatible = { true, None, Outcome::new_success(toolchain()) },
+        incompatible = { false, Some("error[E0658]".to_string()), Outcome::new_failure(toolchain(), "error[E0658]".to_string()) },
+        incompatible_without_error = { false, None, Outcome::new_failure(toolchain(), String::new()) },
+    )]
+    fn outcome_of_response(compatible: bool, error: Option<String>, expected: Outcome) {
+        assert_eq!(outcome_of(toolchain(), compatible, error), expected);
+    }
+}
@@ -19,6 +19,10 @@ impl TestRunner {
 
 impl Check for TestRunner {
     fn check(&self, config: &Config, toolchain: &ToolchainSpec) -> TResult<Outcome> {
+        if let Some(token) = config.cancellation_token() {
+            token.check()?;
+        }
+
         let v = toolchain.version();
 
         if self.accept_versions.contains(toolchain.version()) {
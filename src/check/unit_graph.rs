@@ -0,0 +1,310 @@
+//! Reuse of the outcome of an earlier check, as enabled with the experimental
+//! `--reuse-unit-graph` flag.
+//!
+//! Before a toolchain is checked, cargo is asked for the unit graph of the check, i.e. the
+//! packages, targets, features and profiles which it would compile, with `--unit-graph`. When the
+//! toolchain was checked before, with the same unit graph, and the sources of the local packages
+//! are unchanged, nothing relevant changed, and the outcome of the earlier check is reused.
+//!
+//! Since the outcome of a check depends on the compiler, outcomes are never reused across
+//! toolchains, even when their unit graphs coincide. Reuse pays off when a toolchain is checked
+//! repeatedly, e.g. for feature sets of `cargo msrv matrix` which resolve to the same unit graph,
+//! or for revisions visited by `cargo msrv history --verify` which didn't change the crate.
+
+use std::cell::RefCell;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::cancellation::CancellationToken;
+use crate::check::rustup_toolchain_check::{
+    current_dir_crate_path, report_outcome, runner_command, runner_toolchain,
+};
+use crate::check::{Check, CheckLayer};
+use crate::command::RustupCommand;
+use crate::config::CheckRunner;
+use crate::error::{CargoMSRVError, IoErrorSource};
+use crate::reporter::event::{CheckTiming, UnitGraphReuse};
+use crate::toolchain::ToolchainSpec;
+use crate::{Config, Outcome, Reporter, TResult};
+
+/// `--unit-graph` is unstable, so it's only accepted by a stable cargo when bootstrapping.
+const RUSTC_BOOTSTRAP: &str = "RUSTC_BOOTSTRAP";
+
+/// The outcomes of the checks so far, by the key of their unit graph.
+#[derive(Debug, Default)]
+pub(crate) struct ReusableOutcomes {
+    outcomes: RefCell<HashMap<u64, Outcome>>,
+}
+
+impl ReusableOutcomes {
+    pub(crate) fn get(&self, key: u64) -> Option<Outcome> {
+        self.outcomes.borrow().get(&key).cloned()
+    }
+
+    pub(crate) fn insert(&self, key: u64, outcome: Outcome) {
+        self.outcomes.borrow_mut().insert(key, outcome);
+    }
+}
+
+/// A [`CheckLayer`] which reuses the outcome of an earlier check of the toolchain, with the same
+/// unit graph, instead of running the inner check, when enabled with `--reuse-unit-graph`.
+pub(crate) struct UnitGraphReuseCheck<'reporter, R: Reporter> {
+    reporter: &'reporter R,
+    outcomes: ReusableOutcomes,
+}
+
+impl<'reporter, R: Reporter> UnitGraphReuseCheck<'reporter, R> {
+    pub(crate) fn new(reporter: &'reporter R) -> Self {
+        Self {
+            reporter,
+            outcomes: ReusableOutcomes::default(),
+        }
+    }
+
+    /// The key under which the outcome of the check may be reused. Only cargo commands which are
+    /// run with rustup have a unit graph.
+    fn key(&self, config: &Config, toolchain: &ToolchainSpec) -> TResult<Option<u64>> {
+        let check = config.check_command_for_version(toolchain.version());
+
+        let key = match (config.check_runner(), check.as_slice()) {
+            (CheckRunner::Rustup, ["cargo", ..]) => {
+                let runner = runner_toolchain(config, toolchain);
+                let cmd = runner_command(config, &runner, &check)?;
+                let doc_tests = config
+                    .doc_test_command_for_version(toolchain.version())
+                    .map(|command| command.join(" "))
+                    .unwrap_or_default();
+                let dir = current_dir_crate_path(config)?;
+
+                unit_graph_key(&cmd, dir, &doc_tests, config.cancellation_token())?
+            }
+            _ => Err("only cargo commands run with rustup have a unit graph".to_string()),
+        };
+
+        match key {
+            Ok(key) if self.outcomes.get(key).is_some() => Ok(Some(key)),
+            Ok(key) => {
+                self.reporter
+                    .report_event(UnitGraphReuse::checked(toolchain.to_owned()))?;
+                Ok(Some(key))
+            }
+            Err(reason) => {
+                info!(?toolchain, %reason, "unable to determine the unit graph");
+                self.reporter
+                    .report_event(UnitGraphReuse::unavailable(toolchain.to_owned(), reason))?;
+                Ok(None)
+            }
+        }
+    }
+}
+
+impl<'reporter, R: Reporter> CheckLayer for UnitGraphReuseCheck<'reporter, R> {
+    fn check(
+        &self,
+        config: &Config,
+        toolchain: &ToolchainSpec,
+        inner: &dyn Check,
+    ) -> TResult<Outcome> {
+        if !config.reuse_unit_graph() {
+            return inner.check(config, toolchain);
+        }
+
+        let key = match self.key(config, toolchain)? {
+            Some(key) => key,
+            None => return inner.check(config, toolchain),
+        };
+
+        if let Some(outcome) = self.outcomes.get(key) {
+            info!(?toolchain, "reusing the outcome of an earlier check");
+            self.reporter
+                .report_event(UnitGraphReuse::reused(toolchain.to_owned()))?;
+
+            let timing = CheckTiming::new(Duration::ZERO, None);
+            report_outcome(self.reporter, &outcome, timing, config.no_check_feedback())?;
+
+            return Ok(outcome);
+        }
+
+        let outcome = inner.check(config, toolchain)?;
+        self.outcomes.insert(key, outcome.clone());
+
+        Ok(outcome)
+    }
+}
+
+/// The key of the unit graph of the given `rustup run` command, which includes the toolchain, the
+/// command itself, and the sources of the local packages of the graph. The `extra` part is
+/// included as well, e.g. for the doc test command, which is not part of the graph.
+///
+/// Returns the reason why the unit graph could not be determined, if it could not, e.g. because
+/// the cargo of the toolchain doesn't support `--unit-graph` yet.
+pub(crate) fn unit_graph_key(
+    cmd: &[&str],
+    dir: Option<&Path>,
+    extra: &str,
+    cancellation: Option<&CancellationToken>,
+) -> TResult<Result<u64, String>> {
+    let output = RustupCommand::new()
+        .with_env(RUSTC_BOOTSTRAP, "1")
+        .with_args(cmd.iter())
+        .with_args(["--unit-graph", "-Z", "unstable-options"])
+        .with_optional_dir(dir)
+        .with_stdout()
+        .with_stderr()
+        .with_optional_cancellation(cancellation)
+        .run()?;
+
+    if !output.exit_status().success() {
+        let reason = output
+            .stderr()
+            .lines()
+            .find(|line| line.starts_with("error"))
+            .unwrap_or("cargo failed to report the unit graph")
+            .to_string();
+
+        return Ok(Err(reason));
+    }
+
+    let graph: serde_json::Value = match serde_json::from_str(output.stdout()) {
+        Ok(graph) => graph,
+        Err(error) => return Ok(Err(format!("unable to parse the unit graph: {}", error))),
+    };
+
+    let mut hasher = DefaultHasher::new();
+    cmd.hash(&mut hasher);
+    extra.hash(&mut hasher);
+    graph.to_string().hash(&mut hasher);
+
+    for dir in local_package_dirs(&graph) {
+        if !dir.is_dir() {
+            return Ok(Err(format!(
+                "unable to locate the sources of the package in '{}'",
+                dir.display()
+            )));
+        }
+
+        hash_sources(&dir, &mut hasher)?;
+    }
+
+    Ok(Ok(hasher.finish()))
+}
+
+/// The directories of the local packages of the unit graph, whose sources may change without
+/// changing the graph.
+fn local_package_dirs(graph: &serde_json::Value) -> Vec<PathBuf> {
+    let mut dirs = graph["units"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|unit| unit["pkg_id"].as_str())
+        .filter_map(local_package_dir)
+        .collect::<Vec<_>>();
+
+    dirs.sort();
+    dirs.dedup();
+    dirs
+}
+
+/// The directory of a package, if its id refers to a local package, like
+/// `path+file:///home/user/crate#0.1.0`, or, as written by older versions of cargo,
+/// `crate 0.1.0 (path+file:///home/user/crate)`.
+fn local_package_dir(pkg_id: &str) -> Option<PathBuf> {
+    let (_, path) = pkg_id.split_once("path+file://")?;
+    let end = path.find(|c| c == '#' || c == ')').unwrap_or(path.len());
+
+    Some(PathBuf::from(&path[..end]))
+}
+
+/// Hash the paths and contents of the files in the directory and its subdirectories, except for
+/// build artifacts and hidden files, in a stable order.
+fn hash_sources(dir: &Path, hasher: &mut DefaultHasher) -> TResult<()> {
+    let entries = std::fs::read_dir(dir).map_err(|error| CargoMSRVError::Io {
+        error,
+        source: IoErrorSource::ReadDir(dir.to_path_buf()),
+    })?;
+
+    let mut paths = entries
+        .filter_map(Result::ok)
+        .filter(|entry| {
+            let name = entry.file_name();
+            name != "target" && !name.to_string_lossy().starts_with('.')
+        })
+        .map(|entry| entry.path())
+        .collect::<Vec<_>>();
+
+    paths.sort();
+
+    for path in paths {
+        path.hash(hasher);
+
+        if path.is_dir() {
+            hash_sources(&path, hasher)?;
+        } else {
+            let contents = std::fs::read(&path).map_err(|error| CargoMSRVError::Io {
+                error,
+                source: IoErrorSource::ReadFile(path.clone()),
+            })?;
+
+            contents.hash(hasher);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_dir::{DirBuilder, TestDir};
+
+    #[yare::parameterized(
+        local = { "path+file:///home/user/crate#0.1.0", Some("/home/user/crate") },
+        local_with_name = { "path+file:///home/user/crates/core#core@0.1.0", Some("/home/user/crates/core") },
+        local_before_cargo_1_77 = { "crate 0.1.0 (path+file:///home/user/crate)", Some("/home/user/crate") },
+        registry = { "registry+https://github.com/rust-lang/crates.io-index#serde@1.0.0", None },
+    )]
+    fn package_dir(pkg_id: &str, expected: Option<&str>) {
+        assert_eq!(local_package_dir(pkg_id), expected.map(PathBuf::from));
+    }
+
+    #[test]
+    fn local_package_dirs_of_graph() {
+        let graph = serde_json::json!({
+            "version": 1,
+            "units": [
+                { "pkg_id": "path+file:///crate#0.1.0" },
+                { "pkg_id": "registry+https://github.com/rust-lang/crates.io-index#libc@0.2.0" },
+                { "pkg_id": "path+file:///crate#0.1.0" },
+            ],
+            "roots": [0, 2],
+        });
+
+        assert_eq!(local_package_dirs(&graph), vec![PathBuf::from("/crate")]);
+    }
+
+    #[test]
+    fn changed_sources_change_the_hash() {
+        let tmp = TestDir::temp();
+        std::fs::create_dir_all(tmp.path("src")).unwrap();
+        std::fs::create_dir_all(tmp.path("target")).unwrap();
+        std::fs::write(tmp.path("src/lib.rs"), "pub fn f() {}\n").unwrap();
+
+        let hash = || {
+            let mut hasher = DefaultHasher::new();
+            hash_sources(tmp.root(), &mut hasher).unwrap();
+            hasher.finish()
+        };
+
+        let before = hash();
+
+        // build artifacts don't matter
+        std::fs::write(tmp.path("target/artifact"), "").unwrap();
+        assert_eq!(hash(), before);
+
+        std::fs::write(tmp.path("src/lib.rs"), "pub fn g() {}\n").unwrap();
+        assert_ne!(hash(), before);
+    }
+}
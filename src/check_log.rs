@@ -0,0 +1,130 @@
+//! The output of failed toolchain checks, which is kept in a log per toolchain, so a failure can be
+//! looked into after the run, e.g. with `cargo msrv last-failure`.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::config::Config;
+use crate::log_file::log_folder;
+use crate::toolchain::ToolchainSpec;
+
+const EXTENSION: &str = "log";
+
+/// The folder with the logs of the failed checks, one per toolchain.
+#[derive(Debug)]
+pub(crate) struct CheckLogs {
+    folder: PathBuf,
+}
+
+impl CheckLogs {
+    pub(crate) fn new(folder: PathBuf) -> Self {
+        Self { folder }
+    }
+
+    /// The check logs in the log folder of cargo-msrv, if it can be determined.
+    pub(crate) fn of_config(config: &Config) -> Option<Self> {
+        log_folder(config.scratch_dir()).map(|folder| Self::new(folder.join("checks")))
+    }
+
+    pub(crate) fn folder(&self) -> &Path {
+        &self.folder
+    }
+
+    /// Write the raw output of a failed check of the toolchain, preceded by the check command.
+    /// An earlier log of the toolchain is replaced.
+    pub(crate) fn write_failure(
+        &self,
+        toolchain: &ToolchainSpec,
+        command: &str,
+        output: &[u8],
+    ) -> io::Result<PathBuf> {
+        fs::create_dir_all(&self.folder)?;
+
+        let path = self
+            .folder
+            .join(format!("{}.{}", toolchain.spec(), EXTENSION));
+
+        let mut contents = format!("$ {}\n", command).into_bytes();
+        contents.extend_from_slice(output);
+        fs::write(&path, contents)?;
+
+        Ok(path)
+    }
+
+    /// The log which was written most recently, if any log was written.
+    pub(crate) fn most_recent(&self) -> io::Result<Option<PathBuf>> {
+        let entries = match fs::read_dir(&self.folder) {
+            Ok(entries) => entries,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(error) => return Err(error),
+        };
+
+        let mut most_recent: Option<(SystemTime, PathBuf)> = None;
+
+        for entry in entries {
+            let path = entry?.path();
+
+            if path
+                .extension()
+                .map_or(true, |extension| extension != EXTENSION)
+            {
+                continue;
+            }
+
+            let modified = fs::metadata(&path)?.modified()?;
+
+            if most_recent.as_ref().map_or(true, |(at, _)| modified > *at) {
+                most_recent = Some((modified, path));
+            }
+        }
+
+        Ok(most_recent.map(|(_, path)| path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::semver;
+    use std::time::Duration;
+    use test_dir::{DirBuilder, TestDir};
+
+    fn write(logs: &CheckLogs, minor: u64, output: &[u8]) -> PathBuf {
+        let version = semver::Version::new(1, minor, 0);
+        let toolchain = ToolchainSpec::new(&version, "x86_64-unknown-linux-gnu");
+
+        logs.write_failure(&toolchain, "cargo check", output)
+            .unwrap()
+    }
+
+    #[test]
+    fn write_failure() {
+        let tmp = TestDir::temp();
+        let logs = CheckLogs::new(tmp.path("checks"));
+
+        let path = write(&logs, 61, b"error[E0658]: \xfc");
+
+        assert_eq!(path, tmp.path("checks/1.61.0-x86_64-unknown-linux-gnu.log"));
+        assert_eq!(
+            fs::read(&path).unwrap(),
+            b"$ cargo check\nerror[E0658]: \xfc".to_vec()
+        );
+    }
+
+    #[test]
+    fn most_recent() {
+        let tmp = TestDir::temp();
+        let logs = CheckLogs::new(tmp.path("checks"));
+
+        assert_eq!(logs.most_recent().unwrap(), None);
+
+        write(&logs, 60, b"");
+        // the modification times of the logs must differ
+        std::thread::sleep(Duration::from_millis(50));
+        let expected = write(&logs, 61, b"");
+
+        assert_eq!(logs.most_recent().unwrap(), Some(expected));
+    }
+}
@@ -1,17 +1,24 @@
-use crate::cli::configurators::Configure;
+use crate::cli::configurators::{read_project_config, Configure, FallbackVariables};
 use crate::cli::custom_check_opts::CustomCheckOpts;
 use crate::cli::find_opts::FindOpts;
 use crate::cli::rust_releases_opts::RustReleasesOpts;
 use crate::cli::shared_opts::SharedOpts;
 use crate::cli::toolchain_opts::ToolchainOpts;
-use crate::config::list::ListMsrvVariant;
-use crate::config::ConfigBuilder;
+use crate::config::discover::DiscoverRun;
+use crate::config::impact::ProspectiveDependency;
+use crate::config::list::{ListMsrvVariant, RegistryIndex};
+use crate::config::preferences::PREFERENCE_KEYS;
+use crate::config::project_config::ProjectConfig;
+use crate::config::{ConfigBuilder, FeatureSet, OutputFormat};
 use crate::default_target::default_target;
+use crate::error::IoErrorSource;
 use crate::manifest::bare_version::BareVersion;
-use crate::{Action, CargoMSRVError, Config};
+use crate::server::DEFAULT_ADDRESS;
+use crate::{Action, CargoMSRVError, Config, TResult};
 use clap::{AppSettings, Args, Parser, Subcommand};
 use std::convert::{TryFrom, TryInto};
 use std::ffi::{OsStr, OsString};
+use std::path::PathBuf;
 
 pub(in crate::cli) mod configurators;
 pub(crate) mod custom_check_opts;
@@ -29,9 +36,45 @@ pub struct CargoCli {
 
 impl CargoCli {
     pub fn parse_args<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Self {
-        let modified_args = modify_args(args);
-        CargoCli::parse_from(modified_args)
+        let modified_args = modify_args(args).into_iter().collect::<Vec<_>>();
+        let mut cli = CargoCli::parse_from(&modified_args);
+
+        match &mut cli.subcommand {
+            CargoMsrvCli::Msrv(opts) => {
+                opts.given_flags = given_flags(&modified_args);
+                opts.fallback_variables = FallbackVariables::from_env();
+                opts.project_config = read_project_config(opts);
+            }
+        }
+
+        cli
     }
+
+    /// The format of user output, as given on the command line. Used to report a failure to
+    /// resolve the configuration in the format which was asked for.
+    pub fn output_format(&self) -> OutputFormat {
+        let opts = match &self.subcommand {
+            CargoMsrvCli::Msrv(opts) => &opts.shared_opts.user_output_opts,
+        };
+
+        if opts.no_user_output {
+            OutputFormat::None
+        } else {
+            opts.output_format
+        }
+    }
+}
+
+/// The long flags which were given on the command line, e.g. `--min` for `--min=1.56`. Used to
+/// report where the value of a configuration option came from.
+fn given_flags(args: &[OsString]) -> Vec<String> {
+    args.iter()
+        .map(|arg| arg.to_string_lossy())
+        // The arguments after `--` make up the custom check command
+        .take_while(|arg| arg != "--")
+        .filter(|arg| arg.starts_with("--"))
+        .map(|arg| arg.split('=').next().unwrap_or_default().to_string())
+        .collect()
 }
 
 // When we call cargo-msrv with cargo, cargo will supply the msrv subcommand, in addition
@@ -101,20 +144,111 @@ pub(in crate::cli) struct CargoMsrvOpts {
     /// DEPRECATED: Use the `cargo msrv verify` subcommand instead
     #[clap(long, global = false, hide = true)]
     pub(in crate::cli) verify: bool,
+
+    /// The long flags which were given on the command line
+    #[clap(skip)]
+    pub(in crate::cli) given_flags: Vec<String>,
+
+    /// The environment variables which `verify` falls back to
+    #[clap(skip)]
+    pub(in crate::cli) fallback_variables: FallbackVariables,
+
+    /// The settings of the project config files which apply to the crate
+    #[clap(skip)]
+    pub(in crate::cli) project_config: Option<TResult<ProjectConfig>>,
 }
 
 #[derive(Debug, Subcommand)]
 #[clap(propagate_version = true)]
 pub(in crate::cli) enum SubCommand {
+    /// Inspect the configuration of cargo-msrv
+    Config(ConfigOpts),
+    /// Discover the Cargo projects in a directory tree, and list them with their MSRV
+    ///
+    /// Scans the directory given with '--path', or the current directory, for Cargo manifests.
+    /// Target directories, hidden directories and vendored sources are skipped. With '--run',
+    /// the MSRV of each discovered package is found or verified.
+    Discover(DiscoverOpts),
+    /// Determine which Rust editions are compatible with the MSRV of your crate
+    ///
+    /// Reports the first Rust version which supports each edition, and the newest edition which
+    /// is supported by the MSRV specified in the Cargo manifest.
+    Edition(EditionOpts),
+    /// Show how the MSRV of your crate evolved across its releases
+    ///
+    /// Checks out each tag of the git repository which is reachable from HEAD, or each commit of
+    /// the range given with '--range', in a temporary worktree, and reports the MSRV specified in
+    /// the Cargo manifest at that revision. With '--verify', the MSRV of each revision is also
+    /// verified with its toolchain.
+    History(HistoryOpts),
+    /// Preview whether adding a dependency would force the MSRV of your crate up
+    ///
+    /// Adds the dependency to a copy of the crate, and checks the copy with the MSRV specified in
+    /// the Cargo manifest. With '--find', the new MSRV is searched for when the MSRV no longer
+    /// holds. The options given before the `impact` subcommand, like `--max`, apply to the check
+    /// and the search.
+    Impact(ImpactOpts),
+    /// Locate the log of the most recent failed toolchain check
+    ///
+    /// Prints the path of the log, which holds the check command and its output. A log is kept
+    /// for each toolchain which failed its check, in the log folder of cargo-msrv, unless
+    /// '--no-log' is given.
+    LastFailure(LastFailureOpts),
     /// Display the MSRV's of dependencies
     List(ListOpts),
+    /// Estimate what it would take to lower the MSRV of your crate
+    ///
+    /// Checks a copy of the crate with progressively older releases, below the MSRV specified in
+    /// the Cargo manifest, and reports for each release the minimal changes which would make the
+    /// crate compatible, like pinning a dependency to an older release, or gating code behind a
+    /// cfg. The options given before the `lower` subcommand, like `--min`, apply to the releases
+    /// which are tried.
+    Lower(LowerOpts),
+    /// Determine the MSRV for each combination of targets and feature configurations
+    ///
+    /// Runs the search for each target given with '--target' and each feature configuration given
+    /// with '--features', and reports the MSRV of each combination as a table, or as nested json
+    /// with `--output-format json`. The options given before the `matrix` subcommand, like
+    /// `--min` and `--max`, apply to every search.
+    Matrix(MatrixOpts),
+    /// Enforce the MSRV policy of your team
+    Policy(PolicyOpts),
+    /// List the stable Rust releases which cargo-msrv takes into account
+    ///
+    /// Lists each release of the release index, with its release date, and whether it is included
+    /// in the search space, after the options given before the `releases` subcommand, like `--min`
+    /// and `--max`, are applied. If a release is excluded, the reason is given.
+    Releases,
+    /// Report on the results of earlier cargo-msrv runs
+    Report(ReportOpts),
+    /// Print the JSON schema of the messages written with `--output-format json`
+    ///
+    /// The schema describes the fields which are shared by all messages, and the fields of each
+    /// type of message. With '--out-dir', a schema file is written for each type of message
+    /// instead, like 'msrv_result.schema.json'.
+    Schema(SchemaOpts),
+    /// Serve the release index, install toolchains, and run checks, for other invocations
+    ///
+    /// Fetches the release index once, and keeps it in memory, while it accepts the requests of
+    /// cargo-msrv invocations which are given '--server', on a local socket, and runs their checks.
+    /// Each toolchain is only installed once, so concurrent invocations, e.g. for the crates of a
+    /// monorepo, neither fetch the index again, nor install the same toolchain twice. Runs until it
+    /// is interrupted.
+    Serve(ServeOpts),
     /// Set the MSRV of the current crate to a given Rust version
     Set(SetOpts),
     /// Show the MSRV of your crate, as specified in the Cargo manifest
-    Show,
+    Show(ShowOpts),
     /// Verify whether the MSRV is satisfiable. The MSRV must be specified using the
     /// 'package.rust-version' or 'package.metadata.msrv' key in the Cargo.toml manifest.
     Verify(VerifyOpts),
+    /// Validate the MSRV declared in the Cargo manifest, before it is used
+    ///
+    /// Checks that 'package.rust-version' and 'package.metadata.msrv' are valid Rust versions,
+    /// which are not newer than the most recent stable release, nor older than the first release
+    /// which supports the edition of the crate, and that an MSRV inherited from the workspace is
+    /// declared by the workspace. Each issue is reported with its location in the manifest.
+    Validate,
 }
 
 #[derive(Debug, Args)]
@@ -123,6 +257,234 @@ pub(in crate::cli) struct ListOpts {
     /// Display the MSRV's of crates that your crate depends on
     #[clap(long, possible_values = ListMsrvVariant::variants(), default_value_t)]
     variant: ListMsrvVariant,
+
+    /// Path to a local checkout of the crates.io index
+    ///
+    /// Used by the 'orphans' variant to report, for each dependency which does not declare a
+    /// rust-version, whether the latest release of that dependency does, and in which release
+    /// the rust-version field was added or removed.
+    #[clap(long, value_name = "DIR")]
+    crates_index: Option<PathBuf>,
+
+    /// Path to a local checkout of the index of an alternative registry, given as 'URL=DIR'
+    ///
+    /// The URL is the index URL of the registry, e.g. as configured in '.cargo/config.toml'. Used
+    /// like '--crates-index', for the dependencies which are published to that registry.
+    /// May be given multiple times.
+    #[clap(long, value_name = "URL=DIR", multiple_occurrences = true)]
+    registry_index: Vec<RegistryIndex>,
+
+    /// Report which dependencies force the MSRV of your crate up
+    ///
+    /// For each direct dependency, reports the highest rust-version declared by the dependency or
+    /// any of its transitive dependencies, and the chain of dependencies which leads to the crate
+    /// declaring it.
+    #[clap(long, conflicts_with = "variant")]
+    resolve: bool,
+}
+
+#[derive(Debug, Args)]
+#[clap(next_help_heading = "MATRIX OPTIONS", setting = AppSettings::DeriveDisplayOrder)]
+pub(in crate::cli) struct MatrixOpts {
+    /// A target for which the MSRV is determined
+    ///
+    /// May be given multiple times. Defaults to the target given before the `matrix` subcommand,
+    /// or the default target of rustup.
+    #[clap(long, value_name = "TARGET", multiple_occurrences = true)]
+    pub(in crate::cli) target: Vec<String>,
+
+    /// A feature configuration for which the MSRV is determined
+    ///
+    /// One of 'default', 'no-default-features', 'all-features', or a comma separated list of
+    /// features, which are enabled in addition to the default features. May be given multiple
+    /// times. Defaults to 'default'.
+    #[clap(long, value_name = "FEATURES", multiple_occurrences = true)]
+    pub(in crate::cli) features: Vec<FeatureSet>,
+}
+
+#[derive(Debug, Args)]
+#[clap(next_help_heading = "EDITION OPTIONS", setting = AppSettings::DeriveDisplayOrder)]
+pub(in crate::cli) struct EditionOpts {
+    /// Confirm that the crate can be migrated to the next edition, with `cargo fix --edition`
+    ///
+    /// The migration is run on a copy of the crate, with the MSRV toolchain, or, if the MSRV
+    /// doesn't support the next edition, with the first toolchain which does.
+    #[clap(long)]
+    pub(in crate::cli) check_migration: bool,
+}
+
+#[derive(Debug, Args)]
+#[clap(next_help_heading = "HISTORY OPTIONS", setting = AppSettings::DeriveDisplayOrder)]
+pub(in crate::cli) struct HistoryOpts {
+    /// Walk the commits of the given range, like 'v1.0.0..HEAD', instead of the tags
+    ///
+    /// Accepts any range which 'git rev-list' accepts. The commits are visited from the least to
+    /// the most recent commit.
+    #[clap(long, value_name = "REV_RANGE")]
+    pub(in crate::cli) range: Option<String>,
+
+    /// Verify the MSRV of each revision with its toolchain, like `cargo msrv verify`
+    ///
+    /// The options given before the `history` subcommand, like '--target', apply to the
+    /// verification. Fails when the MSRV of any revision does not hold.
+    #[clap(long)]
+    pub(in crate::cli) verify: bool,
+}
+
+#[derive(Debug, Args)]
+#[clap(next_help_heading = "SERVE OPTIONS", setting = AppSettings::DeriveDisplayOrder)]
+pub(in crate::cli) struct ServeOpts {
+    /// The address on which the server listens, like '127.0.0.1:7430'
+    ///
+    /// Clients give the same address with '--server'. The requests are not authenticated, so the
+    /// server should only listen on a local address.
+    #[clap(long, default_value = DEFAULT_ADDRESS, value_name = "ADDR")]
+    pub(in crate::cli) address: String,
+}
+
+#[derive(Debug, Args)]
+#[clap(next_help_heading = "IMPACT OPTIONS", setting = AppSettings::DeriveDisplayOrder)]
+pub(in crate::cli) struct ImpactOpts {
+    /// The dependency, as 'NAME@VERSION', e.g. 'serde@1.0.190'
+    ///
+    /// The version is a version requirement, as it would be written in the Cargo manifest.
+    /// Without a version, the most recent release is used. A dependency which the crate already
+    /// has is updated to the given version requirement instead.
+    #[clap(value_name = "CRATE@VERSION")]
+    pub(in crate::cli) dependency: ProspectiveDependency,
+
+    /// Find the MSRV with the dependency, when the current MSRV no longer holds
+    ///
+    /// Only the releases which are more recent than the current MSRV are searched.
+    #[clap(long)]
+    pub(in crate::cli) find: bool,
+}
+
+#[derive(Debug, Args)]
+#[clap(next_help_heading = "LOWER OPTIONS", setting = AppSettings::DeriveDisplayOrder)]
+pub(in crate::cli) struct LowerOpts {
+    /// The number of releases less recent than the MSRV to try
+    ///
+    /// The releases are tried from the most recent to the least recent, e.g. Rust 1.59, 1.58 and
+    /// 1.57 for a crate with MSRV 1.60, and '--steps 3'.
+    #[clap(long, default_value_t = 5, value_name = "N")]
+    pub(in crate::cli) steps: usize,
+
+    /// Path to a local checkout of the crates.io index
+    ///
+    /// Used to suggest the most recent release to which a dependency can be pinned, among the
+    /// releases which declare a rust-version that the older release satisfies.
+    #[clap(long, value_name = "DIR")]
+    pub(in crate::cli) crates_index: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+#[clap(next_help_heading = "LAST FAILURE OPTIONS", setting = AppSettings::DeriveDisplayOrder)]
+pub(in crate::cli) struct LastFailureOpts {
+    /// Show the log with the pager given by the PAGER environment variable
+    ///
+    /// Falls back to 'less', or 'more' on Windows, when PAGER is not set.
+    #[clap(long)]
+    pub(in crate::cli) pager: bool,
+}
+
+#[derive(Debug, Args)]
+#[clap(next_help_heading = "SCHEMA OPTIONS", setting = AppSettings::DeriveDisplayOrder)]
+pub(in crate::cli) struct SchemaOpts {
+    /// Write the schema files to the given directory, instead of printing the schema
+    ///
+    /// Writes 'event.schema.json', with the schema of all messages, and a schema file for each
+    /// type of message. The directory is created if it doesn't exist.
+    #[clap(long, value_name = "DIR")]
+    pub(in crate::cli) out_dir: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+pub(in crate::cli) struct ConfigOpts {
+    #[clap(subcommand)]
+    pub(in crate::cli) variant: ConfigVariant,
+}
+
+#[derive(Debug, Subcommand)]
+pub(in crate::cli) enum ConfigVariant {
+    /// Show the resolved configuration, and where each value came from
+    ///
+    /// The configuration is resolved from the options given on the command line (before the
+    /// `config` subcommand), environment variables and the Cargo manifest, as it would be by
+    /// `cargo msrv`. Printed as TOML, or as json with `--output-format json`.
+    Show,
+    /// Remember the value of an option, so it doesn't have to be given on every invocation
+    ///
+    /// The preference is written to `preferences.toml`, in the `cargo-msrv` folder of your
+    /// configuration directory (e.g. `~/.config/cargo-msrv/preferences.toml` on Linux). An option
+    /// given on the command line takes precedence over its preference.
+    Set(ConfigSetOpts),
+}
+
+#[derive(Debug, Args)]
+pub(in crate::cli) struct ConfigSetOpts {
+    /// The configuration key of the option
+    #[clap(value_name = "KEY", possible_values = PREFERENCE_KEYS)]
+    pub(in crate::cli) key: String,
+
+    /// The preferred value, e.g. `json` for `output_format`, or `linear` for `search_method`
+    #[clap(value_name = "VALUE")]
+    pub(in crate::cli) value: String,
+}
+
+#[derive(Debug, Args)]
+pub(in crate::cli) struct PolicyOpts {
+    #[clap(subcommand)]
+    pub(in crate::cli) variant: PolicyVariant,
+}
+
+#[derive(Debug, Subcommand)]
+pub(in crate::cli) enum PolicyVariant {
+    /// Validate the MSRV of your crate against the rules of a policy file
+    ///
+    /// Checks the MSRV specified in the Cargo manifest and, if given with '--result', the MSRV
+    /// found by the latest `find` run against the maximum MSRV, the largest allowed bump of the
+    /// minor version, and the minimum number of supported stable releases, as declared by the
+    /// policy file. Fails if any rule is violated.
+    Check(PolicyCheckOpts),
+}
+
+#[derive(Debug, Args)]
+pub(in crate::cli) struct PolicyCheckOpts {
+    /// The policy file, which declares the rules the MSRV must satisfy
+    #[clap(long, value_name = "FILE", default_value = "msrv-policy.toml")]
+    pub(in crate::cli) policy: PathBuf,
+
+    /// The result file of the latest `find` run, as written with '--write-result'
+    #[clap(long, value_name = "FILE")]
+    pub(in crate::cli) result: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+pub(in crate::cli) struct ReportOpts {
+    #[clap(subcommand)]
+    pub(in crate::cli) variant: ReportVariant,
+}
+
+#[derive(Debug, Subcommand)]
+pub(in crate::cli) enum ReportVariant {
+    /// Compare the results of two cargo-msrv runs
+    ///
+    /// The results must be the output of cargo-msrv with `--output-format json`, saved to a file.
+    /// Reports the change of the MSRV, the toolchains which are newly failing (or passing), and
+    /// the configuration which changed between both runs.
+    Diff(ReportDiffOpts),
+}
+
+#[derive(Debug, Args)]
+pub(in crate::cli) struct ReportDiffOpts {
+    /// The json output of the earlier run
+    #[clap(value_name = "OLD")]
+    pub(in crate::cli) old: PathBuf,
+
+    /// The json output of the later run
+    #[clap(value_name = "NEW")]
+    pub(in crate::cli) new: PathBuf,
 }
 
 #[derive(Debug, Args)]
@@ -136,6 +498,35 @@ pub(in crate::cli) struct SetOpts {
     /// `package.rust-version` in the Cargo manifest.
     #[clap(value_name = "MSRV")]
     msrv: BareVersion,
+
+    /// Set the newest patch release of the minor version of the given MSRV instead
+    ///
+    /// E.g. `1.64.1` instead of `1.64.0`, when Rust 1.64.1 exists, so the MSRV points at the
+    /// release with the most recent fixes.
+    #[clap(long)]
+    prefer_newest_patch: bool,
+}
+
+#[derive(Debug, Args)]
+#[clap(next_help_heading = "DISCOVER OPTIONS", setting = AppSettings::DeriveDisplayOrder)]
+pub(in crate::cli) struct DiscoverOpts {
+    /// Run a subcommand for each discovered package
+    ///
+    /// 'find' determines the MSRV of each package, 'verify' verifies the MSRV of each package
+    /// which specifies one. A package which fails does not stop the others from being run.
+    #[clap(long, value_name = "SUBCOMMAND", possible_values = DiscoverRun::variants())]
+    pub(in crate::cli) run: Option<DiscoverRun>,
+}
+
+#[derive(Debug, Args)]
+#[clap(next_help_heading = "SHOW OPTIONS", setting = AppSettings::DeriveDisplayOrder)]
+pub(in crate::cli) struct ShowOpts {
+    /// Write the MSRV of the Cargo manifest to the configuration file of clippy
+    ///
+    /// When 'clippy.toml' or '.clippy.toml' specifies an 'msrv' which differs from the MSRV of
+    /// the Cargo manifest, it is replaced with the MSRV of the Cargo manifest.
+    #[clap(long)]
+    pub(in crate::cli) sync_clippy: bool,
 }
 
 #[derive(Debug, Args)]
@@ -158,6 +549,75 @@ pub(in crate::cli) struct VerifyOpts {
     /// If not set, the MSRV will be parsed from the Cargo manifest instead.
     #[clap(long, value_name = "rust-version")]
     rust_version: Option<BareVersion>,
+
+    /// Verify the MSRV recorded in the given result file, as written by `--write-result`
+    ///
+    /// The check command must be the same as the one with which the MSRV was found.
+    #[clap(long, value_name = "FILE", conflicts_with = "rust-version")]
+    against_result: Option<PathBuf>,
+
+    /// Write the given Rust version to the Cargo manifest, when the check succeeds
+    ///
+    /// The Rust version is only written when it is newer than the MSRV which is currently
+    /// specified in the Cargo manifest (if any).
+    #[clap(long, requires = "rust-version")]
+    write_on_success: bool,
+
+    /// With `--write-on-success`, write the newest patch release of the minor version instead
+    ///
+    /// E.g. `1.64.1` instead of `1.64.0`, when Rust 1.64.1 exists, so the MSRV points at the
+    /// release with the most recent fixes.
+    #[clap(long, requires = "write-on-success")]
+    prefer_newest_patch: bool,
+
+    /// Verify a two component Rust version against its first patch release
+    ///
+    /// By default, a two component version like `1.60` stands for any patch release of Rust 1.60,
+    /// and is verified against the most recent one (e.g. `1.60.2`). With this flag, it is verified
+    /// against exactly `1.60.0` instead.
+    #[clap(long)]
+    pin_patch: bool,
+
+    /// Verify only the workspace members which are affected by the changes since the given git
+    /// revision
+    ///
+    /// A member is affected when one of its files changed, or when a workspace member it depends
+    /// on is affected. Changes to the workspace manifest, lockfile or toolchain file affect every
+    /// member. Each member is verified against its own MSRV.
+    #[clap(
+        long,
+        value_name = "GIT_REF",
+        conflicts_with_all = &["rust-version", "against-result", "write-on-success"]
+    )]
+    changed_since: Option<String>,
+
+    /// Fail when the MSRV in the Cargo manifest differs from the MSRV of the latest version of
+    /// the crate which is published to crates.io
+    ///
+    /// The `rust_version` of the latest published version is fetched from the crates.io API. A
+    /// release which went out with a stale, or missing, MSRV fails the verification.
+    #[clap(long, conflicts_with = "changed-since")]
+    check_published_metadata: bool,
+
+    /// Report the outcome as a GitHub check run on the verified commit
+    ///
+    /// The check run is created through the GitHub REST API, with the token given by
+    /// `--github-token`. When the check fails, the error diagnostics of the compiler are added
+    /// to the check run as annotations.
+    #[clap(long)]
+    github_check: bool,
+
+    /// The repository on which the GitHub check run is created, as `OWNER/REPO`
+    #[clap(long, env = "GITHUB_REPOSITORY", value_name = "OWNER/REPO")]
+    github_repository: Option<String>,
+
+    /// The commit on which the GitHub check run is created
+    #[clap(long, env = "GITHUB_SHA", value_name = "SHA")]
+    github_sha: Option<String>,
+
+    /// The name of the GitHub check run
+    #[clap(long, default_value = "cargo-msrv verify", value_name = "NAME")]
+    github_check_name: String,
 }
 
 // Interpret the CLI config frontend as general Config
@@ -185,26 +645,61 @@ impl<'opts> TryFrom<&'opts CargoMsrvOpts> for Config<'opts> {
 
     fn try_from(opts: &'opts CargoMsrvOpts) -> Result<Self, Self::Error> {
         let mode = make_mode(opts);
-        let target = default_target()?;
+
+        // When rustup can't be run, the default target is left empty: actions which run toolchains
+        // report rustup as a missing prerequisite, and the other actions don't need a target
+        let target = match default_target() {
+            Err(CargoMSRVError::Io {
+                source: IoErrorSource::SpawnProcess(_),
+                ..
+            }) => String::new(),
+            target => target?,
+        };
 
         let mut builder = ConfigBuilder::new(mode, &target);
 
+        // The scratch dir must be configured before the check command, which may write to it
+        builder = configurators::ScratchDir::configure(builder, opts)?;
+        builder = configurators::Setup::configure(builder, opts)?;
+        builder = configurators::Server::configure(builder, opts)?;
         builder = configurators::CustomCheckCommand::configure(builder, opts)?;
         builder = configurators::PathConfig::configure(builder, opts)?;
         builder = configurators::ManifestPathConfig::configure(builder, opts)?;
+        // The worktree must be checked out before the configurators which read the manifest
+        builder = configurators::AtRev::configure(builder, opts)?;
+        // The files are stubbed out in a copy of the crate, or of its checked out revision
+        builder = configurators::ExcludePaths::configure(builder, opts)?;
         builder = configurators::Target::configure(builder, opts)?;
         builder = configurators::MinVersion::configure(builder, opts)?;
         builder = configurators::MaxVersion::configure(builder, opts)?;
+        builder = configurators::ExcludedVersions::configure(builder, opts)?;
+        builder = configurators::ManifestSyntax::configure(builder, opts)?;
+        builder = configurators::VersionBumpPolicy::configure(builder, opts)?;
         builder = configurators::SearchMethodConfig::configure(builder, opts)?;
         builder = configurators::IncludeAllPatchReleases::configure(builder, opts)?;
+        builder = configurators::VersionResolution::configure(builder, opts)?;
         builder = configurators::OutputToolchainFile::configure(builder, opts)?;
         builder = configurators::WriteMsrv::configure(builder, opts)?;
+        builder = configurators::WriteResult::configure(builder, opts)?;
         builder = configurators::IgnoreLockfile::configure(builder, opts)?;
+        builder = configurators::FetchOnce::configure(builder, opts)?;
+        builder = configurators::PrefetchToolchains::configure(builder, opts)?;
+        builder = configurators::Batch::configure(builder, opts)?;
         builder = configurators::UserOutput::configure(builder, opts)?;
+        builder = configurators::Webhook::configure(builder, opts)?;
+        builder = configurators::Otel::configure(builder, opts)?;
         builder = configurators::ReleaseSource::configure(builder, opts)?;
         builder = configurators::Tracing::configure(builder, opts)?;
         builder = configurators::CheckFeedback::configure(builder, opts)?;
         builder = configurators::SubCommandConfigurator::configure(builder, opts)?;
+        // The environment variables only apply to the options which were not given on the command line
+        builder = configurators::EnvFallbacks::configure(builder, opts)?;
+        // The project config files only apply to the options which were not given otherwise
+        builder = configurators::ProjectConfigFiles::configure(builder, opts)?;
+        builder = configurators::UserPreferences::configure(builder, opts)?;
+        // Whether the declared MSRV is needed depends on the search method, which may be preferred
+        builder = configurators::DeclaredMsrv::configure(builder, opts)?;
+        builder = configurators::Provenance::configure(builder, opts)?;
 
         Ok(builder.build())
     }
@@ -214,10 +709,24 @@ fn make_mode(opts: &CargoMsrvOpts) -> Action {
     opts.subcommand
         .as_ref()
         .map(|subcommand| match subcommand {
+            SubCommand::Config(_) => Action::Config,
+            SubCommand::Discover(_) => Action::Discover,
+            SubCommand::Edition(_) => Action::Edition,
+            SubCommand::History(_) => Action::History,
+            SubCommand::Impact(_) => Action::Impact,
+            SubCommand::LastFailure(_) => Action::LastFailure,
             SubCommand::List(_) => Action::List,
-            SubCommand::Show => Action::Show,
+            SubCommand::Lower(_) => Action::Lower,
+            SubCommand::Matrix(_) => Action::Matrix,
+            SubCommand::Policy(_) => Action::Policy,
+            SubCommand::Releases => Action::Releases,
+            SubCommand::Report(_) => Action::Report,
+            SubCommand::Schema(_) => Action::Schema,
+            SubCommand::Serve(_) => Action::Serve,
+            SubCommand::Show(_) => Action::Show,
             SubCommand::Set(_) => Action::Set,
             SubCommand::Verify(_) => Action::Verify,
+            SubCommand::Validate => Action::Validate,
         })
         .unwrap_or_else(|| {
             if opts.verify {
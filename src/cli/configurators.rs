@@ -2,39 +2,77 @@ use crate::cli::CargoMsrvOpts;
 use crate::config::ConfigBuilder;
 use crate::TResult;
 
+mod at_rev;
+mod batch;
+mod bump_policy;
 mod check_feedback;
 mod custom_check;
+mod declared_msrv;
+mod env_fallbacks;
+mod exclude_paths;
+mod excluded_versions;
+mod fetch_once;
 mod ignore_lockfile;
 mod manifest_path;
+mod manifest_syntax;
 mod max_version;
 mod min_version;
+mod otel;
 mod output_toolchain_file;
 mod path;
+mod prefetch_toolchains;
+mod project_config;
+mod provenance;
 mod release_source;
+mod scratch_dir;
 mod search_method;
 mod search_space;
+mod server;
+mod setup;
 mod sub_command_configurator;
 mod target;
 mod tracing_configurator;
 mod user_output;
+mod user_preferences;
+mod webhook;
 mod write_msrv;
+mod write_result;
 
+pub(in crate::cli) use at_rev::AtRev;
+pub(in crate::cli) use batch::Batch;
+pub(in crate::cli) use bump_policy::VersionBumpPolicy;
 pub(in crate::cli) use check_feedback::CheckFeedback;
 pub(in crate::cli) use custom_check::CustomCheckCommand;
+pub(in crate::cli) use declared_msrv::DeclaredMsrv;
+pub(in crate::cli) use env_fallbacks::{EnvFallbacks, FallbackVariables};
+pub(in crate::cli) use exclude_paths::ExcludePaths;
+pub(in crate::cli) use excluded_versions::ExcludedVersions;
+pub(in crate::cli) use fetch_once::FetchOnce;
 pub(in crate::cli) use ignore_lockfile::IgnoreLockfile;
 pub(in crate::cli) use manifest_path::ManifestPathConfig;
+pub(in crate::cli) use manifest_syntax::ManifestSyntax;
 pub(in crate::cli) use max_version::MaxVersion;
 pub(in crate::cli) use min_version::MinVersion;
+pub(in crate::cli) use otel::Otel;
 pub(in crate::cli) use output_toolchain_file::OutputToolchainFile;
 pub(in crate::cli) use path::PathConfig;
+pub(in crate::cli) use prefetch_toolchains::PrefetchToolchains;
+pub(in crate::cli) use project_config::{read_project_config, ProjectConfigFiles};
+pub(in crate::cli) use provenance::Provenance;
 pub(in crate::cli) use release_source::ReleaseSource;
+pub(in crate::cli) use scratch_dir::ScratchDir;
 pub(in crate::cli) use search_method::SearchMethodConfig;
-pub(in crate::cli) use search_space::IncludeAllPatchReleases;
+pub(in crate::cli) use search_space::{IncludeAllPatchReleases, VersionResolution};
+pub(in crate::cli) use server::Server;
+pub(in crate::cli) use setup::Setup;
 pub(in crate::cli) use sub_command_configurator::SubCommandConfigurator;
 pub(in crate::cli) use target::Target;
 pub(in crate::cli) use tracing_configurator::Tracing;
 pub(in crate::cli) use user_output::UserOutput;
+pub(in crate::cli) use user_preferences::UserPreferences;
+pub(in crate::cli) use webhook::Webhook;
 pub(in crate::cli) use write_msrv::WriteMsrv;
+pub(in crate::cli) use write_result::WriteResult;
 
 /// Used to turn the CLI front-end into a flattened Config.
 ///
@@ -0,0 +1,63 @@
+use std::path::Path;
+
+use crate::cli::configurators::Configure;
+use crate::cli::{CargoMsrvOpts, SubCommand};
+use crate::config::ConfigBuilder;
+use crate::error::{CargoMSRVError, IoErrorSource};
+use crate::long_path::sandbox_path;
+use crate::worktree::Worktree;
+use crate::TResult;
+
+pub(in crate::cli) struct AtRev;
+
+impl Configure for AtRev {
+    fn configure<'c>(
+        builder: ConfigBuilder<'c>,
+        opts: &'c CargoMsrvOpts,
+    ) -> TResult<ConfigBuilder<'c>> {
+        let revision = match &opts.shared_opts.at_rev {
+            Some(revision) => revision,
+            None => return Ok(builder),
+        };
+
+        // The worktree is removed once cargo-msrv is done, and with it anything written to it
+        let writes_to_crate = matches!(opts.subcommand, Some(SubCommand::Set(_)))
+            || opts.find_opts.write_msrv
+            || opts.find_opts.write_toolchain_file;
+
+        if writes_to_crate {
+            return Err(CargoMSRVError::InvalidConfig(
+                "--at-rev can't be combined with `set`, --write-msrv or --write-toolchain-file, \
+                since the temporary worktree to which they would write is removed afterwards"
+                    .to_string(),
+            ));
+        }
+
+        let manifest_path = opts.shared_opts.manifest_path.as_deref();
+        let crate_dir = match (opts.shared_opts.path.as_deref(), manifest_path) {
+            (Some(path), _) => path.to_path_buf(),
+            (None, Some(manifest)) => manifest
+                .parent()
+                .filter(|dir| !dir.as_os_str().is_empty())
+                .unwrap_or_else(|| Path::new("."))
+                .to_path_buf(),
+            (None, None) => std::env::current_dir().map_err(|error| CargoMSRVError::Io {
+                error,
+                source: IoErrorSource::CurrentDir,
+            })?,
+        };
+
+        let path = sandbox_path(opts.shared_opts.sandbox_root(), "cargo-msrv-worktree");
+
+        let worktree = Worktree::add(&crate_dir, revision, path)?;
+        let checked_out_dir = worktree.translate(&crate_dir)?;
+        let checked_out_manifest = manifest_path
+            .and_then(Path::file_name)
+            .map(|name| checked_out_dir.join(name));
+
+        Ok(builder
+            .crate_path(Some(checked_out_dir))
+            .manifest_path(checked_out_manifest)
+            .worktree(worktree))
+    }
+}
@@ -0,0 +1,26 @@
+use crate::cli::configurators::Configure;
+use crate::cli::CargoMsrvOpts;
+use crate::config::{BatchOptions, ConfigBuilder};
+use crate::TResult;
+
+pub(in crate::cli) struct Batch;
+
+impl Configure for Batch {
+    fn configure<'c>(
+        builder: ConfigBuilder<'c>,
+        opts: &'c CargoMsrvOpts,
+    ) -> TResult<ConfigBuilder<'c>> {
+        let find_opts = &opts.find_opts;
+
+        if find_opts.batch.is_none() {
+            return Ok(builder);
+        }
+
+        // A minimum version given by the user applies to every crate, so only read the edition of
+        // each crate when none was given
+        let read_min_edition =
+            find_opts.rust_releases_opts.min.is_none() && !find_opts.no_read_min_edition;
+
+        Ok(builder.batch(BatchOptions::new(read_min_edition)))
+    }
+}
@@ -1,8 +1,15 @@
+use std::path::{Path, PathBuf};
+
 use crate::cli::configurators::Configure;
-use crate::cli::find_opts::FindOpts;
-use crate::cli::{CargoMsrvOpts, SubCommand, VerifyOpts};
-use crate::config::ConfigBuilder;
-use crate::TResult;
+use crate::cli::custom_check_opts::CustomCheckOpts;
+use crate::cli::{CargoMsrvOpts, SubCommand};
+use crate::config::{
+    CargoFlag, CheckHooks, CheckTargets, ConfigBuilder, DependencyKind, DocTests, PackageSelection,
+};
+use crate::error::IoErrorSource;
+use crate::long_path::long_path;
+use crate::manifest::manifest_patch::ManifestPatch;
+use crate::{CargoMSRVError, TResult};
 
 pub(in crate::cli) struct CustomCheckCommand;
 
@@ -11,48 +18,184 @@ impl Configure for CustomCheckCommand {
         builder: ConfigBuilder<'c>,
         opts: &'c CargoMsrvOpts,
     ) -> TResult<ConfigBuilder<'c>> {
-        fn configure_from_verify<'c>(
-            builder: ConfigBuilder<'c>,
-            opts: &'c VerifyOpts,
-        ) -> ConfigBuilder<'c> {
-            if opts.custom_check.custom_check_command.is_empty() {
-                return builder;
+        match &opts.subcommand {
+            Some(SubCommand::Verify(verify)) => configure_from_opts(builder, &verify.custom_check),
+            // The matrix runs the search of `find` for each configuration
+            None | Some(SubCommand::Matrix(_)) => {
+                configure_from_opts(builder, &opts.find_opts.custom_check_opts)
             }
+            _ => Ok(builder),
+        }
+    }
+}
 
-            let cmd = opts
-                .custom_check
-                .custom_check_command
-                .iter()
-                .map(|s| s.as_str())
-                .collect();
+fn configure_from_opts<'c>(
+    builder: ConfigBuilder<'c>,
+    opts: &'c CustomCheckOpts,
+) -> TResult<ConfigBuilder<'c>> {
+    let builder = builder
+        .check_hooks(CheckHooks::new(
+            opts.before_check.clone(),
+            opts.after_check.clone(),
+        ))
+        .doc_tests(doc_tests(opts))
+        .dependency_kinds(dependency_kinds(opts)?)
+        .retries_on_failure(opts.retries_on_failure)
+        .reuse_unit_graph(opts.reuse_unit_graph)
+        .verbose(opts.verbose)
+        .manifest_patch(manifest_patch(opts)?)
+        .clean_after(opts.clean_after);
 
-            builder.check_command(cmd)
-        }
+    if let Some(check_with) = &opts.check_with {
+        let file = rustc_file(check_with)?;
+        return Ok(builder.check_with_rustc(&file));
+    }
 
-        fn configure_from_find<'c>(
-            builder: ConfigBuilder<'c>,
-            opts: &'c FindOpts,
-        ) -> ConfigBuilder<'c> {
-            if opts.custom_check_opts.custom_check_command.is_empty() {
-                return builder;
-            }
+    if opts.custom_check_command.is_empty() {
+        let features = opts
+            .profile
+            .map(|profile| profile.features())
+            .unwrap_or_default();
 
-            let cmd = opts
-                .custom_check_opts
-                .custom_check_command
-                .iter()
-                .map(|s| s.as_str())
-                .collect();
+        return Ok(builder
+            .check_targets(opts.check_targets)
+            .features(features)
+            .packages(package_selection(opts))
+            .cargo_flags(cargo_flags(opts))
+            .target_dir(target_dir(opts)?)
+            .bin_policy(opts.bin_policy));
+    }
 
-            builder.check_command(cmd)
+    let cmd = opts
+        .custom_check_command
+        .iter()
+        .map(|s| s.as_str())
+        .collect();
+
+    Ok(builder.check_command(cmd))
+}
+
+/// The flags given with `--cargo-flag`, followed by those given with their shorthand, like
+/// `--locked`, without duplicates.
+fn cargo_flags(opts: &CustomCheckOpts) -> Vec<CargoFlag> {
+    let shorthands = [
+        (opts.locked, CargoFlag::Locked),
+        (opts.frozen, CargoFlag::Frozen),
+        (opts.offline, CargoFlag::Offline),
+    ];
+
+    let mut flags = opts.cargo_flags.clone();
+
+    for (_, flag) in shorthands.iter().filter(|(given, _)| *given) {
+        if !flags.contains(flag) {
+            flags.push(*flag);
         }
+    }
+
+    flags
+}
+
+fn package_selection(opts: &CustomCheckOpts) -> PackageSelection {
+    if !opts.packages.is_empty() {
+        PackageSelection::Packages(opts.packages.clone())
+    } else if !opts.excluded_packages.is_empty() {
+        PackageSelection::Exclude(opts.excluded_packages.clone())
+    } else {
+        PackageSelection::Default
+    }
+}
 
-        let builder = match &opts.subcommand {
-            Some(SubCommand::Verify(verify)) => configure_from_verify(builder, verify),
-            None => configure_from_find(builder, &opts.find_opts),
-            _ => builder,
-        };
+fn doc_tests(opts: &CustomCheckOpts) -> DocTests {
+    if opts.run_doc_tests {
+        DocTests::Run
+    } else if opts.check_doc_tests {
+        DocTests::Build
+    } else {
+        DocTests::Skip
+    }
+}
+
+/// The kinds of dependencies which take part in the check. A check which compiles targets that
+/// depend on the dev-dependencies, like tests or doc tests, can't leave them out. Otherwise, they
+/// are left out with a check profile.
+fn dependency_kinds(opts: &CustomCheckOpts) -> TResult<Vec<DependencyKind>> {
+    let kinds = &opts.dep_kinds;
+
+    if !kinds.contains(&DependencyKind::Normal) {
+        return Err(CargoMSRVError::InvalidConfig(
+            "Unable to leave out the normal dependencies with --dep-kinds, since the crate can't be checked without them"
+                .to_string(),
+        ));
+    }
+
+    let requires_dev_dependencies = (opts.custom_check_command.is_empty()
+        && opts.check_targets != CheckTargets::Lib)
+        || opts.check_doc_tests
+        || opts.run_doc_tests;
+
+    if !kinds.contains(&DependencyKind::Dev) && requires_dev_dependencies {
+        return Err(CargoMSRVError::InvalidConfig(
+            "Unable to leave out the dev-dependencies with --dep-kinds, since the checked targets or doc tests depend on them"
+                .to_string(),
+        ));
+    }
+
+    if opts.profile.is_some() && !requires_dev_dependencies {
+        return Ok(kinds
+            .iter()
+            .filter(|&&kind| kind != DependencyKind::Dev)
+            .copied()
+            .collect());
+    }
 
-        Ok(builder)
+    Ok(kinds.clone())
+}
+
+/// The target directory given with `--target-dir`. The check may run in another directory than the
+/// current one, e.g. with `--path`, so a relative path is made absolute.
+fn target_dir(opts: &CustomCheckOpts) -> TResult<Option<String>> {
+    let dir = match &opts.target_dir {
+        Some(dir) if dir.is_relative() => std::env::current_dir()
+            .map_err(|error| CargoMSRVError::Io {
+                error,
+                source: IoErrorSource::CurrentDir,
+            })?
+            .join(dir),
+        Some(dir) => dir.clone(),
+        None => return Ok(None),
+    };
+
+    Ok(Some(long_path(&dir).display().to_string()))
+}
+
+/// The patch given with `--manifest-patch`, which is read once, so an invalid patch is reported
+/// before any toolchain is checked.
+fn manifest_patch(opts: &CustomCheckOpts) -> TResult<Option<ManifestPatch>> {
+    opts.manifest_patch
+        .as_deref()
+        .map(ManifestPatch::load)
+        .transpose()
+}
+
+/// The file to compile, given as `--check-with rustc <FILE>`.
+fn rustc_file(check_with: &[String]) -> TResult<PathBuf> {
+    match check_with {
+        [tool, file] if tool == "rustc" => {
+            let file = Path::new(file);
+
+            // The check may run in another directory than the current one, e.g. with --path
+            std::fs::canonicalize(file).map_err(|error| CargoMSRVError::Io {
+                error,
+                source: IoErrorSource::OpenFile(file.to_path_buf()),
+            })
+        }
+        [tool, _] => Err(CargoMSRVError::InvalidConfig(format!(
+            "Unable to check with '{}', the only supported tool is 'rustc'",
+            tool
+        ))),
+        _ => Err(CargoMSRVError::InvalidConfig(
+            "Expected a tool and a file for --check-with, e.g. '--check-with rustc snippet.rs'"
+                .to_string(),
+        )),
     }
 }
@@ -0,0 +1,46 @@
+use crate::cli::configurators::min_version::find_manifest;
+use crate::cli::configurators::Configure;
+use crate::cli::CargoMsrvOpts;
+use crate::config::{Action, ConfigBuilder, SearchMethod};
+use crate::manifest::read_declared_msrv;
+use crate::TResult;
+
+pub(in crate::cli) struct DeclaredMsrv;
+
+impl Configure for DeclaredMsrv {
+    fn configure<'c>(
+        builder: ConfigBuilder<'c>,
+        opts: &'c CargoMsrvOpts,
+    ) -> TResult<ConfigBuilder<'c>> {
+        // The declared MSRV is the start of the `auto` search, and the version from which `impact`
+        // and `lower` depart; other commands don't need it
+        let is_needed = match builder.get_action() {
+            Action::Find => builder.get_search_method() == SearchMethod::Auto,
+            Action::Impact | Action::Lower => true,
+            _ => false,
+        };
+        // Without a Cargo manifest, there is no declared MSRV to start the search at
+        let checks_with_rustc = opts.find_opts.custom_check_opts.check_with.is_some();
+        // In batch mode, the manifest of each crate is read instead
+        let is_batch = opts.find_opts.batch.is_some();
+
+        if !is_needed || checks_with_rustc || is_batch {
+            return Ok(builder);
+        }
+
+        let manifest = find_manifest(&builder)?;
+
+        if !manifest.is_file() {
+            return Ok(builder);
+        }
+
+        // The declared MSRV is a hint, so a manifest from which it can't be read is not fatal
+        match read_declared_msrv(&manifest) {
+            Ok(msrv) => Ok(builder.declared_msrv(msrv)),
+            Err(error) => {
+                warn!(manifest = %manifest.display(), %error, "unable to read the declared MSRV");
+                Ok(builder)
+            }
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use crate::cli::configurators::provenance::flags_of;
+use crate::cli::configurators::Configure;
+use crate::cli::{CargoMsrvOpts, SubCommand};
+use crate::config::value_source::ValueSource;
+use crate::config::ConfigBuilder;
+use crate::manifest::bare_version::BareVersion;
+use crate::{CargoMSRVError, TResult};
+
+const VERIFY_VERSION: &str = "CARGO_MSRV_VERIFY_VERSION";
+const TARGET: &str = "CARGO_MSRV_TARGET";
+const CHECK_CMD: &str = "CARGO_MSRV_CHECK_CMD";
+
+/// The environment variables which `verify` falls back to, for the options which were not given
+/// on the command line. Captured when the command line is parsed, since the check command borrows
+/// from the parsed options.
+#[derive(Debug, Default)]
+pub(in crate::cli) struct FallbackVariables {
+    verify_version: Option<String>,
+    target: Option<String>,
+    /// The check command, split on whitespace; quoting is not supported
+    check_command: Vec<String>,
+}
+
+impl FallbackVariables {
+    pub(in crate::cli) fn from_env() -> Self {
+        Self::from_lookup(|variable| std::env::var(variable).ok())
+    }
+
+    /// Like clap, a variable which is set to an empty value is treated as if it was not set.
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let value = |variable: &str| lookup(variable).filter(|value| !value.trim().is_empty());
+
+        Self {
+            verify_version: value(VERIFY_VERSION),
+            target: value(TARGET),
+            check_command: value(CHECK_CMD)
+                .map(|cmd| cmd.split_whitespace().map(String::from).collect())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Whether a check command is given by the environment.
+    pub(in crate::cli) fn has_check_command(&self) -> bool {
+        !self.check_command.is_empty()
+    }
+}
+
+/// Applies the [`FallbackVariables`] to `verify`. Options given on the command line take
+/// precedence over the environment variables, which in turn take precedence over the MSRV
+/// declared in the Cargo manifest.
+pub(in crate::cli) struct EnvFallbacks;
+
+impl Configure for EnvFallbacks {
+    fn configure<'c>(
+        mut builder: ConfigBuilder<'c>,
+        opts: &'c CargoMsrvOpts,
+    ) -> TResult<ConfigBuilder<'c>> {
+        let verify = match &opts.subcommand {
+            Some(SubCommand::Verify(verify)) => verify,
+            _ => return Ok(builder),
+        };
+
+        let variables = &opts.fallback_variables;
+        let source = |variable: &str| ValueSource::Environment {
+            variable: variable.to_string(),
+        };
+
+        if let Some(version) = &variables.verify_version {
+            if verify.rust_version.is_none() && verify.against_result.is_none() {
+                let version = version.parse::<BareVersion>().map_err(|err| {
+                    CargoMSRVError::InvalidConfig(format!(
+                        "Unable to parse the Rust version '{}' given by the {} environment variable: {}",
+                        version, VERIFY_VERSION, err
+                    ))
+                })?;
+
+                builder = builder.verify_rust_version(version);
+            }
+        }
+
+        if let Some(target) = &variables.target {
+            let given = verify.toolchain_opts.target.is_some()
+                || opts.find_opts.toolchain_opts.target.is_some();
+
+            if !given {
+                builder = builder
+                    .target(target.as_str())
+                    .value_source("target", source(TARGET));
+            }
+        }
+
+        if !variables.check_command.is_empty() {
+            let flags = flags_of("check_command");
+            let given = !verify.custom_check.custom_check_command.is_empty()
+                || opts
+                    .given_flags
+                    .iter()
+                    .any(|given| flags.contains(&given.as_str()));
+
+            if !given {
+                let cmd = variables.check_command.iter().map(|s| s.as_str()).collect();
+
+                builder = builder
+                    .check_command(cmd)
+                    .value_source("check_command", source(CHECK_CMD));
+            }
+        }
+
+        Ok(builder)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_variables() {
+        let variables = FallbackVariables::from_lookup(|variable| match variable {
+            VERIFY_VERSION => Some("1.56".to_string()),
+            TARGET => Some("x86_64-unknown-linux-musl".to_string()),
+            CHECK_CMD => Some("cargo  test --all-features".to_string()),
+            _ => None,
+        });
+
+        assert_eq!(variables.verify_version.as_deref(), Some("1.56"));
+        assert_eq!(
+            variables.target.as_deref(),
+            Some("x86_64-unknown-linux-musl")
+        );
+        assert_eq!(
+            variables.check_command,
+            vec!["cargo", "test", "--all-features"]
+        );
+    }
+
+    #[test]
+    fn empty_variables_are_unset() {
+        let variables = FallbackVariables::from_lookup(|_| Some(" ".to_string()));
+
+        assert_eq!(variables.verify_version, None);
+        assert_eq!(variables.target, None);
+        assert!(variables.check_command.is_empty());
+    }
+}
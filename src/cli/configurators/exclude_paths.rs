@@ -0,0 +1,76 @@
+use std::path::Path;
+
+use crate::cli::configurators::Configure;
+use crate::cli::{CargoMsrvOpts, SubCommand};
+use crate::config::ConfigBuilder;
+use crate::error::{CargoMSRVError, IoErrorSource};
+use crate::long_path::sandbox_path;
+use crate::stubbed_copy::StubbedCopy;
+use crate::TResult;
+
+pub(in crate::cli) struct ExcludePaths;
+
+impl Configure for ExcludePaths {
+    fn configure<'c>(
+        builder: ConfigBuilder<'c>,
+        opts: &'c CargoMsrvOpts,
+    ) -> TResult<ConfigBuilder<'c>> {
+        let (patterns, writes_to_crate) = match &opts.subcommand {
+            Some(SubCommand::Verify(verify)) => {
+                (&verify.custom_check.excluded_paths, verify.write_on_success)
+            }
+            None => (
+                &opts.find_opts.custom_check_opts.excluded_paths,
+                opts.find_opts.write_msrv || opts.find_opts.write_toolchain_file,
+            ),
+            _ => return Ok(builder),
+        };
+
+        if patterns.is_empty() {
+            return Ok(builder);
+        }
+
+        // The copy is removed once cargo-msrv is done, and with it anything written to it
+        if writes_to_crate {
+            return Err(CargoMSRVError::InvalidConfig(
+                "--exclude-path can't be combined with --write-on-success, --write-msrv or \
+                --write-toolchain-file, since the temporary copy of the crate to which they would \
+                write is removed afterwards"
+                    .to_string(),
+            ));
+        }
+
+        let manifest_path = builder.get_manifest_path().map(Path::to_path_buf);
+        let crate_dir = match (builder.get_crate_path(), manifest_path.as_deref()) {
+            (Some(path), _) => path.to_path_buf(),
+            (None, Some(manifest)) => manifest
+                .parent()
+                .filter(|dir| !dir.as_os_str().is_empty())
+                .unwrap_or_else(|| Path::new("."))
+                .to_path_buf(),
+            (None, None) => std::env::current_dir().map_err(|error| CargoMSRVError::Io {
+                error,
+                source: IoErrorSource::CurrentDir,
+            })?,
+        };
+
+        let path = sandbox_path(opts.shared_opts.sandbox_root(), "cargo-msrv-stubbed");
+
+        let copy = StubbedCopy::create(&crate_dir, patterns, path)?;
+        info!(
+            copy = %copy.crate_dir().display(),
+            stubbed = ?copy.stubbed(),
+            "checking a stubbed copy of the crate"
+        );
+
+        let copied_manifest = manifest_path
+            .as_deref()
+            .and_then(Path::file_name)
+            .map(|name| copy.crate_dir().join(name));
+
+        Ok(builder
+            .crate_path(Some(copy.crate_dir().to_path_buf()))
+            .manifest_path(copied_manifest)
+            .stubbed_copy(copy))
+    }
+}
@@ -0,0 +1,50 @@
+use crate::cli::configurators::min_version::find_manifest;
+use crate::cli::configurators::Configure;
+use crate::cli::CargoMsrvOpts;
+use crate::config::value_source::ValueSource;
+use crate::config::ConfigBuilder;
+use crate::manifest::read_excluded_versions;
+use crate::TResult;
+
+pub(in crate::cli) struct ExcludedVersions;
+
+impl Configure for ExcludedVersions {
+    fn configure<'c>(
+        builder: ConfigBuilder<'c>,
+        opts: &'c CargoMsrvOpts,
+    ) -> TResult<ConfigBuilder<'c>> {
+        let mut excluded = opts.find_opts.rust_releases_opts.excluded_versions.clone();
+
+        // Without a Cargo manifest, there are no versions to read from its metadata
+        let checks_with_rustc = opts.find_opts.custom_check_opts.check_with.is_some();
+        let manifest = find_manifest(&builder)?;
+
+        if checks_with_rustc || !manifest.is_file() {
+            return Ok(builder.excluded_versions(excluded));
+        }
+
+        let from_manifest = read_excluded_versions(&manifest)?;
+
+        // The versions of the manifest are only the source of the value, when none were given
+        // on the command line
+        let builder = if excluded.is_empty() && !from_manifest.is_empty() {
+            builder.value_source(
+                "excluded_versions",
+                ValueSource::Manifest {
+                    path: manifest,
+                    key: "package.metadata.cargo-msrv.exclude-versions".to_string(),
+                },
+            )
+        } else {
+            builder
+        };
+
+        for version in from_manifest {
+            if !excluded.contains(&version) {
+                excluded.push(version);
+            }
+        }
+
+        Ok(builder.excluded_versions(excluded))
+    }
+}
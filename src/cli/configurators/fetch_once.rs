@@ -0,0 +1,15 @@
+use crate::cli::configurators::Configure;
+use crate::cli::CargoMsrvOpts;
+use crate::config::ConfigBuilder;
+use crate::TResult;
+
+pub(in crate::cli) struct FetchOnce;
+
+impl Configure for FetchOnce {
+    fn configure<'c>(
+        builder: ConfigBuilder<'c>,
+        opts: &'c CargoMsrvOpts,
+    ) -> TResult<ConfigBuilder<'c>> {
+        Ok(builder.fetch_once(opts.find_opts.fetch_once))
+    }
+}
@@ -10,6 +10,8 @@ impl Configure for IgnoreLockfile {
         builder: ConfigBuilder<'c>,
         opts: &'c CargoMsrvOpts,
     ) -> TResult<ConfigBuilder<'c>> {
-        Ok(builder.ignore_lockfile(opts.find_opts.ignore_lockfile))
+        Ok(builder
+            .ignore_lockfile(opts.find_opts.ignore_lockfile)
+            .lockfile_policy(opts.find_opts.lockfile_policy))
     }
 }
@@ -0,0 +1,29 @@
+use crate::cli::configurators::min_version::find_manifest;
+use crate::cli::configurators::Configure;
+use crate::cli::CargoMsrvOpts;
+use crate::config::ConfigBuilder;
+use crate::manifest::syntax;
+use crate::TResult;
+
+pub(in crate::cli) struct ManifestSyntax;
+
+impl Configure for ManifestSyntax {
+    fn configure<'c>(
+        builder: ConfigBuilder<'c>,
+        opts: &'c CargoMsrvOpts,
+    ) -> TResult<ConfigBuilder<'c>> {
+        // Without a Cargo manifest, there is no syntax which cargo has to read
+        let checks_with_rustc = opts.find_opts.custom_check_opts.check_with.is_some();
+        // In batch mode, the manifest of each crate is read instead
+        let is_batch = opts.find_opts.batch.is_some();
+        let manifest = find_manifest(&builder)?;
+
+        if checks_with_rustc || is_batch || !manifest.is_file() {
+            return Ok(builder);
+        }
+
+        let requirement = syntax::probe(&manifest)?;
+
+        Ok(builder.manifest_requirement(requirement))
+    }
+}
@@ -1,7 +1,8 @@
 use crate::cli::configurators::Configure;
 use crate::cli::CargoMsrvOpts;
 use crate::config::ConfigBuilder;
-use crate::TResult;
+use crate::release_schedule::{last_release_until, release_date};
+use crate::{CargoMSRVError, TResult};
 
 pub(in crate::cli) struct MaxVersion;
 
@@ -10,8 +11,20 @@ impl Configure for MaxVersion {
         builder: ConfigBuilder<'c>,
         opts: &'c CargoMsrvOpts,
     ) -> TResult<ConfigBuilder<'c>> {
-        if let Some(max) = &opts.find_opts.rust_releases_opts.max {
+        let rust_releases_opts = &opts.find_opts.rust_releases_opts;
+
+        if let Some(max) = &rust_releases_opts.max {
             Ok(builder.maximum_version(max.clone()))
+        } else if let Some(date) = rust_releases_opts.max_date {
+            let version = last_release_until(date).ok_or_else(|| {
+                CargoMSRVError::InvalidConfig(format!(
+                    "No Rust release was released on or before {}, the first release is Rust 1.0.0 ({})",
+                    date,
+                    release_date(0)
+                ))
+            })?;
+
+            Ok(builder.maximum_version(version))
         } else {
             Ok(builder)
         }
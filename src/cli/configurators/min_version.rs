@@ -1,10 +1,12 @@
 use crate::cli::configurators::Configure;
 use crate::cli::CargoMsrvOpts;
+use crate::config::value_source::ValueSource;
 use crate::config::ConfigBuilder;
 use std::path;
 use std::path::PathBuf;
 
-use crate::cli::rust_releases_opts::Edition;
+use crate::manifest::edition_minimum_version;
+use crate::release_schedule::first_release_since;
 use crate::{CargoMSRVError, TResult};
 
 pub(in crate::cli) struct MinVersion;
@@ -14,9 +16,13 @@ impl Configure for MinVersion {
         builder: ConfigBuilder<'c>,
         opts: &'c CargoMsrvOpts,
     ) -> TResult<ConfigBuilder<'c>> {
-        if let Some(v) = &opts.find_opts.rust_releases_opts.min {
+        let rust_releases_opts = &opts.find_opts.rust_releases_opts;
+
+        if let Some(v) = &rust_releases_opts.min {
             let version = v.as_bare_version();
             Ok(builder.minimum_version(version))
+        } else if let Some(date) = rust_releases_opts.min_date {
+            Ok(builder.minimum_version(first_release_since(date)))
         } else {
             configure_min_version_not_as_opt(builder, opts)
         }
@@ -27,7 +33,12 @@ fn configure_min_version_not_as_opt<'c>(
     builder: ConfigBuilder<'c>,
     opts: &'c CargoMsrvOpts,
 ) -> TResult<ConfigBuilder<'c>> {
-    if opts.find_opts.no_read_min_edition {
+    // Without a Cargo manifest, there is no edition to read the minimum version from
+    let checks_with_rustc = opts.find_opts.custom_check_opts.check_with.is_some();
+    // In batch mode, the edition is read from the manifest of each crate instead
+    let is_batch = opts.find_opts.batch.is_some();
+
+    if opts.find_opts.no_read_min_edition || checks_with_rustc || is_batch {
         Ok(builder)
     } else {
         let manifest = find_manifest(&builder)?;
@@ -38,7 +49,7 @@ fn configure_min_version_not_as_opt<'c>(
 // TODO{foresterre}: finding Cargo manifest should not be task of configurator fn
 //  And probably, we'll want to read the manifest at most once, instead of here and elsewhere during
 //  the program execution.
-fn find_manifest(builder: &ConfigBuilder) -> TResult<PathBuf> {
+pub(super) fn find_manifest(builder: &ConfigBuilder) -> TResult<PathBuf> {
     use crate::error::IoErrorSource;
 
     let crate_folder = if let Some(path) = builder.get_crate_path() {
@@ -53,33 +64,21 @@ fn find_manifest(builder: &ConfigBuilder) -> TResult<PathBuf> {
     Ok(crate_folder.join("Cargo.toml"))
 }
 
-// TODO{foresterre}: reading and parsing of manifest should not be task of configurator fn
 fn set_min_version_from_manifest<'c>(
     builder: ConfigBuilder<'c>,
     cargo_toml: &path::Path,
 ) -> TResult<ConfigBuilder<'c>> {
-    use crate::error::IoErrorSource;
-    use toml_edit::Document;
-    use toml_edit::Item;
-
-    let contents = std::fs::read_to_string(cargo_toml).map_err(|error| CargoMSRVError::Io {
-        error,
-        source: IoErrorSource::ReadFile(cargo_toml.to_path_buf()),
-    })?;
-    let document = contents
-        .parse::<Document>()
-        .map_err(CargoMSRVError::ParseToml)?;
+    match edition_minimum_version(cargo_toml)? {
+        Some(version) => {
+            let source = ValueSource::Manifest {
+                path: cargo_toml.to_path_buf(),
+                key: "package.edition".to_string(),
+            };
 
-    if let Some(edition) = document
-        .as_table()
-        .get("package")
-        .and_then(Item::as_table)
-        .and_then(|package_table| package_table.get("edition"))
-        .and_then(Item::as_str)
-    {
-        let edition = edition.parse::<Edition>()?;
-        Ok(builder.minimum_version(edition.as_bare_version()))
-    } else {
-        Ok(builder)
+            Ok(builder
+                .minimum_version(version)
+                .value_source("minimum_version", source))
+        }
+        None => Ok(builder),
     }
 }
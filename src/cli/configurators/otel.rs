@@ -0,0 +1,17 @@
+use crate::cli::configurators::Configure;
+use crate::cli::CargoMsrvOpts;
+use crate::config::ConfigBuilder;
+use crate::TResult;
+
+pub(in crate::cli) struct Otel;
+
+impl Configure for Otel {
+    fn configure<'c>(
+        builder: ConfigBuilder<'c>,
+        opts: &'c CargoMsrvOpts,
+    ) -> TResult<ConfigBuilder<'c>> {
+        let endpoint = &opts.shared_opts.user_output_opts.otel_endpoint;
+
+        Ok(builder.otel_endpoint(endpoint.clone()))
+    }
+}
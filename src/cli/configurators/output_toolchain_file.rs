@@ -10,6 +10,8 @@ impl Configure for OutputToolchainFile {
         builder: ConfigBuilder<'c>,
         opts: &'c CargoMsrvOpts,
     ) -> TResult<ConfigBuilder<'c>> {
-        Ok(builder.output_toolchain_file(opts.find_opts.write_toolchain_file))
+        Ok(builder
+            .output_toolchain_file(opts.find_opts.write_toolchain_file)
+            .toolchain_file_format(opts.find_opts.toolchain_file_format))
     }
 }
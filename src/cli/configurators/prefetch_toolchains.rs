@@ -0,0 +1,15 @@
+use crate::cli::configurators::Configure;
+use crate::cli::CargoMsrvOpts;
+use crate::config::ConfigBuilder;
+use crate::TResult;
+
+pub(in crate::cli) struct PrefetchToolchains;
+
+impl Configure for PrefetchToolchains {
+    fn configure<'c>(
+        builder: ConfigBuilder<'c>,
+        opts: &'c CargoMsrvOpts,
+    ) -> TResult<ConfigBuilder<'c>> {
+        Ok(builder.prefetch_toolchains(opts.find_opts.prefetch_toolchains))
+    }
+}
@@ -0,0 +1,126 @@
+use std::path::{Path, PathBuf};
+
+use crate::cli::configurators::provenance::flags_of;
+use crate::cli::configurators::Configure;
+use crate::cli::{CargoMsrvOpts, SubCommand};
+use crate::config::project_config::ProjectConfig;
+use crate::config::value_source::ValueSource;
+use crate::config::{ConfigBuilder, PackageSelection};
+use crate::error::IoErrorSource;
+use crate::{CargoMSRVError, TResult};
+
+/// Read the project config files which apply to the crate. Read when the command line is parsed,
+/// since the check command borrows from the parsed options.
+pub(in crate::cli) fn read_project_config(opts: &CargoMsrvOpts) -> Option<TResult<ProjectConfig>> {
+    // In batch mode, each crate may belong to another workspace
+    if opts.find_opts.batch.is_some() {
+        return None;
+    }
+
+    Some(crate_dir(opts).and_then(|dir| ProjectConfig::resolve(&dir)))
+}
+
+/// The directory of the crate, made absolute, so the root of its workspace can be found.
+fn crate_dir(opts: &CargoMsrvOpts) -> TResult<PathBuf> {
+    let shared_opts = &opts.shared_opts;
+
+    let current_dir = std::env::current_dir().map_err(|error| CargoMSRVError::Io {
+        error,
+        source: IoErrorSource::CurrentDir,
+    })?;
+
+    let dir = shared_opts.path.as_deref().or_else(|| {
+        shared_opts
+            .manifest_path
+            .as_deref()
+            .and_then(Path::parent)
+            .filter(|dir| !dir.as_os_str().is_empty())
+    });
+
+    Ok(match dir {
+        Some(dir) => current_dir.join(dir),
+        None => current_dir,
+    })
+}
+
+/// Applies the settings of the project config files. Options given on the command line, or by an
+/// environment variable, take precedence over them.
+pub(in crate::cli) struct ProjectConfigFiles;
+
+impl Configure for ProjectConfigFiles {
+    fn configure<'c>(
+        mut builder: ConfigBuilder<'c>,
+        opts: &'c CargoMsrvOpts,
+    ) -> TResult<ConfigBuilder<'c>> {
+        let config = match &opts.project_config {
+            Some(Ok(config)) => config,
+            Some(Err(error)) => return Err(CargoMSRVError::InvalidConfig(error.to_string())),
+            None => return Ok(builder),
+        };
+
+        let is_given = |flags: &[&str]| {
+            opts.given_flags
+                .iter()
+                .any(|given| flags.contains(&given.as_str()))
+        };
+
+        let source = |path: &Path, key: &str| ValueSource::ConfigFile {
+            path: path.to_path_buf(),
+            key: key.to_string(),
+        };
+
+        let (custom_check_command, env_check_command) = match &opts.subcommand {
+            Some(SubCommand::Verify(verify)) => (
+                &verify.custom_check.custom_check_command,
+                opts.fallback_variables.has_check_command(),
+            ),
+            _ => (
+                &opts.find_opts.custom_check_opts.custom_check_command,
+                false,
+            ),
+        };
+
+        let is_custom_check_command = !custom_check_command.is_empty() || env_check_command;
+
+        let mut is_configured_check_command = false;
+
+        if let Some(command) = config.check_command() {
+            if !is_custom_check_command && !is_given(flags_of("check_command")) {
+                is_configured_check_command = true;
+                builder = builder
+                    .check_command(command.value().iter().map(String::as_str).collect())
+                    .value_source("check_command", source(command.path(), "check_command"));
+            }
+        }
+
+        if let Some(members) = config.excluded_members() {
+            // Like --exclude, the members are only left out by the default check command
+            if !is_custom_check_command && !is_given(&["--check-with", "--package", "--exclude"]) {
+                builder = builder.packages(PackageSelection::Exclude(members.value().clone()));
+
+                if !is_configured_check_command {
+                    let source = source(members.path(), "excluded_members");
+                    builder = builder.value_source("check_command", source);
+                }
+            }
+        }
+
+        if let Some(version) = config.minimum_version() {
+            if !is_given(flags_of("minimum_version")) {
+                builder = builder
+                    .minimum_version(version.value().clone())
+                    .value_source("minimum_version", source(version.path(), "minimum_version"));
+            }
+        }
+
+        if let Some(version) = config.maximum_version() {
+            if !is_given(flags_of("maximum_version")) {
+                builder = builder
+                    .maximum_version(version.value().clone())
+                    .value_source("maximum_version", source(version.path(), "maximum_version"));
+            }
+        }
+
+        Ok(builder.config_files(config.files().to_vec()))
+    }
+}
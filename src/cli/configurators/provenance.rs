@@ -0,0 +1,176 @@
+use crate::cli::configurators::Configure;
+use crate::cli::{CargoMsrvOpts, SubCommand};
+use crate::config::value_source::ValueSource;
+use crate::config::ConfigBuilder;
+use crate::TResult;
+
+pub(in crate::cli) struct Provenance;
+
+/// The configuration keys, with the flags and the environment variable by which they can be set.
+const OPTIONS: &[(&str, &[&str], Option<&str>)] = &[
+    ("target", &["--target"], None),
+    ("host", &["--host"], None),
+    ("check_profile", &["--profile"], None),
+    ("runner", &["--runner"], None),
+    ("install_profile", &["--install-profile"], None),
+    ("remote_check", &["--remote-check"], None),
+    ("rustc_wrapper", &["--rustc-wrapper"], None),
+    (
+        "check_command",
+        &["--check-with", "--check-targets", "--package", "--exclude"],
+        None,
+    ),
+    (
+        "cargo_flags",
+        &["--cargo-flag", "--locked", "--frozen", "--offline"],
+        None,
+    ),
+    ("bin_policy", &["--bin-policy"], None),
+    ("dependency_kinds", &["--dep-kinds"], None),
+    ("doc_tests", &["--check-doc-tests", "--run-doc-tests"], None),
+    ("retries_on_failure", &["--retries-on-failure"], None),
+    ("reuse_unit_graph", &["--reuse-unit-graph"], None),
+    ("verbose", &["--verbose"], None),
+    ("manifest_patch", &["--manifest-patch"], None),
+    ("target_dir", &["--target-dir"], None),
+    ("clean_after", &["--clean-after"], None),
+    ("before_check", &["--before-check"], None),
+    ("after_check", &["--after-check"], None),
+    ("path", &["--path"], None),
+    ("manifest_path", &["--manifest-path"], None),
+    ("at_rev", &["--at-rev"], None),
+    ("exclude_paths", &["--exclude-path"], None),
+    (
+        "include_all_patch_releases",
+        &["--include-all-patch-releases"],
+        None,
+    ),
+    ("version_resolution", &["--version-resolution"], None),
+    (
+        "minimum_version",
+        &["--min", "--minimum", "--min-date"],
+        None,
+    ),
+    (
+        "maximum_version",
+        &["--max", "--maximum", "--max-date"],
+        None,
+    ),
+    ("excluded_versions", &["--exclude-version"], None),
+    (
+        "search_method",
+        &["--search-method", "--bisect", "--linear", "--exhaustive"],
+        None,
+    ),
+    ("invert", &["--invert"], None),
+    ("explain_search", &["--explain-search"], None),
+    ("max_checks", &["--max-checks"], None),
+    ("known_good", &["--known-good"], None),
+    ("known_bad", &["--known-bad"], None),
+    (
+        "write_toolchain_file",
+        &["--write-toolchain-file", "--toolchain-file"],
+        None,
+    ),
+    ("toolchain_file_format", &["--toolchain-file-format"], None),
+    ("write_msrv", &["--write-msrv"], None),
+    ("prefer_newest_patch", &["--prefer-newest-patch"], None),
+    ("write_result", &["--write-result"], None),
+    ("ignore_lockfile", &["--ignore-lockfile"], None),
+    ("lockfile_policy", &["--lockfile-policy"], None),
+    ("fetch_once", &["--fetch-once"], None),
+    ("prefetch_toolchains", &["--prefetch-toolchains"], None),
+    (
+        "output_format",
+        &["--output-format", "--no-user-output"],
+        None,
+    ),
+    ("locale", &["--locale"], Some("CARGO_MSRV_LOCALE")),
+    ("color", &["--color"], Some("CARGO_TERM_COLOR")),
+    ("release_source", &["--release-source"], None),
+    ("github_token", &["--github-token"], Some("GITHUB_TOKEN")),
+    ("max_index_age", &["--max-index-age"], None),
+    (
+        "scratch_dir",
+        &["--scratch-dir"],
+        Some("CARGO_MSRV_SCRATCH_DIR"),
+    ),
+    (
+        "sandbox_dir",
+        &["--sandbox-dir"],
+        Some("CARGO_MSRV_SANDBOX_DIR"),
+    ),
+    ("setup", &["--setup"], None),
+    ("server", &["--server"], Some("CARGO_MSRV_SERVER")),
+    ("log_target", &["--no-log", "--log-target"], None),
+    ("log_level", &["--log-level"], None),
+    ("log_max_size", &["--log-max-size"], None),
+    ("log_max_age", &["--log-max-age"], None),
+    ("notify_webhook", &["--notify-webhook"], None),
+    ("notify_compatibility", &["--notify-compatibility"], None),
+    (
+        "otel_endpoint",
+        &["--otel-endpoint"],
+        Some("CARGO_MSRV_OTEL_ENDPOINT"),
+    ),
+    ("events", &["--events"], None),
+    ("validate_output", &["--validate-output"], None),
+    ("no_check_feedback", &["--no-check-feedback"], None),
+];
+
+impl Configure for Provenance {
+    fn configure<'c>(
+        mut builder: ConfigBuilder<'c>,
+        opts: &'c CargoMsrvOpts,
+    ) -> TResult<ConfigBuilder<'c>> {
+        for &(key, flags, variable) in OPTIONS {
+            if let Some(source) = value_source(&opts.given_flags, flags, variable) {
+                builder = builder.value_source(key, source);
+            }
+        }
+
+        let custom_check_command = match &opts.subcommand {
+            Some(SubCommand::Verify(verify)) => &verify.custom_check.custom_check_command,
+            _ => &opts.find_opts.custom_check_opts.custom_check_command,
+        };
+
+        if !custom_check_command.is_empty() {
+            let source = ValueSource::CommandLine {
+                flag: "--".to_string(),
+            };
+            builder = builder.value_source("check_command", source);
+        }
+
+        Ok(builder)
+    }
+}
+
+/// The flags by which the option with the given configuration key can be set.
+pub(in crate::cli) fn flags_of(key: &str) -> &'static [&'static str] {
+    OPTIONS
+        .iter()
+        .find(|&&(option, _, _)| option == key)
+        .map(|&(_, flags, _)| flags)
+        .unwrap_or_default()
+}
+
+/// Like clap, an environment variable is only used when the option was not given on the command
+/// line. Returns `None` if the option has its default value.
+fn value_source(
+    given_flags: &[String],
+    flags: &[&str],
+    variable: Option<&str>,
+) -> Option<ValueSource> {
+    if let Some(flag) = given_flags
+        .iter()
+        .find(|given| flags.contains(&given.as_str()))
+    {
+        return Some(ValueSource::CommandLine { flag: flag.clone() });
+    }
+
+    variable
+        .filter(|variable| std::env::var_os(variable).map_or(false, |value| !value.is_empty()))
+        .map(|variable| ValueSource::Environment {
+            variable: variable.to_string(),
+        })
+}
@@ -1,7 +1,8 @@
 use crate::cli::configurators::Configure;
-use crate::cli::CargoMsrvOpts;
+use crate::cli::{CargoMsrvOpts, SubCommand};
 use crate::config::ConfigBuilder;
 use crate::TResult;
+use std::time::Duration;
 
 pub(in crate::cli) struct ReleaseSource;
 
@@ -10,6 +11,16 @@ impl Configure for ReleaseSource {
         builder: ConfigBuilder<'c>,
         opts: &'c CargoMsrvOpts,
     ) -> TResult<ConfigBuilder<'c>> {
-        Ok(builder.release_source(opts.find_opts.rust_releases_opts.release_source))
+        let rust_releases_opts = match &opts.subcommand {
+            Some(SubCommand::Verify(verify)) => &verify.rust_releases_opts,
+            _ => &opts.find_opts.rust_releases_opts,
+        };
+
+        let max_index_age = Duration::from_secs(rust_releases_opts.max_index_age * 3600);
+
+        Ok(builder
+            .release_source(opts.find_opts.rust_releases_opts.release_source)
+            .github_token(rust_releases_opts.github_token.clone())
+            .max_index_age(max_index_age))
     }
 }
@@ -0,0 +1,17 @@
+use crate::cli::configurators::Configure;
+use crate::cli::CargoMsrvOpts;
+use crate::config::ConfigBuilder;
+use crate::TResult;
+
+pub(in crate::cli) struct ScratchDir;
+
+impl Configure for ScratchDir {
+    fn configure<'c>(
+        builder: ConfigBuilder<'c>,
+        opts: &'c CargoMsrvOpts,
+    ) -> TResult<ConfigBuilder<'c>> {
+        Ok(builder
+            .scratch_dir(opts.shared_opts.scratch_dir.clone())
+            .sandbox_dir(opts.shared_opts.sandbox_dir.clone()))
+    }
+}
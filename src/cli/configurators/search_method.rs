@@ -1,7 +1,8 @@
 use crate::cli::configurators::Configure;
 use crate::cli::CargoMsrvOpts;
 use crate::config::{ConfigBuilder, SearchMethod};
-use crate::TResult;
+use crate::search_method::{Polarity, SearchSeeds};
+use crate::{CargoMSRVError, TResult};
 
 pub(in crate::cli) struct SearchMethodConfig;
 
@@ -10,12 +11,53 @@ impl Configure for SearchMethodConfig {
         builder: ConfigBuilder<'c>,
         opts: &'c CargoMsrvOpts,
     ) -> TResult<ConfigBuilder<'c>> {
-        let method = match (opts.find_opts.linear, opts.find_opts.bisect) {
-            (true, false) => builder.search_method(SearchMethod::Linear),
-            (false, true) => builder.search_method(SearchMethod::Bisect),
-            _ => builder.search_method(SearchMethod::default()),
+        let find_opts = &opts.find_opts;
+
+        let method = match (find_opts.linear, find_opts.bisect, find_opts.exhaustive) {
+            (true, false, false) => builder.search_method(SearchMethod::Linear),
+            (false, true, false) => builder.search_method(SearchMethod::Bisect),
+            (false, false, true) => builder.search_method(SearchMethod::Exhaustive),
+            _ => builder.search_method(find_opts.search_method.unwrap_or_default()),
+        };
+
+        let polarity = if opts.find_opts.invert {
+            Polarity::Inverted
+        } else {
+            Polarity::Normal
+        };
+
+        let seeds = SearchSeeds {
+            known_good: find_opts.known_good.clone(),
+            known_bad: find_opts.known_bad.clone(),
         };
 
-        Ok(method)
+        if let (Some(good), Some(bad)) = (&seeds.known_good, &seeds.known_bad) {
+            if seeds.is_contradictory(polarity) {
+                // An inverted search accepts failing checks, so the known bad version is more recent
+                let expected = if polarity.is_inverted() {
+                    "less recent"
+                } else {
+                    "more recent"
+                };
+
+                return Err(CargoMSRVError::InvalidConfig(format!(
+                    "The known good Rust version ({}) must be {} than the known bad Rust version ({})",
+                    good, expected, bad
+                )));
+            }
+        }
+
+        if find_opts.max_checks == Some(0) {
+            return Err(CargoMSRVError::InvalidConfig(
+                "The maximum number of checks, given with --max-checks, must be at least 1"
+                    .to_string(),
+            ));
+        }
+
+        Ok(method
+            .polarity(polarity)
+            .explain_search(opts.find_opts.explain_search)
+            .max_checks(find_opts.max_checks)
+            .search_seeds(seeds))
     }
 }
@@ -1,10 +1,12 @@
 use crate::cli::configurators::Configure;
-use crate::cli::CargoMsrvOpts;
+use crate::cli::{CargoMsrvOpts, SubCommand};
 use crate::config::ConfigBuilder;
 use crate::TResult;
 
 pub(in crate::cli) struct IncludeAllPatchReleases;
 
+pub(in crate::cli) struct VersionResolution;
+
 impl Configure for IncludeAllPatchReleases {
     fn configure<'c>(
         builder: ConfigBuilder<'c>,
@@ -15,3 +17,23 @@ impl Configure for IncludeAllPatchReleases {
         ))
     }
 }
+
+impl Configure for VersionResolution {
+    fn configure<'c>(
+        builder: ConfigBuilder<'c>,
+        opts: &'c CargoMsrvOpts,
+    ) -> TResult<ConfigBuilder<'c>> {
+        let find = &opts.find_opts.rust_releases_opts;
+
+        // The resolution given to `verify` takes precedence over the one given before it
+        let resolution = match &opts.subcommand {
+            Some(SubCommand::Verify(verify)) => verify
+                .rust_releases_opts
+                .version_resolution
+                .or(find.version_resolution),
+            _ => find.version_resolution,
+        };
+
+        Ok(builder.version_resolution(resolution.unwrap_or_default()))
+    }
+}
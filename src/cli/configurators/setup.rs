@@ -0,0 +1,17 @@
+use crate::cli::configurators::Configure;
+use crate::cli::CargoMsrvOpts;
+use crate::config::ConfigBuilder;
+use crate::TResult;
+
+pub(in crate::cli) struct Setup;
+
+impl Configure for Setup {
+    fn configure<'c>(
+        builder: ConfigBuilder<'c>,
+        opts: &'c CargoMsrvOpts,
+    ) -> TResult<ConfigBuilder<'c>> {
+        Ok(builder
+            .setup(opts.shared_opts.setup)
+            .assume_yes(opts.shared_opts.yes))
+    }
+}
@@ -1,10 +1,27 @@
 use crate::cli::configurators::Configure;
-use crate::cli::{CargoMsrvOpts, ListOpts, SetOpts, SubCommand, VerifyOpts};
-use crate::config::list::ListCmdConfig;
+use crate::cli::{
+    CargoMsrvOpts, ConfigOpts, ConfigVariant, DiscoverOpts, EditionOpts, HistoryOpts, ImpactOpts,
+    LastFailureOpts, ListOpts, LowerOpts, MatrixOpts, PolicyOpts, PolicyVariant, ReportOpts,
+    ReportVariant, SchemaOpts, ServeOpts, SetOpts, ShowOpts, SubCommand, VerifyOpts,
+};
+use crate::config::configuration::ConfigurationCmdConfig;
+use crate::config::discover::DiscoverCmdConfig;
+use crate::config::edition::EditionCmdConfig;
+use crate::config::history::HistoryCmdConfig;
+use crate::config::impact::ImpactCmdConfig;
+use crate::config::last_failure::LastFailureCmdConfig;
+use crate::config::list::{ListCmdConfig, ListMsrvVariant};
+use crate::config::lower::LowerCmdConfig;
+use crate::config::matrix::MatrixCmdConfig;
+use crate::config::policy::PolicyCmdConfig;
+use crate::config::report::ReportCmdConfig;
+use crate::config::schema::SchemaCmdConfig;
+use crate::config::serve::ServeCmdConfig;
 use crate::config::set::SetCmdConfig;
+use crate::config::show::ShowCmdConfig;
 use crate::config::verify::VerifyCmdConfig;
-use crate::config::{ConfigBuilder, SubCommandConfig};
-use crate::TResult;
+use crate::config::{ConfigBuilder, GitHubCheckOptions, SubCommandConfig};
+use crate::{CargoMSRVError, TResult};
 
 pub(in crate::cli) struct SubCommandConfigurator;
 
@@ -15,12 +32,51 @@ impl Configure for SubCommandConfigurator {
     ) -> TResult<ConfigBuilder<'c>> {
         if let Some(cmd) = &opts.subcommand {
             match cmd {
+                SubCommand::Config(opts) => {
+                    return configure_configuration(builder, opts);
+                }
+                SubCommand::Discover(opts) => {
+                    return configure_discover(builder, opts);
+                }
+                SubCommand::Edition(opts) => {
+                    return configure_edition(builder, opts);
+                }
+                SubCommand::History(opts) => {
+                    return configure_history(builder, opts);
+                }
+                SubCommand::Impact(opts) => {
+                    return configure_impact(builder, opts);
+                }
+                SubCommand::LastFailure(opts) => {
+                    return configure_last_failure(builder, opts);
+                }
                 SubCommand::List(opts) => {
                     return configure_list(builder, opts);
                 }
+                SubCommand::Lower(opts) => {
+                    return configure_lower(builder, opts);
+                }
+                SubCommand::Matrix(opts) => {
+                    return configure_matrix(builder, opts);
+                }
+                SubCommand::Policy(opts) => {
+                    return configure_policy(builder, opts);
+                }
+                SubCommand::Report(opts) => {
+                    return configure_report(builder, opts);
+                }
+                SubCommand::Schema(opts) => {
+                    return configure_schema(builder, opts);
+                }
+                SubCommand::Serve(opts) => {
+                    return configure_serve(builder, opts);
+                }
                 SubCommand::Set(opts) => {
                     return configure_set(builder, opts);
                 }
+                SubCommand::Show(opts) => {
+                    return configure_show(builder, opts);
+                }
                 SubCommand::Verify(opts) => {
                     return configure_verify(builder, opts);
                 }
@@ -36,18 +92,180 @@ impl Configure for SubCommandConfigurator {
     }
 }
 
+fn configure_configuration<'c>(
+    builder: ConfigBuilder<'c>,
+    opts: &'c ConfigOpts,
+) -> TResult<ConfigBuilder<'c>> {
+    let config = match &opts.variant {
+        ConfigVariant::Show => ConfigurationCmdConfig::Show,
+        ConfigVariant::Set(set) => ConfigurationCmdConfig::Set {
+            key: set.key.clone(),
+            value: set.value.clone(),
+        },
+    };
+
+    let config = SubCommandConfig::ConfigurationConfig(config);
+    Ok(builder.sub_command_config(config))
+}
+
+fn configure_discover<'c>(
+    builder: ConfigBuilder<'c>,
+    opts: &'c DiscoverOpts,
+) -> TResult<ConfigBuilder<'c>> {
+    let config = DiscoverCmdConfig { run: opts.run };
+
+    let config = SubCommandConfig::DiscoverConfig(config);
+    Ok(builder.sub_command_config(config))
+}
+
+fn configure_edition<'c>(
+    builder: ConfigBuilder<'c>,
+    opts: &'c EditionOpts,
+) -> TResult<ConfigBuilder<'c>> {
+    let config = EditionCmdConfig {
+        check_migration: opts.check_migration,
+    };
+
+    let config = SubCommandConfig::EditionConfig(config);
+    Ok(builder.sub_command_config(config))
+}
+
+fn configure_history<'c>(
+    builder: ConfigBuilder<'c>,
+    opts: &'c HistoryOpts,
+) -> TResult<ConfigBuilder<'c>> {
+    let config = HistoryCmdConfig {
+        range: opts.range.clone(),
+        verify: opts.verify,
+    };
+
+    let config = SubCommandConfig::HistoryConfig(config);
+    Ok(builder.sub_command_config(config))
+}
+
+fn configure_impact<'c>(
+    builder: ConfigBuilder<'c>,
+    opts: &'c ImpactOpts,
+) -> TResult<ConfigBuilder<'c>> {
+    let config = ImpactCmdConfig {
+        dependency: opts.dependency.clone(),
+        find: opts.find,
+    };
+
+    let config = SubCommandConfig::ImpactConfig(config);
+    Ok(builder.sub_command_config(config))
+}
+
+fn configure_lower<'c>(
+    builder: ConfigBuilder<'c>,
+    opts: &'c LowerOpts,
+) -> TResult<ConfigBuilder<'c>> {
+    let config = LowerCmdConfig {
+        steps: opts.steps,
+        crates_index: opts.crates_index.clone(),
+    };
+
+    let config = SubCommandConfig::LowerConfig(config);
+    Ok(builder.sub_command_config(config))
+}
+
+fn configure_last_failure<'c>(
+    builder: ConfigBuilder<'c>,
+    opts: &'c LastFailureOpts,
+) -> TResult<ConfigBuilder<'c>> {
+    let config = LastFailureCmdConfig { pager: opts.pager };
+
+    let config = SubCommandConfig::LastFailureConfig(config);
+    Ok(builder.sub_command_config(config))
+}
+
 fn configure_list<'c>(
     builder: ConfigBuilder<'c>,
     opts: &'c ListOpts,
 ) -> TResult<ConfigBuilder<'c>> {
+    let variant = if opts.resolve {
+        ListMsrvVariant::Resolve
+    } else {
+        opts.variant
+    };
+
     let config = ListCmdConfig {
-        variant: opts.variant,
+        variant,
+        crates_index: opts.crates_index.clone(),
+        registry_indexes: opts.registry_index.clone(),
     };
 
     let config = SubCommandConfig::ListConfig(config);
     Ok(builder.sub_command_config(config))
 }
 
+fn configure_matrix<'c>(
+    builder: ConfigBuilder<'c>,
+    opts: &'c MatrixOpts,
+) -> TResult<ConfigBuilder<'c>> {
+    let config = MatrixCmdConfig {
+        targets: opts.target.clone(),
+        feature_sets: opts.features.clone(),
+    };
+
+    let config = SubCommandConfig::MatrixConfig(config);
+    Ok(builder.sub_command_config(config))
+}
+
+fn configure_policy<'c>(
+    builder: ConfigBuilder<'c>,
+    opts: &'c PolicyOpts,
+) -> TResult<ConfigBuilder<'c>> {
+    let config = match &opts.variant {
+        PolicyVariant::Check(check) => PolicyCmdConfig::Check {
+            policy: check.policy.clone(),
+            result: check.result.clone(),
+        },
+    };
+
+    let config = SubCommandConfig::PolicyConfig(config);
+    Ok(builder.sub_command_config(config))
+}
+
+fn configure_report<'c>(
+    builder: ConfigBuilder<'c>,
+    opts: &'c ReportOpts,
+) -> TResult<ConfigBuilder<'c>> {
+    let config = match &opts.variant {
+        ReportVariant::Diff(diff) => ReportCmdConfig::Diff {
+            old: diff.old.clone(),
+            new: diff.new.clone(),
+        },
+    };
+
+    let config = SubCommandConfig::ReportConfig(config);
+    Ok(builder.sub_command_config(config))
+}
+
+fn configure_schema<'c>(
+    builder: ConfigBuilder<'c>,
+    opts: &'c SchemaOpts,
+) -> TResult<ConfigBuilder<'c>> {
+    let config = SchemaCmdConfig {
+        out_dir: opts.out_dir.clone(),
+    };
+
+    let config = SubCommandConfig::SchemaConfig(config);
+    Ok(builder.sub_command_config(config))
+}
+
+fn configure_serve<'c>(
+    builder: ConfigBuilder<'c>,
+    opts: &'c ServeOpts,
+) -> TResult<ConfigBuilder<'c>> {
+    let config = ServeCmdConfig {
+        address: opts.address.clone(),
+    };
+
+    let config = SubCommandConfig::ServeConfig(config);
+    Ok(builder.sub_command_config(config))
+}
+
 fn configure_set<'c>(builder: ConfigBuilder<'c>, opts: &'c SetOpts) -> TResult<ConfigBuilder<'c>> {
     let config = SetCmdConfig {
         msrv: opts.msrv.clone(),
@@ -57,20 +275,87 @@ fn configure_set<'c>(builder: ConfigBuilder<'c>, opts: &'c SetOpts) -> TResult<C
     Ok(builder.sub_command_config(config))
 }
 
+fn configure_show<'c>(
+    builder: ConfigBuilder<'c>,
+    opts: &'c ShowOpts,
+) -> TResult<ConfigBuilder<'c>> {
+    let config = ShowCmdConfig {
+        sync_clippy: opts.sync_clippy,
+    };
+
+    let config = SubCommandConfig::ShowConfig(config);
+    Ok(builder.sub_command_config(config))
+}
+
 fn configure_verify<'c>(
     builder: ConfigBuilder<'c>,
     opts: &'c VerifyOpts,
 ) -> TResult<ConfigBuilder<'c>> {
     let config = VerifyCmdConfig {
         rust_version: opts.rust_version.clone(),
+        against_result: opts.against_result.clone(),
+        write_on_success: opts.write_on_success,
+        pin_patch: opts.pin_patch,
+        changed_since: opts.changed_since.clone(),
+        check_published_metadata: opts.check_published_metadata,
+    };
+
+    let builder = if opts.github_check {
+        builder.github_check(github_check_options(opts)?)
+    } else {
+        builder
     };
 
     let config = SubCommandConfig::VerifyConfig(config);
     Ok(builder.sub_command_config(config))
 }
 
+fn github_check_options(opts: &VerifyOpts) -> TResult<GitHubCheckOptions> {
+    let missing = |what: &str, flag: &str, env: &str| {
+        CargoMSRVError::InvalidConfig(format!(
+            "The GitHub check run requires a {}, given with `{}` (or the {} environment variable)",
+            what, flag, env
+        ))
+    };
+
+    let token = opts
+        .rust_releases_opts
+        .github_token
+        .clone()
+        .ok_or_else(|| missing("token", "--github-token", "GITHUB_TOKEN"))?;
+    let repository = opts
+        .github_repository
+        .clone()
+        .ok_or_else(|| missing("repository", "--github-repository", "GITHUB_REPOSITORY"))?;
+    let sha = opts
+        .github_sha
+        .clone()
+        .ok_or_else(|| missing("commit", "--github-sha", "GITHUB_SHA"))?;
+
+    if !repository.contains('/') {
+        return Err(CargoMSRVError::InvalidConfig(format!(
+            "Given GitHub repository '{}' is not valid, expected OWNER/REPO",
+            repository
+        )));
+    }
+
+    Ok(GitHubCheckOptions::new(
+        repository,
+        sha,
+        opts.github_check_name.as_str(),
+        token,
+    ))
+}
+
 fn configure_deprecated_verify_flag(builder: ConfigBuilder) -> TResult<ConfigBuilder> {
-    let config = VerifyCmdConfig { rust_version: None };
+    let config = VerifyCmdConfig {
+        rust_version: None,
+        against_result: None,
+        write_on_success: false,
+        pin_patch: false,
+        changed_since: None,
+        check_published_metadata: false,
+    };
 
     let config = SubCommandConfig::VerifyConfig(config);
     Ok(builder.sub_command_config(config))
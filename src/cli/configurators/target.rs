@@ -1,5 +1,5 @@
 use crate::cli::configurators::Configure;
-use crate::cli::CargoMsrvOpts;
+use crate::cli::{CargoMsrvOpts, SubCommand};
 use crate::config::ConfigBuilder;
 use crate::TResult;
 
@@ -10,8 +10,69 @@ impl Configure for Target {
         builder: ConfigBuilder<'c>,
         opts: &'c CargoMsrvOpts,
     ) -> TResult<ConfigBuilder<'c>> {
-        // TODO{foresterre}: maybe also for `verify`, not just `find`?
-        if let Some(target) = &opts.find_opts.toolchain_opts.target {
+        let find = &opts.find_opts.toolchain_opts;
+
+        // The toolchain options given to `verify` take precedence over those given before it
+        let (profile, target, host, runner, install_profile, remote_check, rustc_wrapper) =
+            match &opts.subcommand {
+                Some(SubCommand::Verify(verify)) => (
+                    // like the custom check options, the profile given before `verify` isn't used
+                    verify.custom_check.profile,
+                    verify
+                        .toolchain_opts
+                        .target
+                        .as_ref()
+                        .or(find.target.as_ref()),
+                    verify.toolchain_opts.host.as_ref().or(find.host.as_ref()),
+                    verify.toolchain_opts.runner.or(find.runner),
+                    verify
+                        .toolchain_opts
+                        .install_profile
+                        .or(find.install_profile),
+                    verify
+                        .toolchain_opts
+                        .remote_check
+                        .as_ref()
+                        .or(find.remote_check.as_ref()),
+                    verify
+                        .toolchain_opts
+                        .rustc_wrapper
+                        .as_ref()
+                        .or(find.rustc_wrapper.as_ref()),
+                ),
+                _ => (
+                    opts.find_opts.custom_check_opts.profile,
+                    find.target.as_ref(),
+                    find.host.as_ref(),
+                    find.runner,
+                    find.install_profile,
+                    find.remote_check.as_ref(),
+                    find.rustc_wrapper.as_ref(),
+                ),
+            };
+
+        let mut builder = builder
+            .check_runner(runner.unwrap_or_default())
+            .install_profile(install_profile.unwrap_or_default());
+
+        // The profile presets the target, so it's applied before the target and host are given
+        if let Some(profile) = profile {
+            builder = builder.check_profile(profile);
+        }
+
+        if let Some(url) = remote_check {
+            builder = builder.remote_check(url.as_str());
+        }
+
+        if let Some(wrapper) = rustc_wrapper {
+            builder = builder.rustc_wrapper(wrapper.as_str());
+        }
+
+        if let Some(host) = host {
+            builder = builder.host(host.as_str());
+        }
+
+        if let Some(target) = target {
             Ok(builder.target(target.as_str()))
         } else {
             Ok(builder)
@@ -1,7 +1,8 @@
 use crate::cli::configurators::Configure;
 use crate::cli::CargoMsrvOpts;
-use crate::config::{ConfigBuilder, TracingOptions};
+use crate::config::{ConfigBuilder, LogRotation, TracingOptions};
 use crate::TResult;
+use std::time::Duration;
 
 pub(in crate::cli) struct Tracing;
 
@@ -14,11 +15,23 @@ impl Configure for Tracing {
             return Ok(builder);
         }
 
-        let tracing_opts = TracingOptions::new(
-            opts.shared_opts.debug_output_opts.log_target,
-            opts.shared_opts.debug_output_opts.log_level,
+        let debug_output_opts = &opts.shared_opts.debug_output_opts;
+
+        let rotation = LogRotation::new(
+            non_zero(debug_output_opts.log_max_size).map(|mb| mb * 1024 * 1024),
+            non_zero(debug_output_opts.log_max_age)
+                .map(|days| Duration::from_secs(days * 24 * 60 * 60)),
         );
 
+        let tracing_opts =
+            TracingOptions::new(debug_output_opts.log_target, debug_output_opts.log_level)
+                .with_rotation(rotation);
+
         Ok(builder.tracing_config(tracing_opts))
     }
 }
+
+// A limit of 0 means the limit is disabled
+fn non_zero(value: u64) -> Option<u64> {
+    Some(value).filter(|&value| value != 0)
+}
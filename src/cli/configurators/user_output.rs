@@ -1,6 +1,6 @@
 use crate::cli::configurators::Configure;
 use crate::cli::CargoMsrvOpts;
-use crate::config::{ConfigBuilder, OutputFormat};
+use crate::config::{ConfigBuilder, EventFilter, OutputFormat};
 use crate::TResult;
 
 pub(in crate::cli) struct UserOutput;
@@ -10,6 +10,17 @@ impl Configure for UserOutput {
         builder: ConfigBuilder<'c>,
         opts: &'c CargoMsrvOpts,
     ) -> TResult<ConfigBuilder<'c>> {
+        let mut builder = builder
+            .locale(opts.shared_opts.user_output_opts.locale)
+            .color(opts.shared_opts.user_output_opts.color)
+            .validate_output(opts.shared_opts.debug_output_opts.validate_output);
+
+        let events = &opts.shared_opts.user_output_opts.events;
+
+        if !events.is_empty() {
+            builder = builder.event_filter(EventFilter::new(events.iter().map(|kind| kind.trim())));
+        }
+
         if opts.shared_opts.user_output_opts.no_user_output {
             return Ok(builder.output_format(OutputFormat::None));
         }
@@ -0,0 +1,68 @@
+use crate::cli::configurators::provenance::flags_of;
+use crate::cli::configurators::Configure;
+use crate::cli::{CargoMsrvOpts, ConfigOpts, ConfigVariant, SubCommand};
+use crate::config::preferences::{preferences_path, Preferences};
+use crate::config::value_source::ValueSource;
+use crate::config::ConfigBuilder;
+use crate::TResult;
+
+pub(in crate::cli) struct UserPreferences;
+
+impl Configure for UserPreferences {
+    fn configure<'c>(
+        mut builder: ConfigBuilder<'c>,
+        opts: &'c CargoMsrvOpts,
+    ) -> TResult<ConfigBuilder<'c>> {
+        // A preferences file which can't be read must not prevent fixing it with `config set`
+        if let Some(SubCommand::Config(ConfigOpts {
+            variant: ConfigVariant::Set(_),
+        })) = &opts.subcommand
+        {
+            return Ok(builder);
+        }
+
+        let path = match preferences_path() {
+            Some(path) => path,
+            None => return Ok(builder),
+        };
+
+        let preferences = Preferences::read(&path)?;
+
+        // A preference only applies when the option was not given on the command line
+        let is_preferred = |key: &str| {
+            let flags = flags_of(key);
+            !opts
+                .given_flags
+                .iter()
+                .any(|given| flags.contains(&given.as_str()))
+        };
+
+        let source = || ValueSource::Preferences { path: path.clone() };
+
+        if let Some(format) = preferences.output_format() {
+            if is_preferred("output_format") {
+                builder = builder
+                    .output_format(format)
+                    .value_source("output_format", source());
+            }
+        }
+
+        if let Some(release_source) = preferences.release_source() {
+            if is_preferred("release_source") {
+                builder = builder
+                    .release_source(release_source)
+                    .value_source("release_source", source());
+            }
+        }
+
+        if let Some(method) = preferences.search_method() {
+            if is_preferred("search_method") {
+                builder = builder
+                    .search_method(method)
+                    .value_source("search_method", source());
+            }
+        }
+
+        Ok(builder)
+    }
+}
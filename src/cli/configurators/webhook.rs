@@ -0,0 +1,23 @@
+use crate::cli::configurators::Configure;
+use crate::cli::CargoMsrvOpts;
+use crate::config::{ConfigBuilder, WebhookOptions};
+use crate::TResult;
+
+pub(in crate::cli) struct Webhook;
+
+impl Configure for Webhook {
+    fn configure<'c>(
+        builder: ConfigBuilder<'c>,
+        opts: &'c CargoMsrvOpts,
+    ) -> TResult<ConfigBuilder<'c>> {
+        let user_output_opts = &opts.shared_opts.user_output_opts;
+
+        match &user_output_opts.notify_webhook {
+            Some(url) => Ok(builder.webhook(WebhookOptions::new(
+                url.as_str(),
+                user_output_opts.notify_compatibility,
+            ))),
+            None => Ok(builder),
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use crate::cli::configurators::Configure;
-use crate::cli::CargoMsrvOpts;
+use crate::cli::{CargoMsrvOpts, SubCommand};
 use crate::config::ConfigBuilder;
 use crate::TResult;
 
@@ -10,6 +10,14 @@ impl Configure for WriteMsrv {
         builder: ConfigBuilder<'c>,
         opts: &'c CargoMsrvOpts,
     ) -> TResult<ConfigBuilder<'c>> {
-        Ok(builder.write_msrv(opts.find_opts.write_msrv))
+        let prefer_newest_patch = match &opts.subcommand {
+            Some(SubCommand::Set(set)) => set.prefer_newest_patch,
+            Some(SubCommand::Verify(verify)) => verify.prefer_newest_patch,
+            _ => opts.find_opts.prefer_newest_patch,
+        };
+
+        Ok(builder
+            .write_msrv(opts.find_opts.write_msrv)
+            .prefer_newest_patch(prefer_newest_patch))
     }
 }
@@ -1,9 +1,213 @@
+use crate::config::{BinPolicy, CargoFlag, CheckProfile, CheckTargets, CleanAfter, DependencyKind};
 use clap::AppSettings;
 use clap::Args;
+use std::path::PathBuf;
 
 #[derive(Debug, Args)]
 #[clap(next_help_heading = "CUSTOM CHECK OPTIONS", setting = AppSettings::DeriveDisplayOrder)]
 pub struct CustomCheckOpts {
+    /// The targets to include in the default `check` command
+    ///
+    /// By default, only the library and binary targets are checked. Use `all` to also check tests,
+    /// examples and benches, so their MSRV is taken into account as well.
+    /// Ignored when a custom `check` command is given.
+    #[clap(long, possible_values = CheckTargets::variants(), default_value_t, value_name = "TARGETS")]
+    pub check_targets: CheckTargets,
+
+    /// Check with a preset for crates which are built for a target without a toolchain of its own
+    ///
+    /// With `no-std`, the crate is checked for `thumbv7em-none-eabihf`, without its default
+    /// features. With `wasm`, the crate is checked for `wasm32-unknown-unknown`. Another target can
+    /// be given with `--target`. The toolchain of the host is installed with the standard library
+    /// of the target, and the dev-dependencies are left out, unless the checked targets depend on
+    /// them.
+    #[clap(long, possible_values = CheckProfile::variants(), value_name = "PROFILE")]
+    pub profile: Option<CheckProfile>,
+
+    /// The kinds of dependencies which take part in the check
+    ///
+    /// A comma separated list of `normal`, `build` and `dev`. The dependency tables of the kinds
+    /// which are left out are temporarily removed from the Cargo manifest during each check, so
+    /// e.g. dev-only tooling doesn't determine the MSRV. Normal dependencies can't be left out.
+    #[clap(
+        long,
+        possible_values = DependencyKind::variants(),
+        use_value_delimiter = true,
+        default_value = "normal,build,dev",
+        value_name = "KINDS"
+    )]
+    pub dep_kinds: Vec<DependencyKind>,
+
+    /// Add a flag to the default `check` command, for toolchains whose cargo supports it
+    ///
+    /// Cargo of older toolchains rejects flags it doesn't know, so the flag is only added when
+    /// checking a toolchain which supports it: `locked` (Rust 1.12+), `frozen` (Rust 1.12+),
+    /// `offline` (Rust 1.36+) and `ignore-rust-version` (Rust 1.56+). May be given multiple times.
+    /// Ignored when a custom `check` command is given.
+    #[clap(long = "cargo-flag", possible_values = CargoFlag::variants(), multiple_occurrences = true, value_name = "FLAG")]
+    pub cargo_flags: Vec<CargoFlag>,
+
+    /// Check with `cargo check --locked`, for toolchains whose cargo supports it
+    ///
+    /// Shorthand for `--cargo-flag locked`: the check fails when the lockfile is not up to date.
+    /// Ignored when a custom `check` command is given.
+    #[clap(long)]
+    pub locked: bool,
+
+    /// Check with `cargo check --frozen`, for toolchains whose cargo supports it
+    ///
+    /// Shorthand for `--cargo-flag frozen`: like `--locked`, and cargo doesn't access the network.
+    /// Ignored when a custom `check` command is given.
+    #[clap(long)]
+    pub frozen: bool,
+
+    /// Check with `cargo check --offline`, for toolchains whose cargo supports it
+    ///
+    /// Shorthand for `--cargo-flag offline`: cargo doesn't access the network, so the dependencies
+    /// must have been fetched already. Ignored when a custom `check` command is given.
+    #[clap(long)]
+    pub offline: bool,
+
+    /// Check only the given workspace member, instead of the package in the current directory
+    ///
+    /// Speeds up finding the MSRV of a single member of a large workspace, since the other members
+    /// aren't compiled for each toolchain. May be given multiple times.
+    /// Ignored when a custom `check` command is given.
+    #[clap(long = "package", multiple_occurrences = true, value_name = "SPEC")]
+    pub packages: Vec<String>,
+
+    /// Check all workspace members, except the given one
+    ///
+    /// Checks the workspace with `--workspace` (or `--all`, for toolchains whose cargo doesn't
+    /// support `--workspace` yet), excluding the given member. May be given multiple times.
+    /// Ignored when a custom `check` command is given.
+    #[clap(
+        long = "exclude",
+        multiple_occurrences = true,
+        value_name = "SPEC",
+        conflicts_with = "packages"
+    )]
+    pub excluded_packages: Vec<String>,
+
+    /// Stub out the Rust source files matched by the given pattern, before checking
+    ///
+    /// The pattern is a path relative to the crate directory, of which each component may contain
+    /// `*` wildcards, e.g. `examples/nightly_*.rs`. A pattern which matches a directory matches
+    /// all source files within it. The crate is checked in a temporary copy, in which the matched
+    /// files are stubbed out, so e.g. nightly-only examples don't determine the MSRV. May be given
+    /// multiple times.
+    #[clap(
+        long = "exclude-path",
+        multiple_occurrences = true,
+        value_name = "PATTERN",
+        conflicts_with = "check-with"
+    )]
+    pub excluded_paths: Vec<String>,
+
+    /// How to check binaries which specify `required-features`
+    ///
+    /// With `enable-features`, the features required by these binaries are enabled. With `exclude`,
+    /// these binaries are left out of the check, and a warning is shown. With `ignore`, the check
+    /// command is left as is.
+    /// Ignored when a custom `check` command is given.
+    #[clap(long, possible_values = BinPolicy::variants(), default_value_t, value_name = "POLICY")]
+    pub bin_policy: BinPolicy,
+
+    /// Also check that the doc tests compile, with `cargo test --doc --no-run`
+    ///
+    /// Doc tests often use newer syntax than the library itself. The doc tests are only checked
+    /// once the check command passed, and a toolchain is only compatible when both pass.
+    /// Requires a library target.
+    #[clap(long, conflicts_with = "check-with")]
+    pub check_doc_tests: bool,
+
+    /// Also run the doc tests, with `cargo test --doc`
+    ///
+    /// Like `--check-doc-tests`, but the doc tests are run as well as compiled.
+    #[clap(long, conflicts_with = "check-with")]
+    pub run_doc_tests: bool,
+
+    /// Retry a failed toolchain check up to the given amount of times
+    ///
+    /// Before each retry, the target directory is cleaned with `cargo clean`. A toolchain is only
+    /// considered incompatible when each retry fails as well, so flaky checks, e.g. of network
+    /// dependent build scripts, don't skew the search. Retries are reported as `retry_check`
+    /// events.
+    #[clap(long, default_value_t = 0, value_name = "N")]
+    pub retries_on_failure: u32,
+
+    /// Reuse the outcome of an earlier check of a toolchain, when nothing relevant changed
+    /// (experimental)
+    ///
+    /// Before each check, cargo is asked for the unit graph of the check, with `--unit-graph`.
+    /// When the toolchain was checked before with the same unit graph, and the sources of the
+    /// local packages are unchanged, the check is skipped and its earlier outcome is reused.
+    /// Outcomes are not reused across toolchains, since they depend on the compiler. Each decision
+    /// is reported as a `unit_graph_reuse` event.
+    #[clap(long, conflicts_with = "check-with")]
+    pub reuse_unit_graph: bool,
+
+    /// Show the output of the check command while it runs
+    ///
+    /// Each line which the check command writes is reported as it's written, as a `check_output`
+    /// event, instead of only showing the output of a failed check once it finished. Useful to
+    /// follow the progress of long running checks.
+    #[clap(long)]
+    pub verbose: bool,
+
+    /// Merge the given TOML file into the Cargo manifest before each check
+    ///
+    /// E.g. to pin a dependency to an older version, which the cargo of older toolchains can
+    /// resolve. Tables are merged, other values replace those of the manifest. The tables under
+    /// `[rust-version.'<requirement>']` are only merged for the toolchains which match the
+    /// requirement, e.g. `[rust-version.'<1.60'.dependencies]`. The manifest, and the lockfile,
+    /// are restored after each check.
+    #[clap(long, conflicts_with = "check-with", value_name = "PATH")]
+    pub manifest_patch: Option<PathBuf>,
+
+    /// The target directory for the build artifacts of the default `check` command
+    ///
+    /// Given to the check command as `--target-dir`, e.g. to keep the artifacts of the checks apart
+    /// from those of regular builds. A relative path is relative to the current directory.
+    /// Ignored when a custom `check` command is given.
+    #[clap(long, conflicts_with = "check-with", value_name = "PATH")]
+    pub target_dir: Option<PathBuf>,
+
+    /// When to remove the build artifacts of a toolchain check, with `cargo clean`
+    ///
+    /// With `never`, the artifacts are kept. With `on-success`, the artifacts of compatible
+    /// toolchains are removed, while those of failed checks are kept for inspection. With `always`,
+    /// the artifacts are removed after each check, which bounds the disk usage of a long search.
+    #[clap(long, possible_values = CleanAfter::variants(), default_value_t, value_name = "WHEN")]
+    pub clean_after: CleanAfter,
+
+    /// Check by compiling a single file with the given tool, instead of running `cargo check`
+    ///
+    /// The only supported tool is `rustc`, e.g. `--check-with rustc snippet.rs`. The file is
+    /// compiled as a library by the `rustc` of each toolchain, so no Cargo manifest is required.
+    #[clap(
+        long,
+        number_of_values = 2,
+        value_names = &["TOOL", "FILE"],
+        conflicts_with = "custom-check-command"
+    )]
+    pub check_with: Option<Vec<String>>,
+
+    /// Run the given shell command before each toolchain check
+    ///
+    /// The command is run in the crate directory. The toolchain which is about to be checked is
+    /// given by the `MSRV_TOOLCHAIN` and `MSRV_TARGET` environment variables. When the command
+    /// fails, cargo-msrv stops.
+    #[clap(long, value_name = "CMD")]
+    pub before_check: Option<String>,
+
+    /// Run the given shell command after each toolchain check
+    ///
+    /// Like `--before-check`, but the outcome of the check is also given by the `MSRV_OUTCOME`
+    /// environment variable, as either `compatible` or `incompatible`.
+    #[clap(long, value_name = "CMD")]
+    pub after_check: Option<String>,
+
     /// Supply a custom `check` command to be used by cargo msrv
     #[clap(last = true, required = false)]
     pub custom_check_command: Vec<String>,
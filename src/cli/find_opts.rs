@@ -1,14 +1,17 @@
 use crate::cli::custom_check_opts::CustomCheckOpts;
 use crate::cli::rust_releases_opts::RustReleasesOpts;
 use crate::cli::toolchain_opts::ToolchainOpts;
+use crate::config::{LockfilePolicy, SearchMethod, ToolchainFileFormat};
+use crate::manifest::bare_version::BareVersion;
 use clap::AppSettings;
 use clap::Args;
+use std::path::PathBuf;
 
 // Cli Options for top-level cargo-msrv (find) command
 #[derive(Debug, Args)]
 #[clap(next_help_heading = "FIND MSRV OPTIONS", setting = AppSettings::DeriveDisplayOrder)]
 pub struct FindOpts {
-    /// Use a binary search to find the MSRV (default)
+    /// Use a binary search to find the MSRV
     ///
     /// When the search space is sufficiently large, which is common, this is much
     /// faster than a linear search. A binary search will approximately halve the search
@@ -22,6 +25,66 @@ pub struct FindOpts {
     #[clap(long, conflicts_with = "bisect")]
     pub linear: bool,
 
+    /// Check each Rust version, instead of stopping once the MSRV has been found
+    ///
+    /// Like a linear search, but the search continues after the first failing Rust version, so
+    /// the outcome of each Rust version is reported. A warning is shown when a Rust version passes,
+    /// although a more recent Rust version failed: the other search methods assume this can't
+    /// happen. The MSRV is the least recent Rust version from which each more recent version passed.
+    #[clap(long, conflicts_with_all = &["bisect", "linear"])]
+    pub exhaustive: bool,
+
+    /// The method by which the MSRV is searched
+    ///
+    /// 'auto' (default) searches linearly when at most 3 Rust versions remain to be checked, and
+    /// otherwise runs a binary search which first checks the MSRV declared in the Cargo manifest,
+    /// since the MSRV is likely close to it.
+    #[clap(
+        long,
+        value_name = "METHOD",
+        possible_values = SearchMethod::variants(),
+        conflicts_with_all = &["bisect", "linear", "exhaustive"]
+    )]
+    pub search_method: Option<SearchMethod>,
+
+    /// Invert the search, to find the earliest Rust version for which the check command fails
+    ///
+    /// Useful for tools which break on newer compilers: the reported version is the release
+    /// which introduced the regression, so the release before it is the maximum supported
+    /// Rust version.
+    #[clap(long, conflicts_with_all = &["write-msrv", "write-toolchain-file", "write-result"])]
+    pub invert: bool,
+
+    /// Report each decision of the search method
+    ///
+    /// For each checked Rust version, reports the range of releases which remained to be
+    /// searched, the tested release, its outcome, and the range which remains afterwards.
+    #[clap(long)]
+    pub explain_search: bool,
+
+    /// The maximum number of Rust versions which may be checked
+    ///
+    /// Bounds the duration of a search, e.g. for CI jobs with a strict time limit. When the
+    /// budget is exhausted before the MSRV is found, the search stops, and the range of Rust
+    /// versions in which the MSRV must lie, according to the checks so far, is reported as an
+    /// inconclusive result.
+    #[clap(long, value_name = "N")]
+    pub max_checks: Option<u64>,
+
+    /// A Rust version for which the check is known to pass, e.g. from a previous CI run
+    ///
+    /// The Rust versions which are more recent are not checked, since compatibility is assumed to
+    /// be monotonic. The known outcomes are recorded in the result.
+    #[clap(long, value_name = "VERSION")]
+    pub known_good: Option<BareVersion>,
+
+    /// A Rust version for which the check is known to fail, e.g. from a previous CI run
+    ///
+    /// This Rust version, and the Rust versions which are less recent, are not checked, since
+    /// compatibility is assumed to be monotonic. The known outcomes are recorded in the result.
+    #[clap(long, value_name = "VERSION")]
+    pub known_bad: Option<BareVersion>,
+
     /// Pin the MSRV by writing the version to a rust-toolchain file
     ///
     /// The toolchain file will pin the Rust version for this crate.
@@ -29,6 +92,14 @@ pub struct FindOpts {
     #[clap(long, alias = "toolchain-file")]
     pub write_toolchain_file: bool,
 
+    /// The format of the toolchain file written with '--write-toolchain-file'
+    ///
+    /// The 'toml' format writes a rust-toolchain.toml file with a [toolchain] table, and preserves
+    /// the components and targets of an existing toolchain file. The 'legacy' format writes a
+    /// rust-toolchain file which holds just the channel.
+    #[clap(long, possible_values = ToolchainFileFormat::variants(), default_value_t, value_name = "FORMAT")]
+    pub toolchain_file_format: ToolchainFileFormat,
+
     /// Temporarily remove the lockfile, so it will not interfere with the building process
     ///
     /// This is important when testing against older Rust versions such as Cargo versions prior to
@@ -36,6 +107,34 @@ pub struct FindOpts {
     #[clap(long)]
     pub ignore_lockfile: bool,
 
+    /// How a Rust version is checked, when its cargo can't read the format of the lockfile
+    ///
+    /// The format of the lockfile is detected once. Rust versions whose cargo predates the format
+    /// are either rejected as untestable ('untestable'), or checked with a lockfile which their
+    /// cargo regenerates, without '--locked' ('regenerate'). The lockfile of the crate is moved
+    /// aside during such a check, and restored afterwards.
+    #[clap(long, possible_values = LockfilePolicy::variants(), default_value_t, value_name = "POLICY")]
+    pub lockfile_policy: LockfilePolicy,
+
+    /// Fetch the dependencies once, and check each Rust version offline
+    ///
+    /// The dependencies are fetched with 'cargo fetch', by the first Rust version which is checked.
+    /// Each check then runs with '--offline' (when supported by its cargo), so the registry index
+    /// is not updated, and no sources are downloaded again. Compiled dependencies can't be shared
+    /// between Rust versions, since cargo doesn't reuse artifacts of another compiler, so each
+    /// check still compiles the dependencies.
+    #[clap(long)]
+    pub fetch_once: bool,
+
+    /// Install the Rust versions which may be checked next, while a Rust version is checked
+    ///
+    /// The toolchains are installed by rustup in the background, one at a time. A toolchain
+    /// which turns out not to be needed, e.g. because the search went the other way, is
+    /// installed regardless, unless its installation didn't start yet. Toolchains are not
+    /// prefetched when they're installed by the server given with '--server'.
+    #[clap(long)]
+    pub prefetch_toolchains: bool,
+
     /// Don't read the `edition` of the crate and do not use its value to reduce the search space
     #[clap(long)]
     pub no_read_min_edition: bool,
@@ -57,6 +156,33 @@ pub struct FindOpts {
     #[clap(long)]
     pub write_msrv: bool,
 
+    /// With `--write-msrv`, write the newest patch release of the minor version of the MSRV instead
+    ///
+    /// E.g. `1.64.1` instead of `1.64.0`, when Rust 1.64.1 exists, so the MSRV points at the
+    /// release with the most recent fixes.
+    #[clap(long, requires = "write-msrv")]
+    pub prefer_newest_patch: bool,
+
+    /// Write the MSRV, the check command and target, and a timestamp to the given TOML file
+    ///
+    /// The file is meant to be committed, as an alternative to writing the MSRV to the Cargo
+    /// manifest. The recorded MSRV can be verified with `cargo msrv verify --against-result`.
+    #[clap(long, value_name = "FILE", conflicts_with = "batch")]
+    pub write_result: Option<PathBuf>,
+
+    /// Find the MSRV of each crate whose path is read from stdin, one path per line
+    ///
+    /// Only `-` (stdin) is supported as source. For each crate, a json record with its MSRV,
+    /// or the reason why it could not be found, is written to stdout as a single line.
+    /// The release index and the installed toolchains are shared between all crates.
+    #[clap(
+        long,
+        value_name = "SOURCE",
+        possible_values = &["-"],
+        conflicts_with_all = &["path", "manifest-path", "check-with"]
+    )]
+    pub batch: Option<String>,
+
     #[clap(flatten)]
     pub rust_releases_opts: RustReleasesOpts,
 
@@ -1,8 +1,12 @@
+use crate::config::{VersionResolution, DEFAULT_MAX_INDEX_AGE_HOURS};
 use crate::manifest::bare_version;
 use crate::manifest::bare_version::BareVersion;
+use crate::release_index_source::AuthToken;
+use crate::release_schedule::Date;
 use crate::ReleaseSource;
 use clap::AppSettings;
 use clap::Args;
+use std::fmt;
 use std::str::FromStr;
 
 #[derive(Debug, Args)]
@@ -18,6 +22,14 @@ pub struct RustReleasesOpts {
     #[clap(long, value_name = "VERSION_SPEC or EDITION", alias = "minimum")]
     pub min: Option<EditionOrVersion>,
 
+    /// Least recent release date to take into account
+    ///
+    /// The first Rust release which was released on or after the given date (formatted as
+    /// `YYYY-MM-DD`) is used as the least recent version, e.g. to only support compilers which
+    /// were released in the last 18 months.
+    #[clap(long, value_name = "DATE", conflicts_with = "min")]
+    pub min_date: Option<Date>,
+
     /// Most recent version to take into account
     ///
     /// Given version must match a valid Rust toolchain, and be semver compatible, or
@@ -25,12 +37,60 @@ pub struct RustReleasesOpts {
     #[clap(long, value_name = "VERSION_SPEC", alias = "maximum")]
     pub max: Option<BareVersion>,
 
+    /// Most recent release date to take into account
+    ///
+    /// The last Rust release which was released on or before the given date (formatted as
+    /// `YYYY-MM-DD`) is used as the most recent version.
+    #[clap(long, value_name = "DATE", conflicts_with = "max")]
+    pub max_date: Option<Date>,
+
+    /// Leave the given version out of the search space
+    ///
+    /// E.g. a compiler release which is known to be broken for a platform. A two component
+    /// `major.minor` version excludes each patch release of this minor version. May be given
+    /// multiple times. Versions listed by the `package.metadata.cargo-msrv.exclude-versions` key
+    /// of the Cargo manifest are excluded as well.
+    #[clap(
+        long = "exclude-version",
+        multiple_occurrences = true,
+        value_name = "VERSION_SPEC"
+    )]
+    pub excluded_versions: Vec<BareVersion>,
+
     /// Include all patch releases, instead of only the last
     #[clap(long)]
     pub include_all_patch_releases: bool,
 
+    /// Resolve an MSRV which matches none of the Rust releases, instead of failing
+    ///
+    /// E.g. `1.54.0`, when only `1.54.1` and `1.54.2` are released. With `closest-patch`, the
+    /// closest patch release of the same minor release is used, and with `nearest-newer`, the
+    /// least recent release which is more recent than the MSRV. The applied resolution is
+    /// reported.
+    #[clap(long, possible_values = VersionResolution::variants(), value_name = "RESOLUTION")]
+    pub version_resolution: Option<VersionResolution>,
+
     #[clap(long, possible_values = ReleaseSource::variants(), default_value_t, value_name = "SOURCE")]
     pub release_source: ReleaseSource,
+
+    /// Token used to authenticate requests to GitHub when fetching the rust-changelog release source
+    ///
+    /// Authenticated requests are subject to a much higher rate limit, which helps on busy
+    /// CI runners which share an IP address.
+    #[clap(
+        long,
+        env = "GITHUB_TOKEN",
+        hide_env_values = true,
+        value_name = "TOKEN"
+    )]
+    pub github_token: Option<AuthToken>,
+
+    /// Use the cached release index, unless it is older than the given amount of hours
+    ///
+    /// An older cached index is revalidated with the release source, and only downloaded again
+    /// when it changed. Use 0 to always revalidate the cached index.
+    #[clap(long, default_value_t = DEFAULT_MAX_INDEX_AGE_HOURS, value_name = "HOURS")]
+    pub max_index_age: u64,
 }
 
 #[derive(Debug)]
@@ -48,10 +108,15 @@ impl EditionOrVersion {
     }
 }
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[derive(
+    Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, serde::Serialize, schemars::JsonSchema,
+)]
 pub enum Edition {
+    #[serde(rename = "2015")]
     Edition2015,
+    #[serde(rename = "2018")]
     Edition2018,
+    #[serde(rename = "2021")]
     Edition2021,
 }
 
@@ -69,6 +134,12 @@ impl FromStr for Edition {
 }
 
 impl Edition {
+    /// All editions, from least to most recent.
+    pub fn all() -> &'static [Self] {
+        &[Self::Edition2015, Self::Edition2018, Self::Edition2021]
+    }
+
+    /// The first Rust version which supports this edition.
     pub fn as_bare_version(&self) -> bare_version::BareVersion {
         match self {
             Self::Edition2015 => BareVersion::ThreeComponents(1, 0, 0),
@@ -76,6 +147,25 @@ impl Edition {
             Self::Edition2021 => BareVersion::ThreeComponents(1, 56, 0),
         }
     }
+
+    /// The edition which succeeds this edition, if any.
+    pub fn next(&self) -> Option<Self> {
+        match self {
+            Self::Edition2015 => Some(Self::Edition2018),
+            Self::Edition2018 => Some(Self::Edition2021),
+            Self::Edition2021 => None,
+        }
+    }
+}
+
+impl fmt::Display for Edition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Edition2015 => write!(f, "2015"),
+            Self::Edition2018 => write!(f, "2018"),
+            Self::Edition2021 => write!(f, "2021"),
+        }
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -1,10 +1,10 @@
-use crate::config::{OutputFormat, TracingTargetOption};
+use crate::config::{ColorChoice, Locale, LogRotation, OutputFormat, TracingTargetOption};
 
 use crate::log_level::LogLevel;
 use clap::AppSettings;
 use clap::ArgGroup;
 use clap::Args;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 // Cli Options shared between subcommands
 #[derive(Debug, Args)]
@@ -19,6 +19,61 @@ pub struct SharedOpts {
     #[clap(long, value_name = "Cargo Manifest", global = true)]
     pub manifest_path: Option<PathBuf>,
 
+    /// Check the crate at the given git revision, e.g. a tag or commit
+    ///
+    /// The revision is checked out in a temporary git worktree, which is removed afterwards, so
+    /// the working tree of the crate is left untouched.
+    #[clap(long, value_name = "REV", global = true)]
+    pub at_rev: Option<String>,
+
+    /// Directory in which temporary artifacts are written
+    ///
+    /// Temporary artifacts, like the lockfile when it is ignored, and log files, are written to
+    /// this directory, instead of to the crate directory or system directories.
+    #[clap(
+        long,
+        env = "CARGO_MSRV_SCRATCH_DIR",
+        value_name = "DIR",
+        global = true
+    )]
+    pub scratch_dir: Option<PathBuf>,
+
+    /// Directory in which sandboxes are created, like copies of the crate, and git worktrees
+    ///
+    /// Defaults to the scratch directory, or else to the temporary directory of the system. On
+    /// Windows, a short directory, like `C:\msrv`, keeps the paths of the builds within a sandbox
+    /// of a deeply nested workspace below the path length limit.
+    #[clap(
+        long,
+        env = "CARGO_MSRV_SANDBOX_DIR",
+        value_name = "DIR",
+        global = true
+    )]
+    pub sandbox_dir: Option<PathBuf>,
+
+    /// Install missing prerequisites, when they can be installed automatically
+    ///
+    /// When rustup can't be found, it is installed with its official installer, together with the
+    /// stable toolchain. Each install command is confirmed at a prompt, or up front with `--yes`.
+    /// Not supported on Windows.
+    #[clap(long, global = true)]
+    pub setup: bool,
+
+    /// Run the install commands of `--setup` without asking for confirmation
+    ///
+    /// Required to install prerequisites when stdin is not a terminal, e.g. in a CI image without
+    /// a Rust installation.
+    #[clap(long, global = true, requires = "setup")]
+    pub yes: bool,
+
+    /// Obtain the release index from, and install toolchains and run checks with, the server at
+    /// the given address
+    ///
+    /// The server is started with `cargo msrv serve`. When the server can't be reached, the
+    /// release index is fetched, toolchains are installed, and checks are run, as usual.
+    #[clap(long, env = "CARGO_MSRV_SERVER", value_name = "ADDR", global = true)]
+    pub server: Option<String>,
+
     #[clap(flatten)]
     pub user_output_opts: UserOutputOpts,
 
@@ -26,6 +81,16 @@ pub struct SharedOpts {
     pub debug_output_opts: DebugOutputOpts,
 }
 
+impl SharedOpts {
+    /// The directory in which sandboxes are created, if given: the sandbox directory, or else the
+    /// scratch directory.
+    pub fn sandbox_root(&self) -> Option<&Path> {
+        self.sandbox_dir
+            .as_deref()
+            .or_else(|| self.scratch_dir.as_deref())
+    }
+}
+
 #[derive(Debug, Args)]
 #[clap(next_help_heading = "USER OUTPUT OPTIONS", setting = AppSettings::DeriveDisplayOrder)]
 pub struct UserOutputOpts {
@@ -41,6 +106,61 @@ pub struct UserOutputOpts {
     /// Disable user output
     #[clap(long, global = true)]
     pub no_user_output: bool,
+
+    /// Set the language of the human output
+    ///
+    /// Covers the progress and result of the search, and the messages of 'set', 'show' and
+    /// 'serve'. The reports of the other subcommands, like 'list' or 'history', are in English.
+    #[clap(long,
+        possible_values = Locale::variants(),
+        default_value_t,
+        env = "CARGO_MSRV_LOCALE",
+        value_name = "LOCALE",
+        global = true,
+    )]
+    pub locale: Locale,
+
+    /// Set when the human output is colored
+    ///
+    /// With `auto`, the output is colored when it is written to a terminal, and the `NO_COLOR`
+    /// environment variable is not set.
+    #[clap(long,
+        possible_values = ColorChoice::variants(),
+        default_value_t,
+        env = "CARGO_TERM_COLOR",
+        value_name = "WHEN",
+        global = true,
+    )]
+    pub color: ColorChoice,
+
+    /// POST the final result as JSON to the given HTTP endpoint
+    ///
+    /// The result is sent in the same format as the `msrv_result` message of `--output-format json`.
+    #[clap(long, value_name = "URL", global = true)]
+    pub notify_webhook: Option<String>,
+
+    /// Also POST the result of each toolchain check to the webhook
+    #[clap(long, requires = "notify-webhook", global = true)]
+    pub notify_compatibility: bool,
+
+    /// Export each toolchain check as a span to the given OpenTelemetry (OTLP/HTTP) collector
+    ///
+    /// The spans are POSTed once the run is finished, as JSON, to the `/v1/traces` path of the
+    /// given endpoint, e.g. `http://localhost:4318`.
+    #[clap(
+        long,
+        env = "CARGO_MSRV_OTEL_ENDPOINT",
+        value_name = "URL",
+        global = true
+    )]
+    pub otel_endpoint: Option<String>,
+
+    /// Only write events of the given kinds to the json output
+    ///
+    /// A comma separated list of the `type` of the json messages, e.g.
+    /// `compatibility,msrv_result`. A failure to complete the run is always written.
+    #[clap(long, use_value_delimiter = true, value_name = "KINDS", global = true)]
+    pub events: Vec<String>,
 }
 
 #[derive(Debug, Args)]
@@ -51,6 +171,8 @@ pub struct DebugOutputOpts {
     pub no_log: bool,
 
     /// Specify where the program should output its logs
+    ///
+    /// With `both`, logs are written to the log file and to stderr.
     #[clap(
         long,
         arg_enum,
@@ -60,7 +182,26 @@ pub struct DebugOutputOpts {
     )]
     pub log_target: TracingTargetOption,
 
+    /// Rotate the log file once it grows larger than the given size in megabytes
+    ///
+    /// Use 0 to never rotate the log file.
+    #[clap(long, default_value_t = LogRotation::DEFAULT_MAX_SIZE_MB, value_name = "MB", global = true)]
+    pub log_max_size: u64,
+
+    /// Remove log files which are older than the given amount of days
+    ///
+    /// Use 0 to keep log files indefinitely.
+    #[clap(long, default_value_t = LogRotation::DEFAULT_MAX_AGE_DAYS, value_name = "DAYS", global = true)]
+    pub log_max_age: u64,
+
     /// Specify the severity of logs which should be
     #[clap(long, default_value_t, value_name = "LEVEL", global = true)]
     pub log_level: LogLevel,
+
+    /// Validate each event against the schema of the events, as printed by `cargo msrv schema`
+    ///
+    /// Each event which doesn't match the schema is reported on stderr, and fails the run once
+    /// it is over. Meant to catch changes to the json output which break its consumers.
+    #[clap(long, global = true)]
+    pub validate_output: bool,
 }
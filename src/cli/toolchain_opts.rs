@@ -1,3 +1,4 @@
+use crate::config::{CheckRunner, InstallProfile};
 use clap::AppSettings;
 use clap::Args;
 
@@ -9,4 +10,46 @@ pub struct ToolchainOpts {
     /// Check against a custom target (instead of the rustup default)
     #[clap(long, value_name = "TARGET")]
     pub target: Option<String>,
+
+    /// Run the toolchains on the given host, instead of on the target
+    ///
+    /// For targets which have no toolchain of their own, e.g. `wasm32-unknown-unknown`: the
+    /// toolchain of the host is installed with the standard library of the target, and the cargo
+    /// check command is run with `--target`.
+    #[clap(long, value_name = "HOST")]
+    pub host: Option<String>,
+
+    /// How each toolchain is checked
+    ///
+    /// With `cross`, the check command is run by cross, for targets which can't be built on the
+    /// host, e.g. `armv7-linux-androideabi`. The toolchain for the host is installed for each Rust
+    /// version, and cross builds for the `--target` inside its container. Requires a cargo check
+    /// command. Defaults to `rustup`.
+    #[clap(long, possible_values = CheckRunner::variants(), value_name = "RUNNER")]
+    pub runner: Option<CheckRunner>,
+
+    /// The rustup profile with which the toolchains are installed
+    ///
+    /// Defaults to 'minimal', which installs just the compiler, cargo and the standard library,
+    /// to save time and disk space. The components which the check command requires, like clippy
+    /// for `cargo clippy`, are installed on demand, regardless of the profile.
+    #[clap(long, possible_values = InstallProfile::variants(), value_name = "PROFILE")]
+    pub install_profile: Option<InstallProfile>,
+
+    /// Submit each check to a remote execution service at the given URL, instead of running it
+    /// locally
+    ///
+    /// The crate is packaged once with `cargo package`. For each check, the toolchain, the check
+    /// command and the crate archive are POSTed to the URL, which replies whether the crate is
+    /// compatible. The search itself runs locally.
+    #[clap(long, value_name = "URL", conflicts_with = "runner")]
+    pub remote_check: Option<String>,
+
+    /// Run the compiler of each check through the given wrapper, like `sccache`
+    ///
+    /// The wrapper is given to each check command as the `RUSTC_WRAPPER` environment variable, so
+    /// dependencies which were compiled for one toolchain can be reused from the cache of the
+    /// wrapper. With `sccache`, the cache hits and misses of each toolchain are reported.
+    #[clap(long, value_name = "WRAPPER", conflicts_with = "remote-check")]
+    pub rustc_wrapper: Option<String>,
 }
@@ -0,0 +1,132 @@
+//! The `msrv` value of the configuration file of clippy, `clippy.toml` or `.clippy.toml`.
+//!
+//! Clippy uses this value to decide which lints to suggest, e.g. it won't suggest an API which is
+//! more recent than the MSRV. When the value doesn't agree with the MSRV of the Cargo manifest,
+//! clippy may suggest code which doesn't compile with the MSRV, or hold back suggestions which
+//! would.
+
+use crate::error::{CargoMSRVError, IoErrorSource, TResult};
+use crate::manifest::bare_version::BareVersion;
+use std::path::{Path, PathBuf};
+use toml_edit::{value, Document};
+
+/// The names of the configuration file of clippy, in the order in which clippy looks for them.
+pub const FILE_NAMES: [&str; 2] = [".clippy.toml", "clippy.toml"];
+
+/// The key of the MSRV in the configuration file of clippy.
+const MSRV_KEY: &str = "msrv";
+
+/// The configuration files of clippy which exist in the given crate root.
+pub fn find(crate_root: &Path) -> Vec<PathBuf> {
+    FILE_NAMES
+        .iter()
+        .map(|name| crate_root.join(name))
+        .filter(|path| path.is_file())
+        .collect()
+}
+
+/// Read the MSRV of the clippy configuration file at the given path, or `None` if it doesn't
+/// specify one.
+pub fn read_msrv(path: &Path) -> TResult<Option<BareVersion>> {
+    let document = read_document(path)?;
+
+    msrv_of(&document)
+}
+
+/// Write the given MSRV to the clippy configuration file at the given path. The formatting and
+/// other values of the file are preserved.
+pub fn write_msrv(path: &Path, msrv: &BareVersion) -> TResult<()> {
+    let mut document = read_document(path)?;
+
+    document[MSRV_KEY] = value(msrv.to_string());
+
+    std::fs::write(path, document.to_string()).map_err(|error| CargoMSRVError::Io {
+        error,
+        source: IoErrorSource::WriteFile(path.to_path_buf()),
+    })
+}
+
+fn read_document(path: &Path) -> TResult<Document> {
+    let contents = std::fs::read_to_string(path).map_err(|error| CargoMSRVError::Io {
+        error,
+        source: IoErrorSource::ReadFile(path.to_path_buf()),
+    })?;
+
+    contents
+        .parse::<Document>()
+        .map_err(CargoMSRVError::ParseToml)
+}
+
+fn msrv_of(document: &Document) -> TResult<Option<BareVersion>> {
+    let msrv = match document.as_table().get(MSRV_KEY) {
+        Some(msrv) => msrv,
+        None => return Ok(None),
+    };
+
+    let msrv = msrv.as_str().ok_or_else(|| {
+        CargoMSRVError::InvalidConfig(format!(
+            "Expected a version string for '{}' in the clippy configuration",
+            MSRV_KEY
+        ))
+    })?;
+
+    Ok(Some(msrv.parse()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_dir::{DirBuilder, TestDir};
+
+    fn msrv(contents: &str) -> TResult<Option<BareVersion>> {
+        msrv_of(&contents.parse::<Document>().unwrap())
+    }
+
+    #[yare::parameterized(
+        two_components = { "msrv = \"1.56\"\n", Some(BareVersion::TwoComponents(1, 56)) },
+        three_components = { "msrv = \"1.56.1\"\n", Some(BareVersion::ThreeComponents(1, 56, 1)) },
+        other_values = { "avoid-breaking-exported-api = false\nmsrv = \"1.60\"\n", Some(BareVersion::TwoComponents(1, 60)) },
+        none = { "cognitive-complexity-threshold = 30\n", None },
+    )]
+    fn read(contents: &str, expected: Option<BareVersion>) {
+        assert_eq!(msrv(contents).unwrap(), expected);
+    }
+
+    #[yare::parameterized(
+        not_a_string = { "msrv = 1.56\n" },
+        not_a_version = { "msrv = \"stable\"\n" },
+    )]
+    fn invalid(contents: &str) {
+        assert!(msrv(contents).is_err());
+    }
+
+    #[test]
+    fn find_configs() {
+        let tmp = TestDir::temp();
+        std::fs::write(tmp.path("clippy.toml"), "msrv = \"1.56\"\n").unwrap();
+        std::fs::create_dir_all(tmp.path(".clippy.toml")).unwrap();
+
+        assert_eq!(find(tmp.root()), vec![tmp.path("clippy.toml")]);
+    }
+
+    #[test]
+    fn write_preserves_other_values() {
+        let tmp = TestDir::temp();
+        let path = tmp.path("clippy.toml");
+        std::fs::write(
+            &path,
+            "# lints\nmsrv = \"1.56\" # the MSRV\ntoo-many-arguments-threshold = 10\n",
+        )
+        .unwrap();
+
+        write_msrv(&path, &BareVersion::ThreeComponents(1, 60, 0)).unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert!(contents.starts_with("# lints\n"));
+        assert!(contents.contains("too-many-arguments-threshold = 10"));
+        assert_eq!(
+            read_msrv(&path).unwrap(),
+            Some(BareVersion::ThreeComponents(1, 60, 0))
+        );
+    }
+}
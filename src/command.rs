@@ -1,23 +1,30 @@
 use std::ffi::{OsStr, OsString};
+use std::io::{BufRead, BufReader, Read};
 use std::path::Path;
-use std::process::{Command, Stdio};
+use std::process::{Child, Command, Output, Stdio};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::thread::JoinHandle;
 
+use crate::cancellation::{CancellationToken, POLL_INTERVAL};
 use crate::error::{CargoMSRVError, IoErrorSource, TResult};
+use crate::prerequisites::installed_tool;
 
 pub struct RustupCommand {
     command: Command,
     args: Vec<OsString>,
     stdout: Stdio,
     stderr: Stdio,
+    cancellation: Option<CancellationToken>,
 }
 
 impl RustupCommand {
     pub fn new() -> Self {
         Self {
-            command: Command::new("rustup"),
+            command: installed_tool("rustup").map_or_else(|| Command::new("rustup"), Command::new),
             args: Vec::new(),
             stdout: Stdio::null(),
             stderr: Stdio::null(),
+            cancellation: None,
         }
     }
 
@@ -38,6 +45,11 @@ impl RustupCommand {
         self
     }
 
+    pub fn with_env(mut self, key: impl AsRef<OsStr>, value: impl AsRef<OsStr>) -> Self {
+        let _ = self.command.env(key, value);
+        self
+    }
+
     pub fn with_stdout(mut self) -> Self {
         self.stdout = Stdio::piped();
         self
@@ -48,28 +60,79 @@ impl RustupCommand {
         self
     }
 
+    /// Kill the process when the given token is cancelled, instead of waiting for it to finish.
+    pub fn with_optional_cancellation(mut self, token: Option<&CancellationToken>) -> Self {
+        self.cancellation = token.cloned();
+        self
+    }
+
     /// Execute `rustup run [...]`
     pub fn run(self) -> TResult<RustupOutput> {
         self.execute(OsStr::new("run"))
     }
 
+    /// Execute `rustup run [...]`, and hand each line which the process writes to the piped
+    /// stdout or stderr to `on_line`, as soon as it's written. The output is collected as well.
+    pub fn run_streamed(
+        self,
+        on_line: impl FnMut(Pipe, &str) -> TResult<()>,
+    ) -> TResult<RustupOutput> {
+        let cmd = OsStr::new("run");
+        let (child, cancellation) = self.spawn(cmd)?;
+        let output = wait_streamed(child, cancellation.as_ref(), cmd, on_line)?;
+
+        Ok(RustupOutput::new(output))
+    }
+
     /// Execute `rustup install [...]`
     pub fn install(self) -> TResult<RustupOutput> {
         self.execute(OsStr::new("install"))
     }
 
+    /// Execute `rustup component [...]`
+    pub fn component(self) -> TResult<RustupOutput> {
+        self.execute(OsStr::new("component"))
+    }
+
     /// Execute `rustup show [...]`
     pub fn show(self) -> TResult<RustupOutput> {
         self.execute(OsStr::new("show"))
     }
 
+    /// Execute `rustup toolchain list`
+    pub fn toolchain_list(self) -> TResult<RustupOutput> {
+        self.with_args(&["list"]).execute(OsStr::new("toolchain"))
+    }
+
+    /// Execute `rustup --version`
+    pub fn version(self) -> TResult<RustupOutput> {
+        self.execute(OsStr::new("--version"))
+    }
+
     /// Execute a given `rustup` command.
     ///
     /// See also:
     /// * [RustupCommand::run](RustupCommand::run)
     /// * [RustupCommand::install](RustupCommand::run)
     /// * [RustupCommand::show](RustupCommand::run)
-    pub fn execute(mut self, cmd: &OsStr) -> TResult<RustupOutput> {
+    pub fn execute(self, cmd: &OsStr) -> TResult<RustupOutput> {
+        let (child, cancellation) = self.spawn(cmd)?;
+
+        let output = match &cancellation {
+            Some(token) => wait_with_cancellation(child, token, cmd)?,
+            None => child
+                .wait_with_output()
+                .map_err(|error| CargoMSRVError::Io {
+                    error,
+                    source: IoErrorSource::WaitForProcessAndCollectOutput(cmd.to_owned()),
+                })?,
+        };
+
+        Ok(RustupOutput::new(output))
+    }
+
+    /// Spawn the given `rustup` command, unless the token, if any, is cancelled already.
+    fn spawn(mut self, cmd: &OsStr) -> TResult<(Child, Option<CancellationToken>)> {
         debug!(
             cmd = ?cmd,
             args = ?self.args.as_slice()
@@ -81,23 +144,173 @@ impl RustupCommand {
         self.command.stdout(self.stdout);
         self.command.stderr(self.stderr);
 
+        if let Some(token) = &self.cancellation {
+            token.check()?;
+        }
+
         let child = self.command.spawn().map_err(|error| CargoMSRVError::Io {
             error,
             source: IoErrorSource::SpawnProcess(cmd.to_owned()),
         })?;
-        let output = child
-            .wait_with_output()
-            .map_err(|error| CargoMSRVError::Io {
-                error,
-                source: IoErrorSource::WaitForProcessAndCollectOutput(cmd.to_owned()),
-            })?;
-
-        Ok(RustupOutput {
-            output,
-            stdout: once_cell::sync::OnceCell::new(),
-            stderr: once_cell::sync::OnceCell::new(),
-        })
+
+        Ok((child, self.cancellation))
+    }
+}
+
+/// The pipe of a process from which a line of output was read.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub enum Pipe {
+    Stdout,
+    Stderr,
+}
+
+/// Like [`Child::wait_with_output`], but kills the process when the token is cancelled. The output
+/// is collected on separate threads, so a process which fills a pipe doesn't block.
+fn wait_with_cancellation(
+    mut child: Child,
+    token: &CancellationToken,
+    cmd: &OsStr,
+) -> TResult<Output> {
+    let wait_error = |error: std::io::Error| CargoMSRVError::Io {
+        error,
+        source: IoErrorSource::WaitForProcessAndCollectOutput(cmd.to_owned()),
+    };
+
+    let stdout = child.stdout.take().map(collect);
+    let stderr = child.stderr.take().map(collect);
+
+    let status = loop {
+        if token.is_cancelled() {
+            info!(cmd = ?cmd, "cancelled, killing process");
+            kill(&mut child, cmd)?;
+
+            return Err(CargoMSRVError::Cancelled);
+        }
+
+        match child.try_wait().map_err(wait_error)? {
+            Some(status) => break status,
+            None => std::thread::sleep(POLL_INTERVAL),
+        }
+    };
+
+    Ok(Output {
+        status,
+        stdout: join(stdout),
+        stderr: join(stderr),
+    })
+}
+
+/// Like [`wait_with_cancellation`], but each line of output is handed to `on_line` as soon as it's
+/// read. The process is killed when `on_line` fails.
+fn wait_streamed(
+    mut child: Child,
+    token: Option<&CancellationToken>,
+    cmd: &OsStr,
+    mut on_line: impl FnMut(Pipe, &str) -> TResult<()>,
+) -> TResult<Output> {
+    let (sender, receiver) = mpsc::channel();
+
+    let stdout = child
+        .stdout
+        .take()
+        .map(|pipe| stream(pipe, Pipe::Stdout, sender.clone()));
+    let stderr = child
+        .stderr
+        .take()
+        .map(|pipe| stream(pipe, Pipe::Stderr, sender.clone()));
+
+    // The channel disconnects once each pipe has been read to its end
+    drop(sender);
+
+    loop {
+        if token.map_or(false, CancellationToken::is_cancelled) {
+            info!(cmd = ?cmd, "cancelled, killing process");
+            kill(&mut child, cmd)?;
+
+            return Err(CargoMSRVError::Cancelled);
+        }
+
+        let (pipe, line) = match receiver.recv_timeout(POLL_INTERVAL) {
+            Ok(line) => line,
+            Err(RecvTimeoutError::Timeout) => continue,
+            Err(RecvTimeoutError::Disconnected) => break,
+        };
+
+        let line = String::from_utf8_lossy(&line);
+
+        if let Err(error) = on_line(pipe, line.trim_end_matches(|c| c == '\n' || c == '\r')) {
+            kill(&mut child, cmd)?;
+
+            return Err(error);
+        }
     }
+
+    let status = child.wait().map_err(|error| CargoMSRVError::Io {
+        error,
+        source: IoErrorSource::WaitForProcessAndCollectOutput(cmd.to_owned()),
+    })?;
+
+    Ok(Output {
+        status,
+        stdout: join(stdout),
+        stderr: join(stderr),
+    })
+}
+
+fn kill(child: &mut Child, cmd: &OsStr) -> TResult<()> {
+    // The process may have exited in the mean time, in which case it can't be killed
+    let _ = child.kill();
+
+    child
+        .wait()
+        .map(|_| ())
+        .map_err(|error| CargoMSRVError::Io {
+            error,
+            source: IoErrorSource::WaitForProcessAndCollectOutput(cmd.to_owned()),
+        })
+}
+
+/// The output collected by the given thread, if any.
+fn join(handle: Option<JoinHandle<Vec<u8>>>) -> Vec<u8> {
+    handle
+        .map(|handle| handle.join().unwrap_or_default())
+        .unwrap_or_default()
+}
+
+/// Read the given pipe to its end, on a separate thread.
+fn collect(mut pipe: impl Read + Send + 'static) -> JoinHandle<Vec<u8>> {
+    std::thread::spawn(move || {
+        let mut buffer = Vec::new();
+        // Output which can't be read is treated like no output
+        let _ = pipe.read_to_end(&mut buffer);
+        buffer
+    })
+}
+
+/// Read the given pipe to its end, on a separate thread, and send each line as soon as it's read.
+fn stream(
+    pipe: impl Read + Send + 'static,
+    from: Pipe,
+    sender: Sender<(Pipe, Vec<u8>)>,
+) -> JoinHandle<Vec<u8>> {
+    std::thread::spawn(move || {
+        let mut reader = BufReader::new(pipe);
+        let mut buffer = Vec::new();
+
+        loop {
+            let start = buffer.len();
+
+            // Output which can't be read is treated like the end of the output
+            match reader.read_until(b'\n', &mut buffer) {
+                Ok(0) | Err(_) => break,
+                Ok(_) => {
+                    let _ = sender.send((from, buffer[start..].to_vec()));
+                }
+            }
+        }
+
+        buffer
+    })
 }
 
 pub struct RustupOutput {
@@ -107,6 +320,14 @@ pub struct RustupOutput {
 }
 
 impl RustupOutput {
+    fn new(output: Output) -> Self {
+        Self {
+            output,
+            stdout: once_cell::sync::OnceCell::new(),
+            stderr: once_cell::sync::OnceCell::new(),
+        }
+    }
+
     pub fn stdout(&self) -> &str {
         self.stdout
             .get_or_init(|| String::from_utf8_lossy(&self.output.stdout).into_owned())
@@ -119,7 +340,75 @@ impl RustupOutput {
             .as_str()
     }
 
+    /// The raw bytes of stderr, which may not be valid UTF-8, unlike [`RustupOutput::stderr`].
+    pub fn stderr_bytes(&self) -> &[u8] {
+        &self.output.stderr
+    }
+
     pub fn exit_status(&self) -> std::process::ExitStatus {
         self.output.status
     }
 }
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cancelled_process_is_killed() {
+        let child = Command::new("sleep").arg("60").spawn().unwrap();
+
+        let token = CancellationToken::new();
+        token.cancel();
+
+        let result = wait_with_cancellation(child, &token, OsStr::new("sleep"));
+
+        assert!(matches!(result, Err(CargoMSRVError::Cancelled)));
+    }
+
+    #[test]
+    fn output_is_collected() {
+        let child = Command::new("echo")
+            .arg("compiled")
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+
+        let output =
+            wait_with_cancellation(child, &CancellationToken::new(), OsStr::new("echo")).unwrap();
+
+        assert!(output.status.success());
+        assert_eq!(output.stdout, b"compiled\n");
+    }
+
+    #[test]
+    fn output_is_streamed() {
+        let child = Command::new("sh")
+            .args(["-c", "echo checking; echo compiled; echo warning >&2"])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+
+        let mut lines = Vec::new();
+        let output = wait_streamed(child, None, OsStr::new("sh"), |pipe, line| {
+            lines.push((pipe, line.to_string()));
+            Ok(())
+        })
+        .unwrap();
+
+        // the order of the lines of different pipes is not defined
+        lines.sort();
+
+        assert_eq!(
+            lines,
+            vec![
+                (Pipe::Stdout, "checking".to_string()),
+                (Pipe::Stdout, "compiled".to_string()),
+                (Pipe::Stderr, "warning".to_string()),
+            ]
+        );
+        assert_eq!(output.stdout, b"checking\ncompiled\n");
+        assert_eq!(output.stderr, b"warning\n");
+    }
+}
@@ -3,28 +3,91 @@ use std::convert::{TryFrom, TryInto};
 use std::fmt;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
 
+use crate::cancellation::CancellationToken;
 use crate::cli::CargoCli;
+use crate::config::configuration::ConfigurationCmdConfig;
+use crate::config::discover::DiscoverCmdConfig;
+use crate::config::edition::EditionCmdConfig;
+use crate::config::history::HistoryCmdConfig;
+use crate::config::impact::ImpactCmdConfig;
+use crate::config::last_failure::LastFailureCmdConfig;
 use crate::config::list::ListCmdConfig;
+use crate::config::lower::LowerCmdConfig;
+use crate::config::matrix::MatrixCmdConfig;
+use crate::config::policy::PolicyCmdConfig;
+use crate::config::report::ReportCmdConfig;
+use crate::config::schema::SchemaCmdConfig;
+use crate::config::serve::ServeCmdConfig;
 use crate::config::set::SetCmdConfig;
+use crate::config::show::ShowCmdConfig;
+use crate::config::toolchain::SUBCOMMAND_COMPONENTS;
+use crate::config::value_source::{ValueSource, ValueSources};
 use crate::config::verify::VerifyCmdConfig;
 use crate::ctx::{ContextValues, LazyContext};
 use rust_releases::semver;
 
 use crate::error::{CargoMSRVError, TResult};
 use crate::log_level::LogLevel;
-use crate::manifest::bare_version;
-
+use crate::long_path::Sandbox;
+use crate::manifest::manifest_patch::ManifestPatch;
+use crate::manifest::{bare_version, syntax, version_bump};
+use crate::release_index_source::AuthToken;
+use crate::reporter::event::ProgressUnit;
+use crate::search_method::{Polarity, SearchSeeds};
+use crate::stubbed_copy::StubbedCopy;
+use crate::toolchain::ToolchainSpec;
+use crate::worktree::Worktree;
+
+pub(crate) mod check_command;
+pub(crate) mod check_runner;
+pub(crate) mod configuration;
+pub(crate) mod discover;
+pub(crate) mod edition;
+pub(crate) mod history;
+pub(crate) mod impact;
+pub(crate) mod last_failure;
 pub(crate) mod list;
+pub(crate) mod lower;
+pub(crate) mod matrix;
+pub(crate) mod output;
+pub(crate) mod policy;
+pub(crate) mod preferences;
+pub(crate) mod project_config;
+pub(crate) mod report;
+pub(crate) mod schema;
+pub(crate) mod serve;
 pub(crate) mod set;
+pub(crate) mod show;
+pub(crate) mod toolchain;
+pub(crate) mod value_source;
 pub(crate) mod verify;
 
-#[derive(Debug, Clone, Copy)]
+pub use crate::config::check_command::{
+    BinPolicy, CargoFlag, CheckTargets, CheckWith, DependencyKind, DocTests, FeatureSet,
+    PackageSelection,
+};
+pub use crate::config::check_runner::{
+    CheckHook, CheckHooks, CheckRunner, CleanAfter, LockfilePolicy,
+};
+pub use crate::config::output::{ColorChoice, EventFilter, Locale, WebhookOptions};
+pub(crate) use crate::config::toolchain::required_component;
+pub use crate::config::toolchain::{
+    CheckProfile, InstallProfile, ToolchainFileFormat, VersionResolution,
+};
+pub use crate::config::verify::GitHubCheckOptions;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum OutputFormat {
     /// Progress bar rendered to stderr
     Human,
     /// Json status updates printed to stdout
     Json,
+    /// Plain text printed to stderr, with the output of each check folded into a collapsible
+    /// log group, as supported by CI providers like GitHub Actions and GitLab CI
+    Ci,
     /// No output -- meant to be used for debugging and testing
     None,
 }
@@ -40,6 +103,7 @@ impl fmt::Display for OutputFormat {
         match self {
             Self::Human => write!(f, "human"),
             Self::Json => write!(f, "json"),
+            Self::Ci => write!(f, "ci"),
             Self::None => write!(f, "none"),
         }
     }
@@ -52,6 +116,7 @@ impl FromStr for OutputFormat {
         match s {
             "human" => Ok(Self::Human),
             "json" => Ok(Self::Json),
+            "ci" => Ok(Self::Ci),
             unknown => Err(CargoMSRVError::InvalidConfig(format!(
                 "Given output format '{}' is not valid",
                 unknown
@@ -62,11 +127,12 @@ impl FromStr for OutputFormat {
 
 impl OutputFormat {
     pub const JSON: &'static str = "json";
+    pub const CI: &'static str = "ci";
 
     /// A set of formats which may be given as a configuration option
     ///   through the CLI.
     pub fn custom_formats() -> &'static [&'static str] {
-        &["human", Self::JSON]
+        &["human", Self::JSON, Self::CI]
     }
 
     /// Parse the output format from the given `&str`.
@@ -77,6 +143,7 @@ impl OutputFormat {
     pub fn from_custom_format_str(item: &str) -> Self {
         match item {
             Self::JSON => Self::Json,
+            Self::CI => Self::Ci,
             _ => unreachable!(),
         }
     }
@@ -91,7 +158,7 @@ pub fn test_config_from_cli(cli: &CargoCli) -> TResult<Config> {
     Ok(config)
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum Action {
     // Determines the MSRV for a project
@@ -104,6 +171,34 @@ pub enum Action {
     Set,
     // Shows the MSRV of the current crate as specified in the Cargo manifest
     Show,
+    // Reports on the results of earlier runs
+    Report,
+    // Shows the resolved configuration
+    Config,
+    // Determines which editions are compatible with the MSRV
+    Edition,
+    // Lists the releases of the release index
+    Releases,
+    // Determines the MSRV for each combination of targets and features
+    Matrix,
+    // Validates the MSRV against the rules of a policy file
+    Policy,
+    // Lists the Cargo projects in a directory tree, with their MSRV
+    Discover,
+    // Validates the MSRV declared in the Cargo manifest
+    Validate,
+    // Locates the log of the most recent failed toolchain check
+    LastFailure,
+    // Previews whether adding a dependency would force the MSRV up
+    Impact,
+    // Prints the JSON schema of the json output
+    Schema,
+    // Reports how the declared MSRV evolved across the revisions of the repository
+    History,
+    // Serves the release index, installs toolchains, and runs checks, for other invocations
+    Serve,
+    // Estimates what it would take to lower the MSRV
+    Lower,
 }
 
 impl From<Action> for &'static str {
@@ -114,11 +209,25 @@ impl From<Action> for &'static str {
             Action::Verify => "verify",
             Action::Set => "set",
             Action::Show => "show",
+            Action::Report => "report",
+            Action::Config => "config",
+            Action::Edition => "edition",
+            Action::Releases => "releases",
+            Action::Matrix => "matrix",
+            Action::Policy => "policy",
+            Action::Discover => "discover",
+            Action::Validate => "validate",
+            Action::LastFailure => "last-failure",
+            Action::Impact => "impact",
+            Action::Schema => "schema",
+            Action::History => "history",
+            Action::Serve => "serve",
+            Action::Lower => "lower",
         }
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, schemars::JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ReleaseSource {
     RustChangelog,
@@ -183,11 +292,16 @@ impl fmt::Display for ReleaseSource {
     }
 }
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq, serde::Serialize)]
+#[derive(
+    Debug, Copy, Clone, Eq, PartialEq, serde::Serialize, serde::Deserialize, schemars::JsonSchema,
+)]
 #[serde(rename_all = "snake_case")]
 pub enum SearchMethod {
     Linear,
     Bisect,
+    Exhaustive,
+    /// Linear for a small search space, otherwise bisect, starting at the declared MSRV
+    Auto,
 }
 
 impl From<SearchMethod> for &'static str {
@@ -195,16 +309,58 @@ impl From<SearchMethod> for &'static str {
         match method {
             SearchMethod::Linear => "linear",
             SearchMethod::Bisect => "bisect",
+            SearchMethod::Exhaustive => "exhaustive",
+            SearchMethod::Auto => "auto",
+        }
+    }
+}
+
+impl FromStr for SearchMethod {
+    type Err = CargoMSRVError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "linear" => Ok(Self::Linear),
+            "bisect" => Ok(Self::Bisect),
+            "exhaustive" => Ok(Self::Exhaustive),
+            "auto" => Ok(Self::Auto),
+            unknown => Err(CargoMSRVError::InvalidConfig(format!(
+                "Given search method '{}' is not valid, expected 'auto', 'bisect', 'linear' or 'exhaustive'",
+                unknown
+            ))),
         }
     }
 }
 
 impl Default for SearchMethod {
     fn default() -> Self {
-        Self::Bisect
+        Self::Auto
     }
 }
 
+impl SearchMethod {
+    /// The largest search space which the `auto` search method searches linearly. Checking each
+    /// release of such a small search space takes at most one check more than a binary search.
+    pub const AUTO_LINEAR_MAX: usize = 3;
+
+    pub(crate) fn variants() -> &'static [&'static str] {
+        &["auto", "bisect", "linear", "exhaustive"]
+    }
+
+    /// The search method which runs for a search space of the given number of releases: the
+    /// `auto` search method picks a linear or a binary search, the others are used as is.
+    pub fn resolve(self, candidates: usize) -> Self {
+        match self {
+            Self::Auto if candidates <= Self::AUTO_LINEAR_MAX => Self::Linear,
+            Self::Auto => Self::Bisect,
+            method => method,
+        }
+    }
+}
+
+/// The default age, in hours, up to which a cached release index is used without revalidating it.
+pub const DEFAULT_MAX_INDEX_AGE_HOURS: u64 = 24;
+
 // TODO{foresterre}:
 //  This Config approach does not scale with the amount of options
 //  we now have. It also not allow us to easily merge several layers of option inputs,
@@ -213,21 +369,80 @@ impl Default for SearchMethod {
 pub struct Config<'a> {
     action: Action,
     target: String,
+    host: Option<String>,
+    check_profile: Option<CheckProfile>,
+    check_runner: CheckRunner,
+    install_profile: InstallProfile,
+    remote_check: Option<String>,
+    rustc_wrapper: Option<String>,
     check_command: Vec<&'a str>,
+    bin_policy_args: Vec<&'a str>,
+    cargo_flags: Vec<CargoFlag>,
+    packages: PackageSelection,
+    features: FeatureSet,
+    bin_policy: BinPolicy,
+    dependency_kinds: Vec<DependencyKind>,
+    doc_tests: DocTests,
+    retries_on_failure: u32,
+    reuse_unit_graph: bool,
+    verbose: bool,
+    manifest_patch: Option<ManifestPatch>,
+    target_dir: Option<String>,
+    clean_after: CleanAfter,
+    check_with: CheckWith,
+    check_hooks: CheckHooks,
     crate_path: Option<PathBuf>,
     manifest_path: Option<PathBuf>,
+    worktree: Option<Arc<Worktree>>,
+    stubbed_copy: Option<Arc<StubbedCopy>>,
+    rustc_out_dir: Option<Arc<Sandbox>>,
     include_all_patch_releases: bool,
+    version_resolution: VersionResolution,
     minimum_version: Option<bare_version::BareVersion>,
     maximum_version: Option<bare_version::BareVersion>,
+    excluded_versions: Vec<bare_version::BareVersion>,
+    manifest_requirement: Option<syntax::ManifestRequirement>,
+    bump_policy: Option<version_bump::BumpPolicy>,
+    declared_msrv: Option<bare_version::BareVersion>,
     search_method: SearchMethod,
+    polarity: Polarity,
+    explain_search: bool,
+    max_checks: Option<u64>,
+    search_seeds: SearchSeeds,
     output_toolchain_file: bool,
+    toolchain_file_format: ToolchainFileFormat,
     write_msrv: bool,
+    /// Write the newest patch release of the minor version of the MSRV instead
+    prefer_newest_patch: bool,
+    write_result: Option<PathBuf>,
     ignore_lockfile: bool,
+    lockfile_policy: LockfilePolicy,
+    fetch_once: bool,
+    prefetch_toolchains: bool,
     output_format: OutputFormat,
+    locale: Locale,
+    color: ColorChoice,
     release_source: ReleaseSource,
+    github_token: Option<AuthToken>,
+    max_index_age: Duration,
+    cancellation_token: Option<CancellationToken>,
+    progress_unit: Option<ProgressUnit>,
     tracing_config: Option<TracingOptions>,
+    scratch_dir: Option<PathBuf>,
+    sandbox_dir: Option<PathBuf>,
+    setup: bool,
+    assume_yes: bool,
+    server: Option<String>,
+    webhook: Option<WebhookOptions>,
+    otel_endpoint: Option<String>,
+    event_filter: Option<EventFilter>,
+    validate_output: bool,
+    github_check: Option<GitHubCheckOptions>,
+    batch: Option<BatchOptions>,
     no_read_min_edition: Option<semver::Version>,
     no_check_feedback: bool,
+    value_sources: ValueSources,
+    config_files: Vec<PathBuf>,
 
     sub_command_config: SubCommandConfig,
     ctx: LazyContext,
@@ -238,21 +453,79 @@ impl<'a> Config<'a> {
         Self {
             action,
             target: target.into(),
+            host: None,
+            check_profile: None,
+            check_runner: CheckRunner::default(),
+            install_profile: InstallProfile::default(),
+            remote_check: None,
+            rustc_wrapper: None,
             check_command: vec!["cargo", "check"],
+            bin_policy_args: Vec::new(),
+            cargo_flags: Vec::new(),
+            packages: PackageSelection::default(),
+            features: FeatureSet::default(),
+            bin_policy: BinPolicy::default(),
+            dependency_kinds: DependencyKind::all(),
+            doc_tests: DocTests::default(),
+            retries_on_failure: 0,
+            reuse_unit_graph: false,
+            verbose: false,
+            manifest_patch: None,
+            target_dir: None,
+            clean_after: CleanAfter::default(),
+            check_with: CheckWith::default(),
+            check_hooks: CheckHooks::default(),
             crate_path: None,
             manifest_path: None,
+            worktree: None,
+            stubbed_copy: None,
+            rustc_out_dir: None,
             include_all_patch_releases: false,
+            version_resolution: VersionResolution::default(),
             minimum_version: None,
             maximum_version: None,
+            excluded_versions: Vec::new(),
+            manifest_requirement: None,
+            bump_policy: None,
+            declared_msrv: None,
             search_method: SearchMethod::default(),
+            polarity: Polarity::default(),
+            explain_search: false,
+            max_checks: None,
+            search_seeds: SearchSeeds::default(),
             output_toolchain_file: false,
+            toolchain_file_format: ToolchainFileFormat::default(),
             write_msrv: false,
+            prefer_newest_patch: false,
+            write_result: None,
             ignore_lockfile: false,
+            lockfile_policy: LockfilePolicy::default(),
+            fetch_once: false,
+            prefetch_toolchains: false,
             output_format: OutputFormat::Human,
+            locale: Locale::default(),
+            color: ColorChoice::default(),
             release_source: ReleaseSource::RustChangelog,
+            github_token: None,
+            max_index_age: Duration::from_secs(DEFAULT_MAX_INDEX_AGE_HOURS * 3600),
+            cancellation_token: None,
+            progress_unit: None,
             tracing_config: None,
+            scratch_dir: None,
+            sandbox_dir: None,
+            setup: false,
+            assume_yes: false,
+            server: None,
+            webhook: None,
+            otel_endpoint: None,
+            event_filter: None,
+            validate_output: false,
+            github_check: None,
+            batch: None,
             no_read_min_edition: None,
             no_check_feedback: false,
+            value_sources: ValueSources::default(),
+            config_files: Vec::new(),
             sub_command_config: SubCommandConfig::None,
             ctx: LazyContext::default(),
         }
@@ -266,12 +539,221 @@ impl<'a> Config<'a> {
         &self.target
     }
 
+    /// The platform on which the toolchains run, if it was given. Otherwise, they run on the
+    /// target.
+    pub fn host(&self) -> Option<&str> {
+        self.host.as_deref()
+    }
+
+    /// The preset of the check, if one was given with `--profile`.
+    pub fn check_profile(&self) -> Option<CheckProfile> {
+        self.check_profile
+    }
+
+    /// The toolchain of the given Rust version, which builds for the target, and runs on the host.
+    pub fn toolchain_spec<'s>(&'s self, version: &'s semver::Version) -> ToolchainSpec<'s> {
+        let toolchain = ToolchainSpec::new(version, &self.target);
+
+        match self.host() {
+            Some(host) => toolchain.with_host(host),
+            None => toolchain,
+        }
+    }
+
+    /// How each toolchain check is run.
+    pub fn check_runner(&self) -> CheckRunner {
+        self.check_runner
+    }
+
+    /// The rustup profile with which the toolchains are installed.
+    pub fn install_profile(&self) -> InstallProfile {
+        self.install_profile
+    }
+
+    /// The rustup components which the check command requires, on top of the minimal profile, like
+    /// `clippy` for `cargo clippy`.
+    pub fn required_components(&self) -> Vec<&'static str> {
+        if let CheckWith::Rustc { .. } = self.check_with {
+            return Vec::new();
+        }
+
+        let subcommand = self
+            .check_command
+            .iter()
+            .skip_while(|word| **word != "cargo")
+            .skip(1)
+            .find(|word| !word.starts_with('-') && !word.starts_with('+'));
+
+        SUBCOMMAND_COMPONENTS
+            .iter()
+            .filter(|(command, _)| Some(command) == subcommand)
+            .map(|(_, component)| *component)
+            .collect()
+    }
+
+    /// The URL of the remote execution service which runs each check, if the checks are not run
+    /// locally.
+    pub fn remote_check(&self) -> Option<&str> {
+        self.remote_check.as_deref()
+    }
+
+    /// The compiler wrapper, like `sccache`, which is given to each check as `RUSTC_WRAPPER`.
+    pub fn rustc_wrapper(&self) -> Option<&str> {
+        self.rustc_wrapper.as_deref()
+    }
+
     pub fn check_command(&self) -> &Vec<&'a str> {
         &self.check_command
     }
 
     pub fn check_command_string(&self) -> String {
-        self.check_command.join(" ")
+        match &self.check_with {
+            CheckWith::Cargo => {
+                let mut cmd = self.check_command.clone();
+                cmd.extend(self.packages.args_with("--workspace"));
+                cmd.join(" ")
+            }
+            CheckWith::Rustc { file, .. } => format!("{} {}", self.check_command.join(" "), file),
+        }
+    }
+
+    pub fn check_with(&self) -> &CheckWith {
+        &self.check_with
+    }
+
+    /// The commands which are run before and after each toolchain check.
+    pub fn check_hooks(&self) -> &CheckHooks {
+        &self.check_hooks
+    }
+
+    /// The cargo flags which are added to the check command, if supported by the toolchain.
+    pub fn cargo_flags(&self) -> &[CargoFlag] {
+        &self.cargo_flags
+    }
+
+    /// The workspace members which are checked by the default check command.
+    pub fn packages(&self) -> &PackageSelection {
+        &self.packages
+    }
+
+    /// The features which are enabled by the default check command.
+    pub fn features(&self) -> &FeatureSet {
+        &self.features
+    }
+
+    /// How binaries which require features are treated by the default check command.
+    pub fn bin_policy(&self) -> BinPolicy {
+        self.bin_policy
+    }
+
+    /// The kinds of dependencies which take part in the check.
+    pub fn dependency_kinds(&self) -> &[DependencyKind] {
+        &self.dependency_kinds
+    }
+
+    /// The kinds of dependencies whose tables are removed from the Cargo manifest during a check.
+    pub fn excluded_dependency_kinds(&self) -> Vec<DependencyKind> {
+        DependencyKind::all()
+            .into_iter()
+            .filter(|kind| !self.dependency_kinds.contains(kind))
+            .collect()
+    }
+
+    /// Whether doc tests are checked, once the check command passed.
+    pub fn doc_tests(&self) -> DocTests {
+        self.doc_tests
+    }
+
+    /// How many times a failed toolchain check is retried, before the toolchain is considered
+    /// incompatible.
+    pub fn retries_on_failure(&self) -> u32 {
+        self.retries_on_failure
+    }
+
+    /// Whether the outcome of an earlier check of a toolchain is reused, when the unit graph of
+    /// the check and the sources of the local packages are unchanged. Experimental.
+    pub fn reuse_unit_graph(&self) -> bool {
+        self.reuse_unit_graph
+    }
+
+    /// Whether the output of the check command is reported line by line, while it runs.
+    pub fn verbose(&self) -> bool {
+        self.verbose
+    }
+
+    /// The patch which is merged into the Cargo manifest before each check, if any.
+    pub fn manifest_patch(&self) -> Option<&ManifestPatch> {
+        self.manifest_patch.as_ref()
+    }
+
+    /// The target directory which is given to the default check command, with `--target-dir`.
+    pub fn target_dir(&self) -> Option<&str> {
+        self.target_dir.as_deref()
+    }
+
+    /// When the build artifacts of a check are removed, once the toolchain has been checked.
+    pub fn clean_after(&self) -> CleanAfter {
+        self.clean_after
+    }
+
+    /// The check command for a toolchain of the given Rust version, i.e. the check command
+    /// extended with the package selection, the features, the target directory and those cargo
+    /// flags which the cargo of this toolchain supports, or, when checking with `rustc`, with the
+    /// file to compile.
+    pub fn check_command_for_version(&self, version: &semver::Version) -> Vec<&str> {
+        let mut cmd: Vec<&str> = self.check_command.clone();
+        cmd.extend(self.packages.cargo_args(version));
+
+        match &self.check_with {
+            CheckWith::Cargo => {
+                cmd.extend(self.features.cargo_args());
+                cmd.extend(self.target_dir_args());
+            }
+            CheckWith::Rustc { file, out_dir } => {
+                cmd.extend(["--out-dir", out_dir.as_str(), file.as_str()]);
+            }
+        }
+
+        self.extend_with_cargo_flags(&mut cmd, version);
+        cmd.extend_from_slice(&self.bin_policy_args);
+
+        cmd
+    }
+
+    /// The command which checks the doc tests for a toolchain of the given Rust version, if doc
+    /// tests are checked. Like the check command, it's extended with the package selection, the
+    /// features and the supported cargo flags.
+    pub fn doc_test_command_for_version(&self, version: &semver::Version) -> Option<Vec<&str>> {
+        let mut cmd = self.doc_tests.cargo_command()?.to_vec();
+        cmd.extend(self.packages.cargo_args(version));
+        cmd.extend(self.features.cargo_args());
+        cmd.extend(self.target_dir_args());
+
+        self.extend_with_cargo_flags(&mut cmd, version);
+
+        Some(cmd)
+    }
+
+    /// The `--target-dir` argument of cargo, if a target directory is configured.
+    pub fn target_dir_args(&self) -> Vec<&str> {
+        self.target_dir
+            .as_deref()
+            .map(|dir| vec!["--target-dir", dir])
+            .unwrap_or_default()
+    }
+
+    fn extend_with_cargo_flags(&self, cmd: &mut Vec<&str>, version: &semver::Version) {
+        for flag in &self.cargo_flags {
+            if flag.is_supported_by(version) {
+                cmd.push(flag.flag());
+            } else {
+                info!(
+                    flag = flag.flag(),
+                    %version,
+                    "skipping cargo flag, since it is not supported by this toolchain"
+                );
+            }
+        }
     }
 
     /// Should not be used directly. Use the context instead.
@@ -284,10 +766,27 @@ impl<'a> Config<'a> {
         self.manifest_path.as_deref()
     }
 
+    /// The temporary worktree in which the crate is checked at the revision given with
+    /// `--at-rev`, instead of in the working tree of the crate.
+    pub(crate) fn worktree(&self) -> Option<&Worktree> {
+        self.worktree.as_deref()
+    }
+
+    /// The temporary copy of the crate in which the files matched by `--exclude-path` are stubbed
+    /// out, and which is checked instead of the crate.
+    pub(crate) fn stubbed_copy(&self) -> Option<&StubbedCopy> {
+        self.stubbed_copy.as_deref()
+    }
+
     pub fn include_all_patch_releases(&self) -> bool {
         self.include_all_patch_releases
     }
 
+    /// How an MSRV which matches none of the releases of the index is resolved to a release.
+    pub fn version_resolution(&self) -> VersionResolution {
+        self.version_resolution
+    }
+
     pub fn minimum_version(&self) -> Option<&bare_version::BareVersion> {
         self.minimum_version.as_ref()
     }
@@ -296,35 +795,202 @@ impl<'a> Config<'a> {
         self.maximum_version.as_ref()
     }
 
+    /// The versions which are left out of the search space, e.g. because they're known to be
+    /// broken for the target.
+    pub fn excluded_versions(&self) -> &[bare_version::BareVersion] {
+        &self.excluded_versions
+    }
+
+    /// The manifest feature of the crate which requires the most recent cargo to read the
+    /// manifest, if any.
+    pub fn manifest_requirement(&self) -> Option<&syntax::ManifestRequirement> {
+        self.manifest_requirement.as_ref()
+    }
+
+    /// How the crate version is bumped, when the MSRV which is found is more recent than the MSRV
+    /// which is declared in the manifest. Absent if the manifest doesn't declare both.
+    pub fn bump_policy(&self) -> Option<&version_bump::BumpPolicy> {
+        self.bump_policy.as_ref()
+    }
+
+    /// The MSRV which is declared in the Cargo manifest, at which the `auto` search method starts
+    /// its binary search.
+    pub fn declared_msrv(&self) -> Option<&bare_version::BareVersion> {
+        self.declared_msrv.as_ref()
+    }
+
     pub fn search_method(&self) -> SearchMethod {
         self.search_method
     }
 
+    pub fn polarity(&self) -> Polarity {
+        self.polarity
+    }
+
+    pub fn explain_search(&self) -> bool {
+        self.explain_search
+    }
+
+    /// The maximum number of toolchain checks which a search may run, if bounded.
+    pub fn max_checks(&self) -> Option<u64> {
+        self.max_checks
+    }
+
+    /// The outcomes which are known beforehand, and narrow the search space.
+    pub fn search_seeds(&self) -> &SearchSeeds {
+        &self.search_seeds
+    }
+
     pub fn output_toolchain_file(&self) -> bool {
         self.output_toolchain_file
     }
 
+    pub fn toolchain_file_format(&self) -> ToolchainFileFormat {
+        self.toolchain_file_format
+    }
+
     pub fn write_msrv(&self) -> bool {
         self.write_msrv
     }
 
+    /// Whether the newest patch release of the minor version of the MSRV is written to the Cargo
+    /// manifest, e.g. `1.64.1` instead of `1.64.0`, with `--prefer-newest-patch`.
+    pub fn prefer_newest_patch(&self) -> bool {
+        self.prefer_newest_patch
+    }
+
+    /// The file to which the found MSRV is written, with `--write-result`.
+    pub fn write_result(&self) -> Option<&Path> {
+        self.write_result.as_deref()
+    }
+
     pub fn ignore_lockfile(&self) -> bool {
         self.ignore_lockfile
     }
 
+    /// How a toolchain is checked, when its cargo can't read the lockfile of the crate.
+    pub fn lockfile_policy(&self) -> LockfilePolicy {
+        self.lockfile_policy
+    }
+
+    /// Whether the dependencies are fetched once, so each toolchain is checked offline, and only
+    /// has to compile them.
+    pub fn fetch_once(&self) -> bool {
+        self.fetch_once
+    }
+
+    /// Whether the toolchains which may be checked next are installed in the background, while
+    /// a toolchain is checked.
+    pub fn prefetch_toolchains(&self) -> bool {
+        self.prefetch_toolchains
+    }
+
     pub fn output_format(&self) -> OutputFormat {
         self.output_format
     }
 
+    /// The language of the human output.
+    pub fn locale(&self) -> Locale {
+        self.locale
+    }
+
+    /// When the human output is colored.
+    pub fn color(&self) -> ColorChoice {
+        self.color
+    }
+
     pub fn release_source(&self) -> ReleaseSource {
         self.release_source
     }
 
+    pub fn github_token(&self) -> Option<&AuthToken> {
+        self.github_token.as_ref()
+    }
+
+    /// The age up to which a cached release index is used, without revalidating it.
+    pub fn max_index_age(&self) -> Duration {
+        self.max_index_age
+    }
+
+    /// The token by which the running action can be cancelled, if any.
+    pub fn cancellation_token(&self) -> Option<&CancellationToken> {
+        self.cancellation_token.as_ref()
+    }
+
+    /// The unit of a run of several units which is configured, like a configuration of the matrix,
+    /// if any. The progress of the search is reported together with the unit.
+    pub fn progress_unit(&self) -> Option<&ProgressUnit> {
+        self.progress_unit.as_ref()
+    }
+
     /// Options as to configure tracing (and logging) settings. If absent, tracing will be disabled.
     pub fn tracing(&self) -> Option<&TracingOptions> {
         self.tracing_config.as_ref()
     }
 
+    /// The directory in which temporary artifacts are written, if configured. When absent,
+    /// temporary artifacts are written next to the artifact they replace, or to the system
+    /// temporary directory.
+    pub fn scratch_dir(&self) -> Option<&Path> {
+        self.scratch_dir.as_deref()
+    }
+
+    /// The directory in which sandboxes, like copies of the crate, are created, if configured:
+    /// the sandbox directory, or else the scratch directory. When absent, sandboxes are created in
+    /// the system temporary directory.
+    pub fn sandbox_root(&self) -> Option<&Path> {
+        self.sandbox_dir.as_deref().or_else(|| self.scratch_dir())
+    }
+
+    /// Whether missing prerequisites may be installed, when cargo-msrv knows how to install them.
+    pub fn setup(&self) -> bool {
+        self.setup
+    }
+
+    /// Whether the install commands of `--setup` are run without asking for confirmation.
+    pub fn assume_yes(&self) -> bool {
+        self.assume_yes
+    }
+
+    /// The address of the server started with `cargo msrv serve`, which provides the release
+    /// index and installs the toolchains, if any.
+    pub fn server(&self) -> Option<&str> {
+        self.server.as_deref()
+    }
+
+    /// Options as to notify a webhook of the results. If absent, no webhook will be notified.
+    pub fn webhook(&self) -> Option<&WebhookOptions> {
+        self.webhook.as_ref()
+    }
+
+    /// The OpenTelemetry collector to which the toolchain checks are exported as spans, if any.
+    pub fn otel_endpoint(&self) -> Option<&str> {
+        self.otel_endpoint.as_deref()
+    }
+
+    /// The kinds of events which are written by the json output. If absent, all events are written.
+    pub fn event_filter(&self) -> Option<&EventFilter> {
+        self.event_filter.as_ref()
+    }
+
+    /// Whether each event is validated against the schema of the events, as printed by
+    /// `cargo msrv schema`.
+    pub fn validate_output(&self) -> bool {
+        self.validate_output
+    }
+
+    /// Options as to report the outcome of `cargo msrv verify` as a GitHub check run. If absent,
+    /// no check run is created.
+    pub fn github_check(&self) -> Option<&GitHubCheckOptions> {
+        self.github_check.as_ref()
+    }
+
+    /// Options as to find the MSRV of each crate read from stdin. If absent, the MSRV is only
+    /// found for the crate given by the crate path or manifest path.
+    pub fn batch(&self) -> Option<&BatchOptions> {
+        self.batch.as_ref()
+    }
+
     pub fn no_read_min_version(&self) -> Option<&semver::Version> {
         self.no_read_min_edition.as_ref()
     }
@@ -333,6 +999,16 @@ impl<'a> Config<'a> {
         self.no_check_feedback
     }
 
+    /// Where the values of the configuration options came from.
+    pub fn value_sources(&self) -> &ValueSources {
+        &self.value_sources
+    }
+
+    /// The project config files which were read, from the workspace root to the crate.
+    pub fn config_files(&self) -> &[PathBuf] {
+        &self.config_files
+    }
+
     pub fn sub_command_config(&self) -> &SubCommandConfig {
         &self.sub_command_config
     }
@@ -367,6 +1043,26 @@ impl<'a> ConfigBuilder<'a> {
         }
     }
 
+    /// Run the toolchains on the given host, e.g. to check a crate for a target which has no
+    /// toolchain of its own.
+    pub fn host(mut self, host: &str) -> Self {
+        self.inner.host = Some(host.to_string());
+        self
+    }
+
+    /// Check for the default target of the given profile. The toolchains keep running on the
+    /// current target, unless another host is given. Must be set before the target and host which
+    /// are given explicitly.
+    pub fn check_profile(mut self, profile: CheckProfile) -> Self {
+        if self.inner.host.is_none() && !self.inner.target.is_empty() {
+            self.inner.host = Some(self.inner.target.clone());
+        }
+
+        self.inner.target = profile.default_target().to_string();
+        self.inner.check_profile = Some(profile);
+        self
+    }
+
     pub fn mode_intent(mut self, mode_intent: Action) -> Self {
         self.inner.action = mode_intent;
         self
@@ -377,11 +1073,129 @@ impl<'a> ConfigBuilder<'a> {
         self
     }
 
+    pub fn check_runner(mut self, runner: CheckRunner) -> Self {
+        self.inner.check_runner = runner;
+        self
+    }
+
+    pub fn install_profile(mut self, profile: InstallProfile) -> Self {
+        self.inner.install_profile = profile;
+        self
+    }
+
+    pub fn remote_check(mut self, url: impl Into<String>) -> Self {
+        self.inner.remote_check = Some(url.into());
+        self
+    }
+
+    pub fn rustc_wrapper(mut self, wrapper: impl Into<String>) -> Self {
+        self.inner.rustc_wrapper = Some(wrapper.into());
+        self
+    }
+
     pub fn check_command(mut self, cmd: Vec<&'a str>) -> Self {
         self.inner.check_command = cmd;
         self
     }
 
+    /// Extends the check command with the arguments which select the given targets.
+    pub fn check_targets(mut self, targets: CheckTargets) -> Self {
+        self.inner
+            .check_command
+            .extend_from_slice(targets.cargo_args());
+        self
+    }
+
+    /// Cargo flags which are added to the check command, for toolchains which support them.
+    pub fn cargo_flags(mut self, flags: Vec<CargoFlag>) -> Self {
+        self.inner.cargo_flags = flags;
+        self
+    }
+
+    /// Check by compiling the given file with `rustc`, instead of running the check command.
+    /// The file is compiled as a library, so it doesn't need a `main` function.
+    pub fn check_with_rustc(mut self, file: &Path) -> Self {
+        let out_dir = Sandbox::new(self.inner.sandbox_root(), "cargo-msrv-rustc");
+
+        self.inner.check_command = vec!["rustc", "--crate-type", "lib"];
+        self.inner.check_with = CheckWith::Rustc {
+            file: file.display().to_string(),
+            out_dir: out_dir.path().display().to_string(),
+        };
+        self.inner.rustc_out_dir = Some(Arc::new(out_dir));
+        self
+    }
+
+    pub fn check_hooks(mut self, hooks: CheckHooks) -> Self {
+        self.inner.check_hooks = hooks;
+        self
+    }
+
+    /// Select the workspace members which are checked by the default check command.
+    pub fn packages(mut self, packages: PackageSelection) -> Self {
+        self.inner.packages = packages;
+        self
+    }
+
+    pub fn features(mut self, features: FeatureSet) -> Self {
+        self.inner.features = features;
+        self
+    }
+
+    pub fn bin_policy(mut self, policy: BinPolicy) -> Self {
+        self.inner.bin_policy = policy;
+        self
+    }
+
+    /// The arguments which apply the binary policy to the check command, once the targets of the
+    /// crate are resolved.
+    pub(crate) fn bin_policy_args(mut self, args: &'a [String]) -> Self {
+        self.inner.bin_policy_args = args.iter().map(String::as_str).collect();
+        self
+    }
+
+    /// Check the doc tests, once the check command passed.
+    pub fn dependency_kinds(mut self, kinds: Vec<DependencyKind>) -> Self {
+        self.inner.dependency_kinds = kinds;
+        self
+    }
+
+    pub fn doc_tests(mut self, doc_tests: DocTests) -> Self {
+        self.inner.doc_tests = doc_tests;
+        self
+    }
+
+    pub fn retries_on_failure(mut self, retries: u32) -> Self {
+        self.inner.retries_on_failure = retries;
+        self
+    }
+
+    pub fn reuse_unit_graph(mut self, answer: bool) -> Self {
+        self.inner.reuse_unit_graph = answer;
+        self
+    }
+
+    pub fn verbose(mut self, answer: bool) -> Self {
+        self.inner.verbose = answer;
+        self
+    }
+
+    pub fn manifest_patch(mut self, patch: Option<ManifestPatch>) -> Self {
+        self.inner.manifest_patch = patch;
+        self
+    }
+
+    /// The target directory which is given to the default check command, with `--target-dir`.
+    pub fn target_dir(mut self, dir: Option<String>) -> Self {
+        self.inner.target_dir = dir;
+        self
+    }
+
+    pub fn clean_after(mut self, clean_after: CleanAfter) -> Self {
+        self.inner.clean_after = clean_after;
+        self
+    }
+
     pub fn crate_path<P: AsRef<Path>>(mut self, path: Option<P>) -> Self {
         self.inner.crate_path = path.map(|p| PathBuf::from(p.as_ref()));
         self
@@ -392,15 +1206,42 @@ impl<'a> ConfigBuilder<'a> {
         self
     }
 
+    pub(crate) fn worktree(mut self, worktree: Worktree) -> Self {
+        self.inner.worktree = Some(Arc::new(worktree));
+        self
+    }
+
+    pub(crate) fn stubbed_copy(mut self, copy: StubbedCopy) -> Self {
+        self.inner.stubbed_copy = Some(Arc::new(copy));
+        self
+    }
+
     pub fn get_crate_path(&self) -> Option<&Path> {
         self.inner.crate_path.as_deref()
     }
 
+    pub fn get_manifest_path(&self) -> Option<&Path> {
+        self.inner.manifest_path.as_deref()
+    }
+
+    pub fn get_action(&self) -> Action {
+        self.inner.action
+    }
+
+    pub fn get_search_method(&self) -> SearchMethod {
+        self.inner.search_method
+    }
+
     pub fn include_all_patch_releases(mut self, answer: bool) -> Self {
         self.inner.include_all_patch_releases = answer;
         self
     }
 
+    pub fn version_resolution(mut self, resolution: VersionResolution) -> Self {
+        self.inner.version_resolution = resolution;
+        self
+    }
+
     pub fn minimum_version(mut self, version: bare_version::BareVersion) -> Self {
         self.inner.minimum_version = Some(version);
         self
@@ -411,41 +1252,203 @@ impl<'a> ConfigBuilder<'a> {
         self
     }
 
+    pub fn excluded_versions(mut self, versions: Vec<bare_version::BareVersion>) -> Self {
+        self.inner.excluded_versions = versions;
+        self
+    }
+
+    pub fn manifest_requirement(
+        mut self,
+        requirement: Option<syntax::ManifestRequirement>,
+    ) -> Self {
+        self.inner.manifest_requirement = requirement;
+        self
+    }
+
+    pub fn bump_policy(mut self, policy: Option<version_bump::BumpPolicy>) -> Self {
+        self.inner.bump_policy = policy;
+        self
+    }
+
+    pub fn declared_msrv(mut self, msrv: Option<bare_version::BareVersion>) -> Self {
+        self.inner.declared_msrv = msrv;
+        self
+    }
+
     pub fn search_method(mut self, method: SearchMethod) -> Self {
         self.inner.search_method = method;
         self
     }
 
+    pub fn polarity(mut self, polarity: Polarity) -> Self {
+        self.inner.polarity = polarity;
+        self
+    }
+
+    pub fn explain_search(mut self, choice: bool) -> Self {
+        self.inner.explain_search = choice;
+        self
+    }
+
+    pub fn max_checks(mut self, max_checks: Option<u64>) -> Self {
+        self.inner.max_checks = max_checks;
+        self
+    }
+
+    pub fn search_seeds(mut self, seeds: SearchSeeds) -> Self {
+        self.inner.search_seeds = seeds;
+        self
+    }
+
     pub fn output_toolchain_file(mut self, choice: bool) -> Self {
         self.inner.output_toolchain_file = choice;
         self
     }
 
+    pub fn toolchain_file_format(mut self, format: ToolchainFileFormat) -> Self {
+        self.inner.toolchain_file_format = format;
+        self
+    }
+
     pub fn write_msrv(mut self, choice: bool) -> Self {
         self.inner.write_msrv = choice;
         self
     }
 
+    pub fn prefer_newest_patch(mut self, choice: bool) -> Self {
+        self.inner.prefer_newest_patch = choice;
+        self
+    }
+
+    pub fn write_result(mut self, path: Option<PathBuf>) -> Self {
+        self.inner.write_result = path;
+        self
+    }
+
     pub fn ignore_lockfile(mut self, choice: bool) -> Self {
         self.inner.ignore_lockfile = choice;
         self
     }
 
+    pub fn lockfile_policy(mut self, policy: LockfilePolicy) -> Self {
+        self.inner.lockfile_policy = policy;
+        self
+    }
+
+    pub fn fetch_once(mut self, choice: bool) -> Self {
+        self.inner.fetch_once = choice;
+        self
+    }
+
+    pub fn prefetch_toolchains(mut self, choice: bool) -> Self {
+        self.inner.prefetch_toolchains = choice;
+        self
+    }
+
     pub fn output_format(mut self, output_format: OutputFormat) -> Self {
         self.inner.output_format = output_format;
         self
     }
 
+    pub fn locale(mut self, locale: Locale) -> Self {
+        self.inner.locale = locale;
+        self
+    }
+
+    pub fn color(mut self, color: ColorChoice) -> Self {
+        self.inner.color = color;
+        self
+    }
+
     pub fn release_source(mut self, release_source: ReleaseSource) -> Self {
         self.inner.release_source = release_source;
         self
     }
 
+    pub fn github_token(mut self, token: Option<AuthToken>) -> Self {
+        self.inner.github_token = token;
+        self
+    }
+
+    pub fn max_index_age(mut self, max_index_age: Duration) -> Self {
+        self.inner.max_index_age = max_index_age;
+        self
+    }
+
+    pub fn cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.inner.cancellation_token = Some(token);
+        self
+    }
+
+    pub fn progress_unit(mut self, unit: Option<ProgressUnit>) -> Self {
+        self.inner.progress_unit = unit;
+        self
+    }
+
     pub fn tracing_config(mut self, cfg: TracingOptions) -> Self {
         self.inner.tracing_config = Some(cfg);
         self
     }
 
+    /// Write temporary artifacts to the given directory. Must be set before
+    /// [`ConfigBuilder::check_with_rustc`], since the output directory of `rustc` is located in it.
+    pub fn scratch_dir(mut self, dir: Option<PathBuf>) -> Self {
+        self.inner.scratch_dir = dir;
+        self
+    }
+
+    /// Create sandboxes in the given directory. Like [`ConfigBuilder::scratch_dir`], it must be
+    /// set before [`ConfigBuilder::check_with_rustc`].
+    pub fn sandbox_dir(mut self, dir: Option<PathBuf>) -> Self {
+        self.inner.sandbox_dir = dir;
+        self
+    }
+
+    pub fn setup(mut self, setup: bool) -> Self {
+        self.inner.setup = setup;
+        self
+    }
+
+    pub fn assume_yes(mut self, assume_yes: bool) -> Self {
+        self.inner.assume_yes = assume_yes;
+        self
+    }
+
+    pub fn server(mut self, address: Option<String>) -> Self {
+        self.inner.server = address;
+        self
+    }
+
+    pub fn webhook(mut self, options: WebhookOptions) -> Self {
+        self.inner.webhook = Some(options);
+        self
+    }
+
+    pub fn otel_endpoint(mut self, endpoint: Option<String>) -> Self {
+        self.inner.otel_endpoint = endpoint;
+        self
+    }
+
+    pub fn event_filter(mut self, filter: EventFilter) -> Self {
+        self.inner.event_filter = Some(filter);
+        self
+    }
+
+    pub fn validate_output(mut self, validate: bool) -> Self {
+        self.inner.validate_output = validate;
+        self
+    }
+
+    pub fn github_check(mut self, options: GitHubCheckOptions) -> Self {
+        self.inner.github_check = Some(options);
+        self
+    }
+
+    pub fn batch(mut self, options: BatchOptions) -> Self {
+        self.inner.batch = Some(options);
+        self
+    }
+
     pub fn no_read_min_edition(mut self, version: semver::Version) -> Self {
         self.inner.no_read_min_edition = Some(version);
         self
@@ -456,11 +1459,31 @@ impl<'a> ConfigBuilder<'a> {
         self
     }
 
+    /// Record where the value of the option with the given key came from.
+    pub fn value_source(mut self, key: &'static str, source: ValueSource) -> Self {
+        self.inner.value_sources.insert(key, source);
+        self
+    }
+
+    pub fn config_files(mut self, files: Vec<PathBuf>) -> Self {
+        self.inner.config_files = files;
+        self
+    }
+
     pub fn sub_command_config(mut self, cmd_config: SubCommandConfig) -> Self {
         self.inner.sub_command_config = cmd_config;
         self
     }
 
+    /// The Rust version which is checked by `verify`. Has no effect on other sub commands.
+    pub fn verify_rust_version(mut self, version: bare_version::BareVersion) -> Self {
+        if let SubCommandConfig::VerifyConfig(config) = &mut self.inner.sub_command_config {
+            config.rust_version = Some(version);
+        }
+
+        self
+    }
+
     pub fn build(self) -> Config<'a> {
         self.inner.init_context()
     }
@@ -484,25 +1507,60 @@ pub enum SubCommandConfig {
     None,
     ListConfig(ListCmdConfig),
     SetConfig(SetCmdConfig),
-    ShowConfig,
+    ShowConfig(ShowCmdConfig),
     VerifyConfig(VerifyCmdConfig),
+    ReportConfig(ReportCmdConfig),
+    ConfigurationConfig(ConfigurationCmdConfig),
+    EditionConfig(EditionCmdConfig),
+    MatrixConfig(MatrixCmdConfig),
+    PolicyConfig(PolicyCmdConfig),
+    DiscoverConfig(DiscoverCmdConfig),
+    LastFailureConfig(LastFailureCmdConfig),
+    ImpactConfig(ImpactCmdConfig),
+    SchemaConfig(SchemaCmdConfig),
+    HistoryConfig(HistoryCmdConfig),
+    ServeConfig(ServeCmdConfig),
+    LowerConfig(LowerCmdConfig),
 }
 
 impl SubCommandConfig {
     as_sub_command_config!(list, ListConfig, ListCmdConfig);
     as_sub_command_config!(set, SetConfig, SetCmdConfig);
+    as_sub_command_config!(show, ShowConfig, ShowCmdConfig);
     as_sub_command_config!(verify, VerifyConfig, VerifyCmdConfig);
+    as_sub_command_config!(report, ReportConfig, ReportCmdConfig);
+    as_sub_command_config!(configuration, ConfigurationConfig, ConfigurationCmdConfig);
+    as_sub_command_config!(edition, EditionConfig, EditionCmdConfig);
+    as_sub_command_config!(matrix, MatrixConfig, MatrixCmdConfig);
+    as_sub_command_config!(policy, PolicyConfig, PolicyCmdConfig);
+    as_sub_command_config!(discover, DiscoverConfig, DiscoverCmdConfig);
+    as_sub_command_config!(last_failure, LastFailureConfig, LastFailureCmdConfig);
+    as_sub_command_config!(impact, ImpactConfig, ImpactCmdConfig);
+    as_sub_command_config!(schema, SchemaConfig, SchemaCmdConfig);
+    as_sub_command_config!(history, HistoryConfig, HistoryCmdConfig);
+    as_sub_command_config!(serve, ServeConfig, ServeCmdConfig);
+    as_sub_command_config!(lower, LowerConfig, LowerCmdConfig);
 }
 
 #[derive(Debug, Clone)]
 pub struct TracingOptions {
     target: TracingTargetOption,
     level: LogLevel,
+    rotation: LogRotation,
 }
 
 impl TracingOptions {
     pub fn new(target: TracingTargetOption, level: LogLevel) -> Self {
-        Self { target, level }
+        Self {
+            target,
+            level,
+            rotation: LogRotation::default(),
+        }
+    }
+
+    pub fn with_rotation(mut self, rotation: LogRotation) -> Self {
+        self.rotation = rotation;
+        self
     }
 }
 
@@ -511,6 +1569,7 @@ impl Default for TracingOptions {
         Self {
             target: TracingTargetOption::File,
             level: LogLevel::default(),
+            rotation: LogRotation::default(),
         }
     }
 }
@@ -523,12 +1582,76 @@ impl TracingOptions {
     pub fn level(&self) -> &LogLevel {
         &self.level
     }
+
+    pub fn rotation(&self) -> &LogRotation {
+        &self.rotation
+    }
+}
+
+/// When the log file is rotated, and when rotated log files are removed.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct LogRotation {
+    max_size: Option<u64>,
+    max_age: Option<Duration>,
+}
+
+impl LogRotation {
+    pub const DEFAULT_MAX_SIZE_MB: u64 = 10;
+    pub const DEFAULT_MAX_AGE_DAYS: u64 = 14;
+
+    pub fn new(max_size: Option<u64>, max_age: Option<Duration>) -> Self {
+        Self { max_size, max_age }
+    }
+
+    /// The size in bytes after which the log file is rotated. If absent, the log file is never
+    /// rotated.
+    pub fn max_size(&self) -> Option<u64> {
+        self.max_size
+    }
+
+    /// The age after which log files are removed. If absent, log files are kept indefinitely.
+    pub fn max_age(&self) -> Option<Duration> {
+        self.max_age
+    }
+}
+
+impl Default for LogRotation {
+    fn default() -> Self {
+        Self {
+            max_size: Some(Self::DEFAULT_MAX_SIZE_MB * 1024 * 1024),
+            max_age: Some(Duration::from_secs(
+                Self::DEFAULT_MAX_AGE_DAYS * 24 * 60 * 60,
+            )),
+        }
+    }
+}
+
+/// Options of `cargo msrv --batch`, which finds the MSRV of each crate whose path is read from
+/// stdin.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct BatchOptions {
+    read_min_edition: bool,
+}
+
+impl BatchOptions {
+    pub fn new(read_min_edition: bool) -> Self {
+        Self { read_min_edition }
+    }
+
+    /// Whether the minimum version of each crate is set to the first release which supports its
+    /// edition.
+    pub fn read_min_edition(&self) -> bool {
+        self.read_min_edition
+    }
 }
 
 #[derive(Debug, Copy, Clone, ArgEnum)]
 pub enum TracingTargetOption {
     File,
     Stdout,
+    Stderr,
+    /// Both to a file and to stderr
+    Both,
 }
 
 impl Default for TracingTargetOption {
@@ -540,6 +1663,13 @@ impl Default for TracingTargetOption {
 impl TracingTargetOption {
     pub const FILE: &'static str = "file";
     pub const STDOUT: &'static str = "stdout";
+    pub const STDERR: &'static str = "stderr";
+    pub const BOTH: &'static str = "both";
+
+    /// Whether logs are written to the log file.
+    pub fn writes_to_file(&self) -> bool {
+        matches!(self, Self::File | Self::Both)
+    }
 }
 
 impl FromStr for TracingTargetOption {
@@ -549,6 +1679,8 @@ impl FromStr for TracingTargetOption {
         match s {
             Self::FILE => Ok(Self::File),
             Self::STDOUT => Ok(Self::Stdout),
+            Self::STDERR => Ok(Self::Stderr),
+            Self::BOTH => Ok(Self::Both),
             unknown => Err(CargoMSRVError::InvalidConfig(format!(
                 "Given log target '{}' is not valid",
                 unknown
@@ -556,3 +1688,55 @@ impl FromStr for TracingTargetOption {
         }
     }
 }
+
+#[cfg(test)]
+mod variants_tests {
+    use super::*;
+
+    /// The given variant, parsed and displayed again.
+    fn displayed<T: FromStr + fmt::Display>(variant: &str) -> String {
+        match T::from_str(variant) {
+            Ok(value) => value.to_string(),
+            Err(_) => panic!("Variant '{}' is not valid", variant),
+        }
+    }
+
+    #[yare::parameterized(
+        bin_policy = { BinPolicy::variants(), displayed::<BinPolicy> },
+        cargo_flag = { CargoFlag::variants(), displayed::<CargoFlag> },
+        check_profile = { CheckProfile::variants(), displayed::<CheckProfile> },
+        check_runner = { CheckRunner::variants(), displayed::<CheckRunner> },
+        check_targets = { CheckTargets::variants(), displayed::<CheckTargets> },
+        clean_after = { CleanAfter::variants(), displayed::<CleanAfter> },
+        color_choice = { ColorChoice::variants(), displayed::<ColorChoice> },
+        dependency_kind = { DependencyKind::variants(), displayed::<DependencyKind> },
+        install_profile = { InstallProfile::variants(), displayed::<InstallProfile> },
+        locale = { Locale::variants(), displayed::<Locale> },
+        lockfile_policy = { LockfilePolicy::variants(), displayed::<LockfilePolicy> },
+        toolchain_file_format = { ToolchainFileFormat::variants(), displayed::<ToolchainFileFormat> },
+        version_resolution = { VersionResolution::variants(), displayed::<VersionResolution> },
+        search_method = { SearchMethod::variants(), |variant| <&str>::from(SearchMethod::from_str(variant).unwrap()).to_string() },
+    )]
+    fn variants_round_trip(variants: &[&str], round_trip: fn(&str) -> String) {
+        for variant in variants {
+            assert_eq!(&round_trip(variant), variant);
+        }
+    }
+}
+
+#[cfg(test)]
+mod search_method_tests {
+    use super::*;
+
+    #[yare::parameterized(
+        auto_single = { SearchMethod::Auto, 1, SearchMethod::Linear },
+        auto_small = { SearchMethod::Auto, SearchMethod::AUTO_LINEAR_MAX, SearchMethod::Linear },
+        auto_large = { SearchMethod::Auto, SearchMethod::AUTO_LINEAR_MAX + 1, SearchMethod::Bisect },
+        bisect_small = { SearchMethod::Bisect, 2, SearchMethod::Bisect },
+        linear_large = { SearchMethod::Linear, 40, SearchMethod::Linear },
+        exhaustive = { SearchMethod::Exhaustive, 40, SearchMethod::Exhaustive },
+    )]
+    fn resolve(method: SearchMethod, candidates: usize, expected: SearchMethod) {
+        assert_eq!(method.resolve(candidates), expected);
+    }
+}
@@ -0,0 +1,703 @@
+use rust_releases::semver;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::CargoMSRVError;
+
+/// The cargo targets which are included by the default `check` command.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckTargets {
+    /// All targets, including tests, examples and benches
+    All,
+    /// The library and binary targets, which cargo checks by default
+    Lib,
+    /// The test targets
+    Tests,
+    /// The example targets
+    Examples,
+    /// The bench targets
+    Benches,
+}
+
+impl CheckTargets {
+    pub(crate) fn variants() -> &'static [&'static str] {
+        &["all", "lib", "tests", "examples", "benches"]
+    }
+
+    /// The arguments which are appended to the default `check` command, to select the targets.
+    pub fn cargo_args(&self) -> &'static [&'static str] {
+        match self {
+            Self::All => &["--all-targets"],
+            Self::Lib => &[],
+            Self::Tests => &["--tests"],
+            Self::Examples => &["--examples"],
+            Self::Benches => &["--benches"],
+        }
+    }
+}
+
+impl Default for CheckTargets {
+    fn default() -> Self {
+        Self::Lib
+    }
+}
+
+impl FromStr for CheckTargets {
+    type Err = CargoMSRVError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "all" => Ok(Self::All),
+            "lib" => Ok(Self::Lib),
+            "tests" => Ok(Self::Tests),
+            "examples" => Ok(Self::Examples),
+            "benches" => Ok(Self::Benches),
+            unknown => Err(CargoMSRVError::InvalidConfig(format!(
+                "Given check targets '{}' are not valid",
+                unknown
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for CheckTargets {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::All => write!(f, "all"),
+            Self::Lib => write!(f, "lib"),
+            Self::Tests => write!(f, "tests"),
+            Self::Examples => write!(f, "examples"),
+            Self::Benches => write!(f, "benches"),
+        }
+    }
+}
+
+/// The tool with which compatibility is checked.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum CheckWith {
+    /// Run the check command, by default `cargo check`
+    Cargo,
+    /// Compile a single file with `rustc`, without a Cargo manifest. Build artifacts are written
+    /// to `out_dir`.
+    Rustc { file: String, out_dir: String },
+}
+
+impl Default for CheckWith {
+    fn default() -> Self {
+        Self::Cargo
+    }
+}
+
+/// How the default `check` command treats binaries which specify `required-features`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum BinPolicy {
+    /// Leave the check command as is
+    Ignore,
+    /// Enable the features required by the binaries
+    EnableFeatures,
+    /// Exclude the binaries from the check
+    Exclude,
+}
+
+impl BinPolicy {
+    pub(crate) fn variants() -> &'static [&'static str] {
+        &["ignore", "enable-features", "exclude"]
+    }
+}
+
+impl Default for BinPolicy {
+    fn default() -> Self {
+        Self::Ignore
+    }
+}
+
+impl FromStr for BinPolicy {
+    type Err = CargoMSRVError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ignore" => Ok(Self::Ignore),
+            "enable-features" => Ok(Self::EnableFeatures),
+            "exclude" => Ok(Self::Exclude),
+            unknown => Err(CargoMSRVError::InvalidConfig(format!(
+                "Given binary policy '{}' is not valid",
+                unknown
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for BinPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ignore => write!(f, "ignore"),
+            Self::EnableFeatures => write!(f, "enable-features"),
+            Self::Exclude => write!(f, "exclude"),
+        }
+    }
+}
+
+/// A kind of dependency, as declared by a dependency table of the Cargo manifest.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum DependencyKind {
+    /// Declared in `[dependencies]`
+    Normal,
+    /// Declared in `[build-dependencies]`
+    Build,
+    /// Declared in `[dev-dependencies]`
+    Dev,
+}
+
+impl DependencyKind {
+    pub(crate) fn variants() -> &'static [&'static str] {
+        &["normal", "build", "dev"]
+    }
+
+    /// Each kind of dependency, which are all included in the check by default.
+    pub fn all() -> Vec<Self> {
+        vec![Self::Normal, Self::Build, Self::Dev]
+    }
+
+    /// The keys of the dependency tables of this kind, including the legacy spelling with an
+    /// underscore, which cargo still accepts.
+    pub fn table_keys(&self) -> &'static [&'static str] {
+        match self {
+            Self::Normal => &["dependencies"],
+            Self::Build => &["build-dependencies", "build_dependencies"],
+            Self::Dev => &["dev-dependencies", "dev_dependencies"],
+        }
+    }
+}
+
+impl FromStr for DependencyKind {
+    type Err = CargoMSRVError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "normal" => Ok(Self::Normal),
+            "build" => Ok(Self::Build),
+            "dev" => Ok(Self::Dev),
+            unknown => Err(CargoMSRVError::InvalidConfig(format!(
+                "Given dependency kind '{}' is not valid",
+                unknown
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for DependencyKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Normal => write!(f, "normal"),
+            Self::Build => write!(f, "build"),
+            Self::Dev => write!(f, "dev"),
+        }
+    }
+}
+
+/// Whether doc tests are checked for each toolchain, once the check command passed.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum DocTests {
+    /// Doc tests are not checked
+    Skip,
+    /// Doc tests are compiled, with `cargo test --doc --no-run`
+    Build,
+    /// Doc tests are compiled and run, with `cargo test --doc`
+    Run,
+}
+
+impl DocTests {
+    /// The command which checks the doc tests, if doc tests are checked.
+    pub fn cargo_command(&self) -> Option<&'static [&'static str]> {
+        match self {
+            Self::Skip => None,
+            Self::Build => Some(&["cargo", "test", "--doc", "--no-run"]),
+            Self::Run => Some(&["cargo", "test", "--doc"]),
+        }
+    }
+}
+
+impl Default for DocTests {
+    fn default() -> Self {
+        Self::Skip
+    }
+}
+
+impl fmt::Display for DocTests {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Skip => write!(f, "skip"),
+            Self::Build => write!(f, "build"),
+            Self::Run => write!(f, "run"),
+        }
+    }
+}
+
+/// A cargo flag which is added to the default `check` command, but only for toolchains whose
+/// cargo supports it. Older cargo versions reject flags they don't know.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum CargoFlag {
+    /// `--locked`, require the lockfile to be up to date
+    Locked,
+    /// `--frozen`, require the lockfile to be up to date, and don't access the network
+    Frozen,
+    /// `--offline`, don't access the network
+    Offline,
+    /// `--ignore-rust-version`, ignore the `rust-version` specified in the manifest
+    IgnoreRustVersion,
+}
+
+impl CargoFlag {
+    pub(crate) fn variants() -> &'static [&'static str] {
+        &["locked", "frozen", "offline", "ignore-rust-version"]
+    }
+
+    pub fn flag(&self) -> &'static str {
+        match self {
+            Self::Locked => "--locked",
+            Self::Frozen => "--frozen",
+            Self::Offline => "--offline",
+            Self::IgnoreRustVersion => "--ignore-rust-version",
+        }
+    }
+
+    /// The first Rust release whose cargo supports the flag.
+    pub fn supported_since(&self) -> semver::Version {
+        match self {
+            Self::Locked | Self::Frozen => semver::Version::new(1, 12, 0),
+            Self::Offline => semver::Version::new(1, 36, 0),
+            Self::IgnoreRustVersion => semver::Version::new(1, 56, 0),
+        }
+    }
+
+    pub fn is_supported_by(&self, version: &semver::Version) -> bool {
+        let since = self.supported_since();
+
+        // Compare without pre-release identifiers, so e.g. a 1.56.0 beta supports flags which
+        // were stabilized in 1.56.0.
+        (version.major, version.minor, version.patch) >= (since.major, since.minor, since.patch)
+    }
+}
+
+impl FromStr for CargoFlag {
+    type Err = CargoMSRVError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "locked" => Ok(Self::Locked),
+            "frozen" => Ok(Self::Frozen),
+            "offline" => Ok(Self::Offline),
+            "ignore-rust-version" => Ok(Self::IgnoreRustVersion),
+            unknown => Err(CargoMSRVError::InvalidConfig(format!(
+                "Given cargo flag '{}' is not valid",
+                unknown
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for CargoFlag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Locked => write!(f, "locked"),
+            Self::Frozen => write!(f, "frozen"),
+            Self::Offline => write!(f, "offline"),
+            Self::IgnoreRustVersion => write!(f, "ignore-rust-version"),
+        }
+    }
+}
+
+/// The workspace members which are checked by the default `check` command.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum PackageSelection {
+    /// The package in the current directory, or the default members of the workspace
+    Default,
+    /// Only the given packages, as with `cargo check --package`
+    Packages(Vec<String>),
+    /// All workspace members, except the given packages, as with `cargo check --exclude`
+    Exclude(Vec<String>),
+}
+
+impl PackageSelection {
+    /// The arguments which are appended to the default `check` command, to select the packages,
+    /// for a toolchain of the given Rust version.
+    pub fn cargo_args(&self, version: &semver::Version) -> Vec<&str> {
+        // Cargo of Rust releases before 1.39 only supports `--all`, the predecessor of
+        // `--workspace`
+        if (version.major, version.minor) >= (1, 39) {
+            self.args_with("--workspace")
+        } else {
+            self.args_with("--all")
+        }
+    }
+
+    fn args_with<'s>(&'s self, workspace_flag: &'s str) -> Vec<&'s str> {
+        match self {
+            Self::Default => Vec::new(),
+            Self::Packages(packages) => packages
+                .iter()
+                .flat_map(|package| ["--package", package.as_str()])
+                .collect(),
+            Self::Exclude(packages) => std::iter::once(workspace_flag)
+                .chain(
+                    packages
+                        .iter()
+                        .flat_map(|package| ["--exclude", package.as_str()]),
+                )
+                .collect(),
+        }
+    }
+}
+
+impl Default for PackageSelection {
+    fn default() -> Self {
+        Self::Default
+    }
+}
+
+/// The features which are enabled by the default `check` command.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum FeatureSet {
+    /// The default features of the crate
+    Default,
+    /// No features, as with `cargo check --no-default-features`
+    NoDefaultFeatures,
+    /// All features, as with `cargo check --all-features`
+    AllFeatures,
+    /// The default features, and the given comma separated features, as with
+    /// `cargo check --features`
+    Features(String),
+}
+
+impl FeatureSet {
+    /// The arguments which are appended to the default `check` command, to enable the features.
+    pub fn cargo_args(&self) -> Vec<&str> {
+        match self {
+            Self::Default => Vec::new(),
+            Self::NoDefaultFeatures => vec!["--no-default-features"],
+            Self::AllFeatures => vec!["--all-features"],
+            Self::Features(features) => vec!["--features", features.as_str()],
+        }
+    }
+}
+
+impl Default for FeatureSet {
+    fn default() -> Self {
+        Self::Default
+    }
+}
+
+impl FromStr for FeatureSet {
+    type Err = CargoMSRVError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "default" => Ok(Self::Default),
+            "no-default-features" => Ok(Self::NoDefaultFeatures),
+            "all-features" => Ok(Self::AllFeatures),
+            "" => Err(CargoMSRVError::InvalidConfig(
+                "Expected a feature configuration, like 'default', 'no-default-features', \
+                 'all-features' or a comma separated list of features"
+                    .to_string(),
+            )),
+            features => Ok(Self::Features(features.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for FeatureSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Default => write!(f, "default"),
+            Self::NoDefaultFeatures => write!(f, "no-default-features"),
+            Self::AllFeatures => write!(f, "all-features"),
+            Self::Features(features) => write!(f, "{}", features),
+        }
+    }
+}
+
+impl serde::Serialize for FeatureSet {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl schemars::JsonSchema for FeatureSet {
+    fn schema_name() -> String {
+        "FeatureSet".to_string()
+    }
+
+    // `default`, `no-default-features`, `all-features`, or a comma separated list of features
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        <String as schemars::JsonSchema>::json_schema(gen)
+    }
+}
+
+#[cfg(test)]
+mod check_targets_tests {
+    use super::*;
+    use crate::config::{Action, ConfigBuilder};
+
+    #[yare::parameterized(
+        all = { CheckTargets::All, vec!["cargo", "check", "--all-targets"] },
+        lib = { CheckTargets::Lib, vec!["cargo", "check"] },
+        tests = { CheckTargets::Tests, vec!["cargo", "check", "--tests"] },
+        examples = { CheckTargets::Examples, vec!["cargo", "check", "--examples"] },
+        benches = { CheckTargets::Benches, vec!["cargo", "check", "--benches"] },
+    )]
+    fn extends_default_check_command(targets: CheckTargets, expected: Vec<&str>) {
+        let config = ConfigBuilder::new(Action::Find, "x86_64-unknown-linux-gnu")
+            .check_targets(targets)
+            .build();
+
+        assert_eq!(config.check_command(), &expected);
+    }
+}
+
+#[cfg(test)]
+mod cargo_flags_tests {
+    use super::*;
+    use crate::config::{Action, Config, ConfigBuilder};
+
+    fn config(flags: Vec<CargoFlag>) -> Config<'static> {
+        ConfigBuilder::new(Action::Find, "x86_64-unknown-linux-gnu")
+            .cargo_flags(flags)
+            .build()
+    }
+
+    #[yare::parameterized(
+        before_locked = { semver::Version::new(1, 11, 0), vec!["cargo", "check"] },
+        locked = { semver::Version::new(1, 12, 0), vec!["cargo", "check", "--locked"] },
+        offline = { semver::Version::new(1, 36, 0), vec!["cargo", "check", "--locked", "--offline"] },
+        all = { semver::Version::new(1, 56, 0), vec!["cargo", "check", "--locked", "--offline", "--ignore-rust-version"] },
+        pre_release = { semver::Version::parse("1.56.0-beta.1").unwrap(), vec!["cargo", "check", "--locked", "--offline", "--ignore-rust-version"] },
+    )]
+    fn only_supported_flags_are_added(version: semver::Version, expected: Vec<&str>) {
+        let config = config(vec![
+            CargoFlag::Locked,
+            CargoFlag::Offline,
+            CargoFlag::IgnoreRustVersion,
+        ]);
+
+        assert_eq!(config.check_command_for_version(&version), expected);
+    }
+
+    #[yare::parameterized(
+        before_frozen = { semver::Version::new(1, 11, 0), vec!["cargo", "check"] },
+        frozen = { semver::Version::new(1, 12, 0), vec!["cargo", "check", "--frozen"] },
+    )]
+    fn frozen_is_added_when_supported(version: semver::Version, expected: Vec<&str>) {
+        let config = config(vec![CargoFlag::Frozen]);
+
+        assert_eq!(config.check_command_for_version(&version), expected);
+    }
+
+    #[test]
+    fn without_flags_check_command_is_unchanged() {
+        let config = config(Vec::new());
+
+        assert_eq!(
+            config.check_command_for_version(&semver::Version::new(1, 60, 0)),
+            vec!["cargo", "check"]
+        );
+    }
+
+    #[test]
+    fn target_dir_is_added() {
+        let config = ConfigBuilder::new(Action::Find, "x86_64-unknown-linux-gnu")
+            .cargo_flags(vec![CargoFlag::Locked])
+            .target_dir(Some("/tmp/msrv-target".to_string()))
+            .build();
+
+        assert_eq!(
+            config.check_command_for_version(&semver::Version::new(1, 60, 0)),
+            vec![
+                "cargo",
+                "check",
+                "--target-dir",
+                "/tmp/msrv-target",
+                "--locked"
+            ]
+        );
+    }
+}
+
+#[cfg(test)]
+mod package_selection_tests {
+    use super::*;
+    use crate::config::{Action, Config, ConfigBuilder};
+
+    fn config(packages: PackageSelection) -> Config<'static> {
+        ConfigBuilder::new(Action::Find, "x86_64-unknown-linux-gnu")
+            .packages(packages)
+            .build()
+    }
+
+    #[yare::parameterized(
+        default = { PackageSelection::Default, semver::Version::new(1, 60, 0), vec!["cargo", "check"] },
+        packages = { PackageSelection::Packages(vec!["a".to_string(), "b".to_string()]), semver::Version::new(1, 60, 0), vec!["cargo", "check", "--package", "a", "--package", "b"] },
+        exclude = { PackageSelection::Exclude(vec!["a".to_string()]), semver::Version::new(1, 39, 0), vec!["cargo", "check", "--workspace", "--exclude", "a"] },
+        exclude_before_workspace_flag = { PackageSelection::Exclude(vec!["a".to_string()]), semver::Version::new(1, 38, 0), vec!["cargo", "check", "--all", "--exclude", "a"] },
+    )]
+    fn extends_check_command(
+        packages: PackageSelection,
+        version: semver::Version,
+        expected: Vec<&str>,
+    ) {
+        let config = config(packages);
+
+        assert_eq!(config.check_command_for_version(&version), expected);
+    }
+
+    #[test]
+    fn check_command_string_includes_packages() {
+        let config = config(PackageSelection::Exclude(vec!["a".to_string()]));
+
+        assert_eq!(
+            config.check_command_string(),
+            "cargo check --workspace --exclude a"
+        );
+    }
+}
+
+#[cfg(test)]
+mod feature_set_tests {
+    use super::*;
+    use crate::config::{Action, ConfigBuilder};
+    use std::path::Path;
+
+    #[yare::parameterized(
+        default = { "default", FeatureSet::Default },
+        no_default_features = { "no-default-features", FeatureSet::NoDefaultFeatures },
+        all_features = { "all-features", FeatureSet::AllFeatures },
+        features = { "a,b", FeatureSet::Features("a,b".to_string()) },
+    )]
+    fn round_trip(input: &str, expected: FeatureSet) {
+        let features = FeatureSet::from_str(input).unwrap();
+
+        assert_eq!(features, expected);
+        assert_eq!(features.to_string(), input);
+    }
+
+    #[test]
+    fn empty_is_rejected() {
+        assert!(FeatureSet::from_str("").is_err());
+    }
+
+    #[yare::parameterized(
+        cargo = { ConfigBuilder::new(Action::Find, "x86_64-unknown-linux-gnu"), vec!["cargo", "check", "--features", "a,b"] },
+        rustc = { ConfigBuilder::new(Action::Find, "x86_64-unknown-linux-gnu").check_with_rustc(Path::new("snippet.rs")), vec!["rustc"] },
+    )]
+    fn extends_cargo_check_command(builder: ConfigBuilder<'static>, expected: Vec<&str>) {
+        let config = builder
+            .features(FeatureSet::Features("a,b".to_string()))
+            .build();
+
+        let command = config.check_command_for_version(&semver::Version::new(1, 60, 0));
+
+        assert_eq!(&command[..expected.len()], expected.as_slice());
+        assert_eq!(
+            command.contains(&"--features"),
+            expected.contains(&"--features")
+        );
+    }
+}
+
+#[cfg(test)]
+mod doc_tests_tests {
+    use super::*;
+    use crate::config::{Action, ConfigBuilder};
+
+    #[yare::parameterized(
+        skip = { DocTests::Skip, semver::Version::new(1, 60, 0), None },
+        build = { DocTests::Build, semver::Version::new(1, 60, 0), Some(vec!["cargo", "test", "--doc", "--no-run", "--package", "a", "--locked"]) },
+        run = { DocTests::Run, semver::Version::new(1, 60, 0), Some(vec!["cargo", "test", "--doc", "--package", "a", "--locked"]) },
+        unsupported_flag = { DocTests::Run, semver::Version::new(1, 11, 0), Some(vec!["cargo", "test", "--doc", "--package", "a"]) },
+    )]
+    fn doc_test_command(
+        doc_tests: DocTests,
+        version: semver::Version,
+        expected: Option<Vec<&str>>,
+    ) {
+        let config = ConfigBuilder::new(Action::Find, "x86_64-unknown-linux-gnu")
+            .doc_tests(doc_tests)
+            .packages(PackageSelection::Packages(vec!["a".to_string()]))
+            .cargo_flags(vec![CargoFlag::Locked])
+            .build();
+
+        assert_eq!(config.doc_test_command_for_version(&version), expected);
+    }
+}
+
+#[cfg(test)]
+mod dependency_kind_tests {
+    use super::*;
+    use crate::config::{Action, ConfigBuilder};
+
+    #[test]
+    fn excluded_kinds() {
+        let config = ConfigBuilder::new(Action::Find, "x86_64-unknown-linux-gnu")
+            .dependency_kinds(vec![DependencyKind::Normal, DependencyKind::Build])
+            .build();
+
+        assert_eq!(
+            config.excluded_dependency_kinds(),
+            vec![DependencyKind::Dev]
+        );
+    }
+}
+
+#[cfg(test)]
+mod check_with_tests {
+    use super::*;
+    use crate::config::{Action, ConfigBuilder};
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn rustc_compiles_file() {
+        let config = ConfigBuilder::new(Action::Find, "x86_64-unknown-linux-gnu")
+            .check_with_rustc(Path::new("snippet.rs"))
+            .build();
+
+        let command = config.check_command_for_version(&semver::Version::new(1, 40, 0));
+
+        assert_eq!(command[..4], ["rustc", "--crate-type", "lib", "--out-dir"]);
+        assert_out_dir(command[4], &std::env::temp_dir());
+        assert_eq!(command[5..], ["snippet.rs"]);
+        assert_eq!(
+            config.check_command_string(),
+            "rustc --crate-type lib snippet.rs"
+        );
+    }
+
+    #[test]
+    fn rustc_out_dir_in_scratch_dir() {
+        let scratch_dir = PathBuf::from("scratch");
+        let config = ConfigBuilder::new(Action::Find, "x86_64-unknown-linux-gnu")
+            .scratch_dir(Some(scratch_dir.clone()))
+            .check_with_rustc(Path::new("snippet.rs"))
+            .build();
+
+        let command = config.check_command_for_version(&semver::Version::new(1, 40, 0));
+
+        assert_out_dir(command[4], &scratch_dir);
+    }
+
+    /// The out dir of `rustc` is a sandbox of its own, within the given directory.
+    fn assert_out_dir(out_dir: &str, root: &Path) {
+        let out_dir = Path::new(out_dir);
+        let name = out_dir.file_name().unwrap().to_string_lossy();
+
+        assert_eq!(out_dir.parent(), Some(root));
+        assert!(name.starts_with("cargo-msrv-rustc-"));
+    }
+
+    #[test]
+    fn cargo_by_default() {
+        let config = ConfigBuilder::new(Action::Find, "x86_64-unknown-linux-gnu").build();
+
+        assert_eq!(config.check_with(), &CheckWith::Cargo);
+        assert_eq!(config.check_command_string(), "cargo check");
+    }
+}
@@ -0,0 +1,213 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::CargoMSRVError;
+
+/// How each toolchain check is run.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum CheckRunner {
+    /// Run the check command with `rustup run`, using the toolchain for the configured target
+    Rustup,
+    /// Run the check command with `cross`, using the toolchain for the host, which builds for the
+    /// configured target inside a container. Only cargo commands can be run this way.
+    Cross,
+}
+
+impl CheckRunner {
+    pub(crate) fn variants() -> &'static [&'static str] {
+        &["rustup", "cross"]
+    }
+}
+
+impl Default for CheckRunner {
+    fn default() -> Self {
+        Self::Rustup
+    }
+}
+
+impl FromStr for CheckRunner {
+    type Err = CargoMSRVError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "rustup" => Ok(Self::Rustup),
+            "cross" => Ok(Self::Cross),
+            unknown => Err(CargoMSRVError::InvalidConfig(format!(
+                "Given check runner '{}' is not valid",
+                unknown
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for CheckRunner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Rustup => write!(f, "rustup"),
+            Self::Cross => write!(f, "cross"),
+        }
+    }
+}
+
+/// Shell commands which are run around each toolchain check, e.g. to prime a build cache or to
+/// collect metrics.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct CheckHooks {
+    before: Option<String>,
+    after: Option<String>,
+}
+
+impl CheckHooks {
+    pub fn new(before: Option<String>, after: Option<String>) -> Self {
+        Self { before, after }
+    }
+
+    /// The command which is run before each check.
+    pub fn before(&self) -> Option<&str> {
+        self.before.as_deref()
+    }
+
+    /// The command which is run after each check.
+    pub fn after(&self) -> Option<&str> {
+        self.after.as_deref()
+    }
+}
+
+/// Identifies a hook, by the option with which it was given.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum CheckHook {
+    Before,
+    After,
+}
+
+impl fmt::Display for CheckHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Before => write!(f, "--before-check"),
+            Self::After => write!(f, "--after-check"),
+        }
+    }
+}
+
+/// When the build artifacts of a toolchain check are removed from the target directory, once the
+/// toolchain has been checked.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum CleanAfter {
+    /// Keep the build artifacts, so they may be reused by the check of the next toolchain
+    Never,
+    /// Remove the build artifacts of compatible toolchains only, and keep those of failed checks
+    /// for inspection
+    OnSuccess,
+    /// Remove the build artifacts after each check
+    Always,
+}
+
+impl CleanAfter {
+    pub(crate) fn variants() -> &'static [&'static str] {
+        &["never", "on-success", "always"]
+    }
+
+    /// Whether the build artifacts are removed after a check with the given outcome.
+    pub fn applies_to(self, compatible: bool) -> bool {
+        match self {
+            Self::Never => false,
+            Self::OnSuccess => compatible,
+            Self::Always => true,
+        }
+    }
+}
+
+impl Default for CleanAfter {
+    fn default() -> Self {
+        Self::Never
+    }
+}
+
+impl FromStr for CleanAfter {
+    type Err = CargoMSRVError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "never" => Ok(Self::Never),
+            "on-success" => Ok(Self::OnSuccess),
+            "always" => Ok(Self::Always),
+            unknown => Err(CargoMSRVError::InvalidConfig(format!(
+                "Given clean policy '{}' is not valid",
+                unknown
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for CleanAfter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Never => write!(f, "never"),
+            Self::OnSuccess => write!(f, "on-success"),
+            Self::Always => write!(f, "always"),
+        }
+    }
+}
+
+/// How a toolchain is checked, when its cargo can't read the format of the lockfile of the crate.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum LockfilePolicy {
+    /// Reject the toolchain without checking it, since the crate can't be built with its lockfile
+    Untestable,
+    /// Check the toolchain with a lockfile regenerated by its cargo, and without `--locked`. The
+    /// lockfile of the crate is moved aside during the check, and restored afterwards.
+    Regenerate,
+}
+
+impl LockfilePolicy {
+    pub(crate) fn variants() -> &'static [&'static str] {
+        &["untestable", "regenerate"]
+    }
+}
+
+impl Default for LockfilePolicy {
+    fn default() -> Self {
+        Self::Untestable
+    }
+}
+
+impl FromStr for LockfilePolicy {
+    type Err = CargoMSRVError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "untestable" => Ok(Self::Untestable),
+            "regenerate" => Ok(Self::Regenerate),
+            unknown => Err(CargoMSRVError::InvalidConfig(format!(
+                "Given lockfile policy '{}' is not valid",
+                unknown
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for LockfilePolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Untestable => write!(f, "untestable"),
+            Self::Regenerate => write!(f, "regenerate"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[yare::parameterized(
+        never_success = { CleanAfter::Never, true, false },
+        never_failure = { CleanAfter::Never, false, false },
+        on_success_success = { CleanAfter::OnSuccess, true, true },
+        on_success_failure = { CleanAfter::OnSuccess, false, false },
+        always_failure = { CleanAfter::Always, false, true },
+    )]
+    fn applies_to(clean_after: CleanAfter, compatible: bool, expected: bool) {
+        assert_eq!(clean_after.applies_to(compatible), expected);
+    }
+}
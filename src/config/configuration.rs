@@ -0,0 +1,7 @@
+#[derive(Clone, Debug)]
+pub enum ConfigurationCmdConfig {
+    /// Show the resolved configuration, and where each value came from
+    Show,
+    /// Remember the value of an option in the preferences file of the user
+    Set { key: String, value: String },
+}
@@ -0,0 +1,59 @@
+use crate::config::Action;
+use std::fmt::Formatter;
+use std::{fmt, str::FromStr};
+
+#[derive(Clone, Debug)]
+pub struct DiscoverCmdConfig {
+    /// Run `find` or `verify` for each discovered package, after they have been listed
+    pub run: Option<DiscoverRun>,
+}
+
+/// The sub-command which is run for each discovered package.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum DiscoverRun {
+    Find,
+    Verify,
+}
+
+pub(crate) const FIND: &str = "find";
+pub(crate) const VERIFY: &str = "verify";
+
+impl DiscoverRun {
+    pub(crate) const fn variants() -> &'static [&'static str] {
+        &[FIND, VERIFY]
+    }
+
+    pub fn action(&self) -> Action {
+        match self {
+            Self::Find => Action::Find,
+            Self::Verify => Action::Verify,
+        }
+    }
+}
+
+impl FromStr for DiscoverRun {
+    type Err = crate::CargoMSRVError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            FIND => Self::Find,
+            VERIFY => Self::Verify,
+            elsy => {
+                return Err(crate::CargoMSRVError::InvalidConfig(format!(
+                    "Unable to run '{}' for the discovered projects, expected one of: {}",
+                    elsy,
+                    Self::variants().join(", ")
+                )))
+            }
+        })
+    }
+}
+
+impl fmt::Display for DiscoverRun {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Find => write!(f, "{}", FIND),
+            Self::Verify => write!(f, "{}", VERIFY),
+        }
+    }
+}
@@ -0,0 +1,5 @@
+#[derive(Clone, Debug)]
+pub struct EditionCmdConfig {
+    /// Migrate a copy of the crate to the next edition, to confirm the migration is feasible
+    pub check_migration: bool,
+}
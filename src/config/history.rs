@@ -0,0 +1,7 @@
+#[derive(Clone, Debug)]
+pub struct HistoryCmdConfig {
+    /// The range of commits to walk, like `v1.0.0..HEAD`, instead of the tags of the repository
+    pub range: Option<String>,
+    /// Verify the MSRV declared at each revision, with its toolchain
+    pub verify: bool,
+}
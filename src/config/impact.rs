@@ -0,0 +1,100 @@
+use crate::semver;
+use std::fmt::Formatter;
+use std::{fmt, str::FromStr};
+
+#[derive(Clone, Debug)]
+pub struct ImpactCmdConfig {
+    /// The dependency which would be added to the crate
+    pub dependency: ProspectiveDependency,
+    /// Find the MSRV with the dependency, when the current MSRV no longer holds
+    pub find: bool,
+}
+
+/// A dependency which is not yet added to the crate, given as `NAME@VERSION`. The version is a
+/// version requirement, as it would be written in the Cargo manifest.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ProspectiveDependency {
+    name: String,
+    requirement: String,
+}
+
+impl ProspectiveDependency {
+    /// The requirement of a dependency which is given without a version: any release, so the most
+    /// recent one is resolved.
+    const ANY: &'static str = "*";
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn requirement(&self) -> &str {
+        &self.requirement
+    }
+}
+
+impl FromStr for ProspectiveDependency {
+    type Err = crate::CargoMSRVError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: &str| {
+            crate::CargoMSRVError::InvalidConfig(format!(
+                "Unable to parse the dependency '{}', expected NAME@VERSION: {}",
+                s, reason
+            ))
+        };
+
+        let (name, requirement) = match s.split_once('@') {
+            Some((name, requirement)) => (name.trim(), requirement.trim()),
+            None => (s.trim(), Self::ANY),
+        };
+
+        let is_valid_name = |c: char| c.is_ascii_alphanumeric() || c == '-' || c == '_';
+
+        if name.is_empty() || !name.chars().all(is_valid_name) {
+            return Err(invalid("the name of the crate is not valid"));
+        }
+
+        semver::VersionReq::parse(requirement)
+            .map_err(|error| invalid(&format!("the version is not valid ({})", error)))?;
+
+        Ok(Self {
+            name: name.to_string(),
+            requirement: requirement.to_string(),
+        })
+    }
+}
+
+impl fmt::Display for ProspectiveDependency {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}@{}", self.name, self.requirement)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[yare::parameterized(
+        exact = { "serde@1.0.190", "serde", "1.0.190" },
+        partial = { "rand_core@0.6", "rand_core", "0.6" },
+        requirement = { "tokio@>=1.20, <1.30", "tokio", ">=1.20, <1.30" },
+        without_version = { "once-cell", "once-cell", "*" },
+    )]
+    fn parse(input: &str, name: &str, requirement: &str) {
+        let dependency = ProspectiveDependency::from_str(input).unwrap();
+
+        assert_eq!(dependency.name(), name);
+        assert_eq!(dependency.requirement(), requirement);
+    }
+
+    #[yare::parameterized(
+        empty = { "" },
+        without_name = { "@1.0" },
+        invalid_name = { "serde json@1.0" },
+        invalid_version = { "serde@one" },
+    )]
+    fn invalid(input: &str) {
+        assert!(ProspectiveDependency::from_str(input).is_err());
+    }
+}
@@ -0,0 +1,5 @@
+#[derive(Clone, Debug)]
+pub struct LastFailureCmdConfig {
+    /// Show the log with the pager given by the `PAGER` environment variable
+    pub pager: bool,
+}
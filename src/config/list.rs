@@ -1,19 +1,54 @@
 use std::fmt::Formatter;
+use std::path::PathBuf;
 use std::{fmt, str::FromStr};
 
 #[derive(Clone, Debug)]
 pub struct ListCmdConfig {
     pub variant: ListMsrvVariant,
+    /// Path to a local checkout of the crates.io index, used by the orphans variant
+    pub crates_index: Option<PathBuf>,
+    /// Local checkouts of the indexes of alternative registries, used by the orphans variant
+    pub registry_indexes: Vec<RegistryIndex>,
+}
+
+/// A local checkout of the index of an alternative registry, given as `<URL>=<DIR>`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RegistryIndex {
+    /// The URL of the index, as given in the `source` of packages published to the registry
+    pub url: String,
+    pub path: PathBuf,
+}
+
+impl FromStr for RegistryIndex {
+    type Err = crate::CargoMSRVError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once('=') {
+            Some((url, path)) if !url.is_empty() && !path.is_empty() => Ok(Self {
+                url: url.to_string(),
+                path: PathBuf::from(path),
+            }),
+            _ => Err(crate::CargoMSRVError::InvalidConfig(format!(
+                "Expected a registry index as '<URL>=<DIR>', but got '{}'",
+                s
+            ))),
+        }
+    }
 }
 
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum ListMsrvVariant {
     DirectDeps,
     OrderedByMSRV,
+    Orphans,
+    /// Selected with `--resolve`, rather than as a variant
+    Resolve,
 }
 
 pub(crate) const DIRECT_DEPS: &str = "direct-deps";
 pub(crate) const ORDERED_BY_MSRV: &str = "ordered-by-msrv";
+pub(crate) const ORPHANS: &str = "orphans";
+pub(crate) const RESOLVE: &str = "resolve";
 
 impl FromStr for ListMsrvVariant {
     type Err = crate::CargoMSRVError;
@@ -22,6 +57,8 @@ impl FromStr for ListMsrvVariant {
         Ok(match s {
             DIRECT_DEPS => Self::DirectDeps,
             ORDERED_BY_MSRV => Self::OrderedByMSRV,
+            ORPHANS => Self::Orphans,
+            RESOLVE => Self::Resolve,
             elsy => {
                 return Err(crate::CargoMSRVError::InvalidConfig(format!(
                     "No such list variant '{}'",
@@ -37,13 +74,15 @@ impl fmt::Display for ListMsrvVariant {
         match self {
             Self::DirectDeps => write!(f, "{}", DIRECT_DEPS),
             Self::OrderedByMSRV => write!(f, "{}", ORDERED_BY_MSRV),
+            Self::Orphans => write!(f, "{}", ORPHANS),
+            Self::Resolve => write!(f, "{}", RESOLVE),
         }
     }
 }
 
 impl ListMsrvVariant {
     pub(crate) const fn variants() -> &'static [&'static str] {
-        &[DIRECT_DEPS, ORDERED_BY_MSRV]
+        &[DIRECT_DEPS, ORDERED_BY_MSRV, ORPHANS]
     }
 }
 
@@ -0,0 +1,10 @@
+use std::path::PathBuf;
+
+#[derive(Clone, Debug)]
+pub struct LowerCmdConfig {
+    /// The number of releases less recent than the MSRV which are tried
+    pub steps: usize,
+    /// Path to a local checkout of the crates.io index, to suggest the release to which a
+    /// dependency can be pinned
+    pub crates_index: Option<PathBuf>,
+}
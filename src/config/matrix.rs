@@ -0,0 +1,10 @@
+use crate::config::FeatureSet;
+
+#[derive(Clone, Debug)]
+pub struct MatrixCmdConfig {
+    /// The targets for which the MSRV is determined; when empty, the configured target is used
+    pub targets: Vec<String>,
+    /// The feature configurations for which the MSRV is determined; when empty, the default
+    /// features are used
+    pub feature_sets: Vec<FeatureSet>,
+}
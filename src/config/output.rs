@@ -0,0 +1,169 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::CargoMSRVError;
+
+/// The language in which the human output is written.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Locale {
+    English,
+    German,
+}
+
+impl Locale {
+    pub(crate) fn variants() -> &'static [&'static str] {
+        &["en", "de"]
+    }
+}
+
+impl Default for Locale {
+    fn default() -> Self {
+        Self::English
+    }
+}
+
+impl FromStr for Locale {
+    type Err = CargoMSRVError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "en" => Ok(Self::English),
+            "de" => Ok(Self::German),
+            unknown => Err(CargoMSRVError::InvalidConfig(format!(
+                "Given locale '{}' is not valid",
+                unknown
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::English => write!(f, "en"),
+            Self::German => write!(f, "de"),
+        }
+    }
+}
+
+/// When the human output is colored. With `auto`, the output is colored when it is written to a
+/// terminal, and the `NO_COLOR` environment variable is not set.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ColorChoice {
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    pub(crate) fn variants() -> &'static [&'static str] {
+        &["auto", "always", "never"]
+    }
+
+    /// Whether the human output, which is written to stderr, should be colored.
+    pub fn colors_enabled(self) -> bool {
+        match self {
+            Self::Auto => {
+                std::env::var_os("NO_COLOR").is_none() && console::colors_enabled_stderr()
+            }
+            Self::Always => true,
+            Self::Never => false,
+        }
+    }
+}
+
+impl Default for ColorChoice {
+    fn default() -> Self {
+        Self::Auto
+    }
+}
+
+impl FromStr for ColorChoice {
+    type Err = CargoMSRVError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(Self::Auto),
+            "always" => Ok(Self::Always),
+            "never" => Ok(Self::Never),
+            unknown => Err(CargoMSRVError::InvalidConfig(format!(
+                "Given color choice '{}' is not valid",
+                unknown
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for ColorChoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Auto => write!(f, "auto"),
+            Self::Always => write!(f, "always"),
+            Self::Never => write!(f, "never"),
+        }
+    }
+}
+
+/// The HTTP endpoint which is notified of the results of a run.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct WebhookOptions {
+    url: String,
+    include_compatibility: bool,
+}
+
+impl WebhookOptions {
+    pub fn new(url: impl Into<String>, include_compatibility: bool) -> Self {
+        Self {
+            url: url.into(),
+            include_compatibility,
+        }
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Whether the result of each toolchain check is sent, in addition to the final result.
+    pub fn include_compatibility(&self) -> bool {
+        self.include_compatibility
+    }
+}
+
+/// The kinds of events which are written by the json output, by the `type` of their message, e.g.
+/// `compatibility` or `msrv_result`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct EventFilter {
+    kinds: Vec<String>,
+}
+
+impl EventFilter {
+    pub fn new<I, S>(kinds: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            kinds: kinds.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    pub fn kinds(&self) -> &[String] {
+        &self.kinds
+    }
+
+    /// Whether events of the given kind are written.
+    pub fn selects(&self, kind: &str) -> bool {
+        self.kinds.iter().any(|selected| selected == kind)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn explicit_choice_ignores_terminal() {
+        assert!(ColorChoice::Always.colors_enabled());
+        assert!(!ColorChoice::Never.colors_enabled());
+    }
+}
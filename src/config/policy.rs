@@ -0,0 +1,10 @@
+use std::path::PathBuf;
+
+#[derive(Clone, Debug)]
+pub enum PolicyCmdConfig {
+    /// Validate the MSRV against the rules of a policy file
+    Check {
+        policy: PathBuf,
+        result: Option<PathBuf>,
+    },
+}
@@ -0,0 +1,162 @@
+//! Options which are remembered for each user, so they don't have to be given on every invocation.
+//!
+//! Preferences are stored in `preferences.toml`, in the `cargo-msrv` folder of the configuration
+//! directory of the user (e.g. `~/.config/cargo-msrv/preferences.toml` on Linux), and are set with
+//! `cargo msrv config set <KEY> <VALUE>`. An option given on the command line takes precedence
+//! over its preference.
+
+use std::path::{Path, PathBuf};
+
+use toml_edit::{value, Document, Item};
+
+use crate::config::{OutputFormat, ReleaseSource, SearchMethod};
+use crate::error::{CargoMSRVError, IoErrorSource, TResult};
+
+/// The configuration keys which can be set as preference.
+pub const PREFERENCE_KEYS: &[&str] = &["output_format", "release_source", "search_method"];
+
+/// The path of the preferences file, or `None` if the configuration directory of the user can't
+/// be determined.
+pub fn preferences_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("cargo-msrv").join("preferences.toml"))
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Preferences {
+    output_format: Option<OutputFormat>,
+    release_source: Option<ReleaseSource>,
+    search_method: Option<SearchMethod>,
+}
+
+impl Preferences {
+    /// Read the preferences from the given file. A file which does not exist holds no preferences.
+    pub fn read(path: &Path) -> TResult<Self> {
+        let document = match read_document(path)? {
+            Some(document) => document,
+            None => return Ok(Self::default()),
+        };
+
+        let mut preferences = Self::default();
+
+        for &key in PREFERENCE_KEYS {
+            if let Some(value) = document.get(key).and_then(Item::as_str) {
+                preferences.set(key, value)?;
+            }
+        }
+
+        Ok(preferences)
+    }
+
+    /// Set the preference with the given key, and write it to the given file. The other contents
+    /// of the file, like comments, are kept as is.
+    pub fn write(path: &Path, key: &str, preference: &str) -> TResult<()> {
+        // Reject unknown keys and invalid values, before they end up in the file
+        Self::default().set(key, preference)?;
+
+        let mut document = read_document(path)?.unwrap_or_default();
+        document[key] = value(preference);
+
+        if let Some(folder) = path.parent() {
+            std::fs::create_dir_all(folder).map_err(|error| CargoMSRVError::Io {
+                error,
+                source: IoErrorSource::CreateDir(folder.to_path_buf()),
+            })?;
+        }
+
+        std::fs::write(path, document.to_string()).map_err(|error| CargoMSRVError::Io {
+            error,
+            source: IoErrorSource::WriteFile(path.to_path_buf()),
+        })
+    }
+
+    fn set(&mut self, key: &str, preference: &str) -> TResult<()> {
+        match key {
+            "output_format" => self.output_format = Some(preference.parse()?),
+            "release_source" => self.release_source = Some(preference.parse()?),
+            "search_method" => self.search_method = Some(preference.parse()?),
+            unknown => {
+                return Err(CargoMSRVError::InvalidConfig(format!(
+                    "Unable to set preference '{}', expected one of: {}",
+                    unknown,
+                    PREFERENCE_KEYS.join(", ")
+                )))
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn output_format(&self) -> Option<OutputFormat> {
+        self.output_format
+    }
+
+    pub fn release_source(&self) -> Option<ReleaseSource> {
+        self.release_source
+    }
+
+    pub fn search_method(&self) -> Option<SearchMethod> {
+        self.search_method
+    }
+}
+
+fn read_document(path: &Path) -> TResult<Option<Document>> {
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(error) => {
+            return Err(CargoMSRVError::Io {
+                error,
+                source: IoErrorSource::ReadFile(path.to_path_buf()),
+            })
+        }
+    };
+
+    let document = contents.parse::<Document>()?;
+
+    Ok(Some(document))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_dir::{DirBuilder, TestDir};
+
+    #[test]
+    fn missing_file_holds_no_preferences() {
+        let tmp = TestDir::temp();
+
+        let preferences = Preferences::read(&tmp.path("preferences.toml")).unwrap();
+
+        assert_eq!(preferences, Preferences::default());
+    }
+
+    #[test]
+    fn round_trip() {
+        let tmp = TestDir::temp();
+        let path = tmp.path("cargo-msrv").join("preferences.toml");
+
+        Preferences::write(&path, "output_format", "json").unwrap();
+        Preferences::write(&path, "search_method", "linear").unwrap();
+        Preferences::write(&path, "output_format", "ci").unwrap();
+
+        let preferences = Preferences::read(&path).unwrap();
+
+        assert_eq!(preferences.output_format(), Some(OutputFormat::Ci));
+        assert_eq!(preferences.search_method(), Some(SearchMethod::Linear));
+        assert_eq!(preferences.release_source(), None);
+    }
+
+    #[yare::parameterized(
+        unknown_key = { "min", "1.56" },
+        invalid_output_format = { "output_format", "yaml" },
+        invalid_search_method = { "search_method", "random" },
+        invalid_release_source = { "release_source", "somewhere" },
+    )]
+    fn rejected(key: &str, preference: &str) {
+        let tmp = TestDir::temp();
+        let path = tmp.path("preferences.toml");
+
+        assert!(Preferences::write(&path, key, preference).is_err());
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,303 @@
+//! Settings which are shared by the crates of a workspace, so they don't have to be given on every
+//! invocation for each member.
+//!
+//! The settings are read from the `.cargo-msrv.toml` file in the root of the workspace, and are
+//! inherited by each member. A member may override them with a `.cargo-msrv.toml` file in its own
+//! directory. An option given on the command line, or by an environment variable, takes precedence
+//! over both.
+
+use std::path::{Path, PathBuf};
+
+use toml_edit::{Document, Item};
+
+use crate::error::{CargoMSRVError, IoErrorSource, TResult};
+use crate::manifest::bare_version::BareVersion;
+use crate::manifest::workspace::workspace_root;
+
+/// The name of the project config file.
+pub const PROJECT_CONFIG_FILE: &str = ".cargo-msrv.toml";
+
+/// The configuration keys which can be set in a project config file.
+pub const PROJECT_CONFIG_KEYS: &[&str] = &[
+    "check_command",
+    "minimum_version",
+    "maximum_version",
+    "excluded_members",
+];
+
+/// A setting, with the project config file from which it was read.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Setting<T> {
+    value: T,
+    path: PathBuf,
+}
+
+impl<T> Setting<T> {
+    fn new(value: T, path: &Path) -> Self {
+        Self {
+            value,
+            path: path.to_path_buf(),
+        }
+    }
+
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+/// The settings of the project config files which apply to a crate, merged.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ProjectConfig {
+    /// The files which were read, from the workspace root to the crate
+    files: Vec<PathBuf>,
+    /// Whether the crate is the root of its workspace
+    is_workspace_root: bool,
+    check_command: Option<Setting<Vec<String>>>,
+    minimum_version: Option<Setting<BareVersion>>,
+    maximum_version: Option<Setting<BareVersion>>,
+    excluded_members: Option<Setting<Vec<String>>>,
+}
+
+impl ProjectConfig {
+    /// Resolve the settings which apply to the crate in the given directory: those of the root of
+    /// its workspace, overridden by those of the crate itself. A file which does not exist holds
+    /// no settings.
+    pub fn resolve(crate_dir: &Path) -> TResult<Self> {
+        let root = workspace_root(crate_dir);
+        let is_workspace_root = root.as_deref() == Some(crate_dir);
+
+        let dirs = root
+            .iter()
+            .filter(|_| !is_workspace_root)
+            .map(PathBuf::as_path)
+            .chain(std::iter::once(crate_dir));
+
+        let mut config = Self {
+            is_workspace_root,
+            ..Self::default()
+        };
+
+        for dir in dirs {
+            let path = dir.join(PROJECT_CONFIG_FILE);
+
+            if let Some(document) = read_document(&path)? {
+                // a member checks itself only, see `ProjectConfig::excluded_members`
+                if !is_workspace_root
+                    && dir == crate_dir
+                    && document.contains_key("excluded_members")
+                {
+                    warn!(
+                        path = %path.display(),
+                        "excluded_members only applies to the root of a workspace, and is ignored"
+                    );
+                }
+
+                config.merge(&path, &document)?;
+                config.files.push(path);
+            }
+        }
+
+        Ok(config)
+    }
+
+    /// Override the settings with those of the given file.
+    fn merge(&mut self, path: &Path, document: &Document) -> TResult<()> {
+        for (key, item) in document.iter() {
+            let invalid = |expected: &str| {
+                CargoMSRVError::InvalidConfig(format!(
+                    "Unable to read '{}' in '{}', expected {}",
+                    key,
+                    path.display(),
+                    expected
+                ))
+            };
+
+            match key {
+                "check_command" => {
+                    let command = strings(item)
+                        .filter(|command| !command.is_empty())
+                        .ok_or_else(|| invalid("a non-empty list of strings"))?;
+                    self.check_command = Some(Setting::new(command, path));
+                }
+                "minimum_version" => {
+                    let version = version(item).ok_or_else(|| invalid("a Rust version"))?;
+                    self.minimum_version = Some(Setting::new(version, path));
+                }
+                "maximum_version" => {
+                    let version = version(item).ok_or_else(|| invalid("a Rust version"))?;
+                    self.maximum_version = Some(Setting::new(version, path));
+                }
+                "excluded_members" => {
+                    let members = strings(item).ok_or_else(|| invalid("a list of strings"))?;
+                    self.excluded_members = Some(Setting::new(members, path));
+                }
+                unknown => {
+                    return Err(CargoMSRVError::InvalidConfig(format!(
+                        "Unknown key '{}' in '{}', expected one of: {}",
+                        unknown,
+                        path.display(),
+                        PROJECT_CONFIG_KEYS.join(", ")
+                    )))
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The project config files which were read, from the workspace root to the crate. A file
+    /// overrides the settings of the files before it.
+    pub fn files(&self) -> &[PathBuf] {
+        &self.files
+    }
+
+    pub fn check_command(&self) -> Option<&Setting<Vec<String>>> {
+        self.check_command.as_ref()
+    }
+
+    pub fn minimum_version(&self) -> Option<&Setting<BareVersion>> {
+        self.minimum_version.as_ref()
+    }
+
+    pub fn maximum_version(&self) -> Option<&Setting<BareVersion>> {
+        self.maximum_version.as_ref()
+    }
+
+    /// The workspace members which are not checked. Only applies to the root of the workspace,
+    /// since a member checks itself only.
+    pub fn excluded_members(&self) -> Option<&Setting<Vec<String>>> {
+        self.excluded_members
+            .as_ref()
+            .filter(|_| self.is_workspace_root)
+    }
+}
+
+fn strings(item: &Item) -> Option<Vec<String>> {
+    item.as_array()?
+        .iter()
+        .map(|value| value.as_str().map(String::from))
+        .collect()
+}
+
+fn version(item: &Item) -> Option<BareVersion> {
+    item.as_str()?.parse().ok()
+}
+
+fn read_document(path: &Path) -> TResult<Option<Document>> {
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(error) => {
+            return Err(CargoMSRVError::Io {
+                error,
+                source: IoErrorSource::ReadFile(path.to_path_buf()),
+            })
+        }
+    };
+
+    let document = contents.parse::<Document>()?;
+
+    Ok(Some(document))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_dir::{DirBuilder, TestDir};
+
+    fn write(dir: &Path, path: &str, contents: &str) {
+        let path = dir.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    }
+
+    /// A workspace with a single member, in the `member` folder.
+    fn workspace(root_config: Option<&str>, member_config: Option<&str>) -> TestDir {
+        let tmp = TestDir::temp();
+        let member = tmp.path("member");
+
+        write(
+            tmp.root(),
+            "Cargo.toml",
+            "[workspace]\nmembers = [\"member\"]\n",
+        );
+        write(&member, "Cargo.toml", "[package]\nname = \"member\"\n");
+
+        if let Some(config) = root_config {
+            write(tmp.root(), PROJECT_CONFIG_FILE, config);
+        }
+
+        if let Some(config) = member_config {
+            write(&member, PROJECT_CONFIG_FILE, config);
+        }
+
+        tmp
+    }
+
+    #[test]
+    fn member_inherits_from_workspace() {
+        let dir = workspace(
+            Some("minimum_version = \"1.56\"\ncheck_command = [\"cargo\", \"test\"]\n"),
+            Some("minimum_version = \"1.60\"\n"),
+        );
+
+        let config = ProjectConfig::resolve(&dir.path("member")).unwrap();
+
+        let root_file = dir.path(PROJECT_CONFIG_FILE);
+        let member_file = dir.path("member").join(PROJECT_CONFIG_FILE);
+
+        assert_eq!(config.files(), &[root_file.clone(), member_file.clone()]);
+
+        let minimum = config.minimum_version().unwrap();
+        assert_eq!(minimum.value(), &BareVersion::TwoComponents(1, 60));
+        assert_eq!(minimum.path(), member_file);
+
+        let command = config.check_command().unwrap();
+        assert_eq!(command.value(), &vec!["cargo", "test"]);
+        assert_eq!(command.path(), root_file);
+
+        assert!(config.maximum_version().is_none());
+    }
+
+    #[test]
+    fn excluded_members_apply_to_workspace_root() {
+        let dir = workspace(Some("excluded_members = [\"member\"]\n"), None);
+
+        let root = ProjectConfig::resolve(dir.root()).unwrap();
+        let member = ProjectConfig::resolve(&dir.path("member")).unwrap();
+
+        assert_eq!(root.files(), &[dir.path(PROJECT_CONFIG_FILE)]);
+        assert_eq!(
+            root.excluded_members().map(Setting::value),
+            Some(&vec!["member".to_string()])
+        );
+        assert!(member.excluded_members().is_none());
+    }
+
+    #[test]
+    fn without_files() {
+        let dir = workspace(None, None);
+
+        let config = ProjectConfig::resolve(&dir.path("member")).unwrap();
+
+        assert!(config.files().is_empty());
+        assert!(config.check_command().is_none());
+    }
+
+    #[yare::parameterized(
+        unknown_key = { "min = \"1.56\"" },
+        invalid_version = { "minimum_version = \"one\"" },
+        empty_check_command = { "check_command = []" },
+        check_command_as_string = { "check_command = \"cargo test\"" },
+        invalid_toml = { "minimum_version = " },
+    )]
+    fn rejected(contents: &str) {
+        let dir = workspace(None, Some(contents));
+
+        assert!(ProjectConfig::resolve(&dir.path("member")).is_err());
+    }
+}
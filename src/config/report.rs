@@ -0,0 +1,7 @@
+use std::path::PathBuf;
+
+#[derive(Clone, Debug)]
+pub enum ReportCmdConfig {
+    /// Compare the json output of two cargo-msrv runs
+    Diff { old: PathBuf, new: PathBuf },
+}
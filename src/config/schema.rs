@@ -0,0 +1,7 @@
+use std::path::PathBuf;
+
+#[derive(Clone, Debug)]
+pub struct SchemaCmdConfig {
+    /// The directory to which the schema files are written, instead of printing the schema
+    pub out_dir: Option<PathBuf>,
+}
@@ -0,0 +1,5 @@
+#[derive(Clone, Debug)]
+pub struct ServeCmdConfig {
+    /// The address on which the server listens for the requests of other cargo-msrv invocations
+    pub address: String,
+}
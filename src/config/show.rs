@@ -0,0 +1,5 @@
+#[derive(Clone, Debug)]
+pub struct ShowCmdConfig {
+    /// Write the MSRV of the Cargo manifest to the configuration file of clippy, if they disagree
+    pub sync_clippy: bool,
+}
@@ -0,0 +1,291 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::config::FeatureSet;
+use crate::error::CargoMSRVError;
+
+/// A preset of the check, given with `--profile`, for crates which are built for targets without
+/// a toolchain of their own. The toolchain of the host is installed with the standard library of
+/// the target, and the dev-dependencies, which usually require `std`, are left out of the check.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CheckProfile {
+    /// An embedded `no_std` crate, checked without its default features, for
+    /// `thumbv7em-none-eabihf` unless another target is given
+    NoStd,
+    /// A crate for WebAssembly, checked for `wasm32-unknown-unknown` unless another target is given
+    Wasm,
+}
+
+impl CheckProfile {
+    pub(crate) fn variants() -> &'static [&'static str] {
+        &["no-std", "wasm"]
+    }
+
+    /// The target for which the crate is checked, when no target is given.
+    pub fn default_target(&self) -> &'static str {
+        match self {
+            Self::NoStd => "thumbv7em-none-eabihf",
+            Self::Wasm => "wasm32-unknown-unknown",
+        }
+    }
+
+    /// The features which are enabled by the default `check` command.
+    pub fn features(&self) -> FeatureSet {
+        match self {
+            Self::NoStd => FeatureSet::NoDefaultFeatures,
+            Self::Wasm => FeatureSet::Default,
+        }
+    }
+}
+
+impl FromStr for CheckProfile {
+    type Err = CargoMSRVError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "no-std" => Ok(Self::NoStd),
+            "wasm" => Ok(Self::Wasm),
+            unknown => Err(CargoMSRVError::InvalidConfig(format!(
+                "Given check profile '{}' is not valid",
+                unknown
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for CheckProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoStd => write!(f, "no-std"),
+            Self::Wasm => write!(f, "wasm"),
+        }
+    }
+}
+
+/// The rustup profile with which the toolchains are installed, given with `--install-profile`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum InstallProfile {
+    /// Just the compiler, cargo and the standard library. The components which the check command
+    /// requires, like `clippy`, are added on demand.
+    Minimal,
+    /// The components of the minimal profile, with the documentation, `clippy` and `rustfmt`
+    Default,
+    /// Each component which is available for the toolchain
+    Complete,
+}
+
+impl InstallProfile {
+    pub(crate) fn variants() -> &'static [&'static str] {
+        &["minimal", "default", "complete"]
+    }
+}
+
+impl Default for InstallProfile {
+    fn default() -> Self {
+        Self::Minimal
+    }
+}
+
+impl FromStr for InstallProfile {
+    type Err = CargoMSRVError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "minimal" => Ok(Self::Minimal),
+            "default" => Ok(Self::Default),
+            "complete" => Ok(Self::Complete),
+            unknown => Err(CargoMSRVError::InvalidConfig(format!(
+                "Given install profile '{}' is not valid",
+                unknown
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for InstallProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Minimal => write!(f, "minimal"),
+            Self::Default => write!(f, "default"),
+            Self::Complete => write!(f, "complete"),
+        }
+    }
+}
+
+/// The rustup components which are required by a cargo subcommand, and not part of the minimal
+/// profile.
+pub(crate) const SUBCOMMAND_COMPONENTS: &[(&str, &str)] = &[
+    ("clippy", "clippy"),
+    ("fmt", "rustfmt"),
+    ("miri", "miri"),
+    ("llvm-cov", "llvm-tools-preview"),
+];
+
+/// The rustup component with the given name, if it's one which a check command may require.
+pub(crate) fn required_component(name: &str) -> Option<&'static str> {
+    SUBCOMMAND_COMPONENTS
+        .iter()
+        .map(|(_, component)| *component)
+        .find(|component| *component == name)
+}
+
+/// The format of the toolchain file which is written with `--write-toolchain-file`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ToolchainFileFormat {
+    /// A `rust-toolchain` file, which holds just the channel
+    Legacy,
+    /// A `rust-toolchain.toml` file, with a `[toolchain]` table. The components and targets of an
+    /// existing file are preserved.
+    Toml,
+}
+
+impl ToolchainFileFormat {
+    pub(crate) fn variants() -> &'static [&'static str] {
+        &["legacy", "toml"]
+    }
+}
+
+impl Default for ToolchainFileFormat {
+    fn default() -> Self {
+        Self::Toml
+    }
+}
+
+impl FromStr for ToolchainFileFormat {
+    type Err = CargoMSRVError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "legacy" => Ok(Self::Legacy),
+            "toml" => Ok(Self::Toml),
+            unknown => Err(CargoMSRVError::InvalidConfig(format!(
+                "Given toolchain file format '{}' is not valid",
+                unknown
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for ToolchainFileFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Legacy => write!(f, "legacy"),
+            Self::Toml => write!(f, "toml"),
+        }
+    }
+}
+
+/// How an MSRV which matches none of the releases of the index is resolved to a release, given
+/// with `--version-resolution`. E.g. `1.54.0`, when only `1.54.1` and `1.54.2` are indexed.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum VersionResolution {
+    /// Fail, since the MSRV does not match any release
+    Strict,
+    /// Use the patch release of the same minor release which is closest to the MSRV, preferring
+    /// the more recent release on a tie
+    ClosestPatch,
+    /// Use the least recent release which is more recent than the MSRV
+    NearestNewer,
+}
+
+impl VersionResolution {
+    pub(crate) fn variants() -> &'static [&'static str] {
+        &["strict", "closest-patch", "nearest-newer"]
+    }
+}
+
+impl Default for VersionResolution {
+    fn default() -> Self {
+        Self::Strict
+    }
+}
+
+impl FromStr for VersionResolution {
+    type Err = CargoMSRVError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "strict" => Ok(Self::Strict),
+            "closest-patch" => Ok(Self::ClosestPatch),
+            "nearest-newer" => Ok(Self::NearestNewer),
+            unknown => Err(CargoMSRVError::InvalidConfig(format!(
+                "Given version resolution '{}' is not valid",
+                unknown
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for VersionResolution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Strict => write!(f, "strict"),
+            Self::ClosestPatch => write!(f, "closest-patch"),
+            Self::NearestNewer => write!(f, "nearest-newer"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod check_profile_tests {
+    use super::*;
+    use crate::config::{Action, ConfigBuilder};
+    use rust_releases::semver;
+
+    #[yare::parameterized(
+        no_std = { CheckProfile::NoStd, "1.56.0-x86_64-unknown-linux-gnu", "thumbv7em-none-eabihf" },
+        wasm = { CheckProfile::Wasm, "1.56.0-x86_64-unknown-linux-gnu", "wasm32-unknown-unknown" },
+    )]
+    fn runs_on_current_target(profile: CheckProfile, spec: &str, target: &str) {
+        let config = ConfigBuilder::new(Action::Find, "x86_64-unknown-linux-gnu")
+            .check_profile(profile)
+            .build();
+
+        let version = semver::Version::new(1, 56, 0);
+        let toolchain = config.toolchain_spec(&version);
+
+        assert_eq!(toolchain.spec(), spec);
+        assert_eq!(toolchain.target(), target);
+        assert!(toolchain.is_cross_compiling());
+    }
+
+    #[test]
+    fn given_host_and_target() {
+        let config = ConfigBuilder::new(Action::Find, "x86_64-unknown-linux-gnu")
+            .check_profile(CheckProfile::NoStd)
+            .host("aarch64-apple-darwin")
+            .target("thumbv6m-none-eabi")
+            .build();
+
+        let version = semver::Version::new(1, 56, 0);
+        let toolchain = config.toolchain_spec(&version);
+
+        assert_eq!(toolchain.spec(), "1.56.0-aarch64-apple-darwin");
+        assert_eq!(toolchain.target(), "thumbv6m-none-eabi");
+    }
+}
+
+#[cfg(test)]
+mod install_profile_tests {
+    use super::*;
+    use crate::config::{Action, ConfigBuilder};
+
+    #[yare::parameterized(
+        check = { &["cargo", "check"], &[] },
+        clippy = { &["cargo", "clippy", "--", "-D", "warnings"], &["clippy"] },
+        clippy_with_toolchain = { &["cargo", "+nightly", "clippy"], &["clippy"] },
+        fmt = { &["cargo", "fmt", "--check"], &["rustfmt"] },
+        llvm_cov = { &["cargo", "llvm-cov", "--no-report"], &["llvm-tools-preview"] },
+        not_cargo = { &["clippy-driver"], &[] },
+    )]
+    fn required_components(command: &[&'static str], expected: &[&str]) {
+        let config = ConfigBuilder::new(Action::Find, "x")
+            .check_command(command.to_vec())
+            .build();
+
+        assert_eq!(config.required_components(), expected);
+    }
+}
@@ -0,0 +1,94 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::PathBuf;
+
+/// Where the value of a configuration option came from, as shown by `cargo msrv config show`.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case", tag = "type")]
+pub enum ValueSource {
+    /// The option was not set, so its default value is used
+    Default,
+    /// The option was given on the command line, with the given flag
+    CommandLine { flag: String },
+    /// The option was given by the given environment variable
+    Environment { variable: String },
+    /// The value was read from the given key of the Cargo manifest
+    Manifest { path: PathBuf, key: String },
+    /// The value was read from the preferences file of the user, at the given path
+    Preferences { path: PathBuf },
+    /// The value was read from the given key of a project config file, at the given path
+    ConfigFile { path: PathBuf, key: String },
+}
+
+impl fmt::Display for ValueSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Default => write!(f, "default"),
+            Self::CommandLine { flag } => write!(f, "command line ({})", flag),
+            Self::Environment { variable } => write!(f, "environment ({})", variable),
+            Self::Manifest { path, key } => {
+                write!(f, "manifest ({} in {})", key, path.display())
+            }
+            Self::Preferences { path } => write!(f, "preferences ({})", path.display()),
+            Self::ConfigFile { path, key } => {
+                write!(f, "config file ({} in {})", key, path.display())
+            }
+        }
+    }
+}
+
+/// The sources of the configuration options which were not set to their default value, by
+/// configuration key.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ValueSources {
+    sources: BTreeMap<&'static str, ValueSource>,
+}
+
+impl ValueSources {
+    pub fn insert(&mut self, key: &'static str, source: ValueSource) {
+        self.sources.insert(key, source);
+    }
+
+    /// The source of the option with the given key, or [`ValueSource::Default`] if no source
+    /// was recorded.
+    pub fn get(&self, key: &str) -> ValueSource {
+        self.sources
+            .get(key)
+            .cloned()
+            .unwrap_or(ValueSource::Default)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[yare::parameterized(
+        default = { ValueSource::Default, "default" },
+        command_line = { ValueSource::CommandLine { flag: "--min".to_string() }, "command line (--min)" },
+        environment = { ValueSource::Environment { variable: "CARGO_MSRV_LOCALE".to_string() }, "environment (CARGO_MSRV_LOCALE)" },
+        manifest = { ValueSource::Manifest { path: PathBuf::from("Cargo.toml"), key: "package.edition".to_string() }, "manifest (package.edition in Cargo.toml)" },
+        preferences = { ValueSource::Preferences { path: PathBuf::from("preferences.toml") }, "preferences (preferences.toml)" },
+        config_file = { ValueSource::ConfigFile { path: PathBuf::from(".cargo-msrv.toml"), key: "minimum_version".to_string() }, "config file (minimum_version in .cargo-msrv.toml)" },
+    )]
+    fn display(source: ValueSource, expected: &str) {
+        assert_eq!(source.to_string(), expected);
+    }
+
+    #[test]
+    fn unrecorded_source_is_default() {
+        let mut sources = ValueSources::default();
+        sources.insert(
+            "locale",
+            ValueSource::Environment {
+                variable: "CARGO_MSRV_LOCALE".to_string(),
+            },
+        );
+
+        assert_eq!(sources.get("target"), ValueSource::Default);
+        assert!(matches!(
+            sources.get("locale"),
+            ValueSource::Environment { .. }
+        ));
+    }
+}
@@ -1,6 +1,65 @@
 use crate::manifest::bare_version::BareVersion;
+use crate::release_index_source::AuthToken;
+use std::path::PathBuf;
 
 #[derive(Clone, Debug)]
 pub struct VerifyCmdConfig {
     pub rust_version: Option<BareVersion>,
+    /// Verify the MSRV recorded in a result file, as written by `--write-result`
+    pub against_result: Option<PathBuf>,
+    /// Write the verified Rust version to the Cargo manifest, if it is newer than the declared MSRV
+    pub write_on_success: bool,
+    /// Verify two component versions against their `.0` release, instead of the most recent
+    /// patch release
+    pub pin_patch: bool,
+    /// Verify only the workspace members which are affected by the changes since this git
+    /// revision, instead of the crate itself
+    pub changed_since: Option<String>,
+    /// Fail when the MSRV in the Cargo manifest differs from the one of the latest version which
+    /// is published to crates.io
+    pub check_published_metadata: bool,
+}
+
+/// The GitHub check run to which the outcome of `cargo msrv verify` is reported.
+#[derive(Debug, Clone)]
+pub struct GitHubCheckOptions {
+    /// The repository, as `OWNER/REPO`
+    repository: String,
+    /// The commit on which the check run is created
+    sha: String,
+    name: String,
+    token: AuthToken,
+}
+
+impl GitHubCheckOptions {
+    pub fn new(
+        repository: impl Into<String>,
+        sha: impl Into<String>,
+        name: impl Into<String>,
+        token: AuthToken,
+    ) -> Self {
+        Self {
+            repository: repository.into(),
+            sha: sha.into(),
+            name: name.into(),
+            token,
+        }
+    }
+
+    pub fn repository(&self) -> &str {
+        &self.repository
+    }
+
+    pub fn sha(&self) -> &str {
+        &self.sha
+    }
+
+    /// The name of the check run, as shown on the commit
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn token(&self) -> &AuthToken {
+        &self.token
+    }
 }
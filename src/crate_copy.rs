@@ -0,0 +1,123 @@
+//! Temporary copies of a crate, e.g. to check the crate in a sandbox, without touching the crate
+//! itself.
+
+use std::path::Path;
+
+use crate::error::{CargoMSRVError, IoErrorSource, TResult};
+use crate::long_path::long_path;
+
+/// Copy the crate to the given directory, without build artifacts and hidden files, like the
+/// `.git` folder.
+pub(crate) fn copy_crate(from: &Path, to: &Path) -> TResult<()> {
+    // the copy may lie deeper than the crate, e.g. when the sandbox is within the workspace
+    let from = &long_path(from);
+    let to = &long_path(to);
+
+    create_dir(to)?;
+
+    // the copy is never copied into itself, when it lies within the crate
+    let copy = std::fs::canonicalize(to).map_err(|error| CargoMSRVError::Io {
+        error,
+        source: IoErrorSource::ReadDir(to.to_path_buf()),
+    })?;
+
+    copy_dir(from, to, &copy)
+}
+
+fn copy_dir(from: &Path, to: &Path, copy: &Path) -> TResult<()> {
+    create_dir(to)?;
+
+    let entries = std::fs::read_dir(from).map_err(|error| CargoMSRVError::Io {
+        error,
+        source: IoErrorSource::ReadDir(from.to_path_buf()),
+    })?;
+
+    for entry in entries {
+        let entry = entry.map_err(|error| CargoMSRVError::Io {
+            error,
+            source: IoErrorSource::ReadDir(from.to_path_buf()),
+        })?;
+
+        let name = entry.file_name();
+        let is_skipped = name == "target" || name.to_string_lossy().starts_with('.');
+
+        if is_skipped {
+            continue;
+        }
+
+        let path = entry.path();
+        let destination = to.join(&name);
+
+        if path.is_dir() {
+            let is_copy = std::fs::canonicalize(&path).map_or(false, |path| path.starts_with(copy));
+
+            if !is_copy {
+                copy_dir(&path, &destination, copy)?;
+            }
+        } else {
+            std::fs::copy(&path, &destination).map_err(|error| CargoMSRVError::Io {
+                error,
+                source: IoErrorSource::CopyFile(path.clone()),
+            })?;
+        }
+    }
+
+    Ok(())
+}
+
+fn create_dir(dir: &Path) -> TResult<()> {
+    std::fs::create_dir_all(dir).map_err(|error| CargoMSRVError::Io {
+        error,
+        source: IoErrorSource::CreateDir(dir.to_path_buf()),
+    })
+}
+
+/// Remove the copy of the crate. Failing to do so is not fatal, since it's only a temporary copy.
+pub(crate) fn remove_copy(copy: &Path) {
+    if copy.exists() {
+        if let Err(error) = std::fs::remove_dir_all(copy) {
+            warn!(path = %copy.display(), %error, "unable to remove copy of the crate");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_dir::{DirBuilder, FileType, TestDir};
+
+    #[test]
+    fn copy_skips_build_artifacts_and_hidden_files() {
+        let tmp = TestDir::temp()
+            .create("crate", FileType::Dir)
+            .create("crate/src", FileType::Dir)
+            .create("crate/src/lib.rs", FileType::EmptyFile)
+            .create("crate/Cargo.toml", FileType::EmptyFile)
+            .create("crate/target", FileType::Dir)
+            .create("crate/.git", FileType::Dir);
+
+        let copy = tmp.path("copy");
+        copy_crate(&tmp.path("crate"), &copy).unwrap();
+
+        assert!(copy.join("Cargo.toml").is_file());
+        assert!(copy.join("src").join("lib.rs").is_file());
+        assert!(!copy.join("target").exists());
+        assert!(!copy.join(".git").exists());
+    }
+
+    #[test]
+    fn copy_within_crate() {
+        let tmp = TestDir::temp()
+            .create("crate", FileType::Dir)
+            .create("crate/src", FileType::Dir)
+            .create("crate/src/lib.rs", FileType::EmptyFile)
+            .create("crate/Cargo.toml", FileType::EmptyFile);
+
+        let copy = tmp.path("crate").join("ci-tmp").join("copy");
+        copy_crate(&tmp.path("crate"), &copy).unwrap();
+
+        assert!(copy.join("Cargo.toml").is_file());
+        assert!(copy.join("src").join("lib.rs").is_file());
+        assert!(!copy.join("ci-tmp").join("copy").exists());
+    }
+}
@@ -0,0 +1,421 @@
+//! Read the release history of crates from a local checkout (or dump) of the crates.io index, or
+//! of the index of an alternative registry.
+//!
+//! See the [Cargo book](https://doc.rust-lang.org/cargo/reference/registry-index.html#index-files)
+//! for a description of the index layout.
+
+use std::path::{Path, PathBuf};
+
+use cargo_metadata::Source;
+
+use crate::config::list::RegistryIndex;
+use crate::error::{CargoMSRVError, IoErrorSource, TResult};
+use crate::manifest::bare_version::BareVersion;
+use crate::semver;
+
+/// The URLs of the crates.io index, as found in the `source` of packages published to crates.io.
+const CRATES_IO_INDEX_URLS: [&str; 2] = [
+    "https://github.com/rust-lang/crates.io-index",
+    "https://index.crates.io/",
+];
+
+pub struct CratesIndex {
+    root: PathBuf,
+}
+
+impl CratesIndex {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Returns how the `rust-version` field evolved across the releases of the given crate,
+    /// or `None` if the crate can not be found in the index.
+    pub fn rust_version_history(&self, name: &str) -> TResult<Option<RustVersionHistory>> {
+        let path = self.root.join(index_file_path(name));
+
+        if !path.is_file() {
+            return Ok(None);
+        }
+
+        let contents = std::fs::read_to_string(&path).map_err(|error| CargoMSRVError::Io {
+            error,
+            source: IoErrorSource::ReadFile(path.clone()),
+        })?;
+
+        Ok(RustVersionHistory::from_index_file(&contents))
+    }
+
+    /// Returns the most recent release of the given crate, less recent than `below`, if given,
+    /// which declares a `rust-version` that the given Rust version satisfies, or `None` if there
+    /// is no such release, or the crate can not be found in the index.
+    pub fn newest_supporting(
+        &self,
+        name: &str,
+        rust_version: &semver::Version,
+        below: Option<&semver::Version>,
+    ) -> TResult<Option<semver::Version>> {
+        let path = self.root.join(index_file_path(name));
+
+        if !path.is_file() {
+            return Ok(None);
+        }
+
+        let contents = std::fs::read_to_string(&path).map_err(|error| CargoMSRVError::Io {
+            error,
+            source: IoErrorSource::ReadFile(path.clone()),
+        })?;
+
+        Ok(newest_supporting(&contents, rust_version, below))
+    }
+}
+
+/// The local index checkouts of the registries to which dependencies may be published.
+pub struct RegistryIndexes {
+    crates_io: Option<CratesIndex>,
+    alternatives: Vec<(String, CratesIndex)>,
+}
+
+impl RegistryIndexes {
+    pub fn new(crates_io: Option<&Path>, alternatives: &[RegistryIndex]) -> Self {
+        Self {
+            crates_io: crates_io.map(CratesIndex::new),
+            alternatives: alternatives
+                .iter()
+                .map(|registry| (registry.url.clone(), CratesIndex::new(&registry.path)))
+                .collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.crates_io.is_none() && self.alternatives.is_empty()
+    }
+
+    /// The index of the registry from which a package with the given source originates, or `None`
+    /// if the package does not originate from a registry (e.g. a path or git dependency), or when
+    /// no index was given for its registry.
+    pub fn index_of(&self, source: Option<&Source>) -> Option<&CratesIndex> {
+        let url = source.and_then(registry_url)?;
+
+        if is_crates_io(url) {
+            return self.crates_io.as_ref();
+        }
+
+        self.alternatives
+            .iter()
+            .find(|(alternative, _)| same_url(alternative, url))
+            .map(|(_, index)| index)
+    }
+}
+
+/// The URL of the index of the registry from which a package with the given source originates, or
+/// `None` if the package does not originate from a registry.
+pub fn registry_url(source: &Source) -> Option<&str> {
+    let repr = source.repr.as_str();
+
+    repr.strip_prefix("registry+")
+        .or_else(|| repr.strip_prefix("sparse+"))
+}
+
+pub fn is_crates_io(url: &str) -> bool {
+    CRATES_IO_INDEX_URLS
+        .iter()
+        .any(|crates_io| same_url(crates_io, url))
+}
+
+fn same_url(lhs: &str, rhs: &str) -> bool {
+    lhs.trim_end_matches('/') == rhs.trim_end_matches('/')
+}
+
+/// The location of the index file of a crate, relative to the root of the index.
+fn index_file_path(name: &str) -> PathBuf {
+    let name = name.to_lowercase();
+
+    match name.len() {
+        1 => Path::new("1").join(&name),
+        2 => Path::new("2").join(&name),
+        3 => Path::new("3").join(&name[..1]).join(&name),
+        _ => Path::new(&name[..2]).join(&name[2..4]).join(&name),
+    }
+}
+
+fn newest_supporting(
+    contents: &str,
+    rust_version: &semver::Version,
+    below: Option<&semver::Version>,
+) -> Option<semver::Version> {
+    contents
+        .lines()
+        .filter_map(|line| serde_json::from_str::<IndexEntry>(line).ok())
+        .filter(|entry| !entry.yanked && entry.vers.pre.is_empty())
+        .filter(|entry| below.map_or(true, |below| &entry.vers < below))
+        .filter(|entry| {
+            entry
+                .rust_version
+                .as_deref()
+                .and_then(|declared| declared.parse::<BareVersion>().ok())
+                .map_or(false, |declared| {
+                    &declared.to_semver_version() <= rust_version
+                })
+        })
+        .map(|entry| entry.vers)
+        .max()
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct IndexEntry {
+    vers: semver::Version,
+    #[serde(default)]
+    rust_version: Option<String>,
+    #[serde(default)]
+    yanked: bool,
+}
+
+/// Whether the latest release of a crate declares a `rust-version`, and since which release that
+/// has been the case.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct RustVersionHistory {
+    latest: semver::Version,
+    latest_rust_version: Option<String>,
+    change: Option<RustVersionChange>,
+}
+
+/// The release in which the `rust-version` field was most recently added or removed.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case", tag = "kind")]
+pub enum RustVersionChange {
+    Gained { version: semver::Version },
+    Lost { version: semver::Version },
+}
+
+impl RustVersionHistory {
+    fn from_index_file(contents: &str) -> Option<Self> {
+        let mut entries = contents
+            .lines()
+            .filter_map(|line| serde_json::from_str::<IndexEntry>(line).ok())
+            .filter(|entry| !entry.yanked)
+            .collect::<Vec<_>>();
+
+        // most recent release first
+        entries.sort_by(|lhs, rhs| rhs.vers.cmp(&lhs.vers));
+
+        let latest = entries.first()?;
+        let has_rust_version = latest.rust_version.is_some();
+
+        // the oldest release of the most recent uninterrupted run of releases which did (not) declare
+        // a rust-version; only a change if an older release exists outside the run
+        let run = entries
+            .iter()
+            .take_while(|entry| entry.rust_version.is_some() == has_rust_version)
+            .count();
+
+        let change = (run < entries.len()).then(|| {
+            let version = entries[run - 1].vers.clone();
+
+            if has_rust_version {
+                RustVersionChange::Gained { version }
+            } else {
+                RustVersionChange::Lost { version }
+            }
+        });
+
+        Some(Self {
+            latest: latest.vers.clone(),
+            latest_rust_version: latest.rust_version.clone(),
+            change,
+        })
+    }
+
+    pub fn latest(&self) -> &semver::Version {
+        &self.latest
+    }
+
+    pub fn latest_rust_version(&self) -> Option<&str> {
+        self.latest_rust_version.as_deref()
+    }
+
+    pub fn change(&self) -> Option<&RustVersionChange> {
+        self.change.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_dir::{DirBuilder, FileType, TestDir};
+
+    fn source(repr: &str) -> Source {
+        Source {
+            repr: repr.to_string(),
+        }
+    }
+
+    #[yare::parameterized(
+        crates_io = { "registry+https://github.com/rust-lang/crates.io-index", Some("https://github.com/rust-lang/crates.io-index") },
+        sparse = { "sparse+https://index.crates.io/", Some("https://index.crates.io/") },
+        alternative = { "registry+https://dl.example.com/index", Some("https://dl.example.com/index") },
+        git = { "git+https://github.com/foresterre/cargo-msrv?branch=main#0123abc", None },
+    )]
+    fn url_of_registry(repr: &str, expected: Option<&str>) {
+        assert_eq!(registry_url(&source(repr)), expected);
+    }
+
+    #[yare::parameterized(
+        crates_io = { "registry+https://github.com/rust-lang/crates.io-index", Some("crates-io") },
+        crates_io_sparse = { "sparse+https://index.crates.io/", Some("crates-io") },
+        alternative = { "registry+https://dl.example.com/index", Some("example") },
+        alternative_trailing_slash = { "sparse+https://dl.example.com/index/", Some("example") },
+        unknown_registry = { "registry+https://dl.example.org/index", None },
+        git = { "git+https://github.com/foresterre/cargo-msrv#0123abc", None },
+    )]
+    fn index_of_registry(repr: &str, expected: Option<&str>) {
+        let alternatives: [RegistryIndex; 1] =
+            ["https://dl.example.com/index=example".parse().unwrap()];
+        let indexes = RegistryIndexes::new(Some(Path::new("crates-io")), &alternatives);
+
+        let index = indexes.index_of(Some(&source(repr)));
+        assert_eq!(
+            index.map(|index| index.root.as_path()),
+            expected.map(Path::new)
+        );
+    }
+
+    #[test]
+    fn index_of_path_dependency() {
+        let indexes = RegistryIndexes::new(Some(Path::new("crates-io")), &[]);
+
+        assert!(indexes.index_of(None).is_none());
+    }
+
+    #[yare::parameterized(
+        one = { "a", "1/a" },
+        two = { "ab", "2/ab" },
+        three = { "abc", "3/a/abc" },
+        four = { "abcd", "ab/cd/abcd" },
+        long = { "serde_json", "se/rd/serde_json" },
+        uppercase = { "Inflector", "in/fl/inflector" },
+    )]
+    fn path_in_index(name: &str, expected: &str) {
+        assert_eq!(index_file_path(name), PathBuf::from(expected));
+    }
+
+    fn entry(version: &str, rust_version: Option<&str>) -> String {
+        match rust_version {
+            Some(rv) => format!(
+                r#"{{"name":"x","vers":"{}","rust_version":"{}","yanked":false}}"#,
+                version, rv
+            ),
+            None => format!(r#"{{"name":"x","vers":"{}","yanked":false}}"#, version),
+        }
+    }
+
+    #[test]
+    fn gained_rust_version() {
+        let contents = [
+            entry("0.1.0", None),
+            entry("0.2.0", None),
+            entry("0.3.0", Some("1.56")),
+            entry("0.4.0", Some("1.60")),
+        ]
+        .join("\n");
+
+        let history = RustVersionHistory::from_index_file(&contents).unwrap();
+
+        assert_eq!(history.latest(), &semver::Version::new(0, 4, 0));
+        assert_eq!(history.latest_rust_version(), Some("1.60"));
+        assert_eq!(
+            history.change(),
+            Some(&RustVersionChange::Gained {
+                version: semver::Version::new(0, 3, 0)
+            })
+        );
+    }
+
+    #[test]
+    fn lost_rust_version() {
+        let contents = [
+            entry("1.0.0", Some("1.56")),
+            entry("1.1.0", None),
+            entry("1.2.0", None),
+        ]
+        .join("\n");
+
+        let history = RustVersionHistory::from_index_file(&contents).unwrap();
+
+        assert_eq!(history.latest_rust_version(), None);
+        assert_eq!(
+            history.change(),
+            Some(&RustVersionChange::Lost {
+                version: semver::Version::new(1, 1, 0)
+            })
+        );
+    }
+
+    #[test]
+    fn never_declared_rust_version() {
+        let contents = [entry("1.0.0", None), entry("1.1.0", None)].join("\n");
+
+        let history = RustVersionHistory::from_index_file(&contents).unwrap();
+
+        assert_eq!(history.latest(), &semver::Version::new(1, 1, 0));
+        assert!(history.change().is_none());
+    }
+
+    #[yare::parameterized(
+        supported = { semver::Version::new(1, 58, 0), None, Some(semver::Version::new(0, 3, 1)) },
+        most_recent = { semver::Version::new(1, 70, 0), None, Some(semver::Version::new(0, 4, 0)) },
+        below = { semver::Version::new(1, 70, 0), Some(semver::Version::new(0, 4, 0)), Some(semver::Version::new(0, 3, 1)) },
+        unsupported = { semver::Version::new(1, 50, 0), None, None },
+    )]
+    fn newest_supporting_release(
+        rust_version: semver::Version,
+        below: Option<semver::Version>,
+        expected: Option<semver::Version>,
+    ) {
+        let contents = [
+            entry("0.2.0", None),
+            entry("0.3.0", Some("1.56")),
+            entry("0.3.1", Some("1.56.1")),
+            entry("0.4.0", Some("1.60")),
+            entry("0.5.0-rc.1", Some("1.56")),
+            r#"{"name":"x","vers":"0.3.2","rust_version":"1.56","yanked":true}"#.to_string(),
+        ]
+        .join("\n");
+
+        assert_eq!(
+            newest_supporting(&contents, &rust_version, below.as_ref()),
+            expected
+        );
+    }
+
+    #[test]
+    fn yanked_releases_are_ignored() {
+        let contents = [
+            entry("1.0.0", None),
+            r#"{"name":"x","vers":"1.1.0","rust_version":"1.56","yanked":true}"#.to_string(),
+        ]
+        .join("\n");
+
+        let history = RustVersionHistory::from_index_file(&contents).unwrap();
+
+        assert_eq!(history.latest(), &semver::Version::new(1, 0, 0));
+    }
+
+    #[test]
+    fn read_from_index() {
+        let tmp = TestDir::temp()
+            .create("se/rd", FileType::Dir)
+            .create("se/rd/serde", FileType::EmptyFile);
+        std::fs::write(tmp.path("se/rd/serde"), entry("1.0.0", Some("1.31"))).unwrap();
+
+        let index = CratesIndex::new(tmp.root());
+
+        let history = index.rust_version_history("serde").unwrap().unwrap();
+        assert_eq!(history.latest_rust_version(), Some("1.31"));
+
+        assert!(index
+            .rust_version_history("not-in-index")
+            .unwrap()
+            .is_none());
+    }
+}
@@ -1,5 +1,14 @@
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::thread::{self, JoinHandle};
+
+use crate::cancellation::CancellationToken;
 use crate::command::RustupCommand;
+use crate::config::InstallProfile;
 use crate::reporter::event::SetupToolchain;
+use crate::server::{Client, InstallRequest, InstallStatus, Request, Response};
 use crate::toolchain::ToolchainSpec;
 use crate::{CargoMSRVError, Reporter, TResult};
 
@@ -7,54 +16,696 @@ pub trait DownloadToolchain {
     fn download(&self, toolchain: &ToolchainSpec) -> TResult<()>;
 }
 
+/// The toolchains which are installed with rustup, as listed by `rustup toolchain list`.
+#[derive(Debug, Default)]
+pub struct InstalledToolchains {
+    toolchains: HashSet<String>,
+}
+
+impl InstalledToolchains {
+    /// Query rustup for the installed toolchains.
+    ///
+    /// If the installed toolchains can't be listed, none are assumed to be installed, so each
+    /// toolchain will be installed (or updated) by rustup as before.
+    pub fn query() -> Self {
+        let output = RustupCommand::new()
+            .with_stdout()
+            .with_stderr()
+            .toolchain_list();
+
+        match output {
+            Ok(output) if output.exit_status().success() => Self::parse(output.stdout()),
+            _ => {
+                warn!("unable to list the installed toolchains");
+                Self::default()
+            }
+        }
+    }
+
+    /// Parse the output of `rustup toolchain list`, which lists one toolchain per line,
+    /// optionally followed by an annotation like `(default)`.
+    fn parse(list: &str) -> Self {
+        let toolchains = list
+            .lines()
+            .filter_map(|line| line.split_whitespace().next())
+            .map(String::from)
+            .collect();
+
+        Self { toolchains }
+    }
+
+    pub fn contains(&self, toolchain: &ToolchainSpec) -> bool {
+        self.toolchains.contains(toolchain.spec())
+    }
+}
+
 #[derive(Debug)]
 pub struct ToolchainDownloader<'reporter, R: Reporter> {
     reporter: &'reporter R,
+    installed: &'reporter InstalledToolchains,
+    profile: InstallProfile,
+    components: Vec<&'static str>,
+    server: Option<String>,
+    cancellation: Option<CancellationToken>,
+    prefetched: Option<&'reporter PrefetchedToolchains>,
 }
 
 impl<'reporter, R: Reporter> ToolchainDownloader<'reporter, R> {
-    pub fn new(reporter: &'reporter R) -> Self {
-        Self { reporter }
+    pub fn new(reporter: &'reporter R, installed: &'reporter InstalledToolchains) -> Self {
+        Self {
+            reporter,
+            installed,
+            profile: InstallProfile::default(),
+            components: Vec::new(),
+            server: None,
+            cancellation: None,
+            prefetched: None,
+        }
+    }
+
+    /// Install the toolchains with the given rustup profile, instead of the minimal profile.
+    pub fn with_profile(mut self, profile: InstallProfile) -> Self {
+        self.profile = profile;
+        self
+    }
+
+    /// Install the given components with each toolchain, and add them to toolchains which are
+    /// already installed.
+    pub fn with_components(mut self, components: Vec<&'static str>) -> Self {
+        self.components = components;
+        self
+    }
+
+    /// Let the server started with `cargo msrv serve` at the given address install the
+    /// toolchains, so concurrent invocations don't install the same toolchain twice.
+    pub fn with_server(mut self, address: Option<&str>) -> Self {
+        self.server = address.map(String::from);
+        self
+    }
+
+    /// Stop installing the toolchain when the given token is cancelled.
+    pub fn with_optional_cancellation(mut self, token: Option<&CancellationToken>) -> Self {
+        self.cancellation = token.cloned();
+        self
+    }
+
+    /// Wait for a toolchain which is installed in the background, instead of installing it
+    /// again.
+    pub fn with_prefetched(mut self, prefetched: &'reporter PrefetchedToolchains) -> Self {
+        self.prefetched = Some(prefetched);
+        self
+    }
+
+    /// Add the required components to a toolchain which is already installed, since it may have
+    /// been installed with another profile. Components which are installed already are skipped by
+    /// rustup.
+    fn add_components(&self, toolchain: &ToolchainSpec) -> TResult<()> {
+        if self.components.is_empty() {
+            return Ok(());
+        }
+
+        info!(
+            toolchain = toolchain.spec(),
+            components = ?self.components,
+            "adding components"
+        );
+
+        let rustup = RustupCommand::new()
+            .with_stdout()
+            .with_stderr()
+            .with_args(["add", "--toolchain", toolchain.spec()])
+            .with_args(self.components.iter().copied())
+            .with_optional_cancellation(self.cancellation.as_ref())
+            .component()?;
+
+        let status = rustup.exit_status();
+
+        // e.g. clippy, which is only distributed with rustup since Rust 1.29
+        if !status.success() && is_unavailable_for_target(rustup.stderr()) {
+            info!(
+                toolchain = toolchain.spec(),
+                stderr = rustup.stderr(),
+                "components are not available for the toolchain"
+            );
+
+            return Err(CargoMSRVError::ToolchainUnavailable(toolchain.to_owned()));
+        }
+
+        if !status.success() {
+            error!(
+                toolchain = toolchain.spec(),
+                stderr = rustup.stderr(),
+                "rustup failed to add components"
+            );
+
+            return Err(CargoMSRVError::RustupInstallFailed(
+                toolchain.spec().to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn install(&self, toolchain: &ToolchainSpec) -> TResult<()> {
+        let rustup = RustupCommand::new()
+            .with_stdout()
+            .with_stderr()
+            .with_args(install_args(toolchain, self.profile, &self.components))
+            .with_optional_cancellation(self.cancellation.as_ref())
+            .install()?;
+
+        let status = rustup.exit_status();
+
+        if !status.success() && is_unavailable_for_target(rustup.stderr()) {
+            info!(
+                toolchain = toolchain.spec(),
+                stderr = rustup.stderr(),
+                "toolchain is not available for the target"
+            );
+
+            return Err(CargoMSRVError::ToolchainUnavailable(toolchain.to_owned()));
+        }
+
+        if !status.success() {
+            error!(
+                toolchain = toolchain.spec(),
+                stdout = rustup.stdout(),
+                stderr = rustup.stderr(),
+                "rustup failed to install toolchain"
+            );
+
+            eprintln!(
+                "Toolchain Download Failed -> \n\n{:?}\n{:?}\n{:?}\n{:?}\n<-\n\n",
+                toolchain.spec(),
+                rustup.stdout(),
+                rustup.stderr(),
+                "rustup failed to install toolchain"
+            );
+
+            return Err(CargoMSRVError::RustupInstallFailed(
+                toolchain.spec().to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Install the toolchain with the server started with `cargo msrv serve`, which installs each
+    /// toolchain once, and remembers which toolchains it installed already.
+    fn install_with_server(&self, address: &str, toolchain: &ToolchainSpec) -> TResult<()> {
+        let request = Request::Install(InstallRequest::new(
+            toolchain,
+            self.profile,
+            &self.components,
+        ));
+
+        let response = Client::new(address).request(&request)?;
+
+        installed_by_server(address, toolchain, response)
     }
 }
 
 impl<'reporter, R: Reporter> DownloadToolchain for ToolchainDownloader<'reporter, R> {
     #[instrument(skip(self, toolchain))]
     fn download(&self, toolchain: &ToolchainSpec) -> TResult<()> {
+        // the standard library of the target may be missing from an installed toolchain which
+        // runs on another host, so rustup is asked to install it regardless
+        if self.installed.contains(toolchain) && !toolchain.is_cross_compiling() {
+            info!(toolchain = toolchain.spec(), "toolchain already installed");
+
+            self.reporter.report_event(
+                SetupToolchain::new(toolchain.to_owned()).with_already_installed(true),
+            )?;
+
+            return self.add_components(toolchain);
+        }
+
         info!(toolchain = toolchain.spec(), "installing toolchain");
 
         self.reporter
             .run_scoped_event(SetupToolchain::new(toolchain.to_owned()), || {
-                let rustup = RustupCommand::new()
-                    .with_stdout()
-                    .with_stderr()
-                    .with_args(&["--profile", "minimal", toolchain.spec()])
-                    .install()?;
+                if let Some(prefetched) = self.prefetched {
+                    if prefetched.wait(toolchain) {
+                        info!(toolchain = toolchain.spec(), "toolchain was prefetched");
+                        return Ok(());
+                    }
+                }
+
+                if let Some(address) = &self.server {
+                    match self.install_with_server(address, toolchain) {
+                        Err(CargoMSRVError::ServerUnreachable { address, message }) => {
+                            warn!(
+                                %address,
+                                %message,
+                                "unable to reach the server, installing the toolchain locally"
+                            );
+                        }
+                        result => return result,
+                    }
+                }
+
+                self.install(toolchain)
+            })
+    }
+}
+
+/// The toolchains which are installed in the background, with `--prefetch-toolchains`, while
+/// another toolchain is checked.
+///
+/// The toolchains are installed one at a time, in the order in which they were prefetched, by a
+/// worker thread which is started by the first prefetch. Toolchains which are installed by the
+/// worker can't be reported, so the time spent waiting for them is reported instead, by the
+/// [`ToolchainDownloader`]. When the prefetched toolchains are dropped, e.g. because the search
+/// is done, the installation which is still running is cancelled, and the worker is stopped.
+#[derive(Debug, Default)]
+pub struct PrefetchedToolchains {
+    statuses: Arc<PrefetchStatuses>,
+    worker: RefCell<Option<PrefetchWorker>>,
+}
 
-                let status = rustup.exit_status();
+impl PrefetchedToolchains {
+    /// Install the given toolchains in the background, each with the given arguments to
+    /// `rustup install`, unless they were prefetched before. Toolchains which were prefetched
+    /// before, but which are not given again, and whose installation didn't start yet, are not
+    /// installed after all, since they're no longer expected to be checked.
+    ///
+    /// The installations are cancelled when the given token is cancelled.
+    pub fn prefetch(
+        &self,
+        toolchains: Vec<(String, Vec<String>)>,
+        cancellation: Option<&CancellationToken>,
+    ) {
+        let mut worker = self.worker.borrow_mut();
+        let worker = worker
+            .get_or_insert_with(|| PrefetchWorker::start(Arc::clone(&self.statuses), cancellation));
 
-                if !status.success() {
-                    error!(
+        let mut statuses = self.statuses.lock();
+
+        statuses.retain(|toolchain, status| {
+            *status != PrefetchStatus::Queued
+                || toolchains
+                    .iter()
+                    .any(|(prefetched, _)| prefetched == toolchain)
+        });
+
+        for (toolchain, args) in toolchains {
+            if statuses.contains_key(&toolchain) {
+                continue;
+            }
+
+            info!(toolchain = %toolchain, "prefetching toolchain");
+
+            statuses.insert(toolchain.clone(), PrefetchStatus::Queued);
+
+            // once cancelled, the worker is gone, and the toolchain stays queued, so it's
+            // installed when it's checked instead
+            let _ = worker.sender.send(PrefetchJob { toolchain, args });
+        }
+    }
+
+    /// Wait until the given toolchain is installed, if it's being installed in the background.
+    /// Returns whether it was installed, i.e. `false` if the toolchain was never prefetched, or
+    /// its installation failed. A toolchain whose installation didn't start yet is taken from
+    /// the queue, so it's installed by the caller instead.
+    pub fn wait(&self, toolchain: &ToolchainSpec) -> bool {
+        let mut statuses = self.statuses.lock();
+
+        loop {
+            match statuses.get(toolchain.spec()) {
+                Some(PrefetchStatus::Queued) => {
+                    statuses.remove(toolchain.spec());
+                    return false;
+                }
+                Some(PrefetchStatus::Installing) => {
+                    info!(
                         toolchain = toolchain.spec(),
-                        stdout = rustup.stdout(),
-                        stderr = rustup.stderr(),
-                        "rustup failed to install toolchain"
+                        "waiting for prefetched toolchain"
                     );
+                    statuses = self.statuses.changed.wait(statuses).unwrap();
+                }
+                Some(PrefetchStatus::Installed) => return true,
+                Some(PrefetchStatus::Failed) | None => return false,
+            }
+        }
+    }
+}
 
-                    eprintln!(
-                        "Toolchain Download Failed -> \n\n{:?}\n{:?}\n{:?}\n{:?}\n<-\n\n",
-                        toolchain.spec(),
-                        rustup.stdout(),
-                        rustup.stderr(),
-                        "rustup failed to install toolchain"
-                    );
+impl Drop for PrefetchedToolchains {
+    fn drop(&mut self) {
+        if let Some(worker) = self.worker.get_mut().take() {
+            worker.cancellation.cancel();
+            drop(worker.sender);
 
-                    return Err(CargoMSRVError::RustupInstallFailed(
-                        toolchain.spec().to_string(),
-                    ));
+            if worker.handle.join().is_err() {
+                warn!("the worker which prefetched the toolchains panicked");
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum PrefetchStatus {
+    /// Waiting for the worker to install the toolchain
+    Queued,
+    Installing,
+    Installed,
+    Failed,
+}
+
+/// The status of each prefetched toolchain, shared with the worker, which notifies waiting
+/// threads of each change.
+#[derive(Debug, Default)]
+struct PrefetchStatuses {
+    statuses: Mutex<HashMap<String, PrefetchStatus>>,
+    changed: Condvar,
+}
+
+impl PrefetchStatuses {
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, PrefetchStatus>> {
+        self.statuses.lock().unwrap()
+    }
+
+    /// Start installing the given toolchain, unless it's no longer queued.
+    fn claim(&self, toolchain: &str) -> bool {
+        let mut statuses = self.lock();
+
+        match statuses.get_mut(toolchain) {
+            Some(status) if *status == PrefetchStatus::Queued => {
+                *status = PrefetchStatus::Installing;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn finish(&self, toolchain: &str, installed: bool) {
+        let status = if installed {
+            PrefetchStatus::Installed
+        } else {
+            PrefetchStatus::Failed
+        };
+
+        self.lock().insert(toolchain.to_string(), status);
+        self.changed.notify_all();
+    }
+}
+
+#[derive(Debug)]
+struct PrefetchJob {
+    toolchain: String,
+    /// The arguments to `rustup install`
+    args: Vec<String>,
+}
+
+/// The thread which installs the prefetched toolchains.
+#[derive(Debug)]
+struct PrefetchWorker {
+    sender: Sender<PrefetchJob>,
+    handle: JoinHandle<()>,
+    /// Cancelled with the token of the action, or when the prefetched toolchains are dropped
+    cancellation: CancellationToken,
+}
+
+impl PrefetchWorker {
+    fn start(statuses: Arc<PrefetchStatuses>, cancellation: Option<&CancellationToken>) -> Self {
+        let cancellation = cancellation.map_or_else(CancellationToken::new, |token| token.child());
+        let (sender, receiver) = mpsc::channel::<PrefetchJob>();
+
+        let token = cancellation.clone();
+        let handle = thread::spawn(move || {
+            for job in receiver {
+                if token.is_cancelled() {
+                    break;
                 }
 
-                Ok(())
-            })
+                if statuses.claim(&job.toolchain) {
+                    let installed = install_prefetched(&job, &token);
+                    statuses.finish(&job.toolchain, installed);
+                }
+            }
+        });
+
+        Self {
+            sender,
+            handle,
+            cancellation,
+        }
+    }
+}
+
+/// Install a prefetched toolchain. A toolchain which can't be installed is installed again once
+/// it's checked, so the failure is reported then.
+fn install_prefetched(job: &PrefetchJob, cancellation: &CancellationToken) -> bool {
+    let rustup = RustupCommand::new()
+        .with_stdout()
+        .with_stderr()
+        .with_args(&job.args)
+        .with_optional_cancellation(Some(cancellation))
+        .install();
+
+    match rustup {
+        Ok(rustup) if rustup.exit_status().success() => true,
+        Ok(rustup) => {
+            info!(
+                toolchain = %job.toolchain,
+                stderr = rustup.stderr(),
+                "unable to prefetch toolchain"
+            );
+
+            false
+        }
+        Err(error) => {
+            info!(toolchain = %job.toolchain, %error, "unable to prefetch toolchain");
+
+            false
+        }
+    }
+}
+
+/// The arguments to `rustup install`. A toolchain which runs on another host is installed with the
+/// standard library of the target.
+pub(crate) fn install_args<'t>(
+    toolchain: &'t ToolchainSpec,
+    profile: InstallProfile,
+    components: &[&'static str],
+) -> Vec<String> {
+    let mut args = vec![
+        "--profile".to_string(),
+        profile.to_string(),
+        toolchain.spec().to_string(),
+    ];
+
+    if toolchain.is_cross_compiling() {
+        args.extend(["--target".to_string(), toolchain.target().to_string()]);
+    }
+
+    for component in components {
+        args.extend(["--component".to_string(), component.to_string()]);
+    }
+
+    args
+}
+
+/// Whether the server started with `cargo msrv serve` installed the toolchain, given its response
+/// to a request which required the toolchain to be installed.
+pub(crate) fn installed_by_server(
+    address: &str,
+    toolchain: &ToolchainSpec,
+    response: Response,
+) -> TResult<()> {
+    match response {
+        Response::Install {
+            status: InstallStatus::Installed,
+            ..
+        } => Ok(()),
+        Response::Install {
+            status: InstallStatus::Unavailable,
+            stderr,
+        } => {
+            info!(
+                toolchain = toolchain.spec(),
+                stderr = ?stderr,
+                "toolchain is not available for the target"
+            );
+
+            Err(CargoMSRVError::ToolchainUnavailable(toolchain.to_owned()))
+        }
+        Response::Install {
+            status: InstallStatus::Failed,
+            stderr,
+        } => {
+            error!(
+                toolchain = toolchain.spec(),
+                stderr = ?stderr,
+                "the server failed to install toolchain"
+            );
+
+            Err(CargoMSRVError::RustupInstallFailed(
+                toolchain.spec().to_string(),
+            ))
+        }
+        Response::Error { message } => Err(CargoMSRVError::ServerUnreachable {
+            address: address.to_string(),
+            message,
+        }),
+        other => Err(CargoMSRVError::ServerUnreachable {
+            address: address.to_string(),
+            message: format!("unexpected response to an install request: {:?}", other),
+        }),
+    }
+}
+
+/// Whether rustup failed to install a toolchain, because the toolchain does not exist for the
+/// target, e.g. because the target was only supported by later Rust releases.
+pub(crate) fn is_unavailable_for_target(stderr: &str) -> bool {
+    const UNAVAILABLE: &[&str] = &[
+        "is unavailable for download",
+        "not found in channel",
+        "no release found",
+    ];
+
+    UNAVAILABLE.iter().any(|message| stderr.contains(message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::semver;
+
+    const TOOLCHAIN_LIST: &str = "stable-x86_64-unknown-linux-gnu (default)
+1.56.1-x86_64-unknown-linux-gnu
+1.60.0-x86_64-unknown-linux-gnu (active, default)
+";
+
+    #[yare::parameterized(
+        installed = { semver::Version::new(1, 56, 1), "x86_64-unknown-linux-gnu", true },
+        annotated = { semver::Version::new(1, 60, 0), "x86_64-unknown-linux-gnu", true },
+        other_target = { semver::Version::new(1, 56, 1), "x86_64-pc-windows-msvc", false },
+        not_installed = { semver::Version::new(1, 57, 0), "x86_64-unknown-linux-gnu", false },
+    )]
+    fn contains(version: semver::Version, target: &str, expected: bool) {
+        let installed = InstalledToolchains::parse(TOOLCHAIN_LIST);
+
+        assert_eq!(
+            installed.contains(&ToolchainSpec::new(&version, target)),
+            expected
+        );
+    }
+
+    #[test]
+    fn no_toolchains_installed() {
+        let installed = InstalledToolchains::parse("no installed toolchains\n");
+        let version = semver::Version::new(1, 56, 1);
+
+        assert!(!installed.contains(&ToolchainSpec::new(&version, "x86_64-unknown-linux-gnu")));
+    }
+
+    #[test]
+    fn install_args_of_native_toolchain() {
+        let version = semver::Version::new(1, 56, 1);
+        let toolchain = ToolchainSpec::new(&version, "x86_64-unknown-linux-gnu");
+
+        assert_eq!(
+            install_args(&toolchain, InstallProfile::Minimal, &[]),
+            vec!["--profile", "minimal", "1.56.1-x86_64-unknown-linux-gnu"]
+        );
+    }
+
+    #[test]
+    fn install_args_with_profile_and_components() {
+        let version = semver::Version::new(1, 56, 1);
+        let toolchain = ToolchainSpec::new(&version, "x86_64-unknown-linux-gnu");
+
+        assert_eq!(
+            install_args(&toolchain, InstallProfile::Default, &["clippy"]),
+            vec![
+                "--profile",
+                "default",
+                "1.56.1-x86_64-unknown-linux-gnu",
+                "--component",
+                "clippy"
+            ]
+        );
+    }
+
+    #[test]
+    fn install_args_of_toolchain_on_other_host() {
+        let version = semver::Version::new(1, 56, 1);
+        let toolchain = ToolchainSpec::new(&version, "wasm32-unknown-unknown")
+            .with_host("x86_64-unknown-linux-gnu");
+
+        assert_eq!(
+            install_args(&toolchain, InstallProfile::Minimal, &[]),
+            vec![
+                "--profile",
+                "minimal",
+                "1.56.1-x86_64-unknown-linux-gnu",
+                "--target",
+                "wasm32-unknown-unknown"
+            ]
+        );
+    }
+
+    #[yare::parameterized(
+        component_unavailable = { "error: component 'rustc' for target 'aarch64-apple-darwin' is unavailable for download for channel '1.48.0'", true },
+        target_not_found = { "error: target 'aarch64-apple-darwin' not found in channel", true },
+        no_release = { "error: no release found for '1.0.0-aarch64-apple-darwin'", true },
+        network_error = { "error: could not download file from 'https://static.rust-lang.org/dist/channel-rust-1.48.0.toml.sha256'", false },
+    )]
+    fn unavailable_for_target(stderr: &str, expected: bool) {
+        assert_eq!(is_unavailable_for_target(stderr), expected);
+    }
+    fn prefetched_with_status(
+        toolchain: &ToolchainSpec,
+        status: PrefetchStatus,
+    ) -> PrefetchedToolchains {
+        let prefetched = PrefetchedToolchains::default();
+        prefetched
+            .statuses
+            .lock()
+            .insert(toolchain.spec().to_string(), status);
+
+        prefetched
+    }
+
+    #[test]
+    fn toolchain_which_was_not_prefetched_is_not_waited_for() {
+        let version = semver::Version::new(1, 56, 1);
+        let toolchain = ToolchainSpec::new(&version, "x86_64-unknown-linux-gnu");
+
+        assert!(!PrefetchedToolchains::default().wait(&toolchain));
+    }
+
+    #[test]
+    fn queued_toolchain_is_taken_from_the_queue() {
+        let version = semver::Version::new(1, 56, 1);
+        let toolchain = ToolchainSpec::new(&version, "x86_64-unknown-linux-gnu");
+        let prefetched = prefetched_with_status(&toolchain, PrefetchStatus::Queued);
+
+        assert!(!prefetched.wait(&toolchain));
+
+        // the worker skips it, since it's installed by the caller instead
+        assert!(!prefetched.statuses.claim(toolchain.spec()));
+    }
+
+    #[yare::parameterized(
+        installed = { true },
+        failed = { false },
+    )]
+    fn installing_toolchain_is_waited_for(installed: bool) {
+        let version = semver::Version::new(1, 56, 1);
+        let toolchain = ToolchainSpec::new(&version, "x86_64-unknown-linux-gnu");
+        let prefetched = prefetched_with_status(&toolchain, PrefetchStatus::Installing);
+
+        let statuses = Arc::clone(&prefetched.statuses);
+        let spec = toolchain.spec().to_string();
+        let worker = thread::spawn(move || {
+            thread::sleep(std::time::Duration::from_millis(50));
+            statuses.finish(&spec, installed);
+        });
+
+        assert_eq!(prefetched.wait(&toolchain), installed);
+
+        worker.join().unwrap();
     }
 }
@@ -0,0 +1,68 @@
+//! A snapshot of the Rust stable releases, embedded in the binary at build time.
+//!
+//! The snapshot is used as the final fallback release source, when none of the regular release
+//! sources could be reached. Since it can only be as recent as the cargo-msrv build itself, it
+//! may be stale.
+
+use std::io::Read;
+
+use flate2::read::GzDecoder;
+use rust_releases::{Release, ReleaseIndex};
+
+use crate::error::{CargoMSRVError, TResult};
+use crate::semver;
+
+static SNAPSHOT: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/stable-releases.snapshot.gz"));
+
+/// The date at which the embedded snapshot of Rust releases was taken.
+pub const SNAPSHOT_DATE: &str = env!("CARGO_MSRV_RELEASES_SNAPSHOT_DATE");
+
+/// Build a release index from the embedded snapshot of Rust releases.
+pub fn embedded_index() -> TResult<ReleaseIndex> {
+    let mut contents = String::new();
+
+    GzDecoder::new(SNAPSHOT)
+        .read_to_string(&mut contents)
+        .map_err(|err| CargoMSRVError::EmbeddedReleaseIndex(err.to_string()))?;
+
+    let mut releases = contents
+        .lines()
+        .map(|line| semver::Version::parse(line).map(Release::new_stable))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // Releases in an index must be ordered from most to least recent
+    releases.sort_by(|a, b| b.version().cmp(a.version()));
+
+    Ok(releases.into_iter().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshot_is_not_empty() {
+        let index = embedded_index().unwrap();
+
+        assert!(!index.releases().is_empty());
+    }
+
+    #[test]
+    fn snapshot_is_ordered_from_most_to_least_recent() {
+        let index = embedded_index().unwrap();
+        let releases = index.releases();
+
+        assert!(releases
+            .windows(2)
+            .all(|pair| pair[0].version() > pair[1].version()));
+        assert_eq!(
+            index.least_recent().unwrap().version(),
+            &semver::Version::new(1, 0, 0)
+        );
+    }
+
+    #[test]
+    fn snapshot_date_is_set() {
+        assert!(!SNAPSHOT_DATE.is_empty());
+    }
+}
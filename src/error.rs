@@ -1,17 +1,26 @@
 use std::env;
 use std::ffi::OsString;
+use std::fmt;
 use std::io;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::process::ExitStatus;
 use std::string::FromUtf8Error;
+use std::time::Duration;
 
 use rust_releases::Release;
+use serde_json::{json, Map, Value};
 use storyteller::ReporterError;
 
 use crate::cli::rust_releases_opts::{ParseEditionError, ParseEditionOrVersionError};
+use crate::config::CheckHook;
 use crate::log_level::ParseLogLevelError;
 use crate::manifest::bare_version::{BareVersion, NoVersionMatchesManifestMsrvError};
+use crate::reporter::event::MissingPrerequisite;
 
-use crate::sub_command::{show, verify};
+use crate::sub_command::{
+    discover, history, impact, last_failure, lower, policy, report, show, validate, verify,
+};
+use crate::toolchain::OwnedToolchainSpec;
 
 pub(crate) type TResult<T> = Result<T, CargoMSRVError>;
 
@@ -20,15 +29,54 @@ pub enum CargoMSRVError {
     #[error("Unable to parse minimum rust version: {0}")]
     BareVersionParse(#[from] crate::manifest::bare_version::Error),
 
+    #[error("Unable to find the MSRV of {failed} out of {total} crates")]
+    BatchFailed { failed: usize, total: usize },
+
+    #[error("The action was cancelled")]
+    Cancelled,
+
     #[error(transparent)]
     CargoMetadata(#[from] cargo_metadata::Error),
 
+    #[error(
+        "Unable to find the MSRV within the budget of {max_checks} checks, given with --max-checks"
+    )]
+    CheckBudgetExhausted { max_checks: u64 },
+
+    #[error("The command '{command}' given to {hook} failed with {status}")]
+    CheckHookFailed {
+        hook: CheckHook,
+        command: String,
+        status: ExitStatus,
+    },
+
+    #[error("Unable to check out revision '{revision}' in a temporary git worktree: {message}")]
+    CheckoutRevision { revision: String, message: String },
+
+    #[error("Unable to list the {revisions} of the git repository: {message}")]
+    ListRevisions { revisions: String, message: String },
+
     #[error("The default host triple (target) could not be found.")]
     DefaultHostTripleNotFound,
 
+    #[error("Unable to read the Rust releases index embedded in cargo-msrv: {0}")]
+    EmbeddedReleaseIndex(String),
+
     #[error(transparent)]
     Env(#[from] env::VarError),
 
+    #[error("Unable to fetch the Rust release index from '{url}': {message}")]
+    FetchReleaseIndex { url: String, message: String },
+
+    #[error("Unable to fetch the channel manifest from '{url}': {message}")]
+    FetchChannelManifest { url: String, message: String },
+
+    #[error("Unable to package the crate with `cargo package`, for a remote check: {0}")]
+    PackageCrate(String),
+
+    #[error("Unable to run a remote check with '{url}': {message}")]
+    RemoteCheck { url: String, message: String },
+
     #[error("{0}")]
     GenericMessage(String),
 
@@ -41,21 +89,30 @@ pub enum CargoMSRVError {
     #[error("{0}")]
     InvalidConfig(String),
 
+    #[error("Unable to read the MSRV result file '{}': {reason}", .path.display())]
+    InvalidResultFile { path: PathBuf, reason: String },
+
     #[error(transparent)]
     InvalidRustVersionNumber(#[from] std::num::ParseIntError),
 
     #[error(transparent)]
     InvalidUTF8(#[from] FromUtf8Error),
 
+    #[error("Unable to continue, since required tools are missing or outdated: {}", .missing.iter().map(MissingPrerequisite::tool).collect::<Vec<_>>().join(", "))]
+    MissingPrerequisites { missing: Vec<MissingPrerequisite> },
+
+    #[error("Unable to install {tool} with '{command}'")]
+    PrerequisiteInstall { tool: String, command: String },
+
+    #[error("Not installing {tool} with '{command}' without confirmation, since stdin is not a terminal. Run cargo-msrv with --setup --yes to install it")]
+    PrerequisiteInstallUnconfirmed { tool: String, command: String },
+
     #[error("No crate root found for given crate")]
     NoCrateRootFound,
 
     #[error(transparent)]
     NoToolchainsToTry(#[from] NoToolchainsToTryError),
 
-    #[error("Unable to set MSRV for workspace, try setting it for individual packages instead.")]
-    WorkspaceFound,
-
     #[error(transparent)]
     NoVersionMatchesManifestMSRV(#[from] NoVersionMatchesManifestMsrvError),
 
@@ -74,6 +131,15 @@ pub enum CargoMSRVError {
     #[error("Unable to parse Cargo.toml: {0}")]
     ParseToml(#[from] toml_edit::TomlError),
 
+    #[error("None of the registered release sources ({}) could provide the release index", .release_sources.join(", "))]
+    ReleaseSourcesUnavailable { release_sources: Vec<String> },
+
+    #[error("The rate limit of release source '{release_source}' was exceeded. {}", retry_after_hint(.retry_after))]
+    RateLimited {
+        release_source: String,
+        retry_after: Option<Duration>,
+    },
+
     #[error(transparent)]
     RustReleasesSource(#[from] rust_releases::RustChangelogError),
 
@@ -96,6 +162,9 @@ pub enum CargoMSRVError {
     #[error(transparent)]
     SemverError(#[from] rust_releases::semver::Error),
 
+    #[error("Unable to reach the cargo-msrv server at '{address}': {message}")]
+    ServerUnreachable { address: String, message: String },
+
     #[error(transparent)]
     SetMsrv(#[from] SetMsrvError),
 
@@ -105,15 +174,42 @@ pub enum CargoMSRVError {
     #[error(transparent)]
     SubCommandVerify(#[from] verify::Error),
 
+    #[error(transparent)]
+    SubCommandReport(#[from] report::Error),
+
+    #[error(transparent)]
+    SubCommandPolicy(#[from] policy::Error),
+
     #[error(transparent)]
     SubCommandShow(#[from] show::Error),
 
+    #[error(transparent)]
+    SubCommandDiscover(#[from] discover::Error),
+
+    #[error(transparent)]
+    SubCommandValidate(#[from] validate::Error),
+
+    #[error(transparent)]
+    SubCommandLastFailure(#[from] last_failure::Error),
+
+    #[error(transparent)]
+    SubCommandImpact(#[from] impact::Error),
+
+    #[error(transparent)]
+    SubCommandHistory(#[from] history::Error),
+
+    #[error(transparent)]
+    SubCommandLower(#[from] lower::Error),
+
     #[error(transparent)]
     SystemTime(#[from] std::time::SystemTimeError),
 
     #[error("The given toolchain could not be found. Run `rustup toolchain list` for an overview of installed toolchains.")]
     ToolchainNotInstalled,
 
+    #[error("Toolchain '{}' is not available for download, since Rust {} does not support target '{}'.", .0, .0.version(), .0.target())]
+    ToolchainUnavailable(OwnedToolchainSpec),
+
     #[error("The given target could not be found. Run `rustup target list` for an overview of available toolchains.")]
     UnknownTarget,
 
@@ -132,6 +228,14 @@ Thank you in advance!"#
     )]
     UnableToFindAnyGoodVersion { command: String },
 
+    #[error(
+        r#"Unable to find a Rust version for which the check command fails.
+
+The inverted search accepts toolchains for which `{command}` fails, but it passed for the most
+recent Rust version in the search space."#
+    )]
+    UnableToFindRegression { command: String },
+
     #[error("Unable to parse the CLI arguments. Use `cargo msrv help` for more info.")]
     UnableToParseCliArgs,
 
@@ -142,6 +246,229 @@ Thank you in advance!"#
     UnableToRunCheck,
 }
 
+impl CargoMSRVError {
+    /// Identifies the kind of error, e.g. `fetch_release_index`, so consumers of the json output
+    /// don't have to parse the description of the error.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::BareVersionParse(_) => "bare_version_parse",
+            Self::BatchFailed { .. } => "batch_failed",
+            Self::Cancelled => "cancelled",
+            Self::CargoMetadata(_) => "cargo_metadata",
+            Self::CheckBudgetExhausted { .. } => "check_budget_exhausted",
+            Self::CheckHookFailed { .. } => "check_hook_failed",
+            Self::CheckoutRevision { .. } => "checkout_revision",
+            Self::ListRevisions { .. } => "list_revisions",
+            Self::DefaultHostTripleNotFound => "default_host_triple_not_found",
+            Self::EmbeddedReleaseIndex(_) => "embedded_release_index",
+            Self::Env(_) => "env",
+            Self::FetchReleaseIndex { .. } => "fetch_release_index",
+            Self::FetchChannelManifest { .. } => "fetch_channel_manifest",
+            Self::PackageCrate(_) => "package_crate",
+            Self::RemoteCheck { .. } => "remote_check",
+            Self::GenericMessage(_) => "generic_message",
+            Self::Io { .. } => "io",
+            Self::InvalidConfig(_) => "invalid_config",
+            Self::InvalidResultFile { .. } => "invalid_result_file",
+            Self::InvalidRustVersionNumber(_) => "invalid_rust_version_number",
+            Self::InvalidUTF8(_) => "invalid_utf8",
+            Self::MissingPrerequisites { .. } => "missing_prerequisites",
+            Self::PrerequisiteInstall { .. } => "prerequisite_install",
+            Self::PrerequisiteInstallUnconfirmed { .. } => "prerequisite_install_unconfirmed",
+            Self::NoCrateRootFound => "no_crate_root_found",
+            Self::NoToolchainsToTry(_) => "no_toolchains_to_try",
+            Self::NoVersionMatchesManifestMSRV(_) => "no_version_matches_manifest_msrv",
+            Self::NoMSRVKeyInCargoToml(_) => "no_msrv_key_in_cargo_toml",
+            Self::ParseEdition(_) => "parse_edition",
+            Self::ParseEditionOrVersion(_) => "parse_edition_or_version",
+            Self::ParseLogLevel(_) => "parse_log_level",
+            Self::ParseToml(_) => "parse_toml",
+            Self::ReleaseSourcesUnavailable { .. } => "release_sources_unavailable",
+            Self::RateLimited { .. } => "rate_limited",
+            Self::RustReleasesSource(_) => "rust_releases_source",
+            #[cfg(feature = "rust-releases-dist-source")]
+            Self::RustReleasesRustDistSource(_) => "rust_releases_rust_dist_source",
+            Self::RustReleasesSourceParseError(_) => "rust_releases_source_parse_error",
+            Self::RustReleasesEmptyReleaseSet => "rust_releases_empty_release_set",
+            Self::RustupInstallFailed(_) => "rustup_install_failed",
+            Self::RustupRunWithCommandFailed => "rustup_run_with_command_failed",
+            Self::SemverError(_) => "semver",
+            Self::ServerUnreachable { .. } => "server_unreachable",
+            Self::SetMsrv(_) => "set_msrv",
+            Self::Storyteller => "storyteller",
+            Self::SubCommandVerify(_) => "sub_command_verify",
+            Self::SubCommandReport(_) => "sub_command_report",
+            Self::SubCommandPolicy(_) => "sub_command_policy",
+            Self::SubCommandShow(_) => "sub_command_show",
+            Self::SubCommandDiscover(_) => "sub_command_discover",
+            Self::SubCommandValidate(_) => "sub_command_validate",
+            Self::SubCommandLastFailure(_) => "sub_command_last_failure",
+            Self::SubCommandImpact(_) => "sub_command_impact",
+            Self::SubCommandHistory(_) => "sub_command_history",
+            Self::SubCommandLower(_) => "sub_command_lower",
+            Self::SystemTime(_) => "system_time",
+            Self::ToolchainNotInstalled => "toolchain_not_installed",
+            Self::ToolchainUnavailable(_) => "toolchain_unavailable",
+            Self::UnknownTarget => "unknown_target",
+            Self::UnableToCacheChannelManifest => "unable_to_cache_channel_manifest",
+            Self::UnableToFindAnyGoodVersion { .. } => "unable_to_find_any_good_version",
+            Self::UnableToFindRegression { .. } => "unable_to_find_regression",
+            Self::UnableToParseCliArgs => "unable_to_parse_cli_args",
+            Self::UnableToParseRustVersion => "unable_to_parse_rust_version",
+            Self::UnableToRunCheck => "unable_to_run_check",
+        }
+    }
+
+    /// The values of the error, which differ per kind, e.g. the `url` and `message` of a
+    /// `fetch_release_index` error. Empty for kinds of error which are described completely by
+    /// their description.
+    pub fn details(&self) -> Map<String, Value> {
+        let details = match self {
+            Self::BatchFailed { failed, total } => json!({ "failed": failed, "total": total }),
+            Self::SubCommandDiscover(discover::Error::ProjectsFailed { run, failed, total }) => {
+                json!({ "run": run.to_string(), "failed": failed, "total": total })
+            }
+            Self::SubCommandVerify(verify::Error::ChangedFiles { revision, message }) => {
+                json!({ "revision": revision, "message": message })
+            }
+            Self::SubCommandVerify(verify::Error::MembersFailed { failed, total }) => {
+                json!({ "failed": failed, "total": total })
+            }
+            Self::SubCommandVerify(verify::Error::FetchPublishedMetadata { url, message }) => {
+                json!({ "url": url, "message": message })
+            }
+            Self::SubCommandVerify(verify::Error::PublishedMetadataMismatch {
+                name,
+                version,
+                published,
+                local,
+            }) => json!({
+                "name": name,
+                "version": version,
+                "published": published,
+                "local": local,
+            }),
+            Self::SubCommandValidate(validate::Error::InvalidManifest { path, errors }) => {
+                json!({ "path": path, "errors": errors })
+            }
+            Self::SubCommandLastFailure(last_failure::Error::NoFailedCheck { folder }) => {
+                json!({ "folder": folder })
+            }
+            Self::SubCommandHistory(history::Error::NoRevisions { revisions }) => {
+                json!({ "revisions": revisions })
+            }
+            Self::SubCommandHistory(history::Error::VerificationsFailed { failed, total }) => {
+                json!({ "failed": failed, "total": total })
+            }
+            Self::SubCommandImpact(impact::Error::NoMsrv { manifest })
+            | Self::SubCommandImpact(impact::Error::InheritsFromWorkspace { manifest })
+            | Self::SubCommandLower(lower::Error::NoMsrv { manifest })
+            | Self::SubCommandLower(lower::Error::InheritsFromWorkspace { manifest }) => {
+                json!({ "manifest": manifest })
+            }
+            Self::CheckBudgetExhausted { max_checks } => json!({ "max_checks": max_checks }),
+            Self::CheckHookFailed {
+                hook,
+                command,
+                status,
+            } => json!({
+                "hook": hook.to_string(),
+                "command": command,
+                "exit_code": status.code(),
+            }),
+            Self::EmbeddedReleaseIndex(message)
+            | Self::PackageCrate(message)
+            | Self::GenericMessage(message)
+            | Self::InvalidConfig(message) => json!({ "message": message }),
+            Self::FetchReleaseIndex { url, message }
+            | Self::FetchChannelManifest { url, message }
+            | Self::RemoteCheck { url, message } => {
+                json!({ "url": url, "message": message })
+            }
+            Self::ServerUnreachable { address, message } => {
+                json!({ "address": address, "message": message })
+            }
+            Self::CheckoutRevision { revision, message } => {
+                json!({ "revision": revision, "message": message })
+            }
+            Self::ListRevisions { revisions, message } => {
+                json!({ "revisions": revisions, "message": message })
+            }
+            Self::Io { error, source } => json!({
+                "error": error.to_string(),
+                "source": source.to_string(),
+                "path": source.path(),
+            }),
+            Self::InvalidResultFile { path, reason } => json!({ "path": path, "reason": reason }),
+            Self::MissingPrerequisites { missing } => json!({
+                "tools": missing.iter().map(MissingPrerequisite::tool).collect::<Vec<_>>(),
+                "os": env::consts::OS,
+                "missing": missing,
+            }),
+            Self::PrerequisiteInstall { tool, command }
+            | Self::PrerequisiteInstallUnconfirmed { tool, command } => {
+                json!({ "tool": tool, "command": command })
+            }
+            Self::NoToolchainsToTry(error) => json!({
+                "min": error.min,
+                "max": error.max,
+                "search_space": error
+                    .search_space
+                    .iter()
+                    .map(|release| release.version())
+                    .collect::<Vec<_>>(),
+            }),
+            Self::NoMSRVKeyInCargoToml(path)
+            | Self::SubCommandShow(show::Error::NoMSRVInCargoManifest(path)) => {
+                json!({ "path": path })
+            }
+            Self::ReleaseSourcesUnavailable { release_sources } => {
+                json!({ "release_sources": release_sources })
+            }
+            Self::RateLimited {
+                release_source,
+                retry_after,
+            } => json!({
+                "release_source": release_source,
+                "retry_after_secs": retry_after.map(|duration| duration.as_secs()),
+            }),
+            Self::RustReleasesSourceParseError(source) => json!({ "source": source }),
+            Self::RustupInstallFailed(toolchain) => json!({ "toolchain": toolchain }),
+            Self::SetMsrv(SetMsrvError::WorkspaceRequiresRustVersion(msrv)) => {
+                json!({ "msrv": msrv })
+            }
+            Self::SetMsrv(SetMsrvError::InconsistentWorkspace { msrv, conflicts }) => json!({
+                "msrv": msrv,
+                "conflicts": conflicts
+                    .iter()
+                    .map(|conflict| json!({ "manifest": conflict.manifest, "msrv": conflict.msrv }))
+                    .collect::<Vec<_>>(),
+            }),
+            Self::ToolchainUnavailable(toolchain) => json!({ "toolchain": toolchain }),
+            Self::UnableToFindAnyGoodVersion { command }
+            | Self::UnableToFindRegression { command } => json!({ "command": command }),
+            _ => return Map::new(),
+        };
+
+        match details {
+            Value::Object(details) => details,
+            _ => Map::new(),
+        }
+    }
+}
+
+fn retry_after_hint(retry_after: &Option<Duration>) -> String {
+    let retry = match retry_after {
+        Some(duration) => format!("Retry after {} seconds", duration.as_secs()),
+        None => "Retry later".to_string(),
+    };
+
+    format!(
+        "{}, or supply a GitHub token with `--github-token` (or the GITHUB_TOKEN environment variable) for a higher rate limit.",
+        retry
+    )
+}
+
 impl From<String> for CargoMSRVError {
     fn from(s: String) -> Self {
         Self::GenericMessage(s)
@@ -168,6 +495,24 @@ pub enum IoErrorSource {
     #[error("Unable to rename file '{0}'")]
     RenameFile(PathBuf),
 
+    #[error("Unable to create directory '{0}'")]
+    CreateDir(PathBuf),
+
+    #[error("Unable to read directory '{0}'")]
+    ReadDir(PathBuf),
+
+    #[error("Unable to copy file '{0}'")]
+    CopyFile(PathBuf),
+
+    #[error("Unable to read from stdin")]
+    ReadStdin,
+
+    #[error("Unable to write to stdout")]
+    WriteStdout,
+
+    #[error("Unable to listen on '{0}'")]
+    Listen(String),
+
     #[error("Unable to spawn process '{0:?}'")]
     SpawnProcess(OsString),
 
@@ -175,12 +520,60 @@ pub enum IoErrorSource {
     WaitForProcessAndCollectOutput(OsString),
 }
 
+impl IoErrorSource {
+    /// The file or directory on which the operation failed, if any.
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            Self::OpenFile(path)
+            | Self::ReadFile(path)
+            | Self::WriteFile(path)
+            | Self::RemoveFile(path)
+            | Self::RenameFile(path)
+            | Self::CreateDir(path)
+            | Self::ReadDir(path)
+            | Self::CopyFile(path) => Some(path),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum SetMsrvError {
     #[error(
         "Unable to set the MSRV in the 'package.metadata' table: 'package.metadata' is not a table"
     )]
     NotATable,
+
+    #[error("Unable to set the MSRV of the workspace to {0}: the 'workspace.package.rust-version' field requires Rust 1.56 or later")]
+    WorkspaceRequiresRustVersion(BareVersion),
+
+    #[error(
+        "Unable to set the MSRV of the workspace to {msrv}, since {} workspace member(s) declare a less recent MSRV:{}",
+        conflicts.len(),
+        conflicts.iter().map(|conflict| format!("\n  - {}", conflict)).collect::<String>()
+    )]
+    InconsistentWorkspace {
+        msrv: BareVersion,
+        conflicts: Vec<WorkspaceMemberConflict>,
+    },
+}
+
+/// A workspace member which declares an MSRV which is less recent than the MSRV of the workspace.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WorkspaceMemberConflict {
+    pub manifest: PathBuf,
+    pub msrv: BareVersion,
+}
+
+impl fmt::Display for WorkspaceMemberConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "'{}' declares Rust {}",
+            self.manifest.display(),
+            self.msrv
+        )
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -1,4 +1,5 @@
 use crate::manifest::bare_version;
+use crate::reporter::event::Exclusion;
 use crate::{semver, Config};
 use rust_releases::linear::LatestStableReleases;
 use rust_releases::Release;
@@ -10,7 +11,7 @@ pub fn filter_releases(config: &Config, releases: &[Release]) -> Vec<Release> {
         releases.iter().cloned().latest_stable_releases().collect()
     };
 
-    // Pre-filter the [min-version:max-version] range
+    // Pre-filter the [min-version:max-version] range, less the excluded versions
     releases
         .into_iter()
         .filter(|release| {
@@ -20,9 +21,51 @@ pub fn filter_releases(config: &Config, releases: &[Release]) -> Vec<Release> {
                 config.maximum_version(),
             )
         })
+        .filter(|release| !is_excluded(release.version(), config.excluded_versions()))
         .collect::<Vec<_>>()
 }
 
+/// Determine for each release whether it is included in the search space, and if not, why it is
+/// excluded. Releases are included under the same conditions as by [`filter_releases`].
+pub fn classify_releases(
+    config: &Config,
+    releases: &[Release],
+) -> Vec<(Release, Option<Exclusion>)> {
+    let latest_patch_releases = releases
+        .iter()
+        .cloned()
+        .latest_stable_releases()
+        .map(|release| release.version().clone())
+        .collect::<Vec<_>>();
+
+    releases
+        .iter()
+        .map(|release| {
+            let version = release.version();
+
+            let exclusion = match (config.minimum_version(), config.maximum_version()) {
+                (Some(min), _) if !min.is_at_least(version) => Some(Exclusion::BelowMinimumVersion),
+                (_, Some(max)) if !max.is_at_most(version) => Some(Exclusion::AboveMaximumVersion),
+                _ if is_excluded(version, config.excluded_versions()) => {
+                    Some(Exclusion::ExcludedVersion)
+                }
+                _ if !config.include_all_patch_releases()
+                    && !latest_patch_releases.contains(version) =>
+                {
+                    Some(Exclusion::SupersededByPatchRelease)
+                }
+                _ => None,
+            };
+
+            (release.clone(), exclusion)
+        })
+        .collect()
+}
+
+fn is_excluded(current: &semver::Version, excluded: &[bare_version::BareVersion]) -> bool {
+    excluded.iter().any(|version| version.matches(current))
+}
+
 fn include_version(
     current: &semver::Version,
     min_version: Option<&bare_version::BareVersion>,
@@ -38,6 +81,7 @@ fn include_version(
 
 #[cfg(test)]
 mod tests {
+    use crate::config::{Action, ConfigBuilder};
     use crate::manifest::bare_version::BareVersion;
     use parameterized::{ide, parameterized};
     use rust_releases::semver::Version;
@@ -120,4 +164,93 @@ mod tests {
             max_version.as_ref()
         ));
     }
+
+    #[test]
+    fn classify() {
+        let releases = vec![
+            Release::new_stable(Version::new(1, 56, 1)),
+            Release::new_stable(Version::new(1, 56, 0)),
+            Release::new_stable(Version::new(1, 55, 0)),
+            Release::new_stable(Version::new(1, 54, 0)),
+        ];
+
+        let config = ConfigBuilder::new(Action::Releases, "")
+            .minimum_version(BareVersion::TwoComponents(1, 55))
+            .maximum_version(BareVersion::TwoComponents(1, 55))
+            .build();
+
+        let exclusions = classify_releases(&config, &releases)
+            .into_iter()
+            .map(|(_, exclusion)| exclusion)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            exclusions,
+            vec![
+                Some(Exclusion::AboveMaximumVersion),
+                Some(Exclusion::AboveMaximumVersion),
+                None,
+                Some(Exclusion::BelowMinimumVersion),
+            ]
+        );
+    }
+
+    #[test]
+    fn excluded_versions() {
+        let releases = vec![
+            Release::new_stable(Version::new(1, 58, 1)),
+            Release::new_stable(Version::new(1, 57, 0)),
+            Release::new_stable(Version::new(1, 56, 1)),
+            Release::new_stable(Version::new(1, 56, 0)),
+        ];
+
+        let config = ConfigBuilder::new(Action::Releases, "")
+            .include_all_patch_releases(true)
+            .excluded_versions(vec![
+                BareVersion::ThreeComponents(1, 57, 0),
+                BareVersion::TwoComponents(1, 56),
+            ])
+            .build();
+
+        let exclusions = classify_releases(&config, &releases)
+            .into_iter()
+            .map(|(_, exclusion)| exclusion)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            exclusions,
+            vec![
+                None,
+                Some(Exclusion::ExcludedVersion),
+                Some(Exclusion::ExcludedVersion),
+                Some(Exclusion::ExcludedVersion),
+            ]
+        );
+
+        let included = filter_releases(&config, &releases)
+            .iter()
+            .map(|release| release.version().clone())
+            .collect::<Vec<_>>();
+        assert_eq!(included, vec![Version::new(1, 58, 1)]);
+    }
+
+    #[test]
+    fn classify_superseded_patch_releases() {
+        let releases = vec![
+            Release::new_stable(Version::new(1, 56, 1)),
+            Release::new_stable(Version::new(1, 56, 0)),
+        ];
+
+        let config = ConfigBuilder::new(Action::Releases, "").build();
+        let classified = classify_releases(&config, &releases);
+
+        assert_eq!(classified[0].1, None);
+        assert_eq!(classified[1].1, Some(Exclusion::SupersededByPatchRelease));
+
+        let included = filter_releases(&config, &releases)
+            .iter()
+            .map(|release| release.version().clone())
+            .collect::<Vec<_>>();
+        assert_eq!(included, vec![Version::new(1, 56, 1)]);
+    }
 }
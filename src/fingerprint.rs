@@ -0,0 +1,275 @@
+//! A fingerprint of the environment in which an MSRV was determined.
+//!
+//! Results obtained in different environments, e.g. on different machines, with a different
+//! check command or feature selection, or after the lockfile changed, may differ. The fingerprint
+//! is included in the reported [`MsrvResult`], so results can be compared, and invalidated when
+//! the environment changed.
+//!
+//! [`MsrvResult`]: crate::reporter::event::MsrvResult
+
+use std::collections::BTreeSet;
+
+use toml_edit::{Document, Item};
+
+use crate::command::RustupCommand;
+use crate::config::{CheckWith, FeatureSet};
+use crate::default_target::default_target;
+use crate::lockfile::CARGO_LOCK;
+use crate::Config;
+
+#[derive(
+    Clone, Debug, Eq, PartialEq, serde::Serialize, serde::Deserialize, schemars::JsonSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub struct Fingerprint {
+    /// The default host triple of rustup
+    host_triple: Option<String>,
+    /// The version reported by `rustup --version`
+    rustup_version: Option<String>,
+    cargo_msrv_version: String,
+    check_command: String,
+    /// A hash of the features of the crate which are enabled by the check, resolved from the
+    /// `[features]` table of its Cargo manifest
+    features_hash: Option<String>,
+    /// A hash of the Cargo lockfile of the crate, if it has one
+    lockfile_hash: Option<String>,
+}
+
+const UNDEFINED: &str = "undefined";
+
+impl Fingerprint {
+    /// Collect the fingerprint of the current environment.
+    ///
+    /// Parts of the environment which can't be determined, e.g. because rustup can't be run,
+    /// are left out, instead of failing the collection as a whole.
+    pub fn collect(config: &Config) -> Self {
+        let lockfile_hash = config
+            .context()
+            .crate_root_path()
+            .ok()
+            .and_then(|root| std::fs::read(root.join(CARGO_LOCK)).ok())
+            .map(|contents| hash(&contents));
+
+        Self {
+            host_triple: default_target().ok(),
+            rustup_version: rustup_version(),
+            cargo_msrv_version: option_env!("CARGO_PKG_VERSION")
+                .unwrap_or(UNDEFINED)
+                .to_string(),
+            check_command: config.check_command_string(),
+            features_hash: features_hash(config),
+            lockfile_hash,
+        }
+    }
+
+    /// The names of the properties which differ between this and the other fingerprint.
+    ///
+    /// Returns an empty list if both fingerprints describe the same environment.
+    pub fn diff(&self, other: &Fingerprint) -> Vec<&'static str> {
+        let properties = [
+            ("host_triple", self.host_triple == other.host_triple),
+            (
+                "rustup_version",
+                self.rustup_version == other.rustup_version,
+            ),
+            (
+                "cargo_msrv_version",
+                self.cargo_msrv_version == other.cargo_msrv_version,
+            ),
+            ("check_command", self.check_command == other.check_command),
+            ("features_hash", self.features_hash == other.features_hash),
+            ("lockfile_hash", self.lockfile_hash == other.lockfile_hash),
+        ];
+
+        properties
+            .iter()
+            .filter(|(_, is_equal)| !is_equal)
+            .map(|(name, _)| *name)
+            .collect()
+    }
+
+    pub fn check_command(&self) -> &str {
+        &self.check_command
+    }
+
+    pub fn lockfile_hash(&self) -> Option<&str> {
+        self.lockfile_hash.as_deref()
+    }
+}
+
+/// A hash of the features of the crate which are enabled by the check, or `None` if its manifest
+/// can't be read.
+fn features_hash(config: &Config) -> Option<String> {
+    // a file compiled with rustc has no features
+    if *config.check_with() != CheckWith::Cargo {
+        return None;
+    }
+
+    let manifest = config.context().manifest_path().ok()?;
+    let manifest = std::fs::read_to_string(manifest)
+        .ok()?
+        .parse::<Document>()
+        .ok()?;
+    let features = resolved_features(config.features(), &manifest);
+
+    Some(hash(
+        features
+            .into_iter()
+            .collect::<Vec<_>>()
+            .join(",")
+            .as_bytes(),
+    ))
+}
+
+/// The features of the crate which are enabled by the given selection, according to the
+/// `[features]` table of its manifest: the selected features, and the features, optional
+/// dependencies, and features of dependencies, which they enable in turn.
+fn resolved_features(selection: &FeatureSet, manifest: &Document) -> BTreeSet<String> {
+    let table = manifest.get("features").and_then(Item::as_table_like);
+
+    let enables = |feature: &str| -> Vec<String> {
+        table
+            .and_then(|table| table.get(feature))
+            .and_then(Item::as_array)
+            .map(|enabled| {
+                enabled
+                    .iter()
+                    .filter_map(|value| value.as_str())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+
+    let mut selected = match selection {
+        FeatureSet::NoDefaultFeatures => Vec::new(),
+        FeatureSet::AllFeatures => table
+            .map(|table| table.iter().map(|(name, _)| name.to_string()).collect())
+            .unwrap_or_default(),
+        FeatureSet::Default => vec!["default".to_string()],
+        FeatureSet::Features(features) => features
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|feature| !feature.is_empty())
+            .map(str::to_string)
+            .chain(std::iter::once("default".to_string()))
+            .collect(),
+    };
+
+    let mut resolved = BTreeSet::new();
+
+    while let Some(feature) = selected.pop() {
+        if resolved.insert(feature.clone()) {
+            selected.extend(enables(&feature));
+        }
+    }
+
+    resolved
+}
+
+fn rustup_version() -> Option<String> {
+    let output = RustupCommand::new().with_stdout().version().ok()?;
+
+    if !output.exit_status().success() {
+        return None;
+    }
+
+    output
+        .stdout()
+        .lines()
+        .next()
+        .map(|line| line.trim().to_string())
+}
+
+/// A 64-bit FNV-1a hash, which, contrary to the hashers in the standard library, is stable
+/// across Rust versions and platforms, so hashes can be compared between machines.
+pub(crate) fn hash(bytes: &[u8]) -> String {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    let hash = bytes.iter().fold(OFFSET_BASIS, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+    });
+
+    format!("{:016x}", hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fingerprint() -> Fingerprint {
+        Fingerprint {
+            host_triple: Some("x86_64-unknown-linux-gnu".to_string()),
+            rustup_version: Some("rustup 1.25.1 (bb60b1e89 2022-07-12)".to_string()),
+            cargo_msrv_version: "0.15.1".to_string(),
+            check_command: "cargo check".to_string(),
+            features_hash: Some(hash(b"default,std")),
+            lockfile_hash: Some(hash(b"version = 3")),
+        }
+    }
+
+    #[yare::parameterized(
+        empty = { "", "cbf29ce484222325" },
+        a = { "a", "af63dc4c8601ec8c" },
+        foobar = { "foobar", "85944171f73967e8" },
+    )]
+    fn fnv1a_hash(input: &str, expected: &str) {
+        assert_eq!(hash(input.as_bytes()), expected);
+    }
+
+    #[test]
+    fn diff_of_equal_fingerprints_is_empty() {
+        assert!(fingerprint().diff(&fingerprint()).is_empty());
+    }
+
+    #[test]
+    fn diff_lists_changed_properties() {
+        let other = Fingerprint {
+            check_command: "cargo check --all-targets".to_string(),
+            lockfile_hash: None,
+            ..fingerprint()
+        };
+
+        assert_eq!(
+            fingerprint().diff(&other),
+            vec!["check_command", "lockfile_hash"]
+        );
+    }
+
+    const MANIFEST: &str = r#"[package]
+name = "a"
+
+[features]
+default = ["std"]
+std = ["serde/std"]
+derive = ["dep:serde_derive"]
+"#;
+
+    #[yare::parameterized(
+        default = { FeatureSet::Default, &["default", "serde/std", "std"] },
+        no_default_features = { FeatureSet::NoDefaultFeatures, &[] },
+        all_features = { FeatureSet::AllFeatures, &["default", "dep:serde_derive", "derive", "serde/std", "std"] },
+        features = { FeatureSet::Features("derive".to_string()), &["default", "dep:serde_derive", "derive", "serde/std", "std"] },
+    )]
+    fn resolve_features(selection: FeatureSet, expected: &[&str]) {
+        let manifest = MANIFEST.parse::<Document>().unwrap();
+
+        assert_eq!(
+            resolved_features(&selection, &manifest)
+                .iter()
+                .map(String::as_str)
+                .collect::<Vec<_>>(),
+            expected
+        );
+    }
+
+    #[test]
+    fn serialized_round_trip() {
+        let json = serde_json::to_string(&fingerprint()).unwrap();
+
+        assert_eq!(
+            serde_json::from_str::<Fingerprint>(&json).unwrap(),
+            fingerprint()
+        );
+    }
+}
@@ -15,46 +15,104 @@ extern crate core;
 #[macro_use]
 extern crate tracing;
 
+pub use crate::cancellation::CancellationToken;
 pub use crate::outcome::Outcome;
-pub use crate::sub_command::{Find, List, Set, Show, SubCommand, Verify};
+pub use crate::sub_command::{
+    Configuration, Discover, Edition, Find, History, Impact, LastFailure, List, Lower, Matrix,
+    Policy, Releases, Report, Schema, Serve, Set, Show, SubCommand, Validate, Verify,
+};
 
-#[cfg(feature = "rust-releases-dist-source")]
-use rust_releases::RustDist;
-use rust_releases::{semver, Channel, FetchResources, ReleaseIndex, RustChangelog, Source};
+use rust_releases::{semver, ReleaseIndex};
 
-use crate::check::RustupToolchainCheck;
-use crate::config::{Action, Config, ReleaseSource};
+use crate::check::{Check, RemoteCheck, RustupToolchainCheck, ServerCheck};
+use crate::config::set::SetCmdConfig;
+use crate::config::{Action, Config, ConfigBuilder, ReleaseSource, SubCommandConfig};
+use crate::default_target::default_target;
 use crate::error::{CargoMSRVError, TResult};
-use crate::reporter::event::{ActionMessage, FetchIndex, Meta};
+use crate::fingerprint::Fingerprint;
+use crate::prerequisites::check_prerequisites;
+use crate::release_index_source::{cache_dir, IndexCache, ReleaseIndexSource, ReleaseIndexSources};
+use crate::reporter::event::{
+    ActionMessage, EmbeddedIndexFallback, FetchIndex, FetchIndexProgress, Meta, RevisionCheckout,
+};
+use crate::reporter::statistics::StatisticsReporter;
 use crate::reporter::{Event, Reporter};
+use crate::server::ServerSource;
+use crate::sub_command::discover::run_discovered;
+use crate::sub_command::find::find_batch;
+use crate::sub_command::verify::verify_changed_members;
 
+pub mod cancellation;
 pub mod check;
 pub mod cli;
 pub mod config;
 pub mod error;
 pub mod exit_code;
+pub mod log_file;
+pub mod release_index_source;
 pub mod reporter;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
 pub mod toolchain;
+pub mod types;
 
+pub(crate) mod channel_manifest;
+pub(crate) mod check_log;
+pub(crate) mod clippy_config;
 pub(crate) mod combinators;
 pub(crate) mod command;
+pub(crate) mod crate_copy;
+pub(crate) mod crates_index;
 pub(crate) mod ctx;
 pub(crate) mod default_target;
 pub(crate) mod dependency_graph;
 pub(crate) mod download;
+pub(crate) mod embedded_index;
 pub(crate) mod filter_releases;
+pub(crate) mod fingerprint;
 pub(crate) mod formatting;
 pub(crate) mod lockfile;
 pub(crate) mod log_level;
+pub(crate) mod long_path;
 pub(crate) mod manifest;
 pub(crate) mod msrv;
 pub(crate) mod outcome;
+pub(crate) mod prerequisites;
+pub(crate) mod release_schedule;
+pub(crate) mod required_features;
 pub(crate) mod search_method;
+pub(crate) mod server;
+pub(crate) mod stubbed_copy;
 pub(crate) mod sub_command;
 pub(crate) mod typed_bool;
+pub(crate) mod worktree;
 pub(crate) mod writer;
 
 pub fn run_app(config: &Config, reporter: &impl Reporter) -> TResult<()> {
+    run_app_with_release_sources(config, &ReleaseIndexSources::default(), reporter)
+}
+
+/// Like [`run_app`], but obtains the Rust release index from the given release sources,
+/// instead of only the built-in ones.
+pub fn run_app_with_release_sources(
+    config: &Config,
+    sources: &ReleaseIndexSources,
+    reporter: &impl Reporter,
+) -> TResult<()> {
+    let statistics = StatisticsReporter::new(reporter);
+    let result = run_action(config, sources, &statistics);
+
+    // The summary is also reported when the run failed, for what it did until then
+    reporter.report_event(statistics.summary())?;
+
+    result
+}
+
+fn run_action(
+    config: &Config,
+    sources: &ReleaseIndexSources,
+    reporter: &impl Reporter,
+) -> TResult<()> {
     reporter.report_event(Meta::default())?;
 
     let action = config.action();
@@ -66,48 +124,226 @@ pub fn run_app(config: &Config, reporter: &impl Reporter) -> TResult<()> {
 
     reporter.report_event(ActionMessage::new(action))?;
 
+    if let Some(worktree) = config.worktree() {
+        reporter.report_event(RevisionCheckout::new(
+            worktree.revision(),
+            worktree.commit(),
+            worktree.path(),
+        ))?;
+    }
+
+    let installed = check_prerequisites(config, reporter)?;
+
+    // Without rustup, the default target could not be determined while the configuration was
+    // resolved, so it is determined once rustup has been installed with `--setup`
+    let with_default_target;
+    let config = if installed && config.target().is_empty() {
+        with_default_target = ConfigBuilder::from_config(config)
+            .target(&default_target()?)
+            .build();
+        &with_default_target
+    } else {
+        config
+    };
+
     match action {
         Action::Find => {
-            let index = fetch_index(config, reporter)?;
-            let runner = RustupToolchainCheck::new(reporter);
-            Find::new(&index, runner).run(config, reporter)?;
+            let index = fetch_index(config, sources, reporter)?;
+
+            find(config, reporter, &index, runner(config, reporter))?;
+        }
+        Action::Verify if config.sub_command_config().verify().changed_since.is_some() => {
+            let index = fetch_index(config, sources, reporter)?;
+
+            verify_changed_members(config, reporter, &index, || runner(config, reporter))?;
         }
         Action::Verify => {
-            let index = fetch_index(config, reporter)?;
-            let runner = RustupToolchainCheck::new(reporter);
-            Verify::new(&index, runner).run(config, reporter)?;
+            let index = fetch_index(config, sources, reporter)?;
+
+            Verify::new(&index, runner(config, reporter)).run(config, reporter)?;
         }
         Action::List => {
             List::default().run(config, reporter)?;
         }
+        Action::Set if config.prefer_newest_patch() => {
+            let index = fetch_index(config, sources, reporter)?;
+            let msrv = &config.sub_command_config().set().msrv;
+
+            match msrv::newer_patch_release(&index, &msrv.to_semver_version()) {
+                Some(newest) => {
+                    let config = ConfigBuilder::from_config(config)
+                        .sub_command_config(SubCommandConfig::SetConfig(SetCmdConfig {
+                            msrv: newest.into(),
+                        }))
+                        .build();
+
+                    Set::default().run(&config, reporter)?;
+                }
+                None => Set::default().run(config, reporter)?,
+            }
+        }
         Action::Set => {
             Set::default().run(config, reporter)?;
         }
         Action::Show => {
             Show::default().run(config, reporter)?;
         }
+        Action::Report => {
+            Report::default().run(config, reporter)?;
+        }
+        Action::Config => {
+            Configuration::default().run(config, reporter)?;
+        }
+        Action::Edition => {
+            Edition::default().run(config, reporter)?;
+        }
+        Action::Releases => {
+            let index = fetch_index(config, sources, reporter)?;
+            Releases::new(&index).run(config, reporter)?;
+        }
+        Action::Matrix => {
+            let index = fetch_index(config, sources, reporter)?;
+
+            Matrix::new(&index, runner(config, reporter)).run(config, reporter)?;
+        }
+        Action::Policy => {
+            let index = fetch_index(config, sources, reporter)?;
+            Policy::new(&index).run(config, reporter)?;
+        }
+        Action::Discover => {
+            let projects = Discover::default().run(config, reporter)?;
+
+            if let Some(run) = config.sub_command_config().discover().run {
+                let index = fetch_index(config, sources, reporter)?;
+
+                run_discovered(config, reporter, &index, &projects, run, || {
+                    runner(config, reporter)
+                })?;
+            }
+        }
+        Action::Validate => {
+            let index = fetch_index(config, sources, reporter)?;
+            Validate::new(&index).run(config, reporter)?;
+        }
+        Action::LastFailure => {
+            LastFailure::default().run(config, reporter)?;
+        }
+        Action::Impact => {
+            let index = fetch_index(config, sources, reporter)?;
+
+            Impact::new(&index, runner(config, reporter)).run(config, reporter)?;
+        }
+        Action::Lower => {
+            let index = fetch_index(config, sources, reporter)?;
+
+            Lower::new(&index, runner(config, reporter)).run(config, reporter)?;
+        }
+        Action::Schema => {
+            Schema::default().run(config, reporter)?;
+        }
+        Action::Serve => {
+            let index = fetch_index(config, sources, reporter)?;
+            Serve::new(&index).run(config, reporter)?;
+        }
+        Action::History => {
+            // The index is only needed to verify the MSRV of each revision
+            let index = if config.sub_command_config().history().verify {
+                Some(fetch_index(config, sources, reporter)?)
+            } else {
+                None
+            };
+
+            History::new(index.as_ref(), runner(config, reporter)).run(config, reporter)?;
+        }
     }
 
     Ok(())
 }
 
-fn fetch_index(config: &Config, reporter: &impl Reporter) -> TResult<ReleaseIndex> {
-    reporter.run_scoped_event(FetchIndex::new(config.release_source()), || {
-        let source = config.release_source();
+/// The runner with which the toolchains are checked: by the remote execution service given with
+/// `--remote-check`, by the server given with `--server`, or locally, with rustup.
+fn runner<'r, R: Reporter>(config: &'r Config, reporter: &'r R) -> Box<dyn Check + 'r> {
+    match (config.remote_check(), config.server()) {
+        (Some(url), _) => Box::new(RemoteCheck::new(reporter, url)),
+        (None, Some(address)) => Box::new(ServerCheck::new(reporter, address)),
+        (None, None) => Box::new(RustupToolchainCheck::new(reporter)),
+    }
+}
 
-        info!(
-            source = Into::<&'static str>::into(source),
-            "fetching index"
-        );
+/// Find the MSRV of the crate, or of each crate read from stdin in batch mode, running the checks
+/// with the given runner.
+fn find<C: Check>(
+    config: &Config,
+    reporter: &impl Reporter,
+    index: &ReleaseIndex,
+    runner: C,
+) -> TResult<()> {
+    if let Some(options) = config.batch() {
+        let stdin = std::io::stdin();
+        let stdout = std::io::stdout();
+        find_batch(
+            config,
+            options,
+            reporter,
+            index,
+            &runner,
+            stdin.lock(),
+            stdout.lock(),
+        )?;
+    } else {
+        let fingerprint = Fingerprint::collect(config);
+        Find::new(index, runner)
+            .with_fingerprint(fingerprint)
+            .run(config, reporter)?;
+    }
 
-        let index = match config.release_source() {
-            ReleaseSource::RustChangelog => {
-                RustChangelog::fetch_channel(Channel::Stable)?.build_index()?
+    Ok(())
+}
+
+fn fetch_index(
+    config: &Config,
+    sources: &ReleaseIndexSources,
+    reporter: &impl Reporter,
+) -> TResult<ReleaseIndex> {
+    reporter.run_scoped_event(FetchIndex::new(config.release_source()), || {
+        let cache =
+            cache_dir(config.scratch_dir()).map(|dir| IndexCache::new(dir, config.max_index_age()));
+
+        let progress = |it: FetchIndexProgress| {
+            if let Err(error) = reporter.report_event(it) {
+                warn!(%error, "unable to report the progress of the index fetch");
             }
-            #[cfg(feature = "rust-releases-dist-source")]
-            ReleaseSource::RustDist => RustDist::fetch_channel(Channel::Stable)?.build_index()?,
         };
 
+        if let Some(address) = config.server() {
+            match ServerSource::new(address).fetch() {
+                Ok(index) => return Ok(index),
+                Err(error) => warn!(%error, "unable to fetch the release index from the server"),
+            }
+        }
+
+        if let Some(index) = sources.fetch_first_available(
+            config.release_source(),
+            config.github_token(),
+            cache.as_ref(),
+            &progress,
+            config.cancellation_token(),
+        )? {
+            return Ok(index);
+        }
+
+        let index = embedded_index::embedded_index()?;
+
+        warn!(
+            snapshot_date = embedded_index::SNAPSHOT_DATE,
+            "using embedded index"
+        );
+
+        reporter.report_event(EmbeddedIndexFallback::new(
+            embedded_index::SNAPSHOT_DATE,
+            index.most_recent().map(|release| release.version().clone()),
+        ))?;
+
         Ok(index)
     })
 }
@@ -1,10 +1,17 @@
+use std::fmt;
 use std::marker::PhantomData;
 use std::path::{Path, PathBuf};
 
+use toml_edit::{Document, Item};
+
 use crate::error::{CargoMSRVError, IoErrorSource, TResult};
+use crate::fingerprint::hash;
+use crate::semver;
 
 pub struct LockfileHandler<S: LockfileState> {
     state: PathBuf,
+    // Where the lockfile is moved to, while it is ignored
+    replacement: PathBuf,
     marker: PhantomData<S>,
 }
 
@@ -22,23 +29,48 @@ const CARGO_LOCK_REPLACEMENT: &str = "Cargo.lock-ignored-for-cargo-msrv";
 
 impl LockfileHandler<Start> {
     pub fn new<P: AsRef<Path>>(lock_file: P) -> Self {
+        let state = lock_file.as_ref().to_path_buf();
+        let replacement = state.parent().unwrap().join(CARGO_LOCK_REPLACEMENT);
+
         Self {
-            state: lock_file.as_ref().to_path_buf(),
+            state,
+            replacement,
             marker: PhantomData,
         }
     }
 
+    /// Move the lockfile into the given directory, instead of next to itself. The directory may be
+    /// shared by invocations for different crates, so the name of the moved lockfile is keyed by a
+    /// hash of the path of the lockfile.
+    pub fn with_replacement_dir<P: AsRef<Path>>(mut self, dir: P) -> Self {
+        // a relative path would give a different key for the same lockfile, from another directory
+        let path = self
+            .state
+            .canonicalize()
+            .unwrap_or_else(|_| self.state.clone());
+        let key = hash(path.to_string_lossy().as_bytes());
+
+        self.replacement = dir
+            .as_ref()
+            .join(format!("{}-{}", CARGO_LOCK_REPLACEMENT, key));
+        self
+    }
+
     pub fn move_lockfile(self) -> TResult<LockfileHandler<Moved>> {
-        let folder = self.state.parent().unwrap();
-        std::fs::rename(self.state.as_path(), folder.join(CARGO_LOCK_REPLACEMENT)).map_err(
-            |error| CargoMSRVError::Io {
-                error,
-                source: IoErrorSource::RenameFile(self.state.clone()),
-            },
-        )?;
+        let folder = self.replacement.parent().unwrap();
+        std::fs::create_dir_all(folder).map_err(|error| CargoMSRVError::Io {
+            error,
+            source: IoErrorSource::CreateDir(folder.to_path_buf()),
+        })?;
+
+        move_file(&self.state, &self.replacement).map_err(|error| CargoMSRVError::Io {
+            error,
+            source: IoErrorSource::RenameFile(self.state.clone()),
+        })?;
 
         Ok(LockfileHandler {
             state: self.state,
+            replacement: self.replacement,
             marker: PhantomData,
         })
     }
@@ -46,17 +78,258 @@ impl LockfileHandler<Start> {
 
 impl LockfileHandler<Moved> {
     pub fn move_lockfile_back(self) -> TResult<LockfileHandler<Complete>> {
-        let folder = self.state.parent().unwrap();
-        std::fs::rename(folder.join(CARGO_LOCK_REPLACEMENT), self.state.as_path()).map_err(
-            |err| CargoMSRVError::Io {
-                error: err,
-                source: IoErrorSource::RenameFile(self.state.clone()),
-            },
-        )?;
+        move_file(&self.replacement, &self.state).map_err(|err| CargoMSRVError::Io {
+            error: err,
+            source: IoErrorSource::RenameFile(self.state.clone()),
+        })?;
 
         Ok(LockfileHandler {
             state: self.state,
+            replacement: self.replacement,
             marker: PhantomData,
         })
     }
 }
+
+/// Move a file by renaming it. Since a file can't be renamed across file systems, which may be
+/// the case for a scratch directory on a separate mount, the file is copied and removed instead
+/// when renaming fails.
+fn move_file(from: &Path, to: &Path) -> std::io::Result<()> {
+    if std::fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+
+    std::fs::copy(from, to)?;
+    std::fs::remove_file(from)
+}
+
+/// The format of a Cargo lockfile. Cargo can't read a lockfile which uses a format that is more
+/// recent than it knows about, and fails with an error that looks like any other check failure.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum LockfileFormat {
+    /// Checksums are listed in the `[metadata]` table
+    V1,
+    /// Checksums are listed with each package
+    V2,
+    /// Like the second format, with a `version = 3` marker
+    V3,
+    /// Like the third format, with a `version = 4` marker
+    V4,
+}
+
+impl LockfileFormat {
+    /// Detect the format of the lockfile at the given path, or `None` if the crate has no
+    /// lockfile. A lockfile which can't be parsed, or which uses a format unknown to cargo-msrv,
+    /// is left for cargo to judge, and also gives `None`.
+    pub fn probe(path: &Path) -> TResult<Option<Self>> {
+        if !path.is_file() {
+            return Ok(None);
+        }
+
+        let contents = std::fs::read_to_string(path).map_err(|error| CargoMSRVError::Io {
+            error,
+            source: IoErrorSource::ReadFile(path.to_path_buf()),
+        })?;
+
+        Ok(Self::detect(&contents))
+    }
+
+    /// Detect the format of the given lockfile contents.
+    pub fn detect(contents: &str) -> Option<Self> {
+        let document = contents.parse::<Document>().ok()?;
+
+        match document.get("version") {
+            Some(version) => match version.as_integer()? {
+                3 => Some(Self::V3),
+                4 => Some(Self::V4),
+                _ => None,
+            },
+            // A lockfile without any checksums, e.g. of a crate without registry dependencies,
+            // reads the same in both of the unmarked formats
+            None if has_package_checksums(&document) => Some(Self::V2),
+            None => Some(Self::V1),
+        }
+    }
+
+    /// The first Rust release whose cargo can read lockfiles of this format.
+    pub fn readable_since(&self) -> semver::Version {
+        match self {
+            Self::V1 => semver::Version::new(1, 0, 0),
+            Self::V2 => semver::Version::new(1, 38, 0),
+            Self::V3 => semver::Version::new(1, 47, 0),
+            Self::V4 => semver::Version::new(1, 78, 0),
+        }
+    }
+
+    pub fn is_readable_by(&self, version: &semver::Version) -> bool {
+        let since = self.readable_since();
+
+        // Compare without pre-release identifiers, like CargoFlag::is_supported_by
+        (version.major, version.minor, version.patch) >= (since.major, since.minor, since.patch)
+    }
+
+    pub fn version(&self) -> u8 {
+        match self {
+            Self::V1 => 1,
+            Self::V2 => 2,
+            Self::V3 => 3,
+            Self::V4 => 4,
+        }
+    }
+}
+
+fn has_package_checksums(document: &Document) -> bool {
+    document
+        .get("package")
+        .and_then(Item::as_array_of_tables)
+        .map(|packages| {
+            packages
+                .iter()
+                .any(|package| package.contains_key("checksum"))
+        })
+        .unwrap_or_default()
+}
+
+impl fmt::Display for LockfileFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "version {}", self.version())
+    }
+}
+
+impl serde::Serialize for LockfileFormat {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(self.version())
+    }
+}
+
+impl schemars::JsonSchema for LockfileFormat {
+    fn schema_name() -> String {
+        "LockfileFormat".to_string()
+    }
+
+    fn json_schema(_gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        let formats = [Self::V1, Self::V2, Self::V3, Self::V4];
+
+        schemars::schema::SchemaObject {
+            instance_type: Some(schemars::schema::InstanceType::Integer.into()),
+            enum_values: Some(
+                formats
+                    .iter()
+                    .map(|format| format.version().into())
+                    .collect(),
+            ),
+            ..Default::default()
+        }
+        .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_dir::{DirBuilder, TestDir};
+
+    const V1: &str = r#"[[package]]
+name = "a"
+version = "0.1.0"
+dependencies = [
+ "serde 1.0.136 (registry+https://github.com/rust-lang/crates.io-index)",
+]
+
+[[package]]
+name = "serde"
+version = "1.0.136"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[metadata]
+"checksum serde 1.0.136 (registry+https://github.com/rust-lang/crates.io-index)" = "ce31e24b01e1e524df96f1c2fdd054405f8d7376249a5110886fb4b658484789"
+"#;
+
+    const V2: &str = r#"[[package]]
+name = "a"
+version = "0.1.0"
+dependencies = [
+ "serde",
+]
+
+[[package]]
+name = "serde"
+version = "1.0.136"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+checksum = "ce31e24b01e1e524df96f1c2fdd054405f8d7376249a5110886fb4b658484789"
+"#;
+
+    const NO_CHECKSUMS: &str = r#"[[package]]
+name = "a"
+version = "0.1.0"
+"#;
+
+    #[yare::parameterized(
+        v1 = { V1, Some(LockfileFormat::V1) },
+        v2 = { V2, Some(LockfileFormat::V2) },
+        v3 = { "version = 3\n", Some(LockfileFormat::V3) },
+        v4 = { "version = 4\n", Some(LockfileFormat::V4) },
+        no_checksums = { NO_CHECKSUMS, Some(LockfileFormat::V1) },
+        unknown_version = { "version = 9\n", None },
+        invalid = { "[[package", None },
+    )]
+    fn detect(contents: &str, expected: Option<LockfileFormat>) {
+        assert_eq!(LockfileFormat::detect(contents), expected);
+    }
+
+    #[yare::parameterized(
+        v3_by_older = { LockfileFormat::V3, semver::Version::new(1, 46, 0), false },
+        v3_by_same = { LockfileFormat::V3, semver::Version::new(1, 47, 0), true },
+        v3_by_beta = { LockfileFormat::V3, semver::Version::parse("1.47.0-beta.1").unwrap(), true },
+        v4_by_older = { LockfileFormat::V4, semver::Version::new(1, 77, 2), false },
+        v1_by_oldest = { LockfileFormat::V1, semver::Version::new(1, 0, 0), true },
+    )]
+    fn readable_by(format: LockfileFormat, version: semver::Version, expected: bool) {
+        assert_eq!(format.is_readable_by(&version), expected);
+    }
+
+    #[test]
+    fn lockfiles_of_crates_are_moved_apart_in_the_replacement_dir() {
+        let tmp = TestDir::temp();
+        let scratch = tmp.path("scratch");
+
+        let moved = ["a", "b"]
+            .iter()
+            .map(|name| {
+                let lockfile = tmp.path(name).join(CARGO_LOCK);
+                std::fs::create_dir_all(lockfile.parent().unwrap()).unwrap();
+                std::fs::write(&lockfile, *name).unwrap();
+
+                LockfileHandler::new(&lockfile)
+                    .with_replacement_dir(&scratch)
+                    .move_lockfile()
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        assert_ne!(moved[0].replacement, moved[1].replacement);
+
+        for (handle, name) in moved.into_iter().zip(["a", "b"]) {
+            let handle = handle.move_lockfile_back().unwrap();
+            assert_eq!(std::fs::read_to_string(&handle.state).unwrap(), name);
+        }
+    }
+
+    #[test]
+    fn probe_without_lockfile() {
+        let tmp = TestDir::temp();
+
+        assert_eq!(LockfileFormat::probe(&tmp.path(CARGO_LOCK)).unwrap(), None);
+    }
+
+    #[test]
+    fn probe_lockfile() {
+        let tmp = TestDir::temp();
+        std::fs::write(tmp.path(CARGO_LOCK), "version = 3\n").unwrap();
+
+        assert_eq!(
+            LockfileFormat::probe(&tmp.path(CARGO_LOCK)).unwrap(),
+            Some(LockfileFormat::V3)
+        );
+    }
+}
@@ -0,0 +1,221 @@
+//! The log file of cargo-msrv, which is rotated once it grows too large. Log files which are older
+//! than the maximum age are removed when the log file is opened.
+
+use crate::config::LogRotation;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Log files written by cargo-msrv start with this prefix, including the daily log files written
+/// by earlier versions, which were named `cargo-msrv-log.<date>`.
+const LOG_FILE_PREFIX: &str = "cargo-msrv";
+
+/// The folder to which cargo-msrv writes its logs: the `log` folder of the scratch directory, if
+/// one is configured, or else the `cargo-msrv` folder of the local data directory.
+pub fn log_folder(scratch_dir: Option<&Path>) -> Option<PathBuf> {
+    if let Some(dir) = scratch_dir {
+        return Some(dir.join("log"));
+    }
+
+    dirs::data_local_dir().map(|path| path.join("cargo-msrv"))
+}
+
+pub struct LogFile {
+    path: PathBuf,
+    file: File,
+    size: u64,
+    max_size: Option<u64>,
+}
+
+impl LogFile {
+    pub const FILE_NAME: &'static str = "cargo-msrv.log";
+
+    /// Open the log file in the given folder, and create the folder if it does not exist yet.
+    ///
+    /// Log files in the folder which are older than the maximum age of the `rotation` are removed.
+    pub fn open(folder: &Path, rotation: &LogRotation) -> io::Result<Self> {
+        fs::create_dir_all(folder)?;
+
+        if let Some(max_age) = rotation.max_age() {
+            remove_expired(folder, max_age)?;
+        }
+
+        let path = folder.join(Self::FILE_NAME);
+        let file = open_append(&path)?;
+        let size = file.metadata()?.len();
+
+        Ok(Self {
+            path,
+            file,
+            size,
+            max_size: rotation.max_size(),
+        })
+    }
+
+    /// The path of the current log file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Move the current log file aside, and continue with an empty log file.
+    fn rotate(&mut self) -> io::Result<()> {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_millis())
+            .unwrap_or_default();
+
+        let rotated = self
+            .path
+            .with_file_name(format!("{}.{}", Self::FILE_NAME, timestamp));
+
+        fs::rename(&self.path, rotated)?;
+
+        self.file = open_append(&self.path)?;
+        self.size = 0;
+
+        Ok(())
+    }
+}
+
+impl Write for LogFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let exceeds_max_size = self
+            .max_size
+            .map_or(false, |max_size| self.size + buf.len() as u64 > max_size);
+
+        // A single write which is larger than the maximum size is still written as a whole
+        if exceeds_max_size && self.size > 0 {
+            self.rotate()?;
+        }
+
+        let written = self.file.write(buf)?;
+        self.size += written as u64;
+
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+fn open_append(path: &Path) -> io::Result<File> {
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+/// Remove the log files in the folder which were last modified longer than `max_age` ago.
+fn remove_expired(folder: &Path, max_age: Duration) -> io::Result<()> {
+    let now = SystemTime::now();
+
+    for entry in fs::read_dir(folder)? {
+        let entry = entry?;
+
+        if !entry
+            .file_name()
+            .to_string_lossy()
+            .starts_with(LOG_FILE_PREFIX)
+        {
+            continue;
+        }
+
+        let modified = entry.metadata()?.modified()?;
+        let is_expired = now
+            .duration_since(modified)
+            .map_or(false, |age| age > max_age);
+
+        if is_expired {
+            fs::remove_file(entry.path())?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_dir::{DirBuilder, FileType, TestDir};
+
+    fn log_files(folder: &Path) -> Vec<String> {
+        let mut names = fs::read_dir(folder)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect::<Vec<_>>();
+
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn rotates_when_max_size_is_exceeded() {
+        let tmp = TestDir::temp();
+        let rotation = LogRotation::new(Some(8), None);
+
+        let mut log_file = LogFile::open(tmp.root(), &rotation).unwrap();
+        log_file.write_all(b"12345").unwrap();
+        log_file.write_all(b"678").unwrap();
+        log_file.write_all(b"9").unwrap();
+
+        let names = log_files(tmp.root());
+        assert_eq!(names.len(), 2);
+        assert_eq!(names[0], LogFile::FILE_NAME);
+        assert!(names[1].starts_with("cargo-msrv.log."));
+
+        assert_eq!(fs::read_to_string(log_file.path()).unwrap(), "9");
+        assert_eq!(
+            fs::read_to_string(tmp.root().join(&names[1])).unwrap(),
+            "12345678"
+        );
+    }
+
+    #[test]
+    fn appends_without_max_size() {
+        let tmp = TestDir::temp();
+        let rotation = LogRotation::new(None, None);
+
+        let mut log_file = LogFile::open(tmp.root(), &rotation).unwrap();
+        log_file.write_all(b"12345").unwrap();
+        log_file.write_all(b"678").unwrap();
+
+        assert_eq!(log_files(tmp.root()), vec![LogFile::FILE_NAME]);
+        assert_eq!(fs::read_to_string(log_file.path()).unwrap(), "12345678");
+    }
+
+    #[test]
+    fn removes_expired_log_files_only() {
+        let tmp = TestDir::temp()
+            .create("cargo-msrv-log.2022-07-01", FileType::EmptyFile)
+            .create("unrelated", FileType::EmptyFile);
+
+        std::thread::sleep(Duration::from_millis(10));
+
+        let rotation = LogRotation::new(None, Some(Duration::from_millis(1)));
+        LogFile::open(tmp.root(), &rotation).unwrap();
+
+        assert_eq!(log_files(tmp.root()), vec![LogFile::FILE_NAME, "unrelated"]);
+    }
+
+    #[test]
+    fn keeps_recent_log_files() {
+        let tmp = TestDir::temp().create("cargo-msrv.log.1", FileType::EmptyFile);
+
+        let rotation = LogRotation::default();
+        LogFile::open(tmp.root(), &rotation).unwrap();
+
+        assert_eq!(
+            log_files(tmp.root()),
+            vec![LogFile::FILE_NAME, "cargo-msrv.log.1"]
+        );
+    }
+
+    #[test]
+    fn creates_log_folder() {
+        let tmp = TestDir::temp();
+        let folder = tmp.path("log");
+
+        let log_file = LogFile::open(&folder, &LogRotation::default()).unwrap();
+
+        assert_eq!(log_file.path(), folder.join(LogFile::FILE_NAME));
+    }
+}
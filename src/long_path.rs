@@ -0,0 +1,192 @@
+//! Paths which may exceed the limit of 260 characters of the Windows API, like those of the
+//! sandboxes, e.g. a copy of a crate, and target directories, within the deep directory tree of a
+//! workspace.
+//!
+//! On Windows, such a path is given the `\\?\` prefix, which lifts the limit. Elsewhere, paths are
+//! left as they are.
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// The length from which a path is given the `\\?\` prefix. Well below the limit of 260
+/// characters, since a directory is limited to 248 characters, and cargo, and the sandboxes,
+/// append their own paths to the given path.
+#[cfg_attr(not(windows), allow(dead_code))]
+const LONG_PATH: usize = 200;
+
+/// The number of sandbox paths which were handed out by this process.
+static SANDBOXES: AtomicUsize = AtomicUsize::new(0);
+
+/// The directory in which a sandbox is created: within the `root`, given with `--sandbox-dir` or
+/// `--scratch-dir`, or else within the temporary directory of the system. The directory is named
+/// after the given name, the process, and a counter, so runs which share the root, and sandboxes
+/// of the same run, never use the same directory.
+pub(crate) fn sandbox_path(root: Option<&Path>, name: &str) -> PathBuf {
+    let root = root
+        .map(Path::to_path_buf)
+        .unwrap_or_else(std::env::temp_dir);
+
+    let n = SANDBOXES.fetch_add(1, Ordering::Relaxed);
+    let name = format!("{}-{}-{}", name, std::process::id(), n);
+
+    long_path(&root.join(name))
+}
+
+/// A sandbox directory, at a path given by [`sandbox_path`], which is removed when dropped.
+#[derive(Debug)]
+pub(crate) struct Sandbox {
+    path: PathBuf,
+}
+
+impl Sandbox {
+    pub(crate) fn new(root: Option<&Path>, name: &str) -> Self {
+        Self {
+            path: sandbox_path(root, name),
+        }
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for Sandbox {
+    /// Failing to remove the sandbox is not fatal, since it's only temporary.
+    fn drop(&mut self) {
+        if self.path.exists() {
+            if let Err(error) = std::fs::remove_dir_all(&self.path) {
+                warn!(path = %self.path.display(), %error, "unable to remove sandbox");
+            }
+        }
+    }
+}
+
+/// The path, with the `\\?\` prefix on Windows, when it's long enough to exceed the limit of the
+/// Windows API. Since the prefix is only valid for an absolute path, a relative path is made
+/// absolute first.
+#[cfg(windows)]
+pub(crate) fn long_path(path: &Path) -> PathBuf {
+    let absolute = if path.is_relative() {
+        match std::env::current_dir() {
+            Ok(dir) => dir.join(path),
+            Err(_) => return path.to_path_buf(),
+        }
+    } else {
+        path.to_path_buf()
+    };
+
+    absolute
+        .to_str()
+        .and_then(extended_length)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| path.to_path_buf())
+}
+
+/// The path, as is, since only the Windows API limits the length of paths.
+#[cfg(not(windows))]
+pub(crate) fn long_path(path: &Path) -> PathBuf {
+    path.to_path_buf()
+}
+
+/// The given absolute Windows path, with the `\\?\` prefix, if it's long enough to need it, and
+/// doesn't have it already. Windows doesn't normalize a prefixed path, so the separators are
+/// normalized, and the `.` and `..` components are resolved, here.
+#[cfg_attr(not(windows), allow(dead_code))]
+fn extended_length(path: &str) -> Option<String> {
+    if path.len() < LONG_PATH || path.starts_with(r"\\?\") || path.starts_with(r"\\.\") {
+        return None;
+    }
+
+    let path = path.replace('/', r"\");
+    let bytes = path.as_bytes();
+
+    // the components of the root, like the server and share of a UNC path, are never removed by
+    // a `..` component
+    let (prefix, rest, root_components) = if let Some(unc) = path.strip_prefix(r"\\") {
+        (r"\\?\UNC\", unc, 2)
+    } else if bytes.len() > 2
+        && bytes[0].is_ascii_alphabetic()
+        && bytes[1] == b':'
+        && bytes[2] == b'\\'
+    {
+        (r"\\?\", path.as_str(), 1)
+    } else {
+        return None;
+    };
+
+    let mut components = Vec::new();
+
+    for component in rest.split('\\') {
+        match component {
+            "" | "." => {}
+            ".." if components.len() > root_components => {
+                components.pop();
+            }
+            ".." => {}
+            component => components.push(component),
+        }
+    }
+
+    Some(format!("{}{}", prefix, components.join(r"\")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A path within the deep directory tree of a workspace.
+    fn deep(root: &str) -> String {
+        let mut path = root.to_string();
+
+        for level in 0..12 {
+            path.push_str(&format!(r"\member-with-a-long-name-{}", level));
+        }
+
+        path
+    }
+
+    #[yare::parameterized(
+        drive = { &deep(r"C:\ws"), Some(format!(r"\\?\{}", deep(r"C:\ws"))) },
+        unc = { &deep(r"\\server\share"), Some(format!(r"\\?\UNC\{}", deep(r"server\share"))) },
+        forward_slashes = { &deep(r"C:\ws").replace('\\', "/"), Some(format!(r"\\?\{}", deep(r"C:\ws"))) },
+        current_and_parent = { &format!(r"{}\.\target\..\sandbox", deep(r"C:\ws")), Some(format!(r"\\?\{}\sandbox", deep(r"C:\ws"))) },
+        parent_of_root = { &format!(r"C:\..\..\{}", &deep(r"C:\ws")[3..]), Some(format!(r"\\?\{}", deep(r"C:\ws"))) },
+        parent_of_share = { &format!(r"\\server\share\..\{}", &deep(r"\\server\share")[15..]), Some(format!(r"\\?\UNC\{}", deep(r"server\share"))) },
+        prefixed = { &format!(r"\\?\{}", deep(r"C:\ws")), None },
+        device = { &format!(r"\\.\{}", deep(r"C:\ws")), None },
+        relative = { &deep("ws"), None },
+        short = { r"C:\ws\target", None },
+    )]
+    fn extended_length_path(path: &str, expected: Option<String>) {
+        assert_eq!(extended_length(path), expected);
+    }
+
+    #[test]
+    fn sandbox_within_root() {
+        let path = sandbox_path(Some(Path::new("scratch")), "cargo-msrv-impact");
+        let name = path.file_name().unwrap().to_string_lossy();
+
+        assert!(path.parent().unwrap().ends_with("scratch"));
+        assert!(name.starts_with(&format!("cargo-msrv-impact-{}-", std::process::id())));
+    }
+
+    #[test]
+    fn sandbox_paths_are_unique() {
+        let first = sandbox_path(None, "cargo-msrv-impact");
+        let second = sandbox_path(None, "cargo-msrv-impact");
+
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn sandbox_is_removed_when_dropped() {
+        let tmp = test_dir::TestDir::temp();
+        let sandbox = Sandbox::new(Some(test_dir::DirBuilder::root(&tmp)), "cargo-msrv-edition");
+        let path = sandbox.path().to_path_buf();
+
+        std::fs::create_dir_all(path.join("src")).unwrap();
+        drop(sandbox);
+
+        assert!(!path.exists());
+    }
+}
@@ -1,8 +1,16 @@
+use crate::cli::rust_releases_opts::Edition;
+use crate::error::{CargoMSRVError, IoErrorSource, TResult};
 use crate::manifest::bare_version::BareVersion;
 use std::convert::TryFrom;
+use std::path::Path;
 use toml_edit::{Document, Item, TomlError};
 
 pub(crate) mod bare_version;
+pub(crate) mod manifest_patch;
+pub(crate) mod pruned_manifest;
+pub(crate) mod syntax;
+pub(crate) mod version_bump;
+pub(crate) mod workspace;
 
 pub trait TomlParser {
     type Error;
@@ -105,6 +113,145 @@ fn find_minimum_rust_version(document: &Document) -> Option<&str> {
     find_rust_version(document).or_else(|| find_metadata_msrv(document))
 }
 
+/// Read the `edition` of the crate from the `Cargo.toml` manifest at the given path, and get the
+/// first Rust version which supports this edition.
+///
+/// Returns `None` if the manifest does not specify an edition.
+pub(crate) fn edition_minimum_version(cargo_toml: &Path) -> TResult<Option<BareVersion>> {
+    let edition = read_edition(cargo_toml)?;
+
+    Ok(edition.map(|edition| edition.as_bare_version()))
+}
+
+/// Read the `edition` of the crate from the `Cargo.toml` manifest at the given path.
+///
+/// Returns `None` if the manifest does not specify an edition, in which case Cargo defaults to
+/// the 2015 edition.
+pub(crate) fn read_edition(cargo_toml: &Path) -> TResult<Option<Edition>> {
+    let contents = std::fs::read_to_string(cargo_toml).map_err(|error| CargoMSRVError::Io {
+        error,
+        source: IoErrorSource::ReadFile(cargo_toml.to_path_buf()),
+    })?;
+    let document = contents
+        .parse::<Document>()
+        .map_err(CargoMSRVError::ParseToml)?;
+
+    document
+        .as_table()
+        .get("package")
+        .and_then(Item::as_table)
+        .and_then(|package_table| package_table.get("edition"))
+        .and_then(Item::as_str)
+        .map(|edition| Ok(edition.parse::<Edition>()?))
+        .transpose()
+}
+
+/// Read the MSRV which is declared by the `Cargo.toml` manifest at the given path, from the
+/// `package.rust-version` or `package.metadata.msrv` key.
+///
+/// Returns `None` if the manifest does not declare an MSRV.
+pub(crate) fn read_declared_msrv(cargo_toml: &Path) -> TResult<Option<BareVersion>> {
+    let contents = std::fs::read_to_string(cargo_toml).map_err(|error| CargoMSRVError::Io {
+        error,
+        source: IoErrorSource::ReadFile(cargo_toml.to_path_buf()),
+    })?;
+    let document = contents
+        .parse::<Document>()
+        .map_err(CargoMSRVError::ParseToml)?;
+
+    minimum_rust_version(&document)
+}
+
+/// Read the versions which are left out of the search space from the
+/// `package.metadata.cargo-msrv.exclude-versions` key of the `Cargo.toml` manifest at the given
+/// path, e.g. `exclude-versions = ["1.59.0"]`.
+///
+/// Returns an empty list if the manifest does not specify any.
+pub(crate) fn read_excluded_versions(cargo_toml: &Path) -> TResult<Vec<BareVersion>> {
+    let contents = std::fs::read_to_string(cargo_toml).map_err(|error| CargoMSRVError::Io {
+        error,
+        source: IoErrorSource::ReadFile(cargo_toml.to_path_buf()),
+    })?;
+    let document = contents
+        .parse::<Document>()
+        .map_err(CargoMSRVError::ParseToml)?;
+
+    excluded_versions(&document)
+}
+
+fn excluded_versions(document: &Document) -> TResult<Vec<BareVersion>> {
+    let versions = document
+        .as_table()
+        .get("package")
+        .and_then(Item::as_table)
+        .and_then(|package| package.get("metadata"))
+        .and_then(Item::as_table_like)
+        .and_then(|metadata| metadata.get("cargo-msrv"))
+        .and_then(Item::as_table_like)
+        .and_then(|cargo_msrv| cargo_msrv.get("exclude-versions"))
+        .and_then(Item::as_array);
+
+    versions
+        .into_iter()
+        .flat_map(|versions| versions.iter())
+        .map(|version| {
+            let version = version.as_str().ok_or_else(|| {
+                CargoMSRVError::InvalidConfig(
+                    "Expected a list of Rust versions for 'package.metadata.cargo-msrv.exclude-versions'"
+                        .to_string(),
+                )
+            })?;
+
+            Ok(version.parse()?)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod excluded_versions_tests {
+    use super::*;
+
+    #[test]
+    fn listed_versions() {
+        let document = r#"[package]
+name = "some"
+
+[package.metadata.cargo-msrv]
+exclude-versions = ["1.59.0", "1.48"]
+"#
+        .parse::<Document>()
+        .unwrap();
+
+        assert_eq!(
+            excluded_versions(&document).unwrap(),
+            vec![
+                BareVersion::ThreeComponents(1, 59, 0),
+                BareVersion::TwoComponents(1, 48)
+            ]
+        );
+    }
+
+    #[yare::parameterized(
+        no_metadata = { "[package]\nname = \"some\"\n" },
+        metadata_msrv = { "[package]\nname = \"some\"\n\n[package.metadata]\nmsrv = \"1.42\"\n" },
+    )]
+    fn none_listed(contents: &str) {
+        let document = contents.parse::<Document>().unwrap();
+
+        assert!(excluded_versions(&document).unwrap().is_empty());
+    }
+
+    #[yare::parameterized(
+        not_a_string = { "[package.metadata.cargo-msrv]\nexclude-versions = [159]\n" },
+        invalid_version = { "[package.metadata.cargo-msrv]\nexclude-versions = [\"latest\"]\n" },
+    )]
+    fn invalid(contents: &str) {
+        let document = contents.parse::<Document>().unwrap();
+
+        assert!(excluded_versions(&document).is_err());
+    }
+}
+
 #[cfg(test)]
 mod minimal_version_tests {
     use crate::error::CargoMSRVError;
@@ -1,11 +1,20 @@
+use crate::config::VersionResolution;
 use crate::semver;
 
+use std::cmp::Reverse;
 use std::convert::TryFrom;
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 
 type BareVersionUsize = u64;
 
+/// A two- or three component Rust version, as specified by e.g. the `rust-version` field.
+///
+/// A two component version like `1.60` stands for any patch release of that minor release, i.e.
+/// it matches `1.60.0`, but also `1.60.1`. When it is resolved to a single release, the most recent
+/// patch release is picked. A three component version like `1.60.0` only matches that exact
+/// release. Use [`BareVersion::pin_patch`] to treat a two component version as the `.0` release
+/// instead.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum BareVersion {
     TwoComponents(BareVersionUsize, BareVersionUsize),
@@ -79,6 +88,48 @@ impl BareVersion {
             })
     }
 
+    /// The release to which a version which matches none of the `available` releases is resolved,
+    /// with the given resolution, if any. With [`VersionResolution::Strict`], it's never resolved.
+    pub fn resolve_unmatched<'s, I>(
+        &self,
+        available: I,
+        resolution: VersionResolution,
+    ) -> Option<&'s semver::Version>
+    where
+        I: Iterator<Item = &'s semver::Version>,
+    {
+        let requested = self.to_semver_version();
+
+        match resolution {
+            VersionResolution::Strict => None,
+            VersionResolution::ClosestPatch => available
+                .filter(|version| {
+                    version.major == requested.major && version.minor == requested.minor
+                })
+                .min_by_key(|version| {
+                    let distance = if version.patch > requested.patch {
+                        version.patch - requested.patch
+                    } else {
+                        requested.patch - version.patch
+                    };
+
+                    (distance, Reverse(version.patch))
+                }),
+            VersionResolution::NearestNewer => {
+                available.filter(|&version| version > &requested).min()
+            }
+        }
+    }
+
+    /// Pin a two component version to the first release of its minor version, i.e. `1.60` becomes
+    /// `1.60.0`. Three component versions are returned as is.
+    pub fn pin_patch(&self) -> BareVersion {
+        match self {
+            Self::TwoComponents(major, minor) => Self::ThreeComponents(*major, *minor, 0),
+            Self::ThreeComponents(..) => self.clone(),
+        }
+    }
+
     pub fn to_semver_version(&self) -> crate::semver::Version {
         match self {
             Self::TwoComponents(major, minor) => semver::Version::new(*major, *minor, 0),
@@ -113,6 +164,12 @@ impl BareVersion {
         }
     }
 
+    /// Compares whether the `given` version matches `self`, i.e. is the same release, or for a two
+    /// component version, is any patch release of the same minor version.
+    pub fn matches(&self, given: &semver::Version) -> bool {
+        self.is_at_least(given) && self.is_at_most(given)
+    }
+
     /// Compares whether the `given` version matches at most `self`.
     pub fn is_at_most(&self, given: &semver::Version) -> bool {
         match (self, given) {
@@ -340,8 +397,34 @@ impl serde::Serialize for BareVersion {
     }
 }
 
+impl schemars::JsonSchema for BareVersion {
+    fn schema_name() -> String {
+        "BareVersion".to_string()
+    }
+
+    fn json_schema(_gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        let mut schema = schemars::schema::SchemaObject {
+            instance_type: Some(schemars::schema::InstanceType::String.into()),
+            ..Default::default()
+        };
+        schema.string().pattern = Some(r"^\d+\.\d+(\.\d+)?$".to_string());
+        schema.into()
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for BareVersion {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let version = <String as serde::Deserialize>::deserialize(deserializer)?;
+        BareVersion::from_str(&version).map_err(serde::de::Error::custom)
+    }
+}
+
 #[cfg(test)]
 mod bare_version_tests {
+    use crate::config::VersionResolution;
     use crate::manifest::BareVersion;
     use rust_releases::{semver, Release, ReleaseIndex};
     use std::iter::FromIterator;
@@ -455,6 +538,48 @@ mod bare_version_tests {
         assert_eq!(v, &expected);
     }
 
+    #[parameterized(
+        two_component = { BareVersion::TwoComponents(1, 54), BareVersion::ThreeComponents(1, 54, 0) },
+        three_component = { BareVersion::ThreeComponents(1, 54, 2), BareVersion::ThreeComponents(1, 54, 2) },
+    )]
+    fn pin_patch(version: BareVersion, expected: BareVersion) {
+        assert_eq!(version.pin_patch(), expected);
+    }
+
+    #[test]
+    fn pinned_two_components_to_semver() {
+        let index = release_indices();
+        let available = index.releases().iter().map(Release::version);
+
+        let given = BareVersion::TwoComponents(1, 54).pin_patch();
+
+        // 1.54.0 is not in the index, only 1.54.1 and 1.54.2 are
+        assert!(given.try_to_semver(available).is_err())
+    }
+
+    #[parameterized(
+        strict = { BareVersion::ThreeComponents(1, 54, 0), VersionResolution::Strict, None },
+        closest_patch = { BareVersion::ThreeComponents(1, 54, 0), VersionResolution::ClosestPatch, Some(semver::Version::new(1, 54, 1)) },
+        closest_patch_of_newer_patch = { BareVersion::ThreeComponents(1, 54, 7), VersionResolution::ClosestPatch, Some(semver::Version::new(1, 54, 2)) },
+        closest_patch_without_patches = { BareVersion::TwoComponents(1, 40), VersionResolution::ClosestPatch, None },
+        nearest_newer = { BareVersion::ThreeComponents(1, 54, 0), VersionResolution::NearestNewer, Some(semver::Version::new(1, 54, 1)) },
+        nearest_newer_minor = { BareVersion::TwoComponents(1, 40), VersionResolution::NearestNewer, Some(semver::Version::new(1, 54, 1)) },
+        nearest_newer_than_newest = { BareVersion::TwoComponents(3, 0), VersionResolution::NearestNewer, None },
+    )]
+    fn resolve_unmatched(
+        version: BareVersion,
+        resolution: VersionResolution,
+        expected: Option<semver::Version>,
+    ) {
+        let index = release_indices();
+        let available = index.releases().iter().map(Release::version);
+
+        assert_eq!(
+            version.resolve_unmatched(available, resolution),
+            expected.as_ref()
+        );
+    }
+
     #[test]
     fn not_in_index() {
         let index = release_indices();
@@ -0,0 +1,224 @@
+//! A patch which is merged into the Cargo manifest before each check, given with
+//! `--manifest-patch`, e.g. to pin a dependency to an older version, which the cargo of older
+//! toolchains is able to resolve.
+//!
+//! The patch is a TOML document, whose tables are merged into those of the manifest, and whose
+//! other values replace those of the manifest. The tables of its `[rust-version.'<requirement>']`
+//! table are only merged for the toolchains whose version matches the requirement, e.g.:
+//!
+//! ```toml
+//! [patch.crates-io]
+//! example = { path = "../example" }
+//!
+//! [rust-version.'<1.60'.dependencies]
+//! log = "=0.4.14"
+//! ```
+
+use std::path::{Path, PathBuf};
+
+use toml_edit::{Document, Item, Table, TableLike};
+
+use crate::error::{CargoMSRVError, IoErrorSource, TResult};
+use crate::semver;
+
+/// The table of the patch, whose tables are only merged for the matching toolchains.
+const RUST_VERSION_KEY: &str = "rust-version";
+
+#[derive(Clone, Debug)]
+pub struct ManifestPatch {
+    path: PathBuf,
+    /// Merged for each toolchain
+    common: Table,
+    /// Merged for the toolchains which match the requirement, in the order of the patch
+    conditional: Vec<(semver::VersionReq, Table)>,
+}
+
+impl ManifestPatch {
+    pub fn load(path: &Path) -> TResult<Self> {
+        let contents = std::fs::read_to_string(path).map_err(|error| CargoMSRVError::Io {
+            error,
+            source: IoErrorSource::ReadFile(path.to_path_buf()),
+        })?;
+
+        Self::parse(path, &contents)
+    }
+
+    fn parse(path: &Path, contents: &str) -> TResult<Self> {
+        let invalid = |reason: String| {
+            CargoMSRVError::InvalidConfig(format!(
+                "Given manifest patch '{}' is not valid: {}",
+                path.display(),
+                reason
+            ))
+        };
+
+        let mut common = contents.parse::<Document>()?.as_table().clone();
+
+        let conditional = match common.remove(RUST_VERSION_KEY) {
+            Some(item) => {
+                let tables = item
+                    .as_table_like()
+                    .ok_or_else(|| invalid(format!("`{}` is not a table", RUST_VERSION_KEY)))?;
+
+                tables
+                    .iter()
+                    .map(|(requirement, patch)| {
+                        let requirement = semver::VersionReq::parse(requirement).map_err(|_| {
+                            invalid(format!("'{}' is not a version requirement", requirement))
+                        })?;
+                        let patch = patch.as_table().cloned().ok_or_else(|| {
+                            invalid(format!(
+                                "`{}.'{}'` is not a table",
+                                RUST_VERSION_KEY, requirement
+                            ))
+                        })?;
+
+                        Ok((requirement, patch))
+                    })
+                    .collect::<TResult<Vec<_>>>()?
+            }
+            None => Vec::new(),
+        };
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            common,
+            conditional,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Merge the patch into the manifest, for a check with the given toolchain version. Returns
+    /// whether anything was merged.
+    pub fn apply(&self, document: &mut Document, version: &semver::Version) -> bool {
+        let patches = std::iter::once(&self.common).chain(
+            self.conditional
+                .iter()
+                .filter(|(requirement, _)| requirement.matches(version))
+                .map(|(_, patch)| patch),
+        );
+
+        let mut applied = false;
+
+        for patch in patches.filter(|patch| !patch.is_empty()) {
+            merge(document.as_table_mut(), patch);
+            applied = true;
+        }
+
+        applied
+    }
+}
+
+/// Merge the tables of the patch into those of the manifest, recursively. Other values of the
+/// patch, like the version requirement of a dependency, replace those of the manifest.
+fn merge(manifest: &mut dyn TableLike, patch: &dyn TableLike) {
+    for (key, value) in patch.iter() {
+        let both_tables =
+            value.is_table_like() && manifest.get(key).map_or(false, Item::is_table_like);
+
+        if both_tables {
+            if let (Some(manifest), Some(patch)) = (
+                manifest.get_mut(key).and_then(Item::as_table_like_mut),
+                value.as_table_like(),
+            ) {
+                merge(manifest, patch);
+            }
+        } else {
+            manifest.insert(key, value.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = r#"[package]
+name = "example"
+version = "0.1.0"
+
+[dependencies]
+log = { version = "0.4", features = ["std"] }
+serde = "1"
+"#;
+
+    const PATCH: &str = r#"[patch.crates-io]
+serde = { path = "../serde" }
+
+[rust-version.'<1.60'.dependencies]
+log = "=0.4.14"
+"#;
+
+    fn patched(patch: &str, version: semver::Version) -> (bool, Document) {
+        let patch = ManifestPatch::parse(Path::new("patch.toml"), patch).unwrap();
+        let mut document = MANIFEST.parse::<Document>().unwrap();
+
+        let applied = patch.apply(&mut document, &version);
+
+        (applied, document)
+    }
+
+    #[test]
+    fn conditional_patch_applies_to_matching_toolchain() {
+        let (applied, document) = patched(PATCH, semver::Version::new(1, 56, 0));
+
+        assert!(applied);
+        assert_eq!(document["dependencies"]["log"].as_str(), Some("=0.4.14"));
+        assert_eq!(
+            document["patch"]["crates-io"]["serde"]["path"].as_str(),
+            Some("../serde")
+        );
+        assert!(document.get(RUST_VERSION_KEY).is_none());
+    }
+
+    #[test]
+    fn conditional_patch_is_skipped_for_other_toolchains() {
+        let (applied, document) = patched(PATCH, semver::Version::new(1, 60, 0));
+
+        assert!(applied);
+        assert_eq!(
+            document["dependencies"]["log"]["version"].as_str(),
+            Some("0.4")
+        );
+        assert_eq!(
+            document["patch"]["crates-io"]["serde"]["path"].as_str(),
+            Some("../serde")
+        );
+    }
+
+    #[test]
+    fn tables_are_merged() {
+        let patch = "[dependencies.log]\nversion = \"=0.4.14\"\n";
+        let (_, document) = patched(patch, semver::Version::new(1, 56, 0));
+
+        assert_eq!(
+            document["dependencies"]["log"]["version"].as_str(),
+            Some("=0.4.14")
+        );
+        assert!(document["dependencies"]["log"]["features"].is_array());
+        assert_eq!(document["dependencies"]["serde"].as_str(), Some("1"));
+    }
+
+    #[test]
+    fn nothing_to_apply() {
+        let patch = "[rust-version.'<1.60'.dependencies]\nlog = \"=0.4.14\"\n";
+        let (applied, document) = patched(patch, semver::Version::new(1, 60, 0));
+
+        assert!(!applied);
+        assert_eq!(document.to_string(), MANIFEST);
+    }
+
+    #[yare::parameterized(
+        requirement = { "[rust-version.'one point sixty'.dependencies]\nlog = \"=0.4.14\"\n" },
+        not_a_table = { "rust-version = \"1.60\"\n" },
+        conditional_not_a_table = { "[rust-version]\n'<1.60' = \"log\"\n" },
+    )]
+    fn invalid_patch(patch: &str) {
+        let result = ManifestPatch::parse(Path::new("patch.toml"), patch);
+
+        assert!(matches!(result, Err(CargoMSRVError::InvalidConfig(_))));
+    }
+}
@@ -0,0 +1,250 @@
+use std::path::{Path, PathBuf};
+
+use toml_edit::{Document, Item};
+
+use crate::config::DependencyKind;
+use crate::error::{CargoMSRVError, IoErrorSource, TResult};
+use crate::manifest::manifest_patch::ManifestPatch;
+use crate::semver;
+
+/// A Cargo manifest from which the dependency tables of some kinds of dependencies were removed,
+/// so they don't take part in a check, or into which a [`ManifestPatch`] was merged. The original
+/// manifest is written back by [`PrunedManifest::restore`].
+///
+/// Since cargo updates the lockfile to the pruned dependencies, the lockfile is restored as well.
+#[derive(Debug)]
+pub struct PrunedManifest {
+    manifest: PathBuf,
+    original_manifest: String,
+    lockfile: PathBuf,
+    original_lockfile: Option<String>,
+}
+
+impl PrunedManifest {
+    /// Remove the dependency tables of the given kinds from the manifest, including those of
+    /// platform specific dependencies. Returns `None` when the manifest has no such tables, in
+    /// which case it's left untouched.
+    pub fn prune(
+        manifest: &Path,
+        lockfile: &Path,
+        excluded: &[DependencyKind],
+    ) -> TResult<Option<Self>> {
+        Self::rewrite(manifest, lockfile, |document| {
+            remove_dependency_tables(document, excluded)
+        })
+    }
+
+    /// Merge the patch into the manifest, for a check with the given toolchain version. Returns
+    /// `None` when nothing of the patch applies to the toolchain, in which case the manifest is
+    /// left untouched.
+    pub fn patch(
+        manifest: &Path,
+        lockfile: &Path,
+        patch: &ManifestPatch,
+        version: &semver::Version,
+    ) -> TResult<Option<Self>> {
+        Self::rewrite(manifest, lockfile, |document| {
+            patch.apply(document, version)
+        })
+    }
+
+    /// Rewrite the manifest with the given edit, which returns whether it changed the manifest.
+    fn rewrite(
+        manifest: &Path,
+        lockfile: &Path,
+        edit: impl FnOnce(&mut Document) -> bool,
+    ) -> TResult<Option<Self>> {
+        let original_manifest = read_file(manifest)?;
+        let mut document = original_manifest.parse::<Document>()?;
+
+        if !edit(&mut document) {
+            return Ok(None);
+        }
+
+        let original_lockfile = if lockfile.is_file() {
+            Some(read_file(lockfile)?)
+        } else {
+            None
+        };
+
+        write_file(manifest, &document.to_string())?;
+
+        Ok(Some(Self {
+            manifest: manifest.to_path_buf(),
+            original_manifest,
+            lockfile: lockfile.to_path_buf(),
+            original_lockfile,
+        }))
+    }
+
+    /// Write back the original manifest, and the lockfile as it was before the manifest was
+    /// pruned. A lockfile which was generated for the pruned manifest is removed.
+    pub fn restore(self) -> TResult<()> {
+        write_file(&self.manifest, &self.original_manifest)?;
+
+        match &self.original_lockfile {
+            Some(contents) => write_file(&self.lockfile, contents),
+            None if self.lockfile.is_file() => {
+                std::fs::remove_file(&self.lockfile).map_err(|error| CargoMSRVError::Io {
+                    error,
+                    source: IoErrorSource::RemoveFile(self.lockfile.clone()),
+                })
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+/// Remove the dependency tables of the given kinds, from the top level of the manifest, and from
+/// each `[target.<cfg>]` table. Returns whether any table was removed.
+fn remove_dependency_tables(document: &mut Document, excluded: &[DependencyKind]) -> bool {
+    let keys = excluded
+        .iter()
+        .flat_map(|kind| kind.table_keys())
+        .collect::<Vec<_>>();
+
+    let mut removed = false;
+
+    for key in &keys {
+        removed |= document.remove(key).is_some();
+    }
+
+    if let Some(targets) = document.get_mut("target").and_then(Item::as_table_like_mut) {
+        for (_, target) in targets.iter_mut() {
+            if let Some(target) = target.as_table_like_mut() {
+                for key in &keys {
+                    removed |= target.remove(key).is_some();
+                }
+            }
+        }
+    }
+
+    removed
+}
+
+fn read_file(path: &Path) -> TResult<String> {
+    std::fs::read_to_string(path).map_err(|error| CargoMSRVError::Io {
+        error,
+        source: IoErrorSource::ReadFile(path.to_path_buf()),
+    })
+}
+
+fn write_file(path: &Path, contents: &str) -> TResult<()> {
+    std::fs::write(path, contents).map_err(|error| CargoMSRVError::Io {
+        error,
+        source: IoErrorSource::WriteFile(path.to_path_buf()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_dir::{DirBuilder, TestDir};
+
+    const MANIFEST: &str = r#"[package]
+name = "example"
+version = "0.1.0"
+
+[dependencies]
+serde = "1"
+
+[build-dependencies]
+cc = "1"
+
+[dev-dependencies]
+criterion = "0.3"
+
+[target.'cfg(unix)'.dev-dependencies]
+nix = "0.24"
+"#;
+
+    #[test]
+    fn prune_and_restore() {
+        let tmp = TestDir::temp();
+        let manifest = tmp.path("Cargo.toml");
+        let lockfile = tmp.path("Cargo.lock");
+        std::fs::write(&manifest, MANIFEST).unwrap();
+        std::fs::write(&lockfile, "version = 3\n").unwrap();
+
+        let pruned = PrunedManifest::prune(&manifest, &lockfile, &[DependencyKind::Dev])
+            .unwrap()
+            .unwrap();
+
+        let contents = std::fs::read_to_string(&manifest).unwrap();
+        assert!(contents.contains("[dependencies]"));
+        assert!(contents.contains("[build-dependencies]"));
+        assert!(!contents.contains("criterion"));
+        assert!(!contents.contains("nix"));
+
+        // cargo rewrites the lockfile for the pruned manifest
+        std::fs::write(&lockfile, "version = 3\n# pruned\n").unwrap();
+
+        pruned.restore().unwrap();
+
+        assert_eq!(std::fs::read_to_string(&manifest).unwrap(), MANIFEST);
+        assert_eq!(std::fs::read_to_string(&lockfile).unwrap(), "version = 3\n");
+    }
+
+    #[test]
+    fn generated_lockfile_is_removed() {
+        let tmp = TestDir::temp();
+        let manifest = tmp.path("Cargo.toml");
+        let lockfile = tmp.path("Cargo.lock");
+        std::fs::write(&manifest, MANIFEST).unwrap();
+
+        let pruned = PrunedManifest::prune(&manifest, &lockfile, &[DependencyKind::Build])
+            .unwrap()
+            .unwrap();
+
+        std::fs::write(&lockfile, "version = 3\n").unwrap();
+        pruned.restore().unwrap();
+
+        assert!(!lockfile.exists());
+    }
+
+    #[yare::parameterized(
+        nothing_excluded = { &[] },
+        no_such_table = { &[DependencyKind::Dev] },
+    )]
+    fn untouched(excluded: &[DependencyKind]) {
+        let tmp = TestDir::temp();
+        let manifest = tmp.path("Cargo.toml");
+        let contents = "[package]\nname = \"example\"\n\n[dependencies]\nserde = \"1\"\n";
+        std::fs::write(&manifest, contents).unwrap();
+
+        let pruned = PrunedManifest::prune(&manifest, &tmp.path("Cargo.lock"), excluded).unwrap();
+
+        assert!(pruned.is_none());
+        assert_eq!(std::fs::read_to_string(&manifest).unwrap(), contents);
+    }
+
+    #[test]
+    fn patch_and_restore() {
+        let tmp = TestDir::temp();
+        let manifest = tmp.path("Cargo.toml");
+        let patch = tmp.path("patch.toml");
+        std::fs::write(&manifest, MANIFEST).unwrap();
+        std::fs::write(
+            &patch,
+            "[rust-version.'<1.60'.dependencies]\nserde = \"=1.0.100\"\n",
+        )
+        .unwrap();
+
+        let patch = ManifestPatch::load(&patch).unwrap();
+        let patched = PrunedManifest::patch(
+            &manifest,
+            &tmp.path("Cargo.lock"),
+            &patch,
+            &semver::Version::new(1, 56, 0),
+        )
+        .unwrap()
+        .unwrap();
+
+        let contents = std::fs::read_to_string(&manifest).unwrap();
+        assert!(contents.contains("serde = \"=1.0.100\""));
+
+        patched.restore().unwrap();
+
+        assert_eq!(std::fs::read_to_string(&manifest).unwrap(), MANIFEST);
+    }
+}
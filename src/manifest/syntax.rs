@@ -0,0 +1,308 @@
+//! Manifest syntax which the cargo of older toolchains doesn't understand.
+//!
+//! Cargo refuses to read a manifest which uses a key, or a kind of value, that is more recent than
+//! it knows about. The check of such a toolchain fails with a parse error, which looks like any
+//! other incompatibility, so toolchains less recent than the syntax of the manifest requires are
+//! skipped from the search instead.
+
+use crate::error::{CargoMSRVError, IoErrorSource, TResult};
+use crate::semver;
+use std::fmt;
+use std::path::Path;
+use toml_edit::{Document, Item, Table, TableLike};
+
+/// A manifest feature which only the cargo of more recent toolchains can read.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ManifestFeature {
+    /// The second feature resolver, `resolver = "2"`
+    Resolver2,
+    /// A custom profile, which `inherits` from another profile
+    CustomProfile,
+    /// The `strip` option of a profile
+    ProfileStrip,
+    /// A feature which enables an optional dependency with the `dep:` prefix
+    NamespacedFeature,
+    /// A feature which enables a feature of an optional dependency with `?/`
+    WeakDependencyFeature,
+    /// A value which is inherited from the workspace with `workspace = true`
+    WorkspaceInheritance,
+    /// The `[lints]` table
+    Lints,
+}
+
+impl ManifestFeature {
+    /// The first Rust release whose cargo can read manifests which use this feature.
+    pub fn readable_since(&self) -> semver::Version {
+        match self {
+            Self::Resolver2 => semver::Version::new(1, 51, 0),
+            Self::CustomProfile => semver::Version::new(1, 57, 0),
+            Self::ProfileStrip => semver::Version::new(1, 59, 0),
+            Self::NamespacedFeature => semver::Version::new(1, 60, 0),
+            Self::WeakDependencyFeature => semver::Version::new(1, 60, 0),
+            Self::WorkspaceInheritance => semver::Version::new(1, 64, 0),
+            Self::Lints => semver::Version::new(1, 74, 0),
+        }
+    }
+}
+
+impl fmt::Display for ManifestFeature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Resolver2 => write!(f, "the second feature resolver"),
+            Self::CustomProfile => write!(f, "a custom profile"),
+            Self::ProfileStrip => write!(f, "the strip profile option"),
+            Self::NamespacedFeature => write!(f, "a namespaced feature"),
+            Self::WeakDependencyFeature => write!(f, "a weak dependency feature"),
+            Self::WorkspaceInheritance => write!(f, "workspace inheritance"),
+            Self::Lints => write!(f, "the lints table"),
+        }
+    }
+}
+
+/// A manifest feature, and the key of the manifest at which it is used.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ManifestRequirement {
+    pub feature: ManifestFeature,
+    /// The dotted key, e.g. `features.serde`
+    pub key: String,
+}
+
+impl ManifestRequirement {
+    fn new(feature: ManifestFeature, key: impl Into<String>) -> Self {
+        Self {
+            feature,
+            key: key.into(),
+        }
+    }
+}
+
+/// Detect the manifest feature of the manifest at the given path, which requires the most recent
+/// cargo, or `None` if it uses none of them. A manifest which can't be parsed is left for cargo to
+/// judge, and also gives `None`.
+pub fn probe(path: &Path) -> TResult<Option<ManifestRequirement>> {
+    let contents = std::fs::read_to_string(path).map_err(|error| CargoMSRVError::Io {
+        error,
+        source: IoErrorSource::ReadFile(path.to_path_buf()),
+    })?;
+
+    Ok(contents
+        .parse::<Document>()
+        .ok()
+        .and_then(|document| most_recent_requirement(&document)))
+}
+
+/// The manifest feature which is readable by the least amount of cargo releases.
+pub fn most_recent_requirement(document: &Document) -> Option<ManifestRequirement> {
+    requirements(document).into_iter().max_by(|lhs, rhs| {
+        lhs.feature
+            .readable_since()
+            .cmp(&rhs.feature.readable_since())
+    })
+}
+
+/// Each manifest feature used by the given manifest, which can't be read by every cargo release.
+pub fn requirements(document: &Document) -> Vec<ManifestRequirement> {
+    let mut found = Vec::new();
+    let root = document.as_table();
+
+    if root.contains_key("lints") {
+        found.push(ManifestRequirement::new(ManifestFeature::Lints, "lints"));
+    }
+
+    for section in ["package", "workspace"] {
+        let resolver = root
+            .get(section)
+            .and_then(Item::as_table_like)
+            .and_then(|table| table.get("resolver"))
+            .and_then(Item::as_str);
+
+        if resolver == Some("2") {
+            let key = format!("{}.resolver", section);
+            found.push(ManifestRequirement::new(ManifestFeature::Resolver2, key));
+        }
+    }
+
+    if let Some(workspace) = root.get("workspace").and_then(Item::as_table_like) {
+        for table in ["package", "dependencies"] {
+            if workspace.contains_key(table) {
+                let key = format!("workspace.{}", table);
+                found.push(ManifestRequirement::new(
+                    ManifestFeature::WorkspaceInheritance,
+                    key,
+                ));
+            }
+        }
+
+        if workspace.contains_key("lints") {
+            found.push(ManifestRequirement::new(
+                ManifestFeature::Lints,
+                "workspace.lints",
+            ));
+        }
+    }
+
+    if let Some(package) = root.get("package").and_then(Item::as_table_like) {
+        inherited_keys(package, "package", &mut found);
+    }
+
+    for (section, dependencies) in dependency_tables(root) {
+        inherited_keys(dependencies, &section, &mut found);
+    }
+
+    if let Some(profiles) = root.get("profile").and_then(Item::as_table_like) {
+        for (name, profile) in profiles.iter() {
+            let profile = match profile.as_table_like() {
+                Some(profile) => profile,
+                None => continue,
+            };
+
+            if profile.contains_key("inherits") {
+                let key = format!("profile.{}.inherits", name);
+                found.push(ManifestRequirement::new(
+                    ManifestFeature::CustomProfile,
+                    key,
+                ));
+            }
+
+            if profile.contains_key("strip") {
+                let key = format!("profile.{}.strip", name);
+                found.push(ManifestRequirement::new(ManifestFeature::ProfileStrip, key));
+            }
+        }
+    }
+
+    if let Some(features) = root.get("features").and_then(Item::as_table_like) {
+        for (name, enables) in features.iter() {
+            let enables = enables
+                .as_array()
+                .into_iter()
+                .flat_map(|array| array.iter());
+
+            for value in enables.filter_map(|value| value.as_str()) {
+                let feature = if value.starts_with("dep:") {
+                    ManifestFeature::NamespacedFeature
+                } else if value.contains("?/") {
+                    ManifestFeature::WeakDependencyFeature
+                } else {
+                    continue;
+                };
+
+                let key = format!("features.{}", name);
+                found.push(ManifestRequirement::new(feature, key));
+            }
+        }
+    }
+
+    found
+}
+
+/// The dependency tables of the manifest, including those of the `[target]` tables, by their
+/// dotted key.
+fn dependency_tables(root: &Table) -> Vec<(String, &dyn TableLike)> {
+    const KINDS: [&str; 3] = ["dependencies", "dev-dependencies", "build-dependencies"];
+
+    let mut tables = Vec::new();
+
+    for kind in KINDS {
+        if let Some(table) = root.get(kind).and_then(Item::as_table_like) {
+            tables.push((kind.to_string(), table));
+        }
+    }
+
+    let targets = root.get("target").and_then(Item::as_table_like);
+
+    for (target, table) in targets.into_iter().flat_map(|targets| targets.iter()) {
+        let table = match table.as_table_like() {
+            Some(table) => table,
+            None => continue,
+        };
+
+        for kind in KINDS {
+            if let Some(dependencies) = table.get(kind).and_then(Item::as_table_like) {
+                tables.push((format!("target.{}.{}", target, kind), dependencies));
+            }
+        }
+    }
+
+    tables
+}
+
+/// The keys of the given table whose value is inherited from the workspace, e.g.
+/// `version.workspace = true`.
+fn inherited_keys(table: &dyn TableLike, prefix: &str, found: &mut Vec<ManifestRequirement>) {
+    for (name, value) in table.iter() {
+        let inherited = value
+            .as_table_like()
+            .and_then(|value| value.get("workspace"))
+            .and_then(Item::as_bool)
+            .unwrap_or_default();
+
+        if inherited {
+            let key = format!("{}.{}.workspace", prefix, name);
+            found.push(ManifestRequirement::new(
+                ManifestFeature::WorkspaceInheritance,
+                key,
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn most_recent(contents: &str) -> Option<(ManifestFeature, String)> {
+        let document = contents.parse::<Document>().unwrap();
+
+        most_recent_requirement(&document).map(|requirement| (requirement.feature, requirement.key))
+    }
+
+    #[yare::parameterized(
+        lints = { "[lints.rust]\nunsafe_code = \"forbid\"\n", ManifestFeature::Lints, "lints" },
+        resolver = { "[package]\nname = \"a\"\nresolver = \"2\"\n", ManifestFeature::Resolver2, "package.resolver" },
+        workspace_resolver = { "[workspace]\nresolver = \"2\"\n", ManifestFeature::Resolver2, "workspace.resolver" },
+        workspace_lints = { "[workspace.lints.rust]\nunsafe_code = \"forbid\"\n", ManifestFeature::Lints, "workspace.lints" },
+        workspace_package = { "[workspace.package]\nversion = \"0.1.0\"\n", ManifestFeature::WorkspaceInheritance, "workspace.package" },
+        inherited_field = { "[package]\nname = \"a\"\nversion.workspace = true\n", ManifestFeature::WorkspaceInheritance, "package.version.workspace" },
+        inherited_dependency = { "[dev-dependencies]\nserde = { workspace = true }\n", ManifestFeature::WorkspaceInheritance, "dev-dependencies.serde.workspace" },
+        target_dependency = { "[target.'cfg(unix)'.dependencies]\nlibc = { workspace = true }\n", ManifestFeature::WorkspaceInheritance, "target.cfg(unix).dependencies.libc.workspace" },
+        custom_profile = { "[profile.ci]\ninherits = \"release\"\n", ManifestFeature::CustomProfile, "profile.ci.inherits" },
+        strip = { "[profile.release]\nstrip = true\n", ManifestFeature::ProfileStrip, "profile.release.strip" },
+        namespaced = { "[features]\nserde = [\"dep:serde\"]\n", ManifestFeature::NamespacedFeature, "features.serde" },
+        weak = { "[features]\nstd = [\"serde?/std\"]\n", ManifestFeature::WeakDependencyFeature, "features.std" },
+    )]
+    fn detected(contents: &str, feature: ManifestFeature, key: &str) {
+        assert_eq!(most_recent(contents), Some((feature, key.to_string())));
+    }
+
+    #[yare::parameterized(
+        plain = { "[package]\nname = \"a\"\nversion = \"0.1.0\"\n" },
+        resolver_1 = { "[package]\nname = \"a\"\nresolver = \"1\"\n" },
+        features = { "[features]\ndefault = [\"std\"]\nstd = [\"serde/std\"]\n" },
+        dependency = { "[dependencies]\nserde = { version = \"1\", features = [\"derive\"] }\n" },
+        not_inherited = { "[dependencies]\nserde = { workspace = false }\n" },
+    )]
+    fn none_detected(contents: &str) {
+        assert_eq!(most_recent(contents), None);
+    }
+
+    #[test]
+    fn most_recent_of_many() {
+        let contents = r#"[package]
+name = "a"
+resolver = "2"
+
+[features]
+serde = ["dep:serde"]
+
+[lints.clippy]
+all = "warn"
+"#;
+
+        assert_eq!(
+            most_recent(contents),
+            Some((ManifestFeature::Lints, "lints".to_string()))
+        );
+    }
+}
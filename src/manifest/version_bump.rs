@@ -0,0 +1,228 @@
+//! Suggests the next version of a crate, when the MSRV which was found is more recent than the MSRV
+//! which is declared in its Cargo manifest.
+//!
+//! Whether raising the MSRV is a breaking change is a policy of the project, configured with the
+//! `package.metadata.cargo-msrv.msrv-is-breaking` key. By default, raising the MSRV is considered to
+//! be a semver-minor change.
+
+use crate::error::{CargoMSRVError, IoErrorSource, TResult};
+use crate::manifest::bare_version::BareVersion;
+use crate::manifest::find_minimum_rust_version;
+use crate::semver;
+use std::path::Path;
+use toml_edit::{Document, Item};
+
+/// The semver change of the crate version, caused by raising the MSRV.
+#[derive(
+    Clone, Copy, Debug, Eq, PartialEq, serde::Serialize, serde::Deserialize, schemars::JsonSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum BumpKind {
+    Minor,
+    Major,
+}
+
+impl BumpKind {
+    /// The next version of the crate, which is compatible with the `current` version for a minor
+    /// change, and incompatible for a major change. Like cargo, the left-most non-zero component
+    /// is the major version, e.g. a major change of `0.3.1` gives `0.4.0`.
+    pub fn next_version(&self, current: &semver::Version) -> semver::Version {
+        let (major, minor, patch) = (current.major, current.minor, current.patch);
+
+        match (self, major, minor) {
+            (Self::Major, 0, 0) => semver::Version::new(0, 0, patch + 1),
+            (Self::Major, 0, _) => semver::Version::new(0, minor + 1, 0),
+            (Self::Major, _, _) => semver::Version::new(major + 1, 0, 0),
+            (Self::Minor, 0, _) => semver::Version::new(0, minor, patch + 1),
+            (Self::Minor, _, _) => semver::Version::new(major, minor + 1, 0),
+        }
+    }
+}
+
+impl From<BumpKind> for &'static str {
+    fn from(kind: BumpKind) -> Self {
+        match kind {
+            BumpKind::Minor => "minor",
+            BumpKind::Major => "major",
+        }
+    }
+}
+
+/// The suggested version bump of the crate, since the MSRV is raised.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct VersionBump {
+    /// The MSRV which is declared in the Cargo manifest
+    pub declared_msrv: BareVersion,
+    pub kind: BumpKind,
+    /// The version of the crate, as declared in the Cargo manifest
+    pub current_version: semver::Version,
+    pub suggested_version: semver::Version,
+}
+
+/// The values of the Cargo manifest which decide how the crate version is bumped.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BumpPolicy {
+    crate_version: semver::Version,
+    declared_msrv: BareVersion,
+    msrv_is_breaking: bool,
+}
+
+impl BumpPolicy {
+    pub fn new(
+        crate_version: semver::Version,
+        declared_msrv: BareVersion,
+        msrv_is_breaking: bool,
+    ) -> Self {
+        Self {
+            crate_version,
+            declared_msrv,
+            msrv_is_breaking,
+        }
+    }
+
+    /// Read the policy from the `Cargo.toml` manifest at the given path. Returns `None` if the
+    /// manifest doesn't declare both a crate version and an MSRV, since there is nothing to bump
+    /// from then.
+    pub fn read(cargo_toml: &Path) -> TResult<Option<Self>> {
+        let contents = std::fs::read_to_string(cargo_toml).map_err(|error| CargoMSRVError::Io {
+            error,
+            source: IoErrorSource::ReadFile(cargo_toml.to_path_buf()),
+        })?;
+        let document = contents
+            .parse::<Document>()
+            .map_err(CargoMSRVError::ParseToml)?;
+
+        Self::from_document(&document)
+    }
+
+    fn from_document(document: &Document) -> TResult<Option<Self>> {
+        let package = document.as_table().get("package").and_then(Item::as_table);
+
+        // A version which is inherited from the workspace isn't a string
+        let crate_version = package
+            .and_then(|package| package.get("version"))
+            .and_then(Item::as_str)
+            .and_then(|version| version.parse::<semver::Version>().ok());
+
+        let (crate_version, declared_msrv) =
+            match (crate_version, find_minimum_rust_version(document)) {
+                (Some(version), Some(msrv)) => (version, msrv.parse()?),
+                _ => return Ok(None),
+            };
+
+        let msrv_is_breaking = package
+            .and_then(|package| package.get("metadata"))
+            .and_then(Item::as_table_like)
+            .and_then(|metadata| metadata.get("cargo-msrv"))
+            .and_then(Item::as_table_like)
+            .and_then(|cargo_msrv| cargo_msrv.get("msrv-is-breaking"))
+            .map(|value| {
+                value.as_bool().ok_or_else(|| {
+                    CargoMSRVError::InvalidConfig(
+                        "Expected true or false for 'package.metadata.cargo-msrv.msrv-is-breaking'"
+                            .to_string(),
+                    )
+                })
+            })
+            .transpose()?
+            .unwrap_or_default();
+
+        Ok(Some(Self::new(
+            crate_version,
+            declared_msrv,
+            msrv_is_breaking,
+        )))
+    }
+
+    /// The version bump of the crate, if the found `msrv` is more recent than the declared MSRV.
+    pub fn suggest(&self, msrv: &semver::Version) -> Option<VersionBump> {
+        if self.declared_msrv.is_at_most(msrv) {
+            return None;
+        }
+
+        let kind = if self.msrv_is_breaking {
+            BumpKind::Major
+        } else {
+            BumpKind::Minor
+        };
+
+        Some(VersionBump {
+            declared_msrv: self.declared_msrv.clone(),
+            kind,
+            current_version: self.crate_version.clone(),
+            suggested_version: kind.next_version(&self.crate_version),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(contents: &str) -> TResult<Option<BumpPolicy>> {
+        BumpPolicy::from_document(&contents.parse::<Document>().unwrap())
+    }
+
+    #[yare::parameterized(
+        major = { BumpKind::Major, "1.2.3", "2.0.0" },
+        major_unstable = { BumpKind::Major, "0.3.1", "0.4.0" },
+        major_initial = { BumpKind::Major, "0.0.3", "0.0.4" },
+        minor = { BumpKind::Minor, "1.2.3", "1.3.0" },
+        minor_unstable = { BumpKind::Minor, "0.3.1", "0.3.2" },
+        pre_release = { BumpKind::Minor, "1.2.3-alpha.1", "1.3.0" },
+    )]
+    fn next_version(kind: BumpKind, current: &str, expected: &str) {
+        let current = current.parse().unwrap();
+
+        assert_eq!(kind.next_version(&current), expected.parse().unwrap());
+    }
+
+    #[yare::parameterized(
+        not_breaking = { "", BumpKind::Minor, "0.4.3" },
+        breaking = { "[package.metadata.cargo-msrv]\nmsrv-is-breaking = true\n", BumpKind::Major, "0.5.0" },
+        explicitly_not_breaking = { "[package.metadata.cargo-msrv]\nmsrv-is-breaking = false\n", BumpKind::Minor, "0.4.3" },
+    )]
+    fn suggested_bump(metadata: &str, kind: BumpKind, suggested: &str) {
+        let contents = format!(
+            "[package]\nname = \"a\"\nversion = \"0.4.2\"\nrust-version = \"1.56\"\n\n{}",
+            metadata
+        );
+        let policy = policy(&contents).unwrap().unwrap();
+        let bump = policy.suggest(&semver::Version::new(1, 60, 0)).unwrap();
+
+        assert_eq!(bump.kind, kind);
+        assert_eq!(bump.suggested_version, suggested.parse().unwrap());
+        assert_eq!(bump.declared_msrv, BareVersion::TwoComponents(1, 56));
+    }
+
+    #[yare::parameterized(
+        same_release = { "1.56", semver::Version::new(1, 56, 0) },
+        same_minor = { "1.56", semver::Version::new(1, 56, 1) },
+        lower = { "1.56.0", semver::Version::new(1, 55, 0) },
+    )]
+    fn no_bump(declared: &str, msrv: semver::Version) {
+        let contents = format!(
+            "[package]\nname = \"a\"\nversion = \"1.0.0\"\nrust-version = \"{}\"\n",
+            declared
+        );
+        let policy = policy(&contents).unwrap().unwrap();
+
+        assert_eq!(policy.suggest(&msrv), None);
+    }
+
+    #[yare::parameterized(
+        no_msrv = { "[package]\nname = \"a\"\nversion = \"1.0.0\"\n" },
+        inherited_version = { "[package]\nname = \"a\"\nversion.workspace = true\nrust-version = \"1.56\"\n" },
+    )]
+    fn no_policy(contents: &str) {
+        assert_eq!(policy(contents).unwrap(), None);
+    }
+
+    #[test]
+    fn invalid_policy() {
+        let contents = "[package]\nname = \"a\"\nversion = \"1.0.0\"\nrust-version = \"1.56\"\n\n[package.metadata.cargo-msrv]\nmsrv-is-breaking = \"yes\"\n";
+
+        assert!(policy(contents).is_err());
+    }
+}
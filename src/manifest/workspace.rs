@@ -0,0 +1,192 @@
+use std::path::{Path, PathBuf};
+
+use toml_edit::{Document, Item};
+
+use crate::error::{CargoMSRVError, IoErrorSource, TResult};
+use crate::manifest::{CargoManifestParser, TomlParser};
+
+/// Whether the manifest declares a workspace, i.e. has a `[workspace]` table.
+pub(crate) fn is_workspace(manifest: &Document) -> bool {
+    manifest.as_table().contains_key("workspace")
+}
+
+/// Whether the manifest is a virtual manifest: it declares a workspace, but no package.
+pub(crate) fn is_virtual(manifest: &Document) -> bool {
+    is_workspace(manifest) && !manifest.as_table().contains_key("package")
+}
+
+/// The root of the workspace to which the crate in `crate_dir` belongs, if any.
+pub(crate) fn workspace_root(crate_dir: &Path) -> Option<PathBuf> {
+    crate_dir
+        .ancestors()
+        .find(|dir| {
+            std::fs::read_to_string(dir.join("Cargo.toml"))
+                .ok()
+                .and_then(|contents| {
+                    CargoManifestParser::default()
+                        .parse::<Document>(&contents)
+                        .ok()
+                })
+                .map_or(false, |manifest| is_workspace(&manifest))
+        })
+        .map(Path::to_path_buf)
+}
+
+/// The manifests of the members of the workspace declared by the given root manifest, as listed by
+/// `workspace.members`, less those listed by `workspace.exclude`. The root package itself is not
+/// included.
+///
+/// A member is either a path relative to the workspace root, or a pattern of which the last
+/// component contains `*` wildcards, e.g. `crates/*`. Directories without a Cargo manifest are
+/// skipped.
+pub(crate) fn member_manifests(root: &Path, manifest: &Document) -> TResult<Vec<PathBuf>> {
+    let excluded = paths(manifest, "exclude")
+        .map(|path| root.join(path))
+        .collect::<Vec<_>>();
+
+    let mut members = Vec::new();
+
+    for pattern in paths(manifest, "members") {
+        for member in expand(root, pattern)? {
+            let manifest_path = member.join("Cargo.toml");
+
+            if member != root
+                && manifest_path.is_file()
+                && !excluded.iter().any(|excluded| member.starts_with(excluded))
+                && !members.contains(&manifest_path)
+            {
+                members.push(manifest_path);
+            }
+        }
+    }
+
+    Ok(members)
+}
+
+fn paths<'m>(manifest: &'m Document, key: &'m str) -> impl Iterator<Item = &'m str> {
+    manifest
+        .as_table()
+        .get("workspace")
+        .and_then(|workspace| workspace.get(key))
+        .and_then(Item::as_array)
+        .into_iter()
+        .flat_map(|paths| paths.iter().filter_map(|path| path.as_str()))
+}
+
+/// The directories matched by a member path or pattern.
+fn expand(root: &Path, pattern: &str) -> TResult<Vec<PathBuf>> {
+    let pattern = Path::new(pattern);
+
+    let name = match pattern.file_name().and_then(|name| name.to_str()) {
+        Some(name) if name.contains('*') => name,
+        _ => return Ok(vec![root.join(pattern)]),
+    };
+
+    let parent = root.join(pattern.parent().unwrap_or_else(|| Path::new("")));
+
+    if !parent.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let entries = std::fs::read_dir(&parent).map_err(|error| CargoMSRVError::Io {
+        error,
+        source: IoErrorSource::ReadDir(parent.clone()),
+    })?;
+
+    let mut directories = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|file_name| file_name.to_str())
+                .map_or(false, |file_name| wildcard_match(name, file_name))
+        })
+        .collect::<Vec<_>>();
+
+    directories.sort();
+
+    Ok(directories)
+}
+
+/// Match a name against a pattern in which `*` matches any sequence of characters.
+pub(crate) fn wildcard_match(pattern: &str, name: &str) -> bool {
+    match pattern.split_once('*') {
+        None => pattern == name,
+        Some((prefix, rest)) => {
+            name.starts_with(prefix)
+                && (prefix.len()..=name.len())
+                    .filter(|&i| name.is_char_boundary(i))
+                    .any(|i| wildcard_match(rest, &name[i..]))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_dir::{DirBuilder, TestDir};
+
+    fn package(tmp: &TestDir, dir: &str) {
+        std::fs::create_dir_all(tmp.path(dir)).unwrap();
+        std::fs::write(
+            tmp.path(dir).join("Cargo.toml"),
+            "[package]\nname = \"member\"\n",
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn members_and_patterns() {
+        let tmp = TestDir::temp();
+        package(&tmp, "cli");
+        package(&tmp, "crates/core");
+        package(&tmp, "crates/macros");
+        package(&tmp, "crates/legacy");
+        std::fs::create_dir_all(tmp.path("crates/assets")).unwrap();
+
+        let manifest = r#"
+            [workspace]
+            members = ["cli", "crates/*", "missing"]
+            exclude = ["crates/legacy"]
+            "#
+        .parse::<Document>()
+        .unwrap();
+
+        let members = member_manifests(tmp.root(), &manifest).unwrap();
+
+        assert_eq!(
+            members,
+            vec![
+                tmp.path("cli/Cargo.toml"),
+                tmp.path("crates/core/Cargo.toml"),
+                tmp.path("crates/macros/Cargo.toml"),
+            ]
+        );
+    }
+
+    #[yare::parameterized(
+        star = { "*", "core", true },
+        prefix = { "msrv-*", "msrv-core", true },
+        prefix_mismatch = { "msrv-*", "core", false },
+        suffix = { "*-macros", "core-macros", true },
+        infix = { "a*c", "abbc", true },
+        literal = { "core", "core", true },
+        literal_mismatch = { "core", "cores", false },
+    )]
+    fn wildcard(pattern: &str, name: &str, expected: bool) {
+        assert_eq!(wildcard_match(pattern, name), expected);
+    }
+
+    #[yare::parameterized(
+        virtual_manifest = { "[workspace]\nmembers = []\n", true, true },
+        root_package = { "[package]\nname = \"a\"\n\n[workspace]\n", true, false },
+        package = { "[package]\nname = \"a\"\n", false, false },
+    )]
+    fn kind_of_manifest(contents: &str, workspace: bool, virtual_manifest: bool) {
+        let manifest = contents.parse::<Document>().unwrap();
+
+        assert_eq!(is_workspace(&manifest), workspace);
+        assert_eq!(is_virtual(&manifest), virtual_manifest);
+    }
+}
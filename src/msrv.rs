@@ -1,3 +1,11 @@
+use rust_releases::{Release, ReleaseIndex};
+
+use crate::config::Config;
+use crate::error::TResult;
+use crate::manifest::bare_version::BareVersion;
+use crate::reporter::event::VersionResolved;
+use crate::reporter::Reporter;
+use crate::semver;
 use crate::toolchain::OwnedToolchainSpec;
 
 /// An enum to represent the minimal compatibility
@@ -22,3 +30,73 @@ impl MinimumSupportedRustVersion {
         panic!("Unable to unwrap MinimalCompatibility (CapableToolchain::version)")
     }
 }
+
+/// The most recent patch release of the minor version of `version` in the index, if it is more
+/// recent than `version`, e.g. `1.64.1` for `1.64.0`.
+pub(crate) fn newer_patch_release<'index>(
+    index: &'index ReleaseIndex,
+    version: &semver::Version,
+) -> Option<&'index semver::Version> {
+    index
+        .releases()
+        .iter()
+        .map(Release::version)
+        .filter(|release| release.major == version.major && release.minor == version.minor)
+        .max()
+        .filter(|&newest| newest > version)
+}
+
+/// The release which the `msrv` matches, or, when it matches none of the `available` releases,
+/// the release to which it's resolved with `--version-resolution`, which is reported.
+pub(crate) fn resolve_msrv<'s, I>(
+    config: &Config,
+    reporter: &impl Reporter,
+    msrv: &BareVersion,
+    available: I,
+) -> TResult<&'s semver::Version>
+where
+    I: Iterator<Item = &'s semver::Version> + Clone,
+{
+    let error = match msrv.try_to_semver(available.clone()) {
+        Ok(version) => return Ok(version),
+        Err(error) => error,
+    };
+
+    let resolution = config.version_resolution();
+
+    match msrv.resolve_unmatched(available, resolution) {
+        Some(version) => {
+            info!(%msrv, %version, %resolution, "resolved msrv which matches no release");
+            reporter.report_event(VersionResolved::new(
+                msrv.clone(),
+                version.clone(),
+                resolution,
+            ))?;
+
+            Ok(version)
+        }
+        None => Err(error.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[yare::parameterized(
+        newer_patch = { semver::Version::new(1, 64, 0), Some(semver::Version::new(1, 64, 1)) },
+        newest_patch = { semver::Version::new(1, 64, 1), None },
+        no_patch_releases = { semver::Version::new(1, 63, 0), None },
+        not_in_index = { semver::Version::new(1, 99, 0), None },
+    )]
+    fn newer_patch(version: semver::Version, expected: Option<semver::Version>) {
+        let index = [(1, 65, 0), (1, 64, 1), (1, 64, 0), (1, 63, 0)]
+            .iter()
+            .map(|&(major, minor, patch)| {
+                Release::new_stable(semver::Version::new(major, minor, patch))
+            })
+            .collect::<ReleaseIndex>();
+
+        assert_eq!(newer_patch_release(&index, &version), expected.as_ref());
+    }
+}
@@ -2,10 +2,11 @@
 //!
 //! [`check`]: crate::check::Check
 
+use crate::reporter::event::{CheckTiming, EncodingNote};
 use crate::toolchain::OwnedToolchainSpec;
 use rust_releases::semver;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub enum Outcome {
     Success(SuccessOutcome),
     Failure(FailureOutcome),
@@ -13,16 +14,50 @@ pub enum Outcome {
 
 impl Outcome {
     pub fn new_success(toolchain_spec: OwnedToolchainSpec) -> Self {
-        Self::Success(SuccessOutcome { toolchain_spec })
+        Self::Success(SuccessOutcome {
+            toolchain_spec,
+            timing: None,
+        })
     }
 
     pub fn new_failure(toolchain_spec: OwnedToolchainSpec, error_message: String) -> Self {
         Self::Failure(FailureOutcome {
             toolchain_spec,
             error_message,
+            encoding_note: None,
+            timing: None,
         })
     }
 
+    /// Note how long it took to run the check command, so the layers around the check can report
+    /// it, e.g. summed over the retries of the check.
+    pub(crate) fn with_timing(self, timing: Option<CheckTiming>) -> Self {
+        match self {
+            Self::Success(outcome) => Self::Success(SuccessOutcome { timing, ..outcome }),
+            Self::Failure(outcome) => Self::Failure(FailureOutcome { timing, ..outcome }),
+        }
+    }
+
+    /// How long it took to run the check command, if it was run.
+    pub(crate) fn timing(&self) -> Option<&CheckTiming> {
+        match self {
+            Self::Success(outcome) => outcome.timing.as_ref(),
+            Self::Failure(outcome) => outcome.timing.as_ref(),
+        }
+    }
+
+    /// Note that the error message of a failure was decoded lossily, since the output of the
+    /// check command was not valid UTF-8.
+    pub(crate) fn with_encoding_note(self, note: Option<EncodingNote>) -> Self {
+        match self {
+            Self::Failure(outcome) => Self::Failure(FailureOutcome {
+                encoding_note: note,
+                ..outcome
+            }),
+            success => success,
+        }
+    }
+
     pub fn is_success(&self) -> bool {
         match self {
             Self::Success { .. } => true,
@@ -48,10 +83,13 @@ impl Outcome {
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct SuccessOutcome {
     pub(crate) toolchain_spec: OwnedToolchainSpec,
+    pub(crate) timing: Option<CheckTiming>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct FailureOutcome {
     pub(crate) toolchain_spec: OwnedToolchainSpec,
     pub(crate) error_message: String,
+    pub(crate) encoding_note: Option<EncodingNote>,
+    pub(crate) timing: Option<CheckTiming>,
 }
@@ -0,0 +1,540 @@
+//! The external tools on which cargo-msrv depends. These are checked before an action is run, so
+//! all missing or outdated tools are reported at once, instead of the action failing halfway
+//! through, because a process could not be spawned.
+
+use crate::check::is_sccache;
+use crate::config::{Action, BinPolicy, CheckRunner, Config};
+use crate::error::{CargoMSRVError, IoErrorSource, TResult};
+use crate::reporter::event::{MissingPrerequisite, MissingPrerequisites, PrerequisiteInstall};
+use crate::reporter::Reporter;
+use crate::semver;
+use once_cell::sync::OnceCell;
+use std::env;
+use std::io::{self, BufRead, Write};
+use std::path::PathBuf;
+use std::process::Command;
+
+const RUSTUP_INSTALL_HINT: &str = "https://rustup.rs";
+const CROSS_INSTALL_HINT: &str = "https://github.com/cross-rs/cross#installation";
+const SCCACHE_INSTALL_HINT: &str = "https://github.com/mozilla/sccache#installation";
+const RUSTC_WRAPPER_HINT: &str =
+    "https://doc.rust-lang.org/cargo/reference/config.html#buildrustc-wrapper";
+
+/// The official installer of rustup, which also installs the stable toolchain, for unix-like
+/// operating systems. The installer doesn't ask for confirmation itself, since cargo-msrv asks for
+/// it before the command is run.
+const RUSTUP_INSTALL_SCRIPT: &str =
+    "curl --proto '=https' --tlsv1.2 -sSf https://sh.rustup.rs | sh -s -- -y --profile minimal";
+const RUSTUP_INSTALL_WINGET: &str = "winget install --exact --id Rustlang.Rustup";
+
+/// The directory to which rustup, and the proxies of cargo and rustc, were installed with
+/// `--setup`, if they were. The installer only adds it to the PATH of shells which are started
+/// afterwards, so the installed tools are run by their path instead.
+static INSTALLED_BIN_DIR: OnceCell<PathBuf> = OnceCell::new();
+
+/// An external tool which is required to run an action.
+#[derive(Debug, PartialEq)]
+struct Prerequisite<'tool> {
+    tool: &'tool str,
+    install_hint: &'static str,
+    minimum_version: Option<semver::Version>,
+}
+
+/// Whether a tool can be run, and the version it reports with `<tool> --version`, if that version
+/// can be parsed.
+#[derive(Clone, Debug, PartialEq)]
+enum Availability {
+    NotFound,
+    Found(Option<semver::Version>),
+}
+
+/// Check that the external tools on which the configured action depends are available, and report
+/// those which are missing or outdated.
+///
+/// With `--setup`, the missing tools which can be installed automatically are installed first.
+/// Returns whether a tool was installed.
+pub(crate) fn check_prerequisites(config: &Config, reporter: &impl Reporter) -> TResult<bool> {
+    let prerequisites = prerequisites(config);
+    let mut missing = missing_prerequisites(&prerequisites, query_availability);
+
+    let setup = missing
+        .iter()
+        .filter(|prerequisite| config.setup() && prerequisite.setup())
+        .map(|prerequisite| prerequisite.tool().to_string())
+        .collect::<Vec<_>>();
+
+    for tool in &setup {
+        install(tool, config.assume_yes(), reporter)?;
+    }
+
+    if !setup.is_empty() {
+        missing = missing_prerequisites(&prerequisites, query_availability);
+    }
+
+    if missing.is_empty() {
+        return Ok(!setup.is_empty());
+    }
+
+    reporter.report_event(MissingPrerequisites::new(missing.clone()))?;
+
+    Err(CargoMSRVError::MissingPrerequisites { missing })
+}
+
+fn prerequisites(config: &Config) -> Vec<Prerequisite<'_>> {
+    let checks_edition_migration =
+        config.action() == Action::Edition && config.sub_command_config().edition().check_migration;
+    let runs_discovered =
+        config.action() == Action::Discover && config.sub_command_config().discover().run.is_some();
+    let verifies_history =
+        config.action() == Action::History && config.sub_command_config().history().verify;
+    let checks_toolchains = matches!(
+        config.action(),
+        Action::Find | Action::Verify | Action::Matrix | Action::Impact | Action::Lower
+    ) || runs_discovered
+        || verifies_history;
+    // with a remote check, the toolchains are installed by the remote execution service, while the
+    // crate is packaged locally with `cargo package`
+    let runs_remote_checks = checks_toolchains && config.remote_check().is_some();
+    let runs_toolchains = (checks_toolchains && !runs_remote_checks) || checks_edition_migration;
+    // the server installs the toolchains of its clients
+    let installs_toolchains = runs_toolchains || config.action() == Action::Serve;
+
+    // `cargo metadata` is used to resolve dependencies, and the targets of the crate
+    let runs_cargo_metadata = config.action() == Action::List
+        || (runs_toolchains && config.bin_policy() != BinPolicy::Ignore);
+
+    let mut prerequisites = Vec::new();
+
+    if installs_toolchains {
+        prerequisites.push(Prerequisite {
+            tool: "rustup",
+            install_hint: RUSTUP_INSTALL_HINT,
+            // The first release which supports `rustup install --profile minimal`
+            minimum_version: Some(semver::Version::new(1, 20, 0)),
+        });
+    }
+
+    if runs_toolchains && config.check_runner() == CheckRunner::Cross {
+        prerequisites.push(Prerequisite {
+            tool: "cross",
+            install_hint: CROSS_INSTALL_HINT,
+            minimum_version: None,
+        });
+    }
+
+    if let Some(wrapper) = config.rustc_wrapper().filter(|_| runs_toolchains) {
+        prerequisites.push(Prerequisite {
+            tool: wrapper,
+            install_hint: if is_sccache(wrapper) {
+                SCCACHE_INSTALL_HINT
+            } else {
+                RUSTC_WRAPPER_HINT
+            },
+            minimum_version: None,
+        });
+    }
+
+    if runs_cargo_metadata || runs_remote_checks {
+        prerequisites.push(Prerequisite {
+            tool: "cargo",
+            install_hint: RUSTUP_INSTALL_HINT,
+            minimum_version: None,
+        });
+    }
+
+    prerequisites
+}
+
+fn missing_prerequisites(
+    prerequisites: &[Prerequisite],
+    availability: impl Fn(&str) -> Availability,
+) -> Vec<MissingPrerequisite> {
+    prerequisites
+        .iter()
+        .filter_map(|prerequisite| {
+            let tool = prerequisite.tool;
+
+            match (availability(tool), &prerequisite.minimum_version) {
+                (Availability::NotFound, _) => {
+                    let missing = MissingPrerequisite::not_found(tool, prerequisite.install_hint);
+
+                    Some(match install_command(tool, env::consts::OS) {
+                        Some(command) => {
+                            missing.with_install_command(command, can_set_up(tool, env::consts::OS))
+                        }
+                        None => missing,
+                    })
+                }
+                (Availability::Found(Some(found)), Some(minimum)) if &found < minimum => {
+                    Some(MissingPrerequisite::outdated(
+                        tool,
+                        prerequisite.install_hint,
+                        minimum.clone(),
+                        found,
+                    ))
+                }
+                // A tool whose version can't be determined is given the benefit of the doubt
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// The command which installs the given tool on the given operating system, as named by
+/// [`std::env::consts::OS`], if the installation of the tool is known.
+fn install_command(tool: &str, os: &str) -> Option<&'static str> {
+    match (tool, os) {
+        // cargo is installed by rustup, as part of a toolchain
+        ("rustup" | "cargo", "windows") => Some(RUSTUP_INSTALL_WINGET),
+        ("rustup" | "cargo", _) => Some(RUSTUP_INSTALL_SCRIPT),
+        ("cross", _) => Some("cargo install cross"),
+        ("sccache", _) => Some("cargo install sccache"),
+        _ => None,
+    }
+}
+
+/// Whether the given tool is installed with `--setup`, when it can't be found. Only rustup is,
+/// since the other tools are installed with cargo, and only with its official installer.
+fn can_set_up(tool: &str, os: &str) -> bool {
+    tool == "rustup" && os != "windows"
+}
+
+/// Install the given tool with its install command, once the command is confirmed, and make it
+/// available to the processes which are spawned afterwards.
+fn install(tool: &str, assume_yes: bool, reporter: &impl Reporter) -> TResult<()> {
+    let command = match install_command(tool, env::consts::OS) {
+        Some(command) => command,
+        None => return Ok(()),
+    };
+
+    // A declined tool is reported as missing, like any other tool which can't be found
+    if !confirm_install(tool, command, assume_yes)? {
+        return Ok(());
+    }
+
+    reporter.report_event(PrerequisiteInstall::new(tool, command))?;
+
+    let failed = || CargoMSRVError::PrerequisiteInstall {
+        tool: tool.to_string(),
+        command: command.to_string(),
+    };
+
+    // The output is captured, since it would be interleaved with the user output otherwise
+    let output = Command::new("sh")
+        .args(&["-c", command])
+        .output()
+        .map_err(|error| {
+            info!(tool, %error, "unable to run install command");
+            failed()
+        })?;
+
+    info!(
+        tool,
+        stdout = %String::from_utf8_lossy(&output.stdout),
+        stderr = %String::from_utf8_lossy(&output.stderr),
+        "ran install command"
+    );
+
+    if !output.status.success() {
+        return Err(failed());
+    }
+
+    if let Some(bin) = cargo_bin_dir() {
+        let _ = INSTALLED_BIN_DIR.set(bin);
+    }
+
+    Ok(())
+}
+
+/// Whether the given install command may be run: it's confirmed up front with `--yes`, or at a
+/// prompt, when stdin is a terminal. Without a terminal to ask on, the command is not run.
+fn confirm_install(tool: &str, command: &str, assume_yes: bool) -> TResult<bool> {
+    if assume_yes {
+        return Ok(true);
+    }
+
+    if !atty::is(atty::Stream::Stdin) {
+        return Err(CargoMSRVError::PrerequisiteInstallUnconfirmed {
+            tool: tool.to_string(),
+            command: command.to_string(),
+        });
+    }
+
+    let stdin = io::stdin();
+    let question = format!("Install {} with '{}'?", tool, command);
+
+    ask(&question, &mut stdin.lock(), &mut io::stderr()).map_err(|error| CargoMSRVError::Io {
+        error,
+        source: IoErrorSource::ReadStdin,
+    })
+}
+
+/// Ask the given yes/no question on `output`, and read the answer from `input`. Only an explicit
+/// yes is taken as consent.
+fn ask(question: &str, input: &mut impl BufRead, output: &mut impl Write) -> io::Result<bool> {
+    write!(output, "{} [y/N] ", question)?;
+    output.flush()?;
+
+    let mut answer = String::new();
+    input.read_line(&mut answer)?;
+
+    Ok(matches!(
+        answer.trim().to_ascii_lowercase().as_str(),
+        "y" | "yes"
+    ))
+}
+
+/// rustup installs itself, and the proxies of cargo and rustc, to `$CARGO_HOME/bin`.
+fn cargo_bin_dir() -> Option<PathBuf> {
+    env::var_os("CARGO_HOME")
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|home| home.join(".cargo")))
+        .map(|cargo_home| cargo_home.join("bin"))
+}
+
+/// The path of the given tool, if it was installed with `--setup`. Tools which were installed
+/// beforehand are run from the PATH instead.
+pub(crate) fn installed_tool(tool: &str) -> Option<PathBuf> {
+    INSTALLED_BIN_DIR
+        .get()
+        .map(|bin| bin.join(tool))
+        .filter(|path| path.is_file())
+}
+
+fn query_availability(tool: &str) -> Availability {
+    let program = installed_tool(tool).map_or_else(|| tool.into(), PathBuf::into_os_string);
+
+    match Command::new(program).arg("--version").output() {
+        Ok(output) => Availability::Found(parse_version(&String::from_utf8_lossy(&output.stdout))),
+        Err(error) => {
+            info!(tool, %error, "unable to run prerequisite");
+            Availability::NotFound
+        }
+    }
+}
+
+/// Parse the version from the output of `<tool> --version`, e.g. `rustup 1.25.1 (bb60b1e89 2022-07-12)`.
+fn parse_version(output: &str) -> Option<semver::Version> {
+    let version = output.lines().next()?.split_whitespace().nth(1)?;
+
+    semver::Version::parse(version).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::discover::{DiscoverCmdConfig, DiscoverRun};
+    use crate::config::edition::EditionCmdConfig;
+    use crate::config::{ConfigBuilder, SubCommandConfig};
+
+    #[yare::parameterized(
+        rustup = { "rustup 1.25.1 (bb60b1e89 2022-07-12)", Some(semver::Version::new(1, 25, 1)) },
+        cargo_nightly = { "cargo 1.66.0-nightly (7e484fc1a 2022-10-27)", Some(semver::Version::parse("1.66.0-nightly").unwrap()) },
+        no_version = { "rustup", None },
+        empty = { "", None },
+    )]
+    fn version_of_tool(output: &str, expected: Option<semver::Version>) {
+        assert_eq!(parse_version(output), expected);
+    }
+
+    #[yare::parameterized(
+        find = { Action::Find, BinPolicy::Ignore, &["rustup"] },
+        find_with_bin_policy = { Action::Find, BinPolicy::Exclude, &["rustup", "cargo"] },
+        verify = { Action::Verify, BinPolicy::Ignore, &["rustup"] },
+        list = { Action::List, BinPolicy::Ignore, &["cargo"] },
+        show = { Action::Show, BinPolicy::Ignore, &[] },
+        config = { Action::Config, BinPolicy::Ignore, &[] },
+        releases = { Action::Releases, BinPolicy::Exclude, &[] },
+        matrix = { Action::Matrix, BinPolicy::Ignore, &["rustup"] },
+        policy = { Action::Policy, BinPolicy::Ignore, &[] },
+        validate = { Action::Validate, BinPolicy::Ignore, &[] },
+        last_failure = { Action::LastFailure, BinPolicy::Ignore, &[] },
+        impact = { Action::Impact, BinPolicy::Ignore, &["rustup"] },
+        lower = { Action::Lower, BinPolicy::Ignore, &["rustup"] },
+        schema = { Action::Schema, BinPolicy::Ignore, &[] },
+        serve = { Action::Serve, BinPolicy::Exclude, &["rustup"] },
+    )]
+    fn tools_of_action(action: Action, policy: BinPolicy, expected: &[&str]) {
+        let config = ConfigBuilder::new(action, "").bin_policy(policy).build();
+
+        let tools = prerequisites(&config)
+            .iter()
+            .map(|prerequisite| prerequisite.tool)
+            .collect::<Vec<_>>();
+
+        assert_eq!(tools, expected);
+    }
+
+    #[yare::parameterized(
+        rustup = { Action::Find, CheckRunner::Rustup, &["rustup"] },
+        cross = { Action::Verify, CheckRunner::Cross, &["rustup", "cross"] },
+        cross_without_toolchains = { Action::List, CheckRunner::Cross, &["cargo"] },
+    )]
+    fn tools_of_runner(action: Action, runner: CheckRunner, expected: &[&str]) {
+        let config = ConfigBuilder::new(action, "").check_runner(runner).build();
+
+        let tools = prerequisites(&config)
+            .iter()
+            .map(|prerequisite| prerequisite.tool)
+            .collect::<Vec<_>>();
+
+        assert_eq!(tools, expected);
+    }
+
+    #[yare::parameterized(
+        find = { Action::Find, &["cargo"] },
+        matrix = { Action::Matrix, &["cargo"] },
+        not_checking = { Action::Show, &[] },
+    )]
+    fn tools_of_remote_check(action: Action, expected: &[&str]) {
+        let config = ConfigBuilder::new(action, "")
+            .remote_check("http://localhost")
+            .build();
+
+        let tools = prerequisites(&config)
+            .iter()
+            .map(|prerequisite| prerequisite.tool)
+            .collect::<Vec<_>>();
+
+        assert_eq!(tools, expected);
+    }
+
+    #[yare::parameterized(
+        advice = { false, &[] },
+        check_migration = { true, &["rustup"] },
+    )]
+    fn tools_of_edition(check_migration: bool, expected: &[&str]) {
+        let config = ConfigBuilder::new(Action::Edition, "")
+            .sub_command_config(SubCommandConfig::EditionConfig(EditionCmdConfig {
+                check_migration,
+            }))
+            .build();
+
+        let tools = prerequisites(&config)
+            .iter()
+            .map(|prerequisite| prerequisite.tool)
+            .collect::<Vec<_>>();
+
+        assert_eq!(tools, expected);
+    }
+
+    #[yare::parameterized(
+        list_only = { None, &[] },
+        run_find = { Some(DiscoverRun::Find), &["rustup"] },
+        run_verify = { Some(DiscoverRun::Verify), &["rustup"] },
+    )]
+    fn tools_of_discover(run: Option<DiscoverRun>, expected: &[&str]) {
+        let config = ConfigBuilder::new(Action::Discover, "")
+            .sub_command_config(SubCommandConfig::DiscoverConfig(DiscoverCmdConfig { run }))
+            .build();
+
+        let tools = prerequisites(&config)
+            .iter()
+            .map(|prerequisite| prerequisite.tool)
+            .collect::<Vec<_>>();
+
+        assert_eq!(tools, expected);
+    }
+
+    #[yare::parameterized(
+        find = { Action::Find, &["rustup", "sccache"] },
+        verify = { Action::Verify, &["rustup", "sccache"] },
+        not_checking = { Action::Show, &[] },
+    )]
+    fn tools_of_rustc_wrapper(action: Action, expected: &[&str]) {
+        let config = ConfigBuilder::new(action, "")
+            .rustc_wrapper("sccache")
+            .build();
+
+        let tools = prerequisites(&config)
+            .iter()
+            .map(|prerequisite| prerequisite.tool)
+            .collect::<Vec<_>>();
+
+        assert_eq!(tools, expected);
+    }
+
+    fn rustup() -> Prerequisite<'static> {
+        Prerequisite {
+            tool: "rustup",
+            install_hint: RUSTUP_INSTALL_HINT,
+            minimum_version: Some(semver::Version::new(1, 20, 0)),
+        }
+    }
+
+    #[test]
+    fn not_found() {
+        let missing = missing_prerequisites(&[rustup()], |_| Availability::NotFound);
+
+        assert_eq!(missing.len(), 1);
+        assert_eq!(missing[0].tool(), "rustup");
+        assert_eq!(missing[0].install_hint(), RUSTUP_INSTALL_HINT);
+        assert_eq!(
+            missing[0].install_command(),
+            install_command("rustup", env::consts::OS)
+        );
+    }
+
+    #[yare::parameterized(
+        rustup_linux = { "rustup", "linux", Some(RUSTUP_INSTALL_SCRIPT), true },
+        rustup_macos = { "rustup", "macos", Some(RUSTUP_INSTALL_SCRIPT), true },
+        rustup_windows = { "rustup", "windows", Some(RUSTUP_INSTALL_WINGET), false },
+        cargo_linux = { "cargo", "linux", Some(RUSTUP_INSTALL_SCRIPT), false },
+        cross = { "cross", "linux", Some("cargo install cross"), false },
+        sccache = { "sccache", "windows", Some("cargo install sccache"), false },
+        unknown_wrapper = { "my-wrapper", "linux", None, false },
+    )]
+    fn installation(tool: &str, os: &str, command: Option<&str>, setup: bool) {
+        assert_eq!(install_command(tool, os), command);
+        assert_eq!(can_set_up(tool, os), setup);
+    }
+
+    #[test]
+    fn outdated() {
+        let missing = missing_prerequisites(&[rustup()], |_| {
+            Availability::Found(Some(semver::Version::new(1, 18, 3)))
+        });
+
+        assert_eq!(
+            missing,
+            vec![MissingPrerequisite::outdated(
+                "rustup",
+                RUSTUP_INSTALL_HINT,
+                semver::Version::new(1, 20, 0),
+                semver::Version::new(1, 18, 3),
+            )]
+        );
+    }
+
+    #[yare::parameterized(
+        recent = { Availability::Found(Some(semver::Version::new(1, 25, 1))) },
+        minimum = { Availability::Found(Some(semver::Version::new(1, 20, 0))) },
+        unknown_version = { Availability::Found(None) },
+    )]
+    fn available(availability: Availability) {
+        let missing = missing_prerequisites(&[rustup()], |_| availability.clone());
+
+        assert!(missing.is_empty());
+    }
+
+    #[yare::parameterized(
+        yes = { "y\n", true },
+        yes_in_full = { "Yes\n", true },
+        no = { "n\n", false },
+        empty = { "\n", false },
+        end_of_input = { "", false },
+    )]
+    fn consent(answer: &str, expected: bool) {
+        let mut prompt = Vec::new();
+
+        let consent = ask("Install rustup?", &mut answer.as_bytes(), &mut prompt).unwrap();
+
+        assert_eq!(consent, expected);
+        assert_eq!(prompt, b"Install rustup? [y/N] ");
+    }
+
+    #[test]
+    fn confirmed_up_front() {
+        assert!(confirm_install("rustup", RUSTUP_INSTALL_SCRIPT, true).unwrap());
+    }
+}
@@ -0,0 +1,746 @@
+//! Sources from which the index of Rust releases can be obtained.
+//!
+//! By default, cargo-msrv fetches the release index from one of the built-in sources (see
+//! [`ReleaseSource`]). Library consumers may register additional sources, for example an internal
+//! mirror which only returns the subset of releases approved for use within an organisation, by
+//! implementing [`ReleaseIndexSource`] and registering it with [`ReleaseIndexSources::register`].
+//! The registry is then passed to [`run_app_with_release_sources`]. When none of the registered
+//! sources can provide an index, cargo-msrv fails, instead of falling back to the built-in sources,
+//! which may include releases the registered sources leave out.
+//!
+//! When a GitHub token is configured, the Rust changelog is fetched through the GitHub API with
+//! authenticated requests instead, see [`GitHubChangelogSource`].
+//!
+//! Fetched indices are cached on disk, for the duration given by `--max-index-age`. Once a cached
+//! index is older, sources which support it revalidate it with a conditional request, see
+//! [`ReleaseIndexSource::fetch_if_modified`]. Sources which take more than one request to fetch
+//! an index persist their progress next to the cache, so an interrupted fetch is resumed by the
+//! next run, see [`ReleaseIndexSource::fetch_resumable`].
+//!
+//! The built-in sources are fetched on background threads. When the preferred source is
+//! unavailable, or doesn't respond within a few seconds, the next source is fetched concurrently,
+//! so the fallback doesn't wait for the preferred source to time out. The index of the preferred
+//! source is still used when it becomes available.
+//!
+//! [`run_app_with_release_sources`]: crate::run_app_with_release_sources
+
+use std::sync::mpsc::{self, Sender};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+use rust_releases::ReleaseIndex;
+
+use crate::cancellation::{CancellationToken, POLL_INTERVAL};
+use crate::config::ReleaseSource;
+use crate::error::CargoMSRVError;
+use crate::reporter::event::FetchIndexProgress;
+
+pub(crate) use cache::{cache_dir, CachedIndex, IndexCache};
+pub use cache::{Checkpoint, Fetched, Validators};
+pub use github::{AuthToken, GitHubChangelogSource, RustChangelogSource};
+#[cfg(feature = "rust-releases-dist-source")]
+pub use rust_dist::RustDistSource;
+
+mod cache;
+mod github;
+#[cfg(feature = "rust-releases-dist-source")]
+mod rust_dist;
+
+/// How long a built-in source may take to provide an index, before the next source is fetched
+/// concurrently.
+const FALLBACK_DELAY: Duration = Duration::from_secs(5);
+
+/// A source from which an index of Rust releases can be fetched.
+pub trait ReleaseIndexSource {
+    /// A name which identifies the source, e.g. in log messages.
+    fn name(&self) -> &str;
+
+    /// Fetch the index of (stable) Rust releases.
+    fn fetch(&self) -> Result<ReleaseIndex, CargoMSRVError>;
+
+    /// Fetch the index of (stable) Rust releases, unless it did not change since it was fetched
+    /// with the given validators, e.g. because the server responds with `304 Not Modified`.
+    ///
+    /// By default, the index is fetched unconditionally.
+    fn fetch_if_modified(&self, _validators: &Validators) -> Result<Fetched, CargoMSRVError> {
+        self.fetch().map(|index| Fetched::Modified {
+            index,
+            validators: Validators::default(),
+        })
+    }
+
+    /// Fetch the index, like [`ReleaseIndexSource::fetch_if_modified`], for sources which take
+    /// more than one request to do so. The progress is persisted to the `checkpoint` after each
+    /// request, and an earlier fetch which was interrupted is resumed from it. Each request is
+    /// reported to `progress`.
+    ///
+    /// By default, the index is fetched with `fetch_if_modified`, which can't be resumed.
+    fn fetch_resumable(
+        &self,
+        validators: &Validators,
+        _checkpoint: &Checkpoint,
+        _progress: &dyn Fn(FetchIndexProgress),
+    ) -> Result<Fetched, CargoMSRVError> {
+        self.fetch_if_modified(validators)
+    }
+}
+
+impl From<ReleaseSource> for Box<dyn ReleaseIndexSource> {
+    fn from(source: ReleaseSource) -> Self {
+        builtin_source(source, None)
+    }
+}
+
+/// Create the built-in source for the given release source. The Rust changelog is fetched
+/// through the GitHub API when a token is given.
+fn builtin_source(
+    source: ReleaseSource,
+    token: Option<&AuthToken>,
+) -> Box<dyn ReleaseIndexSource + Send> {
+    match (source, token) {
+        (ReleaseSource::RustChangelog, Some(token)) => {
+            Box::new(GitHubChangelogSource::new(token.clone()))
+        }
+        (ReleaseSource::RustChangelog, None) => Box::new(RustChangelogSource),
+        #[cfg(feature = "rust-releases-dist-source")]
+        (ReleaseSource::RustDist, _) => Box::new(RustDistSource),
+    }
+}
+
+/// A registry of release index sources.
+///
+/// Sources registered with [`ReleaseIndexSources::register`] are tried first, in the order in
+/// which they were registered. Without registered sources, the built-in source configured by the
+/// user is tried, followed by the remaining built-in sources.
+pub struct ReleaseIndexSources {
+    custom: Vec<Box<dyn ReleaseIndexSource>>,
+    builtin: Vec<ReleaseSource>,
+}
+
+impl Default for ReleaseIndexSources {
+    fn default() -> Self {
+        let builtin = ReleaseSource::variants()
+            .iter()
+            .filter_map(|source| source.parse().ok())
+            .collect();
+
+        Self {
+            custom: Vec::new(),
+            builtin,
+        }
+    }
+}
+
+impl ReleaseIndexSources {
+    /// A registry without any sources, not even the built-in ones.
+    pub fn empty() -> Self {
+        Self {
+            custom: Vec::new(),
+            builtin: Vec::new(),
+        }
+    }
+
+    /// Register a custom source, which will be preferred over the built-in sources.
+    pub fn register(&mut self, source: impl ReleaseIndexSource + 'static) -> &mut Self {
+        self.custom.push(Box::new(source));
+        self
+    }
+
+    /// Fetch the release index from the first source which is available.
+    ///
+    /// The `token`, if any, is used to authenticate requests to GitHub. When a `cache` is given,
+    /// a sufficiently recent cached index is used instead of fetching it, and an interrupted
+    /// fetch is resumed. The progress of a fetch is reported to `progress`.
+    ///
+    /// Returns `None` if none of the sources could provide an index. A source which exceeded its
+    /// rate limit, and of which no index was cached, ends the search with an error instead, so
+    /// the user learns when to retry, rather than silently getting an older index. Likewise, when
+    /// none of the registered sources could provide an index, the built-in sources are not tried,
+    /// since their index may include releases which the registered sources leave out.
+    ///
+    /// The registered sources are tried one after another, while the built-in sources are fetched
+    /// concurrently, see [`fetch_concurrently`]. Waiting for the built-in sources stops when the
+    /// given `cancellation` token is cancelled.
+    pub(crate) fn fetch_first_available(
+        &self,
+        preferred: ReleaseSource,
+        token: Option<&AuthToken>,
+        cache: Option<&IndexCache>,
+        progress: &dyn Fn(FetchIndexProgress),
+        cancellation: Option<&CancellationToken>,
+    ) -> Result<Option<ReleaseIndex>, CargoMSRVError> {
+        let preferred_builtin = self.builtin.iter().filter(|&&source| source == preferred);
+        let other_builtin = self.builtin.iter().filter(|&&source| source != preferred);
+
+        let builtin = preferred_builtin
+            .chain(other_builtin)
+            .map(|&source| builtin_source(source, token))
+            .collect();
+
+        for source in &self.custom {
+            if let Some(index) = try_fetch(source.as_ref(), cache, progress)? {
+                return Ok(Some(index));
+            }
+        }
+
+        if !self.custom.is_empty() {
+            return Err(CargoMSRVError::ReleaseSourcesUnavailable {
+                release_sources: self
+                    .custom
+                    .iter()
+                    .map(|source| source.name().to_string())
+                    .collect(),
+            });
+        }
+
+        fetch_concurrently(builtin, cache, progress, cancellation, FALLBACK_DELAY)
+    }
+}
+
+/// A message from a thread which fetches the index of a source.
+enum FetchMessage {
+    Progress(FetchIndexProgress),
+    Fetched {
+        /// The position of the source in the order of preference
+        source: usize,
+        result: Result<Option<ReleaseIndex>, CargoMSRVError>,
+    },
+}
+
+/// Fetch the index from the first of the given sources, in order of preference, which is
+/// available, like [`try_fetch`] would when called for each source in turn.
+///
+/// Each source is fetched on its own thread. The next source is fetched as soon as the sources
+/// before it are unavailable, or when the most recently started fetch didn't complete within the
+/// `fallback_delay`. The index of a source is only used once each source which is preferred over
+/// it turned out to be unavailable.
+///
+/// The fetches which are still running once the index is found, or when the `cancellation` token
+/// is cancelled, are left to complete in the background, since a blocking request can't be
+/// interrupted.
+fn fetch_concurrently(
+    sources: Vec<Box<dyn ReleaseIndexSource + Send>>,
+    cache: Option<&IndexCache>,
+    progress: &dyn Fn(FetchIndexProgress),
+    cancellation: Option<&CancellationToken>,
+    fallback_delay: Duration,
+) -> Result<Option<ReleaseIndex>, CargoMSRVError> {
+    let (sender, receiver) = mpsc::channel();
+    let mut sources = sources.into_iter();
+
+    // The result of each started fetch, once it's complete
+    let mut results: Vec<Option<Result<Option<ReleaseIndex>, CargoMSRVError>>> = Vec::new();
+    let mut started_at = Instant::now();
+
+    loop {
+        // the most preferred source which may still provide an index, if any
+        let undecided = results
+            .iter()
+            .position(|result| !matches!(result, Some(Ok(None))));
+
+        if let Some(result) = undecided.and_then(|source| results[source].take()) {
+            return result;
+        }
+
+        let fallback_due = undecided.map_or(true, |_| started_at.elapsed() >= fallback_delay);
+
+        if fallback_due {
+            match sources.next() {
+                Some(source) => {
+                    spawn_fetch(results.len(), source, cache.cloned(), sender.clone());
+                    results.push(None);
+                    started_at = Instant::now();
+                    continue;
+                }
+                // each source is unavailable
+                None if undecided.is_none() => return Ok(None),
+                None => {}
+            }
+        }
+
+        if let Some(token) = cancellation {
+            token.check()?;
+        }
+
+        match receiver.recv_timeout(POLL_INTERVAL) {
+            Ok(FetchMessage::Progress(it)) => progress(it),
+            Ok(FetchMessage::Fetched { source, result }) => results[source] = Some(result),
+            // the sender is held by this function, so the channel can't be disconnected
+            Err(_) => {}
+        }
+    }
+}
+
+/// Fetch the index of the given source on a new thread, and send its progress and result to the
+/// `sender`.
+fn spawn_fetch(
+    position: usize,
+    source: Box<dyn ReleaseIndexSource + Send>,
+    cache: Option<IndexCache>,
+    sender: Sender<FetchMessage>,
+) {
+    thread::spawn(move || {
+        // the receiver is gone once the index was found, or the fetch was cancelled
+        let progress = |it| {
+            let _ = sender.send(FetchMessage::Progress(it));
+        };
+
+        let result = try_fetch(source.as_ref(), cache.as_ref(), &progress);
+
+        let _ = sender.send(FetchMessage::Fetched {
+            source: position,
+            result,
+        });
+    });
+}
+
+fn try_fetch(
+    source: &dyn ReleaseIndexSource,
+    cache: Option<&IndexCache>,
+    progress: &dyn Fn(FetchIndexProgress),
+) -> Result<Option<ReleaseIndex>, CargoMSRVError> {
+    let cache = match cache {
+        Some(cache) => cache,
+        None => return fetch(source),
+    };
+
+    let now = SystemTime::now();
+    let cached = cache.read(source.name());
+
+    if let Some(cached) = cached.as_ref().filter(|cached| cache.is_fresh(cached, now)) {
+        info!(source = source.name(), "using cached index");
+        return Ok(Some(cached.index()));
+    }
+
+    info!(source = source.name(), "fetching index");
+
+    let validators = cached
+        .as_ref()
+        .map(|cached| cached.validators().clone())
+        .unwrap_or_default();
+
+    let checkpoint = cache.checkpoint(source.name());
+
+    match (
+        source.fetch_resumable(&validators, &checkpoint, progress),
+        cached,
+    ) {
+        (Ok(Fetched::Modified { index, validators }), _) => {
+            cache.write(source.name(), &CachedIndex::new(&index, validators, now));
+            Ok(Some(index))
+        }
+        (Ok(Fetched::NotModified), Some(cached)) => {
+            info!(source = source.name(), "cached index not modified");
+            let cached = cached.revalidated(now);
+            cache.write(source.name(), &cached);
+            Ok(Some(cached.index()))
+        }
+        (Ok(Fetched::NotModified), None) => {
+            warn!(
+                source = source.name(),
+                "index not modified, but no index was cached"
+            );
+
+            Ok(None)
+        }
+        (Err(err @ CargoMSRVError::RateLimited { .. }), None) => Err(err),
+        (Err(err), cached) => {
+            warn!(
+                source = source.name(),
+                error = %err,
+                "unable to fetch index"
+            );
+
+            // A stale index is still more recent than the embedded index
+            Ok(cached.map(|cached| cached.index()))
+        }
+    }
+}
+
+fn fetch(source: &dyn ReleaseIndexSource) -> Result<Option<ReleaseIndex>, CargoMSRVError> {
+    info!(source = source.name(), "fetching index");
+
+    match source.fetch() {
+        Ok(index) => Ok(Some(index)),
+        Err(err @ CargoMSRVError::RateLimited { .. }) => Err(err),
+        Err(err) => {
+            warn!(
+                source = source.name(),
+                error = %err,
+                "unable to fetch index"
+            );
+
+            Ok(None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::semver;
+    use rust_releases::Release;
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use std::time::{Duration, UNIX_EPOCH};
+    use test_dir::{DirBuilder, TestDir};
+
+    struct FakeSource {
+        name: &'static str,
+        releases: Option<Vec<Release>>,
+        calls: Rc<Cell<u32>>,
+    }
+
+    impl FakeSource {
+        fn new(name: &'static str, releases: Option<Vec<Release>>) -> Self {
+            Self {
+                name,
+                releases,
+                calls: Rc::default(),
+            }
+        }
+    }
+
+    impl ReleaseIndexSource for FakeSource {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn fetch(&self) -> Result<ReleaseIndex, CargoMSRVError> {
+            self.calls.set(self.calls.get() + 1);
+
+            self.releases
+                .clone()
+                .map(|releases| releases.into_iter().collect())
+                .ok_or_else(|| CargoMSRVError::GenericMessage("unavailable".to_string()))
+        }
+    }
+
+    fn release(minor: u64) -> Release {
+        Release::new_stable(semver::Version::new(1, minor, 0))
+    }
+
+    #[test]
+    fn empty_registry_provides_no_index() {
+        let sources = ReleaseIndexSources::empty();
+
+        assert!(sources
+            .fetch_first_available(ReleaseSource::default(), None, None, &|_| {}, None)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn custom_source_is_used() {
+        let mut sources = ReleaseIndexSources::empty();
+        sources.register(FakeSource::new("mirror", Some(vec![release(56)])));
+
+        let index = sources
+            .fetch_first_available(ReleaseSource::default(), None, None, &|_| {}, None)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(index.releases(), &[release(56)]);
+    }
+
+    #[test]
+    fn unavailable_source_falls_through_to_next() {
+        let unavailable = FakeSource::new("unavailable", None);
+        let calls = Rc::clone(&unavailable.calls);
+
+        let mut sources = ReleaseIndexSources::empty();
+        sources.register(unavailable).register(FakeSource::new(
+            "mirror",
+            Some(vec![release(60), release(59)]),
+        ));
+
+        let index = sources
+            .fetch_first_available(ReleaseSource::default(), None, None, &|_| {}, None)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(calls.get(), 1);
+        assert_eq!(index.releases(), &[release(60), release(59)]);
+    }
+
+    #[test]
+    fn unavailable_sources_do_not_fall_back_to_builtin() {
+        let mut sources = ReleaseIndexSources::default();
+        sources
+            .register(FakeSource::new("first", None))
+            .register(FakeSource::new("second", None));
+
+        let result =
+            sources.fetch_first_available(ReleaseSource::default(), None, None, &|_| {}, None);
+
+        assert!(matches!(
+            result,
+            Err(CargoMSRVError::ReleaseSourcesUnavailable { release_sources })
+                if release_sources == ["first", "second"]
+        ));
+    }
+
+    #[test]
+    fn first_available_source_wins() {
+        let second = FakeSource::new("second", Some(vec![release(2)]));
+        let calls = Rc::clone(&second.calls);
+
+        let mut sources = ReleaseIndexSources::empty();
+        sources
+            .register(FakeSource::new("first", Some(vec![release(1)])))
+            .register(second);
+
+        let index = sources
+            .fetch_first_available(ReleaseSource::default(), None, None, &|_| {}, None)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(calls.get(), 0);
+        assert_eq!(index.releases(), &[release(1)]);
+    }
+
+    /// A source whose index never changes, as answered by a conditional request.
+    struct NotModifiedSource;
+
+    impl ReleaseIndexSource for NotModifiedSource {
+        fn name(&self) -> &str {
+            "mirror"
+        }
+
+        fn fetch(&self) -> Result<ReleaseIndex, CargoMSRVError> {
+            unreachable!("the index is only fetched conditionally")
+        }
+
+        fn fetch_if_modified(&self, _validators: &Validators) -> Result<Fetched, CargoMSRVError> {
+            Ok(Fetched::NotModified)
+        }
+    }
+
+    fn write_cached(cache: &IndexCache, releases: Vec<Release>, fetched_at: SystemTime) {
+        let index: ReleaseIndex = releases.into_iter().collect();
+        let cached = CachedIndex::new(&index, Validators::default(), fetched_at);
+
+        cache.write("mirror", &cached);
+    }
+
+    #[test]
+    fn fresh_cached_index_is_used() {
+        let tmp = TestDir::temp();
+        let cache = IndexCache::new(tmp.path("index"), Duration::from_secs(3600));
+        write_cached(&cache, vec![release(56)], SystemTime::now());
+
+        let source = FakeSource::new("mirror", Some(vec![release(60)]));
+        let calls = Rc::clone(&source.calls);
+
+        let mut sources = ReleaseIndexSources::empty();
+        sources.register(source);
+
+        let index = sources
+            .fetch_first_available(ReleaseSource::default(), None, Some(&cache), &|_| {}, None)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(calls.get(), 0);
+        assert_eq!(index.releases(), &[release(56)]);
+    }
+
+    #[test]
+    fn stale_cached_index_is_refetched() {
+        let tmp = TestDir::temp();
+        let cache = IndexCache::new(tmp.path("index"), Duration::from_secs(3600));
+        write_cached(&cache, vec![release(56)], UNIX_EPOCH);
+
+        let mut sources = ReleaseIndexSources::empty();
+        sources.register(FakeSource::new("mirror", Some(vec![release(60)])));
+
+        let index = sources
+            .fetch_first_available(ReleaseSource::default(), None, Some(&cache), &|_| {}, None)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(index.releases(), &[release(60)]);
+        assert_eq!(
+            cache.read("mirror").unwrap().index().releases(),
+            &[release(60)]
+        );
+    }
+
+    #[test]
+    fn not_modified_cached_index_is_revalidated() {
+        let tmp = TestDir::temp();
+        let cache = IndexCache::new(tmp.path("index"), Duration::from_secs(3600));
+        write_cached(&cache, vec![release(56)], UNIX_EPOCH);
+
+        let mut sources = ReleaseIndexSources::empty();
+        sources.register(NotModifiedSource);
+
+        let index = sources
+            .fetch_first_available(ReleaseSource::default(), None, Some(&cache), &|_| {}, None)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(index.releases(), &[release(56)]);
+        assert!(cache.is_fresh(&cache.read("mirror").unwrap(), SystemTime::now()));
+    }
+
+    #[test]
+    fn stale_cached_index_is_used_when_source_is_unavailable() {
+        let tmp = TestDir::temp();
+        let cache = IndexCache::new(tmp.path("index"), Duration::from_secs(3600));
+        write_cached(&cache, vec![release(56)], UNIX_EPOCH);
+
+        let mut sources = ReleaseIndexSources::empty();
+        sources.register(FakeSource::new("mirror", None));
+
+        let index = sources
+            .fetch_first_available(ReleaseSource::default(), None, Some(&cache), &|_| {}, None)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(index.releases(), &[release(56)]);
+    }
+
+    struct RateLimitedSource;
+
+    impl ReleaseIndexSource for RateLimitedSource {
+        fn name(&self) -> &str {
+            "mirror"
+        }
+
+        fn fetch(&self) -> Result<ReleaseIndex, CargoMSRVError> {
+            Err(CargoMSRVError::RateLimited {
+                release_source: self.name().to_string(),
+                retry_after: Some(Duration::from_secs(60)),
+            })
+        }
+    }
+
+    #[test]
+    fn rate_limited_source_ends_the_search() {
+        let second = FakeSource::new("second", Some(vec![release(2)]));
+        let calls = Rc::clone(&second.calls);
+
+        let mut sources = ReleaseIndexSources::empty();
+        sources.register(RateLimitedSource).register(second);
+
+        let result =
+            sources.fetch_first_available(ReleaseSource::default(), None, None, &|_| {}, None);
+
+        assert!(matches!(
+            result,
+            Err(CargoMSRVError::RateLimited { retry_after, .. })
+                if retry_after == Some(Duration::from_secs(60))
+        ));
+        assert_eq!(calls.get(), 0);
+    }
+
+    #[test]
+    fn stale_cached_index_is_used_when_source_is_rate_limited() {
+        let tmp = TestDir::temp();
+        let cache = IndexCache::new(tmp.path("index"), Duration::from_secs(3600));
+        write_cached(&cache, vec![release(56)], UNIX_EPOCH);
+
+        let mut sources = ReleaseIndexSources::empty();
+        sources.register(RateLimitedSource);
+
+        let index = sources
+            .fetch_first_available(ReleaseSource::default(), None, Some(&cache), &|_| {}, None)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(index.releases(), &[release(56)]);
+    }
+
+    #[test]
+    fn default_registry_contains_builtin_sources() {
+        let sources = ReleaseIndexSources::default();
+
+        assert!(sources.custom.is_empty());
+        assert!(sources.builtin.contains(&ReleaseSource::RustChangelog));
+    }
+
+    /// A source which takes the given time to respond, and which can be fetched on another thread.
+    struct DelayedSource {
+        releases: Option<Vec<Release>>,
+        delay: Duration,
+    }
+
+    impl DelayedSource {
+        fn boxed(
+            releases: Option<Vec<Release>>,
+            delay: Duration,
+        ) -> Box<dyn ReleaseIndexSource + Send> {
+            Box::new(Self { releases, delay })
+        }
+    }
+
+    impl ReleaseIndexSource for DelayedSource {
+        fn name(&self) -> &str {
+            "delayed"
+        }
+
+        fn fetch(&self) -> Result<ReleaseIndex, CargoMSRVError> {
+            std::thread::sleep(self.delay);
+
+            self.releases
+                .clone()
+                .map(|releases| releases.into_iter().collect())
+                .ok_or_else(|| CargoMSRVError::GenericMessage("unavailable".to_string()))
+        }
+    }
+
+    #[test]
+    fn preferred_source_wins_over_faster_fallback() {
+        let sources = vec![
+            DelayedSource::boxed(Some(vec![release(1)]), Duration::from_millis(200)),
+            DelayedSource::boxed(Some(vec![release(2)]), Duration::ZERO),
+        ];
+
+        let index = fetch_concurrently(sources, None, &|_| {}, None, Duration::ZERO)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(index.releases(), &[release(1)]);
+    }
+
+    #[test]
+    fn unavailable_source_falls_back_concurrently() {
+        let sources = vec![
+            DelayedSource::boxed(None, Duration::from_millis(200)),
+            DelayedSource::boxed(Some(vec![release(2)]), Duration::ZERO),
+        ];
+
+        let index = fetch_concurrently(sources, None, &|_| {}, None, Duration::ZERO)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(index.releases(), &[release(2)]);
+    }
+
+    #[test]
+    fn unavailable_sources_provide_no_index() {
+        let sources = vec![
+            DelayedSource::boxed(None, Duration::ZERO),
+            DelayedSource::boxed(None, Duration::ZERO),
+        ];
+
+        let result = fetch_concurrently(sources, None, &|_| {}, None, FALLBACK_DELAY);
+
+        assert!(result.unwrap().is_none());
+    }
+
+    #[test]
+    fn cancelled_fetch_is_not_waited_for() {
+        let sources = vec![DelayedSource::boxed(
+            Some(vec![release(1)]),
+            Duration::from_secs(60),
+        )];
+
+        let token = CancellationToken::new();
+        token.cancel();
+
+        let result = fetch_concurrently(sources, None, &|_| {}, Some(&token), FALLBACK_DELAY);
+
+        assert!(matches!(result, Err(CargoMSRVError::Cancelled)));
+    }
+}
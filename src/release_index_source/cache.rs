@@ -0,0 +1,309 @@
+//! A cache of fetched release indices on disk, keyed by the name of the source.
+//!
+//! A cached index is used as is, while it is younger than the configured maximum age. Once it is
+//! older, the source is asked to revalidate it, which, for sources which support it, is done with
+//! an HTTP conditional request, so the index is only downloaded again when it changed.
+//!
+//! Next to the cached indices, a source may keep a [`Checkpoint`] of a fetch which takes more
+//! than one request, so an interrupted fetch can be resumed by a later run.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use rust_releases::{Release, ReleaseIndex};
+
+use crate::semver;
+
+/// The validators of an HTTP response, with which a later request can be made conditional.
+#[derive(Clone, Debug, Default, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Validators {
+    etag: Option<String>,
+    last_modified: Option<String>,
+}
+
+impl Validators {
+    pub fn new(etag: Option<String>, last_modified: Option<String>) -> Self {
+        Self {
+            etag,
+            last_modified,
+        }
+    }
+
+    /// The `ETag` of the response, to be sent as `If-None-Match`.
+    pub fn etag(&self) -> Option<&str> {
+        self.etag.as_deref()
+    }
+
+    /// The `Last-Modified` date of the response, to be sent as `If-Modified-Since`.
+    pub fn last_modified(&self) -> Option<&str> {
+        self.last_modified.as_deref()
+    }
+}
+
+/// The result of fetching an index, which may be unchanged since it was last fetched.
+pub enum Fetched {
+    /// The index changed, or was fetched unconditionally
+    Modified {
+        index: ReleaseIndex,
+        validators: Validators,
+    },
+    /// The index is the same as the one fetched with the given validators
+    NotModified,
+}
+
+/// The cache directory, and the age up to which a cached index is used without revalidating it.
+#[derive(Clone)]
+pub(crate) struct IndexCache {
+    dir: PathBuf,
+    max_age: Duration,
+}
+
+impl IndexCache {
+    pub(crate) fn new(dir: PathBuf, max_age: Duration) -> Self {
+        Self { dir, max_age }
+    }
+
+    pub(crate) fn read(&self, source: &str) -> Option<CachedIndex> {
+        let contents = std::fs::read_to_string(self.path(source)).ok()?;
+
+        serde_json::from_str(&contents)
+            .map_err(|error| warn!(source, %error, "ignoring unreadable cached index"))
+            .ok()
+    }
+
+    /// Write the cached index. Failing to do so is not fatal, since the index will be fetched
+    /// again on the next run.
+    pub(crate) fn write(&self, source: &str, cached: &CachedIndex) {
+        let path = self.path(source);
+
+        let result = std::fs::create_dir_all(&self.dir).and_then(|_| {
+            let contents = serde_json::to_string(cached)?;
+            std::fs::write(&path, contents)
+        });
+
+        if let Err(error) = result {
+            warn!(source, path = %path.display(), %error, "unable to write cached index");
+        }
+    }
+
+    /// Whether the cached index is recent enough to be used without revalidating it.
+    pub(crate) fn is_fresh(&self, cached: &CachedIndex, now: SystemTime) -> bool {
+        cached.age(now).map_or(false, |age| age <= self.max_age)
+    }
+
+    /// The checkpoint in which the given source persists the progress of an unfinished fetch.
+    pub(crate) fn checkpoint(&self, source: &str) -> Checkpoint {
+        Checkpoint {
+            path: Some(self.dir.join(format!("{}.partial.json", file_stem(source)))),
+        }
+    }
+
+    fn path(&self, source: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", file_stem(source)))
+    }
+}
+
+/// Custom sources may be named freely, so only keep characters which are safe in a file name.
+fn file_stem(source: &str) -> String {
+    source
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect()
+}
+
+/// The persisted progress of a fetch which takes more than one request, from which a fetch which
+/// was interrupted, e.g. by a failing connection, is resumed.
+///
+/// The progress is only persisted when there is a cache directory. Like the cache, failing to
+/// persist the progress is not fatal; the fetch is started from scratch on the next run instead.
+#[derive(Debug, Default)]
+pub struct Checkpoint {
+    path: Option<PathBuf>,
+}
+
+impl Checkpoint {
+    /// A checkpoint which doesn't persist anything, so the fetch can't be resumed.
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    /// The progress of the interrupted fetch, if any.
+    pub fn read<T: serde::de::DeserializeOwned>(&self) -> Option<T> {
+        let path = self.path.as_ref()?;
+        let contents = std::fs::read_to_string(path).ok()?;
+
+        serde_json::from_str(&contents)
+            .map_err(
+                |error| warn!(path = %path.display(), %error, "ignoring unreadable checkpoint"),
+            )
+            .ok()
+    }
+
+    /// Persist the progress of the fetch, so far.
+    pub fn write<T: serde::Serialize>(&self, progress: &T) {
+        let path = match &self.path {
+            Some(path) => path,
+            None => return,
+        };
+
+        let result = path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|_| {
+                let contents = serde_json::to_string(progress)?;
+                std::fs::write(path, contents)
+            });
+
+        if let Err(error) = result {
+            warn!(path = %path.display(), %error, "unable to write checkpoint");
+        }
+    }
+
+    /// Remove the progress, once the fetch is complete, or can't be resumed.
+    pub fn clear(&self) {
+        if let Some(path) = self.path.as_ref().filter(|path| path.exists()) {
+            if let Err(error) = std::fs::remove_file(path) {
+                warn!(path = %path.display(), %error, "unable to remove checkpoint");
+            }
+        }
+    }
+}
+
+/// The directory in which fetched indices are cached, if it can be determined.
+pub(crate) fn cache_dir(scratch_dir: Option<&Path>) -> Option<PathBuf> {
+    if let Some(dir) = scratch_dir {
+        return Some(dir.join("index"));
+    }
+
+    dirs::cache_dir().map(|path| path.join("cargo-msrv").join("index"))
+}
+
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub(crate) struct CachedIndex {
+    /// Seconds since the unix epoch, at which the index was last fetched or revalidated
+    fetched_at: u64,
+    validators: Validators,
+    releases: Vec<semver::Version>,
+}
+
+impl CachedIndex {
+    pub(crate) fn new(index: &ReleaseIndex, validators: Validators, now: SystemTime) -> Self {
+        Self {
+            fetched_at: unix_secs(now),
+            validators,
+            releases: index
+                .releases()
+                .iter()
+                .map(|release| release.version().clone())
+                .collect(),
+        }
+    }
+
+    pub(crate) fn validators(&self) -> &Validators {
+        &self.validators
+    }
+
+    pub(crate) fn index(&self) -> ReleaseIndex {
+        self.releases
+            .iter()
+            .cloned()
+            .map(Release::new_stable)
+            .collect()
+    }
+
+    /// Mark the index as revalidated, i.e. unchanged at the given time.
+    pub(crate) fn revalidated(mut self, now: SystemTime) -> Self {
+        self.fetched_at = unix_secs(now);
+        self
+    }
+
+    /// The age of the index, or `None` if it was fetched in the future, e.g. because the clock
+    /// was changed since.
+    fn age(&self, now: SystemTime) -> Option<Duration> {
+        let fetched_at = UNIX_EPOCH + Duration::from_secs(self.fetched_at);
+        now.duration_since(fetched_at).ok()
+    }
+}
+
+fn unix_secs(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_dir::{DirBuilder, TestDir};
+
+    fn empty_index() -> ReleaseIndex {
+        std::iter::empty().collect()
+    }
+
+    #[yare::parameterized(
+        young = { 3599, true },
+        max_age = { 3600, true },
+        old = { 3601, false },
+    )]
+    fn is_fresh(age: u64, expected: bool) {
+        let cache = IndexCache::new(PathBuf::from("index"), Duration::from_secs(3600));
+        let cached = CachedIndex::new(&empty_index(), Validators::default(), UNIX_EPOCH);
+
+        assert_eq!(
+            cache.is_fresh(&cached, UNIX_EPOCH + Duration::from_secs(age)),
+            expected
+        );
+    }
+
+    #[test]
+    fn fetched_in_the_future_is_not_fresh() {
+        let cache = IndexCache::new(PathBuf::from("index"), Duration::from_secs(3600));
+        let fetched_at = UNIX_EPOCH + Duration::from_secs(3600);
+        let cached = CachedIndex::new(&empty_index(), Validators::default(), fetched_at);
+
+        assert!(!cache.is_fresh(&cached, UNIX_EPOCH));
+    }
+
+    #[yare::parameterized(
+        builtin = { "rust-changelog", "rust-changelog.json" },
+        custom = { "../mirror/v1", "---mirror-v1.json" },
+    )]
+    fn path_is_named_after_source(source: &str, expected: &str) {
+        let cache = IndexCache::new(PathBuf::from("index"), Duration::ZERO);
+
+        assert_eq!(cache.path(source), Path::new("index").join(expected));
+    }
+
+    #[test]
+    fn checkpoint_round_trips() {
+        let tmp = TestDir::temp();
+        let cache = IndexCache::new(tmp.path("index"), Duration::ZERO);
+        let checkpoint = cache.checkpoint("rust-dist");
+
+        assert_eq!(checkpoint.read::<Vec<u64>>(), None);
+
+        checkpoint.write(&vec![1, 2, 3]);
+        assert_eq!(checkpoint.read::<Vec<u64>>(), Some(vec![1, 2, 3]));
+
+        checkpoint.clear();
+        assert_eq!(checkpoint.read::<Vec<u64>>(), None);
+    }
+
+    #[test]
+    fn index_round_trips() {
+        let releases = vec![
+            Release::new_stable(semver::Version::new(1, 60, 0)),
+            Release::new_stable(semver::Version::new(1, 59, 0)),
+        ];
+        let index: ReleaseIndex = releases.iter().cloned().collect();
+        let validators = Validators::new(Some("\"abc\"".to_string()), None);
+
+        let cached = CachedIndex::new(&index, validators.clone(), UNIX_EPOCH);
+        let json = serde_json::to_string(&cached).unwrap();
+        let read: CachedIndex = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(read, cached);
+        assert_eq!(read.validators(), &validators);
+        assert_eq!(read.index().releases(), releases.as_slice());
+    }
+}
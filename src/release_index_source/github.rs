@@ -0,0 +1,363 @@
+//! Fetches the Rust changelog (RELEASES.md) from GitHub, either as a raw file, or through the
+//! GitHub API, authenticated with a token.
+//!
+//! Unauthenticated requests to GitHub share a small rate limit per IP address, which is quickly
+//! exhausted on busy CI runners. Authenticated requests are subject to a much higher limit.
+//!
+//! Both are fetched with conditional requests when a cached index is revalidated, so the
+//! changelog is only downloaded again when it changed.
+
+use std::fmt;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use attohttpc::RequestBuilder;
+use rust_releases::{Release, ReleaseIndex};
+
+use crate::config::ReleaseSource;
+use crate::error::CargoMSRVError;
+use crate::release_index_source::{Checkpoint, Fetched, ReleaseIndexSource, Validators};
+use crate::release_schedule::Date;
+use crate::reporter::event::FetchIndexProgress;
+use crate::semver;
+
+const RELEASES_URL: &str = "https://api.github.com/repos/rust-lang/rust/contents/RELEASES.md";
+const RAW_RELEASES_URL: &str =
+    "https://raw.githubusercontent.com/rust-lang/rust/master/RELEASES.md";
+
+/// A token used to authenticate requests to GitHub.
+///
+/// The token is never printed, not even in debug output.
+#[derive(Clone, PartialEq)]
+pub struct AuthToken(String);
+
+impl AuthToken {
+    pub fn new(token: impl Into<String>) -> Self {
+        Self(token.into())
+    }
+
+    pub(crate) fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Debug for AuthToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("AuthToken(<redacted>)")
+    }
+}
+
+impl FromStr for AuthToken {
+    type Err = CargoMSRVError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let token = s.trim();
+
+        if token.is_empty() {
+            return Err(CargoMSRVError::InvalidConfig(
+                "The GitHub token may not be empty".to_string(),
+            ));
+        }
+
+        Ok(Self::new(token))
+    }
+}
+
+/// Fetches the release index from the Rust changelog, through the GitHub API.
+pub struct GitHubChangelogSource {
+    token: AuthToken,
+}
+
+impl GitHubChangelogSource {
+    pub fn new(token: AuthToken) -> Self {
+        Self { token }
+    }
+}
+
+impl ReleaseIndexSource for GitHubChangelogSource {
+    fn name(&self) -> &str {
+        ReleaseSource::RustChangelog.into()
+    }
+
+    fn fetch(&self) -> Result<ReleaseIndex, CargoMSRVError> {
+        let fetched = self.fetch_if_modified(&Validators::default())?;
+        modified_index(fetched, RELEASES_URL)
+    }
+
+    fn fetch_if_modified(&self, validators: &Validators) -> Result<Fetched, CargoMSRVError> {
+        self.fetch_resumable(validators, &Checkpoint::none(), &|_| {})
+    }
+
+    fn fetch_resumable(
+        &self,
+        validators: &Validators,
+        _checkpoint: &Checkpoint,
+        progress: &dyn Fn(FetchIndexProgress),
+    ) -> Result<Fetched, CargoMSRVError> {
+        let request = attohttpc::get(RELEASES_URL)
+            .header("Accept", "application/vnd.github.raw")
+            .try_header("Authorization", format!("Bearer {}", self.token.as_str()))
+            .map_err(|err| fetch_error(RELEASES_URL, err.to_string()))?;
+
+        fetch_changelog(self.name(), RELEASES_URL, request, validators, progress)
+    }
+}
+
+/// Fetches the release index from the Rust changelog, without authentication.
+pub struct RustChangelogSource;
+
+impl ReleaseIndexSource for RustChangelogSource {
+    fn name(&self) -> &str {
+        ReleaseSource::RustChangelog.into()
+    }
+
+    fn fetch(&self) -> Result<ReleaseIndex, CargoMSRVError> {
+        let fetched = self.fetch_if_modified(&Validators::default())?;
+        modified_index(fetched, RAW_RELEASES_URL)
+    }
+
+    fn fetch_if_modified(&self, validators: &Validators) -> Result<Fetched, CargoMSRVError> {
+        self.fetch_resumable(validators, &Checkpoint::none(), &|_| {})
+    }
+
+    fn fetch_resumable(
+        &self,
+        validators: &Validators,
+        _checkpoint: &Checkpoint,
+        progress: &dyn Fn(FetchIndexProgress),
+    ) -> Result<Fetched, CargoMSRVError> {
+        let request = attohttpc::get(RAW_RELEASES_URL);
+
+        fetch_changelog(self.name(), RAW_RELEASES_URL, request, validators, progress)
+    }
+}
+
+/// The index of an unconditional fetch, which the server never answers with `304 Not Modified`.
+fn modified_index(fetched: Fetched, url: &str) -> Result<ReleaseIndex, CargoMSRVError> {
+    match fetched {
+        Fetched::Modified { index, .. } => Ok(index),
+        Fetched::NotModified => Err(fetch_error(url, "unexpected status code 304".to_string())),
+    }
+}
+
+fn fetch_changelog(
+    name: &str,
+    url: &str,
+    request: RequestBuilder,
+    validators: &Validators,
+    progress: &dyn Fn(FetchIndexProgress),
+) -> Result<Fetched, CargoMSRVError> {
+    let response = conditional(request.header("User-Agent", "cargo-msrv"), validators)
+        .and_then(|request| request.send())
+        .map_err(|err| fetch_error(url, err.to_string()))?;
+
+    let status = response.status().as_u16();
+
+    if status == 304 {
+        return Ok(Fetched::NotModified);
+    }
+
+    if is_rate_limited(
+        status,
+        header(&response, "x-ratelimit-remaining"),
+        header(&response, "retry-after"),
+    ) {
+        return Err(CargoMSRVError::RateLimited {
+            release_source: name.to_string(),
+            retry_after: retry_after(
+                header(&response, "retry-after"),
+                header(&response, "x-ratelimit-reset"),
+                SystemTime::now(),
+            ),
+        });
+    }
+
+    if !response.is_success() {
+        return Err(fetch_error(
+            url,
+            format!("unexpected status code {}", status),
+        ));
+    }
+
+    let validators = Validators::new(
+        header(&response, "etag").map(String::from),
+        header(&response, "last-modified").map(String::from),
+    );
+
+    let changelog = response
+        .text()
+        .map_err(|err| fetch_error(url, err.to_string()))?;
+
+    let index = parse_changelog(&changelog, &today());
+
+    // The changelog is fetched with a single request, which is reported once it completed
+    progress(
+        FetchIndexProgress::new(name, 1, index.releases().len(), 0)
+            .with_bytes(changelog.len() as u64),
+    );
+
+    Ok(Fetched::Modified { index, validators })
+}
+
+/// Make the request conditional on the given validators, so the server responds with
+/// `304 Not Modified` when the changelog did not change.
+fn conditional(
+    mut request: RequestBuilder,
+    validators: &Validators,
+) -> attohttpc::Result<RequestBuilder> {
+    if let Some(etag) = validators.etag() {
+        request = request.try_header("If-None-Match", etag)?;
+    }
+
+    if let Some(last_modified) = validators.last_modified() {
+        request = request.try_header("If-Modified-Since", last_modified)?;
+    }
+
+    Ok(request)
+}
+
+pub(super) fn fetch_error(url: &str, message: String) -> CargoMSRVError {
+    CargoMSRVError::FetchReleaseIndex {
+        url: url.to_string(),
+        message,
+    }
+}
+
+fn header<'r>(response: &'r attohttpc::Response, name: &str) -> Option<&'r str> {
+    response
+        .headers()
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+}
+
+/// GitHub responds with either a 403 or 429 status code when a rate limit is exceeded. A 403 is
+/// told apart from other refusals by the exhausted primary rate limit of the API, or, for the
+/// secondary rate limits which also apply to unauthenticated requests, by a `retry-after` header.
+fn is_rate_limited(status: u16, remaining: Option<&str>, retry_after: Option<&str>) -> bool {
+    status == 429
+        || (status == 403 && (remaining.map(str::trim) == Some("0") || retry_after.is_some()))
+}
+
+/// Determine how long to wait before retrying, from either the `retry-after` header (in seconds),
+/// or the `x-ratelimit-reset` header (a unix timestamp, in seconds).
+fn retry_after(
+    retry_after: Option<&str>,
+    reset: Option<&str>,
+    now: SystemTime,
+) -> Option<Duration> {
+    if let Some(secs) = retry_after.and_then(|value| value.trim().parse::<u64>().ok()) {
+        return Some(Duration::from_secs(secs));
+    }
+
+    let reset = reset.and_then(|value| value.trim().parse::<u64>().ok())?;
+    let now = now.duration_since(UNIX_EPOCH).ok()?.as_secs();
+
+    Some(Duration::from_secs(reset.saturating_sub(now)))
+}
+
+/// Parse the stable releases from the Rust changelog, which lists each release with a heading
+/// like `Version 1.64.0 (2022-09-22)`. Releases dated after `today` have not been released yet,
+/// and are skipped.
+fn parse_changelog(changelog: &str, today: &str) -> ReleaseIndex {
+    let mut releases = changelog
+        .lines()
+        .filter_map(|line| line.strip_prefix("Version "))
+        .filter_map(|heading| {
+            let (version, date) = heading.split_once(' ')?;
+            let date = date.trim().strip_prefix('(')?.strip_suffix(')')?;
+
+            if date > today {
+                return None;
+            }
+
+            semver::Version::parse(version)
+                .ok()
+                .filter(|version| version.major >= 1 && version.pre.is_empty())
+        })
+        .collect::<Vec<_>>();
+
+    // Releases in an index must be ordered from most to least recent
+    releases.sort_by(|lhs, rhs| rhs.cmp(lhs));
+    releases.dedup();
+
+    releases.into_iter().map(Release::new_stable).collect()
+}
+
+/// Today's (UTC) date, formatted like the dates in the Rust changelog, i.e. `YYYY-MM-DD`.
+fn today() -> String {
+    Date::of(SystemTime::now()).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHANGELOG: &str = r#"Version 1.65.0 (2022-11-03)
+==========================
+
+Version 1.64.0 (2022-09-22)
+==========================
+
+Language
+--------
+- [Unions with `ManuallyDrop` fields are now stable.][1]
+
+Version 1.63.0 (2022-08-11)
+==========================
+
+Version 1.0.0-alpha (2015-01-09)
+================================
+
+Version 0.12.0 (2014-10-09)
+===========================
+"#;
+
+    #[test]
+    fn parses_released_stable_versions() {
+        let index = parse_changelog(CHANGELOG, "2022-10-01");
+
+        assert_eq!(
+            index.releases(),
+            &[
+                Release::new_stable(semver::Version::new(1, 64, 0)),
+                Release::new_stable(semver::Version::new(1, 63, 0)),
+            ]
+        );
+    }
+
+    #[yare::parameterized(
+        too_many_requests = { 429, None, None, true },
+        forbidden_exhausted = { 403, Some("0"), None, true },
+        forbidden_remaining = { 403, Some("12"), None, false },
+        forbidden_without_header = { 403, None, None, false },
+        forbidden_retry_after = { 403, None, Some("60"), true },
+        ok = { 200, Some("0"), None, false },
+    )]
+    fn rate_limited(
+        status: u16,
+        remaining: Option<&str>,
+        retry_after: Option<&str>,
+        expected: bool,
+    ) {
+        assert_eq!(is_rate_limited(status, remaining, retry_after), expected);
+    }
+
+    #[yare::parameterized(
+        retry_after_header = { Some("30"), Some("1000100"), Some(Duration::from_secs(30)) },
+        reset_header = { None, Some("1000100"), Some(Duration::from_secs(100)) },
+        reset_in_the_past = { None, Some("999000"), Some(Duration::ZERO) },
+        none = { None, None, None },
+    )]
+    fn retry_after_hint(retry: Option<&str>, reset: Option<&str>, expected: Option<Duration>) {
+        let now = UNIX_EPOCH + Duration::from_secs(1_000_000);
+
+        assert_eq!(retry_after(retry, reset, now), expected);
+    }
+
+    #[test]
+    fn auth_token_is_redacted() {
+        let token = AuthToken::new("ghp_secret");
+
+        assert!(!format!("{:?}", token).contains("ghp_secret"));
+    }
+}
@@ -0,0 +1,371 @@
+//! Fetches the release index from the Rust distribution server, by listing the files of its
+//! `dist/` folder, and keeping the channel manifests of stable releases, e.g.
+//! `dist/channel-rust-1.56.0.toml`.
+//!
+//! The folder is listed from the S3 bucket of the server, which returns at most 1000 files per
+//! request, so the listing takes many requests. On a slow connection, one of them may fail before
+//! the listing is complete. The progress is persisted in a [`Checkpoint`] after each request, so
+//! the next attempt resumes the listing where it was interrupted, instead of starting over.
+
+use std::time::Duration;
+
+use rust_releases::{Release, ReleaseIndex};
+
+use crate::config::ReleaseSource;
+use crate::error::CargoMSRVError;
+use crate::release_index_source::github::fetch_error;
+use crate::release_index_source::{Checkpoint, Fetched, ReleaseIndexSource, Validators};
+use crate::reporter::event::FetchIndexProgress;
+use crate::semver;
+
+const BUCKET_URL: &str = "https://static-rust-lang-org.s3.amazonaws.com/";
+const PREFIX: &str = "dist/";
+const CHANNEL_MANIFEST_PREFIX: &str = "dist/channel-rust-";
+const TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Fetches the release index from the Rust distribution server.
+pub struct RustDistSource;
+
+impl ReleaseIndexSource for RustDistSource {
+    fn name(&self) -> &str {
+        ReleaseSource::RustDist.into()
+    }
+
+    fn fetch(&self) -> Result<ReleaseIndex, CargoMSRVError> {
+        list(self.name(), &Checkpoint::none(), &|_| {}, list_page)
+    }
+
+    fn fetch_resumable(
+        &self,
+        _validators: &Validators,
+        checkpoint: &Checkpoint,
+        progress: &dyn Fn(FetchIndexProgress),
+    ) -> Result<Fetched, CargoMSRVError> {
+        let index = list(self.name(), checkpoint, progress, list_page)?;
+
+        Ok(Fetched::Modified {
+            index,
+            validators: Validators::default(),
+        })
+    }
+}
+
+/// The progress of the listing, as persisted in the checkpoint.
+#[derive(Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+struct Listing {
+    /// The token with which the next page of the listing is requested
+    continuation_token: Option<String>,
+    requests: u64,
+    releases: Vec<semver::Version>,
+}
+
+/// A page of the listing of the bucket.
+#[derive(Debug, Default, PartialEq)]
+struct Page {
+    keys: Vec<String>,
+    /// The token with which the next page is requested, or `None` if this is the last page
+    next_continuation_token: Option<String>,
+    /// The size of the response, in bytes
+    bytes: u64,
+}
+
+#[derive(Debug)]
+enum PageError {
+    /// The continuation token was rejected, e.g. because it expired since the listing was
+    /// interrupted
+    InvalidToken,
+    Failed(CargoMSRVError),
+}
+
+/// List the stable releases, one page at a time, resuming from the progress in the checkpoint.
+///
+/// If the listing fails, the progress is left in the checkpoint, so the next attempt continues
+/// from there. Once the listing is complete, the checkpoint is cleared.
+fn list(
+    name: &str,
+    checkpoint: &Checkpoint,
+    progress: &dyn Fn(FetchIndexProgress),
+    list_page: impl Fn(Option<&str>) -> Result<Page, PageError>,
+) -> Result<ReleaseIndex, CargoMSRVError> {
+    let mut listing = checkpoint.read::<Listing>().unwrap_or_default();
+    let mut resumed_after = listing.requests;
+    // Only counts the bytes downloaded by this fetch, not those of an interrupted fetch
+    let mut downloaded = 0;
+
+    if resumed_after > 0 {
+        info!(
+            source = name,
+            requests = resumed_after,
+            "resuming interrupted fetch"
+        );
+    }
+
+    loop {
+        let page = match list_page(listing.continuation_token.as_deref()) {
+            Ok(page) => page,
+            Err(PageError::InvalidToken) if listing.continuation_token.is_some() => {
+                warn!(
+                    source = name,
+                    "unable to resume interrupted fetch, starting over"
+                );
+
+                checkpoint.clear();
+                listing = Listing::default();
+                resumed_after = 0;
+                continue;
+            }
+            Err(PageError::InvalidToken) => {
+                return Err(fetch_error(BUCKET_URL, "invalid request".to_string()))
+            }
+            Err(PageError::Failed(error)) => return Err(error),
+        };
+
+        listing.requests += 1;
+        downloaded += page.bytes;
+        listing
+            .releases
+            .extend(page.keys.iter().filter_map(|key| stable_release(key)));
+        listing.continuation_token = page.next_continuation_token;
+
+        progress(
+            FetchIndexProgress::new(
+                name,
+                listing.requests,
+                listing.releases.len(),
+                resumed_after,
+            )
+            .with_bytes(downloaded),
+        );
+
+        if listing.continuation_token.is_none() {
+            break;
+        }
+
+        checkpoint.write(&listing);
+    }
+
+    checkpoint.clear();
+
+    let mut releases = listing.releases;
+    releases.sort_by(|lhs, rhs| rhs.cmp(lhs));
+    releases.dedup();
+
+    Ok(releases.into_iter().map(Release::new_stable).collect())
+}
+
+/// Request a page of the listing of the `dist/` folder of the bucket.
+fn list_page(continuation_token: Option<&str>) -> Result<Page, PageError> {
+    let mut request = attohttpc::get(BUCKET_URL)
+        .param("list-type", "2")
+        .param("prefix", PREFIX)
+        .param("delimiter", "/")
+        .header("User-Agent", "cargo-msrv")
+        .timeout(TIMEOUT);
+
+    if let Some(token) = continuation_token {
+        request = request.param("continuation-token", token);
+    }
+
+    let failed = |message: String| PageError::Failed(fetch_error(BUCKET_URL, message));
+
+    let response = request.send().map_err(|err| failed(err.to_string()))?;
+    let status = response.status().as_u16();
+
+    if status == 400 && continuation_token.is_some() {
+        return Err(PageError::InvalidToken);
+    }
+
+    if !response.is_success() {
+        return Err(failed(format!("unexpected status code {}", status)));
+    }
+
+    let body = response.text().map_err(|err| failed(err.to_string()))?;
+
+    Ok(parse_page(&body))
+}
+
+/// Parse a page of the `ListObjectsV2` response of the bucket.
+fn parse_page(xml: &str) -> Page {
+    let keys = elements(xml, "Key").into_iter().map(unescape).collect();
+
+    let is_truncated = elements(xml, "IsTruncated").first() == Some(&"true");
+    let next_continuation_token = elements(xml, "NextContinuationToken")
+        .first()
+        .filter(|_| is_truncated)
+        .map(|token| unescape(token));
+
+    Page {
+        keys,
+        next_continuation_token,
+        bytes: xml.len() as u64,
+    }
+}
+
+/// The text of each element with the given name, e.g. `<Key>dist/index.html</Key>`.
+fn elements<'x>(xml: &'x str, name: &str) -> Vec<&'x str> {
+    let open = format!("<{}>", name);
+    let close = format!("</{}>", name);
+
+    xml.split(open.as_str())
+        .skip(1)
+        .filter_map(|rest| rest.find(close.as_str()).map(|end| &rest[..end]))
+        .collect()
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// The stable release of a channel manifest, e.g. `dist/channel-rust-1.56.0.toml`. Channel
+/// manifests of other channels, like `dist/channel-rust-beta.toml`, and other files give `None`.
+fn stable_release(key: &str) -> Option<semver::Version> {
+    let version = key
+        .strip_prefix(CHANNEL_MANIFEST_PREFIX)?
+        .strip_suffix(".toml")?
+        .parse::<semver::Version>()
+        .ok()?;
+
+    Some(version).filter(|version| version.pre.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::release_index_source::IndexCache;
+    use std::cell::{Cell, RefCell};
+    use test_dir::{DirBuilder, TestDir};
+
+    const PAGE: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+  <Name>static-rust-lang-org</Name>
+  <Prefix>dist/</Prefix>
+  <NextContinuationToken>1a2b&amp;3c</NextContinuationToken>
+  <KeyCount>3</KeyCount>
+  <MaxKeys>1000</MaxKeys>
+  <Delimiter>/</Delimiter>
+  <IsTruncated>true</IsTruncated>
+  <Contents><Key>dist/channel-rust-1.56.0.toml</Key><Size>742301</Size></Contents>
+  <Contents><Key>dist/channel-rust-1.56.0.toml.sha256</Key><Size>96</Size></Contents>
+  <Contents><Key>dist/channel-rust-beta.toml</Key><Size>751202</Size></Contents>
+  <CommonPrefixes><Prefix>dist/2021-10-21/</Prefix></CommonPrefixes>
+</ListBucketResult>
+"#;
+
+    #[test]
+    fn parse_truncated_page() {
+        assert_eq!(
+            parse_page(PAGE),
+            Page {
+                keys: vec![
+                    "dist/channel-rust-1.56.0.toml".to_string(),
+                    "dist/channel-rust-1.56.0.toml.sha256".to_string(),
+                    "dist/channel-rust-beta.toml".to_string(),
+                ],
+                next_continuation_token: Some("1a2b&3c".to_string()),
+                bytes: PAGE.len() as u64,
+            }
+        );
+    }
+
+    #[test]
+    fn parse_last_page() {
+        let page = PAGE.replace(
+            "<IsTruncated>true</IsTruncated>",
+            "<IsTruncated>false</IsTruncated>",
+        );
+
+        assert_eq!(parse_page(&page).next_continuation_token, None);
+    }
+
+    #[yare::parameterized(
+        stable = { "dist/channel-rust-1.56.0.toml", Some(semver::Version::new(1, 56, 0)) },
+        two_components = { "dist/channel-rust-1.56.toml", None },
+        beta = { "dist/channel-rust-beta.toml", None },
+        pre_release = { "dist/channel-rust-1.56.0-beta.1.toml", None },
+        checksum = { "dist/channel-rust-1.56.0.toml.sha256", None },
+        archive = { "dist/rust-1.56.0-x86_64-unknown-linux-gnu.tar.gz", None },
+    )]
+    fn release_of_key(key: &str, expected: Option<semver::Version>) {
+        assert_eq!(stable_release(key), expected);
+    }
+
+    fn page(minor: u64, next: Option<&str>) -> Page {
+        Page {
+            keys: vec![format!("dist/channel-rust-1.{}.0.toml", minor)],
+            next_continuation_token: next.map(String::from),
+            bytes: 100,
+        }
+    }
+
+    #[test]
+    fn interrupted_listing_is_resumed() {
+        let tmp = TestDir::temp();
+        let cache = IndexCache::new(tmp.path("index"), Duration::ZERO);
+        let checkpoint = cache.checkpoint("rust-dist");
+
+        // The first attempt fails at the third request
+        let first = |token: Option<&str>| match token {
+            None => Ok(page(56, Some("a"))),
+            Some("a") => Ok(page(57, Some("b"))),
+            _ => Err(PageError::Failed(CargoMSRVError::GenericMessage(
+                "connection reset".to_string(),
+            ))),
+        };
+
+        assert!(list("rust-dist", &checkpoint, &|_| {}, first).is_err());
+
+        // The second attempt continues with the third request
+        let tokens = RefCell::new(Vec::new());
+        let second = |token: Option<&str>| {
+            tokens.borrow_mut().push(token.map(String::from));
+            Ok(page(58, None))
+        };
+
+        let reported = Cell::new(None);
+        let progress = |it: FetchIndexProgress| reported.set(Some(it));
+        let index = list("rust-dist", &checkpoint, &progress, second).unwrap();
+
+        assert_eq!(tokens.into_inner(), vec![Some("b".to_string())]);
+        assert_eq!(
+            index.releases(),
+            &[
+                Release::new_stable(semver::Version::new(1, 58, 0)),
+                Release::new_stable(semver::Version::new(1, 57, 0)),
+                Release::new_stable(semver::Version::new(1, 56, 0)),
+            ]
+        );
+        assert_eq!(
+            reported.into_inner(),
+            Some(FetchIndexProgress::new("rust-dist", 3, 3, 2).with_bytes(100))
+        );
+        assert_eq!(checkpoint.read::<Listing>(), None);
+    }
+
+    #[test]
+    fn rejected_continuation_token_starts_over() {
+        let tmp = TestDir::temp();
+        let cache = IndexCache::new(tmp.path("index"), Duration::ZERO);
+        let checkpoint = cache.checkpoint("rust-dist");
+        checkpoint.write(&Listing {
+            continuation_token: Some("expired".to_string()),
+            requests: 1,
+            releases: vec![semver::Version::new(1, 56, 0)],
+        });
+
+        let list_page = |token: Option<&str>| match token {
+            None => Ok(page(60, None)),
+            Some(_) => Err(PageError::InvalidToken),
+        };
+
+        let index = list("rust-dist", &checkpoint, &|_| {}, list_page).unwrap();
+
+        assert_eq!(
+            index.releases(),
+            &[Release::new_stable(semver::Version::new(1, 60, 0))]
+        );
+    }
+}
@@ -0,0 +1,278 @@
+//! The release dates of stable Rust releases, as derived from the Rust release schedule.
+//!
+//! Rust 1.0.0 was released on 2015-05-15. Since Rust 1.1.0, released on 2015-06-25, a new minor
+//! version has been released every six weeks. The release index does not include release dates,
+//! so dates given by the user, e.g. with `--min-date`, are mapped onto versions using this schedule.
+
+use std::fmt;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::error::CargoMSRVError;
+use crate::manifest::bare_version::BareVersion;
+
+/// The amount of days between two minor releases.
+const RELEASE_CYCLE_DAYS: i64 = 42;
+
+/// A calendar date, formatted as `YYYY-MM-DD`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct Date {
+    year: i64,
+    month: u8,
+    day: u8,
+}
+
+impl Date {
+    pub fn new(year: i64, month: u8, day: u8) -> Self {
+        Self { year, month, day }
+    }
+
+    /// The (UTC) date of the given time.
+    pub(crate) fn of(time: SystemTime) -> Self {
+        let days = time
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs() / 86_400)
+            .unwrap_or_default();
+
+        Self::from_days_since_epoch(days as i64)
+    }
+
+    pub fn year(&self) -> i64 {
+        self.year
+    }
+
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+
+    /// The amount of days since 1970-01-01, in the proleptic Gregorian calendar. See
+    /// <http://howardhinnant.github.io/date_algorithms.html#days_from_civil>.
+    fn days_since_epoch(&self) -> i64 {
+        let month = i64::from(self.month);
+        let year = if month <= 2 { self.year - 1 } else { self.year };
+
+        let era = year.div_euclid(400);
+        let year_of_era = year.rem_euclid(400);
+        let shifted_month = (month + 9) % 12;
+        let day_of_year = (153 * shifted_month + 2) / 5 + i64::from(self.day) - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    /// The date of the given amount of days since 1970-01-01. See
+    /// <http://howardhinnant.github.io/date_algorithms.html#civil_from_days>.
+    pub(crate) fn from_days_since_epoch(days: i64) -> Self {
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let day_of_era = z.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+
+        let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        };
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+        Self::new(year, month as u8, day as u8)
+    }
+}
+
+impl FromStr for Date {
+    type Err = CargoMSRVError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            CargoMSRVError::InvalidConfig(format!(
+                "Given date '{}' is not valid, expected a date like '2022-01-31'",
+                s
+            ))
+        };
+
+        let mut parts = s.trim().splitn(3, '-');
+        let mut next = || parts.next().ok_or_else(invalid);
+
+        let year = next()?.parse::<i64>().map_err(|_| invalid())?;
+        let month = next()?.parse::<u8>().map_err(|_| invalid())?;
+        let day = next()?.parse::<u8>().map_err(|_| invalid())?;
+
+        let date = Self::new(year, month, day);
+
+        // Reject dates which don't exist, like 2022-02-30, since they wouldn't round trip
+        if (1..=12).contains(&month) && Self::from_days_since_epoch(date.days_since_epoch()) == date
+        {
+            Ok(date)
+        } else {
+            Err(invalid())
+        }
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl serde::Serialize for Date {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl schemars::JsonSchema for Date {
+    fn schema_name() -> String {
+        "Date".to_string()
+    }
+
+    fn json_schema(_gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        schemars::schema::SchemaObject {
+            instance_type: Some(schemars::schema::InstanceType::String.into()),
+            format: Some("date".to_string()),
+            ..Default::default()
+        }
+        .into()
+    }
+}
+
+/// The release date of Rust 1.0.0.
+fn first_release() -> i64 {
+    Date::new(2015, 5, 15).days_since_epoch()
+}
+
+/// The release date of Rust 1.1.0, the first release of the six week release cycle.
+fn first_scheduled_release() -> i64 {
+    Date::new(2015, 6, 25).days_since_epoch()
+}
+
+/// The date at which the given minor version of Rust (`1.<minor>.0`) was, or is scheduled to be,
+/// released.
+pub fn release_date(minor: u64) -> Date {
+    let days = match minor {
+        0 => first_release(),
+        minor => first_scheduled_release() + (minor as i64 - 1) * RELEASE_CYCLE_DAYS,
+    };
+
+    Date::from_days_since_epoch(days)
+}
+
+/// The first minor version of Rust which was released on or after the given date.
+pub fn first_release_since(date: Date) -> BareVersion {
+    let days = date.days_since_epoch();
+
+    let minor = if days <= first_release() {
+        0
+    } else {
+        let since_first_scheduled = (days - first_scheduled_release()).max(0);
+        let cycles = (since_first_scheduled + RELEASE_CYCLE_DAYS - 1) / RELEASE_CYCLE_DAYS;
+
+        1 + cycles as u64
+    };
+
+    BareVersion::TwoComponents(1, minor)
+}
+
+/// The last minor version of Rust which was released on or before the given date, or `None` if
+/// the date precedes the release of Rust 1.0.0.
+pub fn last_release_until(date: Date) -> Option<BareVersion> {
+    let days = date.days_since_epoch();
+
+    let minor = if days < first_release() {
+        return None;
+    } else if days < first_scheduled_release() {
+        0
+    } else {
+        1 + ((days - first_scheduled_release()) / RELEASE_CYCLE_DAYS) as u64
+    };
+
+    Some(BareVersion::TwoComponents(1, minor))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[yare::parameterized(
+        first_release = { 0, Date::new(2015, 5, 15) },
+        first_scheduled_release = { 1, Date::new(2015, 6, 25) },
+        edition_2018 = { 31, Date::new(2018, 12, 6) },
+        edition_2021 = { 56, Date::new(2021, 10, 21) },
+        recent = { 64, Date::new(2022, 9, 22) },
+    )]
+    fn date_of_release(minor: u64, expected: Date) {
+        assert_eq!(release_date(minor), expected);
+    }
+
+    #[yare::parameterized(
+        before_first_release = { Date::new(2014, 1, 1), BareVersion::TwoComponents(1, 0) },
+        on_release = { Date::new(2021, 10, 21), BareVersion::TwoComponents(1, 56) },
+        day_after_release = { Date::new(2021, 10, 22), BareVersion::TwoComponents(1, 57) },
+        day_before_release = { Date::new(2021, 10, 20), BareVersion::TwoComponents(1, 56) },
+        between_first_releases = { Date::new(2015, 6, 1), BareVersion::TwoComponents(1, 1) },
+    )]
+    fn first_release_on_or_after(date: Date, expected: BareVersion) {
+        assert_eq!(first_release_since(date), expected);
+    }
+
+    #[yare::parameterized(
+        before_first_release = { Date::new(2015, 5, 14), None },
+        first_release = { Date::new(2015, 5, 15), Some(BareVersion::TwoComponents(1, 0)) },
+        on_release = { Date::new(2021, 10, 21), Some(BareVersion::TwoComponents(1, 56)) },
+        day_before_release = { Date::new(2021, 10, 20), Some(BareVersion::TwoComponents(1, 55)) },
+    )]
+    fn last_release_on_or_before(date: Date, expected: Option<BareVersion>) {
+        assert_eq!(last_release_until(date), expected);
+    }
+
+    #[yare::parameterized(
+        date = { "2022-01-31", Some(Date::new(2022, 1, 31)) },
+        leap_day = { "2020-02-29", Some(Date::new(2020, 2, 29)) },
+        no_leap_day = { "2022-02-29", None },
+        month_out_of_range = { "2022-13-01", None },
+        missing_day = { "2022-01", None },
+        not_a_date = { "yesterday", None },
+    )]
+    fn parse(input: &str, expected: Option<Date>) {
+        assert_eq!(input.parse::<Date>().ok(), expected);
+    }
+
+    #[yare::parameterized(
+        epoch = { 0, Date::new(1970, 1, 1) },
+        leap_day = { 11_016, Date::new(2000, 2, 29) },
+        rust_1_0 = { 16_570, Date::new(2015, 5, 15) },
+        end_of_year = { 19_357, Date::new(2022, 12, 31) },
+        before_epoch = { -1, Date::new(1969, 12, 31) },
+    )]
+    fn date_of_days(days: i64, expected: Date) {
+        assert_eq!(Date::from_days_since_epoch(days), expected);
+        assert_eq!(expected.days_since_epoch(), days);
+    }
+
+    #[test]
+    fn date_of_time() {
+        let time = UNIX_EPOCH + std::time::Duration::from_secs(19_281 * 86_400 + 3600);
+
+        assert_eq!(Date::of(time), Date::new(2022, 10, 16));
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Date::new(2022, 1, 5).to_string(), "2022-01-05");
+    }
+
+    #[test]
+    fn serialize() {
+        let json = serde_json::to_string(&Date::new(2022, 1, 5)).unwrap();
+
+        assert_eq!(json, "\"2022-01-05\"");
+    }
+}
@@ -1,19 +1,28 @@
+use std::time::Instant;
+
 use storyteller::{event_channel, ChannelEventListener, ChannelReporter, EventListener};
 
-use crate::reporter::event::EventScope;
+use crate::reporter::event::ScopeId;
 use crate::TResult;
 
+pub use handler::CiHandler;
 pub use handler::DiscardOutputHandler;
+pub use handler::GitHubCheckHandler;
 pub use handler::HumanProgressHandler;
 pub use handler::JsonHandler;
+pub use handler::OtelHandler;
+pub use handler::WebhookHandler;
+pub use handler::{InvalidEvents, SchemaValidationHandler};
 
 pub use event::{
-    Event, Message,
+    Event, EventScope, LogFileUnavailable, Message, ScopeId,
     TerminateWithFailure, /* fixme: Needed by binary crate, how much do we want to expose here? */
 };
 
 pub(crate) mod event;
 pub(crate) mod handler;
+pub(crate) mod schema;
+pub(crate) mod statistics;
 
 #[cfg(test)]
 mod testing;
@@ -29,7 +38,8 @@ pub trait Reporter:
     storyteller::Reporter<Event = Event, Err = storyteller::ReporterError<Event>>
 {
     /// Perform a (fallible) action within the scope of the `f` closure, and report the start and
-    /// end of this action.
+    /// end of this action. The start and end share a scope id, and the end carries the elapsed
+    /// duration of the action.
     ///
     /// NB: returns a `crate::TResult` (unlike the regular `report_event` which returns
     /// a `Result<(), reporter::Reporter::Err>`), so the result is flattened to `cargo-msrv's`
@@ -40,16 +50,19 @@ pub trait Reporter:
         f: impl Fn() -> TResult<T>,
     ) -> TResult<T> {
         let event = event.into();
+        let id = ScopeId::next();
 
         // Report that the action is starting
-        let begin = event.with_scope(EventScope::Start);
+        let begin = event.start_of_scope(id);
         self.report_event(begin)?;
 
         // Perform the action
+        let start = Instant::now();
         let result = f();
+        let elapsed = start.elapsed();
 
         // Report that the action has finished
-        let end = event.with_scope(EventScope::End);
+        let end = event.end_of_scope(id, elapsed);
         self.report_event(end)?;
 
         result
@@ -92,12 +105,14 @@ mod tests {
             .unwrap();
 
         let events = reporter.wait_for_events();
+        let id = events[0].scope_id().unwrap();
+        let elapsed = events[1].elapsed().unwrap();
 
         assert_eq!(
             &events,
             &[
-                Event::new(Message::Action(event.clone())).with_scope(EventScope::Start),
-                Event::new(Message::Action(event)).with_scope(EventScope::End)
+                Event::new(Message::Action(event.clone())).start_of_scope(id),
+                Event::new(Message::Action(event)).end_of_scope(id, elapsed)
             ]
         );
 
@@ -117,15 +132,40 @@ mod tests {
             .unwrap_err();
 
         let events = reporter.wait_for_events();
+        let id = events[0].scope_id().unwrap();
+        let elapsed = events[1].elapsed().unwrap();
 
         assert_eq!(
             &events,
             &[
-                Event::new(Message::Action(event.clone())).with_scope(EventScope::Start),
-                Event::new(Message::Action(event)).with_scope(EventScope::End)
+                Event::new(Message::Action(event.clone())).start_of_scope(id),
+                Event::new(Message::Action(event)).end_of_scope(id, elapsed)
             ]
         );
 
         assert!(matches!(out, CargoMSRVError::Storyteller));
     }
+
+    #[test]
+    fn scopes_have_distinct_ids() {
+        let reporter = TestReporter::default();
+        let event = ActionMessage::new(Action::Find);
+
+        for _ in 0..2 {
+            reporter
+                .reporter()
+                .run_scoped_event(event.clone(), || TResult::<()>::Ok(()))
+                .unwrap();
+        }
+
+        let ids = reporter
+            .wait_for_events()
+            .iter()
+            .map(|event| event.scope_id().unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(ids[0], ids[1]);
+        assert_eq!(ids[2], ids[3]);
+        assert_ne!(ids[0], ids[2]);
+    }
 }
@@ -1,47 +1,138 @@
 use std::fmt;
 use std::fmt::Formatter;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 pub use action::ActionMessage;
 pub use auxiliary_output::{
     AuxiliaryOutput, Destination, Item as AuxiliaryOutputItem, MsrvKind, ToolchainFileKind,
 };
+pub use check_output::{CheckOutput, OutputStream};
 pub use check_toolchain::CheckToolchain;
-pub use compatibility::{Compatibility, CompatibilityReport};
+pub use compatibility::{CheckTiming, Compatibility, CompatibilityReport, EncodingNote};
 pub use compatibility_check_method::{CompatibilityCheckMethod, Method};
+pub use component_unavailable::ComponentUnavailable;
+pub use dependency_impact::{DependencyImpact, ImpactVerdict};
+pub use discovered_projects::{DiscoveredProject, DiscoveredProjects, ProjectKind};
+pub use edition_advice::{EditionAdvice, EditionRequirement, MigrationCheck};
+pub use embedded_index_fallback::EmbeddedIndexFallback;
+pub use excluded_binaries::ExcludedBinaries;
+pub use exhaustive_search::{CheckedRelease, ExhaustiveSearch};
+pub use failure_log::FailureLog;
 pub use fetch_index::FetchIndex;
+pub use fetch_index_progress::FetchIndexProgress;
+pub use incompatible_lockfile::IncompatibleLockfile;
+pub use inconclusive_result::InconclusiveResult;
+pub(crate) use list_dep::orphans;
 pub use list_dep::ListDep;
+pub use log_file_unavailable::LogFileUnavailable;
+pub use manifest_syntax::ManifestSyntax;
+pub use manifest_validation::{Diagnostic, DiagnosticCode, ManifestValidation, Severity, Span};
 pub use meta::Meta;
-pub use msrv_result::MsrvResult;
-pub use progress::Progress;
+pub use missing_prerequisites::{MissingPrerequisite, MissingPrerequisites};
+pub use msrv_history::{HistoryEntry, MsrvChange, MsrvHistory};
+pub use msrv_lowering::{Blocker, LoweringStep, MsrvLowering};
+pub use msrv_matrix::{MatrixCell, MatrixRow, MsrvMatrix};
+pub use msrv_result::{DependencyCache, MsrvResult};
+pub use policy_check::{MsrvSubject, PolicyCheck, PolicyViolation};
+pub use preference_set::PreferenceSet;
+pub use prerequisite_install::PrerequisiteInstall;
+pub use progress::{Progress, ProgressUnit};
+pub use release_list::{Exclusion, ListedRelease, ReleaseList};
+pub use report_diff::{ConfigurationChange, ReportDiff};
+pub use required_rust_version::RequiredRustVersion;
+pub use resolved_config::{ConfigOption, ConfigValue, ResolvedConfig};
+pub use retry_check::RetryCheck;
+pub use revision_checkout::RevisionCheckout;
+pub use run_summary::{CacheCounts, Durations, RunSummary, ToolchainCounts};
+pub use search_decision::{SearchDecision, SearchRange};
 pub use search_method::FindMsrv;
+pub use search_space_clamped::SearchSpaceClamped;
+pub use serving::Serving;
 pub use set_output::SetOutputMessage;
 pub use setup_toolchain::SetupToolchain;
 pub use show_output::ShowOutputMessage;
+pub use skipped_member::{SkipReason, SkippedMember};
 pub use termination::TerminateWithFailure;
+pub use tool_config_msrv::ToolConfigMsrv;
+pub use unit_graph_reuse::{ReuseDecision, UnitGraphReuse};
+pub use version_resolved::VersionResolved;
+pub use wrapper_cache_stats::WrapperCacheStats;
 
 mod action;
 mod auxiliary_output;
+mod check_output;
 mod check_toolchain;
 mod compatibility;
 mod compatibility_check_method;
+mod component_unavailable;
+mod dependency_impact;
+mod discovered_projects;
+mod edition_advice;
+mod embedded_index_fallback;
+mod excluded_binaries;
+mod exhaustive_search;
+mod failure_log;
 mod fetch_index;
+mod fetch_index_progress;
+mod incompatible_lockfile;
+mod inconclusive_result;
 mod list_dep;
+mod log_file_unavailable;
+mod manifest_syntax;
+mod manifest_validation;
 mod meta;
+mod missing_prerequisites;
+mod msrv_history;
+mod msrv_lowering;
+mod msrv_matrix;
 mod msrv_result;
+mod policy_check;
+mod preference_set;
+mod prerequisite_install;
 mod progress;
+mod release_list;
+mod report_diff;
+mod required_rust_version;
+mod resolved_config;
+mod retry_check;
+mod revision_checkout;
+mod run_summary;
+mod search_decision;
 mod search_method;
+mod search_space_clamped;
+mod serving;
 mod set_output;
 mod setup_toolchain;
 mod show_output;
+mod skipped_member;
 mod termination;
+mod tool_config_msrv;
+mod unit_graph_reuse;
+mod version_resolved;
+mod wrapper_cache_stats;
 
-#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+#[derive(Clone, Debug, serde::Serialize, schemars::JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct Event {
     #[serde(flatten)]
     message: Message,
+    /// The wall-clock time at which the event was emitted, in seconds since the unix epoch
+    #[serde(serialize_with = "serialize_unix_secs")]
+    #[schemars(with = "f64")]
+    timestamp: SystemTime,
     #[serde(skip_serializing_if = "Option::is_none")]
     scope: Option<EventScope>,
+    /// Shared by the start and the end of a scope, so they can be correlated
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scope_id: Option<ScopeId>,
+    /// The time between the start and the end of a scope, only set at its end
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "compatibility::serialize_optional_secs"
+    )]
+    #[schemars(with = "Option<f64>")]
+    elapsed: Option<Duration>,
 }
 
 impl Event {
@@ -49,7 +140,10 @@ impl Event {
     pub(crate) fn new(message: Message) -> Self {
         Self {
             message,
+            timestamp: SystemTime::now(),
             scope: None,
+            scope_id: None,
+            elapsed: None,
         }
     }
 
@@ -60,17 +154,71 @@ impl Event {
     pub(crate) fn with_scope(&self, scope: EventScope) -> Self {
         let mut cloned = self.clone();
         cloned.scope = Some(scope);
+        // the start and the end of a scope are emitted at different times
+        cloned.timestamp = SystemTime::now();
         cloned
     }
 
+    /// The start of the scope with the given id.
+    pub(crate) fn start_of_scope(&self, id: ScopeId) -> Self {
+        let mut start = self.with_scope(EventScope::Start);
+        start.scope_id = Some(id);
+        start
+    }
+
+    /// The end of the scope with the given id, which took `elapsed` since its start.
+    pub(crate) fn end_of_scope(&self, id: ScopeId, elapsed: Duration) -> Self {
+        let mut end = self.with_scope(EventScope::End);
+        end.scope_id = Some(id);
+        end.elapsed = Some(elapsed);
+        end
+    }
+
     /// Returns `true` if this is the start of the scope, _or_, if this event has no inner scope.
     pub fn is_scope_start(&self) -> bool {
         matches!(self.scope, None | Some(EventScope::Start))
     }
+
+    pub fn scope(&self) -> Option<EventScope> {
+        self.scope
+    }
+
+    pub fn scope_id(&self) -> Option<ScopeId> {
+        self.scope_id
+    }
+
+    /// The duration of the scope, if this is the end of a scope.
+    pub fn elapsed(&self) -> Option<Duration> {
+        self.elapsed
+    }
+
+    /// The wall-clock time at which the event was emitted.
+    pub fn timestamp(&self) -> SystemTime {
+        self.timestamp
+    }
+}
+
+// The time at which an event is emitted differs for each run, so it's not compared
+impl PartialEq for Event {
+    fn eq(&self, other: &Self) -> bool {
+        self.message == other.message
+            && self.scope == other.scope
+            && self.scope_id == other.scope_id
+            && self.elapsed == other.elapsed
+    }
+}
+
+fn serialize_unix_secs<S>(time: &SystemTime, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+
+    serializer.serialize_f64(since_epoch.as_secs_f64())
 }
 
 /// Messages are a kind of event which report the state of this program to the user
-#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
 #[serde(rename_all = "snake_case")]
 #[serde(tag = "type")]
 pub enum Message {
@@ -78,18 +226,43 @@ pub enum Message {
 
     // setup
     Meta(Meta),
+    LogFileUnavailable(LogFileUnavailable),
+    MissingPrerequisites(MissingPrerequisites),
+    PrerequisiteInstall(PrerequisiteInstall),
+    RevisionCheckout(RevisionCheckout),
 
     // get rust-releases index
     FetchIndex(FetchIndex), // todo!
+    FetchIndexProgress(FetchIndexProgress),
+    EmbeddedIndexFallback(EmbeddedIndexFallback),
 
     // todo: SkippedRustVersions // +reason
 
     // install toolchain
     SetupToolchain(SetupToolchain),
+    SearchSpaceClamped(SearchSpaceClamped),
+
+    // msrv which matches none of the releases
+    VersionResolved(VersionResolved),
+
+    // manifest syntax which the cargo of older toolchains can't read
+    ManifestSyntax(ManifestSyntax),
+
+    // targets excluded from the check
+    ExcludedBinaries(ExcludedBinaries),
+
+    // lockfile which the cargo of a toolchain can't read
+    IncompatibleLockfile(IncompatibleLockfile),
 
     // runner + pass/reject
     CheckToolchain(CheckToolchain),
     CompatibilityCheckMethod(CompatibilityCheckMethod),
+    CheckOutput(CheckOutput),
+    RetryCheck(RetryCheck),
+    UnitGraphReuse(UnitGraphReuse),
+    WrapperCacheStats(WrapperCacheStats),
+    RequiredRustVersion(RequiredRustVersion),
+    ComponentUnavailable(ComponentUnavailable),
     Compatibility(Compatibility),
 
     // output written by the program
@@ -97,11 +270,14 @@ pub enum Message {
 
     // command: find
     MsrvResult(MsrvResult),
+    InconclusiveResult(InconclusiveResult),
     FindMsrv(FindMsrv),
     Progress(Progress),
+    SearchDecision(SearchDecision),
+    ExhaustiveSearch(ExhaustiveSearch),
 
     // command: verify
-    // Verify
+    SkippedMember(SkippedMember),
 
     // command: list
     ListDep(ListDep),
@@ -111,6 +287,50 @@ pub enum Message {
 
     // command: show
     ShowOutput(ShowOutputMessage),
+    ToolConfigMsrv(ToolConfigMsrv),
+
+    // command: report
+    ReportDiff(ReportDiff),
+
+    // command: config
+    ResolvedConfig(ResolvedConfig),
+    PreferenceSet(PreferenceSet),
+
+    // command: edition
+    EditionAdvice(EditionAdvice),
+
+    // command: releases
+    ReleaseList(ReleaseList),
+
+    // command: matrix
+    MsrvMatrix(MsrvMatrix),
+
+    // command: policy
+    PolicyCheck(PolicyCheck),
+
+    // command: discover
+    DiscoveredProjects(DiscoveredProjects),
+
+    // command: validate
+    ManifestValidation(ManifestValidation),
+
+    // command: last-failure
+    FailureLog(FailureLog),
+
+    // command: impact
+    DependencyImpact(DependencyImpact),
+
+    // command: history
+    MsrvHistory(MsrvHistory),
+
+    // command: lower
+    MsrvLowering(MsrvLowering),
+
+    // command: serve
+    Serving(Serving),
+
+    // Statistics of the run, once it is over
+    RunSummary(RunSummary),
 
     // Termination, for example when caused by an unrecoverable error
     TerminateWithFailure(TerminateWithFailure),
@@ -120,7 +340,10 @@ impl From<Message> for Event {
     fn from(message: Message) -> Self {
         Event {
             message,
+            timestamp: SystemTime::now(),
             scope: None,
+            scope_id: None,
+            elapsed: None,
         }
     }
 }
@@ -137,9 +360,71 @@ impl fmt::Display for Message {
     }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, serde::Serialize)]
+#[derive(Debug, Copy, Clone, PartialEq, serde::Serialize, schemars::JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum EventScope {
     Start,
     End,
 }
+
+/// Identifies a scope, i.e. a start and end event pair, among the events of a single run.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, serde::Serialize, schemars::JsonSchema)]
+#[serde(transparent)]
+pub struct ScopeId(u64);
+
+impl ScopeId {
+    /// A new id, distinct from all ids created before it.
+    pub(crate) fn next() -> Self {
+        static NEXT: AtomicU64 = AtomicU64::new(1);
+
+        Self(NEXT.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ReleaseSource;
+
+    #[test]
+    fn serialize_end_of_scope() {
+        let event = Event::from(FetchIndex::new(ReleaseSource::RustChangelog))
+            .end_of_scope(ScopeId(7), Duration::from_millis(1500));
+
+        let json = serde_json::to_value(&event).unwrap();
+
+        assert_eq!(json["scope"], "end");
+        assert_eq!(json["scope_id"], 7);
+        assert_eq!(json["elapsed"], 1.5);
+    }
+
+    #[test]
+    fn serialize_timestamp() {
+        let mut event = Event::from(FetchIndex::new(ReleaseSource::RustChangelog));
+        event.timestamp = UNIX_EPOCH + Duration::from_millis(1_665_909_000_250);
+
+        let json = serde_json::to_value(&event).unwrap();
+
+        assert_eq!(json["timestamp"], 1_665_909_000.25);
+    }
+
+    #[test]
+    fn timestamp_is_not_compared() {
+        let event = Event::from(FetchIndex::new(ReleaseSource::RustChangelog));
+        let mut later = event.clone();
+        later.timestamp += Duration::from_secs(1);
+
+        assert_eq!(event, later);
+    }
+
+    #[test]
+    fn unscoped_event_has_no_scope_fields() {
+        let event = Event::from(FetchIndex::new(ReleaseSource::RustChangelog));
+
+        let json = serde_json::to_value(&event).unwrap();
+
+        assert!(json.get("scope").is_none());
+        assert!(json.get("scope_id").is_none());
+        assert!(json.get("elapsed").is_none());
+    }
+}
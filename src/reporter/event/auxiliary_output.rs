@@ -2,7 +2,7 @@ use crate::reporter::event::Message;
 use crate::Event;
 use std::path::PathBuf;
 
-#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct AuxiliaryOutput {
     destination: Destination,
@@ -21,17 +21,22 @@ impl From<AuxiliaryOutput> for Event {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum Destination {
     File(PathBuf),
 }
 
-#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum Item {
     Msrv { kind: MsrvKind },
     ToolchainFile { kind: ToolchainFileKind },
+    // The MSRV and check configuration, as written with `--write-result`
+    ResultFile,
+    // The JSON schema of the given type of message, or of all messages, as written by
+    // `cargo msrv schema --out-dir`
+    SchemaFile { message: Option<String> },
 }
 
 impl Item {
@@ -42,9 +47,17 @@ impl Item {
     pub fn toolchain_file(kind: ToolchainFileKind) -> Self {
         Self::ToolchainFile { kind }
     }
+
+    pub fn result_file() -> Self {
+        Self::ResultFile
+    }
+
+    pub fn schema_file(message: Option<String>) -> Self {
+        Self::SchemaFile { message }
+    }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum MsrvKind {
     // The package.rust-version as supported by the Cargo Manifest format.
@@ -54,10 +67,10 @@ pub enum MsrvKind {
     MetadataFallback,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ToolchainFileKind {
-    /* Legacy, : Unsupported right now */
+    Legacy,
     Toml,
 }
 
@@ -73,7 +86,11 @@ mod tests {
     #[yare::parameterized(
         rust_version_msrv = { Item::msrv(MsrvKind::RustVersion) },
         metadata_fallback_msrv = { Item::msrv(MsrvKind::MetadataFallback) },
+        toolchain_file_legacy = { Item::toolchain_file(ToolchainFileKind::Legacy) },
         toolchain_file_toml = { Item::toolchain_file(ToolchainFileKind::Toml) },
+        result_file = { Item::result_file() },
+        schema_file = { Item::schema_file(Some("msrv_result".to_string())) },
+        event_schema_file = { Item::schema_file(None) },
     )]
     fn reported_action(item: Item) {
         let reporter = TestReporter::default();
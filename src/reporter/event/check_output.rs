@@ -0,0 +1,80 @@
+use crate::reporter::event::Message;
+use crate::toolchain::OwnedToolchainSpec;
+use crate::Event;
+
+/// Reported for each line which the check command of a toolchain writes, while it runs, when
+/// `--verbose` is given, so the progress of a long running check can be followed.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct CheckOutput {
+    toolchain: OwnedToolchainSpec,
+    stream: OutputStream,
+    /// The line, without its line ending
+    line: String,
+}
+
+impl CheckOutput {
+    pub fn new(
+        toolchain: impl Into<OwnedToolchainSpec>,
+        stream: OutputStream,
+        line: impl Into<String>,
+    ) -> Self {
+        Self {
+            toolchain: toolchain.into(),
+            stream,
+            line: line.into(),
+        }
+    }
+
+    pub fn toolchain(&self) -> &OwnedToolchainSpec {
+        &self.toolchain
+    }
+
+    pub fn stream(&self) -> OutputStream {
+        self.stream
+    }
+
+    pub fn line(&self) -> &str {
+        &self.line
+    }
+}
+
+impl From<CheckOutput> for Event {
+    fn from(it: CheckOutput) -> Self {
+        Message::CheckOutput(it).into()
+    }
+}
+
+/// The stream of the check command to which a line was written.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputStream {
+    Stdout,
+    Stderr,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::event::Message;
+    use crate::reporter::TestReporter;
+    use crate::semver;
+    use storyteller::Reporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporter::default();
+        let event = CheckOutput::new(
+            OwnedToolchainSpec::new(&semver::Version::new(1, 56, 0), "x86_64-unknown-linux-gnu"),
+            OutputStream::Stderr,
+            "   Compiling libc v0.2.126",
+        );
+
+        reporter.reporter().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::new(Message::CheckOutput(event)),]
+        );
+    }
+}
@@ -1,13 +1,19 @@
 use crate::reporter::event::Message;
 use crate::toolchain::OwnedToolchainSpec;
 use crate::Event;
+use std::time::Duration;
 
-#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct Compatibility {
     pub toolchain: OwnedToolchainSpec,
     decision: bool,
     pub compatibility_report: CompatibilityReport,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    timing: Option<CheckTiming>,
+    /// Set when the output of the check command was not valid UTF-8
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    encoding_note: Option<EncodingNote>,
 }
 
 impl Compatibility {
@@ -16,6 +22,8 @@ impl Compatibility {
             toolchain: toolchain.into(),
             decision: true,
             compatibility_report: CompatibilityReport::Compatible,
+            timing: None,
+            encoding_note: None,
         }
     }
 
@@ -26,9 +34,21 @@ impl Compatibility {
             compatibility_report: CompatibilityReport::Incompatible {
                 error: error.map(Into::into),
             },
+            timing: None,
+            encoding_note: None,
         }
     }
 
+    pub fn with_timing(mut self, timing: CheckTiming) -> Self {
+        self.timing = Some(timing);
+        self
+    }
+
+    pub fn with_encoding_note(mut self, note: EncodingNote) -> Self {
+        self.encoding_note = Some(note);
+        self
+    }
+
     pub fn toolchain(&self) -> &OwnedToolchainSpec {
         &self.toolchain
     }
@@ -36,6 +56,16 @@ impl Compatibility {
     pub fn is_compatible(&self) -> bool {
         self.decision
     }
+
+    pub fn timing(&self) -> Option<&CheckTiming> {
+        self.timing.as_ref()
+    }
+
+    /// Whether the reported error was decoded lossily, since the output of the check command was
+    /// not valid UTF-8.
+    pub fn encoding_note(&self) -> Option<&EncodingNote> {
+        self.encoding_note.as_ref()
+    }
 }
 
 impl From<Compatibility> for Event {
@@ -44,13 +74,149 @@ impl From<Compatibility> for Event {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum CompatibilityReport {
     Compatible,
     Incompatible { error: Option<String> },
 }
 
+/// The output of the check command was not valid UTF-8, e.g. because localized MSVC tooling on
+/// Windows writes its messages in the code page of the system. Each invalid byte sequence is
+/// replaced by `U+FFFD` in the reported error, and the raw output is written to the log.
+#[derive(
+    Clone, Copy, Debug, Eq, PartialEq, serde::Serialize, serde::Deserialize, schemars::JsonSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub struct EncodingNote {
+    invalid_sequences: usize,
+}
+
+impl EncodingNote {
+    /// The note for the given output, if it is not valid UTF-8.
+    pub fn of(output: &[u8]) -> Option<Self> {
+        let mut invalid_sequences = 0;
+        let mut rest = output;
+
+        while let Err(error) = std::str::from_utf8(rest) {
+            invalid_sequences += 1;
+
+            // a sequence which is cut off by the end of the output has no error length
+            let valid = error.valid_up_to();
+            let invalid = error.error_len().unwrap_or(rest.len() - valid);
+            rest = &rest[valid + invalid..];
+        }
+
+        if invalid_sequences > 0 {
+            Some(Self { invalid_sequences })
+        } else {
+            None
+        }
+    }
+
+    /// The number of byte sequences which were replaced, as `String::from_utf8_lossy` does.
+    pub fn invalid_sequences(&self) -> usize {
+        self.invalid_sequences
+    }
+}
+
+/// How long it took to run the check command for a toolchain.
+#[derive(
+    Clone, Debug, Eq, PartialEq, serde::Serialize, serde::Deserialize, schemars::JsonSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub struct CheckTiming {
+    /// Wall-clock time of the check command, including e.g. dependency resolution
+    #[serde(
+        serialize_with = "serialize_secs",
+        deserialize_with = "deserialize_secs"
+    )]
+    #[schemars(with = "f64")]
+    wall_clock: Duration,
+    /// Build time as reported by cargo, if the check command reported it
+    #[serde(
+        default,
+        serialize_with = "serialize_optional_secs",
+        deserialize_with = "deserialize_optional_secs"
+    )]
+    #[schemars(with = "Option<f64>")]
+    cargo_reported: Option<Duration>,
+}
+
+impl CheckTiming {
+    pub fn new(wall_clock: Duration, cargo_reported: Option<Duration>) -> Self {
+        Self {
+            wall_clock,
+            cargo_reported,
+        }
+    }
+
+    pub fn wall_clock(&self) -> Duration {
+        self.wall_clock
+    }
+
+    pub fn cargo_reported(&self) -> Option<Duration> {
+        self.cargo_reported
+    }
+
+    /// The timing of this check, followed by the `next` check for the same toolchain.
+    pub fn followed_by(self, next: CheckTiming) -> Self {
+        let cargo_reported = match (self.cargo_reported, next.cargo_reported) {
+            (Some(lhs), Some(rhs)) => Some(lhs + rhs),
+            (lhs, rhs) => lhs.or(rhs),
+        };
+
+        Self::new(self.wall_clock + next.wall_clock, cargo_reported)
+    }
+}
+
+pub(super) fn serialize_secs<S>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serializer.serialize_f64(duration.as_secs_f64())
+}
+
+pub(super) fn serialize_optional_secs<S>(
+    duration: &Option<Duration>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    match duration {
+        Some(duration) => serialize_secs(duration, serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
+pub(super) fn deserialize_secs<'de, D>(deserializer: D) -> Result<Duration, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let secs = <f64 as serde::Deserialize>::deserialize(deserializer)?;
+    duration_from_secs(secs)
+}
+
+fn deserialize_optional_secs<'de, D>(deserializer: D) -> Result<Option<Duration>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let secs = <Option<f64> as serde::Deserialize>::deserialize(deserializer)?;
+    secs.map(duration_from_secs).transpose()
+}
+
+fn duration_from_secs<E: serde::de::Error>(secs: f64) -> Result<Duration, E> {
+    if secs.is_finite() && secs >= 0.0 {
+        Ok(Duration::from_secs_f64(secs))
+    } else {
+        Err(E::custom(format!(
+            "Value '{}' is not a valid duration in seconds",
+            secs
+        )))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -93,4 +259,108 @@ mod tests {
             vec![Event::new(Message::Compatibility(event)),]
         );
     }
+
+    #[yare::parameterized(
+        wall_clock_only = { CheckTiming::new(Duration::from_millis(2500), None), r#"{"wall_clock":2.5,"cargo_reported":null}"# },
+        cargo_reported = { CheckTiming::new(Duration::from_secs(3), Some(Duration::from_millis(1250))), r#"{"wall_clock":3.0,"cargo_reported":1.25}"# },
+    )]
+    fn serialized_timing(timing: CheckTiming, expected: &str) {
+        assert_eq!(serde_json::to_string(&timing).unwrap(), expected);
+        assert_eq!(
+            serde_json::from_str::<CheckTiming>(expected).unwrap(),
+            timing
+        );
+    }
+
+    #[test]
+    fn deserialize_reported_json() {
+        let json = r#"{"type":"compatibility","toolchain":{"version":"1.56.0","target":"x86_64-unknown-linux-gnu"},"decision":false,"compatibility_report":{"incompatible":{"error":"whoo!"}}}"#;
+
+        let compatibility = serde_json::from_str::<Compatibility>(json).unwrap();
+
+        assert_eq!(
+            compatibility,
+            Compatibility::incompatible(
+                OwnedToolchainSpec::new(
+                    &semver::Version::new(1, 56, 0),
+                    "x86_64-unknown-linux-gnu"
+                ),
+                Some("whoo!".to_string()),
+            )
+        );
+    }
+
+    #[test]
+    fn deserialize_negative_duration() {
+        let json = r#"{"wall_clock":-1.0,"cargo_reported":null}"#;
+
+        assert!(serde_json::from_str::<CheckTiming>(json).is_err());
+    }
+
+    #[test]
+    fn reported_compatible_toolchain_with_timing() {
+        let reporter = TestReporter::default();
+        let event = Compatibility::compatible(OwnedToolchainSpec::new(
+            &semver::Version::new(1, 2, 3),
+            "test_target",
+        ))
+        .with_timing(CheckTiming::new(Duration::from_secs(1), None));
+
+        reporter.reporter().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::new(Message::Compatibility(event)),]
+        );
+    }
+
+    #[yare::parameterized(
+        both_reported = { Some(Duration::from_secs(1)), Some(Duration::from_secs(2)), Some(Duration::from_secs(3)) },
+        one_reported = { None, Some(Duration::from_secs(2)), Some(Duration::from_secs(2)) },
+        none_reported = { None, None, None },
+    )]
+    fn followed_by(lhs: Option<Duration>, rhs: Option<Duration>, expected: Option<Duration>) {
+        let timing = CheckTiming::new(Duration::from_secs(4), lhs)
+            .followed_by(CheckTiming::new(Duration::from_secs(5), rhs));
+
+        assert_eq!(timing.wall_clock(), Duration::from_secs(9));
+        assert_eq!(timing.cargo_reported(), expected);
+    }
+
+    #[yare::parameterized(
+        valid = { b"error[E0658]: \xe2\x9c\x93", None },
+        empty = { b"", None },
+        latin1 = { b"Fehler: \xe4nderung \xfcberpr\xfcfen", Some(3) },
+        consecutive = { b"\xff\xfe", Some(2) },
+        truncated = { b"error \xe2\x9c", Some(1) },
+    )]
+    fn encoding_note(output: &[u8], expected: Option<usize>) {
+        let note = EncodingNote::of(output);
+
+        assert_eq!(note.map(|note| note.invalid_sequences()), expected);
+        assert_eq!(
+            note.map(|note| note.invalid_sequences()),
+            Some(String::from_utf8_lossy(output).matches('\u{FFFD}').count()).filter(|&n| n > 0)
+        );
+    }
+
+    #[test]
+    fn serialize_encoding_note() {
+        let event = Compatibility::incompatible(
+            OwnedToolchainSpec::new(&semver::Version::new(1, 56, 0), "x86_64-pc-windows-msvc"),
+            Some("Fehler".to_string()),
+        )
+        .with_encoding_note(EncodingNote::of(b"Fehler \xfc").unwrap());
+
+        let json = serde_json::to_value(&event).unwrap();
+
+        assert_eq!(
+            json["encoding_note"],
+            serde_json::json!({ "invalid_sequences": 1 })
+        );
+        assert_eq!(
+            serde_json::from_value::<Compatibility>(json).unwrap(),
+            event
+        );
+    }
 }
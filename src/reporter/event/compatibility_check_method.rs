@@ -3,7 +3,7 @@ use crate::toolchain::OwnedToolchainSpec;
 use crate::Event;
 use std::path::{Path, PathBuf};
 
-#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct CompatibilityCheckMethod {
     toolchain: OwnedToolchainSpec,
@@ -25,7 +25,7 @@ impl From<CompatibilityCheckMethod> for Event {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum Method {
     RustupRun {
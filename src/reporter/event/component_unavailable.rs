@@ -0,0 +1,63 @@
+use crate::reporter::event::Message;
+use crate::toolchain::OwnedToolchainSpec;
+use crate::Event;
+
+/// Reported when a toolchain is rejected without checking it, because its release didn't ship a
+/// component which the check requires, like `clippy` for `cargo clippy`, for the host of the
+/// toolchain.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ComponentUnavailable {
+    /// The rejected toolchain
+    toolchain: OwnedToolchainSpec,
+    /// The required components which were not shipped
+    components: Vec<String>,
+}
+
+impl ComponentUnavailable {
+    pub fn new(toolchain: impl Into<OwnedToolchainSpec>, components: Vec<String>) -> Self {
+        Self {
+            toolchain: toolchain.into(),
+            components,
+        }
+    }
+
+    pub fn toolchain(&self) -> &OwnedToolchainSpec {
+        &self.toolchain
+    }
+
+    pub fn components(&self) -> &[String] {
+        &self.components
+    }
+}
+
+impl From<ComponentUnavailable> for Event {
+    fn from(it: ComponentUnavailable) -> Self {
+        Message::ComponentUnavailable(it).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::event::Message;
+    use crate::reporter::TestReporter;
+    use crate::semver;
+    use storyteller::Reporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporter::default();
+        let event = ComponentUnavailable::new(
+            OwnedToolchainSpec::new(&semver::Version::new(1, 28, 0), "test_target"),
+            vec!["clippy".to_string()],
+        );
+
+        reporter.reporter().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::new(Message::ComponentUnavailable(event)),]
+        );
+    }
+}
@@ -0,0 +1,135 @@
+use crate::config::impact::ProspectiveDependency;
+use crate::manifest::bare_version::BareVersion;
+use crate::reporter::event::Message;
+use crate::semver;
+use crate::Event;
+use std::fmt;
+
+/// Whether adding a dependency would force the MSRV of a crate up, as reported by
+/// `cargo msrv impact`.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct DependencyImpact {
+    dependency: ProspectiveDependency,
+    msrv: BareVersion,
+    verdict: ImpactVerdict,
+}
+
+impl DependencyImpact {
+    pub fn new(
+        dependency: ProspectiveDependency,
+        msrv: BareVersion,
+        verdict: ImpactVerdict,
+    ) -> Self {
+        Self {
+            dependency,
+            msrv,
+            verdict,
+        }
+    }
+
+    pub fn dependency(&self) -> &ProspectiveDependency {
+        &self.dependency
+    }
+
+    pub fn msrv(&self) -> &BareVersion {
+        &self.msrv
+    }
+
+    pub fn verdict(&self) -> &ImpactVerdict {
+        &self.verdict
+    }
+}
+
+impl fmt::Display for DependencyImpact {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.verdict {
+            ImpactVerdict::Compatible => write!(
+                f,
+                "Adding {} keeps the MSRV: Rust {} is still compatible",
+                self.dependency, self.msrv
+            ),
+            ImpactVerdict::RequiresBump { msrv: Some(msrv) } => write!(
+                f,
+                "Adding {} forces an MSRV bump from Rust {} to Rust {}",
+                self.dependency, self.msrv, msrv
+            ),
+            ImpactVerdict::RequiresBump { msrv: None } => write!(
+                f,
+                "Adding {} forces an MSRV bump: Rust {} is no longer compatible (use --find to find the new MSRV)",
+                self.dependency, self.msrv
+            ),
+            ImpactVerdict::NoCompatibleRelease => write!(
+                f,
+                "Adding {} is not compatible with Rust {}, nor with any more recent release",
+                self.dependency, self.msrv
+            ),
+        }
+    }
+}
+
+impl From<DependencyImpact> for Event {
+    fn from(it: DependencyImpact) -> Self {
+        Message::DependencyImpact(it).into()
+    }
+}
+
+/// The impact of a dependency on the MSRV.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case", tag = "kind")]
+pub enum ImpactVerdict {
+    /// The crate is still compatible with its MSRV, with the dependency
+    Compatible,
+    /// The crate is no longer compatible with its MSRV. The new MSRV is only known when it was
+    /// searched for, with `--find`.
+    RequiresBump { msrv: Option<semver::Version> },
+    /// The crate isn't compatible with any release from the MSRV onwards, with the dependency
+    NoCompatibleRelease,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::event::Message;
+    use crate::reporter::TestReporter;
+    use std::str::FromStr;
+    use storyteller::Reporter;
+
+    fn impact(verdict: ImpactVerdict) -> DependencyImpact {
+        DependencyImpact::new(
+            ProspectiveDependency::from_str("serde@1.0.190").unwrap(),
+            BareVersion::TwoComponents(1, 56),
+            verdict,
+        )
+    }
+
+    #[yare::parameterized(
+        compatible = { ImpactVerdict::Compatible },
+        requires_bump = { ImpactVerdict::RequiresBump { msrv: Some(semver::Version::new(1, 60, 0)) } },
+        requires_unknown_bump = { ImpactVerdict::RequiresBump { msrv: None } },
+        no_compatible_release = { ImpactVerdict::NoCompatibleRelease },
+    )]
+    fn reported_event(verdict: ImpactVerdict) {
+        let reporter = TestReporter::default();
+        let event = impact(verdict);
+
+        reporter.reporter().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::new(Message::DependencyImpact(event)),]
+        );
+    }
+
+    #[test]
+    fn display() {
+        let event = impact(ImpactVerdict::RequiresBump {
+            msrv: Some(semver::Version::new(1, 60, 0)),
+        });
+
+        assert_eq!(
+            event.to_string(),
+            "Adding serde@1.0.190 forces an MSRV bump from Rust 1.56 to Rust 1.60.0"
+        );
+    }
+}
@@ -0,0 +1,218 @@
+use crate::manifest::bare_version::BareVersion;
+use crate::reporter::event::Message;
+use crate::Event;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// The Cargo projects which were found in a directory tree by `cargo msrv discover`, with their
+/// declared MSRV.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct DiscoveredProjects {
+    /// The directory which was scanned
+    root: PathBuf,
+    projects: Vec<DiscoveredProject>,
+}
+
+impl DiscoveredProjects {
+    pub fn new(root: impl Into<PathBuf>, projects: Vec<DiscoveredProject>) -> Self {
+        Self {
+            root: root.into(),
+            projects,
+        }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn projects(&self) -> &[DiscoveredProject] {
+        &self.projects
+    }
+}
+
+impl fmt::Display for DiscoveredProjects {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for project in &self.projects {
+            writeln!(f, "{}", project)?;
+        }
+
+        writeln!(
+            f,
+            "{} projects discovered in {}",
+            self.projects.len(),
+            self.root.display()
+        )
+    }
+}
+
+impl From<DiscoveredProjects> for Event {
+    fn from(it: DiscoveredProjects) -> Self {
+        Message::DiscoveredProjects(it).into()
+    }
+}
+
+/// A directory with a Cargo manifest.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct DiscoveredProject {
+    /// The directory of the project, relative to the scanned directory
+    path: PathBuf,
+    kind: ProjectKind,
+    /// The name of the package, if the manifest declares one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    /// The MSRV declared by the manifest, or inherited from the workspace
+    #[serde(skip_serializing_if = "Option::is_none")]
+    msrv: Option<BareVersion>,
+    /// The root of the workspace of which the project is a member, relative to the scanned
+    /// directory
+    #[serde(skip_serializing_if = "Option::is_none")]
+    workspace: Option<PathBuf>,
+}
+
+impl DiscoveredProject {
+    pub fn new(
+        path: impl Into<PathBuf>,
+        kind: ProjectKind,
+        name: Option<String>,
+        msrv: Option<BareVersion>,
+        workspace: Option<PathBuf>,
+    ) -> Self {
+        Self {
+            path: path.into(),
+            kind,
+            name,
+            msrv,
+            workspace,
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn kind(&self) -> ProjectKind {
+        self.kind
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn msrv(&self) -> Option<&BareVersion> {
+        self.msrv.as_ref()
+    }
+
+    pub fn workspace(&self) -> Option<&Path> {
+        self.workspace.as_deref()
+    }
+
+    /// Whether the project has a package, for which the MSRV can be found or verified.
+    pub fn is_package(&self) -> bool {
+        self.kind != ProjectKind::VirtualWorkspace
+    }
+}
+
+impl fmt::Display for DiscoveredProject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path.display(), self.kind)?;
+
+        if let Some(name) = &self.name {
+            write!(f, " '{}'", name)?;
+        }
+
+        if let Some(workspace) = &self.workspace {
+            write!(f, " (member of {})", workspace.display())?;
+        }
+
+        match &self.msrv {
+            Some(msrv) => write!(f, ", MSRV {}", msrv),
+            None => write!(f, ", no MSRV"),
+        }
+    }
+}
+
+/// Whether the manifest of a project declares a package, a workspace, or both.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProjectKind {
+    Package,
+    /// A workspace whose root manifest also declares a package
+    Workspace,
+    /// A workspace whose root manifest declares no package
+    VirtualWorkspace,
+}
+
+impl fmt::Display for ProjectKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Package => write!(f, "package"),
+            Self::Workspace => write!(f, "workspace"),
+            Self::VirtualWorkspace => write!(f, "virtual workspace"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::event::Message;
+    use crate::reporter::TestReporter;
+    use storyteller::Reporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporter::default();
+        let event = DiscoveredProjects::new(
+            "projects",
+            vec![DiscoveredProject::new(
+                "a",
+                ProjectKind::Package,
+                Some("a".to_string()),
+                Some(BareVersion::TwoComponents(1, 56)),
+                None,
+            )],
+        );
+
+        reporter.reporter().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::new(Message::DiscoveredProjects(event))]
+        );
+    }
+
+    #[test]
+    fn display() {
+        let projects = DiscoveredProjects::new(
+            "projects",
+            vec![
+                DiscoveredProject::new(
+                    "app",
+                    ProjectKind::Workspace,
+                    Some("app".to_string()),
+                    Some(BareVersion::TwoComponents(1, 60)),
+                    None,
+                ),
+                DiscoveredProject::new(
+                    "app/crates/core",
+                    ProjectKind::Package,
+                    Some("core".to_string()),
+                    Some(BareVersion::TwoComponents(1, 60)),
+                    Some(PathBuf::from("app")),
+                ),
+                DiscoveredProject::new("tools", ProjectKind::VirtualWorkspace, None, None, None),
+            ],
+        );
+
+        assert_eq!(
+            projects.to_string(),
+            "app: workspace 'app', MSRV 1.60
+app/crates/core: package 'core' (member of app), MSRV 1.60
+tools: virtual workspace, no MSRV
+3 projects discovered in projects
+"
+        );
+    }
+}
@@ -0,0 +1,277 @@
+use crate::cli::rust_releases_opts::Edition;
+use crate::manifest::bare_version::BareVersion;
+use crate::reporter::event::Message;
+use crate::semver;
+use crate::Event;
+use std::fmt;
+
+/// The editions which are compatible with the MSRV of a crate, as reported by `cargo msrv edition`.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct EditionAdvice {
+    current_edition: Edition,
+    msrv: Option<BareVersion>,
+    /// The newest edition which is supported by the MSRV, if an MSRV is specified
+    newest_compatible_edition: Option<Edition>,
+    editions: Vec<EditionRequirement>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    migration: Option<MigrationCheck>,
+}
+
+impl EditionAdvice {
+    pub fn new(current_edition: Edition, msrv: Option<BareVersion>) -> Self {
+        let editions = Edition::all()
+            .iter()
+            .map(|&edition| EditionRequirement::new(edition, msrv.as_ref()))
+            .collect::<Vec<_>>();
+
+        let newest_compatible_edition = msrv.as_ref().and_then(|_| {
+            editions
+                .iter()
+                .filter(|requirement| requirement.supported_by_msrv)
+                .map(|requirement| requirement.edition)
+                .max()
+        });
+
+        Self {
+            current_edition,
+            msrv,
+            newest_compatible_edition,
+            editions,
+            migration: None,
+        }
+    }
+
+    pub fn with_migration(mut self, migration: MigrationCheck) -> Self {
+        self.migration = Some(migration);
+        self
+    }
+
+    pub fn current_edition(&self) -> Edition {
+        self.current_edition
+    }
+
+    pub fn msrv(&self) -> Option<&BareVersion> {
+        self.msrv.as_ref()
+    }
+
+    pub fn newest_compatible_edition(&self) -> Option<Edition> {
+        self.newest_compatible_edition
+    }
+
+    pub fn editions(&self) -> &[EditionRequirement] {
+        &self.editions
+    }
+
+    pub fn migration(&self) -> Option<&MigrationCheck> {
+        self.migration.as_ref()
+    }
+}
+
+impl fmt::Display for EditionAdvice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.msrv {
+            Some(msrv) => writeln!(f, "Edition {}, MSRV Rust {}", self.current_edition, msrv)?,
+            None => writeln!(f, "Edition {}, no MSRV specified", self.current_edition)?,
+        }
+
+        for requirement in &self.editions {
+            let implication = if self.msrv.is_none() {
+                ""
+            } else if requirement.supported_by_msrv {
+                ", supported by the MSRV"
+            } else {
+                ", requires raising the MSRV"
+            };
+
+            writeln!(
+                f,
+                "  - {} requires Rust {}{}",
+                requirement.edition, requirement.minimum_rust_version, implication
+            )?;
+        }
+
+        if let Some(edition) = self.newest_compatible_edition {
+            writeln!(f, "Newest edition supported by the MSRV: {}", edition)?;
+        }
+
+        if let Some(migration) = &self.migration {
+            let result = if migration.feasible {
+                "succeeded"
+            } else {
+                "failed"
+            };
+
+            writeln!(
+                f,
+                "Migration to edition {} with Rust {}: {}",
+                migration.edition, migration.rust_version, result
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl From<EditionAdvice> for Event {
+    fn from(it: EditionAdvice) -> Self {
+        Message::EditionAdvice(it).into()
+    }
+}
+
+/// The first Rust version which supports an edition, and whether the MSRV supports it.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct EditionRequirement {
+    edition: Edition,
+    minimum_rust_version: BareVersion,
+    supported_by_msrv: bool,
+}
+
+impl EditionRequirement {
+    fn new(edition: Edition, msrv: Option<&BareVersion>) -> Self {
+        let minimum_rust_version = edition.as_bare_version();
+        let supported_by_msrv = msrv.map_or(false, |msrv| {
+            msrv.to_semver_version() >= minimum_rust_version.to_semver_version()
+        });
+
+        Self {
+            edition,
+            minimum_rust_version,
+            supported_by_msrv,
+        }
+    }
+
+    pub fn edition(&self) -> Edition {
+        self.edition
+    }
+
+    pub fn minimum_rust_version(&self) -> &BareVersion {
+        &self.minimum_rust_version
+    }
+
+    pub fn supported_by_msrv(&self) -> bool {
+        self.supported_by_msrv
+    }
+}
+
+/// The outcome of migrating a copy of the crate to the next edition, with `cargo fix --edition`.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct MigrationCheck {
+    edition: Edition,
+    rust_version: semver::Version,
+    feasible: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error_message: Option<String>,
+}
+
+impl MigrationCheck {
+    pub fn feasible(edition: Edition, rust_version: semver::Version) -> Self {
+        Self {
+            edition,
+            rust_version,
+            feasible: true,
+            error_message: None,
+        }
+    }
+
+    pub fn infeasible(
+        edition: Edition,
+        rust_version: semver::Version,
+        error_message: String,
+    ) -> Self {
+        Self {
+            edition,
+            rust_version,
+            feasible: false,
+            error_message: Some(error_message),
+        }
+    }
+
+    pub fn edition(&self) -> Edition {
+        self.edition
+    }
+
+    pub fn rust_version(&self) -> &semver::Version {
+        &self.rust_version
+    }
+
+    pub fn is_feasible(&self) -> bool {
+        self.feasible
+    }
+
+    pub fn error_message(&self) -> Option<&str> {
+        self.error_message.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::event::Message;
+    use crate::reporter::TestReporter;
+    use storyteller::Reporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporter::default();
+        let event = EditionAdvice::new(
+            Edition::Edition2018,
+            Some(BareVersion::TwoComponents(1, 40)),
+        );
+
+        reporter.reporter().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::new(Message::EditionAdvice(event)),]
+        );
+    }
+
+    #[yare::parameterized(
+        before_2018 = { BareVersion::TwoComponents(1, 30), Some(Edition::Edition2015) },
+        edition_2018 = { BareVersion::ThreeComponents(1, 31, 0), Some(Edition::Edition2018) },
+        between = { BareVersion::TwoComponents(1, 40), Some(Edition::Edition2018) },
+        edition_2021 = { BareVersion::TwoComponents(1, 56), Some(Edition::Edition2021) },
+    )]
+    fn newest_compatible_edition(msrv: BareVersion, expected: Option<Edition>) {
+        let advice = EditionAdvice::new(Edition::Edition2015, Some(msrv));
+
+        assert_eq!(advice.newest_compatible_edition(), expected);
+    }
+
+    #[test]
+    fn without_msrv() {
+        let advice = EditionAdvice::new(Edition::Edition2015, None);
+
+        assert!(advice.newest_compatible_edition().is_none());
+        assert!(advice
+            .editions()
+            .iter()
+            .all(|requirement| !requirement.supported_by_msrv()));
+    }
+
+    #[test]
+    fn display() {
+        let advice = EditionAdvice::new(
+            Edition::Edition2018,
+            Some(BareVersion::TwoComponents(1, 40)),
+        )
+        .with_migration(MigrationCheck::feasible(
+            Edition::Edition2021,
+            semver::Version::new(1, 56, 0),
+        ));
+
+        assert_eq!(
+            advice.to_string(),
+            "Edition 2018, MSRV Rust 1.40
+  - 2015 requires Rust 1.0.0, supported by the MSRV
+  - 2018 requires Rust 1.31.0, supported by the MSRV
+  - 2021 requires Rust 1.56.0, requires raising the MSRV
+Newest edition supported by the MSRV: 2018
+Migration to edition 2021 with Rust 1.56.0: succeeded
+"
+        );
+    }
+}
@@ -0,0 +1,56 @@
+use crate::reporter::event::Message;
+use crate::{semver, Event};
+
+/// Reported when none of the release sources could be reached, and the snapshot of Rust releases
+/// embedded in the binary is used as release index instead. Since the snapshot is taken at build
+/// time, it may be stale.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct EmbeddedIndexFallback {
+    snapshot_date: &'static str,
+    most_recent_release: Option<semver::Version>,
+}
+
+impl EmbeddedIndexFallback {
+    pub fn new(snapshot_date: &'static str, most_recent_release: Option<semver::Version>) -> Self {
+        Self {
+            snapshot_date,
+            most_recent_release,
+        }
+    }
+
+    pub fn snapshot_date(&self) -> &'static str {
+        self.snapshot_date
+    }
+
+    pub fn most_recent_release(&self) -> Option<&semver::Version> {
+        self.most_recent_release.as_ref()
+    }
+}
+
+impl From<EmbeddedIndexFallback> for Event {
+    fn from(it: EmbeddedIndexFallback) -> Self {
+        Message::EmbeddedIndexFallback(it).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::event::Message;
+    use crate::reporter::TestReporter;
+    use storyteller::Reporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporter::default();
+        let event = EmbeddedIndexFallback::new("2022-08-11", Some(semver::Version::new(1, 63, 0)));
+
+        reporter.reporter().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::new(Message::EmbeddedIndexFallback(event)),]
+        );
+    }
+}
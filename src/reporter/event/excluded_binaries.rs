@@ -0,0 +1,57 @@
+use crate::reporter::event::Message;
+use crate::required_features::BinaryTarget;
+use crate::Event;
+
+/// Reported when binaries which specify `required-features` are excluded from the check, as
+/// configured with `--bin-policy exclude`. The MSRV does not cover these binaries.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ExcludedBinaries {
+    binaries: Vec<BinaryTarget>,
+}
+
+impl ExcludedBinaries {
+    pub fn new(binaries: Vec<BinaryTarget>) -> Self {
+        Self { binaries }
+    }
+
+    pub fn binaries(&self) -> &[BinaryTarget] {
+        &self.binaries
+    }
+
+    /// The names of the excluded binaries, separated by a comma.
+    pub fn names(&self) -> String {
+        self.binaries
+            .iter()
+            .map(BinaryTarget::name)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+impl From<ExcludedBinaries> for Event {
+    fn from(it: ExcludedBinaries) -> Self {
+        Message::ExcludedBinaries(it).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::event::Message;
+    use crate::reporter::TestReporter;
+    use storyteller::Reporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporter::default();
+        let event = ExcludedBinaries::new(Vec::new());
+
+        reporter.reporter().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::new(Message::ExcludedBinaries(event)),]
+        );
+    }
+}
@@ -0,0 +1,101 @@
+use crate::reporter::event::Message;
+use crate::semver;
+use crate::Event;
+
+/// Reported once each release has been checked by an exhaustive search, with `--exhaustive`.
+/// Holds the outcome of the check of each release, and the releases whose outcome contradicts the
+/// outcome of a more recent release.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ExhaustiveSearch {
+    /// The checked releases, from most to least recent
+    releases: Vec<CheckedRelease>,
+    /// The releases which were accepted, although a more recent release was rejected. The
+    /// bisection and linear search methods assume these don't exist.
+    non_monotonic: Vec<semver::Version>,
+}
+
+impl ExhaustiveSearch {
+    pub fn new(releases: Vec<CheckedRelease>, non_monotonic: Vec<semver::Version>) -> Self {
+        Self {
+            releases,
+            non_monotonic,
+        }
+    }
+
+    pub fn releases(&self) -> &[CheckedRelease] {
+        &self.releases
+    }
+
+    pub fn non_monotonic(&self) -> &[semver::Version] {
+        &self.non_monotonic
+    }
+
+    /// The versions of the non-monotonic releases, separated by a comma.
+    pub fn non_monotonic_versions(&self) -> String {
+        self.non_monotonic
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+impl From<ExhaustiveSearch> for Event {
+    fn from(it: ExhaustiveSearch) -> Self {
+        Message::ExhaustiveSearch(it).into()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct CheckedRelease {
+    version: semver::Version,
+    /// Whether the check passed
+    compatible: bool,
+}
+
+impl CheckedRelease {
+    pub fn new(version: semver::Version, compatible: bool) -> Self {
+        Self {
+            version,
+            compatible,
+        }
+    }
+
+    pub fn version(&self) -> &semver::Version {
+        &self.version
+    }
+
+    pub fn compatible(&self) -> bool {
+        self.compatible
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::event::Message;
+    use crate::reporter::TestReporter;
+    use storyteller::Reporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporter::default();
+        let event = ExhaustiveSearch::new(
+            vec![
+                CheckedRelease::new(semver::Version::new(1, 56, 0), true),
+                CheckedRelease::new(semver::Version::new(1, 55, 0), false),
+                CheckedRelease::new(semver::Version::new(1, 54, 0), true),
+            ],
+            vec![semver::Version::new(1, 54, 0)],
+        );
+
+        reporter.reporter().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::new(Message::ExhaustiveSearch(event)),]
+        );
+    }
+}
@@ -0,0 +1,49 @@
+use crate::reporter::event::Message;
+use crate::Event;
+use std::path::{Path, PathBuf};
+
+/// The log of the most recent failed toolchain check, as located by `cargo msrv last-failure`.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct FailureLog {
+    path: PathBuf,
+}
+
+impl FailureLog {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl From<FailureLog> for Event {
+    fn from(it: FailureLog) -> Self {
+        Message::FailureLog(it).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::event::Message;
+    use crate::reporter::TestReporter;
+    use storyteller::Reporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporter::default();
+        let event = FailureLog::new(PathBuf::from(
+            "/home/ferris/.local/share/cargo-msrv/checks/1.61.0-x86_64-unknown-linux-gnu.log",
+        ));
+
+        reporter.reporter().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::new(Message::FailureLog(event))]
+        );
+    }
+}
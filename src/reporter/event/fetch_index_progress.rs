@@ -0,0 +1,96 @@
+use crate::reporter::event::Message;
+use crate::Event;
+
+/// Reported after each request of a fetch of the release index which takes more than one request,
+/// like the listing of the Rust distribution server. Counts the requests and releases so far,
+/// including those of an earlier, interrupted fetch which was resumed.
+///
+/// Also reported once by sources which fetch the index with a single request, like the Rust
+/// changelog, so the bytes they downloaded are accounted for.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct FetchIndexProgress {
+    source: String,
+    requests: u64,
+    releases: usize,
+    /// The number of requests which were completed by the interrupted fetch, or 0 if the fetch
+    /// was started from scratch
+    resumed_after: u64,
+    /// The number of bytes downloaded so far, not counting those of an interrupted fetch
+    bytes: u64,
+}
+
+impl FetchIndexProgress {
+    pub fn new(
+        source: impl Into<String>,
+        requests: u64,
+        releases: usize,
+        resumed_after: u64,
+    ) -> Self {
+        Self {
+            source: source.into(),
+            requests,
+            releases,
+            resumed_after,
+            bytes: 0,
+        }
+    }
+
+    pub fn with_bytes(mut self, bytes: u64) -> Self {
+        self.bytes = bytes;
+        self
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    pub fn requests(&self) -> u64 {
+        self.requests
+    }
+
+    pub fn releases(&self) -> usize {
+        self.releases
+    }
+
+    pub fn resumed_after(&self) -> u64 {
+        self.resumed_after
+    }
+
+    pub fn bytes(&self) -> u64 {
+        self.bytes
+    }
+
+    /// Whether this is the first request since an interrupted fetch was resumed.
+    pub fn is_first_resumed(&self) -> bool {
+        self.resumed_after > 0 && self.requests == self.resumed_after + 1
+    }
+}
+
+impl From<FetchIndexProgress> for Event {
+    fn from(it: FetchIndexProgress) -> Self {
+        Message::FetchIndexProgress(it).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::event::Message;
+    use crate::reporter::TestReporter;
+    use storyteller::Reporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporter::default();
+        let event = FetchIndexProgress::new("rust-dist", 4, 12, 3);
+
+        reporter.reporter().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::new(Message::FetchIndexProgress(event.clone()))]
+        );
+        assert!(event.is_first_resumed());
+    }
+}
@@ -0,0 +1,82 @@
+use crate::config::LockfilePolicy;
+use crate::lockfile::LockfileFormat;
+use crate::reporter::event::Message;
+use crate::semver;
+use crate::toolchain::OwnedToolchainSpec;
+use crate::Event;
+
+/// Reported when the cargo of a toolchain can't read the format of the lockfile of the crate. The
+/// policy, as configured with `--lockfile-policy`, decides whether the toolchain is rejected as
+/// untestable, or checked with a regenerated lockfile.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct IncompatibleLockfile {
+    toolchain: OwnedToolchainSpec,
+    /// The format version of the lockfile
+    lockfile_format: LockfileFormat,
+    /// The first Rust release whose cargo can read the lockfile
+    readable_since: semver::Version,
+    decision: LockfilePolicy,
+}
+
+impl IncompatibleLockfile {
+    pub fn new(
+        toolchain: impl Into<OwnedToolchainSpec>,
+        lockfile_format: LockfileFormat,
+        decision: LockfilePolicy,
+    ) -> Self {
+        Self {
+            toolchain: toolchain.into(),
+            lockfile_format,
+            readable_since: lockfile_format.readable_since(),
+            decision,
+        }
+    }
+
+    pub fn toolchain(&self) -> &OwnedToolchainSpec {
+        &self.toolchain
+    }
+
+    pub fn lockfile_format(&self) -> LockfileFormat {
+        self.lockfile_format
+    }
+
+    pub fn readable_since(&self) -> &semver::Version {
+        &self.readable_since
+    }
+
+    pub fn decision(&self) -> LockfilePolicy {
+        self.decision
+    }
+}
+
+impl From<IncompatibleLockfile> for Event {
+    fn from(it: IncompatibleLockfile) -> Self {
+        Message::IncompatibleLockfile(it).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::event::Message;
+    use crate::reporter::TestReporter;
+    use storyteller::Reporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporter::default();
+        let event = IncompatibleLockfile::new(
+            OwnedToolchainSpec::new(&semver::Version::new(1, 40, 0), "test_target"),
+            LockfileFormat::V3,
+            LockfilePolicy::Untestable,
+        );
+
+        reporter.reporter().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::new(Message::IncompatibleLockfile(event)),]
+        );
+    }
+}
@@ -0,0 +1,75 @@
+use crate::reporter::event::Message;
+use crate::semver;
+use crate::Event;
+
+/// Reported instead of a result when the budget of checks, given with `--max-checks`, is exhausted
+/// before the MSRV was found. Holds the range of Rust versions in which the MSRV must lie,
+/// according to the checks which did run.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct InconclusiveResult {
+    max_checks: u64,
+    /// The least recent Rust version whose check was accepted: the MSRV is at most this version
+    #[serde(skip_serializing_if = "Option::is_none")]
+    least_recent_accepted: Option<semver::Version>,
+    /// The most recent Rust version whose check was rejected: the MSRV is more recent
+    #[serde(skip_serializing_if = "Option::is_none")]
+    most_recent_rejected: Option<semver::Version>,
+}
+
+impl InconclusiveResult {
+    pub fn new(
+        max_checks: u64,
+        least_recent_accepted: Option<semver::Version>,
+        most_recent_rejected: Option<semver::Version>,
+    ) -> Self {
+        Self {
+            max_checks,
+            least_recent_accepted,
+            most_recent_rejected,
+        }
+    }
+
+    pub fn max_checks(&self) -> u64 {
+        self.max_checks
+    }
+
+    pub fn least_recent_accepted(&self) -> Option<&semver::Version> {
+        self.least_recent_accepted.as_ref()
+    }
+
+    pub fn most_recent_rejected(&self) -> Option<&semver::Version> {
+        self.most_recent_rejected.as_ref()
+    }
+}
+
+impl From<InconclusiveResult> for Event {
+    fn from(it: InconclusiveResult) -> Self {
+        Message::InconclusiveResult(it).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::event::Message;
+    use crate::reporter::TestReporter;
+    use storyteller::Reporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporter::default();
+        let event = InconclusiveResult::new(
+            3,
+            Some(semver::Version::new(1, 58, 0)),
+            Some(semver::Version::new(1, 50, 0)),
+        );
+
+        reporter.reporter().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::new(Message::InconclusiveResult(event)),]
+        );
+    }
+}
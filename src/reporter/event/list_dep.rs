@@ -1,24 +1,44 @@
-use crate::config::list::ListMsrvVariant;
+use crate::config::list::{ListMsrvVariant, DIRECT_DEPS, ORDERED_BY_MSRV, ORPHANS, RESOLVE};
+use crate::crates_index::RustVersionHistory;
 use crate::dependency_graph::DependencyGraph;
 use crate::reporter::event::Message;
 use crate::Event;
+use cargo_metadata::PackageId;
+use std::collections::BTreeMap;
 
 use crate::reporter::event::list_dep::ordered_by_msrv::OrderedByMsrvFormatter;
+use crate::reporter::event::list_dep::orphans::OrphansFormatter;
+use crate::reporter::event::list_dep::resolve::ResolveFormatter;
 use direct_deps::DirectDepsFormatter;
 
 mod direct_deps;
 mod metadata;
 mod ordered_by_msrv;
+mod orphans;
+mod resolve;
+
+pub(crate) use orphans::orphans;
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct ListDep {
     variant: ListMsrvVariant,
     graph: DependencyGraph,
+    // Release history of dependencies, as found in the index of their registry, keyed by package id
+    history: Option<BTreeMap<PackageId, RustVersionHistory>>,
 }
 
 impl ListDep {
     pub fn new(variant: ListMsrvVariant, graph: DependencyGraph) -> Self {
-        Self { variant, graph }
+        Self {
+            variant,
+            graph,
+            history: None,
+        }
+    }
+
+    pub fn with_history(mut self, history: BTreeMap<PackageId, RustVersionHistory>) -> Self {
+        self.history = Some(history);
+        self
     }
 }
 
@@ -33,6 +53,10 @@ impl ToString for ListDep {
         match self.variant {
             ListMsrvVariant::DirectDeps => DirectDepsFormatter::new(&self.graph).to_string(),
             ListMsrvVariant::OrderedByMSRV => OrderedByMsrvFormatter::new(&self.graph).to_string(),
+            ListMsrvVariant::Orphans => {
+                OrphansFormatter::new(&self.graph, self.history.as_ref()).to_string()
+            }
+            ListMsrvVariant::Resolve => ResolveFormatter::new(&self.graph).to_string(),
         }
     }
 }
@@ -49,6 +73,44 @@ impl serde::Serialize for ListDep {
             ListMsrvVariant::OrderedByMSRV => {
                 OrderedByMsrvFormatter::new(&self.graph).serialize(serializer)
             }
+            ListMsrvVariant::Orphans => {
+                OrphansFormatter::new(&self.graph, self.history.as_ref()).serialize(serializer)
+            }
+            ListMsrvVariant::Resolve => ResolveFormatter::new(&self.graph).serialize(serializer),
         }
     }
 }
+
+impl schemars::JsonSchema for ListDep {
+    fn schema_name() -> String {
+        "ListDep".to_string()
+    }
+
+    // The values which are listed for each dependency differ per variant
+    fn json_schema(_gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        let variants = [DIRECT_DEPS, ORDERED_BY_MSRV, ORPHANS, RESOLVE];
+        let variant = schemars::schema::SchemaObject {
+            instance_type: Some(schemars::schema::InstanceType::String.into()),
+            enum_values: Some(variants.iter().map(|&variant| variant.into()).collect()),
+            ..Default::default()
+        };
+        let list = schemars::schema::SchemaObject {
+            instance_type: Some(schemars::schema::InstanceType::Array.into()),
+            ..Default::default()
+        };
+
+        let mut schema = schemars::schema::SchemaObject {
+            instance_type: Some(schemars::schema::InstanceType::Object.into()),
+            ..Default::default()
+        };
+        let object = schema.object();
+        object
+            .properties
+            .insert("variant".to_string(), variant.into());
+        object.properties.insert("list".to_string(), list.into());
+        object.required.insert("variant".to_string());
+        object.required.insert("list".to_string());
+
+        schema.into()
+    }
+}
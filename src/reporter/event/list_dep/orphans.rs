@@ -0,0 +1,130 @@
+use crate::config::list::ORPHANS;
+use crate::crates_index::{is_crates_io, registry_url, RustVersionChange, RustVersionHistory};
+use crate::dependency_graph::DependencyGraph;
+use crate::formatting::table;
+use crate::reporter::event::list_dep::metadata::package_msrv;
+use cargo_metadata::{Package, PackageId};
+use petgraph::visit::Bfs;
+use std::collections::BTreeMap;
+use tabled::{Style, Tabled};
+
+pub struct OrphansFormatter<'g> {
+    graph: &'g DependencyGraph,
+    history: Option<&'g BTreeMap<PackageId, RustVersionHistory>>,
+}
+
+impl<'g> OrphansFormatter<'g> {
+    pub fn new(
+        graph: &'g DependencyGraph,
+        history: Option<&'g BTreeMap<PackageId, RustVersionHistory>>,
+    ) -> Self {
+        Self { graph, history }
+    }
+}
+
+impl ToString for OrphansFormatter<'_> {
+    fn to_string(&self) -> String {
+        let values = dependencies(self.graph, self.history);
+
+        table(values).with(Style::modern()).to_string()
+    }
+}
+
+impl serde::Serialize for OrphansFormatter<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let serializable = SerializableValues {
+            variant: ORPHANS,
+            list: dependencies(self.graph, self.history).collect(),
+        };
+
+        serializable.serialize(serializer)
+    }
+}
+
+/// The direct and transitive dependencies of the root crate which do not declare an MSRV.
+pub(crate) fn orphans(graph: &DependencyGraph) -> impl Iterator<Item = &Package> + '_ {
+    let root_index = graph.index()[graph.root_crate()].into();
+    let mut bfs = Bfs::new(graph.packages(), root_index);
+
+    std::iter::from_fn(move || bfs.next(graph.packages()))
+        .filter(move |nx| *nx != root_index)
+        .map(move |nx| &graph.packages()[nx])
+        .filter(|package| package_msrv(package).is_none())
+}
+
+fn dependencies<'g>(
+    graph: &'g DependencyGraph,
+    history: Option<&'g BTreeMap<PackageId, RustVersionHistory>>,
+) -> impl Iterator<Item = Values<'g>> + 'g {
+    orphans(graph).map(move |package| Values {
+        name: &package.name,
+        version: &package.version,
+        registry: package
+            .source
+            .as_ref()
+            .and_then(registry_url)
+            .filter(|url| !is_crates_io(url)),
+        latest: history.and_then(|history| history.get(&package.id)),
+    })
+}
+
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+struct Values<'a> {
+    name: &'a str,
+    version: &'a crate::semver::Version,
+    // The index URL of the registry the dependency originates from, unless that's crates.io
+    #[serde(skip_serializing_if = "Option::is_none")]
+    registry: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    latest: Option<&'a RustVersionHistory>,
+}
+
+impl Tabled for Values<'_> {
+    const LENGTH: usize = 6;
+
+    fn fields(&self) -> Vec<String> {
+        let latest = self.latest.map(|history| history.latest().to_string());
+        let latest_rust_version = self
+            .latest
+            .and_then(|history| history.latest_rust_version())
+            .map(ToString::to_string);
+        let change = self
+            .latest
+            .and_then(|history| history.change())
+            .map(|change| match change {
+                RustVersionChange::Gained { version } => format!("gained in {}", version),
+                RustVersionChange::Lost { version } => format!("lost in {}", version),
+            });
+
+        vec![
+            self.name.to_string(),
+            self.version.to_string(),
+            self.registry.unwrap_or_default().to_string(),
+            latest.unwrap_or_default(),
+            latest_rust_version.unwrap_or_default(),
+            change.unwrap_or_default(),
+        ]
+    }
+
+    fn headers() -> Vec<String> {
+        vec![
+            "Name".to_string(),
+            "Version".to_string(),
+            "Registry".to_string(),
+            "Latest release".to_string(),
+            "Latest rust-version".to_string(),
+            "rust-version".to_string(),
+        ]
+    }
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+struct SerializableValues<'v> {
+    variant: &'static str,
+    list: Vec<Values<'v>>,
+}
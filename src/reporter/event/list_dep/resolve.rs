@@ -0,0 +1,293 @@
+use crate::config::list::RESOLVE;
+use crate::dependency_graph::DependencyGraph;
+use crate::formatting::table;
+use crate::reporter::event::list_dep::metadata::{format_version, package_msrv};
+use crate::semver;
+use petgraph::stable_graph::{NodeIndex, StableDiGraph};
+use std::collections::{HashMap, VecDeque};
+use tabled::{Style, Tabled};
+
+pub struct ResolveFormatter<'g> {
+    graph: &'g DependencyGraph,
+}
+
+impl<'g> ResolveFormatter<'g> {
+    pub fn new(graph: &'g DependencyGraph) -> Self {
+        Self { graph }
+    }
+}
+
+impl ToString for ResolveFormatter<'_> {
+    fn to_string(&self) -> String {
+        let values = dependencies(self.graph);
+        let summary = lower_bound(&values).map(|lower_bound| {
+            format!(
+                "\n\nThe MSRV is at least Rust {}, as imposed by: {}",
+                lower_bound.msrv,
+                lower_bound.chain.join(" → ")
+            )
+        });
+
+        let mut output = table(values).with(Style::modern()).to_string();
+        output.push_str(&summary.unwrap_or_default());
+
+        output
+    }
+}
+
+impl serde::Serialize for ResolveFormatter<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let list = dependencies(self.graph);
+
+        let serializable = SerializableValues {
+            variant: RESOLVE,
+            lower_bound: lower_bound(&list),
+            list: &list,
+        };
+
+        serializable.serialize(serializer)
+    }
+}
+
+/// For each direct dependency, the highest MSRV declared by the dependency itself or by any of its
+/// transitive dependencies. Ordered from the highest to the lowest MSRV.
+fn dependencies(graph: &DependencyGraph) -> Vec<Values<'_>> {
+    let root_index = graph.index()[graph.root_crate()].into();
+    let packages = graph.packages();
+
+    let mut values = packages
+        .neighbors_directed(root_index, petgraph::Direction::Outgoing)
+        .map(|dependency| {
+            let package = &packages[dependency];
+            let bound = highest_msrv(packages, dependency, package_msrv);
+
+            Values {
+                name: &package.name,
+                version: &package.version,
+                msrv: bound.as_ref().map(|bound| bound.msrv.clone()),
+                imposed_by: bound
+                    .map(|bound| {
+                        bound
+                            .chain
+                            .into_iter()
+                            .map(|nx| packages[nx].name.clone())
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    // Highest MSRV first, and dependencies without a declared MSRV last
+    values.sort_by(|lhs, rhs| rhs.msrv.cmp(&lhs.msrv).then(lhs.name.cmp(rhs.name)));
+    values
+}
+
+/// The dependency chain which imposes the overall lower bound on the MSRV of the root crate.
+fn lower_bound<'v>(values: &'v [Values<'_>]) -> Option<LowerBound<'v>> {
+    // Values are ordered by MSRV, so when several dependencies impose the same lower bound, the
+    // shortest chain is picked
+    let highest = values.first()?.msrv.as_ref()?;
+
+    values
+        .iter()
+        .take_while(|value| value.msrv.as_ref() == Some(highest))
+        .min_by_key(|value| value.imposed_by.len())
+        .map(|value| LowerBound {
+            msrv: highest,
+            chain: &value.imposed_by,
+        })
+}
+
+/// The highest MSRV which is declared by a package reachable from a starting package.
+#[derive(Debug, PartialEq)]
+struct Bound {
+    msrv: semver::Version,
+    // The chain of packages from the starting package, up to and including the declaring package
+    chain: Vec<NodeIndex<usize>>,
+}
+
+/// Find the highest MSRV declared by the `start` package, or any of its transitive dependencies.
+///
+/// The graph is traversed breadth first, so when multiple packages declare the highest MSRV, the
+/// one with the shortest chain from `start` is picked.
+fn highest_msrv<N>(
+    graph: &StableDiGraph<N, (), usize>,
+    start: NodeIndex<usize>,
+    msrv: impl Fn(&N) -> Option<semver::Version>,
+) -> Option<Bound> {
+    let mut predecessors = HashMap::new();
+    let mut queue = VecDeque::from(vec![start]);
+    let mut highest: Option<(semver::Version, NodeIndex<usize>)> = None;
+
+    predecessors.insert(start, start);
+
+    while let Some(nx) = queue.pop_front() {
+        if let Some(version) = msrv(&graph[nx]) {
+            if highest
+                .as_ref()
+                .map_or(true, |(current, _)| &version > current)
+            {
+                highest = Some((version, nx));
+            }
+        }
+
+        for dependency in graph.neighbors_directed(nx, petgraph::Direction::Outgoing) {
+            if !predecessors.contains_key(&dependency) {
+                predecessors.insert(dependency, nx);
+                queue.push_back(dependency);
+            }
+        }
+    }
+
+    let (msrv, declared_by) = highest?;
+
+    let mut chain = vec![declared_by];
+    let mut current = declared_by;
+
+    while current != start {
+        current = predecessors[&current];
+        chain.push(current);
+    }
+
+    chain.reverse();
+
+    Some(Bound { msrv, chain })
+}
+
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+struct Values<'a> {
+    name: &'a str,
+    version: &'a semver::Version,
+    msrv: Option<semver::Version>,
+    imposed_by: Vec<String>,
+}
+
+impl Tabled for Values<'_> {
+    const LENGTH: usize = 4;
+
+    fn fields(&self) -> Vec<String> {
+        vec![
+            self.name.to_string(),
+            self.version.to_string(),
+            format_version(self.msrv.as_ref()),
+            self.imposed_by.join(" → "),
+        ]
+    }
+
+    fn headers() -> Vec<String> {
+        vec![
+            "Name".to_string(),
+            "Version".to_string(),
+            "MSRV".to_string(),
+            "Imposed by".to_string(),
+        ]
+    }
+}
+
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+struct LowerBound<'v> {
+    msrv: &'v semver::Version,
+    chain: &'v [String],
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+struct SerializableValues<'v, 'g> {
+    variant: &'static str,
+    lower_bound: Option<LowerBound<'v>>,
+    list: &'v [Values<'g>],
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Graph = StableDiGraph<Option<semver::Version>, (), usize>;
+
+    fn msrv(version: &Option<semver::Version>) -> Option<semver::Version> {
+        version.clone()
+    }
+
+    fn version(minor: u64) -> Option<semver::Version> {
+        Some(semver::Version::new(1, minor, 0))
+    }
+
+    #[test]
+    fn highest_msrv_of_transitive_dependency() {
+        let mut graph = Graph::default();
+        let a = graph.add_node(version(40));
+        let b = graph.add_node(None);
+        let c = graph.add_node(version(60));
+        let d = graph.add_node(version(50));
+        graph.add_edge(a, b, ());
+        graph.add_edge(b, c, ());
+        graph.add_edge(a, d, ());
+
+        let bound = highest_msrv(&graph, a, msrv).unwrap();
+
+        assert_eq!(
+            bound,
+            Bound {
+                msrv: semver::Version::new(1, 60, 0),
+                chain: vec![a, b, c],
+            }
+        );
+    }
+
+    #[test]
+    fn highest_msrv_prefers_shortest_chain() {
+        let mut graph = Graph::default();
+        let a = graph.add_node(None);
+        let b = graph.add_node(None);
+        let c = graph.add_node(version(60));
+        let d = graph.add_node(version(60));
+        graph.add_edge(a, b, ());
+        graph.add_edge(b, c, ());
+        graph.add_edge(a, d, ());
+
+        let bound = highest_msrv(&graph, a, msrv).unwrap();
+
+        assert_eq!(bound.chain, vec![a, d]);
+    }
+
+    #[test]
+    fn highest_msrv_of_start() {
+        let mut graph = Graph::default();
+        let a = graph.add_node(version(60));
+        let b = graph.add_node(version(50));
+        graph.add_edge(a, b, ());
+
+        let bound = highest_msrv(&graph, a, msrv).unwrap();
+
+        assert_eq!(bound.chain, vec![a]);
+    }
+
+    #[test]
+    fn highest_msrv_without_declared_msrv() {
+        let mut graph = Graph::default();
+        let a = graph.add_node(None);
+        let b = graph.add_node(None);
+        graph.add_edge(a, b, ());
+
+        assert!(highest_msrv(&graph, a, msrv).is_none());
+    }
+
+    #[test]
+    fn highest_msrv_with_cycle() {
+        let mut graph = Graph::default();
+        let a = graph.add_node(version(40));
+        let b = graph.add_node(version(50));
+        graph.add_edge(a, b, ());
+        graph.add_edge(b, a, ());
+
+        let bound = highest_msrv(&graph, a, msrv).unwrap();
+
+        assert_eq!(bound.chain, vec![a, b]);
+    }
+}
@@ -0,0 +1,61 @@
+use crate::reporter::event::Message;
+use crate::Event;
+use std::path::{Path, PathBuf};
+
+/// Reported when the log file can't be written, for example because the log folder can't be
+/// created. The program continues, but the logs which would have been written to the log file are
+/// discarded.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct LogFileUnavailable {
+    // Absent when no log folder could be determined at all
+    folder: Option<PathBuf>,
+    reason: String,
+}
+
+impl LogFileUnavailable {
+    pub fn new(folder: Option<PathBuf>, reason: impl Into<String>) -> Self {
+        Self {
+            folder,
+            reason: reason.into(),
+        }
+    }
+
+    pub fn folder(&self) -> Option<&Path> {
+        self.folder.as_deref()
+    }
+
+    pub fn reason(&self) -> &str {
+        &self.reason
+    }
+}
+
+impl From<LogFileUnavailable> for Event {
+    fn from(it: LogFileUnavailable) -> Self {
+        Message::LogFileUnavailable(it).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::event::Message;
+    use crate::reporter::TestReporter;
+    use storyteller::Reporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporter::default();
+        let event = LogFileUnavailable::new(
+            Some(PathBuf::from("/var/log/cargo-msrv")),
+            "Permission denied",
+        );
+
+        reporter.reporter().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::new(Message::LogFileUnavailable(event)),]
+        );
+    }
+}
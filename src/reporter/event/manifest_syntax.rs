@@ -0,0 +1,69 @@
+use crate::manifest::syntax::{ManifestFeature, ManifestRequirement};
+use crate::reporter::event::Message;
+use crate::semver;
+use crate::Event;
+
+/// Reported when the Cargo manifest uses a feature which the cargo of older toolchains can't
+/// read. Releases less recent than the first Rust release whose cargo can read it are skipped,
+/// since their checks would fail with a parse error of the manifest.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ManifestSyntax {
+    feature: ManifestFeature,
+    /// The key of the manifest at which the feature is used
+    key: String,
+    /// The first Rust release whose cargo can read the manifest
+    readable_since: semver::Version,
+}
+
+impl ManifestSyntax {
+    pub fn new(requirement: ManifestRequirement) -> Self {
+        Self {
+            feature: requirement.feature,
+            key: requirement.key,
+            readable_since: requirement.feature.readable_since(),
+        }
+    }
+
+    pub fn feature(&self) -> ManifestFeature {
+        self.feature
+    }
+
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    pub fn readable_since(&self) -> &semver::Version {
+        &self.readable_since
+    }
+}
+
+impl From<ManifestSyntax> for Event {
+    fn from(it: ManifestSyntax) -> Self {
+        Message::ManifestSyntax(it).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::event::Message;
+    use crate::reporter::TestReporter;
+    use storyteller::Reporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporter::default();
+        let event = ManifestSyntax::new(ManifestRequirement {
+            feature: ManifestFeature::Lints,
+            key: "lints".to_string(),
+        });
+
+        reporter.reporter().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::new(Message::ManifestSyntax(event)),]
+        );
+    }
+}
@@ -0,0 +1,297 @@
+use crate::reporter::event::Message;
+use crate::Event;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// The issues which `cargo msrv validate` found with the MSRV declared in a Cargo manifest.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ManifestValidation {
+    /// The manifest which was validated
+    manifest: PathBuf,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl ManifestValidation {
+    pub fn new(manifest: impl Into<PathBuf>, diagnostics: Vec<Diagnostic>) -> Self {
+        Self {
+            manifest: manifest.into(),
+            diagnostics,
+        }
+    }
+
+    pub fn manifest(&self) -> &Path {
+        &self.manifest
+    }
+
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    /// The number of diagnostics with the given severity.
+    pub fn count(&self, severity: Severity) -> usize {
+        self.diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.severity == severity)
+            .count()
+    }
+}
+
+impl fmt::Display for ManifestValidation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for diagnostic in &self.diagnostics {
+            writeln!(f, "{}", diagnostic)?;
+        }
+
+        if self.diagnostics.is_empty() {
+            return writeln!(
+                f,
+                "No issues found with the MSRV declared in {}",
+                self.manifest.display()
+            );
+        }
+
+        writeln!(
+            f,
+            "{} error(s) and {} warning(s) found in {}",
+            self.count(Severity::Error),
+            self.count(Severity::Warning),
+            self.manifest.display()
+        )
+    }
+}
+
+impl From<ManifestValidation> for Event {
+    fn from(it: ManifestValidation) -> Self {
+        Message::ManifestValidation(it).into()
+    }
+}
+
+/// An issue with a key of a Cargo manifest.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct Diagnostic {
+    severity: Severity,
+    code: DiagnosticCode,
+    message: String,
+    /// The manifest which declares the key; the manifest of the workspace root for inherited keys
+    manifest: PathBuf,
+    /// The dotted path of the key, e.g. `package.rust-version`
+    key: String,
+    /// The location of the key in the manifest, if it could be found
+    #[serde(skip_serializing_if = "Option::is_none")]
+    span: Option<Span>,
+}
+
+impl Diagnostic {
+    pub fn new(
+        severity: Severity,
+        code: DiagnosticCode,
+        message: impl Into<String>,
+        manifest: impl Into<PathBuf>,
+        key: impl Into<String>,
+        span: Option<Span>,
+    ) -> Self {
+        Self {
+            severity,
+            code,
+            message: message.into(),
+            manifest: manifest.into(),
+            key: key.into(),
+            span,
+        }
+    }
+
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    pub fn code(&self) -> DiagnosticCode {
+        self.code
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn manifest(&self) -> &Path {
+        &self.manifest
+    }
+
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    pub fn span(&self) -> Option<&Span> {
+        self.span.as_ref()
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{}[{}]: {}: {}",
+            self.severity, self.code, self.key, self.message
+        )?;
+
+        match &self.span {
+            Some(span) => write!(
+                f,
+                "  --> {}:{}:{}",
+                self.manifest.display(),
+                span.line,
+                span.column
+            ),
+            None => write!(f, "  --> {}", self.manifest.display()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Error => write!(f, "error"),
+            Self::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+/// The kind of issue found by `cargo msrv validate`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DiagnosticCode {
+    /// The value of the key is not a string
+    InvalidType,
+    /// The value of the key is not a two or three component version, e.g. `1.56`
+    InvalidSyntax,
+    /// The version is newer than the most recent stable Rust release
+    NewerThanStable,
+    /// The version is older than the first Rust release which supports the edition of the package
+    BelowEditionMinimum,
+    /// The key is inherited from a workspace, but the package is not part of a workspace
+    MissingWorkspace,
+    /// The key is inherited from a workspace, but the workspace does not declare it
+    MissingWorkspaceKey,
+    /// The package declares a different version than its workspace
+    DiffersFromWorkspace,
+    /// `package.rust-version` and `package.metadata.msrv` declare different versions
+    ConflictingKeys,
+}
+
+impl DiagnosticCode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::InvalidType => "invalid_type",
+            Self::InvalidSyntax => "invalid_syntax",
+            Self::NewerThanStable => "newer_than_stable",
+            Self::BelowEditionMinimum => "below_edition_minimum",
+            Self::MissingWorkspace => "missing_workspace",
+            Self::MissingWorkspaceKey => "missing_workspace_key",
+            Self::DiffersFromWorkspace => "differs_from_workspace",
+            Self::ConflictingKeys => "conflicting_keys",
+        }
+    }
+}
+
+impl fmt::Display for DiagnosticCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// The location of a key and its value in a manifest.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct Span {
+    /// The line of the key, starting at 1
+    pub line: usize,
+    /// The column of the key, in bytes, starting at 1
+    pub column: usize,
+    /// The byte offset of the key
+    pub start: usize,
+    /// The byte offset of the end of its value
+    pub end: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::event::Message;
+    use crate::reporter::TestReporter;
+    use storyteller::Reporter;
+
+    fn newer_than_stable() -> Diagnostic {
+        Diagnostic::new(
+            Severity::Error,
+            DiagnosticCode::NewerThanStable,
+            "1.99 is newer than the most recent stable release, 1.64.0",
+            "Cargo.toml",
+            "package.rust-version",
+            Some(Span {
+                line: 3,
+                column: 1,
+                start: 28,
+                end: 50,
+            }),
+        )
+    }
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporter::default();
+        let event = ManifestValidation::new("Cargo.toml", vec![newer_than_stable()]);
+
+        reporter.reporter().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::new(Message::ManifestValidation(event))]
+        );
+    }
+
+    #[test]
+    fn display() {
+        let validation = ManifestValidation::new(
+            "Cargo.toml",
+            vec![
+                newer_than_stable(),
+                Diagnostic::new(
+                    Severity::Warning,
+                    DiagnosticCode::ConflictingKeys,
+                    "1.56 differs from package.rust-version, 1.99",
+                    "Cargo.toml",
+                    "package.metadata.msrv",
+                    None,
+                ),
+            ],
+        );
+
+        assert_eq!(
+            validation.to_string(),
+            "error[newer_than_stable]: package.rust-version: 1.99 is newer than the most recent stable release, 1.64.0
+  --> Cargo.toml:3:1
+warning[conflicting_keys]: package.metadata.msrv: 1.56 differs from package.rust-version, 1.99
+  --> Cargo.toml
+1 error(s) and 1 warning(s) found in Cargo.toml
+"
+        );
+    }
+
+    #[test]
+    fn display_without_issues() {
+        let validation = ManifestValidation::new("Cargo.toml", vec![]);
+
+        assert_eq!(
+            validation.to_string(),
+            "No issues found with the MSRV declared in Cargo.toml\n"
+        );
+    }
+}
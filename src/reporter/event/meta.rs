@@ -1,7 +1,7 @@
 use crate::reporter::event::Message;
 use crate::Event;
 
-#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct Meta {
     instance: &'static str,
@@ -0,0 +1,135 @@
+use crate::reporter::event::Message;
+use crate::semver;
+use crate::Event;
+
+/// Reported before an action is run, when external tools on which the action depends are missing,
+/// or are older than the minimum supported version.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct MissingPrerequisites {
+    missing: Vec<MissingPrerequisite>,
+}
+
+impl MissingPrerequisites {
+    pub fn new(missing: Vec<MissingPrerequisite>) -> Self {
+        Self { missing }
+    }
+
+    pub fn missing(&self) -> &[MissingPrerequisite] {
+        &self.missing
+    }
+}
+
+impl From<MissingPrerequisites> for Event {
+    fn from(it: MissingPrerequisites) -> Self {
+        Message::MissingPrerequisites(it).into()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct MissingPrerequisite {
+    tool: String,
+    // Where to find installation instructions for the tool
+    install_hint: String,
+    // The command which installs the tool on the operating system on which cargo-msrv runs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    install_command: Option<String>,
+    // Whether the tool is installed with this command, when cargo-msrv is run with `--setup`
+    setup: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    minimum_version: Option<semver::Version>,
+    // The version which was found, if the tool was found, but is outdated
+    #[serde(skip_serializing_if = "Option::is_none")]
+    found_version: Option<semver::Version>,
+}
+
+impl MissingPrerequisite {
+    pub fn not_found(tool: impl Into<String>, install_hint: impl Into<String>) -> Self {
+        Self {
+            tool: tool.into(),
+            install_hint: install_hint.into(),
+            install_command: None,
+            setup: false,
+            minimum_version: None,
+            found_version: None,
+        }
+    }
+
+    pub fn outdated(
+        tool: impl Into<String>,
+        install_hint: impl Into<String>,
+        minimum_version: semver::Version,
+        found_version: semver::Version,
+    ) -> Self {
+        Self {
+            tool: tool.into(),
+            install_hint: install_hint.into(),
+            install_command: None,
+            setup: false,
+            minimum_version: Some(minimum_version),
+            found_version: Some(found_version),
+        }
+    }
+
+    /// Set the command which installs the tool, and whether `--setup` runs this command.
+    pub fn with_install_command(mut self, command: impl Into<String>, setup: bool) -> Self {
+        self.install_command = Some(command.into());
+        self.setup = setup;
+        self
+    }
+
+    pub fn tool(&self) -> &str {
+        &self.tool
+    }
+
+    pub fn install_hint(&self) -> &str {
+        &self.install_hint
+    }
+
+    pub fn install_command(&self) -> Option<&str> {
+        self.install_command.as_deref()
+    }
+
+    pub fn setup(&self) -> bool {
+        self.setup
+    }
+
+    pub fn minimum_version(&self) -> Option<&semver::Version> {
+        self.minimum_version.as_ref()
+    }
+
+    pub fn found_version(&self) -> Option<&semver::Version> {
+        self.found_version.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::event::Message;
+    use crate::reporter::TestReporter;
+    use storyteller::Reporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporter::default();
+        let event = MissingPrerequisites::new(vec![
+            MissingPrerequisite::not_found("cargo", "https://rustup.rs")
+                .with_install_command("curl https://sh.rustup.rs | sh", true),
+            MissingPrerequisite::outdated(
+                "rustup",
+                "https://rustup.rs",
+                semver::Version::new(1, 20, 0),
+                semver::Version::new(1, 18, 3),
+            ),
+        ]);
+
+        reporter.reporter().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::new(Message::MissingPrerequisites(event)),]
+        );
+    }
+}
@@ -0,0 +1,264 @@
+use crate::manifest::bare_version::BareVersion;
+use crate::release_schedule::Date;
+use crate::reporter::event::Message;
+use crate::Event;
+use std::fmt;
+
+/// How the MSRV declared in the Cargo manifest evolved across the revisions of a crate, from the
+/// least to the most recent revision, as reported by `cargo msrv history`.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct MsrvHistory {
+    revisions: Vec<HistoryEntry>,
+}
+
+impl MsrvHistory {
+    pub fn new(revisions: Vec<HistoryEntry>) -> Self {
+        Self { revisions }
+    }
+
+    pub fn revisions(&self) -> &[HistoryEntry] {
+        &self.revisions
+    }
+
+    /// The revisions at which the MSRV changed.
+    pub fn changes(&self) -> impl Iterator<Item = &HistoryEntry> {
+        self.revisions
+            .iter()
+            .filter(|entry| entry.change != MsrvChange::Unchanged)
+    }
+}
+
+impl fmt::Display for MsrvHistory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self
+            .revisions
+            .iter()
+            .map(|entry| entry.revision.len())
+            .max()
+            .unwrap_or_default();
+
+        for entry in &self.revisions {
+            let date = entry
+                .date
+                .map(|date| date.to_string())
+                .unwrap_or_else(|| "-".to_string());
+            let msrv = entry
+                .msrv
+                .as_ref()
+                .map(ToString::to_string)
+                .unwrap_or_else(|| "none".to_string());
+
+            write!(
+                f,
+                "{:<width$}  {:<10}  {:<8}  {}",
+                entry.revision,
+                date,
+                msrv,
+                entry.change,
+                width = width
+            )?;
+
+            match entry.verified {
+                Some(true) => writeln!(f, ", verified")?,
+                Some(false) => writeln!(f, ", verification failed")?,
+                None => writeln!(f)?,
+            }
+        }
+
+        write!(
+            f,
+            "The MSRV changed {} times across {} revisions",
+            self.changes().count(),
+            self.revisions.len()
+        )
+    }
+}
+
+impl From<MsrvHistory> for Event {
+    fn from(it: MsrvHistory) -> Self {
+        Message::MsrvHistory(it).into()
+    }
+}
+
+/// A revision of the crate, e.g. a tag, with the MSRV declared at that revision.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct HistoryEntry {
+    revision: String,
+    commit: String,
+    /// The date of the commit
+    #[serde(skip_serializing_if = "Option::is_none")]
+    date: Option<Date>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    msrv: Option<BareVersion>,
+    change: MsrvChange,
+    /// Whether the MSRV was verified to hold, with `--verify`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    verified: Option<bool>,
+}
+
+impl HistoryEntry {
+    /// A revision, with the MSRV declared at that revision and how it changed since the MSRV of
+    /// the previous revision.
+    pub fn new(
+        revision: impl Into<String>,
+        commit: impl Into<String>,
+        date: Option<Date>,
+        msrv: Option<BareVersion>,
+        previous: Option<&BareVersion>,
+    ) -> Self {
+        let change = MsrvChange::between(previous, msrv.as_ref());
+
+        Self {
+            revision: revision.into(),
+            commit: commit.into(),
+            date,
+            msrv,
+            change,
+            verified: None,
+        }
+    }
+
+    pub fn with_verified(mut self, verified: Option<bool>) -> Self {
+        self.verified = verified;
+        self
+    }
+
+    pub fn revision(&self) -> &str {
+        &self.revision
+    }
+
+    pub fn commit(&self) -> &str {
+        &self.commit
+    }
+
+    pub fn date(&self) -> Option<Date> {
+        self.date
+    }
+
+    pub fn msrv(&self) -> Option<&BareVersion> {
+        self.msrv.as_ref()
+    }
+
+    pub fn change(&self) -> MsrvChange {
+        self.change
+    }
+
+    pub fn verified(&self) -> Option<bool> {
+        self.verified
+    }
+}
+
+/// How the MSRV of a revision differs from the MSRV of the previous revision.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MsrvChange {
+    /// The MSRV is the same as before, or there still is none
+    Unchanged,
+    /// An MSRV is declared, where there was none before
+    Declared,
+    Raised,
+    Lowered,
+    /// The MSRV is no longer declared
+    Removed,
+}
+
+impl MsrvChange {
+    pub fn between(previous: Option<&BareVersion>, current: Option<&BareVersion>) -> Self {
+        match (previous, current) {
+            (None, None) => Self::Unchanged,
+            (None, Some(_)) => Self::Declared,
+            (Some(_), None) => Self::Removed,
+            (Some(previous), Some(current)) => {
+                let previous = previous.to_semver_version();
+                let current = current.to_semver_version();
+
+                if current > previous {
+                    Self::Raised
+                } else if current < previous {
+                    Self::Lowered
+                } else {
+                    Self::Unchanged
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for MsrvChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unchanged => write!(f, "unchanged"),
+            Self::Declared => write!(f, "declared"),
+            Self::Raised => write!(f, "raised"),
+            Self::Lowered => write!(f, "lowered"),
+            Self::Removed => write!(f, "removed"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::event::Message;
+    use crate::reporter::TestReporter;
+    use storyteller::Reporter;
+
+    fn history() -> MsrvHistory {
+        let v1 = BareVersion::TwoComponents(1, 56);
+        let v2 = BareVersion::TwoComponents(1, 60);
+
+        MsrvHistory::new(vec![
+            HistoryEntry::new("v0.1.0", "a1", Some(Date::new(2021, 3, 1)), None, None),
+            HistoryEntry::new(
+                "v1.0.0",
+                "b2",
+                Some(Date::new(2022, 1, 31)),
+                Some(v1.clone()),
+                None,
+            )
+            .with_verified(Some(true)),
+            HistoryEntry::new("v1.1.0", "c3", None, Some(v2), Some(&v1)),
+        ])
+    }
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporter::default();
+        let event = history();
+
+        reporter.reporter().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::new(Message::MsrvHistory(event)),]
+        );
+    }
+
+    #[yare::parameterized(
+        none = { None, None, MsrvChange::Unchanged },
+        declared = { None, Some(BareVersion::TwoComponents(1, 56)), MsrvChange::Declared },
+        removed = { Some(BareVersion::TwoComponents(1, 56)), None, MsrvChange::Removed },
+        raised = { Some(BareVersion::TwoComponents(1, 56)), Some(BareVersion::ThreeComponents(1, 56, 1)), MsrvChange::Raised },
+        lowered = { Some(BareVersion::TwoComponents(1, 60)), Some(BareVersion::TwoComponents(1, 56)), MsrvChange::Lowered },
+        same = { Some(BareVersion::TwoComponents(1, 56)), Some(BareVersion::ThreeComponents(1, 56, 0)), MsrvChange::Unchanged },
+    )]
+    fn change(previous: Option<BareVersion>, current: Option<BareVersion>, expected: MsrvChange) {
+        assert_eq!(
+            MsrvChange::between(previous.as_ref(), current.as_ref()),
+            expected
+        );
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            history().to_string(),
+            "v0.1.0  2021-03-01  none      unchanged\n\
+             v1.0.0  2022-01-31  1.56      declared, verified\n\
+             v1.1.0  -           1.60      raised\n\
+             The MSRV changed 2 times across 3 revisions"
+        );
+    }
+}
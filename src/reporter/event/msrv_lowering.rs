@@ -0,0 +1,274 @@
+use crate::manifest::bare_version::BareVersion;
+use crate::manifest::syntax::ManifestFeature;
+use crate::reporter::event::Message;
+use crate::semver;
+use crate::Event;
+use std::fmt;
+
+/// What it would take to support the releases less recent than the MSRV, as reported by
+/// `cargo msrv lower`.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct MsrvLowering {
+    msrv: BareVersion,
+    /// The releases which were tried, from the most recent to the least recent
+    steps: Vec<LoweringStep>,
+}
+
+impl MsrvLowering {
+    pub fn new(msrv: BareVersion, steps: Vec<LoweringStep>) -> Self {
+        Self { msrv, steps }
+    }
+
+    pub fn msrv(&self) -> &BareVersion {
+        &self.msrv
+    }
+
+    pub fn steps(&self) -> &[LoweringStep] {
+        &self.steps
+    }
+}
+
+impl fmt::Display for MsrvLowering {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.steps.is_empty() {
+            return write!(
+                f,
+                "There are no releases less recent than the MSRV, Rust {}, to try",
+                self.msrv
+            );
+        }
+
+        write!(f, "Lowering the MSRV from Rust {}:", self.msrv)?;
+
+        for step in &self.steps {
+            match step.blockers.len() {
+                0 => write!(f, "\nRust {} is compatible, without changes", step.version)?,
+                1 => write!(f, "\nRust {} needs 1 change:", step.version)?,
+                n => write!(f, "\nRust {} needs {} changes:", step.version, n)?,
+            }
+
+            for blocker in &step.blockers {
+                write!(f, "\n  - {}", blocker)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl From<MsrvLowering> for Event {
+    fn from(it: MsrvLowering) -> Self {
+        Message::MsrvLowering(it).into()
+    }
+}
+
+/// A release less recent than the MSRV, and what keeps the crate from supporting it.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct LoweringStep {
+    version: semver::Version,
+    /// Empty if the crate is compatible with the release as it is
+    blockers: Vec<Blocker>,
+}
+
+impl LoweringStep {
+    pub fn new(version: semver::Version, blockers: Vec<Blocker>) -> Self {
+        Self { version, blockers }
+    }
+
+    pub fn version(&self) -> &semver::Version {
+        &self.version
+    }
+
+    pub fn blockers(&self) -> &[Blocker] {
+        &self.blockers
+    }
+
+    pub fn is_compatible(&self) -> bool {
+        self.blockers.is_empty()
+    }
+}
+
+/// Keeps the crate from supporting a release, with the change which would resolve it.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case", tag = "kind")]
+pub enum Blocker {
+    /// A dependency requires a more recent Rust version, or doesn't build with the release. It
+    /// may be pinned to an older release, which is known when a crates.io index is given.
+    Dependency {
+        name: String,
+        version: Option<semver::Version>,
+        /// The Rust version which the dependency requires, if it declares one
+        requires: Option<semver::Version>,
+        /// The most recent release of the dependency which supports the release
+        pin: Option<semver::Version>,
+        /// Whether the crate depends on it optionally, behind a feature
+        optional: bool,
+    },
+    /// The code of the crate uses a language or library feature which is unstable, or unknown,
+    /// in the release
+    Code {
+        message: String,
+        /// The feature, if the compiler named it
+        feature: Option<String>,
+        location: Option<String>,
+    },
+    /// The crate uses an edition which the release doesn't know
+    Edition { edition: String },
+    /// The Cargo manifest uses syntax which the cargo of the release can't read
+    ManifestSyntax {
+        feature: ManifestFeature,
+        key: String,
+    },
+    /// The check failed in a way which isn't understood
+    Other { message: String },
+}
+
+impl fmt::Display for Blocker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Dependency {
+                name,
+                version,
+                requires,
+                pin,
+                optional,
+            } => {
+                match pin {
+                    Some(pin) => write!(
+                        f,
+                        "pin {} to {}, with `cargo update -p {} --precise {}`",
+                        name, pin, name, pin
+                    )?,
+                    None => write!(f, "pin {} to an older release", name)?,
+                }
+
+                if *optional {
+                    write!(f, ", or disable the feature which enables it")?;
+                }
+
+                let dependency = match version {
+                    Some(version) => format!("{} {}", name, version),
+                    None => name.to_string(),
+                };
+
+                match requires {
+                    Some(requires) => write!(f, " ({} requires Rust {})", dependency, requires),
+                    None => write!(f, " ({} doesn't build)", dependency),
+                }
+            }
+            Self::Code {
+                message,
+                feature,
+                location,
+            } => {
+                write!(f, "gate the code")?;
+
+                if let Some(location) = location {
+                    write!(f, " at {}", location)?;
+                }
+
+                write!(f, " behind a cfg, or rewrite it ({}", message)?;
+
+                if let Some(feature) = feature {
+                    write!(f, ", feature '{}'", feature)?;
+                }
+
+                write!(f, ")")
+            }
+            Self::Edition { edition } => write!(
+                f,
+                "lower the edition of the crate from {}, which the release doesn't know",
+                edition
+            ),
+            Self::ManifestSyntax { feature, key } => write!(
+                f,
+                "remove {} at '{}' from the Cargo manifest, which cargo reads since Rust {}",
+                feature,
+                key,
+                feature.readable_since()
+            ),
+            Self::Other { message } => {
+                write!(f, "no suggestion, the check failed with: {}", message)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::event::Message;
+    use crate::reporter::TestReporter;
+    use storyteller::Reporter;
+
+    fn lowering() -> MsrvLowering {
+        MsrvLowering::new(
+            BareVersion::TwoComponents(1, 60),
+            vec![
+                LoweringStep::new(semver::Version::new(1, 59, 0), vec![]),
+                LoweringStep::new(
+                    semver::Version::new(1, 58, 1),
+                    vec![
+                        Blocker::Dependency {
+                            name: "log".to_string(),
+                            version: Some(semver::Version::new(0, 4, 20)),
+                            requires: Some(semver::Version::new(1, 60, 0)),
+                            pin: Some(semver::Version::new(0, 4, 17)),
+                            optional: false,
+                        },
+                        Blocker::Code {
+                            message: "`let...else` statements are unstable".to_string(),
+                            feature: Some("let_else".to_string()),
+                            location: Some("src/lib.rs:3:5".to_string()),
+                        },
+                    ],
+                ),
+            ],
+        )
+    }
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporter::default();
+        let event = lowering();
+
+        reporter.reporter().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::new(Message::MsrvLowering(event)),]
+        );
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            lowering().to_string(),
+            "Lowering the MSRV from Rust 1.60:
+Rust 1.59.0 is compatible, without changes
+Rust 1.58.1 needs 2 changes:
+  - pin log to 0.4.17, with `cargo update -p log --precise 0.4.17` (log 0.4.20 requires Rust 1.60.0)
+  - gate the code at src/lib.rs:3:5 behind a cfg, or rewrite it (`let...else` statements are unstable, feature 'let_else')"
+        );
+    }
+
+    #[yare::parameterized(
+        unknown_pin = {
+            Blocker::Dependency { name: "log".to_string(), version: None, requires: None, pin: None, optional: true },
+            "pin log to an older release, or disable the feature which enables it (log doesn't build)"
+        },
+        edition = {
+            Blocker::Edition { edition: "2021".to_string() },
+            "lower the edition of the crate from 2021, which the release doesn't know"
+        },
+        manifest_syntax = {
+            Blocker::ManifestSyntax { feature: ManifestFeature::Resolver2, key: "package.resolver".to_string() },
+            "remove the second feature resolver at 'package.resolver' from the Cargo manifest, which cargo reads since Rust 1.51.0"
+        },
+    )]
+    fn display_blocker(blocker: Blocker, expected: &str) {
+        assert_eq!(blocker.to_string(), expected);
+    }
+}
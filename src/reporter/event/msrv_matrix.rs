@@ -0,0 +1,248 @@
+use crate::config::FeatureSet;
+use crate::reporter::event::Message;
+use crate::semver;
+use crate::Event;
+use std::fmt;
+
+/// The MSRV of each configuration of targets and features, as reported by `cargo msrv matrix`.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct MsrvMatrix {
+    targets: Vec<MatrixRow>,
+}
+
+impl MsrvMatrix {
+    pub fn new(targets: Vec<MatrixRow>) -> Self {
+        Self { targets }
+    }
+
+    pub fn targets(&self) -> &[MatrixRow] {
+        &self.targets
+    }
+}
+
+impl fmt::Display for MsrvMatrix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let header = ["Target", "Features", "MSRV"];
+
+        let rows = self
+            .targets
+            .iter()
+            .flat_map(|row| {
+                row.configurations.iter().map(move |cell| {
+                    [
+                        row.target.clone(),
+                        cell.features.to_string(),
+                        cell.outcome(),
+                    ]
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let mut widths = header.map(str::len);
+
+        for row in &rows {
+            for (width, column) in widths.iter_mut().zip(row) {
+                *width = (*width).max(column.len());
+            }
+        }
+
+        writeln!(
+            f,
+            "{:<w0$}  {:<w1$}  {}",
+            header[0],
+            header[1],
+            header[2],
+            w0 = widths[0],
+            w1 = widths[1]
+        )?;
+
+        for [target, features, outcome] in &rows {
+            writeln!(
+                f,
+                "{:<w0$}  {:<w1$}  {}",
+                target,
+                features,
+                outcome,
+                w0 = widths[0],
+                w1 = widths[1]
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl From<MsrvMatrix> for Event {
+    fn from(it: MsrvMatrix) -> Self {
+        Message::MsrvMatrix(it).into()
+    }
+}
+
+/// The MSRV of each feature configuration, for a single target.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct MatrixRow {
+    target: String,
+    configurations: Vec<MatrixCell>,
+}
+
+impl MatrixRow {
+    pub fn new(target: impl Into<String>, configurations: Vec<MatrixCell>) -> Self {
+        Self {
+            target: target.into(),
+            configurations,
+        }
+    }
+
+    pub fn target(&self) -> &str {
+        &self.target
+    }
+
+    pub fn configurations(&self) -> &[MatrixCell] {
+        &self.configurations
+    }
+}
+
+/// The outcome of the search for a single configuration of target and features: the MSRV, no
+/// compatible version at all, or an error which prevented the search from completing.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct MatrixCell {
+    features: FeatureSet,
+    msrv: Option<semver::Version>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl MatrixCell {
+    pub fn msrv(features: FeatureSet, msrv: semver::Version) -> Self {
+        Self {
+            features,
+            msrv: Some(msrv),
+            error: None,
+        }
+    }
+
+    pub fn none(features: FeatureSet) -> Self {
+        Self {
+            features,
+            msrv: None,
+            error: None,
+        }
+    }
+
+    pub fn error(features: FeatureSet, error: impl Into<String>) -> Self {
+        Self {
+            features,
+            msrv: None,
+            error: Some(error.into()),
+        }
+    }
+
+    pub fn features(&self) -> &FeatureSet {
+        &self.features
+    }
+
+    pub fn msrv_version(&self) -> Option<&semver::Version> {
+        self.msrv.as_ref()
+    }
+
+    pub fn error_message(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    fn outcome(&self) -> String {
+        match (&self.msrv, &self.error) {
+            (Some(msrv), _) => msrv.to_string(),
+            (None, Some(error)) => format!("error: {}", error),
+            (None, None) => "none".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::event::Message;
+    use crate::reporter::TestReporter;
+    use storyteller::Reporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporter::default();
+        let event = MsrvMatrix::new(vec![MatrixRow::new(
+            "x86_64-unknown-linux-gnu",
+            vec![MatrixCell::msrv(
+                FeatureSet::Default,
+                semver::Version::new(1, 56, 0),
+            )],
+        )]);
+
+        reporter.reporter().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::new(Message::MsrvMatrix(event)),]
+        );
+    }
+
+    #[test]
+    fn display() {
+        let matrix = MsrvMatrix::new(vec![
+            MatrixRow::new(
+                "x86_64-unknown-linux-gnu",
+                vec![
+                    MatrixCell::msrv(FeatureSet::Default, semver::Version::new(1, 56, 0)),
+                    MatrixCell::none(FeatureSet::AllFeatures),
+                ],
+            ),
+            MatrixRow::new(
+                "wasm32-unknown-unknown",
+                vec![
+                    MatrixCell::msrv(FeatureSet::Default, semver::Version::new(1, 60, 0)),
+                    MatrixCell::error(FeatureSet::AllFeatures, "unavailable"),
+                ],
+            ),
+        ]);
+
+        assert_eq!(
+            matrix.to_string(),
+            "Target                    Features      MSRV
+x86_64-unknown-linux-gnu  default       1.56.0
+x86_64-unknown-linux-gnu  all-features  none
+wasm32-unknown-unknown    default       1.60.0
+wasm32-unknown-unknown    all-features  error: unavailable
+"
+        );
+    }
+
+    #[test]
+    fn nested_json() {
+        let matrix = MsrvMatrix::new(vec![MatrixRow::new(
+            "x86_64-unknown-linux-gnu",
+            vec![
+                MatrixCell::msrv(
+                    FeatureSet::Features("a,b".to_string()),
+                    semver::Version::new(1, 56, 0),
+                ),
+                MatrixCell::none(FeatureSet::NoDefaultFeatures),
+            ],
+        )]);
+
+        let json = serde_json::to_value(&matrix).unwrap();
+
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "targets": [{
+                    "target": "x86_64-unknown-linux-gnu",
+                    "configurations": [
+                        { "features": "a,b", "msrv": "1.56.0" },
+                        { "features": "no-default-features", "msrv": null },
+                    ]
+                }]
+            })
+        );
+    }
+}
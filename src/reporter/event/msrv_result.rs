@@ -1,20 +1,46 @@
 use crate::config::{Config, SearchMethod};
+use crate::fingerprint::Fingerprint;
 use crate::manifest::bare_version::BareVersion;
+use crate::manifest::version_bump::VersionBump;
+use crate::reporter::event::compatibility::{deserialize_secs, serialize_secs};
 use crate::reporter::event::Message;
+use crate::search_method::{Polarity, SearchSeeds};
 use crate::typed_bool::{False, True};
 use crate::{semver, Event};
+use std::time::Duration;
 
-#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct MsrvResult {
+    // The search space is not part of the json output, so it's left at its default when the
+    // result is deserialized
     #[serde(skip)]
     pub target: String,
-    #[serde(skip)]
+    #[serde(skip, default = "unknown_version")]
     pub minimum_version: BareVersion,
-    #[serde(skip)]
+    #[serde(skip, default = "unknown_version")]
     pub maximum_version: BareVersion,
+    /// The versions which were left out of the search space, e.g. with `--exclude-version`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub excluded_versions: Vec<BareVersion>,
     #[serde(skip)]
     pub search_method: SearchMethod,
+    #[serde(skip)]
+    pub polarity: Polarity,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    fingerprint: Option<Fingerprint>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    dependency_cache: Option<DependencyCache>,
+    /// The suggested version bump of the crate, if the MSRV is more recent than the declared MSRV
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    version_bump: Option<VersionBump>,
+    /// The most recent patch release of the minor version of the MSRV, if it is more recent than
+    /// the MSRV, e.g. `1.64.1` for `1.64.0`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    newer_patch_release: Option<semver::Version>,
+    /// The outcomes which were known beforehand, given with `--known-good` and `--known-bad`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    seeds: Option<SearchSeeds>,
 
     #[serde(flatten)]
     result: ResultDetails,
@@ -37,8 +63,15 @@ impl MsrvResult {
                 .maximum_version()
                 .map(Clone::clone)
                 .unwrap_or_else(|| max),
+            excluded_versions: config.excluded_versions().to_vec(),
 
             search_method: config.search_method(),
+            polarity: config.polarity(),
+            fingerprint: None,
+            dependency_cache: None,
+            version_bump: None,
+            newer_patch_release: None,
+            seeds: None,
 
             result: ResultDetails::Determined {
                 version,
@@ -58,13 +91,71 @@ impl MsrvResult {
                 .maximum_version()
                 .map(Clone::clone)
                 .unwrap_or_else(|| max),
+            excluded_versions: config.excluded_versions().to_vec(),
 
             search_method: config.search_method(),
+            polarity: config.polarity(),
+            fingerprint: None,
+            dependency_cache: None,
+            version_bump: None,
+            newer_patch_release: None,
+            seeds: None,
 
             result: ResultDetails::Undetermined { success: False },
         }
     }
 
+    pub fn with_fingerprint(mut self, fingerprint: Fingerprint) -> Self {
+        self.fingerprint = Some(fingerprint);
+        self
+    }
+
+    pub fn with_dependency_cache(mut self, dependency_cache: DependencyCache) -> Self {
+        self.dependency_cache = Some(dependency_cache);
+        self
+    }
+
+    pub fn with_version_bump(mut self, version_bump: VersionBump) -> Self {
+        self.version_bump = Some(version_bump);
+        self
+    }
+
+    pub fn with_newer_patch_release(mut self, version: semver::Version) -> Self {
+        self.newer_patch_release = Some(version);
+        self
+    }
+
+    pub fn with_seeds(mut self, seeds: SearchSeeds) -> Self {
+        self.seeds = Some(seeds);
+        self
+    }
+
+    /// The environment in which the result was obtained, if it was recorded.
+    pub fn fingerprint(&self) -> Option<&Fingerprint> {
+        self.fingerprint.as_ref()
+    }
+
+    /// How the dependencies were shared between the checks, with `--fetch-once`.
+    pub fn dependency_cache(&self) -> Option<&DependencyCache> {
+        self.dependency_cache.as_ref()
+    }
+
+    /// The suggested version bump of the crate, since the MSRV is raised.
+    pub fn version_bump(&self) -> Option<&VersionBump> {
+        self.version_bump.as_ref()
+    }
+
+    /// The most recent patch release of the minor version of the MSRV, if the MSRV isn't the most
+    /// recent one.
+    pub fn newer_patch_release(&self) -> Option<&semver::Version> {
+        self.newer_patch_release.as_ref()
+    }
+
+    /// The outcomes which were known beforehand, and narrowed the search space, if any.
+    pub fn seeds(&self) -> Option<&SearchSeeds> {
+        self.seeds.as_ref()
+    }
+
     pub fn msrv(&self) -> Option<&semver::Version> {
         if let Self {
             result: ResultDetails::Determined { version, .. },
@@ -78,13 +169,49 @@ impl MsrvResult {
     }
 }
 
+fn unknown_version() -> BareVersion {
+    BareVersion::TwoComponents(0, 0)
+}
+
 impl From<MsrvResult> for Event {
     fn from(it: MsrvResult) -> Self {
         Message::MsrvResult(it).into()
     }
 }
 
-#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+/// The dependencies which were fetched once, and shared by the toolchain checks which ran offline.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct DependencyCache {
+    /// Time it took to fetch the dependencies
+    #[serde(
+        serialize_with = "serialize_secs",
+        deserialize_with = "deserialize_secs"
+    )]
+    #[schemars(with = "f64")]
+    fetch: Duration,
+    /// The amount of checks which used the fetched dependencies
+    offline_checks: u32,
+}
+
+impl DependencyCache {
+    pub fn new(fetch: Duration, offline_checks: u32) -> Self {
+        Self {
+            fetch,
+            offline_checks,
+        }
+    }
+
+    pub fn fetch(&self) -> Duration {
+        self.fetch
+    }
+
+    pub fn offline_checks(&self) -> u32 {
+        self.offline_checks
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "snake_case")]
 enum ResultDetails {
     Determined {
@@ -96,6 +223,49 @@ enum ResultDetails {
     },
 }
 
+// Unlike the schema which would be derived for an externally tagged enum, properties are allowed
+// next to the variant, since the details are flattened into the `MsrvResult`
+impl schemars::JsonSchema for ResultDetails {
+    fn is_referenceable() -> bool {
+        false
+    }
+
+    fn schema_name() -> String {
+        "ResultDetails".to_string()
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        let determined = object_schema(vec![
+            ("version", gen.subschema_for::<semver::Version>()),
+            ("success", gen.subschema_for::<True>()),
+        ]);
+        let undetermined = object_schema(vec![("success", gen.subschema_for::<False>())]);
+
+        let mut schema = schemars::schema::SchemaObject::default();
+        schema.subschemas().one_of = Some(vec![
+            object_schema(vec![("determined", determined)]),
+            object_schema(vec![("undetermined", undetermined)]),
+        ]);
+        schema.into()
+    }
+}
+
+/// An object which requires each of the given properties.
+fn object_schema(properties: Vec<(&str, schemars::schema::Schema)>) -> schemars::schema::Schema {
+    let mut schema = schemars::schema::SchemaObject {
+        instance_type: Some(schemars::schema::InstanceType::Object.into()),
+        ..Default::default()
+    };
+    let object = schema.object();
+
+    for (name, property) in properties {
+        object.properties.insert(name.to_string(), property);
+        object.required.insert(name.to_string());
+    }
+
+    schema.into()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -144,4 +314,117 @@ mod tests {
             assert_eq!(res.msrv(), None);
         }
     }
+
+    #[yare::parameterized(
+        determined = { Some(semver::Version::new(1, 3, 0)) },
+        undetermined = { None },
+    )]
+    fn deserialize_serialized_event(version: Option<semver::Version>) {
+        let config = Config::new(Action::Find, "x86_64-unknown-linux-gnu".to_string());
+        let min = BareVersion::TwoComponents(1, 0);
+        let max = BareVersion::ThreeComponents(1, 4, 0);
+
+        let event = match version {
+            Some(version) => MsrvResult::new_msrv(version, &config, min, max),
+            None => MsrvResult::none(&config, min, max),
+        };
+
+        let json = serde_json::to_string(&Event::new(Message::MsrvResult(event.clone()))).unwrap();
+        let deserialized = serde_json::from_str::<MsrvResult>(&json).unwrap();
+
+        assert_eq!(deserialized.msrv(), event.msrv());
+        assert_eq!(
+            serde_json::to_string(&deserialized).unwrap(),
+            serde_json::to_string(&event).unwrap()
+        );
+    }
+
+    #[test]
+    fn search_space_is_not_serialized() {
+        let config = Config::new(Action::Find, "x86_64-unknown-linux-gnu".to_string());
+        let min = BareVersion::TwoComponents(1, 0);
+        let max = BareVersion::ThreeComponents(1, 4, 0);
+
+        let event = MsrvResult::new_msrv(semver::Version::new(1, 3, 0), &config, min, max);
+        let json = serde_json::to_value(&event).unwrap();
+
+        for field in [
+            "target",
+            "minimum_version",
+            "maximum_version",
+            "search_method",
+            "polarity",
+        ] {
+            assert!(json.get(field).is_none(), "{} is serialized", field);
+        }
+    }
+
+    #[test]
+    fn serialize_dependency_cache() {
+        let config = Config::new(Action::Find, "x86_64-unknown-linux-gnu".to_string());
+        let min = BareVersion::TwoComponents(1, 0);
+        let max = BareVersion::ThreeComponents(1, 4, 0);
+
+        let event = MsrvResult::new_msrv(semver::Version::new(1, 3, 0), &config, min, max)
+            .with_dependency_cache(DependencyCache::new(Duration::from_secs(2), 3));
+
+        let json = serde_json::to_value(&event).unwrap();
+
+        assert_eq!(
+            json["dependency_cache"],
+            serde_json::json!({
+                "fetch": 2.0,
+                "offline_checks": 3,
+            })
+        );
+
+        // the search space is not serialized, so only the field itself round-trips
+        let deserialized = serde_json::from_value::<MsrvResult>(json).unwrap();
+        assert_eq!(deserialized.dependency_cache(), event.dependency_cache());
+    }
+
+    #[test]
+    fn serialize_newer_patch_release() {
+        let config = Config::new(Action::Find, "x86_64-unknown-linux-gnu".to_string());
+        let min = BareVersion::TwoComponents(1, 0);
+        let max = BareVersion::ThreeComponents(1, 4, 0);
+
+        let event = MsrvResult::new_msrv(semver::Version::new(1, 3, 0), &config, min, max)
+            .with_newer_patch_release(semver::Version::new(1, 3, 1));
+
+        let json = serde_json::to_value(&event).unwrap();
+
+        assert_eq!(json["newer_patch_release"], serde_json::json!("1.3.1"));
+
+        // the search space is not serialized, so only the field itself round-trips
+        let deserialized = serde_json::from_value::<MsrvResult>(json).unwrap();
+        assert_eq!(
+            deserialized.newer_patch_release(),
+            event.newer_patch_release()
+        );
+    }
+
+    #[test]
+    fn serialize_seeds() {
+        let config = Config::new(Action::Find, "x86_64-unknown-linux-gnu".to_string());
+        let min = BareVersion::ThreeComponents(1, 56, 0);
+        let max = BareVersion::TwoComponents(1, 70);
+
+        let event = MsrvResult::new_msrv(semver::Version::new(1, 60, 0), &config, min, max)
+            .with_seeds(SearchSeeds {
+                known_good: Some(BareVersion::TwoComponents(1, 70)),
+                known_bad: Some(BareVersion::TwoComponents(1, 55)),
+            });
+
+        let json = serde_json::to_value(&event).unwrap();
+
+        assert_eq!(
+            json["seeds"],
+            serde_json::json!({ "known_good": "1.70", "known_bad": "1.55" })
+        );
+
+        // the search space is not serialized, so only the field itself round-trips
+        let deserialized = serde_json::from_value::<MsrvResult>(json).unwrap();
+        assert_eq!(deserialized.seeds(), event.seeds());
+    }
 }
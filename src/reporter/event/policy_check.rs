@@ -0,0 +1,218 @@
+use crate::manifest::bare_version::BareVersion;
+use crate::reporter::event::Message;
+use crate::semver;
+use crate::Event;
+use std::fmt;
+use std::path::PathBuf;
+
+/// The outcome of `cargo msrv policy check`: the MSRV's which were checked against the rules of
+/// the policy file, and each rule they violate.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct PolicyCheck {
+    policy: PathBuf,
+    /// The MSRV as specified in the Cargo manifest
+    msrv: Option<BareVersion>,
+    /// The MSRV as found by the latest `find` run, if its result file was given
+    found_msrv: Option<semver::Version>,
+    violations: Vec<PolicyViolation>,
+}
+
+impl PolicyCheck {
+    pub fn new(
+        policy: impl Into<PathBuf>,
+        msrv: Option<BareVersion>,
+        found_msrv: Option<semver::Version>,
+        violations: Vec<PolicyViolation>,
+    ) -> Self {
+        Self {
+            policy: policy.into(),
+            msrv,
+            found_msrv,
+            violations,
+        }
+    }
+
+    pub fn msrv(&self) -> Option<&BareVersion> {
+        self.msrv.as_ref()
+    }
+
+    pub fn found_msrv(&self) -> Option<&semver::Version> {
+        self.found_msrv.as_ref()
+    }
+
+    pub fn violations(&self) -> &[PolicyViolation] {
+        &self.violations
+    }
+}
+
+impl fmt::Display for PolicyCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.violations.is_empty() {
+            return writeln!(
+                f,
+                "The MSRV satisfies each rule of the policy in '{}'",
+                self.policy.display()
+            );
+        }
+
+        writeln!(
+            f,
+            "The MSRV violates {} rule(s) of the policy in '{}':",
+            self.violations.len(),
+            self.policy.display()
+        )?;
+
+        for violation in &self.violations {
+            writeln!(f, "  - {}", violation)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl From<PolicyCheck> for Event {
+    fn from(it: PolicyCheck) -> Self {
+        Message::PolicyCheck(it).into()
+    }
+}
+
+/// A rule of the policy file which is violated, tagged by the key of the rule.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(tag = "rule", rename_all = "snake_case")]
+pub enum PolicyViolation {
+    /// The Cargo manifest does not specify an MSRV
+    MissingMsrv,
+    /// The MSRV is more recent than the maximum allowed MSRV
+    MaxMsrv {
+        subject: MsrvSubject,
+        msrv: semver::Version,
+        max_msrv: BareVersion,
+    },
+    /// The MSRV found by `find` moves further away from the MSRV of the Cargo manifest than the
+    /// allowed number of minor versions
+    MaxMinorBump {
+        from: semver::Version,
+        to: semver::Version,
+        max_minor_bump: u64,
+    },
+    /// The MSRV supports fewer stable releases than required
+    MinSupportedReleases {
+        subject: MsrvSubject,
+        msrv: semver::Version,
+        supported_releases: u64,
+        min_supported_releases: u64,
+    },
+}
+
+impl fmt::Display for PolicyViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingMsrv => write!(f, "the Cargo manifest does not specify an MSRV"),
+            Self::MaxMsrv {
+                subject,
+                msrv,
+                max_msrv,
+            } => write!(
+                f,
+                "max_msrv: the {} ({}) is more recent than {}",
+                subject, msrv, max_msrv
+            ),
+            Self::MaxMinorBump {
+                from,
+                to,
+                max_minor_bump,
+            } => write!(
+                f,
+                "max_minor_bump: the MSRV moves from {} to {}, while at most {} minor version(s) are allowed",
+                from, to, max_minor_bump
+            ),
+            Self::MinSupportedReleases {
+                subject,
+                msrv,
+                supported_releases,
+                min_supported_releases,
+            } => write!(
+                f,
+                "min_supported_releases: the {} ({}) supports {} stable release(s), while at least {} are required",
+                subject, msrv, supported_releases, min_supported_releases
+            ),
+        }
+    }
+}
+
+/// Which MSRV violates a rule.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MsrvSubject {
+    /// The MSRV as specified in the Cargo manifest
+    Manifest,
+    /// The MSRV as found by the latest `find` run
+    FindResult,
+}
+
+impl fmt::Display for MsrvSubject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Manifest => write!(f, "MSRV of the Cargo manifest"),
+            Self::FindResult => write!(f, "MSRV found by `find`"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::event::Message;
+    use crate::reporter::TestReporter;
+    use storyteller::Reporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporter::default();
+        let event = PolicyCheck::new(
+            "msrv-policy.toml",
+            Some(BareVersion::TwoComponents(1, 56)),
+            Some(semver::Version::new(1, 60, 0)),
+            vec![PolicyViolation::MaxMinorBump {
+                from: semver::Version::new(1, 56, 0),
+                to: semver::Version::new(1, 60, 0),
+                max_minor_bump: 2,
+            }],
+        );
+
+        reporter.reporter().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::new(Message::PolicyCheck(event)),]
+        );
+    }
+
+    #[test]
+    fn tagged_violations() {
+        let violations = vec![
+            PolicyViolation::MissingMsrv,
+            PolicyViolation::MaxMsrv {
+                subject: MsrvSubject::FindResult,
+                msrv: semver::Version::new(1, 66, 0),
+                max_msrv: BareVersion::TwoComponents(1, 65),
+            },
+        ];
+
+        let json = serde_json::to_value(&violations).unwrap();
+
+        assert_eq!(
+            json,
+            serde_json::json!([
+                { "rule": "missing_msrv" },
+                {
+                    "rule": "max_msrv",
+                    "subject": "find_result",
+                    "msrv": "1.66.0",
+                    "max_msrv": "1.65",
+                },
+            ])
+        );
+    }
+}
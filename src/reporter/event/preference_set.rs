@@ -0,0 +1,65 @@
+use crate::reporter::event::Message;
+use crate::Event;
+use std::path::{Path, PathBuf};
+
+/// Reported when a preference was written to the preferences file, by `cargo msrv config set`.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct PreferenceSet {
+    key: String,
+    value: String,
+    path: PathBuf,
+}
+
+impl PreferenceSet {
+    pub fn new(key: impl Into<String>, value: impl Into<String>, path: PathBuf) -> Self {
+        Self {
+            key: key.into(),
+            value: value.into(),
+            path,
+        }
+    }
+
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl From<PreferenceSet> for Event {
+    fn from(it: PreferenceSet) -> Self {
+        Message::PreferenceSet(it).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::event::Message;
+    use crate::reporter::TestReporter;
+    use storyteller::Reporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporter::default();
+        let event = PreferenceSet::new(
+            "output_format",
+            "json",
+            PathBuf::from("cargo-msrv/preferences.toml"),
+        );
+
+        reporter.reporter().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::new(Message::PreferenceSet(event)),]
+        );
+    }
+}
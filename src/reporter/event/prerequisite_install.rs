@@ -0,0 +1,58 @@
+use crate::reporter::event::Message;
+use crate::Event;
+
+/// Reported when a missing prerequisite is installed, because cargo-msrv was run with `--setup`.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct PrerequisiteInstall {
+    tool: String,
+    /// The command which installs the tool
+    command: String,
+}
+
+impl PrerequisiteInstall {
+    pub fn new(tool: impl Into<String>, command: impl Into<String>) -> Self {
+        Self {
+            tool: tool.into(),
+            command: command.into(),
+        }
+    }
+
+    pub fn tool(&self) -> &str {
+        &self.tool
+    }
+
+    pub fn command(&self) -> &str {
+        &self.command
+    }
+}
+
+impl From<PrerequisiteInstall> for Event {
+    fn from(it: PrerequisiteInstall) -> Self {
+        Message::PrerequisiteInstall(it).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::event::Message;
+    use crate::reporter::TestReporter;
+    use storyteller::Reporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporter::default();
+        let event = PrerequisiteInstall::new(
+            "rustup",
+            "curl --proto '=https' --tlsv1.2 -sSf https://sh.rustup.rs | sh -s -- -y",
+        );
+
+        reporter.reporter().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::new(Message::PrerequisiteInstall(event))]
+        );
+    }
+}
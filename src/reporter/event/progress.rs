@@ -2,12 +2,18 @@ use crate::reporter::event::Message;
 use crate::Event;
 
 /// Progression indicates how far we are
-#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+///
+/// When a run consists of several units, like the configurations of `cargo msrv matrix` or the
+/// members of a workspace, the progress within the current unit is accompanied by the unit, and
+/// how far the run is in terms of units.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct Progress {
     current: u64,
     max: u64,
     iteration: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    unit: Option<ProgressUnit>,
 }
 
 impl From<Progress> for Event {
@@ -22,8 +28,62 @@ impl Progress {
             current,
             max,
             iteration,
+            unit: None,
         }
     }
+
+    /// The progress at the start of a unit, before any of its toolchains is checked.
+    pub fn start_of_unit(unit: ProgressUnit) -> Self {
+        Self::new(0, 0, 0).with_unit(Some(unit))
+    }
+
+    pub fn with_unit(mut self, unit: Option<ProgressUnit>) -> Self {
+        self.unit = unit;
+        self
+    }
+
+    pub fn unit(&self) -> Option<&ProgressUnit> {
+        self.unit.as_ref()
+    }
+
+    /// Whether the progress was reported at the start of a unit, see [`Progress::start_of_unit`].
+    pub fn is_start_of_unit(&self) -> bool {
+        self.unit.is_some() && self.iteration == 0
+    }
+}
+
+/// A unit of a run which consists of several units, e.g. a single configuration of the matrix.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ProgressUnit {
+    /// The name of the unit, e.g. the name of a workspace member
+    name: String,
+    /// The position of the unit, starting at 1
+    current: u64,
+    /// The amount of units in the run
+    total: u64,
+}
+
+impl ProgressUnit {
+    pub fn new(name: impl Into<String>, current: u64, total: u64) -> Self {
+        Self {
+            name: name.into(),
+            current,
+            total,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn current(&self) -> u64 {
+        self.current
+    }
+
+    pub fn total(&self) -> u64 {
+        self.total
+    }
 }
 
 #[cfg(test)]
@@ -45,4 +105,28 @@ mod tests {
             vec![Event::new(Message::Progress(event)),]
         );
     }
+
+    #[test]
+    fn reported_unit_event() {
+        let reporter = TestReporter::default();
+        let event = Progress::new(3, 20, 2).with_unit(Some(ProgressUnit::new("core", 2, 5)));
+
+        reporter.reporter().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::new(Message::Progress(event)),]
+        );
+    }
+
+    #[yare::parameterized(
+        without_unit = { Progress::new(3, 20, 2), serde_json::json!({ "current": 3, "max": 20, "iteration": 2 }) },
+        with_unit = {
+            Progress::start_of_unit(ProgressUnit::new("core", 2, 5)),
+            serde_json::json!({ "current": 0, "max": 0, "iteration": 0, "unit": { "name": "core", "current": 2, "total": 5 } })
+        },
+    )]
+    fn serialized(progress: Progress, expected: serde_json::Value) {
+        assert_eq!(serde_json::to_value(&progress).unwrap(), expected);
+    }
 }
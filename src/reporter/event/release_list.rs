@@ -0,0 +1,183 @@
+use crate::release_schedule::Date;
+use crate::reporter::event::Message;
+use crate::semver;
+use crate::Event;
+use std::fmt;
+
+/// The stable releases of the release index, and whether each is included in the search space, as
+/// reported by `cargo msrv releases`.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ReleaseList {
+    releases: Vec<ListedRelease>,
+}
+
+impl ReleaseList {
+    pub fn new(releases: Vec<ListedRelease>) -> Self {
+        Self { releases }
+    }
+
+    pub fn releases(&self) -> &[ListedRelease] {
+        &self.releases
+    }
+}
+
+impl fmt::Display for ReleaseList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for release in &self.releases {
+            match release.exclusion {
+                Some(exclusion) => writeln!(
+                    f,
+                    "{} ({}): excluded, {}",
+                    release.version, release.release_date, exclusion
+                )?,
+                None => writeln!(
+                    f,
+                    "{} ({}): included",
+                    release.version, release.release_date
+                )?,
+            }
+        }
+
+        let included = self
+            .releases
+            .iter()
+            .filter(|release| release.is_included())
+            .count();
+
+        writeln!(
+            f,
+            "{} of {} releases included",
+            included,
+            self.releases.len()
+        )
+    }
+}
+
+impl From<ReleaseList> for Event {
+    fn from(it: ReleaseList) -> Self {
+        Message::ReleaseList(it).into()
+    }
+}
+
+/// A release of the release index. The release date is the date on which the minor version was
+/// released, according to the Rust release schedule, since patch releases are not scheduled.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ListedRelease {
+    version: semver::Version,
+    release_date: Date,
+    included: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    exclusion: Option<Exclusion>,
+}
+
+impl ListedRelease {
+    pub fn new(version: semver::Version, release_date: Date, exclusion: Option<Exclusion>) -> Self {
+        Self {
+            version,
+            release_date,
+            included: exclusion.is_none(),
+            exclusion,
+        }
+    }
+
+    pub fn version(&self) -> &semver::Version {
+        &self.version
+    }
+
+    pub fn release_date(&self) -> Date {
+        self.release_date
+    }
+
+    pub fn is_included(&self) -> bool {
+        self.included
+    }
+
+    pub fn exclusion(&self) -> Option<Exclusion> {
+        self.exclusion
+    }
+}
+
+/// Why a release is left out of the search space.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Exclusion {
+    /// The release is less recent than the minimum version, e.g. as given by `--min`
+    BelowMinimumVersion,
+    /// The release is more recent than the maximum version, e.g. as given by `--max`
+    AboveMaximumVersion,
+    /// The release was excluded, e.g. with `--exclude-version`
+    ExcludedVersion,
+    /// A more recent patch release of the same minor version exists, and
+    /// `--include-all-patch-releases` was not given
+    SupersededByPatchRelease,
+}
+
+impl fmt::Display for Exclusion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BelowMinimumVersion => write!(f, "below the minimum version"),
+            Self::AboveMaximumVersion => write!(f, "above the maximum version"),
+            Self::ExcludedVersion => write!(f, "excluded"),
+            Self::SupersededByPatchRelease => {
+                write!(f, "superseded by a more recent patch release")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::event::Message;
+    use crate::reporter::TestReporter;
+    use storyteller::Reporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporter::default();
+        let event = ReleaseList::new(vec![ListedRelease::new(
+            semver::Version::new(1, 56, 0),
+            Date::new(2021, 10, 21),
+            None,
+        )]);
+
+        reporter.reporter().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::new(Message::ReleaseList(event)),]
+        );
+    }
+
+    #[test]
+    fn display() {
+        let list = ReleaseList::new(vec![
+            ListedRelease::new(
+                semver::Version::new(1, 56, 1),
+                Date::new(2021, 10, 21),
+                None,
+            ),
+            ListedRelease::new(
+                semver::Version::new(1, 56, 0),
+                Date::new(2021, 10, 21),
+                Some(Exclusion::SupersededByPatchRelease),
+            ),
+            ListedRelease::new(
+                semver::Version::new(1, 55, 0),
+                Date::new(2021, 9, 9),
+                Some(Exclusion::BelowMinimumVersion),
+            ),
+        ]);
+
+        assert_eq!(
+            list.to_string(),
+            "1.56.1 (2021-10-21): included
+1.56.0 (2021-10-21): excluded, superseded by a more recent patch release
+1.55.0 (2021-09-09): excluded, below the minimum version
+1 of 3 releases included
+"
+        );
+    }
+}
@@ -0,0 +1,183 @@
+use std::fmt;
+
+use crate::reporter::event::Message;
+use crate::toolchain::OwnedToolchainSpec;
+use crate::{semver, Event};
+
+/// The differences between the results of two cargo-msrv runs.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ReportDiff {
+    old_msrv: Option<semver::Version>,
+    new_msrv: Option<semver::Version>,
+    /// Toolchains which were compatible in the old run, but incompatible in the new run
+    newly_failing: Vec<OwnedToolchainSpec>,
+    /// Toolchains which were incompatible in the old run, but compatible in the new run
+    newly_passing: Vec<OwnedToolchainSpec>,
+    changed_configuration: Vec<ConfigurationChange>,
+}
+
+impl ReportDiff {
+    pub fn new(
+        old_msrv: Option<semver::Version>,
+        new_msrv: Option<semver::Version>,
+        newly_failing: Vec<OwnedToolchainSpec>,
+        newly_passing: Vec<OwnedToolchainSpec>,
+        changed_configuration: Vec<ConfigurationChange>,
+    ) -> Self {
+        Self {
+            old_msrv,
+            new_msrv,
+            newly_failing,
+            newly_passing,
+            changed_configuration,
+        }
+    }
+
+    pub fn has_changes(&self) -> bool {
+        self.old_msrv != self.new_msrv
+            || !self.newly_failing.is_empty()
+            || !self.newly_passing.is_empty()
+            || !self.changed_configuration.is_empty()
+    }
+}
+
+impl fmt::Display for ReportDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn version(version: Option<&semver::Version>) -> String {
+            version
+                .map(|version| format!("Rust {}", version))
+                .unwrap_or_else(|| "N/A".to_string())
+        }
+
+        if self.old_msrv == self.new_msrv {
+            writeln!(f, "MSRV: {} (unchanged)", version(self.new_msrv.as_ref()))?;
+        } else {
+            writeln!(
+                f,
+                "MSRV: {} -> {}",
+                version(self.old_msrv.as_ref()),
+                version(self.new_msrv.as_ref())
+            )?;
+        }
+
+        if !self.newly_failing.is_empty() {
+            writeln!(f, "Newly failing toolchains:")?;
+
+            for toolchain in &self.newly_failing {
+                writeln!(f, "  - {}", toolchain)?;
+            }
+        }
+
+        if !self.newly_passing.is_empty() {
+            writeln!(f, "Newly passing toolchains:")?;
+
+            for toolchain in &self.newly_passing {
+                writeln!(f, "  - {}", toolchain)?;
+            }
+        }
+
+        if !self.changed_configuration.is_empty() {
+            writeln!(f, "Changed configuration:")?;
+
+            for change in &self.changed_configuration {
+                writeln!(f, "  - {}", change)?;
+            }
+        }
+
+        if !self.has_changes() {
+            writeln!(f, "No changes")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl From<ReportDiff> for Event {
+    fn from(it: ReportDiff) -> Self {
+        Message::ReportDiff(it).into()
+    }
+}
+
+/// A configuration property which differs between two runs. A value is absent when the property
+/// was not recorded by that run.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ConfigurationChange {
+    property: String,
+    old: Option<String>,
+    new: Option<String>,
+}
+
+impl ConfigurationChange {
+    pub fn new(property: String, old: Option<String>, new: Option<String>) -> Self {
+        Self { property, old, new }
+    }
+}
+
+impl fmt::Display for ConfigurationChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} -> {}",
+            self.property,
+            self.old.as_deref().unwrap_or("(absent)"),
+            self.new.as_deref().unwrap_or("(absent)")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::event::Message;
+    use crate::reporter::TestReporter;
+    use storyteller::Reporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporter::default();
+        let event = ReportDiff::new(
+            Some(semver::Version::new(1, 56, 0)),
+            Some(semver::Version::new(1, 58, 0)),
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+        );
+
+        reporter.reporter().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::new(Message::ReportDiff(event)),]
+        );
+    }
+
+    #[test]
+    fn display() {
+        let event = ReportDiff::new(
+            Some(semver::Version::new(1, 56, 0)),
+            Some(semver::Version::new(1, 58, 0)),
+            vec![OwnedToolchainSpec::new(
+                &semver::Version::new(1, 57, 0),
+                "x86_64-unknown-linux-gnu",
+            )],
+            Vec::new(),
+            vec![ConfigurationChange::new(
+                "fingerprint.check_command".to_string(),
+                Some("cargo check".to_string()),
+                None,
+            )],
+        );
+
+        assert_eq!(
+            event.to_string(),
+            "MSRV: Rust 1.56.0 -> Rust 1.58.0
+Newly failing toolchains:
+  - 1.57.0-x86_64-unknown-linux-gnu
+Changed configuration:
+  - fingerprint.check_command: cargo check -> (absent)
+"
+        );
+    }
+}
@@ -0,0 +1,63 @@
+use crate::reporter::event::Message;
+use crate::semver;
+use crate::toolchain::OwnedToolchainSpec;
+use crate::Event;
+
+/// Reported when cargo refused to build the crate with a toolchain, because the crate, or one of
+/// its dependencies, requires a more recent Rust version. Toolchains less recent than the required
+/// Rust version are rejected from then on, without being checked.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct RequiredRustVersion {
+    /// The toolchain which cargo refused
+    toolchain: OwnedToolchainSpec,
+    /// The Rust version which cargo requires
+    rust_version: semver::Version,
+}
+
+impl RequiredRustVersion {
+    pub fn new(toolchain: impl Into<OwnedToolchainSpec>, rust_version: semver::Version) -> Self {
+        Self {
+            toolchain: toolchain.into(),
+            rust_version,
+        }
+    }
+
+    pub fn toolchain(&self) -> &OwnedToolchainSpec {
+        &self.toolchain
+    }
+
+    pub fn rust_version(&self) -> &semver::Version {
+        &self.rust_version
+    }
+}
+
+impl From<RequiredRustVersion> for Event {
+    fn from(it: RequiredRustVersion) -> Self {
+        Message::RequiredRustVersion(it).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::event::Message;
+    use crate::reporter::TestReporter;
+    use storyteller::Reporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporter::default();
+        let event = RequiredRustVersion::new(
+            OwnedToolchainSpec::new(&semver::Version::new(1, 56, 0), "test_target"),
+            semver::Version::new(1, 60, 0),
+        );
+
+        reporter.reporter().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::new(Message::RequiredRustVersion(event)),]
+        );
+    }
+}
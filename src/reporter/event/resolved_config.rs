@@ -0,0 +1,257 @@
+use crate::config::value_source::ValueSource;
+use crate::reporter::event::Message;
+use crate::Event;
+use std::fmt;
+use std::path::PathBuf;
+
+/// The resolved configuration, as shown by `cargo msrv config show`.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ResolvedConfig {
+    options: Vec<ConfigOption>,
+    /// The project config files which were read, from the workspace root to the crate, each
+    /// overriding the files before it
+    config_files: Vec<PathBuf>,
+}
+
+impl ResolvedConfig {
+    pub fn new(options: Vec<ConfigOption>, config_files: Vec<PathBuf>) -> Self {
+        Self {
+            options,
+            config_files,
+        }
+    }
+
+    pub fn options(&self) -> &[ConfigOption] {
+        &self.options
+    }
+
+    pub fn config_files(&self) -> &[PathBuf] {
+        &self.config_files
+    }
+}
+
+impl From<ResolvedConfig> for Event {
+    fn from(it: ResolvedConfig) -> Self {
+        Message::ResolvedConfig(it).into()
+    }
+}
+
+/// Printed as TOML, with the source of each value as a trailing comment, below the project config
+/// files which were read.
+impl fmt::Display for ResolvedConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.config_files.is_empty() {
+            writeln!(f, "# config files, from the workspace root to the crate:")?;
+
+            for path in &self.config_files {
+                writeln!(f, "#   {}", path.display())?;
+            }
+        }
+
+        for option in &self.options {
+            match option.value.to_toml() {
+                Some(value) => writeln!(f, "{} = {}  # {}", option.key, value, option.source)?,
+                None => writeln!(f, "# {} is not set  # {}", option.key, option.source)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ConfigOption {
+    key: String,
+    value: ConfigValue,
+    source: ValueSource,
+}
+
+impl ConfigOption {
+    pub fn new(key: impl Into<String>, value: impl Into<ConfigValue>, source: ValueSource) -> Self {
+        Self {
+            key: key.into(),
+            value: value.into(),
+            source,
+        }
+    }
+
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    pub fn value(&self) -> &ConfigValue {
+        &self.value
+    }
+
+    pub fn source(&self) -> &ValueSource {
+        &self.source
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(untagged)]
+pub enum ConfigValue {
+    Unset,
+    Bool(bool),
+    Integer(u64),
+    String(String),
+    List(Vec<String>),
+}
+
+impl ConfigValue {
+    fn to_toml(&self) -> Option<toml_edit::Value> {
+        match self {
+            Self::Unset => None,
+            Self::Bool(value) => Some((*value).into()),
+            Self::Integer(value) => Some((*value as i64).into()),
+            Self::String(value) => Some(value.as_str().into()),
+            Self::List(values) => Some(values.iter().map(String::as_str).collect()),
+        }
+    }
+}
+
+impl From<bool> for ConfigValue {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<u64> for ConfigValue {
+    fn from(value: u64) -> Self {
+        Self::Integer(value)
+    }
+}
+
+impl From<String> for ConfigValue {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<&str> for ConfigValue {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_string())
+    }
+}
+
+impl From<Vec<String>> for ConfigValue {
+    fn from(values: Vec<String>) -> Self {
+        Self::List(values)
+    }
+}
+
+impl<T: Into<ConfigValue>> From<Option<T>> for ConfigValue {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Self::Unset, Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::event::Message;
+    use crate::reporter::TestReporter;
+    use storyteller::Reporter;
+
+    fn resolved_config() -> ResolvedConfig {
+        let options = vec![
+            ConfigOption::new("target", "x86_64-unknown-linux-gnu", ValueSource::Default),
+            ConfigOption::new(
+                "cargo_flags",
+                vec!["locked".to_string(), "offline".to_string()],
+                ValueSource::CommandLine {
+                    flag: "--cargo-flag".to_string(),
+                },
+            ),
+            ConfigOption::new(
+                "minimum_version",
+                "1.56",
+                ValueSource::Manifest {
+                    path: PathBuf::from("Cargo.toml"),
+                    key: "package.edition".to_string(),
+                },
+            ),
+            ConfigOption::new("maximum_version", None::<String>, ValueSource::Default),
+            ConfigOption::new(
+                "write_msrv",
+                true,
+                ValueSource::CommandLine {
+                    flag: "--write-msrv".to_string(),
+                },
+            ),
+            ConfigOption::new("log_max_size", 10_u64, ValueSource::Default),
+        ];
+
+        ResolvedConfig::new(options, vec![])
+    }
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporter::default();
+        let event = resolved_config();
+
+        reporter.reporter().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::new(Message::ResolvedConfig(event))]
+        );
+    }
+
+    #[test]
+    fn display_as_toml() {
+        let expected = r#"target = "x86_64-unknown-linux-gnu"  # default
+cargo_flags = ["locked", "offline"]  # command line (--cargo-flag)
+minimum_version = "1.56"  # manifest (package.edition in Cargo.toml)
+# maximum_version is not set  # default
+write_msrv = true  # command line (--write-msrv)
+log_max_size = 10  # default
+"#;
+
+        assert_eq!(resolved_config().to_string(), expected);
+    }
+
+    #[test]
+    fn display_config_files() {
+        let config = ResolvedConfig::new(
+            vec![ConfigOption::new(
+                "minimum_version",
+                "1.60",
+                ValueSource::ConfigFile {
+                    path: PathBuf::from("member/.cargo-msrv.toml"),
+                    key: "minimum_version".to_string(),
+                },
+            )],
+            vec![
+                PathBuf::from(".cargo-msrv.toml"),
+                PathBuf::from("member/.cargo-msrv.toml"),
+            ],
+        );
+
+        let expected = r#"# config files, from the workspace root to the crate:
+#   .cargo-msrv.toml
+#   member/.cargo-msrv.toml
+minimum_version = "1.60"  # config file (minimum_version in member/.cargo-msrv.toml)
+"#;
+
+        assert_eq!(config.to_string(), expected);
+    }
+
+    #[test]
+    fn serialize_as_json() {
+        let option = ConfigOption::new(
+            "locale",
+            "de",
+            ValueSource::Environment {
+                variable: "CARGO_MSRV_LOCALE".to_string(),
+            },
+        );
+
+        assert_eq!(
+            serde_json::to_string(&option).unwrap(),
+            r#"{"key":"locale","value":"de","source":{"type":"environment","variable":"CARGO_MSRV_LOCALE"}}"#
+        );
+    }
+}
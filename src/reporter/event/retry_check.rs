@@ -0,0 +1,83 @@
+use crate::reporter::event::Message;
+use crate::toolchain::OwnedToolchainSpec;
+use crate::Event;
+
+/// Reported when the check of a toolchain failed, and is retried with a clean target directory,
+/// as configured with `--retries-on-failure`. A toolchain for which a retry passes has a flaky
+/// check, e.g. because of a network dependent build script.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct RetryCheck {
+    toolchain: OwnedToolchainSpec,
+    /// The retry which is about to be run, starting at 1
+    attempt: u32,
+    /// The maximum amount of retries
+    retries: u32,
+    /// The output of the failed check
+    error_message: String,
+}
+
+impl RetryCheck {
+    pub fn new(
+        toolchain: impl Into<OwnedToolchainSpec>,
+        attempt: u32,
+        retries: u32,
+        error_message: String,
+    ) -> Self {
+        Self {
+            toolchain: toolchain.into(),
+            attempt,
+            retries,
+            error_message,
+        }
+    }
+
+    pub fn toolchain(&self) -> &OwnedToolchainSpec {
+        &self.toolchain
+    }
+
+    pub fn attempt(&self) -> u32 {
+        self.attempt
+    }
+
+    pub fn retries(&self) -> u32 {
+        self.retries
+    }
+
+    pub fn error_message(&self) -> &str {
+        &self.error_message
+    }
+}
+
+impl From<RetryCheck> for Event {
+    fn from(it: RetryCheck) -> Self {
+        Message::RetryCheck(it).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::event::Message;
+    use crate::reporter::TestReporter;
+    use crate::semver;
+    use storyteller::Reporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporter::default();
+        let event = RetryCheck::new(
+            OwnedToolchainSpec::new(&semver::Version::new(1, 2, 3), "test_target"),
+            1,
+            2,
+            "error: failed to run custom build command".to_string(),
+        );
+
+        reporter.reporter().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::new(Message::RetryCheck(event)),]
+        );
+    }
+}
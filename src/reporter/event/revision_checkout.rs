@@ -0,0 +1,71 @@
+use crate::reporter::event::Message;
+use crate::Event;
+use std::path::{Path, PathBuf};
+
+/// Reported when the crate is checked at the revision given with `--at-rev`, which was checked
+/// out in a temporary git worktree.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct RevisionCheckout {
+    revision: String,
+    /// The commit to which the revision resolved
+    commit: String,
+    worktree: PathBuf,
+}
+
+impl RevisionCheckout {
+    pub fn new(
+        revision: impl Into<String>,
+        commit: impl Into<String>,
+        worktree: impl Into<PathBuf>,
+    ) -> Self {
+        Self {
+            revision: revision.into(),
+            commit: commit.into(),
+            worktree: worktree.into(),
+        }
+    }
+
+    pub fn revision(&self) -> &str {
+        &self.revision
+    }
+
+    pub fn commit(&self) -> &str {
+        &self.commit
+    }
+
+    pub fn worktree(&self) -> &Path {
+        &self.worktree
+    }
+}
+
+impl From<RevisionCheckout> for Event {
+    fn from(it: RevisionCheckout) -> Self {
+        Message::RevisionCheckout(it).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::event::Message;
+    use crate::reporter::TestReporter;
+    use storyteller::Reporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporter::default();
+        let event = RevisionCheckout::new(
+            "v1.2.0",
+            "3678f10c4b1a9d0f0e5f6a7b8c9d0e1f2a3b4c5d",
+            "/tmp/cargo-msrv-worktree",
+        );
+
+        reporter.reporter().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::new(Message::RevisionCheckout(event))]
+        );
+    }
+}
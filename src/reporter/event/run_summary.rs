@@ -0,0 +1,161 @@
+use crate::reporter::event::compatibility::serialize_secs;
+use crate::reporter::event::Message;
+use crate::Event;
+use std::time::Duration;
+
+/// Reported once the run is over, whether it succeeded or not. Summarizes the toolchains which
+/// were considered, the time spent in each phase of the run, and what it downloaded and stored.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct RunSummary {
+    toolchains: ToolchainCounts,
+    cache: CacheCounts,
+    durations: Durations,
+    /// The bytes of the release index which were downloaded by cargo-msrv itself. Toolchains are
+    /// downloaded by rustup, which doesn't report how many bytes it downloaded.
+    downloaded_bytes: u64,
+    /// The disk space used by the toolchains which were installed during the run
+    disk_used_bytes: u64,
+}
+
+impl RunSummary {
+    pub fn new(
+        toolchains: ToolchainCounts,
+        cache: CacheCounts,
+        durations: Durations,
+        downloaded_bytes: u64,
+        disk_used_bytes: u64,
+    ) -> Self {
+        Self {
+            toolchains,
+            cache,
+            durations,
+            downloaded_bytes,
+            disk_used_bytes,
+        }
+    }
+
+    pub fn toolchains(&self) -> &ToolchainCounts {
+        &self.toolchains
+    }
+
+    pub fn cache(&self) -> &CacheCounts {
+        &self.cache
+    }
+
+    pub fn durations(&self) -> &Durations {
+        &self.durations
+    }
+
+    pub fn downloaded_bytes(&self) -> u64 {
+        self.downloaded_bytes
+    }
+
+    pub fn disk_used_bytes(&self) -> u64 {
+        self.disk_used_bytes
+    }
+}
+
+impl From<RunSummary> for Event {
+    fn from(it: RunSummary) -> Self {
+        Message::RunSummary(it).into()
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ToolchainCounts {
+    /// The toolchains in the search space, and those checked outside of a search, e.g. by `verify`
+    pub considered: u64,
+    pub checked: u64,
+    /// The toolchains which were considered, but never checked, e.g. because bisection ruled
+    /// them out
+    pub skipped: u64,
+    pub installed: u64,
+}
+
+/// The crates which were taken from the cache of the compiler wrapper, as configured with
+/// `--rustc-wrapper`, and those which were compiled.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct CacheCounts {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct Durations {
+    #[serde(serialize_with = "serialize_secs")]
+    #[schemars(with = "f64")]
+    pub total: Duration,
+    #[serde(serialize_with = "serialize_secs")]
+    #[schemars(with = "f64")]
+    pub fetch_index: Duration,
+    #[serde(serialize_with = "serialize_secs")]
+    #[schemars(with = "f64")]
+    pub setup_toolchains: Duration,
+    #[serde(serialize_with = "serialize_secs")]
+    #[schemars(with = "f64")]
+    pub search: Duration,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::event::Message;
+    use crate::reporter::TestReporter;
+    use storyteller::Reporter;
+
+    fn summary() -> RunSummary {
+        RunSummary::new(
+            ToolchainCounts {
+                considered: 12,
+                checked: 4,
+                skipped: 8,
+                installed: 2,
+            },
+            CacheCounts {
+                hits: 30,
+                misses: 5,
+            },
+            Durations {
+                total: Duration::from_secs(40),
+                fetch_index: Duration::from_millis(500),
+                setup_toolchains: Duration::from_secs(20),
+                search: Duration::from_secs(19),
+            },
+            2048,
+            1 << 30,
+        )
+    }
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporter::default();
+        let event = summary();
+
+        reporter.reporter().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::new(Message::RunSummary(event))]
+        );
+    }
+
+    #[test]
+    fn serialize() {
+        let json = serde_json::to_value(summary()).unwrap();
+
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "toolchains": { "considered": 12, "checked": 4, "skipped": 8, "installed": 2 },
+                "cache": { "hits": 30, "misses": 5 },
+                "durations": { "total": 40.0, "fetch_index": 0.5, "setup_toolchains": 20.0, "search": 19.0 },
+                "downloaded_bytes": 2048,
+                "disk_used_bytes": 1073741824u64,
+            })
+        );
+    }
+}
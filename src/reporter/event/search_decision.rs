@@ -0,0 +1,142 @@
+use crate::config::SearchMethod;
+use crate::reporter::event::Message;
+use crate::semver;
+use crate::Event;
+use rust_releases::Release;
+use std::fmt;
+
+/// Reported for each Rust version which is checked by a search method, when enabled with
+/// `--explain-search`. Describes the range of releases which remained to be searched, the
+/// release which was tested, and the range which remains after the outcome of the check.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct SearchDecision {
+    search_method: SearchMethod,
+    /// The releases which remained to be searched, before the check
+    range: SearchRange,
+    tested: semver::Version,
+    /// Whether the check of the tested release passed
+    compatible: bool,
+    /// The releases which remain to be searched after the check, or `None` if the search is done
+    remaining: Option<SearchRange>,
+}
+
+impl SearchDecision {
+    pub fn new(
+        search_method: SearchMethod,
+        range: SearchRange,
+        tested: semver::Version,
+        compatible: bool,
+        remaining: Option<SearchRange>,
+    ) -> Self {
+        Self {
+            search_method,
+            range,
+            tested,
+            compatible,
+            remaining,
+        }
+    }
+
+    pub fn search_method(&self) -> SearchMethod {
+        self.search_method
+    }
+
+    pub fn range(&self) -> &SearchRange {
+        &self.range
+    }
+
+    pub fn tested(&self) -> &semver::Version {
+        &self.tested
+    }
+
+    pub fn compatible(&self) -> bool {
+        self.compatible
+    }
+
+    pub fn remaining(&self) -> Option<&SearchRange> {
+        self.remaining.as_ref()
+    }
+}
+
+impl From<SearchDecision> for Event {
+    fn from(it: SearchDecision) -> Self {
+        Message::SearchDecision(it).into()
+    }
+}
+
+/// An inclusive range of releases, displayed from least to most recent, e.g. `[1.50.0..1.70.0]`.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct SearchRange {
+    least_recent: semver::Version,
+    most_recent: semver::Version,
+}
+
+impl SearchRange {
+    /// The range spanned by the given releases, which must be ordered from most to least recent.
+    ///
+    /// Panics if no releases are given.
+    pub fn of(releases: &[Release]) -> Self {
+        Self {
+            least_recent: releases[releases.len() - 1].version().clone(),
+            most_recent: releases[0].version().clone(),
+        }
+    }
+
+    pub fn least_recent(&self) -> &semver::Version {
+        &self.least_recent
+    }
+
+    pub fn most_recent(&self) -> &semver::Version {
+        &self.most_recent
+    }
+}
+
+impl fmt::Display for SearchRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}..{}]", self.least_recent, self.most_recent)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::event::Message;
+    use crate::reporter::TestReporter;
+    use storyteller::Reporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporter::default();
+        let releases = (50..=70)
+            .rev()
+            .map(|minor| Release::new_stable(semver::Version::new(1, minor, 0)))
+            .collect::<Vec<_>>();
+
+        let event = SearchDecision::new(
+            SearchMethod::Bisect,
+            SearchRange::of(&releases),
+            semver::Version::new(1, 60, 0),
+            false,
+            Some(SearchRange::of(&releases[..10])),
+        );
+
+        reporter.reporter().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::new(Message::SearchDecision(event)),]
+        );
+    }
+
+    #[test]
+    fn display_range() {
+        let releases = [
+            Release::new_stable(semver::Version::new(1, 70, 0)),
+            Release::new_stable(semver::Version::new(1, 61, 0)),
+        ];
+
+        assert_eq!(SearchRange::of(&releases).to_string(), "[1.61.0..1.70.0]");
+    }
+}
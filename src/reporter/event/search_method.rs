@@ -2,18 +2,30 @@ use crate::config::SearchMethod as Method;
 use crate::reporter::event::Message;
 use crate::Event;
 
-#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct FindMsrv {
     search_method: Method,
+    /// The number of releases in the search space
+    search_space: usize,
 }
 
 impl FindMsrv {
     pub(crate) fn new(method: Method) -> Self {
         Self {
             search_method: method,
+            search_space: 0,
         }
     }
+
+    pub(crate) fn with_search_space(mut self, search_space: usize) -> Self {
+        self.search_space = search_space;
+        self
+    }
+
+    pub fn search_space(&self) -> usize {
+        self.search_space
+    }
 }
 
 impl From<FindMsrv> for Event {
@@ -32,6 +44,7 @@ mod tests {
     #[yare::parameterized(
         linear = { Method::Linear },
         bisect = { Method::Bisect },
+        exhaustive = { Method::Exhaustive },
     )]
     fn reported_event(method: Method) {
         let reporter = TestReporter::default();
@@ -0,0 +1,62 @@
+use crate::reporter::event::Message;
+use crate::semver;
+use crate::toolchain::OwnedToolchainSpec;
+use crate::Event;
+
+/// Reported when a toolchain is not available for the target, e.g. because the target is more
+/// recent than the toolchain. The toolchain, and all less recent toolchains, are removed from the
+/// search space, instead of being considered incompatible.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct SearchSpaceClamped {
+    unavailable: OwnedToolchainSpec,
+    // The least recent release which remains in the search space
+    minimum: semver::Version,
+}
+
+impl SearchSpaceClamped {
+    pub fn new(unavailable: OwnedToolchainSpec, minimum: semver::Version) -> Self {
+        Self {
+            unavailable,
+            minimum,
+        }
+    }
+
+    pub fn unavailable(&self) -> &OwnedToolchainSpec {
+        &self.unavailable
+    }
+
+    pub fn minimum(&self) -> &semver::Version {
+        &self.minimum
+    }
+}
+
+impl From<SearchSpaceClamped> for Event {
+    fn from(it: SearchSpaceClamped) -> Self {
+        Message::SearchSpaceClamped(it).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::event::Message;
+    use crate::reporter::TestReporter;
+    use storyteller::Reporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporter::default();
+        let event = SearchSpaceClamped::new(
+            OwnedToolchainSpec::new(&semver::Version::new(1, 48, 0), "aarch64-apple-darwin"),
+            semver::Version::new(1, 49, 0),
+        );
+
+        reporter.reporter().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::new(Message::SearchSpaceClamped(event)),]
+        );
+    }
+}
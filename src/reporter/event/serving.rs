@@ -0,0 +1,56 @@
+use crate::reporter::event::Message;
+use crate::Event;
+
+/// Reported by `cargo msrv serve`, once it listens for the requests of other cargo-msrv
+/// invocations.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct Serving {
+    address: String,
+    /// The number of releases of the release index which is served
+    releases: usize,
+}
+
+impl Serving {
+    pub fn new(address: impl Into<String>, releases: usize) -> Self {
+        Self {
+            address: address.into(),
+            releases,
+        }
+    }
+
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    pub fn releases(&self) -> usize {
+        self.releases
+    }
+}
+
+impl From<Serving> for Event {
+    fn from(it: Serving) -> Self {
+        Message::Serving(it).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::event::Message;
+    use crate::reporter::TestReporter;
+    use storyteller::Reporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporter::default();
+        let event = Serving::new("127.0.0.1:7430", 72);
+
+        reporter.reporter().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::new(Message::Serving(event)),]
+        );
+    }
+}
@@ -3,7 +3,7 @@ use crate::reporter::event::Message;
 use crate::Event;
 use std::path::{Path, PathBuf};
 
-#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct SetOutputMessage {
     version: BareVersion,
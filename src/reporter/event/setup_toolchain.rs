@@ -2,18 +2,34 @@ use crate::reporter::event::Message;
 use crate::toolchain::OwnedToolchainSpec;
 use crate::Event;
 
-#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct SetupToolchain {
     toolchain: OwnedToolchainSpec,
+    /// Whether the toolchain was already installed, in which case installing it was skipped
+    already_installed: bool,
 }
 
 impl SetupToolchain {
     pub fn new(toolchain: impl Into<OwnedToolchainSpec>) -> Self {
         Self {
             toolchain: toolchain.into(),
+            already_installed: false,
         }
     }
+
+    pub fn with_already_installed(mut self, already_installed: bool) -> Self {
+        self.already_installed = already_installed;
+        self
+    }
+
+    pub fn toolchain(&self) -> &OwnedToolchainSpec {
+        &self.toolchain
+    }
+
+    pub fn already_installed(&self) -> bool {
+        self.already_installed
+    }
 }
 
 impl From<SetupToolchain> for Event {
@@ -45,4 +61,17 @@ mod tests {
             vec![Event::new(Message::SetupToolchain(event)),]
         );
     }
+
+    #[test]
+    fn serialized_already_installed() {
+        let event = SetupToolchain::new(OwnedToolchainSpec::new(
+            &semver::Version::new(1, 2, 3),
+            "test_target",
+        ))
+        .with_already_installed(true);
+
+        let json = serde_json::to_value(&event).unwrap();
+
+        assert_eq!(json["already_installed"], serde_json::Value::Bool(true));
+    }
 }
@@ -0,0 +1,93 @@
+use crate::reporter::event::Message;
+use crate::Event;
+use std::path::{Path, PathBuf};
+
+/// Reported by `verify --changed-since` for each workspace member which is not verified.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct SkippedMember {
+    name: String,
+    path: PathBuf,
+    reason: SkipReason,
+}
+
+impl SkippedMember {
+    pub fn new(name: impl Into<String>, path: PathBuf, reason: SkipReason) -> Self {
+        Self {
+            name: name.into(),
+            path,
+            reason,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn reason(&self) -> &SkipReason {
+        &self.reason
+    }
+}
+
+impl From<SkippedMember> for Event {
+    fn from(it: SkippedMember) -> Self {
+        Message::SkippedMember(it).into()
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SkipReason {
+    /// None of the changes since the given git revision affect the member
+    Unchanged { since: String },
+    /// The member does not declare an MSRV, so there is nothing to verify
+    NoMsrv,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::event::Message;
+    use crate::reporter::TestReporter;
+    use storyteller::Reporter;
+
+    #[yare::parameterized(
+        unchanged = { SkipReason::Unchanged { since: "origin/main".to_string() } },
+        no_msrv = { SkipReason::NoMsrv },
+    )]
+    fn reported_event(reason: SkipReason) {
+        let reporter = TestReporter::default();
+        let event = SkippedMember::new("lemon", PathBuf::from("crates/lemon"), reason);
+
+        reporter.reporter().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::new(Message::SkippedMember(event))]
+        );
+    }
+
+    #[test]
+    fn serialize_reason() {
+        let event = SkippedMember::new(
+            "lemon",
+            PathBuf::from("crates/lemon"),
+            SkipReason::Unchanged {
+                since: "origin/main".to_string(),
+            },
+        );
+
+        assert_eq!(
+            serde_json::to_value(&event).unwrap(),
+            serde_json::json!({
+                "name": "lemon",
+                "path": "crates/lemon",
+                "reason": { "unchanged": { "since": "origin/main" } },
+            })
+        );
+    }
+}
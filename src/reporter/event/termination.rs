@@ -1,29 +1,46 @@
 use crate::reporter::event::Message;
 use crate::{CargoMSRVError, Event};
+use std::path::{Path, PathBuf};
 
 /// Represents a serializable reason why the program should terminate with a failure (a non-zero
 /// exit code).
-#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct TerminateWithFailure {
     // Not all failure terminations are errors, for example, if we fail to verify we want to exit
     // with a non-zero exit code i.e. 'Terminate with failure',
     is_error: bool,
     reason: SerializableReason,
+    // The log file which may contain more details about the failure, if logs are written to a file
+    #[serde(skip_serializing_if = "Option::is_none")]
+    log_file: Option<PathBuf>,
 }
 
 impl TerminateWithFailure {
     pub fn new(error: CargoMSRVError) -> Self {
-        let is_error = matches!(error, CargoMSRVError::UnableToFindAnyGoodVersion { .. });
+        let is_error = matches!(
+            error,
+            CargoMSRVError::UnableToFindAnyGoodVersion { .. }
+                | CargoMSRVError::UnableToFindRegression { .. }
+        );
 
         Self {
             is_error,
             reason: SerializableReason {
+                kind: error.kind(),
                 description: format!("{}", &error),
+                details: error.details(),
             },
+            log_file: None,
         }
     }
 
+    /// Point to the log file, which may contain more details about the failure.
+    pub fn with_log_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.log_file = Some(path.into());
+        self
+    }
+
     pub fn is_error(&self) -> bool {
         self.is_error
     }
@@ -31,6 +48,15 @@ impl TerminateWithFailure {
     pub fn as_message(&self) -> &str {
         &self.reason.description
     }
+
+    /// The kind of error which caused the failure, e.g. `fetch_release_index`.
+    pub fn kind(&self) -> &str {
+        self.reason.kind
+    }
+
+    pub fn log_file(&self) -> Option<&Path> {
+        self.log_file.as_deref()
+    }
 }
 
 impl From<TerminateWithFailure> for Event {
@@ -39,10 +65,15 @@ impl From<TerminateWithFailure> for Event {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
 #[serde(rename_all = "snake_case")]
 struct SerializableReason {
+    /// Discriminates the kind of error, see [`CargoMSRVError::kind`]
+    kind: &'static str,
     description: String,
+    /// The values of the error, which differ per kind
+    #[serde(skip_serializing_if = "serde_json::Map::is_empty")]
+    details: serde_json::Map<String, serde_json::Value>,
 }
 
 #[cfg(test)]
@@ -89,4 +120,44 @@ mod tests {
                 .starts_with("Unable to find a Minimum Supported Rust Version (MSRV)"));
         }
     }
+
+    #[test]
+    fn serializes_log_file() {
+        let event = TerminateWithFailure::new(CargoMSRVError::Storyteller)
+            .with_log_file("/tmp/cargo-msrv/cargo-msrv.log");
+
+        let value = serde_json::to_value(&event).unwrap();
+
+        assert_eq!(value["log_file"], "/tmp/cargo-msrv/cargo-msrv.log");
+    }
+
+    #[test]
+    fn serializes_error_kind_and_details() {
+        let event = TerminateWithFailure::new(CargoMSRVError::FetchReleaseIndex {
+            url: "https://example.com/index".to_string(),
+            message: "unexpected status code 503".to_string(),
+        });
+
+        let value = serde_json::to_value(&event).unwrap();
+
+        assert_eq!(value["reason"]["kind"], "fetch_release_index");
+        assert_eq!(
+            value["reason"]["details"],
+            serde_json::json!({
+                "url": "https://example.com/index",
+                "message": "unexpected status code 503",
+            })
+        );
+    }
+
+    #[test]
+    fn serializes_error_without_details() {
+        let event = TerminateWithFailure::new(CargoMSRVError::Cancelled);
+
+        let value = serde_json::to_value(&event).unwrap();
+
+        assert_eq!(event.kind(), "cancelled");
+        assert_eq!(value["reason"]["kind"], "cancelled");
+        assert!(value["reason"].get("details").is_none());
+    }
 }
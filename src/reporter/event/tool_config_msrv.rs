@@ -0,0 +1,97 @@
+use crate::manifest::bare_version::BareVersion;
+use crate::reporter::event::Message;
+use crate::Event;
+use std::path::{Path, PathBuf};
+
+/// Reported by `cargo msrv show`, for each configuration file of a tool, like `clippy.toml`, which
+/// specifies an MSRV of its own. The MSRV of the tool should agree with the MSRV of the Cargo
+/// manifest.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ToolConfigMsrv {
+    tool: String,
+    path: PathBuf,
+    version: BareVersion,
+    manifest_version: BareVersion,
+    /// Whether the MSRV of the tool was overwritten with the MSRV of the manifest, by
+    /// `--sync-clippy`
+    synced: bool,
+}
+
+impl ToolConfigMsrv {
+    pub fn new(
+        tool: impl Into<String>,
+        path: impl Into<PathBuf>,
+        version: BareVersion,
+        manifest_version: BareVersion,
+        synced: bool,
+    ) -> Self {
+        Self {
+            tool: tool.into(),
+            path: path.into(),
+            version,
+            manifest_version,
+            synced,
+        }
+    }
+
+    pub fn tool(&self) -> &str {
+        &self.tool
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn version(&self) -> &BareVersion {
+        &self.version
+    }
+
+    pub fn manifest_version(&self) -> &BareVersion {
+        &self.manifest_version
+    }
+
+    pub fn synced(&self) -> bool {
+        self.synced
+    }
+
+    /// Whether the MSRV of the tool is the same release as the MSRV of the manifest, e.g. `1.56`
+    /// and `1.56.0` are.
+    pub fn is_consistent(&self) -> bool {
+        self.version.pin_patch() == self.manifest_version.pin_patch()
+    }
+}
+
+impl From<ToolConfigMsrv> for Event {
+    fn from(it: ToolConfigMsrv) -> Self {
+        Message::ToolConfigMsrv(it).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::event::Message;
+    use crate::reporter::TestReporter;
+    use storyteller::Reporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporter::default();
+        let event = ToolConfigMsrv::new(
+            "clippy",
+            "clippy.toml",
+            BareVersion::TwoComponents(1, 56),
+            BareVersion::ThreeComponents(1, 56, 0),
+            false,
+        );
+
+        reporter.reporter().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::new(Message::ToolConfigMsrv(event.clone()))]
+        );
+        assert!(event.is_consistent());
+    }
+}
@@ -0,0 +1,96 @@
+use crate::reporter::event::Message;
+use crate::toolchain::OwnedToolchainSpec;
+use crate::Event;
+
+/// Reported for each check of a toolchain with `--reuse-unit-graph`, before the check is run.
+/// Tells whether the outcome of an earlier check of the toolchain is reused, since the unit graph
+/// of the check, as reported by cargo, and the sources of the local packages are unchanged.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct UnitGraphReuse {
+    toolchain: OwnedToolchainSpec,
+    decision: ReuseDecision,
+    /// Why the unit graph could not be determined, if it could not
+    reason: Option<String>,
+}
+
+impl UnitGraphReuse {
+    pub fn reused(toolchain: impl Into<OwnedToolchainSpec>) -> Self {
+        Self::new(toolchain, ReuseDecision::Reused, None)
+    }
+
+    pub fn checked(toolchain: impl Into<OwnedToolchainSpec>) -> Self {
+        Self::new(toolchain, ReuseDecision::Checked, None)
+    }
+
+    pub fn unavailable(toolchain: impl Into<OwnedToolchainSpec>, reason: String) -> Self {
+        Self::new(toolchain, ReuseDecision::Unavailable, Some(reason))
+    }
+
+    fn new(
+        toolchain: impl Into<OwnedToolchainSpec>,
+        decision: ReuseDecision,
+        reason: Option<String>,
+    ) -> Self {
+        Self {
+            toolchain: toolchain.into(),
+            decision,
+            reason,
+        }
+    }
+
+    pub fn toolchain(&self) -> &OwnedToolchainSpec {
+        &self.toolchain
+    }
+
+    pub fn decision(&self) -> ReuseDecision {
+        self.decision
+    }
+
+    pub fn reason(&self) -> Option<&str> {
+        self.reason.as_deref()
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReuseDecision {
+    /// The outcome of an earlier check with the same unit graph is reused
+    Reused,
+    /// The unit graph was not seen before, so the toolchain is checked
+    Checked,
+    /// The unit graph could not be determined, e.g. because the cargo of the toolchain doesn't
+    /// support `--unit-graph`, so the toolchain is checked
+    Unavailable,
+}
+
+impl From<UnitGraphReuse> for Event {
+    fn from(it: UnitGraphReuse) -> Self {
+        Message::UnitGraphReuse(it).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::event::Message;
+    use crate::reporter::TestReporter;
+    use crate::semver;
+    use storyteller::Reporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporter::default();
+        let event = UnitGraphReuse::reused(OwnedToolchainSpec::new(
+            &semver::Version::new(1, 2, 3),
+            "test_target",
+        ));
+
+        reporter.reporter().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::new(Message::UnitGraphReuse(event)),]
+        );
+    }
+}
@@ -0,0 +1,74 @@
+use crate::config::VersionResolution;
+use crate::manifest::bare_version::BareVersion;
+use crate::reporter::event::Message;
+use crate::semver;
+use crate::Event;
+
+/// Reported when the MSRV matched none of the releases of the index, and was resolved to another
+/// release with `--version-resolution`.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct VersionResolved {
+    /// The MSRV, which matched none of the releases
+    requested: BareVersion,
+    /// The release to which the MSRV was resolved
+    resolved: semver::Version,
+    resolution: VersionResolution,
+}
+
+impl VersionResolved {
+    pub fn new(
+        requested: BareVersion,
+        resolved: semver::Version,
+        resolution: VersionResolution,
+    ) -> Self {
+        Self {
+            requested,
+            resolved,
+            resolution,
+        }
+    }
+
+    pub fn requested(&self) -> &BareVersion {
+        &self.requested
+    }
+
+    pub fn resolved(&self) -> &semver::Version {
+        &self.resolved
+    }
+
+    pub fn resolution(&self) -> VersionResolution {
+        self.resolution
+    }
+}
+
+impl From<VersionResolved> for Event {
+    fn from(it: VersionResolved) -> Self {
+        Message::VersionResolved(it).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::event::Message;
+    use crate::reporter::TestReporter;
+    use storyteller::Reporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporter::default();
+        let event = VersionResolved::new(
+            BareVersion::ThreeComponents(1, 54, 0),
+            semver::Version::new(1, 54, 1),
+            VersionResolution::ClosestPatch,
+        );
+
+        reporter.reporter().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::new(Message::VersionResolved(event)),]
+        );
+    }
+}
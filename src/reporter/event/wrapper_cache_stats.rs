@@ -0,0 +1,80 @@
+use crate::reporter::event::Message;
+use crate::toolchain::OwnedToolchainSpec;
+use crate::Event;
+
+/// Reported once a toolchain has been checked with a compiler wrapper, as configured with
+/// `--rustc-wrapper`, which keeps cache statistics, like `sccache`. Counts the crates which were
+/// taken from the cache of the wrapper, and those which were compiled, during the check.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct WrapperCacheStats {
+    toolchain: OwnedToolchainSpec,
+    wrapper: String,
+    hits: u64,
+    misses: u64,
+}
+
+impl WrapperCacheStats {
+    pub fn new(
+        toolchain: impl Into<OwnedToolchainSpec>,
+        wrapper: impl Into<String>,
+        hits: u64,
+        misses: u64,
+    ) -> Self {
+        Self {
+            toolchain: toolchain.into(),
+            wrapper: wrapper.into(),
+            hits,
+            misses,
+        }
+    }
+
+    pub fn toolchain(&self) -> &OwnedToolchainSpec {
+        &self.toolchain
+    }
+
+    pub fn wrapper(&self) -> &str {
+        &self.wrapper
+    }
+
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+}
+
+impl From<WrapperCacheStats> for Event {
+    fn from(it: WrapperCacheStats) -> Self {
+        Message::WrapperCacheStats(it).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::event::Message;
+    use crate::reporter::TestReporter;
+    use crate::semver;
+    use storyteller::Reporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporter::default();
+        let event = WrapperCacheStats::new(
+            OwnedToolchainSpec::new(&semver::Version::new(1, 2, 3), "test_target"),
+            "sccache",
+            12,
+            3,
+        );
+
+        reporter.reporter().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::new(Message::WrapperCacheStats(event)),]
+        );
+    }
+}
@@ -7,16 +7,28 @@ use std::sync::{Arc, Mutex, RwLock};
 use std::{io, marker};
 use storyteller::{EventHandler, Reporter};
 
+mod catalog;
+mod ci_handler;
 mod discard_output_handler;
+mod error_summary;
+mod github_check_handler;
 mod human_progress_handler;
 mod json_handler;
+mod otel_handler;
+mod schema_validation_handler;
+mod webhook_handler;
 
 #[cfg(test)]
 mod testing;
 
+pub use ci_handler::CiHandler;
 pub use discard_output_handler::DiscardOutputHandler;
+pub use github_check_handler::GitHubCheckHandler;
 pub use human_progress_handler::HumanProgressHandler;
 pub use json_handler::JsonHandler;
+pub use otel_handler::OtelHandler;
+pub use schema_validation_handler::{InvalidEvents, SchemaValidationHandler};
+pub use webhook_handler::WebhookHandler;
 
 #[cfg(test)]
 pub use testing::TestingHandler;
@@ -0,0 +1,592 @@
+//! The user-facing messages of the human output, for each supported [`Locale`].
+//!
+//! Messages may contain `{}` placeholders, which are filled in order by [`fill`].
+//!
+//! The catalog covers the progress, warnings and result of the search, and the messages of `set`,
+//! `show` and `serve`. The reports of the other subcommands, like `cargo msrv list` or
+//! `cargo msrv history`, are written by their `Display` implementations, which are in English
+//! regardless of the locale.
+
+use crate::config::Locale;
+use std::fmt::{Display, Write};
+
+/// The messages of the human output in a single language.
+pub struct Catalog {
+    // status leads
+    pub lead_meta: &'static str,
+    pub lead_ok: &'static str,
+    pub lead_warn: &'static str,
+    pub lead_fail: &'static str,
+    pub lead_set: &'static str,
+    pub lead_show: &'static str,
+
+    // progress
+    pub progress_elapsed: &'static str,
+    /// `{}` position of the unit, `{}` number of units, `{}` name of the unit
+    pub progress_unit: &'static str,
+    /// `{}` source, `{}` number of requests, `{}` number of releases
+    pub fetch_index_progress: &'static str,
+    /// `{}` source, `{}` number of requests of the interrupted fetch
+    pub fetch_index_resumed: &'static str,
+    /// `{}` revision, `{}` commit, `{}` path of the worktree
+    pub revision_checkout: &'static str,
+
+    // warnings
+    /// `{}` snapshot date, `{}` most recent release suffix
+    pub embedded_index_fallback: &'static str,
+    /// `{}` most recent release
+    pub embedded_index_most_recent: &'static str,
+    /// `{}` names of the binaries
+    pub excluded_binaries: &'static str,
+    /// `{}` unavailable version, `{}` target, `{}` least recent remaining version
+    pub search_space_clamped: &'static str,
+    /// `{}` requested version, `{}` resolved version, `{}` resolution
+    pub version_resolved: &'static str,
+    /// `{}` manifest key, `{}` first version which reads the manifest
+    pub manifest_syntax: &'static str,
+    /// `{}` version, `{}` lockfile format, `{}` first version which reads the format
+    pub lockfile_untestable: &'static str,
+    /// `{}` version, `{}` lockfile format, `{}` first version which reads the format
+    pub lockfile_regenerated: &'static str,
+    /// `{}` refused version, `{}` required version
+    pub required_rust_version: &'static str,
+    /// `{}` version, `{}` components, `{}` host
+    pub component_unavailable: &'static str,
+    /// `{}` log folder suffix, `{}` reason
+    pub log_file_unavailable: &'static str,
+    /// `{}` log folder
+    pub log_file_unavailable_folder: &'static str,
+
+    // failure
+    /// `{}` tool, `{}` install hint
+    pub prerequisite_not_found: &'static str,
+    /// `{}` tool, `{}` minimum version, `{}` found version, `{}` install hint
+    pub prerequisite_outdated: &'static str,
+    /// `{}` install command
+    pub prerequisite_install_command: &'static str,
+    pub prerequisite_setup_hint: &'static str,
+    /// `{}` tool, `{}` install command
+    pub prerequisite_install: &'static str,
+    /// `{}` log file
+    pub log_file_hint: &'static str,
+
+    // compatibility checks
+    /// `{}` sequence number, `{}` version
+    pub check_header: &'static str,
+    pub is_compatible: &'static str,
+    pub is_incompatible: &'static str,
+    /// `{}` number of left out errors
+    pub errors_omitted: &'static str,
+    /// `{}` version
+    pub same_failure_as: &'static str,
+    /// `{}` version, `{}` retry, `{}` maximum amount of retries
+    pub retry_check: &'static str,
+    /// `{}` number of invalid byte sequences
+    pub encoding_note: &'static str,
+    /// `{}` wrapper, `{}` version, `{}` cache hits, `{}` cache misses
+    pub wrapper_cache_stats: &'static str,
+    /// `{}` version
+    pub unit_graph_reused: &'static str,
+    /// `{}` version, `{}` reason
+    pub unit_graph_unavailable: &'static str,
+    /// `{}` remaining range, `{}` version, `{}` outcome, `{}` what remains after the check
+    pub search_decision: &'static str,
+    /// `{}` remaining range
+    pub search_decision_next: &'static str,
+    pub search_decision_done: &'static str,
+    /// `{}` number of releases, `{}` outcome of each release
+    pub exhaustive_search: &'static str,
+    /// `{}` versions
+    pub non_monotonic: &'static str,
+    /// `{}` maximum number of checks
+    pub inconclusive_result: &'static str,
+    /// `{}` version
+    pub inconclusive_at_most: &'static str,
+    /// `{}` version
+    pub inconclusive_more_recent_than: &'static str,
+    /// `{}` member, `{}` path of the member, `{}` git revision
+    pub skipped_member_unchanged: &'static str,
+    /// `{}` member, `{}` path of the member
+    pub skipped_member_no_msrv: &'static str,
+
+    // set and show
+    /// `{}` version
+    pub set_output: &'static str,
+    /// `{}` version
+    pub show_output: &'static str,
+    /// `{}` path of the log
+    pub failure_log: &'static str,
+    /// `{}` number of releases, `{}` address
+    pub serving: &'static str,
+    /// `{}` tool, `{}` version, `{}` path of the configuration file
+    pub tool_config_msrv: &'static str,
+    /// `{}` tool, `{}` version, `{}` path of the configuration file, `{}` version of the manifest
+    pub tool_config_msrv_mismatch: &'static str,
+    /// `{}` tool, `{}` path of the configuration file, `{}` version of the manifest
+    pub tool_config_msrv_synced: &'static str,
+    /// `{}` key, `{}` value, `{}` path of the preferences file
+    pub preference_set: &'static str,
+
+    // result
+    pub result_header: &'static str,
+    /// `{}` label of the minimum, `{}` label of the maximum
+    pub result_considered: &'static str,
+    pub result_min: &'static str,
+    pub result_max: &'static str,
+    pub result_excluded: &'static str,
+    pub result_known_good: &'static str,
+    pub result_known_bad: &'static str,
+    pub result_search_method: &'static str,
+    pub result_msrv: &'static str,
+    pub result_first_failing: &'static str,
+    pub result_target: &'static str,
+    pub not_available: &'static str,
+
+    // check durations
+    pub durations_header: &'static str,
+    pub durations_wall_clock: &'static str,
+    pub durations_cargo_reported: &'static str,
+    pub compatible: &'static str,
+    pub incompatible: &'static str,
+
+    // phase durations
+    pub phases_header: &'static str,
+    pub phase_fetch_index: &'static str,
+    /// `{}` number of installed toolchains
+    pub phase_setup_toolchains: &'static str,
+    pub phase_search: &'static str,
+    /// `{}` time to fetch the dependencies, `{}` number of offline checks
+    pub dependency_cache: &'static str,
+    /// `{}` declared MSRV, `{}` semver change, `{}` current crate version, `{}` suggested crate version
+    pub version_bump: &'static str,
+    /// `{}` newer patch release, `{}` MSRV
+    pub newer_patch_release: &'static str,
+
+    // run summary
+    /// `{}` toolchains considered, `{}` checked, `{}` skipped, `{}` installed, `{}` cache hits,
+    /// `{}` bytes downloaded, `{}` disk used, `{}` total duration, `{}` fetch index, `{}` setup
+    /// toolchains, `{}` search
+    pub run_summary: &'static str,
+}
+
+const ENGLISH: Catalog = Catalog {
+    lead_meta: "Meta",
+    lead_ok: "OK",
+    lead_warn: "Warn",
+    lead_fail: "FAIL",
+    lead_set: "Set",
+    lead_show: "Show",
+
+    progress_elapsed: "Elapsed",
+    progress_unit: "Unit {} of {}: {}",
+    fetch_index_progress: "Fetching {} index: {} requests, {} releases",
+    fetch_index_resumed: "Resuming the interrupted fetch of the {} index, after {} requests",
+    revision_checkout: "Checking revision {} (commit {}), checked out in a temporary worktree at {}",
+
+    embedded_index_fallback: "Unable to fetch the Rust releases index, using the index embedded in cargo-msrv instead (snapshot of {}{}). The embedded index may be stale.",
+    embedded_index_most_recent: ", most recent release: Rust {}",
+    excluded_binaries: "Excluded binaries which require features from the check: {}. The MSRV does not cover these binaries.",
+    search_space_clamped: "Rust {} is not available for target '{}', skipping it and less recent releases. Continuing from Rust {}.",
+    version_resolved: "The MSRV {} matches none of the Rust releases, using Rust {} instead ({})",
+    manifest_syntax: "The Cargo manifest uses '{}', which cargo can only read since Rust {}. Less recent releases are skipped, since they can't parse the manifest.",
+    lockfile_untestable: "Rust {} can't read the lockfile, which uses format {} (readable since Rust {}). Rejecting it as untestable.",
+    lockfile_regenerated: "Rust {} can't read the lockfile, which uses format {} (readable since Rust {}). Checking it with a regenerated lockfile, without --locked.",
+    required_rust_version: "Cargo of Rust {} refused to build the crate, since it requires Rust {}. Less recent releases are rejected without checking them.",
+    component_unavailable: "Rust {} didn't ship {} for {}. Rejecting it without checking it.",
+    log_file_unavailable: "Unable to write to the log file{}: {}. Logs are not written to a file for this run.",
+    log_file_unavailable_folder: " in '{}'",
+
+    prerequisite_not_found: "{} is required, but it could not be found. Install it (see {}), and make sure it is on the PATH.",
+    prerequisite_outdated: "{} {} or newer is required, but version {} was found. Update it (see {}).",
+    prerequisite_install_command: "It can be installed with: {}",
+    prerequisite_setup_hint: "Run cargo-msrv with --setup to install it automatically.",
+    prerequisite_install: "Installing {} with: {}",
+    log_file_hint: "More details may be found in the log file at '{}'.",
+
+    check_header: "Compatibility Check #{}: Rust {}",
+    is_compatible: "Is compatible",
+    is_incompatible: "Is Incompatible",
+    errors_omitted: "… and {} more errors",
+    same_failure_as: "Same failure as Rust {}",
+    retry_check: "Check with Rust {} failed, retrying with a clean target directory (retry {} of {})",
+    encoding_note: "The output of the check is not valid UTF-8, {} invalid byte sequences were replaced by �. The raw output is written to the log file",
+    wrapper_cache_stats: "{} cache with Rust {}: {} hits, {} misses",
+    unit_graph_reused: "Reusing the outcome of an earlier check with Rust {}, since its unit graph and sources are unchanged",
+    unit_graph_unavailable: "Unable to determine the unit graph of the check with Rust {}, so its outcome can't be reused: {}",
+    search_decision: "Range {}, tested Rust {}: {} → {}",
+    search_decision_next: "new range {}",
+    search_decision_done: "search done",
+    exhaustive_search: "Checked {} releases: {}",
+    non_monotonic: "Compatibility is not monotonic: the outcome of Rust {} contradicts the outcome of a more recent release. A bisection would not detect this.",
+    inconclusive_result: "The budget of {} checks is exhausted, before the MSRV was found.",
+    inconclusive_at_most: " The MSRV is at most Rust {}.",
+    inconclusive_more_recent_than: " The MSRV is more recent than Rust {}.",
+    skipped_member_unchanged: "Skipped {} ({}): not affected by the changes since {}",
+    skipped_member_no_msrv: "Skipped {} ({}): no MSRV declared",
+
+    set_output: "Rust {}",
+    show_output: "MSRV is Rust {}",
+    failure_log: "The most recent failed check is logged at '{}'",
+    serving: "Serving the index of {} Rust releases, and installing toolchains, on {}, until interrupted",
+    tool_config_msrv: "The {} MSRV (Rust {}, in {}) agrees with the Cargo manifest",
+    tool_config_msrv_mismatch: "The {} MSRV is Rust {} (in {}), but the Cargo manifest declares Rust {}. Use --sync-clippy to make them agree.",
+    tool_config_msrv_synced: "Set the {} MSRV in {} to Rust {}",
+    preference_set: "{} = {} (saved to {})",
+
+    result_header: "Result:",
+    result_considered: "Considered ({} … {}):",
+    result_min: "min",
+    result_max: "max",
+    result_excluded: "Excluded:",
+    result_known_good: "Known good:",
+    result_known_bad: "Known bad:",
+    result_search_method: "Search method:",
+    result_msrv: "MSRV:",
+    result_first_failing: "First failing:",
+    result_target: "Target:",
+    not_available: "N/A",
+
+    durations_header: "Check durations:",
+    durations_wall_clock: "wall-clock",
+    durations_cargo_reported: "cargo reported",
+    compatible: "compatible",
+    incompatible: "incompatible",
+
+    phases_header: "Phase durations:",
+    phase_fetch_index: "Fetch release index",
+    phase_setup_toolchains: "Install toolchains ({})",
+    phase_search: "Search",
+    dependency_cache: "Fetched the dependencies once in {}, and ran {} checks offline",
+    version_bump: "The MSRV is raised from Rust {}, which is a semver-{} change. Suggested crate version: {} → {}",
+    newer_patch_release: "Rust {} is a more recent patch release of the MSRV {}, and may include security fixes. Use --prefer-newest-patch to write it instead",
+
+    run_summary: "{} toolchains considered, {} checked, {} skipped · {} installed · {} cache hits · {} downloaded · {} on disk · {} total (index {}, setup {}, search {})",
+};
+
+const GERMAN: Catalog = Catalog {
+    lead_meta: "Meta",
+    lead_ok: "OK",
+    lead_warn: "Warnung",
+    lead_fail: "FEHLER",
+    lead_set: "Gesetzt",
+    lead_show: "Anzeige",
+
+    progress_elapsed: "Vergangen",
+    progress_unit: "Einheit {} von {}: {}",
+    fetch_index_progress: "{}-Index wird abgerufen: {} Anfragen, {} Releases",
+    fetch_index_resumed: "Der unterbrochene Abruf des {}-Index wird nach {} Anfragen fortgesetzt",
+    revision_checkout: "Revision {} (Commit {}) wird geprüft, ausgecheckt in einem temporären Worktree unter {}",
+
+    embedded_index_fallback: "Der Rust-Release-Index konnte nicht abgerufen werden, stattdessen wird der in cargo-msrv eingebettete Index verwendet (Stand {}{}). Der eingebettete Index ist möglicherweise veraltet.",
+    embedded_index_most_recent: ", neuestes Release: Rust {}",
+    excluded_binaries: "Binaries, die Features voraussetzen, wurden von der Prüfung ausgeschlossen: {}. Die MSRV gilt nicht für diese Binaries.",
+    search_space_clamped: "Rust {} ist für das Ziel '{}' nicht verfügbar, es und ältere Releases werden übersprungen. Weiter ab Rust {}.",
+    version_resolved: "Die MSRV {} entspricht keinem Rust-Release, stattdessen wird Rust {} verwendet ({})",
+    manifest_syntax: "Das Cargo-Manifest verwendet '{}', das Cargo erst ab Rust {} lesen kann. Ältere Releases werden übersprungen, da sie das Manifest nicht parsen können.",
+    lockfile_untestable: "Rust {} kann die Lockfile nicht lesen, die das Format {} verwendet (lesbar ab Rust {}). Es wird als nicht prüfbar abgelehnt.",
+    lockfile_regenerated: "Rust {} kann die Lockfile nicht lesen, die das Format {} verwendet (lesbar ab Rust {}). Es wird mit einer neu erzeugten Lockfile geprüft, ohne --locked.",
+    required_rust_version: "Cargo von Rust {} hat den Build des Crates verweigert, das Rust {} voraussetzt. Ältere Releases werden ohne Prüfung abgelehnt.",
+    component_unavailable: "Rust {} wurde ohne {} für {} ausgeliefert. Es wird ohne Prüfung abgelehnt.",
+    log_file_unavailable: "Die Logdatei{} kann nicht geschrieben werden: {}. Für diesen Lauf werden keine Logs in eine Datei geschrieben.",
+    log_file_unavailable_folder: " in '{}'",
+
+    prerequisite_not_found: "{} wird benötigt, wurde aber nicht gefunden. Installiere es (siehe {}) und stelle sicher, dass es im PATH liegt.",
+    prerequisite_outdated: "{} {} oder neuer wird benötigt, gefunden wurde aber Version {}. Aktualisiere es (siehe {}).",
+    prerequisite_install_command: "Es kann installiert werden mit: {}",
+    prerequisite_setup_hint: "Führe cargo-msrv mit --setup aus, um es automatisch zu installieren.",
+    prerequisite_install: "Installiere {} mit: {}",
+    log_file_hint: "Weitere Details stehen möglicherweise in der Logdatei unter '{}'.",
+
+    check_header: "Kompatibilitätsprüfung #{}: Rust {}",
+    is_compatible: "Ist kompatibel",
+    is_incompatible: "Ist nicht kompatibel",
+    errors_omitted: "… und {} weitere Fehler",
+    same_failure_as: "Gleicher Fehler wie bei Rust {}",
+    retry_check: "Prüfung mit Rust {} fehlgeschlagen, neuer Versuch mit leerem Target-Verzeichnis (Versuch {} von {})",
+    encoding_note: "Die Ausgabe der Prüfung ist kein gültiges UTF-8, {} ungültige Bytefolgen wurden durch � ersetzt. Die Rohausgabe wird in die Logdatei geschrieben",
+    wrapper_cache_stats: "{}-Cache mit Rust {}: {} Treffer, {} Fehlschläge",
+    unit_graph_reused: "Ergebnis einer früheren Prüfung mit Rust {} wird wiederverwendet, da Unit-Graph und Quellen unverändert sind",
+    unit_graph_unavailable: "Unit-Graph der Prüfung mit Rust {} nicht ermittelbar, das Ergebnis kann nicht wiederverwendet werden: {}",
+    search_decision: "Bereich {}, Rust {} geprüft: {} → {}",
+    search_decision_next: "neuer Bereich {}",
+    search_decision_done: "Suche abgeschlossen",
+    exhaustive_search: "{} Versionen geprüft: {}",
+    non_monotonic: "Die Kompatibilität ist nicht monoton: das Ergebnis von Rust {} widerspricht dem Ergebnis einer neueren Version. Eine Bisektion würde dies nicht erkennen.",
+    inconclusive_result: "Das Budget von {} Prüfungen ist erschöpft, bevor die MSRV gefunden wurde.",
+    inconclusive_at_most: " Die MSRV ist höchstens Rust {}.",
+    inconclusive_more_recent_than: " Die MSRV ist neuer als Rust {}.",
+    skipped_member_unchanged: "{} ({}) übersprungen: nicht betroffen von den Änderungen seit {}",
+    skipped_member_no_msrv: "{} ({}) übersprungen: keine MSRV angegeben",
+
+    set_output: "Rust {}",
+    show_output: "MSRV ist Rust {}",
+    failure_log: "Die zuletzt fehlgeschlagene Prüfung ist protokolliert in '{}'",
+    serving: "Der Index von {} Rust-Releases wird bereitgestellt, und Toolchains werden installiert, auf {}, bis zur Unterbrechung",
+    tool_config_msrv: "Die {}-MSRV (Rust {}, in {}) stimmt mit dem Cargo-Manifest überein",
+    tool_config_msrv_mismatch: "Die {}-MSRV ist Rust {} (in {}), aber das Cargo-Manifest deklariert Rust {}. Verwende --sync-clippy, um sie anzugleichen.",
+    tool_config_msrv_synced: "Die {}-MSRV in {} wurde auf Rust {} gesetzt",
+    preference_set: "{} = {} (gespeichert in {})",
+
+    result_header: "Ergebnis:",
+    result_considered: "Berücksichtigt ({} … {}):",
+    result_min: "min",
+    result_max: "max",
+    result_excluded: "Ausgeschlossen:",
+    result_known_good: "Bekannt kompatibel:",
+    result_known_bad: "Bekannt inkompatibel:",
+    result_search_method: "Suchmethode:",
+    result_msrv: "MSRV:",
+    result_first_failing: "Erste fehlschlagende:",
+    result_target: "Ziel:",
+    not_available: "k. A.",
+
+    durations_header: "Prüfdauer:",
+    durations_wall_clock: "Gesamtzeit",
+    durations_cargo_reported: "laut cargo",
+    compatible: "kompatibel",
+    incompatible: "nicht kompatibel",
+
+    phases_header: "Dauer der Phasen:",
+    phase_fetch_index: "Release-Index abrufen",
+    phase_setup_toolchains: "Toolchains installieren ({})",
+    phase_search: "Suche",
+    dependency_cache: "Abhängigkeiten einmalig in {} abgerufen und {} Prüfungen offline ausgeführt",
+    version_bump: "Die MSRV wird von Rust {} angehoben, das ist eine semver-{}-Änderung. Vorgeschlagene Crate-Version: {} → {}",
+    newer_patch_release: "Rust {} ist eine neuere Patch-Version der MSRV {} und enthält möglicherweise Sicherheitskorrekturen. Mit --prefer-newest-patch wird sie stattdessen geschrieben",
+
+    run_summary: "{} Toolchains berücksichtigt, {} geprüft, {} übersprungen · {} installiert · {} Cache-Treffer · {} heruntergeladen · {} belegt · {} gesamt (Index {}, Installation {}, Suche {})",
+};
+
+impl Locale {
+    /// The messages of the human output in this language.
+    pub fn catalog(&self) -> &'static Catalog {
+        match self {
+            Self::English => &ENGLISH,
+            Self::German => &GERMAN,
+        }
+    }
+}
+
+/// Fill the `{}` placeholders of a message with the given arguments, in order.
+pub fn fill(message: &str, args: &[&dyn Display]) -> String {
+    let mut args = args.iter();
+    let mut filled = String::with_capacity(message.len());
+
+    for (i, part) in message.split("{}").enumerate() {
+        if i > 0 {
+            if let Some(arg) = args.next() {
+                let _ = write!(filled, "{}", arg);
+            }
+        }
+
+        filled.push_str(part);
+    }
+
+    filled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// All messages of a catalog. Destructured, so a message can't be added without also adding it
+    /// here.
+    fn messages(catalog: &Catalog) -> Vec<&'static str> {
+        let Catalog {
+            lead_meta,
+            lead_ok,
+            lead_warn,
+            lead_fail,
+            lead_set,
+            lead_show,
+            progress_elapsed,
+            progress_unit,
+            fetch_index_progress,
+            fetch_index_resumed,
+            revision_checkout,
+            embedded_index_fallback,
+            embedded_index_most_recent,
+            excluded_binaries,
+            search_space_clamped,
+            version_resolved,
+            manifest_syntax,
+            lockfile_untestable,
+            lockfile_regenerated,
+            required_rust_version,
+            component_unavailable,
+            log_file_unavailable,
+            log_file_unavailable_folder,
+            prerequisite_not_found,
+            prerequisite_outdated,
+            prerequisite_install_command,
+            prerequisite_setup_hint,
+            prerequisite_install,
+            log_file_hint,
+            check_header,
+            is_compatible,
+            is_incompatible,
+            errors_omitted,
+            same_failure_as,
+            retry_check,
+            encoding_note,
+            wrapper_cache_stats,
+            unit_graph_reused,
+            unit_graph_unavailable,
+            search_decision,
+            search_decision_next,
+            search_decision_done,
+            exhaustive_search,
+            non_monotonic,
+            inconclusive_result,
+            inconclusive_at_most,
+            inconclusive_more_recent_than,
+            skipped_member_unchanged,
+            skipped_member_no_msrv,
+            set_output,
+            show_output,
+            failure_log,
+            serving,
+            tool_config_msrv,
+            tool_config_msrv_mismatch,
+            tool_config_msrv_synced,
+            preference_set,
+            result_header,
+            result_considered,
+            result_min,
+            result_max,
+            result_excluded,
+            result_known_good,
+            result_known_bad,
+            result_search_method,
+            result_msrv,
+            result_first_failing,
+            result_target,
+            not_available,
+            durations_header,
+            durations_wall_clock,
+            durations_cargo_reported,
+            compatible,
+            incompatible,
+            phases_header,
+            phase_fetch_index,
+            phase_setup_toolchains,
+            phase_search,
+            dependency_cache,
+            version_bump,
+            newer_patch_release,
+            run_summary,
+        } = *catalog;
+
+        vec![
+            lead_meta,
+            lead_ok,
+            lead_warn,
+            lead_fail,
+            lead_set,
+            lead_show,
+            progress_elapsed,
+            progress_unit,
+            fetch_index_progress,
+            fetch_index_resumed,
+            revision_checkout,
+            embedded_index_fallback,
+            embedded_index_most_recent,
+            excluded_binaries,
+            search_space_clamped,
+            version_resolved,
+            manifest_syntax,
+            lockfile_untestable,
+            lockfile_regenerated,
+            required_rust_version,
+            component_unavailable,
+            log_file_unavailable,
+            log_file_unavailable_folder,
+            prerequisite_not_found,
+            prerequisite_outdated,
+            prerequisite_install_command,
+            prerequisite_setup_hint,
+            prerequisite_install,
+            log_file_hint,
+            check_header,
+            is_compatible,
+            is_incompatible,
+            errors_omitted,
+            same_failure_as,
+            retry_check,
+            encoding_note,
+            wrapper_cache_stats,
+            unit_graph_reused,
+            unit_graph_unavailable,
+            search_decision,
+            search_decision_next,
+            search_decision_done,
+            exhaustive_search,
+            non_monotonic,
+            inconclusive_result,
+            inconclusive_at_most,
+            inconclusive_more_recent_than,
+            skipped_member_unchanged,
+            skipped_member_no_msrv,
+            set_output,
+            show_output,
+            failure_log,
+            serving,
+            tool_config_msrv,
+            tool_config_msrv_mismatch,
+            tool_config_msrv_synced,
+            preference_set,
+            result_header,
+            result_considered,
+            result_min,
+            result_max,
+            result_excluded,
+            result_known_good,
+            result_known_bad,
+            result_search_method,
+            result_msrv,
+            result_first_failing,
+            result_target,
+            not_available,
+            durations_header,
+            durations_wall_clock,
+            durations_cargo_reported,
+            compatible,
+            incompatible,
+            phases_header,
+            phase_fetch_index,
+            phase_setup_toolchains,
+            phase_search,
+            dependency_cache,
+            version_bump,
+            newer_patch_release,
+            run_summary,
+        ]
+    }
+
+    #[yare::parameterized(
+        german = { Locale::German },
+    )]
+    fn same_placeholders_as_english(locale: Locale) {
+        let placeholders = |catalog| {
+            messages(catalog)
+                .into_iter()
+                .map(|message| message.matches("{}").count())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            placeholders(locale.catalog()),
+            placeholders(Locale::English.catalog())
+        );
+    }
+
+    #[yare::parameterized(
+        no_placeholders = { "Is compatible", &[], "Is compatible" },
+        in_order = { "Check #{}: Rust {}", &[&1, &"1.56.0"], "Check #1: Rust 1.56.0" },
+        missing_argument = { "Rust {}", &[], "Rust " },
+        placeholder_at_start = { "{} is the MSRV", &[&"1.56.0"], "1.56.0 is the MSRV" },
+    )]
+    fn fill_placeholders(message: &str, args: &[&dyn Display], expected: &str) {
+        assert_eq!(fill(message, args), expected);
+    }
+}
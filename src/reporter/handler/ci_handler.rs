@@ -0,0 +1,673 @@
+use crate::config::{Locale, LockfilePolicy, SearchMethod};
+use crate::reporter::event::{
+    Compatibility, CompatibilityReport, Message, ReuseDecision, SkipReason,
+};
+use crate::reporter::handler::catalog::Catalog;
+use crate::reporter::handler::json_handler::SendWriter;
+use std::io;
+use std::io::{Stderr, Write};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+use storyteller::EventHandler;
+
+/// Plain text output, where the output of each toolchain check is folded into a collapsible
+/// log group, so long CI logs remain navigable.
+pub struct CiHandler<W: SendWriter> {
+    writer: Arc<Mutex<W>>,
+    flavor: CiFlavor,
+    catalog: &'static Catalog,
+    sequence_number: AtomicU32,
+}
+
+/// The syntax used to delimit log groups.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum CiFlavor {
+    /// `::group::` and `::endgroup::` workflow commands
+    GitHub,
+    /// `section_start` and `section_end` markers
+    GitLab,
+    /// Headers without folding, for CI providers which we don't know how to fold logs for
+    Plain,
+}
+
+impl CiFlavor {
+    /// Detect the CI provider from the environment variables set by the provider.
+    fn from_env() -> Self {
+        if std::env::var_os("GITHUB_ACTIONS").is_some() {
+            Self::GitHub
+        } else if std::env::var_os("GITLAB_CI").is_some() {
+            Self::GitLab
+        } else {
+            Self::Plain
+        }
+    }
+
+    fn start_group(&self, id: u32, header: &str) -> String {
+        match self {
+            Self::GitHub => format!("::group::{}", header),
+            Self::GitLab => format!(
+                "\x1b[0Ksection_start:{}:cargo_msrv_check_{}[collapsed=true]\r\x1b[0K{}",
+                unix_timestamp(),
+                id,
+                header
+            ),
+            Self::Plain => format!("==> {}", header),
+        }
+    }
+
+    fn end_group(&self, id: u32) -> Option<String> {
+        match self {
+            Self::GitHub => Some("::endgroup::".to_string()),
+            Self::GitLab => Some(format!(
+                "\x1b[0Ksection_end:{}:cargo_msrv_check_{}\r\x1b[0K",
+                unix_timestamp(),
+                id
+            )),
+            Self::Plain => None,
+        }
+    }
+}
+
+fn unix_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+impl<W: SendWriter> CiHandler<W> {
+    const LOCK_FAILURE_MSG: &'static str = "Unable to lock writer for CiHandler";
+    const WRITE_FAILURE_MSG: &'static str = "Unable to write output for CiHandler";
+
+    fn new(writer: W, flavor: CiFlavor, locale: Locale) -> Self {
+        Self {
+            writer: Arc::new(Mutex::new(writer)),
+            flavor,
+            catalog: locale.catalog(),
+            sequence_number: AtomicU32::new(0),
+        }
+    }
+
+    fn println(&self, message: impl AsRef<str>) {
+        let mut w = self.writer.lock().expect(Self::LOCK_FAILURE_MSG);
+        writeln!(&mut w, "{}", message.as_ref()).expect(Self::WRITE_FAILURE_MSG);
+    }
+}
+
+impl CiHandler<Stderr> {
+    pub fn stderr(locale: Locale) -> Self {
+        Self::new(io::stderr(), CiFlavor::from_env(), locale)
+    }
+}
+
+impl<W: SendWriter> EventHandler for CiHandler<W> {
+    type Event = super::Event;
+
+    fn handle(&self, event: Self::Event) {
+        match event.message() {
+            Message::Meta(it) => {
+                self.println(format!(
+                    "{} {} ({})",
+                    it.instance(),
+                    it.version(),
+                    it.sha_short()
+                ));
+            }
+            Message::FetchIndexProgress(it) => {
+                if it.is_first_resumed() {
+                    self.println(format!(
+                        "Resuming the interrupted fetch of the {} index, after {} requests",
+                        it.source(),
+                        it.resumed_after()
+                    ));
+                }
+
+                self.println(format!(
+                    "Fetching {} index: {} requests, {} releases",
+                    it.source(),
+                    it.requests(),
+                    it.releases()
+                ));
+            }
+            Message::EmbeddedIndexFallback(it) => {
+                self.println(format!(
+                    "warning: Unable to fetch the Rust releases index, using the index embedded in cargo-msrv instead (snapshot of {}). The embedded index may be stale.",
+                    it.snapshot_date()
+                ));
+            }
+            Message::ExcludedBinaries(it) => {
+                self.println(format!(
+                    "warning: Excluded binaries which require features from the check: {}. The MSRV does not cover these binaries.",
+                    it.names()
+                ));
+            }
+            Message::IncompatibleLockfile(it) => {
+                let decision = match it.decision() {
+                    LockfilePolicy::Untestable => "Rejecting it as untestable.",
+                    LockfilePolicy::Regenerate => {
+                        "Checking it with a regenerated lockfile, without --locked."
+                    }
+                };
+
+                self.println(format!(
+                    "warning: Rust {} can't read the lockfile, which uses format {} (readable since Rust {}). {}",
+                    it.toolchain().version(),
+                    it.lockfile_format(),
+                    it.readable_since(),
+                    decision
+                ));
+            }
+            Message::RetryCheck(it) => {
+                self.println(format!(
+                    "warning: Check with Rust {} failed, retrying with a clean target directory (retry {} of {})",
+                    it.toolchain().version(),
+                    it.attempt(),
+                    it.retries()
+                ));
+            }
+            Message::WrapperCacheStats(it) => {
+                self.println(format!(
+                    "{} cache with Rust {}: {} hits, {} misses",
+                    it.wrapper(),
+                    it.toolchain().version(),
+                    it.hits(),
+                    it.misses()
+                ));
+            }
+            Message::UnitGraphReuse(it) => match it.decision() {
+                ReuseDecision::Reused => self.println(format!(
+                    "Reusing the outcome of an earlier check with Rust {}, since its unit graph and sources are unchanged",
+                    it.toolchain().version()
+                )),
+                ReuseDecision::Unavailable => self.println(format!(
+                    "Unable to determine the unit graph of the check with Rust {}, so its outcome can't be reused: {}",
+                    it.toolchain().version(),
+                    it.reason().unwrap_or_default()
+                )),
+                ReuseDecision::Checked => {}
+            },
+            Message::RequiredRustVersion(it) => {
+                self.println(format!(
+                    "warning: Cargo of Rust {} refused to build the crate, since it requires Rust {}. Less recent releases are rejected without checking them.",
+                    it.toolchain().version(),
+                    it.rust_version()
+                ));
+            }
+            Message::ComponentUnavailable(it) => {
+                self.println(format!(
+                    "warning: Rust {} didn't ship {} for {}. Rejecting it without checking it.",
+                    it.toolchain().version(),
+                    it.components().join(", "),
+                    it.toolchain().host()
+                ));
+            }
+            Message::LogFileUnavailable(it) => {
+                let folder = it
+                    .folder()
+                    .map(|folder| format!(" in '{}'", folder.display()))
+                    .unwrap_or_default();
+
+                self.println(format!(
+                    "warning: Unable to write to the log file{}: {}. Logs are not written to a file for this run.",
+                    folder,
+                    it.reason()
+                ));
+            }
+            Message::RevisionCheckout(it) => {
+                self.println(format!(
+                    "Checking revision {} (commit {}), checked out in a temporary worktree at {}",
+                    it.revision(),
+                    it.commit(),
+                    it.worktree().display()
+                ));
+            }
+            Message::MissingPrerequisites(it) => {
+                for missing in it.missing() {
+                    match (missing.minimum_version(), missing.found_version()) {
+                        (Some(minimum), Some(found)) => self.println(format!(
+                            "error: {} {} or newer is required, but version {} was found. Update it (see {}).",
+                            missing.tool(),
+                            minimum,
+                            found,
+                            missing.install_hint()
+                        )),
+                        _ => self.println(format!(
+                            "error: {} is required, but it could not be found. Install it (see {}), and make sure it is on the PATH.",
+                            missing.tool(),
+                            missing.install_hint()
+                        )),
+                    }
+
+                    if let Some(command) = missing.install_command() {
+                        self.println(format!("note: It can be installed with: {}", command));
+                    }
+                    if missing.setup() {
+                        self.println("note: Run cargo-msrv with --setup to install it automatically.");
+                    }
+                }
+            }
+            Message::PrerequisiteInstall(it) => {
+                self.println(format!("Installing {} with: {}", it.tool(), it.command()));
+            }
+            Message::SearchSpaceClamped(it) => {
+                self.println(format!(
+                    "warning: Rust {} is not available for target '{}', skipping it and less recent releases. Continuing from Rust {}.",
+                    it.unavailable().version(),
+                    it.unavailable().target(),
+                    it.minimum()
+                ));
+            }
+            Message::VersionResolved(it) => {
+                self.println(format!(
+                    "warning: The MSRV {} matches none of the Rust releases, using Rust {} instead ({})",
+                    it.requested(),
+                    it.resolved(),
+                    it.resolution()
+                ));
+            }
+            Message::ManifestSyntax(it) => {
+                self.println(format!(
+                    "warning: The Cargo manifest uses {} at '{}', which cargo can only read since Rust {}. Less recent releases are skipped.",
+                    it.feature(),
+                    it.key(),
+                    it.readable_since()
+                ));
+            }
+            Message::CheckToolchain(it) if event.is_scope_start() => {
+                let id = self.sequence_number.fetch_add(1, Ordering::SeqCst) + 1;
+                let header = format!(
+                    "Compatibility Check #{}: Rust {} ({})",
+                    id,
+                    it.toolchain.version(),
+                    it.toolchain.target()
+                );
+                self.println(self.flavor.start_group(id, &header));
+            }
+            // within the group of the check, like the output of the check command itself
+            Message::CheckOutput(it) => self.println(it.line()),
+            Message::CheckToolchain(_) /* is scope end */ => {
+                let id = self.sequence_number.load(Ordering::SeqCst);
+                if let Some(end) = self.flavor.end_group(id) {
+                    self.println(end);
+                }
+            }
+            Message::Compatibility(Compatibility { compatibility_report: CompatibilityReport::Compatible, toolchain, .. }) => {
+                self.println(format!("Rust {} is compatible", toolchain.version()));
+            }
+            Message::Compatibility(it @ Compatibility { compatibility_report: CompatibilityReport::Incompatible { error }, toolchain, .. }) => {
+                self.println(format!("Rust {} is incompatible", toolchain.version()));
+
+                if let Some(note) = it.encoding_note() {
+                    self.println(format!(
+                        "The output of the check is not valid UTF-8, {} invalid byte sequences were replaced. The raw output is written to the log file",
+                        note.invalid_sequences()
+                    ));
+                }
+
+                if let Some(error_report) = error.as_deref() {
+                    self.println(error_report.trim_end());
+                }
+            }
+            Message::SearchDecision(it) => {
+                let tested = match it.search_method() {
+                    SearchMethod::Bisect | SearchMethod::Auto => "midpoint",
+                    SearchMethod::Linear | SearchMethod::Exhaustive => "release",
+                };
+                let outcome = if it.compatible() {
+                    "compatible"
+                } else {
+                    "incompatible"
+                };
+                let remaining = it
+                    .remaining()
+                    .map(|range| format!("new range {}", range))
+                    .unwrap_or_else(|| "done".to_string());
+
+                self.println(format!(
+                    "range {}, testing {} {}: {} → {}",
+                    it.range(),
+                    tested,
+                    it.tested(),
+                    outcome,
+                    remaining
+                ));
+            }
+            Message::ExhaustiveSearch(it) => {
+                let releases = it
+                    .releases()
+                    .iter()
+                    .map(|release| {
+                        let outcome = if release.compatible() {
+                            "compatible"
+                        } else {
+                            "incompatible"
+                        };
+
+                        format!("{} {}", release.version(), outcome)
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                self.println(format!("Checked each release: {}", releases));
+
+                if !it.non_monotonic().is_empty() {
+                    self.println(format!(
+                        "warning: Compatibility is not monotonic: the outcome of Rust {} contradicts the outcome of a more recent release. A bisection would not detect this.",
+                        it.non_monotonic_versions()
+                    ));
+                }
+            }
+            Message::MsrvResult(result) => {
+                let catalog = self.catalog;
+                let label = if result.polarity.is_inverted() {
+                    catalog.result_first_failing
+                } else {
+                    catalog.result_msrv
+                };
+
+                match result.msrv() {
+                    Some(version) => self.println(format!("{} Rust {}", label, version)),
+                    None => self.println(format!("{} {}", label, catalog.not_available)),
+                }
+
+                if !result.excluded_versions.is_empty() {
+                    let excluded = result
+                        .excluded_versions
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect::<Vec<_>>()
+                        .join(", ");
+
+                    self.println(format!("{} {}", catalog.result_excluded, excluded));
+                }
+
+                if let Some(seeds) = result.seeds() {
+                    if let Some(version) = &seeds.known_good {
+                        self.println(format!("{} Rust {}", catalog.result_known_good, version));
+                    }
+
+                    if let Some(version) = &seeds.known_bad {
+                        self.println(format!("{} Rust {}", catalog.result_known_bad, version));
+                    }
+                }
+
+                if let Some(cache) = result.dependency_cache() {
+                    self.println(format!(
+                        "Dependencies fetched once in {:.2}s, {} checks ran offline",
+                        cache.fetch().as_secs_f64(),
+                        cache.offline_checks()
+                    ));
+                }
+
+                if let Some(bump) = result.version_bump() {
+                    let kind: &str = bump.kind.into();
+
+                    self.println(format!(
+                        "Version bump: the MSRV is raised from Rust {}, a semver-{} change. Suggested crate version: {} -> {}",
+                        bump.declared_msrv, kind, bump.current_version, bump.suggested_version
+                    ));
+                }
+
+                if let (Some(newer), Some(msrv)) = (result.newer_patch_release(), result.msrv()) {
+                    self.println(format!(
+                        "Newer patch release: Rust {} is a more recent patch release of the MSRV {}",
+                        newer, msrv
+                    ));
+                }
+            }
+            Message::InconclusiveResult(it) => {
+                let mut message = format!(
+                    "warning: The budget of {} checks is exhausted, before the MSRV was found.",
+                    it.max_checks()
+                );
+
+                if let Some(version) = it.least_recent_accepted() {
+                    message.push_str(&format!(" The MSRV is at most Rust {}.", version));
+                }
+
+                if let Some(version) = it.most_recent_rejected() {
+                    message.push_str(&format!(" The MSRV is more recent than Rust {}.", version));
+                }
+
+                self.println(message);
+            }
+            Message::SkippedMember(it) => {
+                let reason = match it.reason() {
+                    SkipReason::Unchanged { since } => format!("not affected by the changes since {}", since),
+                    SkipReason::NoMsrv => "no MSRV declared".to_string(),
+                };
+
+                self.println(format!(
+                    "Skipped workspace member {} ({}): {}",
+                    it.name(),
+                    it.path().display(),
+                    reason
+                ));
+            }
+            Message::ListDep(list) => {
+                self.println(list.to_string());
+            }
+            Message::SetOutput(output) => {
+                self.println(format!("Set MSRV to Rust {}", output.version()));
+            }
+            Message::ShowOutput(output) => {
+                self.println(format!("MSRV is Rust {}", output.version()));
+            }
+            Message::ToolConfigMsrv(it) if it.synced() => {
+                self.println(format!(
+                    "Set the {} MSRV in {} to Rust {}",
+                    it.tool(),
+                    it.path().display(),
+                    it.manifest_version()
+                ));
+            }
+            Message::ToolConfigMsrv(it) => {
+                let agreement = if it.is_consistent() {
+                    "agrees with"
+                } else {
+                    "differs from"
+                };
+
+                self.println(format!(
+                    "{} MSRV is Rust {} (in {}), which {} the Cargo manifest (Rust {})",
+                    it.tool(),
+                    it.version(),
+                    it.path().display(),
+                    agreement,
+                    it.manifest_version()
+                ));
+            }
+            Message::ReportDiff(diff) => {
+                self.println(diff.to_string());
+            }
+            Message::ResolvedConfig(config) => {
+                self.println(config.to_string());
+            }
+            Message::PreferenceSet(it) => {
+                self.println(format!(
+                    "Set preference {} = {} (saved to {})",
+                    it.key(),
+                    it.value(),
+                    it.path().display()
+                ));
+            }
+            Message::EditionAdvice(advice) => {
+                self.println(advice.to_string());
+            }
+            Message::ReleaseList(list) => {
+                self.println(list.to_string());
+            }
+            Message::MsrvMatrix(matrix) => {
+                self.println(matrix.to_string());
+            }
+            Message::PolicyCheck(check) => {
+                self.println(check.to_string());
+            }
+            Message::DiscoveredProjects(projects) => {
+                self.println(projects.to_string());
+            }
+            Message::ManifestValidation(validation) => {
+                self.println(validation.to_string());
+            }
+            Message::DependencyImpact(impact) => {
+                self.println(impact.to_string());
+            }
+            Message::MsrvHistory(history) => {
+                self.println(history.to_string());
+            }
+            Message::MsrvLowering(lowering) => {
+                self.println(lowering.to_string());
+            }
+            Message::Serving(it) => self.println(format!(
+                "Serving the index of {} Rust releases, and installing toolchains, on {}, until interrupted",
+                it.releases(),
+                it.address()
+            )),
+            Message::FailureLog(log) => {
+                self.println(format!(
+                    "The most recent failed check is logged at '{}'",
+                    log.path().display()
+                ));
+            }
+            Message::RunSummary(summary) if summary.toolchains().checked > 0 => {
+                let toolchains = summary.toolchains();
+                let durations = summary.durations();
+
+                self.println(format!(
+                    "Summary: {} toolchains considered, {} checked, {} skipped; {} installed; {} cache hits; {} bytes downloaded; {} bytes on disk; {:.2}s total (fetch index {:.2}s, setup toolchains {:.2}s, search {:.2}s)",
+                    toolchains.considered,
+                    toolchains.checked,
+                    toolchains.skipped,
+                    toolchains.installed,
+                    summary.cache().hits,
+                    summary.downloaded_bytes(),
+                    summary.disk_used_bytes(),
+                    durations.total.as_secs_f64(),
+                    durations.fetch_index.as_secs_f64(),
+                    durations.setup_toolchains.as_secs_f64(),
+                    durations.search.as_secs_f64(),
+                ));
+            }
+            Message::TerminateWithFailure(termination) => {
+                self.println(termination.as_message());
+
+                if let Some(log_file) = termination.log_file() {
+                    self.println(format!(
+                        "More details may be found in the log file at '{}'.",
+                        log_file.display()
+                    ));
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::bare_version::BareVersion;
+    use crate::reporter::event::{CheckToolchain, EventScope, MsrvResult};
+    use crate::search_method::SearchSeeds;
+    use crate::toolchain::OwnedToolchainSpec;
+    use crate::{semver, Action, Config, Event};
+
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl SendWriter for SharedBuffer {}
+
+    impl SharedBuffer {
+        fn contents(&self) -> String {
+            String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+        }
+    }
+
+    fn check_events() -> Vec<Event> {
+        let toolchain = OwnedToolchainSpec::new(&semver::Version::new(1, 56, 0), "x");
+        let check: Event = CheckToolchain::new(toolchain.clone()).into();
+
+        vec![
+            check.with_scope(EventScope::Start),
+            Compatibility::incompatible(toolchain, Some("error[E0658]".to_string())).into(),
+            check.with_scope(EventScope::End),
+        ]
+    }
+
+    fn output(flavor: CiFlavor) -> String {
+        let buffer = SharedBuffer::default();
+        let handler = CiHandler::new(buffer.clone(), flavor, Locale::English);
+
+        for event in check_events() {
+            handler.handle(event);
+        }
+
+        buffer.contents()
+    }
+
+    #[test]
+    fn github_groups() {
+        assert_eq!(
+            output(CiFlavor::GitHub),
+            "::group::Compatibility Check #1: Rust 1.56.0 (x)\nRust 1.56.0 is incompatible\nerror[E0658]\n::endgroup::\n"
+        );
+    }
+
+    #[test]
+    fn gitlab_sections() {
+        let output = output(CiFlavor::GitLab);
+        let lines = output.lines().collect::<Vec<_>>();
+
+        assert!(lines[0].starts_with("\x1b[0Ksection_start:"));
+        assert!(lines[0].ends_with(
+            ":cargo_msrv_check_1[collapsed=true]\r\x1b[0KCompatibility Check #1: Rust 1.56.0 (x)"
+        ));
+        assert_eq!(lines[1], "Rust 1.56.0 is incompatible");
+        assert_eq!(lines[2], "error[E0658]");
+        assert!(lines[3].starts_with("\x1b[0Ksection_end:"));
+        assert!(lines[3].ends_with(":cargo_msrv_check_1\r\x1b[0K"));
+    }
+
+    #[test]
+    fn result_in_locale() {
+        let config = Config::new(Action::Find, "x".to_string());
+        let result = MsrvResult::new_msrv(
+            semver::Version::new(1, 60, 0),
+            &config,
+            BareVersion::TwoComponents(1, 56),
+            BareVersion::TwoComponents(1, 70),
+        )
+        .with_seeds(SearchSeeds {
+            known_good: Some(BareVersion::TwoComponents(1, 70)),
+            known_bad: Some(BareVersion::TwoComponents(1, 55)),
+        });
+
+        let buffer = SharedBuffer::default();
+        let handler = CiHandler::new(buffer.clone(), CiFlavor::Plain, Locale::German);
+        handler.handle(Event::new(Message::MsrvResult(result)));
+
+        assert_eq!(
+            buffer.contents(),
+            "MSRV: Rust 1.60.0\nBekannt kompatibel: Rust 1.70\nBekannt inkompatibel: Rust 1.55\n"
+        );
+    }
+
+    #[test]
+    fn plain_headers() {
+        assert_eq!(
+            output(CiFlavor::Plain),
+            "==> Compatibility Check #1: Rust 1.56.0 (x)\nRust 1.56.0 is incompatible\nerror[E0658]\n"
+        );
+    }
+}
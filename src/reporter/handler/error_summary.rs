@@ -0,0 +1,147 @@
+//! A summary of the output of a failed compatibility check, so the human output shows the
+//! diagnostics which caused the check to fail, instead of the complete output of the check command.
+
+/// The maximum number of diagnostics included in a summary.
+const MAX_DIAGNOSTICS: usize = 5;
+
+/// Lines which start like an error diagnostic, but only conclude the output of cargo or rustc.
+const CONCLUDING_LINES: [&str; 4] = [
+    "error: aborting due to",
+    "error: could not compile",
+    "error: Could not compile",
+    "error: build failed",
+];
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct ErrorSummary {
+    /// The first unique error diagnostics, each with the location it refers to, if any
+    diagnostics: Vec<String>,
+    /// The number of unique error diagnostics which were left out of the summary
+    omitted: usize,
+}
+
+impl ErrorSummary {
+    /// Summarize the output of a failed check. Returns `None` if the output doesn't contain any
+    /// error diagnostics, e.g. when a custom check command failed.
+    pub(crate) fn from_output(output: &str) -> Option<Self> {
+        let lines = output.lines().map(str::trim_end).collect::<Vec<_>>();
+        let mut unique: Vec<String> = Vec::new();
+
+        for (i, line) in lines.iter().enumerate() {
+            if !is_error_diagnostic(line) {
+                continue;
+            }
+
+            // The location follows the headline of the diagnostic, e.g. `  --> src/lib.rs:1:5`
+            let diagnostic = match lines.get(i + 1) {
+                Some(next) if next.trim_start().starts_with("--> ") => {
+                    format!("{}\n{}", line, next)
+                }
+                _ => line.to_string(),
+            };
+
+            if !unique.contains(&diagnostic) {
+                unique.push(diagnostic);
+            }
+        }
+
+        if unique.is_empty() {
+            return None;
+        }
+
+        let omitted = unique.len().saturating_sub(MAX_DIAGNOSTICS);
+        unique.truncate(MAX_DIAGNOSTICS);
+
+        Some(Self {
+            diagnostics: unique,
+            omitted,
+        })
+    }
+
+    pub(crate) fn diagnostics(&self) -> &[String] {
+        &self.diagnostics
+    }
+
+    pub(crate) fn omitted(&self) -> usize {
+        self.omitted
+    }
+}
+
+fn is_error_diagnostic(line: &str) -> bool {
+    if line.starts_with("error[E") {
+        return true;
+    }
+
+    line.starts_with("error: ")
+        && !CONCLUDING_LINES
+            .iter()
+            .any(|concluding| line.starts_with(concluding))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OUTPUT: &str = r#"    Checking example v0.1.0 (/example)
+error[E0658]: `let...else` statements are unstable
+ --> src/lib.rs:2:5
+  |
+2 |     let Some(x) = y else { return };
+  |     ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+
+error[E0658]: `let...else` statements are unstable
+ --> src/lib.rs:2:5
+  |
+2 |     let Some(x) = y else { return };
+  |     ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+
+error: expected one of `.`, `;`, `?`, or an operator, found `else`
+ --> src/main.rs:3:9
+
+error: aborting due to 2 previous errors
+
+For more information about this error, try `rustc --explain E0658`.
+error: could not compile `example` due to 2 previous errors
+"#;
+
+    #[test]
+    fn unique_diagnostics_with_location() {
+        let summary = ErrorSummary::from_output(OUTPUT).unwrap();
+
+        assert_eq!(
+            summary.diagnostics(),
+            &[
+                "error[E0658]: `let...else` statements are unstable\n --> src/lib.rs:2:5".to_string(),
+                "error: expected one of `.`, `;`, `?`, or an operator, found `else`\n --> src/main.rs:3:9".to_string(),
+            ]
+        );
+        assert_eq!(summary.omitted(), 0);
+    }
+
+    #[test]
+    fn limits_number_of_diagnostics() {
+        let output = (0..8)
+            .map(|i| {
+                format!(
+                    "error[E0433]: failed to resolve: use of undeclared crate `c{}`",
+                    i
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let summary = ErrorSummary::from_output(&output).unwrap();
+
+        assert_eq!(summary.diagnostics().len(), MAX_DIAGNOSTICS);
+        assert_eq!(summary.omitted(), 3);
+    }
+
+    #[yare::parameterized(
+        empty = { "" },
+        custom_command = { "FAIL: 3 tests failed" },
+        only_concluding = { "error: could not compile `example` due to previous error" },
+    )]
+    fn no_diagnostics(output: &str) {
+        assert!(ErrorSummary::from_output(output).is_none());
+    }
+}
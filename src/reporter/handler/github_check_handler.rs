@@ -0,0 +1,331 @@
+use crate::config::GitHubCheckOptions;
+use crate::reporter::event::{Compatibility, CompatibilityReport, Message};
+use serde_json::json;
+use std::sync::Mutex;
+use std::time::Duration;
+use storyteller::EventHandler;
+
+const API_URL: &str = "https://api.github.com";
+
+/// The maximum number of annotations which GitHub accepts with a single request.
+const MAX_ANNOTATIONS: usize = 50;
+
+/// The maximum length of the summary of a check run, as accepted by GitHub.
+const MAX_SUMMARY_LEN: usize = 65535;
+
+/// Reports the outcome of `cargo msrv verify` as a GitHub check run on the verified commit.
+///
+/// The check run is created once the toolchain check starts, and completed when the run is
+/// finished. If the check fails, the error diagnostics of the compiler are added as annotations.
+pub struct GitHubCheckHandler {
+    options: GitHubCheckOptions,
+    state: Mutex<CheckRunState>,
+}
+
+#[derive(Debug, Default)]
+struct CheckRunState {
+    /// The id of the check run, once it has been created
+    id: Option<u64>,
+    compatibility: Option<Compatibility>,
+    /// The reason the run failed, if it terminated with a failure
+    failure: Option<String>,
+}
+
+impl GitHubCheckHandler {
+    const TIMEOUT: Duration = Duration::from_secs(30);
+
+    pub fn new(options: GitHubCheckOptions) -> Self {
+        Self {
+            options,
+            state: Mutex::new(CheckRunState::default()),
+        }
+    }
+
+    fn check_runs_url(&self) -> String {
+        format!("{}/repos/{}/check-runs", API_URL, self.options.repository())
+    }
+
+    /// Create the check run, and return its id.
+    fn create(&self, body: serde_json::Value) -> Result<u64, String> {
+        let request = attohttpc::post(self.check_runs_url());
+        let response = self.send(request, body)?;
+
+        response
+            .get("id")
+            .and_then(serde_json::Value::as_u64)
+            .ok_or_else(|| "the created check run has no id".to_string())
+    }
+
+    fn update(&self, id: u64, body: serde_json::Value) -> Result<(), String> {
+        let request = attohttpc::patch(format!("{}/{}", self.check_runs_url(), id));
+        self.send(request, body).map(|_| ())
+    }
+
+    fn send(
+        &self,
+        request: attohttpc::RequestBuilder,
+        body: serde_json::Value,
+    ) -> Result<serde_json::Value, String> {
+        let token = self.options.token().as_str();
+
+        let response = request
+            .header("Accept", "application/vnd.github+json")
+            .header("Content-Type", "application/json")
+            .header("User-Agent", "cargo-msrv")
+            .try_header("Authorization", format!("Bearer {}", token))
+            .map_err(|err| err.to_string())?
+            .timeout(Self::TIMEOUT)
+            .bytes(body.to_string().into_bytes())
+            .send()
+            .map_err(|err| err.to_string())?;
+
+        if !response.is_success() {
+            return Err(format!(
+                "unexpected status code {}",
+                response.status().as_u16()
+            ));
+        }
+
+        let text = response.text().map_err(|err| err.to_string())?;
+        serde_json::from_str(&text).map_err(|err| err.to_string())
+    }
+
+    fn in_progress(&self) -> serde_json::Value {
+        json!({
+            "name": self.options.name(),
+            "head_sha": self.options.sha(),
+            "status": "in_progress",
+        })
+    }
+
+    fn completed(&self, state: &CheckRunState) -> Option<serde_json::Value> {
+        let (conclusion, title, summary, annotations) = match (&state.compatibility, &state.failure)
+        {
+            (Some(compatibility), _) => {
+                let version = compatibility.toolchain().version();
+
+                match &compatibility.compatibility_report {
+                    CompatibilityReport::Compatible => (
+                        "success",
+                        format!("Compatible with Rust {}", version),
+                        format!("The crate is compatible with Rust {}.", version),
+                        Vec::new(),
+                    ),
+                    CompatibilityReport::Incompatible { error } => {
+                        let output = error.as_deref().unwrap_or_default();
+
+                        (
+                            "failure",
+                            format!("Incompatible with Rust {}", version),
+                            incompatible_summary(version, output),
+                            annotations(output),
+                        )
+                    }
+                }
+            }
+            (None, Some(failure)) => (
+                "failure",
+                "Unable to verify the MSRV".to_string(),
+                failure.clone(),
+                Vec::new(),
+            ),
+            // The run didn't get as far as checking a toolchain, and didn't fail either
+            (None, None) => return None,
+        };
+
+        Some(json!({
+            "name": self.options.name(),
+            "head_sha": self.options.sha(),
+            "status": "completed",
+            "conclusion": conclusion,
+            "output": {
+                "title": title,
+                "summary": summary,
+                "annotations": annotations,
+            },
+        }))
+    }
+}
+
+impl EventHandler for GitHubCheckHandler {
+    type Event = super::Event;
+
+    fn handle(&self, event: Self::Event) {
+        let mut state = self.state.lock().unwrap();
+
+        match event.message() {
+            Message::CheckToolchain(_) if event.is_scope_start() && state.id.is_none() => {
+                match self.create(self.in_progress()) {
+                    Ok(id) => state.id = Some(id),
+                    Err(message) => warn_unreported(&self.options, &message),
+                }
+            }
+            Message::Compatibility(it) => state.compatibility = Some(it.clone()),
+            Message::TerminateWithFailure(it) => state.failure = Some(it.as_message().to_string()),
+            _ => {}
+        }
+    }
+
+    fn finish(&self) {
+        let state = self.state.lock().unwrap();
+
+        let body = match self.completed(&state) {
+            Some(body) => body,
+            None => return,
+        };
+
+        // A check run which can't be reported should not fail the run, since the outcome is
+        // still reported through the regular user output
+        let result = match state.id {
+            Some(id) => self.update(id, body),
+            None => self.create(body).map(|_| ()),
+        };
+
+        if let Err(message) = result {
+            warn_unreported(&self.options, &message);
+        }
+    }
+}
+
+fn warn_unreported(options: &GitHubCheckOptions, message: &str) {
+    tracing::warn!(
+        repository = options.repository(),
+        message = message,
+        "unable to report GitHub check run"
+    );
+}
+
+fn incompatible_summary(version: &crate::semver::Version, output: &str) -> String {
+    let header = format!("The crate is not compatible with Rust {}.", version);
+
+    if output.is_empty() {
+        return header;
+    }
+
+    // Leave room for the header and the fences of the code block
+    let room = MAX_SUMMARY_LEN - header.len() - 16;
+    let output = if output.len() > room {
+        let start = (output.len() - room..output.len())
+            .find(|&i| output.is_char_boundary(i))
+            .unwrap_or(output.len());
+
+        &output[start..]
+    } else {
+        output
+    };
+
+    format!("{}\n\n```\n{}\n```", header, output)
+}
+
+/// An annotation of a check run, which points to the location of an error diagnostic.
+#[derive(Debug, PartialEq, serde::Serialize)]
+struct Annotation {
+    path: String,
+    start_line: u64,
+    end_line: u64,
+    annotation_level: &'static str,
+    message: String,
+}
+
+/// The annotations for the error diagnostics in the output of a failed check, which refer to a
+/// location, e.g. `error[E0658]: ...` followed by `  --> src/lib.rs:1:5`.
+fn annotations(output: &str) -> Vec<Annotation> {
+    let lines = output.lines().map(str::trim_end).collect::<Vec<_>>();
+    let mut annotations: Vec<Annotation> = Vec::new();
+
+    for (i, line) in lines.iter().enumerate() {
+        if !(line.starts_with("error[E") || line.starts_with("error: ")) {
+            continue;
+        }
+
+        let location = match lines.get(i + 1).and_then(|next| location(next)) {
+            Some(location) => location,
+            None => continue,
+        };
+
+        let annotation = Annotation {
+            path: location.0.to_string(),
+            start_line: location.1,
+            end_line: location.1,
+            annotation_level: "failure",
+            message: line.to_string(),
+        };
+
+        if !annotations.contains(&annotation) {
+            annotations.push(annotation);
+        }
+    }
+
+    annotations.truncate(MAX_ANNOTATIONS);
+    annotations
+}
+
+/// Parse the location of a diagnostic, e.g. `  --> src/lib.rs:1:5`, into its path and line.
+fn location(line: &str) -> Option<(&str, u64)> {
+    let location = line.trim_start().strip_prefix("--> ")?;
+
+    let mut parts = location.rsplitn(3, ':');
+    let _column = parts.next()?;
+    let line = parts.next()?.parse().ok()?;
+    let path = parts.next()?;
+
+    Some((path, line))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OUTPUT: &str = "error[E0658]: use of unstable library feature 'let_else'
+  --> src/lib.rs:3:5
+   |
+3  |     let Some(x) = y else { return };
+   |     ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+
+error: expected item, found `}`
+ --> src/main.rs:12:1
+
+error: could not compile `example` due to 2 previous errors
+";
+
+    #[test]
+    fn annotations_of_located_diagnostics() {
+        assert_eq!(
+            annotations(OUTPUT),
+            vec![
+                Annotation {
+                    path: "src/lib.rs".to_string(),
+                    start_line: 3,
+                    end_line: 3,
+                    annotation_level: "failure",
+                    message: "error[E0658]: use of unstable library feature 'let_else'".to_string(),
+                },
+                Annotation {
+                    path: "src/main.rs".to_string(),
+                    start_line: 12,
+                    end_line: 12,
+                    annotation_level: "failure",
+                    message: "error: expected item, found `}`".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[yare::parameterized(
+        relative = { "  --> src/lib.rs:3:5", Some(("src/lib.rs", 3)) },
+        windows = { " --> C:\\crate\\src\\lib.rs:10:1", Some(("C:\\crate\\src\\lib.rs", 10)) },
+        no_location = { "   |", None },
+        no_line = { "  --> src/lib.rs", None },
+    )]
+    fn parse_location(line: &str, expected: Option<(&str, u64)>) {
+        assert_eq!(location(line), expected);
+    }
+
+    #[test]
+    fn long_output_is_truncated() {
+        let output = "x".repeat(MAX_SUMMARY_LEN * 2);
+        let version = crate::semver::Version::new(1, 56, 0);
+
+        assert!(incompatible_summary(&version, &output).len() <= MAX_SUMMARY_LEN);
+    }
+}
@@ -1,7 +1,11 @@
+use crate::config::{ColorChoice, Locale, LockfilePolicy};
 use crate::formatting::TermWidth;
 use crate::reporter::event::{
-    CheckToolchain, Compatibility, CompatibilityReport, Message, MsrvResult,
+    CheckTiming, CheckToolchain, Compatibility, CompatibilityReport, Message, MsrvResult,
+    ReuseDecision, SkipReason, TerminateWithFailure,
 };
+use crate::reporter::handler::catalog::{fill, Catalog};
+use crate::reporter::handler::error_summary::ErrorSummary;
 use crate::{semver, Action, Event};
 use owo_colors::OwoColorize;
 use std::collections::HashMap;
@@ -10,28 +14,58 @@ use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use storyteller::EventHandler;
-use tabled::object::Segment;
+use tabled::object::{Columns, Segment};
 use tabled::width::Percent;
-use tabled::{Alignment, Disable, Header, Margin, Modify, Style, Table, Width};
+use tabled::{Alignment, Disable, Header, Margin, Modify, Style, Table};
 use thiserror::private::PathAsDisplay;
 
 pub struct HumanProgressHandler {
     pb: indicatif::ProgressBar,
     sequence_number: AtomicU32,
+    timings: Mutex<Vec<(semver::Version, bool, CheckTiming)>>,
+    phases: Mutex<PhaseTimings>,
+    // The summaries of the failures printed so far, to refer to instead of repeating them
+    failures: Mutex<Vec<(semver::Version, ErrorSummary)>>,
+    catalog: &'static Catalog,
+    colors: bool,
 }
 
 impl Default for HumanProgressHandler {
     fn default() -> Self {
-        let mp = Self::styled_progress_bar();
+        Self::new(Locale::default(), ColorChoice::default())
+    }
+}
+
+impl HumanProgressHandler {
+    /// Instantiate the handler, which writes its messages in the language of the given `locale`,
+    /// and colors them depending on the given `color` choice.
+    pub fn new(locale: Locale, color: ColorChoice) -> Self {
+        let catalog = locale.catalog();
+        let mp = Self::styled_progress_bar(catalog);
 
         Self {
             pb: mp,
             sequence_number: AtomicU32::new(1),
+            timings: Mutex::new(Vec::new()),
+            phases: Mutex::new(PhaseTimings::default()),
+            failures: Mutex::new(Vec::new()),
+            catalog,
+            colors: color.colors_enabled(),
+        }
+    }
+
+    /// Print a message above the progress bar. The messages are styled regardless of whether
+    /// colors are enabled, so the styling is removed here when they are not.
+    fn println(&self, message: impl AsRef<str>) {
+        let message = message.as_ref();
+
+        if self.colors {
+            self.pb.println(message);
+        } else {
+            self.pb.println(console::strip_ansi_codes(message));
         }
     }
-}
 
-impl HumanProgressHandler {
     fn start_runner_progress(&self, version: &semver::Version) {
         self.sequence_number.fetch_add(1, Ordering::SeqCst);
         self.pb.reset();
@@ -42,11 +76,82 @@ impl HumanProgressHandler {
         self.pb.finish_and_clear();
     }
 
-    fn styled_progress_bar() -> indicatif::ProgressBar {
+    fn record_timing(&self, compatibility: &Compatibility) {
+        if let Some(timing) = compatibility.timing() {
+            let mut timings = self.timings.lock().unwrap();
+            timings.push((
+                compatibility.toolchain.version().clone(),
+                compatibility.is_compatible(),
+                timing.clone(),
+            ));
+        }
+    }
+
+    /// Add the elapsed duration of a finished scope to the duration of its phase.
+    fn record_phase(&self, event: &Event) {
+        let elapsed = match event.elapsed() {
+            Some(elapsed) => elapsed,
+            None => return,
+        };
+
+        let mut phases = self.phases.lock().unwrap();
+
+        match event.message() {
+            Message::FetchIndex(_) => phases.fetch_index += elapsed,
+            Message::SetupToolchain(_) => {
+                phases.setup_toolchains += elapsed;
+                phases.installed_toolchains += 1;
+            }
+            Message::FindMsrv(_) => phases.search += elapsed,
+            _ => {}
+        }
+    }
+
+    /// Print a summary of the error diagnostics in the output of a failed check, or a reference to
+    /// an earlier toolchain which failed with the same diagnostics. The complete output is printed
+    /// when it doesn't contain any diagnostics, and remains available in the json output.
+    fn print_error_report(&self, version: &semver::Version, error_report: &str) {
+        let summary = match ErrorSummary::from_output(error_report) {
+            Some(summary) => summary,
+            None => {
+                self.println(message_box(error_report));
+                return;
+            }
+        };
+
+        let mut failures = self.failures.lock().unwrap();
+
+        if let Some((earlier, _)) = failures.iter().find(|(_, earlier)| *earlier == summary) {
+            let reference = fill(self.catalog.same_failure_as, &[earlier]);
+            self.println(format!("  {:>16}  {}", "", reference.dimmed()));
+            return;
+        }
+
+        let mut message = summary.diagnostics().join("\n");
+        if summary.omitted() > 0 {
+            message.push('\n');
+            message.push_str(&fill(self.catalog.errors_omitted, &[&summary.omitted()]));
+        }
+
+        self.println(message_box(&message));
+        failures.push((version.clone(), summary));
+    }
+
+    fn print_log_file_hint(&self, termination: &TerminateWithFailure) {
+        if let Some(log_file) = termination.log_file() {
+            let hint = fill(self.catalog.log_file_hint, &[&log_file.display()]);
+            self.println(format!("{}", hint.dimmed()));
+        }
+    }
+
+    fn styled_progress_bar(catalog: &Catalog) -> indicatif::ProgressBar {
         let pb = indicatif::ProgressBar::new_spinner();
         pb.set_style(
             indicatif::ProgressStyle::default_spinner()
-                .template("{spinner} {msg:<16} Elapsed {elapsed}")
+                .template(&format!(
+                    "{{spinner}} {{prefix}}{{msg:<16}} {} {{elapsed}}",
+                    catalog.progress_elapsed
+                ))
                 .unwrap()
                 .tick_chars("◐◓◑◒"),
         );
@@ -59,62 +164,329 @@ impl EventHandler for HumanProgressHandler {
     type Event = super::Event;
 
     fn handle(&self, event: Self::Event) {
+        let catalog = self.catalog;
+
+        self.record_phase(&event);
+
         #[allow(unused_must_use)]
         match event.message() {
             Message::Meta(it) => {
-                let message = Status::meta(format_args!(
+                let message = Status::meta(catalog, format_args!(
                     "{} {} ({})",
                     it.instance(),
                     it.version(),
                     it.sha_short(),
                 ));
-                self.pb.println(message);
+                self.println(message);
             }
             Message::Action(it) if it.action().should_enable_spinner() => {
                 self.pb.reset(); // We'll reset here to ensure the steady tick call below works
                 self.pb.enable_steady_tick(Duration::from_millis(150));
             }
+            Message::FetchIndexProgress(it) => {
+                if it.is_first_resumed() {
+                    let message = Status::meta(catalog, fill(catalog.fetch_index_resumed, &[&it.source(), &it.resumed_after()]));
+                    self.println(message);
+                }
+
+                self.pb.set_message(fill(catalog.fetch_index_progress, &[&it.source(), &it.requests(), &it.releases()]));
+            }
+            Message::Progress(it) => {
+                // The position of the unit precedes the toolchain which is being checked
+                if let Some(unit) = it.unit() {
+                    if it.is_start_of_unit() {
+                        let message = Status::meta(catalog, fill(catalog.progress_unit, &[&unit.current(), &unit.total(), &unit.name()]));
+                        self.println(message);
+                    }
+
+                    self.pb.set_prefix(format!("[{}/{}] ", unit.current(), unit.total()));
+                }
+            }
+            Message::FetchIndex(_) if !event.is_scope_start() => {
+                self.pb.set_message("");
+            }
+            Message::EmbeddedIndexFallback(it) => {
+                let most_recent = it
+                    .most_recent_release()
+                    .map(|version| fill(catalog.embedded_index_most_recent, &[version]))
+                    .unwrap_or_default();
+                let message = Status::warn(catalog, fill(
+                    catalog.embedded_index_fallback,
+                    &[&it.snapshot_date(), &most_recent],
+                ));
+                self.println(message);
+            }
+            Message::ExcludedBinaries(it) => {
+                let message = Status::warn(catalog, fill(catalog.excluded_binaries, &[&it.names()]));
+                self.println(message);
+            }
+            Message::IncompatibleLockfile(it) => {
+                let message = match it.decision() {
+                    LockfilePolicy::Untestable => catalog.lockfile_untestable,
+                    LockfilePolicy::Regenerate => catalog.lockfile_regenerated,
+                };
+                let message = Status::warn(catalog, fill(message, &[it.toolchain().version(), &it.lockfile_format(), it.readable_since()]));
+                self.println(message);
+            }
+            Message::RetryCheck(it) => {
+                let message = Status::warn(catalog, fill(catalog.retry_check, &[it.toolchain().version(), &it.attempt(), &it.retries()]));
+                self.println(message);
+            }
+            Message::WrapperCacheStats(it) => {
+                let message = Status::meta(catalog, fill(catalog.wrapper_cache_stats, &[&it.wrapper(), it.toolchain().version(), &it.hits(), &it.misses()]));
+                self.println(message);
+            }
+            Message::UnitGraphReuse(it) => match it.decision() {
+                ReuseDecision::Reused => {
+                    let message = Status::meta(catalog, fill(catalog.unit_graph_reused, &[it.toolchain().version()]));
+                    self.println(message);
+                }
+                ReuseDecision::Unavailable => {
+                    let message = Status::meta(catalog, fill(catalog.unit_graph_unavailable, &[it.toolchain().version(), &it.reason().unwrap_or_default()]));
+                    self.println(message);
+                }
+                ReuseDecision::Checked => {}
+            },
+            Message::RequiredRustVersion(it) => {
+                let message = Status::warn(catalog, fill(catalog.required_rust_version, &[it.toolchain().version(), it.rust_version()]));
+                self.println(message);
+            }
+            Message::ComponentUnavailable(it) => {
+                let message = Status::warn(catalog, fill(catalog.component_unavailable, &[it.toolchain().version(), &it.components().join(", "), &it.toolchain().host()]));
+                self.println(message);
+            }
+            Message::LogFileUnavailable(it) => {
+                let folder = it.folder().map(|folder| fill(catalog.log_file_unavailable_folder, &[&folder.display()])).unwrap_or_default();
+                let message = Status::warn(catalog, fill(catalog.log_file_unavailable, &[&folder, &it.reason()]));
+                self.println(message);
+            }
+            Message::RevisionCheckout(it) => {
+                let message = Status::meta(catalog, fill(catalog.revision_checkout, &[&it.revision(), &it.commit(), &it.worktree().display()]));
+                self.println(message);
+            }
+            Message::MissingPrerequisites(it) => {
+                for missing in it.missing() {
+                    let message = match (missing.minimum_version(), missing.found_version()) {
+                        (Some(minimum), Some(found)) => fill(catalog.prerequisite_outdated, &[&missing.tool(), minimum, found, &missing.install_hint()]),
+                        _ => fill(catalog.prerequisite_not_found, &[&missing.tool(), &missing.install_hint()]),
+                    };
+                    self.println(Status::fail(catalog, message));
+
+                    if let Some(command) = missing.install_command() {
+                        let hint = fill(catalog.prerequisite_install_command, &[&command]);
+                        self.println(format!("{}", hint.dimmed()));
+                    }
+                    if missing.setup() {
+                        self.println(format!("{}", catalog.prerequisite_setup_hint.dimmed()));
+                    }
+                }
+            }
+            Message::PrerequisiteInstall(it) => {
+                let message = Status::meta(catalog, fill(catalog.prerequisite_install, &[&it.tool(), &it.command()]));
+                self.println(message);
+            }
+            Message::SearchSpaceClamped(it) => {
+                let unavailable = it.unavailable();
+                let message = Status::warn(catalog, fill(
+                    catalog.search_space_clamped,
+                    &[unavailable.version(), &unavailable.target(), it.minimum()],
+                ));
+                self.println(message);
+            }
+            Message::VersionResolved(it) => {
+                let message = Status::warn(catalog, fill(catalog.version_resolved, &[it.requested(), it.resolved(), &it.resolution()]));
+                self.println(message);
+            }
+            Message::ManifestSyntax(it) => {
+                let message = Status::warn(catalog, fill(catalog.manifest_syntax, &[&it.key(), it.readable_since()]));
+                self.println(message);
+            }
             Message::CheckToolchain(it) if event.is_scope_start() => {
-                self.pb.println(it.header(self.sequence_number.load(Ordering::SeqCst)));
+                self.println(it.header(catalog, self.sequence_number.load(Ordering::SeqCst)));
                 self.start_runner_progress(it.toolchain.version());
             }
+            Message::CheckOutput(it) => {
+                self.println(format!("{}", it.line().dimmed()));
+            }
             Message::CheckToolchain(it) /* is scope end */ => {
                 let version = it.toolchain.version();
                 self.finish_runner_progress();
             }
-            Message::Compatibility(Compatibility {  compatibility_report: CompatibilityReport::Compatible, toolchain, .. }) => {
+            Message::Compatibility(it @ Compatibility {  compatibility_report: CompatibilityReport::Compatible, toolchain, .. }) => {
+                self.record_timing(it);
                 let version = toolchain.version();
-                let message = Status::ok("Is compatible");
-                self.pb.println(message);
+                let message = Status::ok(catalog, catalog.is_compatible);
+                self.println(message);
             }
-            Message::Compatibility(Compatibility {  compatibility_report: CompatibilityReport::Incompatible { error }, toolchain, .. }) => {
+            Message::Compatibility(it @ Compatibility {  compatibility_report: CompatibilityReport::Incompatible { error }, toolchain, .. }) => {
+                self.record_timing(it);
                 let version = toolchain.version();
-                let message = Status::fail("Is Incompatible");
-                self.pb.println(message);
+                let message = Status::fail(catalog, catalog.is_incompatible);
+                self.println(message);
+
+                if let Some(note) = it.encoding_note() {
+                    let message = Status::warn(catalog, fill(catalog.encoding_note, &[&note.invalid_sequences()]));
+                    self.println(message);
+                }
 
                 if let Some(error_report) = error.as_deref() {
-                    self.pb.println(message_box(error_report));
+                    self.print_error_report(version, error_report);
+                }
+            }
+            Message::SearchDecision(it) => {
+                let outcome = if it.compatible() { catalog.compatible } else { catalog.incompatible };
+                let remaining = it.remaining().map(|range| fill(catalog.search_decision_next, &[range])).unwrap_or_else(|| catalog.search_decision_done.to_string());
+                let message = Status::meta(catalog, fill(catalog.search_decision, &[it.range(), it.tested(), &outcome, &remaining]));
+                self.println(message);
+            }
+            Message::ExhaustiveSearch(it) => {
+                let releases = it.releases().iter().map(|release| {
+                    let outcome = if release.compatible() { catalog.compatible } else { catalog.incompatible };
+                    format!("{} {}", release.version(), outcome)
+                }).collect::<Vec<_>>().join(", ");
+                self.println(Status::meta(catalog, fill(catalog.exhaustive_search, &[&it.releases().len(), &releases])));
+
+                if !it.non_monotonic().is_empty() {
+                    let message = Status::warn(catalog, fill(catalog.non_monotonic, &[&it.non_monotonic_versions()]));
+                    self.println(message);
                 }
             }
             Message::MsrvResult(result) => {
-                self.pb.println(format!("\n{}\n", result.summary()));
+                self.println(format!("\n{}\n", result.summary(catalog)));
+
+                let timings = self.timings.lock().unwrap();
+                if !timings.is_empty() {
+                    self.println(format!("{}\n", timings_table(catalog, &timings)));
+                }
+
+                let phases = self.phases.lock().unwrap();
+                if !phases.is_empty() {
+                    self.println(format!("{}\n", phases_table(catalog, &phases)));
+                }
+
+                if let Some(cache) = result.dependency_cache() {
+                    let message = fill(
+                        catalog.dependency_cache,
+                        &[&secs(cache.fetch()), &cache.offline_checks()],
+                    );
+                    self.println(format!("{}\n", Status::meta(catalog, message)));
+                }
+
+                if let Some(bump) = result.version_bump() {
+                    let kind: &str = bump.kind.into();
+                    let message = fill(
+                        catalog.version_bump,
+                        &[&bump.declared_msrv, &kind, &bump.current_version, &bump.suggested_version],
+                    );
+                    self.println(format!("{}\n", Status::meta(catalog, message)));
+                }
+
+                if let (Some(newer), Some(msrv)) = (result.newer_patch_release(), result.msrv()) {
+                    let message = fill(catalog.newer_patch_release, &[newer, msrv]);
+                    self.println(format!("{}\n", Status::meta(catalog, message)));
+                }
+            }
+            Message::InconclusiveResult(it) => {
+                let mut message = fill(catalog.inconclusive_result, &[&it.max_checks()]);
+
+                if let Some(version) = it.least_recent_accepted() {
+                    message.push_str(&fill(catalog.inconclusive_at_most, &[version]));
+                }
+
+                if let Some(version) = it.most_recent_rejected() {
+                    message.push_str(&fill(catalog.inconclusive_more_recent_than, &[version]));
+                }
+
+                self.println(format!("\n{}\n", Status::warn(catalog, message)));
+            }
+            Message::SkippedMember(it) => {
+                let message = match it.reason() {
+                    SkipReason::Unchanged { since } => fill(catalog.skipped_member_unchanged, &[&it.name(), &it.path().display(), since]),
+                    SkipReason::NoMsrv => fill(catalog.skipped_member_no_msrv, &[&it.name(), &it.path().display()]),
+                };
+                self.println(Status::meta(catalog, message));
             }
             Message::ListDep(list) => {
-                self.pb.println(list.to_string());
+                self.println(list.to_string());
             }
             Message::SetOutput(output) => {
-                let message = Status::with_lead("Set".bright_green(), format_args!("Rust {}", output.version()));
-                self.pb.println(message);
+                let message = Status::with_lead(catalog.lead_set.bright_green(), fill(catalog.set_output, &[output.version()]));
+                self.println(message);
             }
             Message::ShowOutput(output) => {
-                let message = Status::with_lead("Show".bright_green(), format_args!("MSRV is Rust {}", output.version()));
-                self.pb.println(message);
+                let message = Status::with_lead(catalog.lead_show.bright_green(), fill(catalog.show_output, &[output.version()]));
+                self.println(message);
+            }
+            Message::ToolConfigMsrv(it) => {
+                let path = it.path().display();
+                let message = if it.synced() {
+                    Status::with_lead(catalog.lead_set.bright_green(), fill(catalog.tool_config_msrv_synced, &[&it.tool(), &path, it.manifest_version()]))
+                } else if it.is_consistent() {
+                    Status::ok(catalog, fill(catalog.tool_config_msrv, &[&it.tool(), it.version(), &path]))
+                } else {
+                    Status::warn(catalog, fill(catalog.tool_config_msrv_mismatch, &[&it.tool(), it.version(), &path, it.manifest_version()]))
+                };
+                self.println(message);
+            }
+            Message::ReportDiff(diff) => {
+                self.println(diff.to_string());
+            }
+            Message::ResolvedConfig(config) => {
+                self.println(config.to_string());
+            }
+            Message::PreferenceSet(it) => {
+                let message = Status::with_lead(catalog.lead_set.bright_green(), fill(catalog.preference_set, &[&it.key(), &it.value(), &it.path().display()]));
+                self.println(message);
+            }
+            Message::EditionAdvice(advice) => {
+                self.println(advice.to_string());
+            }
+            Message::ReleaseList(list) => {
+                self.println(list.to_string());
+            }
+            Message::MsrvMatrix(matrix) => {
+                self.println(matrix.to_string());
+            }
+            Message::PolicyCheck(check) => {
+                self.println(check.to_string());
+            }
+            Message::DiscoveredProjects(projects) => {
+                self.println(projects.to_string());
+            }
+            Message::ManifestValidation(validation) => {
+                self.println(validation.to_string());
+            }
+            Message::DependencyImpact(impact) => {
+                self.println(impact.to_string());
+            }
+            Message::MsrvHistory(history) => {
+                self.println(history.to_string());
+            }
+            Message::MsrvLowering(lowering) => {
+                self.println(lowering.to_string());
+            }
+            Message::Serving(it) => {
+                let message = Status::with_lead(catalog.lead_show.bright_green(), fill(catalog.serving, &[&it.releases(), &it.address()]));
+                self.println(message);
+            }
+            Message::FailureLog(log) => {
+                let message = Status::with_lead(catalog.lead_show.bright_green(), fill(catalog.failure_log, &[&log.path().display()]));
+                self.println(message);
+            }
+            // Only a run which checked toolchains has statistics worth a footer
+            Message::RunSummary(summary) if summary.toolchains().checked > 0 => {
+                let toolchains = summary.toolchains();
+                let durations = summary.durations();
+                let message = fill(catalog.run_summary, &[&toolchains.considered, &toolchains.checked, &toolchains.skipped, &toolchains.installed, &summary.cache().hits, &bytes(summary.downloaded_bytes()), &bytes(summary.disk_used_bytes()), &secs(durations.total), &secs(durations.fetch_index), &secs(durations.setup_toolchains), &secs(durations.search)]);
+                self.println(Status::meta(catalog, message));
             }
             Message::TerminateWithFailure(termination) if termination.is_error() => {
-                self.pb.println(format!("\n\n{}", termination.as_message().red()));
+                self.println(format!("\n\n{}", termination.as_message().red()));
+                self.print_log_file_hint(termination);
             }
             Message::TerminateWithFailure(termination) if !termination.is_error() => {
-                self.pb.println(format!("\n\n{}", termination.as_message().dimmed().bold()));
+                self.println(format!("\n\n{}", termination.as_message().dimmed().bold()));
+                self.print_log_file_hint(termination);
             }
             _ => {}
         };
@@ -128,91 +500,274 @@ impl Action {
 }
 
 impl CheckToolchain {
-    fn header(&self, nth: u32) -> String {
-        format!(
-            "\n{} #{}: Rust {}",
-            "Compatibility Check",
-            nth,
-            self.toolchain.version(),
-        )
-        .bold()
-        .to_string()
+    fn header(&self, catalog: &Catalog, nth: u32) -> String {
+        let header = fill(catalog.check_header, &[&nth, self.toolchain.version()]);
+
+        format!("\n{}", header).bold().to_string()
     }
 }
 
 impl MsrvResult {
-    fn summary(&self) -> String {
-        result_table(self)
+    fn summary(&self, catalog: &Catalog) -> String {
+        result_table(catalog, self)
     }
 }
 
 struct Status;
 
 impl Status {
-    fn meta(message: impl Display) -> String {
-        let lead = format!("[{}]", "Meta".bright_blue());
-        format!("  {:>16}  {}", lead, message)
+    fn meta(catalog: &Catalog, message: impl Display) -> String {
+        Self::with_lead(catalog.lead_meta.bright_blue(), message)
+    }
+
+    fn ok(catalog: &Catalog, message: impl Display) -> String {
+        Self::with_lead(catalog.lead_ok.bright_green(), message)
     }
 
-    fn ok(message: impl Display) -> String {
-        let lead = format!("[{}]", "OK".bright_green());
-        format!("  {:>16}  {}", lead, message)
+    fn warn(catalog: &Catalog, message: impl Display) -> String {
+        Self::with_lead(catalog.lead_warn.bright_yellow(), message)
     }
 
-    fn fail(message: impl Display) -> String {
-        let lead = format!("[{}]", "FAIL".bright_red());
-        format!("  {:>16}  {}", lead, message)
+    fn fail(catalog: &Catalog, message: impl Display) -> String {
+        Self::with_lead(catalog.lead_fail.bright_red(), message)
     }
 
+    /// Right-align the lead in a column, like cargo aligns its status words. The lead is padded by
+    /// its visible width, so colored and plain leads line up alike.
     fn with_lead(lead: impl Display, message: impl Display) -> String {
         let lead = format!("[{}]", lead);
-        format!("  {:>16}  {}", lead, message)
+        let lead = console::pad_str(&lead, 16, console::Alignment::Right, None);
+        format!("  {}  {}", lead, message)
     }
 }
 
+/// Print the message in a box, with each line truncated to the width of the terminal.
 fn message_box(message: &str) -> String {
+    // The borders of the box, and their padding, take four columns
+    let width = TermWidth::width().saturating_sub(4);
+
+    let message = message
+        .lines()
+        .map(|line| console::truncate_str(line, width, "…"))
+        .collect::<Vec<_>>()
+        .join("\n");
+
     Table::new(&[format!("{}", message.dimmed())])
         .with(Disable::Row(..1)) // Disables the header; Style::header_off doesn't work! ordering matters!
         .with(Style::rounded())
-        .with(Width::wrap(TermWidth::width()))
         .to_string()
 }
 
-fn result_table(result: &MsrvResult) -> String {
-    fn msrv(result: &MsrvResult) -> String {
+fn result_table(catalog: &Catalog, result: &MsrvResult) -> String {
+    fn msrv(catalog: &Catalog, result: &MsrvResult) -> String {
         result
             .msrv()
             .map(|version| format!("{}", version.green().bold().underline()))
-            .unwrap_or_else(|| format!("{}", "N/A".red()))
+            .unwrap_or_else(|| format!("{}", catalog.not_available.red()))
     }
 
     let target = result.target.as_str();
     let search_method: &str = result.search_method.into();
 
-    let content = &[
-        &[
-            format!("Considered ({} … {}):", "min".cyan(), "max".yellow()),
-            format!(
-                "Rust {} … Rust {}",
-                result.minimum_version.cyan(),
-                result.maximum_version.yellow()
-            ),
-        ],
-        &[
-            "Search method:".to_string(),
+    let mut content = vec![[
+        fill(
+            catalog.result_considered,
+            &[&catalog.result_min.cyan(), &catalog.result_max.yellow()],
+        ),
+        format!(
+            "Rust {} … Rust {}",
+            result.minimum_version.cyan(),
+            result.maximum_version.yellow()
+        ),
+    ]];
+
+    if !result.excluded_versions.is_empty() {
+        let excluded = result
+            .excluded_versions
+            .iter()
+            .map(|version| format!("Rust {}", version))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        content.push([catalog.result_excluded.to_string(), excluded]);
+    }
+
+    if let Some(seeds) = result.seeds() {
+        if let Some(version) = &seeds.known_good {
+            content.push([
+                catalog.result_known_good.to_string(),
+                format!("Rust {}", version),
+            ]);
+        }
+
+        if let Some(version) = &seeds.known_bad {
+            content.push([
+                catalog.result_known_bad.to_string(),
+                format!("Rust {}", version),
+            ]);
+        }
+    }
+
+    content.extend([
+        [
+            catalog.result_search_method.to_string(),
             format!("{}", search_method.bright_purple()),
         ],
-        &["MSRV:".to_string(), msrv(result)],
-        &[
-            format!("{}", "Target:".dimmed()),
+        [
+            result_label(catalog, result).to_string(),
+            msrv(catalog, result),
+        ],
+        [
+            format!("{}", catalog.result_target.dimmed()),
             format!("{}", target.dimmed()),
         ],
-    ];
+    ]);
+
+    Table::new(&content)
+        .with(Disable::Row(..1)) // Disables the header; Style::header_off doesn't work! ordering matters!
+        .with(Header(format!("{}", catalog.result_header.bold())))
+        .with(Modify::new(Segment::all()).with(Alignment::left()))
+        .with(Style::blank())
+        .to_string()
+}
+
+fn result_label(catalog: &Catalog, result: &MsrvResult) -> &'static str {
+    if result.polarity.is_inverted() {
+        catalog.result_first_failing
+    } else {
+        catalog.result_msrv
+    }
+}
+
+fn timings_table(catalog: &Catalog, timings: &[(semver::Version, bool, CheckTiming)]) -> String {
+    let content = timings
+        .iter()
+        .map(|(version, is_compatible, timing)| {
+            [
+                format!("Rust {}", version),
+                if *is_compatible {
+                    format!("{}", catalog.compatible.green())
+                } else {
+                    format!("{}", catalog.incompatible.red())
+                },
+                secs(timing.wall_clock()),
+                timing
+                    .cargo_reported()
+                    .map(secs)
+                    .unwrap_or_else(|| format!("{}", catalog.not_available.dimmed())),
+            ]
+        })
+        .collect::<Vec<_>>();
 
     Table::new(content)
         .with(Disable::Row(..1)) // Disables the header; Style::header_off doesn't work! ordering matters!
-        .with(Header(format!("{}", "Result:".bold())))
+        .with(Header(format!(
+            "{} ({} / {})",
+            catalog.durations_header.bold(),
+            catalog.durations_wall_clock,
+            catalog.durations_cargo_reported.dimmed()
+        )))
         .with(Modify::new(Segment::all()).with(Alignment::left()))
+        // Durations are aligned by their decimal point
+        .with(Modify::new(Columns::new(2..)).with(Alignment::right()))
         .with(Style::blank())
         .to_string()
 }
+
+/// The total duration of each phase of a run, as measured by the scopes of its events.
+#[derive(Debug, Default)]
+struct PhaseTimings {
+    fetch_index: Duration,
+    setup_toolchains: Duration,
+    installed_toolchains: u32,
+    search: Duration,
+}
+
+impl PhaseTimings {
+    fn is_empty(&self) -> bool {
+        self.fetch_index.is_zero() && self.installed_toolchains == 0 && self.search.is_zero()
+    }
+}
+
+fn phases_table(catalog: &Catalog, phases: &PhaseTimings) -> String {
+    let mut content = Vec::new();
+
+    if !phases.fetch_index.is_zero() {
+        content.push([
+            catalog.phase_fetch_index.to_string(),
+            secs(phases.fetch_index),
+        ]);
+    }
+
+    if phases.installed_toolchains > 0 {
+        content.push([
+            fill(
+                catalog.phase_setup_toolchains,
+                &[&phases.installed_toolchains],
+            ),
+            secs(phases.setup_toolchains),
+        ]);
+    }
+
+    if !phases.search.is_zero() {
+        content.push([catalog.phase_search.to_string(), secs(phases.search)]);
+    }
+
+    Table::new(content)
+        .with(Disable::Row(..1)) // Disables the header; Style::header_off doesn't work! ordering matters!
+        .with(Header(format!("{}", catalog.phases_header.bold())))
+        .with(Modify::new(Segment::all()).with(Alignment::left()))
+        .with(Style::blank())
+        .to_string()
+}
+
+fn secs(duration: Duration) -> String {
+    format!("{:.2}s", duration.as_secs_f64())
+}
+
+/// The number of bytes, in the largest binary unit of which there is at least one, e.g. `1.50 MiB`.
+fn bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = UNITS[0];
+
+    for next in &UNITS[1..] {
+        if size < 1024.0 {
+            break;
+        }
+
+        size /= 1024.0;
+        unit = next;
+    }
+
+    format!("{:.2} {}", size, unit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn colored_and_plain_leads_line_up() {
+        let colored = Status::with_lead("OK".bright_green(), "Is compatible");
+        let plain = Status::with_lead("OK", "Is compatible");
+
+        assert_eq!(console::strip_ansi_codes(&colored), plain);
+        assert_eq!(plain, "              [OK]  Is compatible");
+    }
+
+    #[yare::parameterized(
+        bytes_only = { 512, "512 B" },
+        kibibytes = { 2048, "2.00 KiB" },
+        mebibytes = { 1536 * 1024, "1.50 MiB" },
+        gibibytes = { 3 << 30, "3.00 GiB" },
+    )]
+    fn human_readable_bytes(size: u64, expected: &str) {
+        assert_eq!(bytes(size), expected);
+    }
+}
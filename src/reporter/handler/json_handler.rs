@@ -1,3 +1,5 @@
+use crate::config::EventFilter;
+use crate::reporter::event::Message;
 use std::io;
 use std::io::Stderr;
 use std::sync::{Arc, Mutex};
@@ -7,6 +9,7 @@ pub trait SendWriter: io::Write + Send + 'static {}
 
 pub struct JsonHandler<W: SendWriter> {
     writer: Arc<Mutex<W>>,
+    filter: Option<EventFilter>,
 }
 
 impl<W: SendWriter> JsonHandler<W> {
@@ -16,15 +19,43 @@ impl<W: SendWriter> JsonHandler<W> {
         "{ \"panic\": true, \"cause\": \"Unable to serialize event for JsonHandle\", \"experimental\": true }";
     const WRITE_FAILURE_MSG: &'static str =
         "{ \"panic\": true, \"cause\": \"Unable to write serialized event for JsonHandle\", \"experimental\": true }";
+
+    fn new(writer: W) -> Self {
+        Self {
+            writer: Arc::new(Mutex::new(writer)),
+            filter: None,
+        }
+    }
+
+    /// Only write the events of the kinds selected by the filter. A failure to complete the run
+    /// is always written, so it can't go unnoticed.
+    pub fn with_filter(mut self, filter: Option<EventFilter>) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    fn is_selected(&self, event: &super::Event, serialized: &serde_json::Value) -> bool {
+        let filter = match &self.filter {
+            Some(filter) => filter,
+            None => return true,
+        };
+
+        if let Message::TerminateWithFailure(_) = event.message() {
+            return true;
+        }
+
+        serialized
+            .get("type")
+            .and_then(serde_json::Value::as_str)
+            .map_or(false, |kind| filter.selects(kind))
+    }
 }
 
 impl SendWriter for Stderr {}
 
 impl JsonHandler<Stderr> {
     pub fn stderr() -> Self {
-        Self {
-            writer: Arc::new(Mutex::new(io::stderr())),
-        }
+        Self::new(io::stderr())
     }
 }
 
@@ -32,9 +63,88 @@ impl<W: SendWriter> EventHandler for JsonHandler<W> {
     type Event = super::Event;
 
     fn handle(&self, event: Self::Event) {
-        let mut w = self.writer.lock().expect(Self::LOCK_FAILURE_MSG);
+        let serialized = serde_json::to_value(&event).expect(Self::SERIALIZE_FAILURE_MSG);
+
+        if !self.is_selected(&event, &serialized) {
+            return;
+        }
+
+        // Serialized again, since the fields of the value would be sorted by their name
         let serialized_event = serde_json::to_string(&event).expect(Self::SERIALIZE_FAILURE_MSG);
+        let mut w = self.writer.lock().expect(Self::LOCK_FAILURE_MSG);
 
         writeln!(&mut w, "{}", &serialized_event).expect(Self::WRITE_FAILURE_MSG);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::event::{Compatibility, Progress, TerminateWithFailure};
+    use crate::toolchain::OwnedToolchainSpec;
+    use crate::{semver, CargoMSRVError, Event};
+    use std::io::Write;
+
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl SendWriter for SharedBuffer {}
+
+    /// The `type` of each written message.
+    fn written_kinds(filter: Option<EventFilter>) -> Vec<String> {
+        let buffer = SharedBuffer::default();
+        let handler = JsonHandler::new(buffer.clone()).with_filter(filter);
+
+        let toolchain = OwnedToolchainSpec::new(&semver::Version::new(1, 56, 0), "x");
+        let events: Vec<Event> = vec![
+            Progress::new(1, 10, 1).into(),
+            Compatibility::compatible(toolchain).into(),
+            TerminateWithFailure::new(CargoMSRVError::UnableToFindAnyGoodVersion {
+                command: "cargo check".to_string(),
+            })
+            .into(),
+        ];
+
+        for event in events {
+            handler.handle(event);
+        }
+
+        let contents = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+
+        contents
+            .lines()
+            .map(|line| {
+                let value: serde_json::Value = serde_json::from_str(line).unwrap();
+                value["type"].as_str().unwrap().to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn writes_all_events_without_filter() {
+        assert_eq!(
+            written_kinds(None),
+            vec!["progress", "compatibility", "terminate_with_failure"]
+        );
+    }
+
+    #[test]
+    fn writes_selected_events() {
+        let filter = EventFilter::new(["compatibility", "msrv_result"]);
+
+        assert_eq!(
+            written_kinds(Some(filter)),
+            vec!["compatibility", "terminate_with_failure"]
+        );
+    }
+}
@@ -0,0 +1,407 @@
+use crate::reporter::event::{Message, ScopeId};
+use crate::toolchain::OwnedToolchainSpec;
+use serde_json::json;
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use storyteller::EventHandler;
+
+/// The path to which the spans are POSTed, relative to the endpoint of the collector.
+const TRACES_PATH: &str = "/v1/traces";
+
+/// The status codes of a span, as defined by OpenTelemetry.
+const STATUS_OK: u8 = 1;
+const STATUS_ERROR: u8 = 2;
+
+/// Exports the run, and each toolchain check, as spans to an OpenTelemetry collector, through
+/// the JSON encoding of OTLP/HTTP.
+///
+/// The spans are POSTed at once when the run is finished, as children of a span which covers the
+/// whole run. The start and end of a span are the timestamps of the events which started and
+/// ended it.
+pub struct OtelHandler {
+    endpoint: String,
+    trace_id: String,
+    run_span_id: String,
+    state: Mutex<TraceState>,
+}
+
+#[derive(Debug, Default)]
+struct TraceState {
+    /// The timestamps of the first and the last event of the run
+    first: Option<SystemTime>,
+    last: Option<SystemTime>,
+    /// The toolchain checks which started, but didn't end yet
+    open: HashMap<ScopeId, OpenCheck>,
+    checks: Vec<CheckSpan>,
+    /// The reason the run failed, if it terminated with a failure
+    failure: Option<String>,
+}
+
+#[derive(Debug)]
+struct OpenCheck {
+    toolchain: OwnedToolchainSpec,
+    start: SystemTime,
+    compatible: Option<bool>,
+}
+
+#[derive(Debug)]
+struct CheckSpan {
+    span_id: String,
+    toolchain: OwnedToolchainSpec,
+    start: SystemTime,
+    duration: Duration,
+    compatible: Option<bool>,
+}
+
+impl OtelHandler {
+    const TIMEOUT: Duration = Duration::from_secs(30);
+
+    pub fn new(endpoint: impl Into<String>) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            trace_id: format!("{}{}", random_id(), random_id()),
+            run_span_id: random_id(),
+            state: Mutex::new(TraceState::default()),
+        }
+    }
+
+    fn traces_url(&self) -> String {
+        let endpoint = self.endpoint.trim_end_matches('/');
+
+        if endpoint.ends_with(TRACES_PATH) {
+            endpoint.to_string()
+        } else {
+            format!("{}{}", endpoint, TRACES_PATH)
+        }
+    }
+
+    /// The spans of the run, in the JSON encoding of an OTLP `ExportTraceServiceRequest`.
+    fn export_request(&self, state: &TraceState) -> Option<serde_json::Value> {
+        let (first, last) = (state.first?, state.last?);
+
+        let run_status = match &state.failure {
+            Some(message) => json!({ "code": STATUS_ERROR, "message": message }),
+            None => json!({ "code": STATUS_OK }),
+        };
+
+        let run = json!({
+            "traceId": self.trace_id,
+            "spanId": self.run_span_id,
+            "name": "cargo-msrv",
+            "kind": 1,
+            "startTimeUnixNano": unix_nanos(first),
+            "endTimeUnixNano": unix_nanos(last),
+            "attributes": [],
+            "status": run_status,
+        });
+
+        let checks = state.checks.iter().map(|check| {
+            let outcome = match check.compatible {
+                Some(true) => "compatible",
+                Some(false) => "incompatible",
+                None => "unknown",
+            };
+            let status = match check.compatible {
+                Some(true) => STATUS_OK,
+                _ => STATUS_ERROR,
+            };
+
+            json!({
+                "traceId": self.trace_id,
+                "spanId": check.span_id,
+                "parentSpanId": self.run_span_id,
+                "name": "check_toolchain",
+                "kind": 1,
+                "startTimeUnixNano": unix_nanos(check.start),
+                "endTimeUnixNano": unix_nanos(check.start + check.duration),
+                "attributes": [
+                    string_attribute("rust.version", &check.toolchain.version().to_string()),
+                    string_attribute("rust.target", check.toolchain.target()),
+                    string_attribute("cargo_msrv.outcome", outcome),
+                    json!({
+                        "key": "cargo_msrv.duration",
+                        "value": { "doubleValue": check.duration.as_secs_f64() },
+                    }),
+                ],
+                "status": { "code": status },
+            })
+        });
+
+        let spans = std::iter::once(run).chain(checks).collect::<Vec<_>>();
+
+        Some(json!({
+            "resourceSpans": [{
+                "resource": {
+                    "attributes": [string_attribute("service.name", "cargo-msrv")],
+                },
+                "scopeSpans": [{
+                    "scope": { "name": "cargo-msrv", "version": env!("CARGO_PKG_VERSION") },
+                    "spans": spans,
+                }],
+            }],
+        }))
+    }
+
+    fn export(&self, body: serde_json::Value) -> Result<(), String> {
+        let response = attohttpc::post(self.traces_url())
+            .header("Content-Type", "application/json")
+            .header("User-Agent", "cargo-msrv")
+            .timeout(Self::TIMEOUT)
+            .bytes(body.to_string().into_bytes())
+            .send()
+            .map_err(|err| err.to_string())?;
+
+        if response.is_success() {
+            Ok(())
+        } else {
+            Err(format!(
+                "unexpected status code {}",
+                response.status().as_u16()
+            ))
+        }
+    }
+}
+
+impl EventHandler for OtelHandler {
+    type Event = super::Event;
+
+    fn handle(&self, event: Self::Event) {
+        let mut state = self.state.lock().unwrap();
+        let timestamp = event.timestamp();
+
+        state.first.get_or_insert(timestamp);
+        state.last = Some(timestamp);
+
+        match (event.message(), event.scope_id()) {
+            (Message::CheckToolchain(it), Some(id)) if event.is_scope_start() => {
+                state.open.insert(
+                    id,
+                    OpenCheck {
+                        toolchain: it.toolchain.clone(),
+                        start: timestamp,
+                        compatible: None,
+                    },
+                );
+            }
+            (Message::CheckToolchain(_), Some(id)) => {
+                if let Some(open) = state.open.remove(&id) {
+                    let duration = event.elapsed().unwrap_or_else(|| {
+                        timestamp.duration_since(open.start).unwrap_or_default()
+                    });
+
+                    state.checks.push(CheckSpan {
+                        span_id: random_id(),
+                        toolchain: open.toolchain,
+                        start: open.start,
+                        duration,
+                        compatible: open.compatible,
+                    });
+                }
+            }
+            (Message::Compatibility(it), _) => {
+                // the outcome of the last attempt counts, when a check is retried
+                let toolchain = it.toolchain();
+
+                if let Some(open) = state.open.values_mut().find(|open| {
+                    open.toolchain.version() == toolchain.version()
+                        && open.toolchain.target() == toolchain.target()
+                }) {
+                    open.compatible = Some(it.is_compatible());
+                }
+            }
+            (Message::TerminateWithFailure(it), _) => {
+                state.failure = Some(it.as_message().to_string());
+            }
+            _ => {}
+        }
+    }
+
+    fn finish(&self) {
+        let state = self.state.lock().unwrap();
+
+        let body = match self.export_request(&state) {
+            Some(body) => body,
+            None => return,
+        };
+
+        // Spans which can't be exported should not fail the run, since the outcome is still
+        // reported through the regular user output
+        if let Err(message) = self.export(body) {
+            tracing::warn!(
+                endpoint = self.endpoint.as_str(),
+                message = message.as_str(),
+                "unable to export spans to OpenTelemetry collector"
+            );
+        }
+    }
+}
+
+fn string_attribute(key: &str, value: &str) -> serde_json::Value {
+    json!({ "key": key, "value": { "stringValue": value } })
+}
+
+/// The nanoseconds since the unix epoch, as a string, since OTLP encodes 64 bit integers as
+/// strings in JSON.
+fn unix_nanos(time: SystemTime) -> String {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos()
+        .to_string()
+}
+
+/// A random 64 bit id, as 16 hexadecimal digits. The hasher of the standard library is seeded
+/// with random keys, which is random enough to tell traces apart.
+fn random_id() -> String {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u128(
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos(),
+    );
+
+    format!("{:016x}", hasher.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::event::{CheckToolchain, Compatibility, TerminateWithFailure};
+    use crate::semver;
+    use crate::{CargoMSRVError, Event};
+
+    fn toolchain(minor: u64) -> OwnedToolchainSpec {
+        OwnedToolchainSpec::new(
+            &semver::Version::new(1, minor, 0),
+            "x86_64-unknown-linux-gnu",
+        )
+    }
+
+    fn check(handler: &OtelHandler, minor: u64, compatible: bool) {
+        let event = Event::from(CheckToolchain::new(toolchain(minor)));
+        let id = ScopeId::next();
+
+        handler.handle(event.start_of_scope(id));
+
+        let compatibility = if compatible {
+            Compatibility::compatible(toolchain(minor))
+        } else {
+            Compatibility::incompatible(toolchain(minor), None)
+        };
+        handler.handle(compatibility.into());
+
+        handler.handle(event.end_of_scope(id, Duration::from_millis(1500)));
+    }
+
+    fn spans(handler: &OtelHandler) -> Vec<serde_json::Value> {
+        let state = handler.state.lock().unwrap();
+        let request = handler.export_request(&state).unwrap();
+
+        request["resourceSpans"][0]["scopeSpans"][0]["spans"]
+            .as_array()
+            .unwrap()
+            .clone()
+    }
+
+    fn attribute<'s>(span: &'s serde_json::Value, key: &str) -> &'s serde_json::Value {
+        let attribute = span["attributes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|attribute| attribute["key"] == key)
+            .unwrap();
+
+        &attribute["value"]
+    }
+
+    #[test]
+    fn checks_are_children_of_the_run() {
+        let handler = OtelHandler::new("http://localhost:4318");
+
+        check(&handler, 56, false);
+        check(&handler, 60, true);
+
+        let spans = spans(&handler);
+
+        assert_eq!(spans.len(), 3);
+        assert_eq!(spans[0]["name"], "cargo-msrv");
+        assert_eq!(spans[0]["status"]["code"], STATUS_OK);
+
+        for span in &spans[1..] {
+            assert_eq!(span["traceId"], spans[0]["traceId"]);
+            assert_eq!(span["parentSpanId"], spans[0]["spanId"]);
+            assert_eq!(span["name"], "check_toolchain");
+        }
+
+        assert_eq!(
+            attribute(&spans[1], "rust.version")["stringValue"],
+            "1.56.0"
+        );
+        assert_eq!(
+            attribute(&spans[1], "cargo_msrv.outcome")["stringValue"],
+            "incompatible"
+        );
+        assert_eq!(spans[1]["status"]["code"], STATUS_ERROR);
+        assert_eq!(
+            attribute(&spans[2], "cargo_msrv.outcome")["stringValue"],
+            "compatible"
+        );
+        assert_eq!(
+            attribute(&spans[2], "cargo_msrv.duration")["doubleValue"],
+            1.5
+        );
+    }
+
+    #[test]
+    fn span_covers_the_elapsed_duration() {
+        let handler = OtelHandler::new("http://localhost:4318");
+
+        check(&handler, 56, true);
+
+        let spans = spans(&handler);
+        let start = spans[1]["startTimeUnixNano"].as_str().unwrap();
+        let end = spans[1]["endTimeUnixNano"].as_str().unwrap();
+
+        assert_eq!(
+            end.parse::<u128>().unwrap() - start.parse::<u128>().unwrap(),
+            1_500_000_000
+        );
+    }
+
+    #[test]
+    fn failed_run() {
+        let handler = OtelHandler::new("http://localhost:4318");
+
+        handler.handle(TerminateWithFailure::new(CargoMSRVError::Storyteller).into());
+
+        let spans = spans(&handler);
+
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0]["status"]["code"], STATUS_ERROR);
+    }
+
+    #[test]
+    fn nothing_to_export() {
+        let handler = OtelHandler::new("http://localhost:4318");
+        let state = handler.state.lock().unwrap();
+
+        assert!(handler.export_request(&state).is_none());
+    }
+
+    #[yare::parameterized(
+        base = { "http://localhost:4318", "http://localhost:4318/v1/traces" },
+        trailing_slash = { "http://localhost:4318/", "http://localhost:4318/v1/traces" },
+        traces_path = { "http://localhost:4318/v1/traces", "http://localhost:4318/v1/traces" },
+    )]
+    fn traces_url(endpoint: &str, expected: &str) {
+        assert_eq!(OtelHandler::new(endpoint).traces_url(), expected);
+    }
+
+    #[test]
+    fn distinct_ids() {
+        assert_ne!(random_id(), random_id());
+    }
+}
@@ -0,0 +1,170 @@
+use crate::reporter::handler::json_handler::SendWriter;
+use crate::reporter::schema::Validator;
+use std::io;
+use std::io::{Stderr, Write};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use storyteller::EventHandler;
+
+/// Validates each event against the schema of the events, as enabled with `--validate-output`,
+/// to catch events whose serialization doesn't match the schema.
+///
+/// The violations are written to the writer, and the events which violate the schema are
+/// counted, so the run can fail once all events have been handled. An event which can't be
+/// serialized, or whose violations can't be written, is counted as well.
+pub struct SchemaValidationHandler<W: SendWriter> {
+    validator: Validator,
+    writer: Arc<Mutex<W>>,
+    invalid_events: InvalidEvents,
+}
+
+impl<W: SendWriter> SchemaValidationHandler<W> {
+    fn new(writer: W) -> Self {
+        Self {
+            validator: Validator::for_events(),
+            writer: Arc::new(Mutex::new(writer)),
+            invalid_events: InvalidEvents::default(),
+        }
+    }
+
+    /// The count of the events which violated the schema, which keeps being updated while the
+    /// handler handles events.
+    pub fn invalid_events(&self) -> InvalidEvents {
+        self.invalid_events.clone()
+    }
+
+    /// Validate the event, and write its violations, if any. Returns whether the event matches
+    /// the schema.
+    fn validate(&self, event: &super::Event) -> io::Result<bool> {
+        let serialized = serde_json::to_value(event)?;
+        let violations = self.validator.validate(&serialized);
+
+        if violations.is_empty() {
+            return Ok(true);
+        }
+
+        let kind = serialized
+            .get("type")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or("unknown");
+
+        let mut w = self.writer.lock().unwrap_or_else(PoisonError::into_inner);
+
+        for violation in violations {
+            tracing::error!(event = kind, %violation, "event does not match the schema");
+
+            writeln!(
+                &mut w,
+                "error: the '{}' event does not match the schema: {}",
+                kind, violation
+            )?;
+        }
+
+        Ok(false)
+    }
+}
+
+impl SchemaValidationHandler<Stderr> {
+    pub fn stderr() -> Self {
+        Self::new(io::stderr())
+    }
+}
+
+impl<W: SendWriter> EventHandler for SchemaValidationHandler<W> {
+    type Event = super::Event;
+
+    fn handle(&self, event: Self::Event) {
+        let is_valid = self.validate(&event).unwrap_or_else(|error| {
+            tracing::error!(%error, "unable to validate event");
+            false
+        });
+
+        if !is_valid {
+            self.invalid_events.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+}
+
+/// The amount of events which did not match the schema, shared with the handler which validates
+/// them.
+#[derive(Clone, Debug, Default)]
+pub struct InvalidEvents(Arc<AtomicUsize>);
+
+impl InvalidEvents {
+    pub fn count(&self) -> usize {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::event::{Progress, TerminateWithFailure};
+    use crate::{CargoMSRVError, Event};
+
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl SendWriter for SharedBuffer {}
+
+    #[test]
+    fn valid_events_are_not_reported() {
+        let buffer = SharedBuffer::default();
+        let handler = SchemaValidationHandler::new(buffer.clone());
+        let invalid_events = handler.invalid_events();
+
+        let events: Vec<Event> = vec![
+            Progress::new(1, 10, 1).into(),
+            TerminateWithFailure::new(CargoMSRVError::UnableToFindAnyGoodVersion {
+                command: "cargo check".to_string(),
+            })
+            .into(),
+        ];
+
+        for event in events {
+            handler.handle(event);
+        }
+
+        assert_eq!(invalid_events.count(), 0);
+        assert!(buffer.0.lock().unwrap().is_empty());
+    }
+
+    /// A writer which fails each write.
+    struct Closed;
+
+    impl Write for Closed {
+        fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::BrokenPipe, "closed"))
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl SendWriter for Closed {}
+
+    #[test]
+    fn unwritable_violation_is_counted() {
+        let handler = SchemaValidationHandler {
+            validator: Validator::new(serde_json::json!(false)),
+            writer: Arc::new(Mutex::new(Closed)),
+            invalid_events: InvalidEvents::default(),
+        };
+        let invalid_events = handler.invalid_events();
+
+        handler.handle(Progress::new(1, 10, 1).into());
+
+        assert_eq!(invalid_events.count(), 1);
+    }
+}
@@ -0,0 +1,104 @@
+use crate::config::WebhookOptions;
+use crate::reporter::event::Message;
+use std::time::Duration;
+use storyteller::EventHandler;
+
+/// Sends the final result, and optionally the result of each toolchain check, to an HTTP endpoint.
+///
+/// Each notification is POSTed as a single JSON message, in the same format as the messages of
+/// `--output-format json`.
+pub struct WebhookHandler {
+    options: WebhookOptions,
+}
+
+impl WebhookHandler {
+    const TIMEOUT: Duration = Duration::from_secs(30);
+
+    pub fn new(options: WebhookOptions) -> Self {
+        Self { options }
+    }
+
+    fn should_notify(&self, message: &Message) -> bool {
+        match message {
+            Message::MsrvResult(_) | Message::InconclusiveResult(_) => true,
+            Message::Compatibility(_) => self.options.include_compatibility(),
+            _ => false,
+        }
+    }
+
+    fn notify(&self, body: String) -> Result<(), String> {
+        let response = attohttpc::post(self.options.url())
+            .header("Content-Type", "application/json")
+            .header("User-Agent", "cargo-msrv")
+            .timeout(Self::TIMEOUT)
+            .bytes(body.into_bytes())
+            .send()
+            .map_err(|err| err.to_string())?;
+
+        if response.is_success() {
+            Ok(())
+        } else {
+            Err(format!(
+                "unexpected status code {}",
+                response.status().as_u16()
+            ))
+        }
+    }
+}
+
+impl EventHandler for WebhookHandler {
+    type Event = super::Event;
+
+    fn handle(&self, event: Self::Event) {
+        if !self.should_notify(event.message()) {
+            return;
+        }
+
+        // A webhook which can't be reached should not fail the run, since the result is
+        // still reported through the regular user output
+        let result = serde_json::to_string(&event)
+            .map_err(|err| err.to_string())
+            .and_then(|body| self.notify(body));
+
+        if let Err(message) = result {
+            tracing::warn!(
+                url = self.options.url(),
+                message = message.as_str(),
+                "unable to notify webhook"
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::event::{Compatibility, Meta};
+    use crate::semver;
+    use crate::toolchain::OwnedToolchainSpec;
+
+    fn compatibility() -> Message {
+        let toolchain = OwnedToolchainSpec::new(&semver::Version::new(1, 56, 0), "x");
+        Message::Compatibility(Compatibility::compatible(toolchain))
+    }
+
+    #[yare::parameterized(
+        without_compatibility = { false, false },
+        with_compatibility = { true, true },
+    )]
+    fn notify_compatibility(include_compatibility: bool, expected: bool) {
+        let handler = WebhookHandler::new(WebhookOptions::new(
+            "http://localhost",
+            include_compatibility,
+        ));
+
+        assert_eq!(handler.should_notify(&compatibility()), expected);
+    }
+
+    #[test]
+    fn ignores_other_messages() {
+        let handler = WebhookHandler::new(WebhookOptions::new("http://localhost", true));
+
+        assert!(!handler.should_notify(&Message::Meta(Meta::default())));
+    }
+}
@@ -0,0 +1,169 @@
+//! The JSON schema of the events which are written with `--output-format json`.
+//!
+//! The schema is derived from the types of the events, so it follows their serialization. It is
+//! printed by `cargo msrv schema`, and each event is validated against it with
+//! `--validate-output`.
+
+use schemars::schema::RootSchema;
+use serde_json::Value;
+
+use crate::reporter::event::Event;
+
+pub use validator::{Validator, Violation};
+
+mod validator;
+
+/// The schema of the events: the fields shared by all events, like `scope`, and one of the
+/// messages, which is selected by its `type`.
+pub fn event_schema() -> RootSchema {
+    schemars::schema_for!(Event)
+}
+
+/// The schema of each type of message, together with the `type` of the message, e.g.
+/// `msrv_result`. Like the schema of the events, each schema includes the fields which are shared
+/// by all events.
+pub fn message_schemas() -> Vec<(String, Value)> {
+    let schema = serde_json::to_value(event_schema()).expect("the schema is serializable");
+
+    let messages = schema
+        .get("oneOf")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+
+    messages
+        .into_iter()
+        .filter_map(|message| {
+            let kind = message_type(&message)?.to_string();
+
+            let mut schema = schema.clone();
+            schema["title"] = Value::String(kind.clone());
+            schema["oneOf"] = Value::Array(vec![message]);
+
+            Some((kind, schema))
+        })
+        .collect()
+}
+
+/// The `type` of the messages which match the given schema, which is the only value allowed for
+/// the `type` property.
+fn message_type(message: &Value) -> Option<&str> {
+    let kind = message.pointer("/properties/type")?;
+
+    kind.get("const")
+        .or_else(|| kind.pointer("/enum/0"))
+        .and_then(Value::as_str)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::value_source::ValueSource;
+    use crate::config::{Action, ConfigBuilder, ReleaseSource, VersionResolution};
+    use crate::manifest::bare_version::BareVersion;
+    use crate::reporter::event::{
+        ActionMessage, CheckOutput, CheckToolchain, Compatibility, ComponentUnavailable,
+        ConfigOption, ConfigValue, FetchIndex, InconclusiveResult, LoweringStep, MsrvLowering,
+        MsrvResult, OutputStream, Progress, ProgressUnit, ResolvedConfig, ScopeId, Serving,
+        TerminateWithFailure, UnitGraphReuse, VersionResolved,
+    };
+    use crate::toolchain::OwnedToolchainSpec;
+    use crate::{semver, CargoMSRVError};
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    fn toolchain() -> OwnedToolchainSpec {
+        OwnedToolchainSpec::new(&semver::Version::new(1, 56, 0), "x86_64-unknown-linux-gnu")
+    }
+
+    fn validate(event: Event) -> Vec<Violation> {
+        let validator = Validator::for_events();
+        let serialized = serde_json::to_value(&event).unwrap();
+
+        validator.validate(&serialized)
+    }
+
+    #[yare::parameterized(
+        action = { ActionMessage::new(Action::Find).into() },
+        fetch_index = { FetchIndex::new(ReleaseSource::RustChangelog).into() },
+        check_toolchain = { CheckToolchain::new(toolchain()).into() },
+        check_output = { CheckOutput::new(toolchain(), OutputStream::Stderr, "   Compiling libc v0.2.126").into() },
+        compatible = { Compatibility::compatible(toolchain()).into() },
+        incompatible = { Compatibility::incompatible(toolchain(), Some("error[E0658]".to_string())).into() },
+        component_unavailable = { ComponentUnavailable::new(toolchain(), vec!["clippy".to_string()]).into() },
+        progress = { Progress::new(1, 10, 1).into() },
+        progress_of_unit = { Progress::new(1, 10, 1).with_unit(Some(ProgressUnit::new("core", 1, 2))).into() },
+        unit_graph_reuse = { UnitGraphReuse::unavailable(toolchain(), "error: unknown flag".to_string()).into() },
+        version_resolved = { VersionResolved::new(BareVersion::TwoComponents(1, 64), semver::Version::new(1, 65, 0), VersionResolution::NearestNewer).into() },
+        serving = { Serving::new("127.0.0.1:7430", 72).into() },
+        msrv_lowering = { MsrvLowering::new(BareVersion::TwoComponents(1, 60), vec![LoweringStep::new(semver::Version::new(1, 59, 0), vec![])]).into() },
+        inconclusive_result = { InconclusiveResult::new(2, Some(semver::Version::new(1, 58, 0)), None).into() },
+        terminate_with_failure = { TerminateWithFailure::new(CargoMSRVError::UnableToFindAnyGoodVersion { command: "cargo check".to_string() }).into() },
+    )]
+    fn valid_event(event: Event) {
+        assert_eq!(validate(event), vec![]);
+    }
+
+    #[yare::parameterized(
+        determined = { Some(semver::Version::new(1, 56, 0)) },
+        undetermined = { None },
+    )]
+    fn valid_msrv_result(msrv: Option<semver::Version>) {
+        let config = ConfigBuilder::new(Action::Find, "x86_64-unknown-linux-gnu").build();
+        let min = BareVersion::TwoComponents(1, 40);
+        let max = BareVersion::ThreeComponents(1, 60, 0);
+
+        let result = match msrv {
+            Some(version) => MsrvResult::new_msrv(version, &config, min, max),
+            None => MsrvResult::none(&config, min, max),
+        };
+
+        assert_eq!(validate(result.into()), vec![]);
+    }
+
+    #[test]
+    fn valid_resolved_config() {
+        let option =
+            |key: &str, value: ConfigValue| ConfigOption::new(key, value, ValueSource::Default);
+        let config = ResolvedConfig::new(
+            vec![
+                option("target", ConfigValue::String("x".to_string())),
+                option("min", ConfigValue::Unset),
+                option("features", ConfigValue::List(vec!["a".to_string()])),
+            ],
+            vec![PathBuf::from(".cargo-msrv.toml")],
+        );
+
+        assert_eq!(validate(config.into()), vec![]);
+    }
+
+    #[test]
+    fn valid_end_of_scope() {
+        let event = Event::from(FetchIndex::new(ReleaseSource::RustChangelog))
+            .end_of_scope(ScopeId::next(), Duration::from_millis(1500));
+
+        assert_eq!(validate(event), vec![]);
+    }
+
+    #[test]
+    fn schema_of_each_message() {
+        let schemas = message_schemas();
+        let kinds = schemas
+            .iter()
+            .map(|(kind, _)| kind.as_str())
+            .collect::<Vec<_>>();
+
+        assert!(kinds.contains(&"action"));
+        assert!(kinds.contains(&"msrv_result"));
+        assert!(kinds.contains(&"terminate_with_failure"));
+
+        let (_, progress) = schemas.iter().find(|(kind, _)| kind == "progress").unwrap();
+        let validator = Validator::new(progress.clone());
+
+        let event = serde_json::to_value(Event::from(Progress::new(1, 10, 1))).unwrap();
+        assert_eq!(validator.validate(&event), vec![]);
+
+        let other = serde_json::to_value(Event::from(CheckToolchain::new(toolchain()))).unwrap();
+        assert!(!validator.validate(&other).is_empty());
+    }
+}
@@ -0,0 +1,434 @@
+use serde_json::{Map, Value};
+use std::fmt;
+
+/// Validates values against a JSON schema, like the schema of the events.
+///
+/// Only the keywords which occur in the schemas derived for the events are validated: `$ref`,
+/// `type`, `enum`, `const`, `minimum`, `maximum`, `properties`, `required`,
+/// `additionalProperties`, `items`, `allOf`, `anyOf` and `oneOf`. Other keywords, like `pattern`
+/// and `format`, are ignored.
+#[derive(Clone, Debug)]
+pub struct Validator {
+    root: Value,
+}
+
+impl Validator {
+    pub fn new(root: Value) -> Self {
+        Self { root }
+    }
+
+    /// A validator for the schema of the events.
+    pub fn for_events() -> Self {
+        let schema =
+            serde_json::to_value(super::event_schema()).expect("the schema is serializable");
+
+        Self::new(schema)
+    }
+
+    /// The ways in which the value doesn't match the schema, if any.
+    pub fn validate(&self, value: &Value) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        self.check(&self.root, value, "", &mut violations);
+        violations
+    }
+
+    fn check(&self, schema: &Value, value: &Value, path: &str, violations: &mut Vec<Violation>) {
+        let schema = match schema {
+            Value::Object(schema) => schema,
+            Value::Bool(false) => {
+                violations.push(Violation::new(path, "no value is allowed"));
+                return;
+            }
+            _ => return,
+        };
+
+        if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+            match self.resolve(reference) {
+                Some(referenced) => self.check(referenced, value, path, violations),
+                None => violations.push(Violation::new(
+                    path,
+                    format!(
+                        "the schema refers to the unknown definition '{}'",
+                        reference
+                    ),
+                )),
+            }
+        }
+
+        if let Some(expected) = schema.get("type") {
+            if !is_of_type(expected, value) {
+                violations.push(Violation::new(
+                    path,
+                    format!("expected {}, found {}", describe(expected), type_of(value)),
+                ));
+                // The other keywords would only repeat the mismatch
+                return;
+            }
+        }
+
+        if let Some(allowed) = schema.get("enum").and_then(Value::as_array) {
+            if !allowed.contains(value) {
+                let allowed = allowed.iter().map(Value::to_string).collect::<Vec<_>>();
+                violations.push(Violation::new(
+                    path,
+                    format!("expected one of {}, found {}", allowed.join(", "), value),
+                ));
+            }
+        }
+
+        if let Some(constant) = schema.get("const") {
+            if constant != value {
+                violations.push(Violation::new(
+                    path,
+                    format!("expected {}, found {}", constant, value),
+                ));
+            }
+        }
+
+        if let Some(number) = value.as_f64() {
+            check_bounds(schema, number, path, violations);
+        }
+
+        match value {
+            Value::Object(object) => self.check_object(schema, object, path, violations),
+            Value::Array(items) => self.check_items(schema, items, path, violations),
+            _ => {}
+        }
+
+        self.check_subschemas(schema, value, path, violations);
+    }
+
+    fn check_object(
+        &self,
+        schema: &Map<String, Value>,
+        object: &Map<String, Value>,
+        path: &str,
+        violations: &mut Vec<Violation>,
+    ) {
+        let required = schema
+            .get("required")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str);
+
+        for property in required {
+            if !object.contains_key(property) {
+                violations.push(Violation::new(
+                    path,
+                    format!("the required property '{}' is missing", property),
+                ));
+            }
+        }
+
+        let properties = schema.get("properties").and_then(Value::as_object);
+        let additional = schema.get("additionalProperties");
+
+        for (key, value) in object {
+            let property_path = format!("{}/{}", path, escape(key));
+
+            match (properties.and_then(|p| p.get(key)), additional) {
+                (Some(property), _) => self.check(property, value, &property_path, violations),
+                (None, Some(Value::Bool(false))) => violations.push(Violation::new(
+                    path,
+                    format!("the property '{}' is not allowed", key),
+                )),
+                (None, Some(additional)) => {
+                    self.check(additional, value, &property_path, violations)
+                }
+                (None, None) => {}
+            }
+        }
+    }
+
+    fn check_items(
+        &self,
+        schema: &Map<String, Value>,
+        items: &[Value],
+        path: &str,
+        violations: &mut Vec<Violation>,
+    ) {
+        let item_path = |index: usize| format!("{}/{}", path, index);
+
+        match schema.get("items") {
+            // A tuple, with a schema for each position
+            Some(Value::Array(schemas)) => {
+                for (index, (schema, item)) in schemas.iter().zip(items).enumerate() {
+                    self.check(schema, item, &item_path(index), violations);
+                }
+            }
+            Some(schema) => {
+                for (index, item) in items.iter().enumerate() {
+                    self.check(schema, item, &item_path(index), violations);
+                }
+            }
+            None => {}
+        }
+    }
+
+    fn check_subschemas(
+        &self,
+        schema: &Map<String, Value>,
+        value: &Value,
+        path: &str,
+        violations: &mut Vec<Violation>,
+    ) {
+        let subschemas = |keyword: &str| {
+            schema
+                .get(keyword)
+                .and_then(Value::as_array)
+                .map(|subschemas| self.check_each(subschemas, value, path))
+        };
+
+        if let Some(results) = subschemas("allOf") {
+            violations.extend(results.into_iter().flatten());
+        }
+
+        if let Some(results) = subschemas("anyOf") {
+            if !results.iter().any(Vec::is_empty) {
+                violations.extend(closest(results));
+            }
+        }
+
+        if let Some(results) = subschemas("oneOf") {
+            match results.iter().filter(|result| result.is_empty()).count() {
+                0 => violations.extend(closest(results)),
+                1 => {}
+                matches => violations.push(Violation::new(
+                    path,
+                    format!("matches {} alternatives, instead of exactly one", matches),
+                )),
+            }
+        }
+    }
+
+    /// The violations of the value, for each of the given schemas.
+    fn check_each(&self, schemas: &[Value], value: &Value, path: &str) -> Vec<Vec<Violation>> {
+        schemas
+            .iter()
+            .map(|schema| {
+                let mut violations = Vec::new();
+                self.check(schema, value, path, &mut violations);
+                violations
+            })
+            .collect()
+    }
+
+    /// Resolve a reference within the schema, like `#/definitions/BareVersion`.
+    fn resolve(&self, reference: &str) -> Option<&Value> {
+        reference
+            .strip_prefix('#')
+            .and_then(|pointer| self.root.pointer(pointer))
+    }
+}
+
+fn check_bounds(
+    schema: &Map<String, Value>,
+    number: f64,
+    path: &str,
+    violations: &mut Vec<Violation>,
+) {
+    if let Some(minimum) = schema.get("minimum").and_then(Value::as_f64) {
+        if number < minimum {
+            violations.push(Violation::new(
+                path,
+                format!("expected at least {}, found {}", minimum, number),
+            ));
+        }
+    }
+
+    if let Some(maximum) = schema.get("maximum").and_then(Value::as_f64) {
+        if number > maximum {
+            violations.push(Violation::new(
+                path,
+                format!("expected at most {}, found {}", maximum, number),
+            ));
+        }
+    }
+}
+
+/// When a value matches none of the alternatives, the violations of the alternative which it
+/// matches most closely are the most helpful.
+fn closest(results: Vec<Vec<Violation>>) -> Vec<Violation> {
+    results.into_iter().min_by_key(Vec::len).unwrap_or_default()
+}
+
+fn is_of_type(expected: &Value, value: &Value) -> bool {
+    let is = |name: &str| match name {
+        "null" => value.is_null(),
+        "boolean" => value.is_boolean(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "number" => value.is_number(),
+        "string" => value.is_string(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        _ => true,
+    };
+
+    match expected {
+        Value::String(name) => is(name.as_str()),
+        Value::Array(names) => names.iter().filter_map(Value::as_str).any(is),
+        _ => true,
+    }
+}
+
+fn describe(expected: &Value) -> String {
+    match expected {
+        Value::Array(names) => names
+            .iter()
+            .filter_map(Value::as_str)
+            .collect::<Vec<_>>()
+            .join(" or "),
+        Value::String(name) => name.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn type_of(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(number) if number.is_f64() => "number",
+        Value::Number(_) => "integer",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// Escape a property name, to be used as a part of a JSON pointer.
+fn escape(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+/// A way in which a value doesn't match a schema.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Violation {
+    /// The JSON pointer to the part of the value which doesn't match, e.g. `/toolchain/version`
+    path: String,
+    reason: String,
+}
+
+impl Violation {
+    fn new(path: &str, reason: impl Into<String>) -> Self {
+        Self {
+            path: path.to_string(),
+            reason: reason.into(),
+        }
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn reason(&self) -> &str {
+        &self.reason
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.reason)
+        } else {
+            write!(f, "{}: {}", self.path, self.reason)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn validator() -> Validator {
+        Validator::new(json!({
+            "type": "object",
+            "required": ["type", "toolchain"],
+            "properties": {
+                "type": { "type": "string", "enum": ["check_toolchain"] },
+                "toolchain": { "$ref": "#/definitions/Toolchain" },
+                "attempt": { "type": "integer", "minimum": 0.0 },
+                "scope": { "type": ["string", "null"] }
+            },
+            "oneOf": [
+                { "required": ["compatible"], "properties": { "compatible": { "const": true } } },
+                { "required": ["error"], "properties": { "error": { "type": "string" } } }
+            ],
+            "definitions": {
+                "Toolchain": {
+                    "type": "object",
+                    "required": ["version"],
+                    "properties": {
+                        "version": { "type": "string" },
+                        "components": { "type": "array", "items": { "type": "string" } }
+                    },
+                    "additionalProperties": false
+                }
+            }
+        }))
+    }
+
+    fn violations(value: Value) -> Vec<String> {
+        validator()
+            .validate(&value)
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn valid() {
+        let value = json!({
+            "type": "check_toolchain",
+            "toolchain": { "version": "1.56.0", "components": ["rustc"] },
+            "attempt": 1,
+            "scope": null,
+            "compatible": true
+        });
+
+        assert!(violations(value).is_empty());
+    }
+
+    #[yare::parameterized(
+        missing_property = {
+            json!({ "type": "check_toolchain", "compatible": true }),
+            "the required property 'toolchain' is missing"
+        },
+        unknown_message = {
+            json!({ "type": "progress", "toolchain": { "version": "1.56.0" }, "compatible": true }),
+            "/type: expected one of \"check_toolchain\", found \"progress\""
+        },
+        referenced_type = {
+            json!({ "type": "check_toolchain", "toolchain": { "version": 156 }, "compatible": true }),
+            "/toolchain/version: expected string, found integer"
+        },
+        additional_property = {
+            json!({ "type": "check_toolchain", "toolchain": { "version": "1.56.0", "host": "x" }, "compatible": true }),
+            "/toolchain: the property 'host' is not allowed"
+        },
+        item = {
+            json!({ "type": "check_toolchain", "toolchain": { "version": "1.56.0", "components": [1] }, "compatible": true }),
+            "/toolchain/components/0: expected string, found integer"
+        },
+        minimum = {
+            json!({ "type": "check_toolchain", "toolchain": { "version": "1.56.0" }, "attempt": -1, "compatible": true }),
+            "/attempt: expected at least 0, found -1"
+        },
+        nullable = {
+            json!({ "type": "check_toolchain", "toolchain": { "version": "1.56.0" }, "scope": 1, "compatible": true }),
+            "/scope: expected string or null, found integer"
+        },
+        no_alternative = {
+            json!({ "type": "check_toolchain", "toolchain": { "version": "1.56.0" }, "compatible": false }),
+            "/compatible: expected true, found false"
+        },
+        both_alternatives = {
+            json!({ "type": "check_toolchain", "toolchain": { "version": "1.56.0" }, "compatible": true, "error": "" }),
+            "matches 2 alternatives, instead of exactly one"
+        },
+    )]
+    fn invalid(value: Value, expected: &str) {
+        assert_eq!(violations(value), vec![expected.to_string()]);
+    }
+}
@@ -0,0 +1,273 @@
+//! Statistics of a run, which are collected from the events reported during the run, and reported
+//! as a [`RunSummary`] once the run is over.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Instant;
+
+use storyteller::ReporterError;
+
+use crate::reporter::event::{CacheCounts, Durations, Message, RunSummary, ToolchainCounts};
+use crate::reporter::Reporter;
+use crate::toolchain::OwnedToolchainSpec;
+use crate::Event;
+
+/// Passes each event on to the wrapped reporter, after recording it in the statistics of the run.
+pub(crate) struct StatisticsReporter<'reporter, R: Reporter> {
+    reporter: &'reporter R,
+    started: Instant,
+    statistics: Mutex<Statistics>,
+}
+
+impl<'reporter, R: Reporter> StatisticsReporter<'reporter, R> {
+    pub(crate) fn new(reporter: &'reporter R) -> Self {
+        Self {
+            reporter,
+            started: Instant::now(),
+            statistics: Mutex::new(Statistics::default()),
+        }
+    }
+
+    /// The summary of the events reported so far. The disk space used by the toolchains which were
+    /// installed is measured now, in the toolchains directory of rustup.
+    pub(crate) fn summary(&self) -> RunSummary {
+        let statistics = self.statistics.lock().unwrap();
+
+        let disk_used = rustup_home().map_or(0, |home| {
+            statistics
+                .installed
+                .iter()
+                .map(|toolchain| dir_size(&home.join("toolchains").join(toolchain.spec())))
+                .sum()
+        });
+
+        let mut toolchains = statistics.toolchains;
+        toolchains.skipped = toolchains.considered.saturating_sub(toolchains.checked);
+
+        let mut durations = statistics.durations;
+        durations.total = self.started.elapsed();
+
+        RunSummary::new(
+            toolchains,
+            statistics.cache,
+            durations,
+            statistics.downloaded_bytes,
+            disk_used,
+        )
+    }
+}
+
+impl<'reporter, R: Reporter> storyteller::Reporter for StatisticsReporter<'reporter, R> {
+    type Event = Event;
+    type Err = ReporterError<Event>;
+
+    fn report_event(&self, event: impl Into<Self::Event>) -> Result<(), Self::Err> {
+        let event = event.into();
+        self.statistics.lock().unwrap().record(&event);
+        self.reporter.report_event(event)
+    }
+
+    // The wrapped reporter is disconnected by its owner
+    fn disconnect(self) -> Result<(), Self::Err> {
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default)]
+struct Statistics {
+    toolchains: ToolchainCounts,
+    cache: CacheCounts,
+    durations: Durations,
+    downloaded_bytes: u64,
+    /// The bytes downloaded so far by the fetch of the index, per release source
+    fetching: HashMap<String, u64>,
+    /// Whether a search for the MSRV is running
+    searching: bool,
+    installed: Vec<OwnedToolchainSpec>,
+}
+
+impl Statistics {
+    fn record(&mut self, event: &Event) {
+        let elapsed = event.elapsed().unwrap_or_default();
+
+        match event.message() {
+            Message::FetchIndexProgress(it) => {
+                self.fetching.insert(it.source().to_string(), it.bytes());
+            }
+            Message::FetchIndex(_) if !event.is_scope_start() => {
+                self.downloaded_bytes += self.fetching.drain().map(|(_, bytes)| bytes).sum::<u64>();
+                self.durations.fetch_index += elapsed;
+            }
+            Message::SetupToolchain(it) if !event.is_scope_start() => {
+                self.toolchains.installed += 1;
+                self.durations.setup_toolchains += elapsed;
+                self.installed.push(it.toolchain().clone());
+            }
+            Message::FindMsrv(it) if event.is_scope_start() => {
+                self.searching = true;
+                self.toolchains.considered += it.search_space() as u64;
+            }
+            Message::FindMsrv(_) => {
+                self.searching = false;
+                self.durations.search += elapsed;
+            }
+            Message::CheckToolchain(_) if event.is_scope_start() => {
+                self.toolchains.checked += 1;
+
+                if !self.searching {
+                    self.toolchains.considered += 1;
+                }
+            }
+            Message::WrapperCacheStats(it) => {
+                self.cache.hits += it.hits();
+                self.cache.misses += it.misses();
+            }
+            _ => {}
+        }
+    }
+}
+
+fn rustup_home() -> Option<PathBuf> {
+    std::env::var_os("RUSTUP_HOME")
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|home| home.join(".rustup")))
+}
+
+/// The size of the files in the given directory and its subdirectories. Symlinks are not
+/// followed, and entries which can't be read are skipped.
+fn dir_size(dir: &Path) -> u64 {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return 0,
+    };
+
+    entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let metadata = std::fs::symlink_metadata(entry.path()).ok()?;
+
+            if metadata.is_dir() {
+                Some(dir_size(&entry.path()))
+            } else {
+                Some(metadata.len())
+            }
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SearchMethod;
+    use crate::reporter::event::{
+        CheckToolchain, FetchIndex, FetchIndexProgress, FindMsrv, ScopeId, SetupToolchain,
+        WrapperCacheStats,
+    };
+    use crate::reporter::FakeTestReporter;
+    use crate::semver;
+    use std::time::Duration;
+    use storyteller::Reporter as _;
+    use test_dir::{DirBuilder, TestDir};
+
+    fn scoped(reporter: &impl Reporter, event: impl Into<Event>, elapsed: Duration) {
+        let event = event.into();
+        let id = ScopeId::next();
+
+        reporter.report_event(event.start_of_scope(id)).unwrap();
+        reporter
+            .report_event(event.end_of_scope(id, elapsed))
+            .unwrap();
+    }
+
+    fn toolchain(minor: u64) -> OwnedToolchainSpec {
+        OwnedToolchainSpec::new(
+            &semver::Version::new(1, minor, 0),
+            "x86_64-unknown-linux-gnu",
+        )
+    }
+
+    #[test]
+    fn summarizes_reported_events() {
+        let fake = FakeTestReporter::default();
+        let reporter = StatisticsReporter::new(&fake);
+
+        reporter
+            .report_event(FetchIndexProgress::new("rust-dist", 1, 10, 0).with_bytes(100))
+            .unwrap();
+        reporter
+            .report_event(FetchIndexProgress::new("rust-dist", 2, 20, 0).with_bytes(250))
+            .unwrap();
+        scoped(
+            &reporter,
+            FetchIndex::new(Default::default()),
+            Duration::from_secs(1),
+        );
+
+        let search = FindMsrv::new(SearchMethod::Bisect).with_search_space(10);
+        let id = ScopeId::next();
+        reporter
+            .report_event(Event::from(search.clone()).start_of_scope(id))
+            .unwrap();
+
+        scoped(
+            &reporter,
+            SetupToolchain::new(toolchain(56)),
+            Duration::from_secs(2),
+        );
+        reporter
+            .report_event(SetupToolchain::new(toolchain(57)).with_already_installed(true))
+            .unwrap();
+
+        for minor in [56, 57, 58] {
+            scoped(
+                &reporter,
+                CheckToolchain::new(toolchain(minor)),
+                Duration::from_secs(1),
+            );
+        }
+
+        reporter
+            .report_event(WrapperCacheStats::new(toolchain(56), "sccache", 7, 3))
+            .unwrap();
+        reporter
+            .report_event(Event::from(search).end_of_scope(id, Duration::from_secs(5)))
+            .unwrap();
+
+        // Not part of the search
+        scoped(
+            &reporter,
+            CheckToolchain::new(toolchain(60)),
+            Duration::from_secs(1),
+        );
+
+        let summary = reporter.summary();
+
+        assert_eq!(
+            summary.toolchains(),
+            &ToolchainCounts {
+                considered: 11,
+                checked: 4,
+                skipped: 7,
+                installed: 1,
+            }
+        );
+        assert_eq!(summary.cache(), &CacheCounts { hits: 7, misses: 3 });
+        assert_eq!(summary.durations().fetch_index, Duration::from_secs(1));
+        assert_eq!(summary.durations().setup_toolchains, Duration::from_secs(2));
+        assert_eq!(summary.durations().search, Duration::from_secs(5));
+        assert_eq!(summary.downloaded_bytes(), 250);
+    }
+
+    #[test]
+    fn size_of_dir() {
+        let tmp = TestDir::temp();
+        std::fs::create_dir_all(tmp.path("toolchain/bin")).unwrap();
+        std::fs::create_dir_all(tmp.path("toolchain/lib")).unwrap();
+        std::fs::write(tmp.path("toolchain/bin/rustc"), [0; 300]).unwrap();
+        std::fs::write(tmp.path("toolchain/lib/librustc.so"), [0; 200]).unwrap();
+
+        assert_eq!(dir_size(&tmp.path("toolchain")), 500);
+        assert_eq!(dir_size(&tmp.path("missing")), 0);
+    }
+}
@@ -0,0 +1,186 @@
+//! Binaries which are only built when certain features are enabled.
+//!
+//! A binary target may specify `required-features` in the Cargo manifest. How such binaries are
+//! treated by the default check command is configured with the [`BinPolicy`].
+
+use cargo_metadata::{MetadataCommand, Package};
+
+use crate::config::{BinPolicy, Config};
+use crate::prerequisites::installed_tool;
+use crate::TResult;
+
+/// A binary target of the crate.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct BinaryTarget {
+    name: String,
+    required_features: Vec<String>,
+}
+
+impl BinaryTarget {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn required_features(&self) -> &[String] {
+        &self.required_features
+    }
+
+    fn has_required_features(&self) -> bool {
+        !self.required_features.is_empty()
+    }
+}
+
+/// The library and binary targets of a crate, which are checked by the default check command.
+#[derive(Debug, Default)]
+pub struct CrateTargets {
+    has_lib: bool,
+    binaries: Vec<BinaryTarget>,
+}
+
+impl CrateTargets {
+    /// Resolve the targets of the crate with `cargo metadata`.
+    pub fn resolve(config: &Config) -> TResult<Self> {
+        let manifest_path = config.context().manifest_path()?;
+
+        let mut command = MetadataCommand::new();
+
+        // cargo is not on the PATH yet, if it was installed with `--setup`
+        if let Some(cargo) = installed_tool("cargo") {
+            command.cargo_path(cargo);
+        }
+
+        let metadata = command.manifest_path(manifest_path).no_deps().exec()?;
+
+        // A virtual workspace manifest has no root package, and thus no targets of its own
+        Ok(metadata
+            .root_package()
+            .map(Self::from_package)
+            .unwrap_or_default())
+    }
+
+    fn from_package(package: &Package) -> Self {
+        // Library targets are of kind `lib`, `rlib`, `dylib`, `cdylib`, `staticlib` or `proc-macro`
+        let has_lib = package.targets.iter().any(|target| {
+            target
+                .kind
+                .iter()
+                .any(|kind| kind.ends_with("lib") || kind == "proc-macro")
+        });
+
+        let binaries = package
+            .targets
+            .iter()
+            .filter(|target| target.kind.iter().any(|kind| kind == "bin"))
+            .map(|target| BinaryTarget {
+                name: target.name.clone(),
+                required_features: target.required_features.clone(),
+            })
+            .collect();
+
+        Self { has_lib, binaries }
+    }
+
+    /// The binaries which require features to be enabled.
+    pub fn binaries_with_required_features(&self) -> impl Iterator<Item = &BinaryTarget> {
+        self.binaries
+            .iter()
+            .filter(|binary| binary.has_required_features())
+    }
+
+    /// The arguments which are appended to the default check command, according to the given
+    /// policy.
+    ///
+    /// Returns no arguments when none of the binaries require features.
+    pub fn check_args(&self, policy: BinPolicy) -> Vec<String> {
+        if self.binaries_with_required_features().next().is_none() {
+            return Vec::new();
+        }
+
+        match policy {
+            BinPolicy::Ignore => Vec::new(),
+            BinPolicy::EnableFeatures => {
+                let mut features = self
+                    .binaries_with_required_features()
+                    .flat_map(|binary| binary.required_features.iter().cloned())
+                    .collect::<Vec<_>>();
+
+                features.sort();
+                features.dedup();
+
+                vec!["--features".to_string(), features.join(",")]
+            }
+            BinPolicy::Exclude => {
+                // Cargo can't exclude individual binaries, so all other targets are selected instead
+                let lib = self.has_lib.then(|| "--lib".to_string());
+
+                let binaries = self
+                    .binaries
+                    .iter()
+                    .filter(|binary| !binary.has_required_features())
+                    .flat_map(|binary| ["--bin".to_string(), binary.name.clone()]);
+
+                lib.into_iter().chain(binaries).collect()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn binary(name: &str, required_features: &[&str]) -> BinaryTarget {
+        BinaryTarget {
+            name: name.to_string(),
+            required_features: required_features.iter().map(|f| f.to_string()).collect(),
+        }
+    }
+
+    fn targets() -> CrateTargets {
+        CrateTargets {
+            has_lib: true,
+            binaries: vec![
+                binary("plain", &[]),
+                binary("server", &["net", "tls"]),
+                binary("client", &["net"]),
+            ],
+        }
+    }
+
+    #[yare::parameterized(
+        ignore = { BinPolicy::Ignore, vec![] },
+        enable_features = { BinPolicy::EnableFeatures, vec!["--features", "net,tls"] },
+        exclude = { BinPolicy::Exclude, vec!["--lib", "--bin", "plain"] },
+    )]
+    fn check_args(policy: BinPolicy, expected: Vec<&str>) {
+        assert_eq!(targets().check_args(policy), expected);
+    }
+
+    #[yare::parameterized(
+        ignore = { BinPolicy::Ignore },
+        enable_features = { BinPolicy::EnableFeatures },
+        exclude = { BinPolicy::Exclude },
+    )]
+    fn no_args_without_required_features(policy: BinPolicy) {
+        let targets = CrateTargets {
+            has_lib: true,
+            binaries: vec![binary("plain", &[])],
+        };
+
+        assert!(targets.check_args(policy).is_empty());
+    }
+
+    #[test]
+    fn exclude_without_lib() {
+        let targets = CrateTargets {
+            has_lib: false,
+            ..targets()
+        };
+
+        assert_eq!(
+            targets.check_args(BinPolicy::Exclude),
+            vec!["--bin", "plain"]
+        );
+    }
+}
@@ -1,24 +1,38 @@
 use rust_releases::Release;
 
-pub use {bisect::Bisect, linear::Linear};
+pub use {
+    bisect::Bisect, exhaustive::Exhaustive, linear::Linear, polarity::Polarity, seeds::SearchSeeds,
+};
 
 use crate::msrv::MinimumSupportedRustVersion;
+use crate::outcome::Outcome;
+use crate::reporter::event::{SearchDecision, SearchRange};
 use crate::reporter::Reporter;
 use crate::{Config, TResult};
 
 /// Use a bisection method to find the MSRV. By using a binary search, we halve our search space each
 /// step, making this an efficient search function.
 pub(crate) mod bisect;
+/// Check each release, without stopping at the first rejected one, so releases whose outcome
+/// contradicts the outcome of a more recent release are detected. Slower than a linear search, but
+/// doesn't assume that compatibility is monotonic.
+pub(crate) mod exhaustive;
 /// Find the MSRV by stepping through the most-recent to least-recent version, one-by-one. This is
 /// not very efficient, but is useful as a baseline, or if you're certain the MSRV is very close to
 /// the head.
 pub(crate) mod linear;
+/// Determines whether a passing or a failing check is accepted by a search method.
+pub(crate) mod polarity;
+/// Outcomes which are known beforehand, and narrow the search space.
+pub(crate) mod seeds;
 
 pub trait FindMinimalSupportedRustVersion {
     /// Method to find the minimum capable toolchain.
     ///
     /// The search space must be ordered from most to least recent.
     ///
+    /// Whether a check is accepted depends on the [`Polarity`] of the configuration.
+    ///
     /// This method returns TODO desc error variants, success case
     fn find_toolchain(
         &self,
@@ -27,3 +41,36 @@ pub trait FindMinimalSupportedRustVersion {
         reporter: &impl Reporter,
     ) -> TResult<MinimumSupportedRustVersion>;
 }
+
+/// Reports the decision which a search method made based on the outcome of a check, when enabled
+/// with `--explain-search`.
+///
+/// The `range` holds the releases which remained to be searched before the check, and `remaining`
+/// those which remain after it; `None` or an empty slice if the search is done. Both must be
+/// ordered from most to least recent.
+fn explain_decision(
+    config: &Config,
+    reporter: &impl Reporter,
+    range: &[Release],
+    tested: &Release,
+    outcome: &Outcome,
+    remaining: Option<&[Release]>,
+) -> TResult<()> {
+    if !config.explain_search() {
+        return Ok(());
+    }
+
+    let remaining = remaining
+        .filter(|releases| !releases.is_empty())
+        .map(SearchRange::of);
+
+    reporter.report_event(SearchDecision::new(
+        config.search_method(),
+        SearchRange::of(range),
+        tested.version().clone(),
+        outcome.is_success(),
+        remaining,
+    ))?;
+
+    Ok(())
+}
@@ -1,23 +1,34 @@
 use bisector::{Bisector, ConvergeTo, Indices, Step};
-use rust_releases::Release;
+use rust_releases::{semver, Release};
 
 use crate::check::Check;
 use crate::error::NoToolchainsToTryError;
 use crate::msrv::MinimumSupportedRustVersion;
-use crate::outcome::{FailureOutcome, Outcome, SuccessOutcome};
+use crate::outcome::Outcome;
 use crate::reporter::event::{FindMsrv, Progress};
 use crate::reporter::Reporter;
-use crate::search_method::FindMinimalSupportedRustVersion;
-use crate::toolchain::{OwnedToolchainSpec, ToolchainSpec};
+use crate::search_method::{explain_decision, FindMinimalSupportedRustVersion};
 use crate::{Config, TResult};
 
 pub struct Bisect<'runner, R: Check> {
     runner: &'runner R,
+    /// The release which is checked first, instead of the middle of the search space
+    start: Option<semver::Version>,
 }
 
 impl<'runner, R: Check> Bisect<'runner, R> {
     pub fn new(runner: &'runner R) -> Self {
-        Self { runner }
+        Self {
+            runner,
+            start: None,
+        }
+    }
+
+    /// Check the given release first, and bisect the releases on the side of it where the MSRV
+    /// must be. Ignored if the release isn't part of the search space.
+    pub fn starting_at(mut self, start: Option<semver::Version>) -> Self {
+        self.start = start;
+        self
     }
 
     fn run_check(
@@ -25,14 +36,15 @@ impl<'runner, R: Check> Bisect<'runner, R> {
         release: &Release,
         config: &Config,
         _reporter: &impl Reporter,
-    ) -> TResult<ConvergeTo<FailureOutcome, SuccessOutcome>> {
-        let toolchain = ToolchainSpec::new(release.version(), config.target());
-        match runner.check(config, &toolchain) {
-            Ok(outcome) => match outcome {
-                Outcome::Success(outcome) => Ok(ConvergeTo::Right(outcome)),
-                Outcome::Failure(outcome) => Ok(ConvergeTo::Left(outcome)),
-            },
-            Err(err) => Err(err),
+    ) -> TResult<ConvergeTo<Outcome, Outcome>> {
+        let toolchain = config.toolchain_spec(release.version());
+        let outcome = runner.check(config, &toolchain)?;
+
+        // Converge to the right when the outcome is accepted, i.e. towards less recent releases
+        if config.polarity().accepts(&outcome) {
+            Ok(ConvergeTo::Right(outcome))
+        } else {
+            Ok(ConvergeTo::Left(outcome))
         }
     }
 
@@ -40,15 +52,41 @@ impl<'runner, R: Check> Bisect<'runner, R> {
         iteration: u64,
         total: u64,
         indices: Indices,
+        config: &Config,
         reporter: &impl Reporter,
     ) -> TResult<()> {
         let current = indices.middle() as u64;
+        let progress =
+            Progress::new(current, total, iteration).with_unit(config.progress_unit().cloned());
 
-        reporter.report_event(Progress::new(current, total, iteration))?;
+        reporter.report_event(progress)?;
 
         Ok(())
     }
 
+    /// Prefetch the releases which may be checked after the release in the middle of the given
+    /// indices, i.e. the middle of either half, depending on the outcome of its check.
+    fn prefetch_next(&self, search_space: &[Release], indices: Indices, config: &Config) {
+        let middle = indices.middle();
+        let halves = [
+            Indices::new(indices.left, middle),
+            Indices::new(middle + 1, indices.right),
+        ];
+
+        let toolchains = halves
+            .iter()
+            .filter_map(|half| next_check(search_space, *half))
+            .map(|release| config.toolchain_spec(release.version()))
+            .collect::<Vec<_>>();
+
+        self.runner.prefetch(config, &toolchains);
+    }
+
+    /// The releases which remain to be searched within the given indices, if any.
+    fn range(search_space: &[Release], indices: Indices) -> Option<&[Release]> {
+        search_space.get(indices.left..=indices.right)
+    }
+
     fn minimum_capable(msrv: Option<&Release>, config: &Config) -> MinimumSupportedRustVersion {
         msrv.map_or(
             MinimumSupportedRustVersion::NoCompatibleToolchain,
@@ -56,7 +94,7 @@ impl<'runner, R: Check> Bisect<'runner, R> {
                 let version = release.version();
 
                 MinimumSupportedRustVersion::Toolchain {
-                    toolchain: OwnedToolchainSpec::new(version, config.target()),
+                    toolchain: config.toolchain_spec(version).to_owned(),
                 }
             },
         )
@@ -70,76 +108,196 @@ impl<'runner, R: Check> FindMinimalSupportedRustVersion for Bisect<'runner, R> {
         config: &Config,
         reporter: &impl Reporter,
     ) -> TResult<MinimumSupportedRustVersion> {
-        reporter.run_scoped_event(FindMsrv::new(config.search_method()), || {
-            let searcher = Bisector::new(search_space);
-
-            let total = search_space.len() as u64;
-            let mut iteration = 0_u64;
-            let mut indices =
-                Indices::try_from_bisector(&searcher).map_err(|_| NoToolchainsToTryError {
-                    min: config.minimum_version().map(Clone::clone),
-                    max: config.maximum_version().map(Clone::clone),
-                    search_space: search_space.to_vec(),
-                })?;
-
-            let mut last_compatible_index = None;
-
-            info!(?search_space);
-
-            while let Step {
-                indices: next_indices,
-                result: Some(step),
-            } = searcher.try_bisect(
-                |release| Self::run_check(self.runner, release, config, reporter),
-                indices,
-            )? {
-                iteration += 1;
-
-                info!(?indices, ?next_indices);
-
-                Self::show_progress(iteration, total, indices, reporter)?;
-
-                match step {
-                    ConvergeTo::Left(_outcome) => {}
-                    ConvergeTo::Right(_outcome) => {
-                        last_compatible_index = Some(indices);
-                    }
-                }
+        let event = FindMsrv::new(config.search_method()).with_search_space(search_space.len());
+
+        reporter.run_scoped_event(event, || {
+            let start = self.start.as_ref().and_then(|start| {
+                search_space
+                    .iter()
+                    .position(|release| release.version() == start)
+            });
+
+            match start {
+                Some(start) => self.bisect_from(start, search_space, config, reporter),
+                None => self.bisect(search_space, config, reporter),
+            }
+        })
+    }
+}
+
+impl<'runner, R: Check> Bisect<'runner, R> {
+    /// Check the release at the `start` index, and bisect the less recent releases if it's
+    /// accepted, or the more recent releases if it isn't.
+    fn bisect_from(
+        &self,
+        start: usize,
+        search_space: &[Release],
+        config: &Config,
+        reporter: &impl Reporter,
+    ) -> TResult<MinimumSupportedRustVersion> {
+        let release = &search_space[start];
+
+        info!(start = %release.version(), "starting the search at the declared msrv");
+
+        // either side of the start release is bisected next
+        let toolchains = [&search_space[start + 1..], &search_space[..start]]
+            .iter()
+            .filter(|side| !side.is_empty())
+            .filter_map(|side| next_check(*side, Indices::new(0, side.len() - 1)))
+            .map(|release| config.toolchain_spec(release.version()))
+            .collect::<Vec<_>>();
+
+        self.runner.prefetch(config, &toolchains);
 
-                indices = next_indices;
+        let (accepted, outcome, remaining) =
+            match Self::run_check(self.runner, release, config, reporter)? {
+                ConvergeTo::Right(outcome) => (true, outcome, &search_space[start + 1..]),
+                ConvergeTo::Left(outcome) => (false, outcome, &search_space[..start]),
+            };
+
+        explain_decision(
+            config,
+            reporter,
+            search_space,
+            release,
+            &outcome,
+            Some(remaining),
+        )?;
+
+        let msrv = if remaining.is_empty() {
+            MinimumSupportedRustVersion::NoCompatibleToolchain
+        } else {
+            self.bisect(remaining, config, reporter)?
+        };
+
+        // When none of the less recent releases is accepted, the start release is the MSRV
+        match msrv {
+            MinimumSupportedRustVersion::NoCompatibleToolchain if accepted => {
+                Ok(Self::minimum_capable(Some(release), config))
             }
+            msrv => Ok(msrv),
+        }
+    }
 
-            let converged_to_release = &search_space[indices.middle()];
+    fn bisect(
+        &self,
+        search_space: &[Release],
+        config: &Config,
+        reporter: &impl Reporter,
+    ) -> TResult<MinimumSupportedRustVersion> {
+        let searcher = Bisector::new(search_space);
 
-            // Work-around for regression:
-            // https://github.com/foresterre/cargo-msrv/issues/288
-            let msrv = if indices.middle() == search_space.len() - 1 {
-                Self::show_progress(iteration + 1, total, indices, reporter)?;
+        let total = search_space.len() as u64;
+        let mut iteration = 0_u64;
+        let mut indices =
+            Indices::try_from_bisector(&searcher).map_err(|_| NoToolchainsToTryError {
+                min: config.minimum_version().map(Clone::clone),
+                max: config.maximum_version().map(Clone::clone),
+                search_space: search_space.to_vec(),
+            })?;
 
-                match Self::run_check(self.runner, converged_to_release, config, reporter)? {
-                    ConvergeTo::Left(_outcome) => {
-                        last_compatible_index.map(|i| &search_space[i.middle()])
-                    }
-                    ConvergeTo::Right(_outcome) => Some(converged_to_release),
+        let mut last_compatible_index = None;
+
+        info!(?search_space);
+
+        while let Step {
+            indices: next_indices,
+            result: Some(step),
+        } = searcher.try_bisect(
+            |release| {
+                self.prefetch_next(search_space, indices, config);
+                Self::run_check(self.runner, release, config, reporter)
+            },
+            indices,
+        )? {
+            iteration += 1;
+
+            info!(?indices, ?next_indices);
+
+            Self::show_progress(iteration, total, indices, config, reporter)?;
+
+            let outcome = match &step {
+                ConvergeTo::Left(outcome) => outcome,
+                ConvergeTo::Right(outcome) => {
+                    last_compatible_index = Some(indices);
+                    outcome
                 }
-            } else {
-                last_compatible_index.map(|i| &search_space[i.middle()])
             };
 
-            Ok(Self::minimum_capable(msrv, config))
-        })
+            explain_decision(
+                config,
+                reporter,
+                &search_space[indices.left..=indices.right],
+                &search_space[indices.middle()],
+                outcome,
+                Self::range(search_space, next_indices),
+            )?;
+
+            indices = next_indices;
+        }
+
+        let converged_to_release = &search_space[indices.middle()];
+
+        // Work-around for regression:
+        // https://github.com/foresterre/cargo-msrv/issues/288
+        let msrv = if indices.middle() == search_space.len() - 1 {
+            Self::show_progress(iteration + 1, total, indices, config, reporter)?;
+
+            let (msrv, outcome) =
+                match Self::run_check(self.runner, converged_to_release, config, reporter)? {
+                    ConvergeTo::Left(outcome) => (
+                        last_compatible_index.map(|i| &search_space[i.middle()]),
+                        outcome,
+                    ),
+                    ConvergeTo::Right(outcome) => (Some(converged_to_release), outcome),
+                };
+
+            explain_decision(
+                config,
+                reporter,
+                &search_space[indices.middle()..],
+                converged_to_release,
+                &outcome,
+                None,
+            )?;
+
+            msrv
+        } else {
+            last_compatible_index.map(|i| &search_space[i.middle()])
+        };
+
+        Ok(Self::minimum_capable(msrv, config))
+    }
+}
+
+/// The release which is checked next by [`Bisect::bisect`], when the given indices remain to be
+/// searched: the release in the middle, unless the search is done. The least recent release of
+/// the search space is checked even then, since the search doesn't otherwise know whether it's
+/// accepted.
+fn next_check(search_space: &[Release], indices: Indices) -> Option<&Release> {
+    let is_least_recent = indices.middle() + 1 == search_space.len();
+
+    if indices.left < indices.right || is_least_recent {
+        search_space.get(indices.middle())
+    } else {
+        None
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::cell::RefCell;
+    use std::collections::HashSet;
+
     use rust_releases::Release;
 
-    use crate::check::TestRunner;
+    use crate::check::{Check, TestRunner};
+    use crate::outcome::Outcome;
     use crate::reporter::TestReporter;
     use crate::search_method::FindMinimalSupportedRustVersion;
     use crate::semver::Version;
-    use crate::{semver, Action, Config};
+    use crate::toolchain::ToolchainSpec;
+    use crate::{semver, Action, Config, TResult};
 
     use super::Bisect;
 
@@ -349,4 +507,100 @@ mod tests {
 
         assert_eq!(result.unwrap_version(), expected_msrv);
     }
+
+    #[yare::parameterized(
+        all_succeed = { &[58, 57, 56, 55, 54], 56, 54 },
+        declared_is_msrv = { &[58, 57, 56], 56, 56 },
+        declared_is_too_low = { &[58, 57], 56, 57 },
+        most_recent_succeeds = { &[58], 56, 58 },
+        start_is_least_recent = { &[58, 57, 56, 55, 54], 54, 54 },
+        start_is_most_recent = { &[58], 58, 58 },
+        start_outside_search_space = { &[58, 57, 56], 40, 56 },
+    )]
+    fn find_toolchain_starting_at(accept: &[u64], start: u64, expected_minor: u64) {
+        let search_space = [58, 57, 56, 55, 54]
+            .iter()
+            .map(|&minor| Release::new_stable(semver::Version::new(1, minor, 0)))
+            .collect::<Vec<_>>();
+        let accept = accept
+            .iter()
+            .map(|&minor| semver::Version::new(1, minor, 0))
+            .collect::<Vec<_>>();
+
+        let runner = TestRunner::with_ok(&accept);
+        let bisect = Bisect::new(&runner).starting_at(Some(semver::Version::new(1, start, 0)));
+
+        let reporter = TestReporter::default();
+
+        let result = bisect
+            .find_toolchain(&search_space, &fake_config(), reporter.reporter())
+            .unwrap();
+
+        assert_eq!(
+            result.unwrap_version(),
+            semver::Version::new(1, expected_minor, 0)
+        );
+    }
+    /// Records the toolchains which were checked, without being prefetched first.
+    struct PrefetchRecorder {
+        runner: TestRunner,
+        prefetched: RefCell<HashSet<semver::Version>>,
+        not_prefetched: RefCell<Vec<semver::Version>>,
+    }
+
+    impl Check for PrefetchRecorder {
+        fn check(&self, config: &Config, toolchain: &ToolchainSpec) -> TResult<Outcome> {
+            if !self.prefetched.borrow().contains(toolchain.version()) {
+                self.not_prefetched
+                    .borrow_mut()
+                    .push(toolchain.version().clone());
+            }
+
+            self.runner.check(config, toolchain)
+        }
+
+        fn prefetch(&self, _config: &Config, toolchains: &[ToolchainSpec]) {
+            self.prefetched.borrow_mut().extend(
+                toolchains
+                    .iter()
+                    .map(|toolchain| toolchain.version().clone()),
+            );
+        }
+    }
+
+    #[yare::parameterized(
+        none_accepted = { &[], None },
+        all_accepted = { &[58, 57, 56, 55, 54, 53, 52, 51, 50], None },
+        most_recent_accepted = { &[58], None },
+        middle_accepted = { &[58, 57, 56, 55], None },
+        starting_at_msrv = { &[58, 57, 56, 55], Some(55) },
+        starting_below_msrv = { &[58, 57, 56, 55], Some(52) },
+        starting_at_least_recent = { &[58, 57, 56, 55, 54, 53, 52, 51, 50], Some(50) },
+    )]
+    fn each_check_but_the_first_is_prefetched(accept: &[u64], start: Option<u64>) {
+        let search_space = (50..=58)
+            .rev()
+            .map(|minor| Release::new_stable(semver::Version::new(1, minor, 0)))
+            .collect::<Vec<_>>();
+        let accept = accept
+            .iter()
+            .map(|&minor| semver::Version::new(1, minor, 0))
+            .collect::<Vec<_>>();
+
+        let runner = PrefetchRecorder {
+            runner: TestRunner::with_ok(&accept),
+            prefetched: RefCell::default(),
+            not_prefetched: RefCell::default(),
+        };
+        let bisect =
+            Bisect::new(&runner).starting_at(start.map(|minor| semver::Version::new(1, minor, 0)));
+
+        let reporter = TestReporter::default();
+
+        bisect
+            .find_toolchain(&search_space, &fake_config(), reporter.reporter())
+            .unwrap();
+
+        assert_eq!(runner.not_prefetched.borrow().len(), 1);
+    }
 }
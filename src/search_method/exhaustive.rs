@@ -0,0 +1,207 @@
+use rust_releases::Release;
+
+use crate::check::Check;
+use crate::msrv::MinimumSupportedRustVersion;
+use crate::reporter::event::{CheckedRelease, ExhaustiveSearch, FindMsrv};
+use crate::reporter::Reporter;
+use crate::search_method::{explain_decision, FindMinimalSupportedRustVersion};
+use crate::{Config, TResult};
+
+pub struct Exhaustive<'runner, R: Check> {
+    runner: &'runner R,
+}
+
+impl<'runner, R: Check> Exhaustive<'runner, R> {
+    pub fn new(runner: &'runner R) -> Self {
+        Self { runner }
+    }
+}
+
+impl<'runner, R: Check> FindMinimalSupportedRustVersion for Exhaustive<'runner, R> {
+    fn find_toolchain<'spec>(
+        &self,
+        search_space: &'spec [Release],
+        config: &'spec Config,
+        reporter: &impl Reporter,
+    ) -> TResult<MinimumSupportedRustVersion> {
+        let event = FindMsrv::new(config.search_method()).with_search_space(search_space.len());
+
+        reporter.run_scoped_event(event, || {
+            let mut checked = Vec::with_capacity(search_space.len());
+            let mut accepted = Vec::with_capacity(search_space.len());
+
+            for (i, release) in search_space.iter().enumerate() {
+                if let Some(next) = search_space.get(i + 1) {
+                    self.runner
+                        .prefetch(config, &[config.toolchain_spec(next.version())]);
+                }
+
+                let toolchain = config.toolchain_spec(release.version());
+                let outcome = self.runner.check(config, &toolchain)?;
+
+                explain_decision(
+                    config,
+                    reporter,
+                    &search_space[i..],
+                    release,
+                    &outcome,
+                    Some(&search_space[i + 1..]),
+                )?;
+
+                checked.push(CheckedRelease::new(
+                    release.version().clone(),
+                    outcome.is_success(),
+                ));
+                accepted.push(config.polarity().accepts(&outcome));
+            }
+
+            let non_monotonic = non_monotonic(&accepted)
+                .map(|i| search_space[i].version().clone())
+                .collect();
+
+            reporter.report_event(ExhaustiveSearch::new(checked, non_monotonic))?;
+
+            Ok(minimum_capable(search_space, &accepted, config))
+        })
+    }
+}
+
+/// The indices of the accepted releases which are less recent than a rejected release. The other
+/// search methods assume these don't exist, i.e. that each release which is more recent than an
+/// accepted release is accepted as well.
+fn non_monotonic(accepted: &[bool]) -> impl Iterator<Item = usize> + '_ {
+    let first_rejected = accepted
+        .iter()
+        .position(|&accepted| !accepted)
+        .unwrap_or(accepted.len());
+
+    (first_rejected..accepted.len()).filter(move |&i| accepted[i])
+}
+
+/// The least recent release from which each more recent release is accepted, like the one which
+/// would be found by a linear search. A less recent release which is accepted once again, after a
+/// rejected release, is not taken into account.
+fn minimum_capable(
+    releases: &[Release],
+    accepted: &[bool],
+    config: &Config,
+) -> MinimumSupportedRustVersion {
+    let contiguous = accepted.iter().take_while(|&&accepted| accepted).count();
+
+    match contiguous.checked_sub(1) {
+        Some(i) => MinimumSupportedRustVersion::Toolchain {
+            toolchain: config.toolchain_spec(releases[i].version()).to_owned(),
+        },
+        None => MinimumSupportedRustVersion::NoCompatibleToolchain,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::TestRunner;
+    use crate::config::{ConfigBuilder, SearchMethod};
+    use crate::reporter::event::Message;
+    use crate::reporter::TestReporter;
+    use crate::toolchain::OwnedToolchainSpec;
+    use crate::{semver, Action, ReleaseIndex};
+    use std::iter::FromIterator;
+
+    fn releases() -> Vec<Release> {
+        (52..=56)
+            .rev()
+            .map(|minor| Release::new_stable(semver::Version::new(1, minor, 0)))
+            .collect()
+    }
+
+    fn search(supported: &[u64]) -> (MinimumSupportedRustVersion, ExhaustiveSearch) {
+        let config = ConfigBuilder::new(Action::Find, "my-test-target")
+            .search_method(SearchMethod::Exhaustive)
+            .build();
+        let reporter = TestReporter::default();
+
+        let supported = supported
+            .iter()
+            .map(|&minor| semver::Version::new(1, minor, 0))
+            .collect::<Vec<_>>();
+
+        let runner = TestRunner::with_ok(&supported);
+        let index = ReleaseIndex::from_iter(releases());
+
+        let msrv = Exhaustive::new(&runner)
+            .find_toolchain(index.releases(), &config, reporter.reporter())
+            .unwrap();
+
+        let search = reporter
+            .wait_for_events()
+            .into_iter()
+            .find_map(|event| match event.message() {
+                Message::ExhaustiveSearch(search) => Some(search.clone()),
+                _ => None,
+            })
+            .unwrap();
+
+        (msrv, search)
+    }
+
+    fn toolchain(minor: u64) -> MinimumSupportedRustVersion {
+        MinimumSupportedRustVersion::Toolchain {
+            toolchain: OwnedToolchainSpec::new(
+                &semver::Version::new(1, minor, 0),
+                "my-test-target",
+            ),
+        }
+    }
+
+    #[test]
+    fn each_release_is_checked() {
+        let (msrv, search) = search(&[56, 55, 54]);
+
+        assert_eq!(msrv, toolchain(54));
+        assert_eq!(
+            search.releases(),
+            &[
+                CheckedRelease::new(semver::Version::new(1, 56, 0), true),
+                CheckedRelease::new(semver::Version::new(1, 55, 0), true),
+                CheckedRelease::new(semver::Version::new(1, 54, 0), true),
+                CheckedRelease::new(semver::Version::new(1, 53, 0), false),
+                CheckedRelease::new(semver::Version::new(1, 52, 0), false),
+            ]
+        );
+        assert!(search.non_monotonic().is_empty());
+    }
+
+    #[test]
+    fn release_which_fails_in_the_middle() {
+        let (msrv, search) = search(&[56, 54, 53]);
+
+        assert_eq!(msrv, toolchain(56));
+        assert_eq!(
+            search.non_monotonic(),
+            &[
+                semver::Version::new(1, 54, 0),
+                semver::Version::new(1, 53, 0)
+            ]
+        );
+    }
+
+    #[test]
+    fn none_supported() {
+        let (msrv, search) = search(&[]);
+
+        assert_eq!(msrv, MinimumSupportedRustVersion::NoCompatibleToolchain);
+        assert_eq!(search.releases().len(), 5);
+        assert!(search.non_monotonic().is_empty());
+    }
+
+    #[yare::parameterized(
+        monotonic = { &[true, true, false, false], &[] },
+        all_rejected = { &[false, false], &[] },
+        all_accepted = { &[true, true], &[] },
+        gap = { &[true, false, true, false, true], &[2, 4] },
+        most_recent_rejected = { &[false, true, true], &[1, 2] },
+    )]
+    fn non_monotonic_releases(accepted: &[bool], expected: &[usize]) {
+        assert_eq!(non_monotonic(accepted).collect::<Vec<_>>(), expected);
+    }
+}
@@ -5,8 +5,7 @@ use crate::msrv::MinimumSupportedRustVersion;
 use crate::outcome::Outcome;
 use crate::reporter::event::FindMsrv;
 use crate::reporter::Reporter;
-use crate::search_method::FindMinimalSupportedRustVersion;
-use crate::toolchain::{OwnedToolchainSpec, ToolchainSpec};
+use crate::search_method::{explain_decision, FindMinimalSupportedRustVersion};
 use crate::{Config, TResult};
 
 pub struct Linear<'runner, R: Check> {
@@ -24,7 +23,7 @@ impl<'runner, R: Check> Linear<'runner, R> {
         config: &Config,
         _reporter: &impl Reporter,
     ) -> TResult<Outcome> {
-        let toolchain = ToolchainSpec::new(release.version(), config.target());
+        let toolchain = config.toolchain_spec(release.version());
         runner.check(config, &toolchain)
     }
 
@@ -37,7 +36,7 @@ impl<'runner, R: Check> Linear<'runner, R> {
             let version = releases[i].version();
 
             MinimumSupportedRustVersion::Toolchain {
-                toolchain: OwnedToolchainSpec::new(version, config.target()),
+                toolchain: config.toolchain_spec(version).to_owned(),
             }
         })
     }
@@ -50,17 +49,31 @@ impl<'runner, R: Check> FindMinimalSupportedRustVersion for Linear<'runner, R> {
         config: &'spec Config,
         reporter: &impl Reporter,
     ) -> TResult<MinimumSupportedRustVersion> {
-        reporter.run_scoped_event(FindMsrv::new(config.search_method()), || {
+        let event = FindMsrv::new(config.search_method()).with_search_space(search_space.len());
+
+        reporter.run_scoped_event(event, || {
             let mut last_compatible_index = None;
 
             for (i, release) in search_space.iter().enumerate() {
-                let outcome = Self::run_check(self.runner, release, config, reporter)?;
+                if let Some(next) = search_space.get(i + 1) {
+                    self.runner
+                        .prefetch(config, &[config.toolchain_spec(next.version())]);
+                }
 
-                match outcome {
-                    Outcome::Failure(_outcome) => {
-                        break;
-                    }
-                    Outcome::Success(_outcome) => {}
+                let outcome = Self::run_check(self.runner, release, config, reporter)?;
+                let accepted = config.polarity().accepts(&outcome);
+
+                explain_decision(
+                    config,
+                    reporter,
+                    &search_space[i..],
+                    release,
+                    &outcome,
+                    accepted.then(|| &search_space[i + 1..]),
+                )?;
+
+                if !accepted {
+                    break;
                 }
 
                 last_compatible_index = Some(i);
@@ -79,7 +92,10 @@ impl<'runner, R: Check> FindMinimalSupportedRustVersion for Linear<'runner, R> {
 mod tests {
     use super::*;
     use crate::check::TestRunner;
+    use crate::config::{ConfigBuilder, SearchMethod};
+    use crate::reporter::event::{Message, SearchDecision, SearchRange};
     use crate::reporter::TestReporter;
+    use crate::toolchain::OwnedToolchainSpec;
     use crate::{semver, Action, Config, ReleaseIndex};
     use rust_releases::Release;
     use std::iter::FromIterator;
@@ -212,4 +228,68 @@ mod tests {
         let expected = MinimumSupportedRustVersion::NoCompatibleToolchain;
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn explain_search() {
+        let config = ConfigBuilder::new(Action::Find, "my-test-target")
+            .search_method(SearchMethod::Linear)
+            .explain_search(true)
+            .build();
+        let reporter = TestReporter::default();
+
+        let supported_releases = vec![
+            Release::new_stable(semver::Version::new(1, 56, 0)),
+            Release::new_stable(semver::Version::new(1, 55, 0)),
+        ];
+
+        let index_of_releases = vec![
+            Release::new_stable(semver::Version::new(1, 56, 0)),
+            Release::new_stable(semver::Version::new(1, 55, 0)),
+            Release::new_stable(semver::Version::new(1, 54, 0)),
+        ];
+
+        let runner = TestRunner::with_ok(supported_releases.iter().map(Release::version));
+        let index = ReleaseIndex::from_iter(index_of_releases);
+
+        Linear::new(&runner)
+            .find_toolchain(index.releases(), &config, reporter.reporter())
+            .unwrap();
+
+        let releases = index.releases();
+        let decisions = reporter
+            .wait_for_events()
+            .into_iter()
+            .filter_map(|event| match event.message() {
+                Message::SearchDecision(decision) => Some(decision.clone()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            decisions,
+            vec![
+                SearchDecision::new(
+                    SearchMethod::Linear,
+                    SearchRange::of(releases),
+                    semver::Version::new(1, 56, 0),
+                    true,
+                    Some(SearchRange::of(&releases[1..])),
+                ),
+                SearchDecision::new(
+                    SearchMethod::Linear,
+                    SearchRange::of(&releases[1..]),
+                    semver::Version::new(1, 55, 0),
+                    true,
+                    Some(SearchRange::of(&releases[2..])),
+                ),
+                SearchDecision::new(
+                    SearchMethod::Linear,
+                    SearchRange::of(&releases[2..]),
+                    semver::Version::new(1, 54, 0),
+                    false,
+                    None,
+                ),
+            ]
+        );
+    }
 }
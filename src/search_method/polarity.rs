@@ -0,0 +1,59 @@
+use crate::outcome::Outcome;
+
+/// Determines which outcome of a check is accepted by a search method.
+///
+/// Search methods look for the least recent release in a search space (ordered from most to
+/// least recent), for which the outcome of the check, and all more recent checks, is accepted.
+#[derive(
+    Debug, Copy, Clone, Eq, PartialEq, serde::Serialize, serde::Deserialize, schemars::JsonSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum Polarity {
+    /// A passing check is accepted, so the search finds the earliest compatible toolchain,
+    /// i.e. the MSRV.
+    Normal,
+    /// A failing check is accepted, so the search finds the earliest toolchain for which the
+    /// check fails, i.e. the release which introduced a regression.
+    Inverted,
+}
+
+impl Polarity {
+    /// Whether the given outcome is accepted by a search with this polarity.
+    pub fn accepts(&self, outcome: &Outcome) -> bool {
+        match self {
+            Self::Normal => outcome.is_success(),
+            Self::Inverted => !outcome.is_success(),
+        }
+    }
+
+    pub fn is_inverted(&self) -> bool {
+        matches!(self, Self::Inverted)
+    }
+}
+
+impl Default for Polarity {
+    fn default() -> Self {
+        Self::Normal
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::semver;
+    use crate::toolchain::OwnedToolchainSpec;
+
+    fn toolchain() -> OwnedToolchainSpec {
+        OwnedToolchainSpec::new(&semver::Version::new(1, 56, 0), "x")
+    }
+
+    #[yare::parameterized(
+        normal_success = { Polarity::Normal, Outcome::new_success(toolchain()), true },
+        normal_failure = { Polarity::Normal, Outcome::new_failure(toolchain(), "f".to_string()), false },
+        inverted_success = { Polarity::Inverted, Outcome::new_success(toolchain()), false },
+        inverted_failure = { Polarity::Inverted, Outcome::new_failure(toolchain(), "f".to_string()), true },
+    )]
+    fn accepts(polarity: Polarity, outcome: Outcome, expected: bool) {
+        assert_eq!(polarity.accepts(&outcome), expected);
+    }
+}
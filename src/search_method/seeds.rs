@@ -0,0 +1,97 @@
+use rust_releases::semver;
+
+use crate::manifest::bare_version::BareVersion;
+use crate::search_method::Polarity;
+
+/// Outcomes which are known beforehand, e.g. from previous CI runs, given with `--known-good` and
+/// `--known-bad`. Assuming compatibility is monotonic, the releases on the far side of a seed
+/// don't have to be checked.
+#[derive(
+    Clone, Debug, Default, Eq, PartialEq, serde::Serialize, serde::Deserialize, schemars::JsonSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub struct SearchSeeds {
+    /// A Rust version for which the check is known to pass
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub known_good: Option<BareVersion>,
+    /// A Rust version for which the check is known to fail
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub known_bad: Option<BareVersion>,
+}
+
+impl SearchSeeds {
+    pub fn is_empty(&self) -> bool {
+        self.known_good.is_none() && self.known_bad.is_none()
+    }
+
+    /// The seeds whose outcome is respectively accepted and rejected by a search with the given
+    /// polarity.
+    fn by_polarity(&self, polarity: Polarity) -> (Option<&BareVersion>, Option<&BareVersion>) {
+        match polarity {
+            Polarity::Normal => (self.known_good.as_ref(), self.known_bad.as_ref()),
+            Polarity::Inverted => (self.known_bad.as_ref(), self.known_good.as_ref()),
+        }
+    }
+
+    /// Whether the seeds contradict each other: a search accepts the more recent releases, so the
+    /// accepted seed must be more recent than the rejected seed.
+    pub fn is_contradictory(&self, polarity: Polarity) -> bool {
+        match self.by_polarity(polarity) {
+            (Some(accepted), Some(rejected)) => {
+                accepted.to_semver_version() <= rejected.to_semver_version()
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether the search result may be the given release: it must be at most as recent as the
+    /// accepted seed, and more recent than the rejected seed.
+    pub fn may_contain(&self, polarity: Polarity, version: &semver::Version) -> bool {
+        let (accepted, rejected) = self.by_polarity(polarity);
+
+        accepted.map_or(true, |accepted| accepted.is_at_most(version))
+            && rejected.map_or(true, |rejected| !rejected.is_at_most(version))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seeds(known_good: Option<&str>, known_bad: Option<&str>) -> SearchSeeds {
+        SearchSeeds {
+            known_good: known_good.map(|v| v.parse().unwrap()),
+            known_bad: known_bad.map(|v| v.parse().unwrap()),
+        }
+    }
+
+    #[yare::parameterized(
+        within = { seeds(Some("1.70"), Some("1.55")), Polarity::Normal, "1.60.0", true },
+        known_good = { seeds(Some("1.70"), Some("1.55")), Polarity::Normal, "1.70.1", true },
+        above_known_good = { seeds(Some("1.70"), Some("1.55")), Polarity::Normal, "1.71.0", false },
+        known_bad = { seeds(Some("1.70"), Some("1.55")), Polarity::Normal, "1.55.2", false },
+        below_known_bad = { seeds(Some("1.70"), Some("1.55")), Polarity::Normal, "1.40.0", false },
+        only_known_good = { seeds(Some("1.70"), None), Polarity::Normal, "1.40.0", true },
+        only_known_bad = { seeds(None, Some("1.55")), Polarity::Normal, "1.71.0", true },
+        inverted_within = { seeds(Some("1.55"), Some("1.70")), Polarity::Inverted, "1.60.0", true },
+        inverted_known_bad = { seeds(Some("1.55"), Some("1.70")), Polarity::Inverted, "1.70.0", true },
+        inverted_known_good = { seeds(Some("1.55"), Some("1.70")), Polarity::Inverted, "1.55.0", false },
+    )]
+    fn may_contain(seeds: SearchSeeds, polarity: Polarity, version: &str, expected: bool) {
+        let version = semver::Version::parse(version).unwrap();
+
+        assert_eq!(seeds.may_contain(polarity, &version), expected);
+    }
+
+    #[yare::parameterized(
+        consistent = { seeds(Some("1.70"), Some("1.55")), Polarity::Normal, false },
+        reversed = { seeds(Some("1.55"), Some("1.70")), Polarity::Normal, true },
+        same = { seeds(Some("1.60"), Some("1.60")), Polarity::Normal, true },
+        single = { seeds(Some("1.60"), None), Polarity::Normal, false },
+        inverted_consistent = { seeds(Some("1.55"), Some("1.70")), Polarity::Inverted, false },
+        inverted_reversed = { seeds(Some("1.70"), Some("1.55")), Polarity::Inverted, true },
+    )]
+    fn contradictory(seeds: SearchSeeds, polarity: Polarity, expected: bool) {
+        assert_eq!(seeds.is_contradictory(polarity), expected);
+    }
+}
@@ -0,0 +1,223 @@
+//! The protocol between `cargo msrv serve` and the cargo-msrv invocations which use it, with
+//! `--server`.
+//!
+//! The server holds the release index, which it fetched once, installs the toolchains on behalf
+//! of its clients, and runs their checks, so concurrent invocations, e.g. in a busy CI runner of a
+//! monorepo, neither fetch the index again, nor install the same toolchain twice. Each toolchain is
+//! installed by one request at a time, while different toolchains are installed concurrently.
+//!
+//! A client names the toolchain, and the server builds the arguments to rustup from it, so a client
+//! can't pass arbitrary arguments to rustup. For the same reason, only cargo commands are checked.
+//!
+//! Each request is a line of JSON, sent over its own TCP connection, to which the server answers
+//! with a line of JSON.
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpStream;
+use std::path::PathBuf;
+
+use rust_releases::{Release, ReleaseIndex};
+
+use crate::config::InstallProfile;
+use crate::error::CargoMSRVError;
+use crate::release_index_source::ReleaseIndexSource;
+use crate::semver;
+use crate::toolchain::{OwnedToolchainSpec, ToolchainSpec};
+
+/// The address on which `cargo msrv serve` listens by default.
+pub const DEFAULT_ADDRESS: &str = "127.0.0.1:7430";
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case", tag = "type")]
+pub(crate) enum Request {
+    /// The versions of the releases of the index held by the server
+    ReleaseIndex,
+    /// Install a toolchain
+    Install(InstallRequest),
+    /// Install a toolchain, and check the crate in the given directory with it
+    Check {
+        install: InstallRequest,
+        dir: PathBuf,
+        /// The cargo command, as it would be run by `rustup run <toolchain>`
+        check_command: Vec<String>,
+    },
+}
+
+/// The toolchain to install, with the rustup profile and the components to install it with.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct InstallRequest {
+    pub(crate) toolchain: OwnedToolchainSpec,
+    pub(crate) profile: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) components: Vec<String>,
+}
+
+impl InstallRequest {
+    pub(crate) fn new(
+        toolchain: &ToolchainSpec,
+        profile: InstallProfile,
+        components: &[&'static str],
+    ) -> Self {
+        Self {
+            toolchain: toolchain.to_owned(),
+            profile: profile.to_string(),
+            components: components.iter().map(|it| it.to_string()).collect(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case", tag = "type")]
+pub(crate) enum Response {
+    /// The versions of the releases, from the most to the least recent release
+    ReleaseIndex { releases: Vec<String> },
+    Install {
+        status: InstallStatus,
+        /// The output of rustup, when the toolchain could not be installed
+        stderr: Option<String>,
+    },
+    /// The outcome of a check, once the toolchain was installed
+    Check {
+        compatible: bool,
+        /// The output of the check command, when the crate is not compatible
+        error: Option<String>,
+    },
+    /// The request could not be handled
+    Error { message: String },
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum InstallStatus {
+    Installed,
+    /// The toolchain is not available for the target
+    Unavailable,
+    Failed,
+}
+
+/// A connection to a server started with `cargo msrv serve`.
+pub(crate) struct Client<'a> {
+    address: &'a str,
+}
+
+impl<'a> Client<'a> {
+    pub(crate) fn new(address: &'a str) -> Self {
+        Self { address }
+    }
+
+    /// Send a request, and wait for the response of the server, which, for an install, may take
+    /// a while.
+    pub(crate) fn request(&self, request: &Request) -> Result<Response, CargoMSRVError> {
+        let unreachable = |message: String| CargoMSRVError::ServerUnreachable {
+            address: self.address.to_string(),
+            message,
+        };
+
+        let mut stream =
+            TcpStream::connect(self.address).map_err(|error| unreachable(error.to_string()))?;
+
+        let mut line = serde_json::to_string(request).expect("the request is serializable");
+        line.push('\n');
+
+        stream
+            .write_all(line.as_bytes())
+            .map_err(|error| unreachable(error.to_string()))?;
+
+        let mut response = String::new();
+        BufReader::new(stream)
+            .read_line(&mut response)
+            .map_err(|error| unreachable(error.to_string()))?;
+
+        serde_json::from_str(&response).map_err(|error| {
+            unreachable(format!(
+                "unable to parse the response of the server: {}",
+                error
+            ))
+        })
+    }
+}
+
+/// Obtains the release index from a server started with `cargo msrv serve`.
+pub(crate) struct ServerSource<'a> {
+    client: Client<'a>,
+}
+
+impl<'a> ServerSource<'a> {
+    pub(crate) fn new(address: &'a str) -> Self {
+        Self {
+            client: Client::new(address),
+        }
+    }
+}
+
+impl<'a> ReleaseIndexSource for ServerSource<'a> {
+    fn name(&self) -> &str {
+        "cargo-msrv-server"
+    }
+
+    fn fetch(&self) -> Result<ReleaseIndex, CargoMSRVError> {
+        match self.client.request(&Request::ReleaseIndex)? {
+            Response::ReleaseIndex { releases } => {
+                let mut releases = releases
+                    .iter()
+                    .map(|version| semver::Version::parse(version).map(Release::new_stable))
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                // Releases in an index must be ordered from most to least recent
+                releases.sort();
+
+                Ok(releases.into_iter().collect())
+            }
+            Response::Error { message } => Err(CargoMSRVError::ServerUnreachable {
+                address: self.client.address.to_string(),
+                message,
+            }),
+            other => Err(CargoMSRVError::ServerUnreachable {
+                address: self.client.address.to_string(),
+                message: format!(
+                    "unexpected response to a release index request: {:?}",
+                    other
+                ),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn install_request() -> InstallRequest {
+        let version = semver::Version::new(1, 56, 0);
+        let toolchain = ToolchainSpec::new(&version, "x86_64-unknown-linux-gnu");
+
+        InstallRequest::new(&toolchain, InstallProfile::Minimal, &["clippy"])
+    }
+
+    #[yare::parameterized(
+        release_index = { Request::ReleaseIndex, r#"{"type":"release_index"}"# },
+        install = { Request::Install(install_request()), r#"{"type":"install","toolchain":{"version":"1.56.0","target":"x86_64-unknown-linux-gnu"},"profile":"minimal","components":["clippy"]}"# },
+        check = { Request::Check { install: install_request(), dir: PathBuf::from("/crate"), check_command: vec!["cargo".to_string(), "check".to_string()] }, r#"{"type":"check","install":{"toolchain":{"version":"1.56.0","target":"x86_64-unknown-linux-gnu"},"profile":"minimal","components":["clippy"]},"dir":"/crate","check_command":["cargo","check"]}"# },
+    )]
+    fn request_round_trip(request: Request, json: &str) {
+        assert_eq!(serde_json::to_string(&request).unwrap(), json);
+        assert_eq!(serde_json::from_str::<Request>(json).unwrap(), request);
+    }
+
+    #[test]
+    fn response_round_trip() {
+        let response = Response::Install {
+            status: InstallStatus::Unavailable,
+            stderr: Some("error: no release found".to_string()),
+        };
+
+        let json = serde_json::to_string(&response).unwrap();
+
+        assert_eq!(
+            json,
+            r#"{"type":"install","status":"unavailable","stderr":"error: no release found"}"#
+        );
+        assert_eq!(serde_json::from_str::<Response>(&json).unwrap(), response);
+    }
+}
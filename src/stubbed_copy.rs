@@ -0,0 +1,363 @@
+//! Temporary copies of a crate, in which the source files matched by the patterns given with
+//! `--exclude-path` are stubbed out, e.g. examples which require a nightly toolchain, so they
+//! don't determine the MSRV. The crate itself is left untouched.
+
+use std::path::{Path, PathBuf};
+
+use crate::crate_copy::{copy_crate, remove_copy};
+use crate::error::{CargoMSRVError, IoErrorSource, TResult};
+use crate::manifest::workspace::{wildcard_match, workspace_root};
+
+/// The contents of a stubbed out file which is the root of a binary crate, like an example.
+const STUBBED_BINARY: &str = "// stubbed out by cargo-msrv, see `--exclude-path`\nfn main() {}\n";
+
+/// The contents of another stubbed out file, like a module.
+const STUBBED_MODULE: &str = "// stubbed out by cargo-msrv, see `--exclude-path`\n";
+
+/// A copy of a crate, or of the workspace to which it belongs, with the excluded source files
+/// stubbed out. The copy is removed when it is dropped.
+#[derive(Debug)]
+pub(crate) struct StubbedCopy {
+    patterns: Vec<String>,
+    /// The root of the copy
+    path: PathBuf,
+    /// The directory of the crate within the copy
+    crate_dir: PathBuf,
+    /// The stubbed out files, relative to the directory of the crate
+    stubbed: Vec<PathBuf>,
+}
+
+impl StubbedCopy {
+    /// Copy the crate in `crate_dir` to `path`, and stub out the source files matched by the
+    /// patterns. When the crate is a member of a workspace, the whole workspace is copied, so
+    /// what the crate inherits from the workspace still applies. A copy which was left behind at
+    /// `path` by an earlier, interrupted, run is replaced.
+    pub(crate) fn create(crate_dir: &Path, patterns: &[String], path: PathBuf) -> TResult<Self> {
+        let crate_dir = canonicalize(crate_dir)?;
+        let root = workspace_root(&crate_dir).unwrap_or_else(|| crate_dir.clone());
+
+        remove_copy(&path);
+
+        // dropped on failure, which removes the partial copy
+        let mut copy = Self {
+            patterns: patterns.to_vec(),
+            crate_dir: path.join(
+                crate_dir
+                    .strip_prefix(&root)
+                    .unwrap_or_else(|_| Path::new("")),
+            ),
+            path,
+            stubbed: Vec::new(),
+        };
+
+        copy_crate(&root, &copy.path)?;
+
+        for pattern in patterns {
+            let matched = matching_files(&copy.crate_dir, pattern)?;
+
+            if matched.is_empty() {
+                return Err(CargoMSRVError::InvalidConfig(format!(
+                    "The pattern '{}' given with --exclude-path matches no Rust source files of the \
+                    crate",
+                    pattern
+                )));
+            }
+
+            for file in matched {
+                stub(&copy.crate_dir.join(&file))?;
+                info!(path = %file.display(), "stubbed out excluded file");
+
+                if !copy.stubbed.contains(&file) {
+                    copy.stubbed.push(file);
+                }
+            }
+        }
+
+        Ok(copy)
+    }
+
+    pub(crate) fn patterns(&self) -> &[String] {
+        &self.patterns
+    }
+
+    /// The directory of the crate within the copy.
+    pub(crate) fn crate_dir(&self) -> &Path {
+        &self.crate_dir
+    }
+
+    /// The stubbed out files, relative to the directory of the crate.
+    pub(crate) fn stubbed(&self) -> &[PathBuf] {
+        &self.stubbed
+    }
+}
+
+impl Drop for StubbedCopy {
+    fn drop(&mut self) {
+        remove_copy(&self.path);
+    }
+}
+
+/// The Rust source files in `dir` which are matched by the pattern, relative to `dir`. A pattern
+/// is a path relative to `dir`, of which each component may contain `*` wildcards, e.g.
+/// `examples/nightly_*.rs`. A pattern which matches a directory, matches all files within it.
+fn matching_files(dir: &Path, pattern: &str) -> TResult<Vec<PathBuf>> {
+    let pattern = pattern
+        .split(|c| c == '/' || c == '\\')
+        .filter(|component| !component.is_empty() && *component != ".")
+        .collect::<Vec<_>>();
+
+    let mut matched = Vec::new();
+
+    for file in source_files(dir)? {
+        let relative = file.strip_prefix(dir).unwrap_or(&file).to_path_buf();
+        let components = relative
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>();
+
+        let is_match = components.len() >= pattern.len()
+            && pattern
+                .iter()
+                .zip(&components)
+                .all(|(pattern, name)| wildcard_match(pattern, name));
+
+        if is_match {
+            matched.push(relative);
+        }
+    }
+
+    matched.sort();
+
+    Ok(matched)
+}
+
+/// The `.rs` files in `dir` and its subdirectories. Symbolic links are skipped, so a link which
+/// points outside of the crate, or at one of its parents, is not followed.
+fn source_files(dir: &Path) -> TResult<Vec<PathBuf>> {
+    let entries = std::fs::read_dir(dir).map_err(|error| CargoMSRVError::Io {
+        error,
+        source: IoErrorSource::ReadDir(dir.to_path_buf()),
+    })?;
+
+    let mut files = Vec::new();
+
+    for entry in entries {
+        let path = entry
+            .map_err(|error| CargoMSRVError::Io {
+                error,
+                source: IoErrorSource::ReadDir(dir.to_path_buf()),
+            })?
+            .path();
+
+        let file_type = std::fs::symlink_metadata(&path)
+            .map_err(|error| CargoMSRVError::Io {
+                error,
+                source: IoErrorSource::ReadDir(dir.to_path_buf()),
+            })?
+            .file_type();
+
+        if file_type.is_symlink() {
+            continue;
+        }
+
+        if file_type.is_dir() {
+            files.extend(source_files(&path)?);
+        } else if path
+            .extension()
+            .map_or(false, |extension| extension == "rs")
+        {
+            files.push(path);
+        }
+    }
+
+    Ok(files)
+}
+
+/// Replace the contents of a source file with a stub. A file with a `main` function is assumed
+/// to be the root of a binary crate, so its stub keeps an empty `main` function.
+fn stub(file: &Path) -> TResult<()> {
+    let contents = std::fs::read_to_string(file).map_err(|error| CargoMSRVError::Io {
+        error,
+        source: IoErrorSource::ReadFile(file.to_path_buf()),
+    })?;
+
+    let stub = if contents.contains("fn main(") {
+        STUBBED_BINARY
+    } else {
+        STUBBED_MODULE
+    };
+
+    std::fs::write(file, stub).map_err(|error| CargoMSRVError::Io {
+        error,
+        source: IoErrorSource::WriteFile(file.to_path_buf()),
+    })
+}
+
+fn canonicalize(path: &Path) -> TResult<PathBuf> {
+    path.canonicalize().map_err(|error| CargoMSRVError::Io {
+        error,
+        source: IoErrorSource::ReadDir(path.to_path_buf()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::long_path::sandbox_path;
+    use test_dir::{DirBuilder, TestDir};
+
+    fn write(dir: &Path, path: &str, contents: &str) {
+        let path = dir.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    }
+
+    fn crate_dir(tmp: &TestDir) -> PathBuf {
+        let dir = tmp.path("crate");
+        write(&dir, "Cargo.toml", "[package]\nname = \"a\"\n");
+        write(&dir, "src/lib.rs", "mod nightly;\n");
+        write(&dir, "src/nightly.rs", "#![feature(never_type)]\n");
+        write(&dir, "examples/stable.rs", "fn main() {}\n");
+        write(&dir, "examples/nightly_gat.rs", "fn main() { gat() }\n");
+        write(&dir, "examples/nightly/main.rs", "fn main() { never() }\n");
+        dir
+    }
+
+    #[yare::parameterized(
+        file = { "src/nightly.rs", &["src/nightly.rs"] },
+        wildcard = { "examples/nightly*", &["examples/nightly/main.rs", "examples/nightly_gat.rs"] },
+        directory = { "./examples/", &["examples/nightly/main.rs", "examples/nightly_gat.rs", "examples/stable.rs"] },
+        none = { "benches/*.rs", &[] },
+    )]
+    fn matching(pattern: &str, expected: &[&str]) {
+        let tmp = TestDir::temp();
+        let dir = crate_dir(&tmp);
+
+        let matched = matching_files(&dir, pattern).unwrap();
+
+        assert_eq!(
+            matched,
+            expected.iter().map(PathBuf::from).collect::<Vec<_>>()
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symbolic_links_are_not_followed() {
+        let tmp = TestDir::temp();
+        let dir = crate_dir(&tmp);
+        write(tmp.root(), "outside/lib.rs", "");
+
+        std::os::unix::fs::symlink(&dir, dir.join("examples/parent")).unwrap();
+        std::os::unix::fs::symlink(tmp.path("outside"), dir.join("src/outside")).unwrap();
+
+        let matched = matching_files(&dir, "*").unwrap();
+
+        assert!(matched.iter().all(|file| {
+            !file.starts_with("examples/parent") && !file.starts_with("src/outside")
+        }));
+        assert_eq!(matched.len(), 5);
+    }
+
+    #[test]
+    fn stubbed_copy_of_crate() {
+        let tmp = TestDir::temp();
+        let dir = crate_dir(&tmp);
+        let patterns = vec![
+            "src/nightly.rs".to_string(),
+            "examples/nightly_*".to_string(),
+        ];
+
+        let copy = StubbedCopy::create(&dir, &patterns, tmp.path("copy")).unwrap();
+
+        let read = |path: &str| std::fs::read_to_string(copy.crate_dir().join(path)).unwrap();
+        assert_eq!(read("src/nightly.rs"), STUBBED_MODULE);
+        assert_eq!(read("examples/nightly_gat.rs"), STUBBED_BINARY);
+        assert_eq!(read("examples/stable.rs"), "fn main() {}\n");
+
+        // the crate itself is left untouched
+        assert_eq!(
+            std::fs::read_to_string(dir.join("src/nightly.rs")).unwrap(),
+            "#![feature(never_type)]\n"
+        );
+
+        drop(copy);
+        assert!(!tmp.path("copy").exists());
+    }
+
+    #[test]
+    fn stubbed_copy_of_workspace_member() {
+        let tmp = TestDir::temp();
+        write(
+            tmp.root(),
+            "ws/Cargo.toml",
+            "[workspace]\nmembers = [\"a\"]\n",
+        );
+        write(tmp.root(), "ws/a/Cargo.toml", "[package]\nname = \"a\"\n");
+        write(tmp.root(), "ws/a/src/lib.rs", "");
+        write(tmp.root(), "ws/a/examples/nightly.rs", "fn main() {}\n");
+
+        let copy = StubbedCopy::create(
+            &tmp.path("ws/a"),
+            &["examples/nightly.rs".to_string()],
+            tmp.path("copy"),
+        )
+        .unwrap();
+
+        assert_eq!(copy.crate_dir(), tmp.path("copy").join("a"));
+        assert!(tmp.path("copy").join("Cargo.toml").is_file());
+        assert_eq!(copy.stubbed(), &[PathBuf::from("examples/nightly.rs")]);
+    }
+
+    #[test]
+    fn stubbed_copy_of_deep_workspace() {
+        let tmp = TestDir::temp();
+
+        // deep enough to exceed the path length limit of the Windows API, without a prefix
+        let member = (0..12)
+            .map(|level| format!("member-with-a-long-name-{}", level))
+            .collect::<Vec<_>>()
+            .join("/");
+
+        write(
+            tmp.root(),
+            "ws/Cargo.toml",
+            &format!("[workspace]\nmembers = [\"{}\"]\n", member),
+        );
+        write(
+            tmp.root(),
+            &format!("ws/{}/Cargo.toml", member),
+            "[package]\nname = \"a\"\n",
+        );
+        write(tmp.root(), &format!("ws/{}/src/lib.rs", member), "");
+        write(
+            tmp.root(),
+            &format!("ws/{}/examples/nightly.rs", member),
+            "fn main() {}\n",
+        );
+
+        let copy = StubbedCopy::create(
+            &tmp.path("ws").join(&member),
+            &["examples/nightly.rs".to_string()],
+            sandbox_path(Some(tmp.root()), "cargo-msrv-stubbed"),
+        )
+        .unwrap();
+
+        assert!(copy.crate_dir().join("src").join("lib.rs").is_file());
+        assert_eq!(
+            std::fs::read_to_string(copy.crate_dir().join("examples").join("nightly.rs")).unwrap(),
+            STUBBED_BINARY
+        );
+    }
+
+    #[test]
+    fn pattern_without_matches() {
+        let tmp = TestDir::temp();
+        let dir = crate_dir(&tmp);
+
+        let result = StubbedCopy::create(&dir, &["benches/*".to_string()], tmp.path("copy"));
+
+        assert!(matches!(result, Err(CargoMSRVError::InvalidConfig(_))));
+        assert!(!tmp.path("copy").exists());
+    }
+}
@@ -3,15 +3,34 @@
 /// Use case:
 ///
 /// * Run `cargo msrv verify` on the CI, to verify the crates MSRV is acceptable.
-pub use {find::Find, list::List, set::Set, show::Show, verify::Verify};
+pub use {
+    configuration::Configuration, discover::Discover, edition::Edition, find::Find,
+    history::History, impact::Impact, last_failure::LastFailure, list::List, lower::Lower,
+    matrix::Matrix, policy::Policy, releases::Releases, report::Report, schema::Schema,
+    serve::Serve, set::Set, show::Show, validate::Validate, verify::Verify,
+};
 
 use crate::reporter::Reporter;
 use crate::{Config, TResult};
 
+pub(crate) mod configuration;
+pub(crate) mod discover;
+pub(crate) mod edition;
 pub(crate) mod find;
+pub(crate) mod history;
+pub(crate) mod impact;
+pub(crate) mod last_failure;
 pub(crate) mod list;
+pub(crate) mod lower;
+pub(crate) mod matrix;
+pub(crate) mod policy;
+pub(crate) mod releases;
+pub(crate) mod report;
+pub(crate) mod schema;
+pub(crate) mod serve;
 pub(crate) mod set;
 pub(crate) mod show;
+pub(crate) mod validate;
 pub(crate) mod verify;
 
 /// A sub-command of `cargo-msrv`.
@@ -0,0 +1,323 @@
+use crate::config::configuration::ConfigurationCmdConfig;
+use crate::config::preferences::{preferences_path, Preferences};
+use crate::config::{Config, TracingTargetOption};
+use crate::error::{CargoMSRVError, TResult};
+use crate::manifest::manifest_patch::ManifestPatch;
+use crate::reporter::event::{ConfigOption, ConfigValue, PreferenceSet, ResolvedConfig};
+use crate::reporter::Reporter;
+use crate::SubCommand;
+use std::path::Path;
+
+/// Inspects the configuration of cargo-msrv, e.g. to find out why it behaves differently in CI
+/// than locally.
+#[derive(Default)]
+pub struct Configuration;
+
+impl SubCommand for Configuration {
+    type Output = ();
+
+    fn run(&self, config: &Config, reporter: &impl Reporter) -> TResult<Self::Output> {
+        match config.sub_command_config().configuration() {
+            ConfigurationCmdConfig::Show => {
+                reporter.report_event(ResolvedConfig::new(
+                    options(config),
+                    config.config_files().to_vec(),
+                ))?;
+            }
+            ConfigurationCmdConfig::Set { key, value } => {
+                let path = preferences_path().ok_or_else(|| {
+                    CargoMSRVError::InvalidConfig(
+                        "Unable to determine the directory of the preferences file".to_string(),
+                    )
+                })?;
+
+                Preferences::write(&path, key, value)?;
+                reporter.report_event(PreferenceSet::new(key.as_str(), value.as_str(), path))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The resolved configuration options, with the source of each value.
+fn options(config: &Config) -> Vec<ConfigOption> {
+    let option = |key: &'static str, value: ConfigValue| {
+        ConfigOption::new(key, value, config.value_sources().get(key))
+    };
+
+    let path = |path: Option<&Path>| path.map(|path| path.display().to_string());
+    let tracing = config.tracing();
+    let rotation = tracing.map(|tracing| tracing.rotation());
+
+    vec![
+        option("target", config.target().as_str().into()),
+        option("host", config.host().into()),
+        option(
+            "check_profile",
+            config
+                .check_profile()
+                .map(|profile| profile.to_string())
+                .into(),
+        ),
+        option("runner", config.check_runner().to_string().into()),
+        option(
+            "install_profile",
+            config.install_profile().to_string().into(),
+        ),
+        option("remote_check", config.remote_check().into()),
+        option("rustc_wrapper", config.rustc_wrapper().into()),
+        option("check_command", config.check_command_string().into()),
+        option(
+            "cargo_flags",
+            config
+                .cargo_flags()
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .into(),
+        ),
+        option("bin_policy", config.bin_policy().to_string().into()),
+        option(
+            "dependency_kinds",
+            config
+                .dependency_kinds()
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .into(),
+        ),
+        option("doc_tests", config.doc_tests().to_string().into()),
+        option(
+            "retries_on_failure",
+            u64::from(config.retries_on_failure()).into(),
+        ),
+        option("reuse_unit_graph", config.reuse_unit_graph().into()),
+        option("verbose", config.verbose().into()),
+        option(
+            "manifest_patch",
+            path(config.manifest_patch().map(ManifestPatch::path)).into(),
+        ),
+        option("target_dir", config.target_dir().into()),
+        option("clean_after", config.clean_after().to_string().into()),
+        option("before_check", config.check_hooks().before().into()),
+        option("after_check", config.check_hooks().after().into()),
+        option("path", path(config.crate_path()).into()),
+        option("manifest_path", path(config.manifest_path()).into()),
+        option(
+            "at_rev",
+            config.worktree().map(|worktree| worktree.revision()).into(),
+        ),
+        option(
+            "exclude_paths",
+            config
+                .stubbed_copy()
+                .map(|copy| copy.patterns().to_vec())
+                .unwrap_or_default()
+                .into(),
+        ),
+        option(
+            "include_all_patch_releases",
+            config.include_all_patch_releases().into(),
+        ),
+        option(
+            "version_resolution",
+            config.version_resolution().to_string().into(),
+        ),
+        option(
+            "minimum_version",
+            config.minimum_version().map(ToString::to_string).into(),
+        ),
+        option(
+            "maximum_version",
+            config.maximum_version().map(ToString::to_string).into(),
+        ),
+        option(
+            "excluded_versions",
+            config
+                .excluded_versions()
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .into(),
+        ),
+        option(
+            "search_method",
+            Into::<&'static str>::into(config.search_method()).into(),
+        ),
+        option("invert", config.polarity().is_inverted().into()),
+        option("explain_search", config.explain_search().into()),
+        option("max_checks", config.max_checks().into()),
+        option(
+            "known_good",
+            config
+                .search_seeds()
+                .known_good
+                .as_ref()
+                .map(ToString::to_string)
+                .into(),
+        ),
+        option(
+            "known_bad",
+            config
+                .search_seeds()
+                .known_bad
+                .as_ref()
+                .map(ToString::to_string)
+                .into(),
+        ),
+        option(
+            "write_toolchain_file",
+            config.output_toolchain_file().into(),
+        ),
+        option(
+            "toolchain_file_format",
+            config.toolchain_file_format().to_string().into(),
+        ),
+        option("write_msrv", config.write_msrv().into()),
+        option("prefer_newest_patch", config.prefer_newest_patch().into()),
+        option("write_result", path(config.write_result()).into()),
+        option("ignore_lockfile", config.ignore_lockfile().into()),
+        option(
+            "lockfile_policy",
+            config.lockfile_policy().to_string().into(),
+        ),
+        option("fetch_once", config.fetch_once().into()),
+        option("prefetch_toolchains", config.prefetch_toolchains().into()),
+        option("output_format", config.output_format().to_string().into()),
+        option("locale", config.locale().to_string().into()),
+        option("color", config.color().to_string().into()),
+        option("release_source", config.release_source().to_string().into()),
+        // The token itself is never shown, since the output may end up in CI logs
+        option(
+            "github_token",
+            config.github_token().map(|_| "<redacted>").into(),
+        ),
+        option(
+            "max_index_age",
+            (config.max_index_age().as_secs() / 3600).into(),
+        ),
+        option("scratch_dir", path(config.scratch_dir()).into()),
+        option("sandbox_dir", path(config.sandbox_root()).into()),
+        option("setup", config.setup().into()),
+        option("server", config.server().into()),
+        option(
+            "log_target",
+            tracing
+                .map_or("none", |tracing| log_target(tracing.target()))
+                .into(),
+        ),
+        option(
+            "log_level",
+            tracing.map(|tracing| tracing.level().to_string()).into(),
+        ),
+        option(
+            "log_max_size",
+            rotation
+                .map(|rotation| rotation.max_size().map_or(0, |bytes| bytes / 1024 / 1024))
+                .into(),
+        ),
+        option(
+            "log_max_age",
+            rotation
+                .map(|rotation| rotation.max_age().map_or(0, |age| age.as_secs() / 86_400))
+                .into(),
+        ),
+        option(
+            "notify_webhook",
+            config.webhook().map(|webhook| webhook.url()).into(),
+        ),
+        option(
+            "notify_compatibility",
+            config
+                .webhook()
+                .map_or(false, |webhook| webhook.include_compatibility())
+                .into(),
+        ),
+        option("otel_endpoint", config.otel_endpoint().into()),
+        option(
+            "events",
+            config
+                .event_filter()
+                .map(|filter| filter.kinds().to_vec())
+                .into(),
+        ),
+        option("validate_output", config.validate_output().into()),
+        option("no_check_feedback", config.no_check_feedback().into()),
+    ]
+}
+
+fn log_target(target: &TracingTargetOption) -> &'static str {
+    match target {
+        TracingTargetOption::File => TracingTargetOption::FILE,
+        TracingTargetOption::Stdout => TracingTargetOption::STDOUT,
+        TracingTargetOption::Stderr => TracingTargetOption::STDERR,
+        TracingTargetOption::Both => TracingTargetOption::BOTH,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::value_source::ValueSource;
+    use crate::config::{ConfigBuilder, SubCommandConfig};
+    use crate::reporter::event::Message;
+    use crate::reporter::TestReporter;
+    use crate::{Action, Event};
+
+    #[test]
+    fn reports_values_with_source() {
+        let config = ConfigBuilder::new(Action::Config, "x86_64-unknown-linux-gnu")
+            .write_msrv(true)
+            .value_source(
+                "write_msrv",
+                ValueSource::CommandLine {
+                    flag: "--write-msrv".to_string(),
+                },
+            )
+            .sub_command_config(SubCommandConfig::ConfigurationConfig(
+                ConfigurationCmdConfig::Show,
+            ))
+            .build();
+
+        let reporter = TestReporter::default();
+        Configuration::default()
+            .run(&config, reporter.reporter())
+            .unwrap();
+
+        let events = reporter.wait_for_events();
+        let resolved = events
+            .iter()
+            .find_map(|event: &Event| match event.message() {
+                Message::ResolvedConfig(resolved) => Some(resolved),
+                _ => None,
+            })
+            .unwrap();
+
+        let get = |key: &str| {
+            resolved
+                .options()
+                .iter()
+                .find(|option| option.key() == key)
+                .unwrap()
+        };
+
+        assert_eq!(
+            get("target").value(),
+            &ConfigValue::String("x86_64-unknown-linux-gnu".to_string())
+        );
+        assert_eq!(get("target").source(), &ValueSource::Default);
+        assert_eq!(get("write_msrv").value(), &ConfigValue::Bool(true));
+        assert_eq!(
+            get("write_msrv").source(),
+            &ValueSource::CommandLine {
+                flag: "--write-msrv".to_string()
+            }
+        );
+        assert_eq!(get("maximum_version").value(), &ConfigValue::Unset);
+        assert_eq!(
+            get("log_target").value(),
+            &ConfigValue::String("none".to_string())
+        );
+    }
+}
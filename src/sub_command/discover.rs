@@ -0,0 +1,402 @@
+//! Discovers the Cargo projects in a directory tree, like a repository which holds several
+//! unrelated crates and workspaces, which may be nested.
+//!
+//! Target directories, hidden directories and vendored sources are not scanned, since the
+//! manifests they hold are not projects of their own.
+
+use std::convert::TryFrom;
+use std::path::{Path, PathBuf};
+
+use rust_releases::ReleaseIndex;
+use toml_edit::{Document, Item};
+
+use crate::check::Check;
+use crate::config::discover::DiscoverRun;
+use crate::config::verify::VerifyCmdConfig;
+use crate::config::{Config, ConfigBuilder, SubCommandConfig};
+use crate::error::{CargoMSRVError, IoErrorSource, TResult};
+use crate::fingerprint::Fingerprint;
+use crate::manifest::bare_version::BareVersion;
+use crate::manifest::version_bump::BumpPolicy;
+use crate::manifest::workspace::{is_virtual, is_workspace, member_manifests};
+use crate::manifest::{syntax, CargoManifest};
+use crate::reporter::event::{
+    DiscoveredProject, DiscoveredProjects, Progress, ProgressUnit, ProjectKind,
+};
+use crate::reporter::Reporter;
+use crate::sub_command::{Find, SubCommand, Verify};
+
+/// The file which cargo writes to each crate in a directory of vendored sources.
+const VENDORED_CHECKSUM: &str = ".cargo-checksum.json";
+
+#[derive(Default)]
+pub struct Discover;
+
+impl SubCommand for Discover {
+    type Output = Vec<DiscoveredProject>;
+
+    fn run(&self, config: &Config, reporter: &impl Reporter) -> TResult<Self::Output> {
+        let root = config.context().crate_root_path()?;
+        let projects = discover(root)?;
+
+        reporter.report_event(DiscoveredProjects::new(root, projects.clone()))?;
+
+        Ok(projects)
+    }
+}
+
+/// Find or verify the MSRV of each discovered package, with a runner made by `runner`.
+///
+/// Only packages which declare an MSRV are verified. A package which fails does not stop the
+/// others; a cancelled run does stop.
+pub(crate) fn run_discovered<C: Check>(
+    config: &Config,
+    reporter: &impl Reporter,
+    index: &ReleaseIndex,
+    projects: &[DiscoveredProject],
+    run: DiscoverRun,
+    runner: impl Fn() -> C,
+) -> TResult<()> {
+    let root = config.context().crate_root_path()?;
+    let mut total = 0;
+    let mut failed = 0;
+
+    let packages = projects
+        .iter()
+        .filter(|project| project.is_package())
+        .filter(|project| {
+            let skipped = run == DiscoverRun::Verify && project.msrv().is_none();
+
+            if skipped {
+                info!(path = %project.path().display(), "not verifying project without an msrv");
+            }
+
+            !skipped
+        })
+        .collect::<Vec<_>>();
+
+    for (n, project) in packages.iter().enumerate() {
+        let msrv = project.msrv();
+        let crate_path = root.join(project.path());
+
+        info!(crate_path = %crate_path.display(), action = %run, "running discovered project");
+
+        let name = project
+            .name()
+            .map(String::from)
+            .unwrap_or_else(|| project.path().display().to_string());
+        let unit = ProgressUnit::new(name, n as u64 + 1, packages.len() as u64);
+        reporter.report_event(Progress::start_of_unit(unit.clone()))?;
+
+        let result = project_config(config, run, &crate_path, msrv).and_then(|project_config| {
+            let project_config = ConfigBuilder::from_config(&project_config)
+                .progress_unit(Some(unit))
+                .build();
+
+            match run {
+                DiscoverRun::Find => {
+                    let fingerprint = Fingerprint::collect(&project_config);
+                    Find::new(index, runner())
+                        .with_fingerprint(fingerprint)
+                        .run(&project_config, reporter)
+                        .map(|_| ())
+                }
+                DiscoverRun::Verify => Verify::new(index, runner()).run(&project_config, reporter),
+            }
+        });
+
+        total += 1;
+
+        match result {
+            Err(CargoMSRVError::Cancelled) => return Err(CargoMSRVError::Cancelled),
+            Err(error) => {
+                warn!(crate_path = %crate_path.display(), %error, "discovered project failed");
+                failed += 1;
+            }
+            Ok(()) => {}
+        }
+    }
+
+    if failed > 0 {
+        Err(CargoMSRVError::SubCommandDiscover(Error::ProjectsFailed {
+            run,
+            failed,
+            total,
+        }))
+    } else {
+        Ok(())
+    }
+}
+
+/// The configuration of `discover`, applied to a single project.
+pub(crate) fn project_config<'c>(
+    config: &'c Config,
+    run: DiscoverRun,
+    crate_path: &Path,
+    msrv: Option<&BareVersion>,
+) -> TResult<Config<'c>> {
+    let manifest = crate_path.join("Cargo.toml");
+
+    // The MSRV is given, since it may be inherited from the workspace
+    let sub_command_config = match run {
+        DiscoverRun::Find => SubCommandConfig::None,
+        DiscoverRun::Verify => SubCommandConfig::VerifyConfig(VerifyCmdConfig {
+            rust_version: msrv.cloned(),
+            against_result: None,
+            write_on_success: false,
+            pin_patch: false,
+            changed_since: None,
+            check_published_metadata: false,
+        }),
+    };
+
+    Ok(ConfigBuilder::from_config(config)
+        .mode_intent(run.action())
+        .crate_path(Some(crate_path))
+        .sub_command_config(sub_command_config)
+        .manifest_requirement(syntax::probe(&manifest)?)
+        .bump_policy(BumpPolicy::read(&manifest)?)
+        .declared_msrv(msrv.cloned())
+        .build())
+}
+
+/// The Cargo projects in the directory tree of `root`, ordered by their path.
+pub(crate) fn discover(root: &Path) -> TResult<Vec<DiscoveredProject>> {
+    let mut manifests = Vec::new();
+    scan(root, &mut manifests)?;
+
+    // Each member of a workspace, by the directory of the workspace root
+    let mut members = Vec::new();
+
+    for (dir, document) in manifests.iter().filter(|(_, doc)| is_workspace(doc)) {
+        for manifest in member_manifests(dir, document)? {
+            if let Some(member) = manifest.parent() {
+                members.push((member.to_path_buf(), dir.as_path(), document));
+            }
+        }
+    }
+
+    let relative = |dir: &Path| match dir.strip_prefix(root) {
+        Ok(path) if path.as_os_str().is_empty() => PathBuf::from("."),
+        Ok(path) => path.to_path_buf(),
+        Err(_) => dir.to_path_buf(),
+    };
+
+    let projects = manifests
+        .iter()
+        .map(|(dir, document)| {
+            let workspace = members.iter().find(|(member, _, _)| member == dir);
+
+            let kind = if is_virtual(document) {
+                ProjectKind::VirtualWorkspace
+            } else if is_workspace(document) {
+                ProjectKind::Workspace
+            } else {
+                ProjectKind::Package
+            };
+
+            let msrv = declared_msrv(document).or_else(|| {
+                workspace
+                    .filter(|_| inherits_msrv(document))
+                    .and_then(|(_, _, workspace)| workspace_msrv(workspace))
+            });
+
+            DiscoveredProject::new(
+                relative(dir),
+                kind,
+                package_name(document),
+                msrv,
+                workspace.map(|(_, workspace_root, _)| relative(workspace_root)),
+            )
+        })
+        .collect();
+
+    Ok(projects)
+}
+
+/// Collect the directories with a Cargo manifest, depth first and ordered by name. The projects
+/// below a project are collected too, since projects may be nested.
+fn scan(dir: &Path, manifests: &mut Vec<(PathBuf, Document)>) -> TResult<()> {
+    let manifest = dir.join("Cargo.toml");
+
+    if manifest.is_file() {
+        let contents = std::fs::read_to_string(&manifest).map_err(|error| CargoMSRVError::Io {
+            error,
+            source: IoErrorSource::ReadFile(manifest.clone()),
+        })?;
+
+        // A manifest which can't be parsed, e.g. a template, is not a project
+        match contents.parse::<Document>() {
+            Ok(document) => manifests.push((dir.to_path_buf(), document)),
+            Err(error) => warn!(manifest = %manifest.display(), %error, "skipping manifest"),
+        }
+    }
+
+    let entries = std::fs::read_dir(dir).map_err(|error| CargoMSRVError::Io {
+        error,
+        source: IoErrorSource::ReadDir(dir.to_path_buf()),
+    })?;
+
+    // Symbolic links are not followed, so a link can't make the scan go round in circles
+    let mut directories = entries
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().map_or(false, |kind| kind.is_dir()))
+        .map(|entry| entry.path())
+        .filter(|path| !is_skipped(path))
+        .collect::<Vec<_>>();
+
+    directories.sort();
+
+    for directory in directories {
+        scan(&directory, manifests)?;
+    }
+
+    Ok(())
+}
+
+/// Whether the directory is a target directory, a hidden directory, or a vendored crate.
+fn is_skipped(dir: &Path) -> bool {
+    let name = dir.file_name().and_then(|name| name.to_str()).unwrap_or("");
+
+    name == "target" || name.starts_with('.') || dir.join(VENDORED_CHECKSUM).is_file()
+}
+
+fn package_name(document: &Document) -> Option<String> {
+    document
+        .as_table()
+        .get("package")
+        .and_then(|package| package.get("name"))
+        .and_then(Item::as_str)
+        .map(String::from)
+}
+
+pub(crate) fn declared_msrv(document: &Document) -> Option<BareVersion> {
+    CargoManifest::try_from(document.clone())
+        .ok()
+        .and_then(|manifest| manifest.minimum_rust_version().cloned())
+}
+
+/// Whether the package inherits its MSRV from the workspace, with `rust-version.workspace = true`.
+pub(crate) fn inherits_msrv(document: &Document) -> bool {
+    document
+        .as_table()
+        .get("package")
+        .and_then(|package| package.get("rust-version"))
+        .and_then(|rust_version| rust_version.get("workspace"))
+        .and_then(Item::as_bool)
+        .unwrap_or_default()
+}
+
+/// The MSRV which the members of a workspace may inherit, `workspace.package.rust-version`.
+pub(crate) fn workspace_msrv(document: &Document) -> Option<BareVersion> {
+    document
+        .as_table()
+        .get("workspace")
+        .and_then(|workspace| workspace.get("package"))
+        .and_then(|package| package.get("rust-version"))
+        .and_then(Item::as_str)
+        .and_then(|version| version.parse().ok())
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Unable to {run} the MSRV of {failed} out of {total} discovered packages")]
+    ProjectsFailed {
+        run: DiscoverRun,
+        failed: usize,
+        total: usize,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_dir::{DirBuilder, TestDir};
+
+    fn manifest(tmp: &TestDir, dir: &str, contents: &str) {
+        std::fs::create_dir_all(tmp.path(dir)).unwrap();
+        std::fs::write(tmp.path(dir).join("Cargo.toml"), contents).unwrap();
+    }
+
+    fn summary(projects: &[DiscoveredProject]) -> Vec<String> {
+        projects.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn nested_projects() {
+        let tmp = TestDir::temp();
+        manifest(
+            &tmp,
+            "app",
+            "[package]\nname = \"app\"\nrust-version = \"1.60\"\n\n[workspace]\nmembers = [\"crates/*\"]\n\n[workspace.package]\nrust-version = \"1.58\"\n",
+        );
+        manifest(
+            &tmp,
+            "app/crates/core",
+            "[package]\nname = \"core\"\nrust-version.workspace = true\n",
+        );
+        manifest(&tmp, "lib", "[package]\nname = \"lib\"\n");
+        manifest(&tmp, "tools", "[workspace]\nmembers = []\n");
+
+        let projects = discover(tmp.root()).unwrap();
+
+        assert_eq!(
+            summary(&projects),
+            vec![
+                "app: workspace 'app', MSRV 1.60",
+                "app/crates/core: package 'core' (member of app), MSRV 1.58",
+                "lib: package 'lib', no MSRV",
+                "tools: virtual workspace, no MSRV",
+            ]
+        );
+    }
+
+    #[test]
+    fn root_project() {
+        let tmp = TestDir::temp();
+        manifest(
+            &tmp,
+            ".",
+            "[package]\nname = \"a\"\nrust-version = \"1.56\"\n",
+        );
+
+        let projects = discover(tmp.root()).unwrap();
+
+        assert_eq!(summary(&projects), vec![".: package 'a', MSRV 1.56"]);
+    }
+
+    #[test]
+    fn skipped_directories() {
+        let tmp = TestDir::temp();
+        manifest(&tmp, "a", "[package]\nname = \"a\"\n");
+        manifest(
+            &tmp,
+            "a/target/package/a-0.1.0",
+            "[package]\nname = \"a\"\n",
+        );
+        manifest(&tmp, ".git/hooks", "[package]\nname = \"hook\"\n");
+        manifest(&tmp, "vendor/serde", "[package]\nname = \"serde\"\n");
+        std::fs::write(tmp.path("vendor/serde").join(VENDORED_CHECKSUM), "{}").unwrap();
+
+        let projects = discover(tmp.root()).unwrap();
+
+        assert_eq!(summary(&projects), vec!["a: package 'a', no MSRV"]);
+    }
+
+    #[test]
+    fn unparsable_manifest() {
+        let tmp = TestDir::temp();
+        manifest(&tmp, "template", "[package]\nname = {{ name }}\n");
+
+        assert!(discover(tmp.root()).unwrap().is_empty());
+    }
+
+    #[yare::parameterized(
+        inherited = { "[package]\nrust-version.workspace = true\n", true },
+        declared = { "[package]\nrust-version = \"1.56\"\n", false },
+        none = { "[package]\nname = \"a\"\n", false },
+    )]
+    fn inherited_msrv(contents: &str, expected: bool) {
+        assert_eq!(inherits_msrv(&contents.parse().unwrap()), expected);
+    }
+}
@@ -0,0 +1,135 @@
+use std::convert::TryFrom;
+use std::path::Path;
+
+use toml_edit::Document;
+
+use crate::cli::rust_releases_opts::Edition as RustEdition;
+use crate::command::RustupCommand;
+use crate::config::Config;
+use crate::crate_copy::copy_crate;
+use crate::download::{DownloadToolchain, InstalledToolchains, ToolchainDownloader};
+use crate::error::{CargoMSRVError, IoErrorSource, TResult};
+use crate::long_path::Sandbox;
+use crate::manifest::bare_version::BareVersion;
+use crate::manifest::{read_edition, CargoManifest, CargoManifestParser, TomlParser};
+use crate::reporter::event::{EditionAdvice, MigrationCheck};
+use crate::reporter::Reporter;
+use crate::semver;
+use crate::SubCommand;
+
+/// Determines which editions are compatible with the MSRV of the crate, and optionally confirms
+/// that the crate can be migrated to the next edition.
+#[derive(Default)]
+pub struct Edition;
+
+impl SubCommand for Edition {
+    type Output = ();
+
+    fn run(&self, config: &Config, reporter: &impl Reporter) -> TResult<Self::Output> {
+        advise_edition(config, reporter)
+    }
+}
+
+fn advise_edition(config: &Config, reporter: &impl Reporter) -> TResult<()> {
+    let cargo_toml = config.context().manifest_path()?;
+
+    let contents = std::fs::read_to_string(cargo_toml).map_err(|error| CargoMSRVError::Io {
+        error,
+        source: IoErrorSource::ReadFile(cargo_toml.to_path_buf()),
+    })?;
+
+    let manifest = CargoManifestParser::default().parse::<Document>(&contents)?;
+    let manifest = CargoManifest::try_from(manifest)?;
+    let msrv = manifest.minimum_rust_version().cloned();
+
+    // Cargo defaults to the 2015 edition, when none is specified
+    let edition = read_edition(cargo_toml)?.unwrap_or(RustEdition::Edition2015);
+
+    let mut advice = EditionAdvice::new(edition, msrv.clone());
+
+    if config.sub_command_config().edition().check_migration {
+        if let Some(next) = edition.next() {
+            let migration = check_migration(config, reporter, next, msrv.as_ref())?;
+            advice = advice.with_migration(migration);
+        }
+    }
+
+    reporter.report_event(advice)?;
+
+    Ok(())
+}
+
+/// Migrate a copy of the crate to the given edition with `cargo fix --edition`, using the MSRV
+/// toolchain, or, if the MSRV does not support the edition, the first toolchain which does.
+fn check_migration(
+    config: &Config,
+    reporter: &impl Reporter,
+    edition: RustEdition,
+    msrv: Option<&BareVersion>,
+) -> TResult<MigrationCheck> {
+    let version = migration_version(edition, msrv);
+    let toolchain = config.toolchain_spec(&version);
+
+    let installed = InstalledToolchains::query();
+    ToolchainDownloader::new(reporter, &installed)
+        .with_optional_cancellation(config.cancellation_token())
+        .download(&toolchain)?;
+
+    let crate_root = config.context().crate_root_path()?;
+    let sandbox = Sandbox::new(config.sandbox_root(), "cargo-msrv-edition");
+    let copy = sandbox.path();
+
+    copy_crate(crate_root, copy)?;
+
+    // `cargo fix --edition` prepares the code of the current edition for the next edition
+    let output = RustupCommand::new()
+        .with_args([
+            toolchain.spec(),
+            "cargo",
+            "fix",
+            "--edition",
+            "--allow-no-vcs",
+        ])
+        .with_dir(copy)
+        .with_stderr()
+        .with_optional_cancellation(config.cancellation_token())
+        .run()?;
+
+    if output.exit_status().success() {
+        Ok(MigrationCheck::feasible(edition, version))
+    } else {
+        info!(stderr = output.stderr(), "edition migration failed");
+
+        Ok(MigrationCheck::infeasible(
+            edition,
+            version,
+            output.stderr().to_string(),
+        ))
+    }
+}
+
+fn migration_version(edition: RustEdition, msrv: Option<&BareVersion>) -> semver::Version {
+    let supported_since = edition.as_bare_version().to_semver_version();
+
+    msrv.map(BareVersion::to_semver_version)
+        .filter(|msrv| *msrv >= supported_since)
+        .unwrap_or(supported_since)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[yare::parameterized(
+        no_msrv = { RustEdition::Edition2021, None, semver::Version::new(1, 56, 0) },
+        msrv_before_edition = { RustEdition::Edition2021, Some(BareVersion::TwoComponents(1, 40)), semver::Version::new(1, 56, 0) },
+        msrv_after_edition = { RustEdition::Edition2018, Some(BareVersion::ThreeComponents(1, 40, 2)), semver::Version::new(1, 40, 2) },
+    )]
+    fn toolchain_of_migration(
+        edition: RustEdition,
+        msrv: Option<BareVersion>,
+        expected: semver::Version,
+    ) {
+        assert_eq!(migration_version(edition, msrv.as_ref()), expected);
+    }
+}
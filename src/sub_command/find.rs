@@ -1,21 +1,31 @@
 use rust_releases::{Release, ReleaseIndex};
 
-use crate::check::Check;
-use crate::config::{Config, SearchMethod};
+use crate::channel_manifest::ChannelManifests;
+use crate::check::{
+    BudgetedCheck, Check, CheckChain, ComponentAvailabilityCheck, RequiredRustVersionCheck,
+};
+use crate::config::{Config, ConfigBuilder, SearchMethod};
 use crate::error::{CargoMSRVError, TResult};
 use crate::filter_releases::filter_releases;
+use crate::fingerprint::Fingerprint;
 use crate::manifest::bare_version::BareVersion;
-use crate::msrv::MinimumSupportedRustVersion;
-use crate::reporter::event::MsrvResult;
+use crate::msrv::{newer_patch_release, MinimumSupportedRustVersion};
+use crate::reporter::event::{
+    DependencyCache, InconclusiveResult, ManifestSyntax, MsrvResult, SearchSpaceClamped,
+};
 use crate::reporter::Reporter;
-use crate::search_method::{Bisect, FindMinimalSupportedRustVersion, Linear};
+use crate::search_method::{Bisect, Exhaustive, FindMinimalSupportedRustVersion, Linear};
+use crate::writer::result_file::write_result_file;
 use crate::writer::toolchain_file::write_toolchain_file;
 use crate::writer::write_msrv::write_msrv;
 use crate::{semver, SubCommand};
 
+pub(crate) use batch::find_batch;
+
 pub struct Find<'index, C: Check> {
     release_index: &'index ReleaseIndex,
     runner: C,
+    fingerprint: Option<Fingerprint>,
 }
 
 impl<'index, C: Check> Find<'index, C> {
@@ -23,15 +33,28 @@ impl<'index, C: Check> Find<'index, C> {
         Self {
             release_index,
             runner,
+            fingerprint: None,
         }
     }
+
+    /// Include the fingerprint of the environment in the reported result.
+    pub fn with_fingerprint(mut self, fingerprint: Fingerprint) -> Self {
+        self.fingerprint = Some(fingerprint);
+        self
+    }
 }
 
 impl<'index, C: Check> SubCommand for Find<'index, C> {
     type Output = semver::Version;
 
     fn run(&self, config: &Config, reporter: &impl Reporter) -> TResult<Self::Output> {
-        find_msrv(config, reporter, self.release_index, &self.runner)
+        find_msrv(
+            config,
+            reporter,
+            self.release_index,
+            &self.runner,
+            self.fingerprint.as_ref(),
+        )
     }
 }
 
@@ -40,16 +63,21 @@ fn find_msrv(
     reporter: &impl Reporter,
     release_index: &ReleaseIndex,
     runner: &impl Check,
+    fingerprint: Option<&Fingerprint>,
 ) -> TResult<semver::Version> {
-    let search_result = search(config, reporter, release_index, runner)?;
+    let search_result = search(config, reporter, release_index, runner, fingerprint)?;
 
     match &search_result {
         MinimumSupportedRustVersion::NoCompatibleToolchain => {
             info!("no minimal-compatible toolchain found");
 
-            Err(CargoMSRVError::UnableToFindAnyGoodVersion {
-                command: config.check_command_string(),
-            })
+            let command = config.check_command_string();
+
+            if config.polarity().is_inverted() {
+                Err(CargoMSRVError::UnableToFindRegression { command })
+            } else {
+                Err(CargoMSRVError::UnableToFindAnyGoodVersion { command })
+            }
         }
         MinimumSupportedRustVersion::Toolchain { toolchain } => {
             info!(
@@ -62,7 +90,11 @@ fn find_msrv(
             }
 
             if config.write_msrv() {
-                write_msrv(config, reporter, toolchain.version())?;
+                write_msrv(config, reporter, release_index, toolchain.version())?;
+            }
+
+            if let Some(path) = config.write_result() {
+                write_result_file(config, reporter, path, toolchain.version())?;
             }
 
             Ok(toolchain.version().clone())
@@ -70,69 +102,247 @@ fn find_msrv(
     }
 }
 
-fn search(
+/// Search the MSRV among the releases of the index which are included by the configuration.
+pub(crate) fn search(
     config: &Config,
     reporter: &impl Reporter,
     index: &ReleaseIndex,
     runner: &impl Check,
+    fingerprint: Option<&Fingerprint>,
 ) -> TResult<MinimumSupportedRustVersion> {
     let releases = index.releases();
-    let included_releases = filter_releases(config, releases);
+    let mut included_releases = filter_releases(config, releases);
+
+    // the cargo of toolchains less recent than the manifest syntax requires can't parse the manifest
+    if let Some(requirement) = config.manifest_requirement() {
+        let readable_since = requirement.feature.readable_since();
+
+        if included_releases
+            .iter()
+            .any(|release| release.version() < &readable_since)
+        {
+            included_releases.retain(|release| release.version() >= &readable_since);
+            reporter.report_event(ManifestSyntax::new(requirement.clone()))?;
+        }
+    }
+
+    // the outcomes on the far side of a known outcome follow from it, so they're not checked
+    let seeds = config.search_seeds();
 
-    run_with_search_method(config, &included_releases, reporter, runner)
+    if !seeds.is_empty() {
+        let polarity = config.polarity();
+        included_releases.retain(|release| seeds.may_contain(polarity, release.version()));
+
+        info!(
+            ?seeds,
+            remaining = included_releases.len(),
+            "narrowed the search space to the known outcomes"
+        );
+    }
+
+    // the search stops once the budget given with `--max-checks` is exhausted
+    let budget = BudgetedCheck::new(config.max_checks());
+
+    // toolchains less recent than the Rust version which cargo requires, or which didn't ship a
+    // component which the check requires, are rejected unchecked, so they don't count towards
+    // the budget
+    let runner = &CheckChain::new(runner)
+        .layer(&budget)
+        .layer(ComponentAvailabilityCheck::new(
+            reporter,
+            ChannelManifests::new(config),
+        ))
+        .layer(RequiredRustVersionCheck::new(reporter));
+
+    loop {
+        match run_with_search_method(
+            config,
+            index,
+            &included_releases,
+            reporter,
+            runner,
+            fingerprint,
+        ) {
+            // Older toolchains don't support the target either, so only the newer releases remain
+            Err(CargoMSRVError::ToolchainUnavailable(toolchain)) => {
+                included_releases.retain(|release| release.version() > toolchain.version());
+
+                if included_releases.is_empty() {
+                    return Err(CargoMSRVError::ToolchainUnavailable(toolchain));
+                }
+
+                info!(
+                    %toolchain,
+                    "toolchain is unavailable for the target, clamping the search space"
+                );
+
+                // Not empty, so a least recent release exists
+                let minimum = included_releases[included_releases.len() - 1]
+                    .version()
+                    .clone();
+                reporter.report_event(SearchSpaceClamped::new(toolchain, minimum))?;
+            }
+            // The checks so far narrowed down the range in which the MSRV must lie
+            Err(CargoMSRVError::CheckBudgetExhausted { max_checks }) => {
+                reporter.report_event(InconclusiveResult::new(
+                    max_checks,
+                    budget.least_recent_accepted(),
+                    budget.most_recent_rejected(),
+                ))?;
+
+                return Err(CargoMSRVError::CheckBudgetExhausted { max_checks });
+            }
+            result => return result,
+        }
+    }
 }
 
 fn run_with_search_method(
     config: &Config,
+    index: &ReleaseIndex,
     included_releases: &[Release],
     reporter: &impl Reporter,
     runner: &impl Check,
+    fingerprint: Option<&Fingerprint>,
 ) -> TResult<MinimumSupportedRustVersion> {
-    let search_method = config.search_method();
+    let search_method = config.search_method().resolve(included_releases.len());
     info!(?search_method);
 
-    // Run a linear or binary search depending on the configuration
+    // `auto` starts the binary search at the MSRV which is declared in the manifest
+    let start = if config.search_method() == SearchMethod::Auto {
+        config
+            .declared_msrv()
+            .and_then(|msrv| {
+                msrv.try_to_semver(included_releases.iter().map(Release::version))
+                    .ok()
+            })
+            .cloned()
+    } else {
+        None
+    };
+
+    // The method picked by `auto` is reported as the search method which ran
+    let resolved;
+    let config = if search_method == config.search_method() {
+        config
+    } else {
+        resolved = ConfigBuilder::from_config(config)
+            .search_method(search_method)
+            .build();
+        &resolved
+    };
+
+    // Run a linear, binary or exhaustive search depending on the configuration
     match search_method {
-        SearchMethod::Linear => {
-            run_searcher(&Linear::new(runner), included_releases, config, reporter)
-        }
-        SearchMethod::Bisect => {
-            run_searcher(&Bisect::new(runner), included_releases, config, reporter)
-        }
+        SearchMethod::Linear => run_searcher(
+            &Linear::new(runner),
+            runner,
+            index,
+            included_releases,
+            config,
+            reporter,
+            fingerprint,
+        ),
+        SearchMethod::Bisect => run_searcher(
+            &Bisect::new(runner).starting_at(start),
+            runner,
+            index,
+            included_releases,
+            config,
+            reporter,
+            fingerprint,
+        ),
+        SearchMethod::Exhaustive => run_searcher(
+            &Exhaustive::new(runner),
+            runner,
+            index,
+            included_releases,
+            config,
+            reporter,
+            fingerprint,
+        ),
+        SearchMethod::Auto => unreachable!("the auto search method is resolved before searching"),
     }
 }
 
 fn run_searcher(
     method: &impl FindMinimalSupportedRustVersion,
+    runner: &impl Check,
+    index: &ReleaseIndex,
     releases: &[Release],
     config: &Config,
     reporter: &impl Reporter,
+    fingerprint: Option<&Fingerprint>,
 ) -> TResult<MinimumSupportedRustVersion> {
     let minimum_capable = method.find_toolchain(releases, config, reporter)?;
 
-    report_outcome(&minimum_capable, releases, config, reporter)?;
+    let dependency_cache = runner.dependency_cache();
+    report_outcome(
+        &minimum_capable,
+        index,
+        releases,
+        config,
+        reporter,
+        fingerprint,
+        dependency_cache,
+    )?;
 
     Ok(minimum_capable)
 }
 
 fn report_outcome(
     minimum_capable: &MinimumSupportedRustVersion,
+    index: &ReleaseIndex,
     releases: &[Release],
     config: &Config,
     reporter: &impl Reporter,
+    fingerprint: Option<&Fingerprint>,
+    dependency_cache: Option<DependencyCache>,
 ) -> TResult<()> {
     let (min, max) = min_max_releases(releases)?;
 
-    match minimum_capable {
+    let result = match minimum_capable {
         MinimumSupportedRustVersion::Toolchain { toolchain } => {
             let version = toolchain.version();
+            let result = MsrvResult::new_msrv(version.clone(), config, min, max);
 
-            reporter.report_event(MsrvResult::new_msrv(version.clone(), config, min, max))?;
-        }
-        MinimumSupportedRustVersion::NoCompatibleToolchain => {
-            reporter.report_event(MsrvResult::none(config, min, max))?;
+            // The first failing release of an inverted search is not an MSRV
+            let version_bump = config
+                .bump_policy()
+                .filter(|_| !config.polarity().is_inverted())
+                .and_then(|policy| policy.suggest(version));
+
+            let result = match version_bump {
+                Some(version_bump) => result.with_version_bump(version_bump),
+                None => result,
+            };
+
+            match newer_patch_release(index, version) {
+                Some(newer) => result.with_newer_patch_release(newer.clone()),
+                None => result,
+            }
         }
-    }
+        MinimumSupportedRustVersion::NoCompatibleToolchain => MsrvResult::none(config, min, max),
+    };
+
+    let result = match fingerprint {
+        Some(fingerprint) => result.with_fingerprint(fingerprint.clone()),
+        None => result,
+    };
+
+    let seeds = config.search_seeds();
+    let result = if seeds.is_empty() {
+        result
+    } else {
+        result.with_seeds(seeds.clone())
+    };
+
+    let result = match dependency_cache {
+        Some(dependency_cache) => result.with_dependency_cache(dependency_cache),
+        None => result,
+    };
+
+    reporter.report_event(result)?;
 
     Ok(())
 }
@@ -150,5 +360,6 @@ fn min_max_releases(rust_releases: &[Release]) -> TResult<(BareVersion, BareVers
     Ok((min.into(), max.into()))
 }
 
+mod batch;
 #[cfg(test)]
 mod tests;
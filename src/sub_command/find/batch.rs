@@ -0,0 +1,286 @@
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+
+use rust_releases::ReleaseIndex;
+
+use crate::check::Check;
+use crate::config::{BatchOptions, Config, ConfigBuilder};
+use crate::error::{CargoMSRVError, IoErrorSource, TResult};
+use crate::fingerprint::Fingerprint;
+use crate::manifest::version_bump::BumpPolicy;
+use crate::manifest::{edition_minimum_version, read_declared_msrv, syntax};
+use crate::reporter::Reporter;
+use crate::semver;
+
+use super::find_msrv;
+
+/// Find the MSRV of each crate whose path is read from `input`, one path per line.
+///
+/// The release index and the runner, and with it the installed toolchains, are shared between all
+/// crates. For each crate, a single line json record is written to `output`. A crate for which the
+/// MSRV can't be found does not stop the batch; the error is written to its record instead. A
+/// cancelled batch does stop.
+pub(crate) fn find_batch(
+    config: &Config,
+    options: &BatchOptions,
+    reporter: &impl Reporter,
+    index: &ReleaseIndex,
+    runner: &impl Check,
+    input: impl BufRead,
+    mut output: impl Write,
+) -> TResult<()> {
+    let mut total = 0;
+    let mut failed = 0;
+
+    for line in input.lines() {
+        let line = line.map_err(|error| CargoMSRVError::Io {
+            error,
+            source: IoErrorSource::ReadStdin,
+        })?;
+
+        let crate_path = Path::new(line.trim());
+
+        if crate_path.as_os_str().is_empty() {
+            continue;
+        }
+
+        info!(crate_path = %crate_path.display(), "finding msrv of crate in batch");
+
+        let result = crate_config(config, options, crate_path).and_then(|crate_config| {
+            let fingerprint = Fingerprint::collect(&crate_config);
+            find_msrv(&crate_config, reporter, index, runner, Some(&fingerprint))
+        });
+
+        // A cancelled batch stops, instead of failing each of the remaining crates
+        if let Err(CargoMSRVError::Cancelled) = result {
+            return Err(CargoMSRVError::Cancelled);
+        }
+
+        total += 1;
+
+        if result.is_err() {
+            failed += 1;
+        }
+
+        write_record(&mut output, &BatchRecord::new(crate_path, result))?;
+    }
+
+    if failed > 0 {
+        Err(CargoMSRVError::BatchFailed { failed, total })
+    } else {
+        Ok(())
+    }
+}
+
+/// The configuration of the batch, applied to a single crate.
+fn crate_config<'c>(
+    config: &'c Config,
+    options: &BatchOptions,
+    crate_path: &Path,
+) -> TResult<Config<'c>> {
+    let builder = ConfigBuilder::from_config(config).crate_path(Some(crate_path));
+
+    let manifest = crate_path.join("Cargo.toml");
+
+    let builder = if options.read_min_edition() {
+        match edition_minimum_version(&manifest)? {
+            Some(version) => builder.minimum_version(version),
+            None => builder,
+        }
+    } else {
+        builder
+    };
+
+    let requirement = if manifest.is_file() {
+        syntax::probe(&manifest)?
+    } else {
+        None
+    };
+
+    let policy = if manifest.is_file() {
+        BumpPolicy::read(&manifest)?
+    } else {
+        None
+    };
+
+    let declared_msrv = if manifest.is_file() {
+        read_declared_msrv(&manifest)?
+    } else {
+        None
+    };
+
+    Ok(builder
+        .manifest_requirement(requirement)
+        .bump_policy(policy)
+        .declared_msrv(declared_msrv)
+        .build())
+}
+
+fn write_record(output: &mut impl Write, record: &BatchRecord) -> TResult<()> {
+    serde_json::to_writer(&mut *output, record)
+        .map_err(io::Error::from)
+        .and_then(|_| writeln!(output))
+        .and_then(|_| output.flush())
+        .map_err(|error| CargoMSRVError::Io {
+            error,
+            source: IoErrorSource::WriteStdout,
+        })
+}
+
+/// The result of a single crate of the batch.
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+struct BatchRecord<'p> {
+    crate_path: &'p Path,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    msrv: Option<semver::Version>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl<'p> BatchRecord<'p> {
+    fn new(crate_path: &'p Path, result: TResult<semver::Version>) -> Self {
+        match result {
+            Ok(msrv) => Self {
+                crate_path,
+                msrv: Some(msrv),
+                error: None,
+            },
+            Err(error) => Self {
+                crate_path,
+                msrv: None,
+                error: Some(error.to_string()),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::TestRunner;
+    use crate::reporter::FakeTestReporter;
+    use crate::Action;
+    use crate::CancellationToken;
+    use rust_releases::Release;
+    use std::iter::FromIterator;
+    use test_dir::{DirBuilder, FileType, TestDir};
+
+    fn run_batch(input: &str) -> (TResult<()>, Vec<serde_json::Value>) {
+        let config = ConfigBuilder::new(Action::Find, "x86_64-unknown-linux-gnu").build();
+        run_batch_with_config(&config, input)
+    }
+
+    fn run_batch_with_config(
+        config: &Config,
+        input: &str,
+    ) -> (TResult<()>, Vec<serde_json::Value>) {
+        let index = ReleaseIndex::from_iter(vec![
+            Release::new_stable(semver::Version::new(1, 56, 0)),
+            Release::new_stable(semver::Version::new(1, 55, 0)),
+            Release::new_stable(semver::Version::new(1, 54, 0)),
+        ]);
+        let runner = TestRunner::with_ok(&[
+            semver::Version::new(1, 56, 0),
+            semver::Version::new(1, 55, 0),
+        ]);
+
+        let mut output = Vec::new();
+        let result = find_batch(
+            config,
+            &BatchOptions::new(true),
+            &FakeTestReporter::default(),
+            &index,
+            &runner,
+            input.as_bytes(),
+            &mut output,
+        );
+
+        let records = String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        (result, records)
+    }
+
+    #[test]
+    fn record_per_crate() {
+        let tmp = TestDir::temp()
+            .create("a", FileType::Dir)
+            .create("a/Cargo.toml", FileType::EmptyFile)
+            .create("b", FileType::Dir)
+            .create("b/Cargo.toml", FileType::EmptyFile);
+        let a = tmp.path("a");
+        let b = tmp.path("b");
+
+        let input = format!("{}\n\n{}\n", a.display(), b.display());
+        let (result, records) = run_batch(&input);
+
+        assert!(result.is_ok());
+        assert_eq!(
+            records,
+            vec![
+                serde_json::json!({ "crate_path": a, "msrv": "1.55.0" }),
+                serde_json::json!({ "crate_path": b, "msrv": "1.55.0" }),
+            ]
+        );
+    }
+
+    #[test]
+    fn edition_of_each_crate_bounds_its_search_space() {
+        let tmp = TestDir::temp()
+            .create("a", FileType::Dir)
+            .create("a/Cargo.toml", FileType::EmptyFile);
+        let a = tmp.path("a");
+        std::fs::write(a.join("Cargo.toml"), "[package]\nedition = \"2021\"\n").unwrap();
+
+        let (result, records) = run_batch(&format!("{}\n", a.display()));
+
+        assert!(result.is_ok());
+        assert_eq!(records[0]["msrv"], "1.56.0");
+    }
+
+    #[test]
+    fn failing_crate_does_not_stop_batch() {
+        let tmp = TestDir::temp()
+            .create("a", FileType::Dir)
+            .create("a/Cargo.toml", FileType::EmptyFile);
+        let a = tmp.path("a");
+        let missing = tmp.path("missing");
+
+        let input = format!("{}\n{}\n", missing.display(), a.display());
+        let (result, records) = run_batch(&input);
+
+        assert!(matches!(
+            result,
+            Err(CargoMSRVError::BatchFailed {
+                failed: 1,
+                total: 2
+            })
+        ));
+        assert!(records[0]["error"].is_string());
+        assert_eq!(records[1]["msrv"], "1.55.0");
+    }
+
+    #[test]
+    fn cancelled_batch_stops() {
+        let tmp = TestDir::temp()
+            .create("a", FileType::Dir)
+            .create("a/Cargo.toml", FileType::EmptyFile);
+        let a = tmp.path("a");
+
+        let token = CancellationToken::new();
+        token.cancel();
+
+        let config = ConfigBuilder::new(Action::Find, "x86_64-unknown-linux-gnu")
+            .cancellation_token(token)
+            .build();
+
+        let (result, records) = run_batch_with_config(&config, &format!("{}\n", a.display()));
+
+        assert!(matches!(result, Err(CargoMSRVError::Cancelled)));
+        assert!(records.is_empty());
+    }
+}
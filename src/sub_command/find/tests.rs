@@ -2,7 +2,13 @@ use super::*;
 use crate::check::TestRunner;
 use crate::config::ConfigBuilder;
 use crate::manifest::bare_version::BareVersion;
+use crate::manifest::syntax::{ManifestFeature, ManifestRequirement};
+use crate::manifest::version_bump::BumpPolicy;
+use crate::outcome::Outcome;
+use crate::reporter::event::Message;
 use crate::reporter::TestReporter;
+use crate::search_method::{Polarity, SearchSeeds};
+use crate::toolchain::{OwnedToolchainSpec, ToolchainSpec};
 use crate::{Action, Event};
 use rust_releases::semver;
 use std::iter::FromIterator;
@@ -165,3 +171,417 @@ fn no_releases_available() {
 
     assert!(!events.contains(&unexpected_event));
 }
+
+#[yare::parameterized(
+    bisect = { SearchMethod::Bisect },
+    linear = { SearchMethod::Linear },
+)]
+fn inverted_finds_first_failing(method: SearchMethod) {
+    let index = ReleaseIndex::from_iter(vec![
+        Release::new_stable(semver::Version::new(1, 56, 0)),
+        Release::new_stable(semver::Version::new(1, 55, 0)),
+        Release::new_stable(semver::Version::new(1, 54, 0)),
+        Release::new_stable(semver::Version::new(1, 53, 0)),
+        Release::new_stable(semver::Version::new(1, 52, 0)),
+    ]);
+
+    let config = ConfigBuilder::new(Action::Find, "")
+        .search_method(method)
+        .polarity(Polarity::Inverted)
+        .build();
+    let reporter = TestReporter::default();
+    let runner = TestRunner::with_ok(&[
+        semver::Version::new(1, 54, 0),
+        semver::Version::new(1, 53, 0),
+        semver::Version::new(1, 52, 0),
+    ]);
+
+    let cmd = Find::new(&index, runner);
+    let found = cmd.run(&config, reporter.reporter()).unwrap();
+    assert_eq!(found, semver::Version::new(1, 55, 0));
+}
+
+#[test]
+fn inverted_none_failing() {
+    let index = ReleaseIndex::from_iter(vec![
+        Release::new_stable(semver::Version::new(1, 56, 0)),
+        Release::new_stable(semver::Version::new(1, 55, 0)),
+        Release::new_stable(semver::Version::new(1, 54, 0)),
+    ]);
+
+    let config = ConfigBuilder::new(Action::Find, "")
+        .polarity(Polarity::Inverted)
+        .build();
+    let reporter = TestReporter::default();
+    let runner = TestRunner::with_ok(&[
+        semver::Version::new(1, 56, 0),
+        semver::Version::new(1, 55, 0),
+        semver::Version::new(1, 54, 0),
+    ]);
+
+    let cmd = Find::new(&index, runner);
+    let err = cmd.run(&config, reporter.reporter()).unwrap_err();
+    assert!(matches!(err, CargoMSRVError::UnableToFindRegression { .. }));
+}
+
+#[test]
+fn reported_result_includes_fingerprint() {
+    let index = ReleaseIndex::from_iter(vec![
+        Release::new_stable(semver::Version::new(1, 56, 0)),
+        Release::new_stable(semver::Version::new(1, 55, 0)),
+    ]);
+
+    let config = Config::new(Action::Find, "".to_string());
+    let reporter = TestReporter::default();
+    let runner = TestRunner::with_ok(&[semver::Version::new(1, 56, 0)]);
+    let fingerprint = serde_json::from_str::<Fingerprint>(
+        r#"{"host_triple":"x86_64-unknown-linux-gnu","rustup_version":null,"cargo_msrv_version":"0.15.1","check_command":"cargo check","features_hash":null,"lockfile_hash":null}"#,
+    )
+    .unwrap();
+
+    let cmd = Find::new(&index, runner).with_fingerprint(fingerprint.clone());
+    cmd.run(&config, reporter.reporter()).unwrap();
+
+    let events = reporter.wait_for_events();
+    let expected: Vec<Event> = vec![MsrvResult::new_msrv(
+        semver::Version::new(1, 56, 0),
+        &config,
+        BareVersion::ThreeComponents(1, 55, 0),
+        BareVersion::ThreeComponents(1, 56, 0),
+    )
+    .with_fingerprint(fingerprint)
+    .into()];
+
+    phenomenon::contains_at_least_ordered(events, expected).assert_this();
+}
+
+/// Accepts each toolchain, but toolchains less recent than `first_available` can't be installed.
+struct UnavailableBelow {
+    first_available: semver::Version,
+}
+
+impl Check for UnavailableBelow {
+    fn check(&self, config: &Config, toolchain: &ToolchainSpec) -> TResult<Outcome> {
+        let toolchain = OwnedToolchainSpec::new(toolchain.version(), config.target());
+
+        if toolchain.version() < &self.first_available {
+            Err(CargoMSRVError::ToolchainUnavailable(toolchain))
+        } else {
+            Ok(Outcome::new_success(toolchain))
+        }
+    }
+}
+
+#[yare::parameterized(
+    bisect = { SearchMethod::Bisect },
+    linear = { SearchMethod::Linear },
+)]
+fn skips_toolchains_unavailable_for_target(method: SearchMethod) {
+    let index = ReleaseIndex::from_iter(vec![
+        Release::new_stable(semver::Version::new(1, 56, 0)),
+        Release::new_stable(semver::Version::new(1, 55, 0)),
+        Release::new_stable(semver::Version::new(1, 54, 0)),
+        Release::new_stable(semver::Version::new(1, 53, 0)),
+        Release::new_stable(semver::Version::new(1, 52, 0)),
+    ]);
+
+    let config = ConfigBuilder::new(Action::Find, "")
+        .search_method(method)
+        .build();
+    let reporter = TestReporter::default();
+    let runner = UnavailableBelow {
+        first_available: semver::Version::new(1, 54, 0),
+    };
+
+    let cmd = Find::new(&index, runner);
+    let found = cmd.run(&config, reporter.reporter()).unwrap();
+    assert_eq!(found, semver::Version::new(1, 54, 0));
+
+    let events = reporter.wait_for_events();
+    let expected: Vec<Event> = vec![MsrvResult::new_msrv(
+        semver::Version::new(1, 54, 0),
+        &config,
+        BareVersion::ThreeComponents(1, 54, 0),
+        BareVersion::ThreeComponents(1, 56, 0),
+    )
+    .into()];
+
+    phenomenon::contains_at_least_ordered(events, expected).assert_this();
+}
+
+#[test]
+fn all_toolchains_unavailable_for_target() {
+    let index = ReleaseIndex::from_iter(vec![
+        Release::new_stable(semver::Version::new(1, 56, 0)),
+        Release::new_stable(semver::Version::new(1, 55, 0)),
+    ]);
+
+    let config = Config::new(Action::Find, "".to_string());
+    let reporter = TestReporter::default();
+    let runner = UnavailableBelow {
+        first_available: semver::Version::new(1, 57, 0),
+    };
+
+    let cmd = Find::new(&index, runner);
+    let err = cmd.run(&config, reporter.reporter()).unwrap_err();
+    assert!(matches!(err, CargoMSRVError::ToolchainUnavailable(_)));
+}
+
+#[test]
+fn skips_toolchains_which_cannot_read_the_manifest() {
+    let index = ReleaseIndex::from_iter(vec![
+        Release::new_stable(semver::Version::new(1, 62, 0)),
+        Release::new_stable(semver::Version::new(1, 61, 0)),
+        Release::new_stable(semver::Version::new(1, 60, 0)),
+        Release::new_stable(semver::Version::new(1, 59, 0)),
+        Release::new_stable(semver::Version::new(1, 58, 0)),
+    ]);
+
+    let requirement = ManifestRequirement {
+        feature: ManifestFeature::NamespacedFeature,
+        key: "features.serde".to_string(),
+    };
+    let config = ConfigBuilder::new(Action::Find, "")
+        .search_method(SearchMethod::Linear)
+        .manifest_requirement(Some(requirement.clone()))
+        .build();
+    let reporter = TestReporter::default();
+    // older toolchains would pass, if they could read the manifest
+    let runner = TestRunner::with_ok(&[
+        semver::Version::new(1, 62, 0),
+        semver::Version::new(1, 61, 0),
+        semver::Version::new(1, 60, 0),
+        semver::Version::new(1, 59, 0),
+        semver::Version::new(1, 58, 0),
+    ]);
+
+    let cmd = Find::new(&index, runner);
+    let found = cmd.run(&config, reporter.reporter()).unwrap();
+    assert_eq!(found, semver::Version::new(1, 60, 0));
+
+    let events = reporter.wait_for_events();
+    let expected: Vec<Event> = vec![ManifestSyntax::new(requirement).into()];
+
+    phenomenon::contains_at_least_ordered(events, expected).assert_this();
+}
+
+#[test]
+fn linear_search_stops_when_check_budget_is_exhausted() {
+    let index = ReleaseIndex::from_iter(vec![
+        Release::new_stable(semver::Version::new(1, 56, 0)),
+        Release::new_stable(semver::Version::new(1, 55, 0)),
+        Release::new_stable(semver::Version::new(1, 54, 0)),
+        Release::new_stable(semver::Version::new(1, 53, 0)),
+    ]);
+
+    let config = ConfigBuilder::new(Action::Find, "")
+        .search_method(SearchMethod::Linear)
+        .max_checks(Some(2))
+        .build();
+    let reporter = TestReporter::default();
+    let runner = TestRunner::with_ok(&[
+        semver::Version::new(1, 56, 0),
+        semver::Version::new(1, 55, 0),
+        semver::Version::new(1, 54, 0),
+    ]);
+
+    let cmd = Find::new(&index, runner);
+    let result = cmd.run(&config, reporter.reporter());
+    assert!(matches!(
+        result,
+        Err(CargoMSRVError::CheckBudgetExhausted { max_checks: 2 })
+    ));
+
+    let events = reporter.wait_for_events();
+    let expected: Vec<Event> =
+        vec![InconclusiveResult::new(2, Some(semver::Version::new(1, 55, 0)), None).into()];
+
+    phenomenon::contains_at_least_ordered(events, expected).assert_this();
+}
+
+#[test]
+fn bisect_reports_range_of_msrv_when_check_budget_is_exhausted() {
+    let index = ReleaseIndex::from_iter(
+        (37..=56)
+            .rev()
+            .map(|minor| Release::new_stable(semver::Version::new(1, minor, 0))),
+    );
+
+    let config = ConfigBuilder::new(Action::Find, "")
+        .search_method(SearchMethod::Bisect)
+        .max_checks(Some(2))
+        .build();
+    let reporter = TestReporter::default();
+    let msrv = semver::Version::new(1, 45, 0);
+    let runner = TestRunner::with_ok(
+        &(45..=56)
+            .map(|minor| semver::Version::new(1, minor, 0))
+            .collect::<Vec<_>>(),
+    );
+
+    let cmd = Find::new(&index, runner);
+    assert!(cmd.run(&config, reporter.reporter()).is_err());
+
+    let inconclusive = reporter
+        .wait_for_events()
+        .into_iter()
+        .find_map(|event| match event.message() {
+            Message::InconclusiveResult(it) => Some(it.clone()),
+            _ => None,
+        })
+        .unwrap();
+
+    assert!(inconclusive
+        .least_recent_accepted()
+        .map_or(true, |v| v >= &msrv));
+    assert!(inconclusive
+        .most_recent_rejected()
+        .map_or(true, |v| v < &msrv));
+}
+
+#[yare::parameterized(
+    bisect = { SearchMethod::Bisect },
+    linear = { SearchMethod::Linear },
+)]
+fn searches_between_known_outcomes(method: SearchMethod) {
+    let index = ReleaseIndex::from_iter(vec![
+        Release::new_stable(semver::Version::new(1, 57, 0)),
+        Release::new_stable(semver::Version::new(1, 56, 0)),
+        Release::new_stable(semver::Version::new(1, 55, 0)),
+        Release::new_stable(semver::Version::new(1, 54, 0)),
+        Release::new_stable(semver::Version::new(1, 53, 0)),
+        Release::new_stable(semver::Version::new(1, 52, 0)),
+    ]);
+
+    let seeds = SearchSeeds {
+        known_good: Some(BareVersion::TwoComponents(1, 56)),
+        known_bad: Some(BareVersion::TwoComponents(1, 53)),
+    };
+    let config = ConfigBuilder::new(Action::Find, "")
+        .search_method(method)
+        .search_seeds(seeds.clone())
+        .build();
+    let reporter = TestReporter::default();
+    // the releases beyond the known outcomes would pass too, but they are not checked
+    let runner = TestRunner::with_ok(&[
+        semver::Version::new(1, 57, 0),
+        semver::Version::new(1, 56, 0),
+        semver::Version::new(1, 55, 0),
+        semver::Version::new(1, 54, 0),
+        semver::Version::new(1, 53, 0),
+        semver::Version::new(1, 52, 0),
+    ]);
+
+    let cmd = Find::new(&index, runner);
+    let found = cmd.run(&config, reporter.reporter()).unwrap();
+    assert_eq!(found, semver::Version::new(1, 54, 0));
+
+    let events = reporter.wait_for_events();
+    let expected: Vec<Event> = vec![MsrvResult::new_msrv(
+        semver::Version::new(1, 54, 0),
+        &config,
+        BareVersion::ThreeComponents(1, 54, 0),
+        BareVersion::ThreeComponents(1, 56, 0),
+    )
+    .with_seeds(seeds)
+    .into()];
+
+    phenomenon::contains_at_least_ordered(events, expected).assert_this();
+}
+
+#[test]
+fn suggests_version_bump_when_msrv_is_raised() {
+    let index = ReleaseIndex::from_iter(vec![
+        Release::new_stable(semver::Version::new(1, 58, 0)),
+        Release::new_stable(semver::Version::new(1, 57, 0)),
+        Release::new_stable(semver::Version::new(1, 56, 0)),
+    ]);
+
+    let policy = BumpPolicy::new(
+        semver::Version::new(1, 4, 2),
+        BareVersion::TwoComponents(1, 56),
+        true,
+    );
+    let config = ConfigBuilder::new(Action::Find, "")
+        .bump_policy(Some(policy.clone()))
+        .build();
+    let reporter = TestReporter::default();
+    let runner = TestRunner::with_ok(&[semver::Version::new(1, 58, 0)]);
+
+    let cmd = Find::new(&index, runner);
+    cmd.run(&config, reporter.reporter()).unwrap();
+
+    let events = reporter.wait_for_events();
+    let version_bump = policy.suggest(&semver::Version::new(1, 58, 0)).unwrap();
+    assert_eq!(
+        version_bump.suggested_version,
+        semver::Version::new(2, 0, 0)
+    );
+
+    let expected: Vec<Event> = vec![MsrvResult::new_msrv(
+        semver::Version::new(1, 58, 0),
+        &config,
+        BareVersion::ThreeComponents(1, 56, 0),
+        BareVersion::ThreeComponents(1, 58, 0),
+    )
+    .with_version_bump(version_bump)
+    .into()];
+
+    phenomenon::contains_at_least_ordered(events, expected).assert_this();
+}
+
+/// Accepts each toolchain, and counts the checks as if they used dependencies fetched up front.
+struct FetchedDependencies {
+    checks: std::cell::Cell<u32>,
+}
+
+impl Check for FetchedDependencies {
+    fn check(&self, config: &Config, toolchain: &ToolchainSpec) -> TResult<Outcome> {
+        self.checks.set(self.checks.get() + 1);
+
+        Ok(Outcome::new_success(OwnedToolchainSpec::new(
+            toolchain.version(),
+            config.target(),
+        )))
+    }
+
+    fn dependency_cache(&self) -> Option<DependencyCache> {
+        Some(DependencyCache::new(
+            std::time::Duration::from_secs(2),
+            self.checks.get(),
+        ))
+    }
+}
+
+#[test]
+fn reports_dependency_cache() {
+    let index = ReleaseIndex::from_iter(vec![
+        Release::new_stable(semver::Version::new(1, 56, 0)),
+        Release::new_stable(semver::Version::new(1, 55, 0)),
+        Release::new_stable(semver::Version::new(1, 54, 0)),
+    ]);
+
+    let config = ConfigBuilder::new(Action::Find, "")
+        .search_method(SearchMethod::Linear)
+        .fetch_once(true)
+        .build();
+    let reporter = TestReporter::default();
+    let runner = FetchedDependencies {
+        checks: std::cell::Cell::new(0),
+    };
+
+    let cmd = Find::new(&index, runner);
+    cmd.run(&config, reporter.reporter()).unwrap();
+
+    let events = reporter.wait_for_events();
+    let expected: Vec<Event> = vec![MsrvResult::new_msrv(
+        semver::Version::new(1, 54, 0),
+        &config,
+        BareVersion::ThreeComponents(1, 54, 0),
+        BareVersion::ThreeComponents(1, 56, 0),
+    )
+    .with_dependency_cache(DependencyCache::new(std::time::Duration::from_secs(2), 3))
+    .into()];
+
+    phenomenon::contains_at_least_ordered(events, expected).assert_this();
+}
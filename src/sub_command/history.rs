@@ -0,0 +1,219 @@
+//! Reports how the MSRV of a crate evolved across the revisions of its repository, with
+//! `cargo msrv history`.
+//!
+//! Each revision, a tag reachable from `HEAD` or a commit of the range given with `--range`, is
+//! checked out in a temporary worktree, so the working tree of the user is left untouched.
+
+use std::path::{Path, PathBuf};
+
+use rust_releases::ReleaseIndex;
+use toml_edit::Document;
+
+use crate::check::Check;
+use crate::config::discover::DiscoverRun;
+use crate::config::{Config, ConfigBuilder};
+use crate::error::{CargoMSRVError, TResult};
+use crate::long_path::sandbox_path;
+use crate::manifest::bare_version::BareVersion;
+use crate::manifest::workspace::is_workspace;
+use crate::manifest::{CargoManifestParser, TomlParser};
+use crate::reporter::event::{HistoryEntry, MsrvHistory, Progress, ProgressUnit};
+use crate::reporter::Reporter;
+use crate::sub_command::discover::{declared_msrv, inherits_msrv, project_config, workspace_msrv};
+use crate::sub_command::{SubCommand, Verify};
+use crate::worktree::{self, Worktree};
+
+/// Walks the revisions of the repository of a crate, and reports the MSRV of the crate at each
+/// revision. When given a release index, the MSRV of each revision is verified as well.
+pub struct History<'index, C: Check> {
+    release_index: Option<&'index ReleaseIndex>,
+    runner: C,
+}
+
+impl<'index, C: Check> History<'index, C> {
+    pub fn new(release_index: Option<&'index ReleaseIndex>, runner: C) -> Self {
+        Self {
+            release_index,
+            runner,
+        }
+    }
+}
+
+impl<'index, C: Check> SubCommand for History<'index, C> {
+    type Output = MsrvHistory;
+
+    fn run(&self, config: &Config, reporter: &impl Reporter) -> TResult<Self::Output> {
+        let history_config = config.sub_command_config().history();
+        let crate_root = config.context().crate_root_path()?;
+
+        let revisions = match &history_config.range {
+            Some(range) => worktree::commits(crate_root, range)?,
+            None => worktree::tags(crate_root)?,
+        };
+
+        if revisions.is_empty() {
+            let revisions = match &history_config.range {
+                Some(range) => format!("the range '{}'", range),
+                None => "the tags reachable from HEAD".to_string(),
+            };
+
+            return Err(CargoMSRVError::SubCommandHistory(Error::NoRevisions {
+                revisions,
+            }));
+        }
+
+        let manifest_name = config
+            .context()
+            .manifest_path()?
+            .file_name()
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from("Cargo.toml"));
+
+        let worktree_path = sandbox_path(config.sandbox_root(), "cargo-msrv-history");
+
+        let mut entries: Vec<HistoryEntry> = Vec::with_capacity(revisions.len());
+        let mut failed = 0;
+
+        for (n, revision) in revisions.iter().enumerate() {
+            let unit = ProgressUnit::new(revision.clone(), n as u64 + 1, revisions.len() as u64);
+            reporter.report_event(Progress::start_of_unit(unit.clone()))?;
+
+            let worktree = Worktree::add(crate_root, revision, worktree_path.clone())?;
+            let crate_dir = worktree.translate(crate_root)?;
+            let msrv = msrv_at(&crate_dir, &manifest_name, worktree.path());
+
+            let verified = match (self.release_index, &msrv) {
+                (Some(index), Some(msrv)) => {
+                    let result =
+                        project_config(config, DiscoverRun::Verify, &crate_dir, Some(msrv))
+                            .and_then(|verify_config| {
+                                let verify_config = ConfigBuilder::from_config(&verify_config)
+                                    .progress_unit(Some(unit))
+                                    .build();
+
+                                Verify::new(index, &self.runner).run(&verify_config, reporter)
+                            });
+
+                    match result {
+                        Err(CargoMSRVError::Cancelled) => return Err(CargoMSRVError::Cancelled),
+                        Err(error) => {
+                            warn!(%revision, %error, "unable to verify the msrv of the revision");
+                            failed += 1;
+                            Some(false)
+                        }
+                        Ok(()) => Some(true),
+                    }
+                }
+                _ => None,
+            };
+
+            let previous = entries.last().and_then(HistoryEntry::msrv);
+            let entry = HistoryEntry::new(
+                revision.clone(),
+                worktree.commit().to_string(),
+                worktree.date(),
+                msrv,
+                previous,
+            )
+            .with_verified(verified);
+
+            entries.push(entry);
+        }
+
+        let total = entries
+            .iter()
+            .filter(|entry| entry.verified().is_some())
+            .count();
+        let history = MsrvHistory::new(entries);
+
+        reporter.report_event(history.clone())?;
+
+        if failed > 0 {
+            return Err(CargoMSRVError::SubCommandHistory(
+                Error::VerificationsFailed { failed, total },
+            ));
+        }
+
+        Ok(history)
+    }
+}
+
+/// The MSRV of the crate in `crate_dir` at a revision, if its manifest specifies one. A member of
+/// a workspace may inherit the MSRV from the workspace, whose root is looked up within `root`, the
+/// root of the worktree.
+fn msrv_at(crate_dir: &Path, manifest_name: &Path, root: &Path) -> Option<BareVersion> {
+    // The crate may not exist yet, or its manifest may not be valid, at older revisions
+    let document = read_manifest(&crate_dir.join(manifest_name))?;
+
+    if !inherits_msrv(&document) {
+        return declared_msrv(&document);
+    }
+
+    crate_dir
+        .ancestors()
+        .skip(1)
+        .take_while(|dir| dir.starts_with(root))
+        .filter_map(|dir| read_manifest(&dir.join("Cargo.toml")))
+        .find(is_workspace)
+        .and_then(|workspace| workspace_msrv(&workspace))
+}
+
+fn read_manifest(path: &Path) -> Option<Document> {
+    let contents = std::fs::read_to_string(path).ok()?;
+
+    CargoManifestParser::default()
+        .parse::<Document>(&contents)
+        .map_err(|error| warn!(path = %path.display(), %error, "unable to parse the manifest"))
+        .ok()
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Unable to find any revisions to walk, in {revisions}")]
+    NoRevisions { revisions: String },
+
+    #[error("The MSRV of {failed} out of {total} verified revisions does not hold")]
+    VerificationsFailed { failed: usize, total: usize },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_dir::{DirBuilder, TestDir};
+
+    fn write(dir: &Path, path: &str, contents: &str) {
+        let path = dir.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    }
+
+    #[yare::parameterized(
+        declared = { "[package]\nrust-version = \"1.56\"\n", Some(BareVersion::TwoComponents(1, 56)) },
+        undeclared = { "[package]\nname = \"a\"\n", None },
+        inherited = { "[package]\nrust-version.workspace = true\n", Some(BareVersion::TwoComponents(1, 60)) },
+        invalid = { "[package\n", None },
+    )]
+    fn msrv_of_member(manifest: &str, expected: Option<BareVersion>) {
+        let tmp = TestDir::temp();
+        let root = tmp.root();
+        write(
+            root,
+            "Cargo.toml",
+            "[workspace]\nmembers = [\"a\"]\n\n[workspace.package]\nrust-version = \"1.60\"\n",
+        );
+        write(root, "a/Cargo.toml", manifest);
+
+        let msrv = msrv_at(&root.join("a"), Path::new("Cargo.toml"), root);
+
+        assert_eq!(msrv, expected);
+    }
+
+    #[test]
+    fn msrv_of_missing_crate() {
+        let tmp = TestDir::temp();
+
+        let msrv = msrv_at(&tmp.path("a"), Path::new("Cargo.toml"), tmp.root());
+
+        assert_eq!(msrv, None);
+    }
+}
@@ -0,0 +1,415 @@
+//! Previews whether adding a dependency would force the MSRV of a crate up, with
+//! `cargo msrv impact <CRATE@VERSION>`.
+//!
+//! The dependency is added to the manifest of a copy of the crate, the sandbox, which is checked
+//! with the MSRV. The crate itself is left untouched.
+
+use std::path::{Path, PathBuf};
+
+use rust_releases::{Release, ReleaseIndex};
+use toml_edit::{table, value, Document, Item, TableLike};
+
+use crate::check::{Check, CheckChain};
+use crate::config::impact::ProspectiveDependency;
+use crate::config::{Config, ConfigBuilder, DependencyKind};
+use crate::crate_copy::copy_crate;
+use crate::error::{CargoMSRVError, IoErrorSource, TResult};
+use crate::lockfile::CARGO_LOCK;
+use crate::long_path::Sandbox;
+use crate::manifest::bare_version::BareVersion;
+use crate::manifest::{CargoManifestParser, TomlParser};
+use crate::msrv::{resolve_msrv, MinimumSupportedRustVersion};
+use crate::reporter::event::{DependencyImpact, ImpactVerdict};
+use crate::reporter::Reporter;
+use crate::sub_command::find::search;
+use crate::sub_command::SubCommand;
+
+/// Determines whether the crate still builds with its MSRV, once the given dependency is added,
+/// and optionally, which MSRV it would require instead.
+pub struct Impact<'index, C: Check> {
+    release_index: &'index ReleaseIndex,
+    runner: C,
+}
+
+impl<'index, C: Check> Impact<'index, C> {
+    pub fn new(release_index: &'index ReleaseIndex, runner: C) -> Self {
+        Self {
+            release_index,
+            runner,
+        }
+    }
+}
+
+impl<'index, C: Check> SubCommand for Impact<'index, C> {
+    type Output = DependencyImpact;
+
+    fn run(&self, config: &Config, reporter: &impl Reporter) -> TResult<Self::Output> {
+        let dependency = &config.sub_command_config().impact().dependency;
+        let manifest = config.context().manifest_path()?;
+
+        let msrv = config
+            .declared_msrv()
+            .cloned()
+            .ok_or_else(|| Error::NoMsrv {
+                manifest: manifest.to_path_buf(),
+            })?;
+
+        let crate_root = config.context().crate_root_path()?;
+        let sandbox = Sandbox::new(config.sandbox_root(), "cargo-msrv-impact");
+
+        let verdict = create_sandbox(crate_root, sandbox.path(), |document| {
+            if inherits_from_workspace(document) {
+                return Err(CargoMSRVError::SubCommandImpact(
+                    Error::InheritsFromWorkspace {
+                        manifest: crate_root.join("Cargo.toml"),
+                    },
+                ));
+            }
+
+            prepare_manifest(document, crate_root, dependency)
+        })
+        .and_then(|()| self.assess(config, reporter, sandbox.path(), &msrv))?;
+
+        let impact = DependencyImpact::new(dependency.clone(), msrv, verdict);
+        reporter.report_event(impact.clone())?;
+
+        Ok(impact)
+    }
+}
+
+impl<'index, C: Check> Impact<'index, C> {
+    /// Check the sandbox with the MSRV, and, if it fails and `--find` is given, search for the
+    /// MSRV of the sandbox among the more recent releases.
+    fn assess(
+        &self,
+        config: &Config,
+        reporter: &impl Reporter,
+        sandbox: &Path,
+        msrv: &BareVersion,
+    ) -> TResult<ImpactVerdict> {
+        let sandbox_config = ConfigBuilder::from_config(config)
+            .crate_path(Some(sandbox))
+            .build();
+
+        let version = resolve_msrv(
+            config,
+            reporter,
+            msrv,
+            self.release_index.releases().iter().map(Release::version),
+        )?;
+        let toolchain = config.toolchain_spec(version);
+
+        // surfaces the Rust version which cargo requires, when the dependency declares one
+        let runner = CheckChain::from_config(&self.runner, config, reporter);
+
+        if runner.check(&sandbox_config, &toolchain)?.is_success() {
+            return Ok(ImpactVerdict::Compatible);
+        }
+
+        if !config.sub_command_config().impact().find {
+            return Ok(ImpactVerdict::RequiresBump { msrv: None });
+        }
+
+        // The releases less recent than the MSRV are not of interest, unless `--min` is more recent
+        let minimum = match config.minimum_version() {
+            Some(min) if min.to_semver_version() > msrv.to_semver_version() => min.clone(),
+            _ => msrv.clone(),
+        };
+
+        let find_config = ConfigBuilder::from_config(&sandbox_config)
+            .minimum_version(minimum)
+            .build();
+
+        match search(
+            &find_config,
+            reporter,
+            self.release_index,
+            &self.runner,
+            None,
+        )? {
+            MinimumSupportedRustVersion::Toolchain { toolchain } => {
+                Ok(ImpactVerdict::RequiresBump {
+                    msrv: Some(toolchain.version().clone()),
+                })
+            }
+            MinimumSupportedRustVersion::NoCompatibleToolchain => {
+                Ok(ImpactVerdict::NoCompatibleRelease)
+            }
+        }
+    }
+}
+
+/// Copy the crate to the sandbox, and let `prepare` edit the manifest of the copy, e.g. to add the
+/// dependency.
+pub(super) fn create_sandbox(
+    crate_root: &Path,
+    sandbox: &Path,
+    prepare: impl FnOnce(&mut Document) -> TResult<()>,
+) -> TResult<()> {
+    copy_crate(crate_root, sandbox)?;
+
+    // The lockfile of a workspace member is kept by the workspace, so the dependencies of the copy
+    // resolve to the same versions as those of the crate
+    let lockfile = sandbox.join(CARGO_LOCK);
+    if !lockfile.is_file() {
+        if let Some(workspace_lockfile) = crate_root
+            .ancestors()
+            .map(|dir| dir.join(CARGO_LOCK))
+            .find(|path| path.is_file())
+        {
+            std::fs::copy(&workspace_lockfile, &lockfile).map_err(|error| CargoMSRVError::Io {
+                error,
+                source: IoErrorSource::CopyFile(workspace_lockfile.clone()),
+            })?;
+        }
+    }
+
+    let manifest = sandbox.join("Cargo.toml");
+    let contents = std::fs::read_to_string(&manifest).map_err(|error| CargoMSRVError::Io {
+        error,
+        source: IoErrorSource::ReadFile(manifest.clone()),
+    })?;
+
+    let mut document = CargoManifestParser::default().parse::<Document>(&contents)?;
+    prepare(&mut document)?;
+
+    std::fs::write(&manifest, document.to_string()).map_err(|error| CargoMSRVError::Io {
+        error,
+        source: IoErrorSource::WriteFile(manifest.clone()),
+    })
+}
+
+/// Add the dependency to the manifest of the sandbox, or update its version requirement, if the
+/// crate already depends on it.
+fn prepare_manifest(
+    document: &mut Document,
+    crate_root: &Path,
+    dependency: &ProspectiveDependency,
+) -> TResult<()> {
+    isolate_manifest(document, crate_root);
+
+    let dependencies = &mut document["dependencies"];
+
+    match dependencies {
+        // Explicitly create the table, otherwise it would default to an inline table instead
+        Item::None => *dependencies = table(),
+        Item::Table(_) => {}
+        _ => {
+            return Err(CargoMSRVError::SubCommandImpact(
+                Error::DependenciesNotATable,
+            ))
+        }
+    }
+
+    let entry = &mut dependencies[dependency.name()];
+
+    match entry.as_table_like_mut() {
+        // keep the features and other keys of an existing dependency
+        Some(existing) => {
+            existing.remove("path");
+            existing.remove("git");
+            existing.insert("version", value(dependency.requirement()));
+        }
+        None => *entry = value(dependency.requirement()),
+    }
+
+    Ok(())
+}
+
+/// Since the sandbox lives elsewhere, the paths of the path dependencies are made absolute, and
+/// the sandbox is made the root of its own workspace.
+pub(super) fn isolate_manifest(document: &mut Document, crate_root: &Path) {
+    for_each_dependency_table(document, |dependencies| {
+        absolute_paths(dependencies, crate_root);
+    });
+
+    // The sandbox is not a member of the workspace of the crate, but the root of its own, since
+    // it may be located within another workspace, e.g. when the scratch dir is
+    if let Some(package) = document
+        .get_mut("package")
+        .and_then(Item::as_table_like_mut)
+    {
+        package.remove("workspace");
+    }
+
+    if !document.contains_key("workspace") {
+        document["workspace"] = table();
+    }
+}
+
+/// Visit the dependency tables of each kind, from the top level of the manifest, and from each
+/// `[target.<cfg>]` table.
+pub(super) fn for_each_dependency_table(
+    document: &mut Document,
+    mut visit: impl FnMut(&mut dyn TableLike),
+) {
+    let keys = DependencyKind::all()
+        .iter()
+        .flat_map(|kind| kind.table_keys())
+        .copied()
+        .collect::<Vec<_>>();
+
+    for key in &keys {
+        if let Some(dependencies) = document.get_mut(key).and_then(Item::as_table_like_mut) {
+            visit(dependencies);
+        }
+    }
+
+    if let Some(targets) = document.get_mut("target").and_then(Item::as_table_like_mut) {
+        for (_, target) in targets.iter_mut() {
+            if let Some(target) = target.as_table_like_mut() {
+                for key in &keys {
+                    if let Some(dependencies) =
+                        target.get_mut(key).and_then(Item::as_table_like_mut)
+                    {
+                        visit(dependencies);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Make the relative paths of the path dependencies absolute, relative to the crate root.
+fn absolute_paths(dependencies: &mut dyn TableLike, crate_root: &Path) {
+    for (_, dependency) in dependencies.iter_mut() {
+        let path = dependency
+            .get("path")
+            .and_then(Item::as_str)
+            .map(PathBuf::from)
+            .filter(|path| path.is_relative());
+
+        if let (Some(path), Some(dependency)) = (path, dependency.as_table_like_mut()) {
+            let path = crate_root.join(path);
+            dependency.insert("path", value(path.to_string_lossy().as_ref()));
+        }
+    }
+}
+
+/// Whether a field of the package, or a dependency, is inherited from the workspace, with
+/// `workspace = true`, which can't be resolved outside of the workspace.
+pub(super) fn inherits_from_workspace(document: &mut Document) -> bool {
+    let inherits = |item: &Item| item.get("workspace").and_then(Item::as_bool) == Some(true);
+
+    let package_inherits = document
+        .get("package")
+        .and_then(Item::as_table_like)
+        .map_or(false, |package| {
+            package.iter().any(|(_, field)| inherits(field))
+        });
+
+    let mut dependency_inherits = false;
+    for_each_dependency_table(document, |dependencies| {
+        dependency_inherits |= dependencies
+            .iter()
+            .any(|(_, dependency)| inherits(dependency));
+    });
+
+    package_inherits || dependency_inherits
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("No MSRV is specified in '{}', to preview the impact of a dependency on", .manifest.display())]
+    NoMsrv { manifest: PathBuf },
+
+    #[error("The manifest '{}' inherits from its workspace, which the copy of the crate can't resolve", .manifest.display())]
+    InheritsFromWorkspace { manifest: PathBuf },
+
+    #[error(
+        "Unable to add the dependency, since 'dependencies' is not a table in the Cargo manifest"
+    )]
+    DependenciesNotATable,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    const MANIFEST: &str = r#"[package]
+name = "example"
+version = "0.1.0"
+rust-version = "1.56"
+
+[dependencies]
+serde = { version = "1.0.100", features = ["derive"] }
+helper = { path = "../helper" }
+
+[target.'cfg(unix)'.dev-dependencies]
+fixtures = { path = "fixtures" }
+"#;
+
+    fn prepare(manifest: &str, dependency: &str) -> TResult<Document> {
+        let mut document = manifest.parse::<Document>().unwrap();
+        let dependency = ProspectiveDependency::from_str(dependency).unwrap();
+
+        prepare_manifest(&mut document, Path::new("/work/example"), &dependency)?;
+
+        Ok(document)
+    }
+
+    #[test]
+    fn adds_dependency() {
+        let document = prepare(MANIFEST, "anyhow@1.0.70").unwrap();
+
+        assert_eq!(document["dependencies"]["anyhow"].as_str(), Some("1.0.70"));
+    }
+
+    #[test]
+    fn updates_existing_dependency() {
+        let document = prepare(MANIFEST, "serde@1.0.190").unwrap();
+        let serde = &document["dependencies"]["serde"];
+
+        assert_eq!(serde["version"].as_str(), Some("1.0.190"));
+        assert_eq!(serde["features"].as_array().map(|it| it.len()), Some(1));
+    }
+
+    #[test]
+    fn adds_dependencies_table() {
+        let manifest = "[package]\nname = \"example\"\nversion = \"0.1.0\"\n";
+        let document = prepare(manifest, "anyhow@1").unwrap();
+
+        assert!(document["dependencies"].is_table());
+        assert_eq!(document["dependencies"]["anyhow"].as_str(), Some("1"));
+    }
+
+    #[test]
+    fn makes_paths_absolute() {
+        let document = prepare(MANIFEST, "anyhow@1").unwrap();
+
+        let helper = Path::new(document["dependencies"]["helper"]["path"].as_str().unwrap());
+        let fixtures = Path::new(
+            document["target"]["cfg(unix)"]["dev-dependencies"]["fixtures"]["path"]
+                .as_str()
+                .unwrap(),
+        );
+
+        assert_eq!(helper, Path::new("/work/example").join("../helper"));
+        assert_eq!(fixtures, Path::new("/work/example").join("fixtures"));
+    }
+
+    #[yare::parameterized(
+        standalone = { MANIFEST },
+        workspace_root = { "[package]\nname = \"example\"\nversion = \"0.1.0\"\n\n[workspace]\nmembers = [\"crates/*\"]\n" },
+        member_of = { "[package]\nname = \"example\"\nversion = \"0.1.0\"\nworkspace = \"..\"\n" },
+    )]
+    fn own_workspace(manifest: &str) {
+        let document = prepare(manifest, "anyhow@1").unwrap();
+
+        assert!(document["workspace"].is_table());
+        assert!(document["package"].get("workspace").is_none());
+    }
+
+    #[yare::parameterized(
+        standalone = { MANIFEST, false },
+        package_field = { "[package]\nname = \"example\"\nversion.workspace = true\n", true },
+        dependency = { "[package]\nname = \"example\"\n\n[dependencies]\nserde = { workspace = true }\n", true },
+        target_dependency = { "[package]\nname = \"example\"\n\n[target.'cfg(unix)'.dependencies]\nlibc.workspace = true\n", true },
+    )]
+    fn inheritance(manifest: &str, expected: bool) {
+        let mut document = manifest.parse::<Document>().unwrap();
+
+        assert_eq!(inherits_from_workspace(&mut document), expected);
+    }
+}
@@ -0,0 +1,149 @@
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::check_log::CheckLogs;
+use crate::config::Config;
+use crate::error::{CargoMSRVError, IoErrorSource, TResult};
+use crate::reporter::event::FailureLog;
+use crate::reporter::Reporter;
+use crate::sub_command::SubCommand;
+
+/// The pager which is used when the `PAGER` environment variable is not set.
+const DEFAULT_PAGER: &str = if cfg!(windows) { "more" } else { "less" };
+
+/// Locates the log of the most recent failed toolchain check, and optionally shows it with a pager.
+#[derive(Default)]
+pub struct LastFailure;
+
+impl SubCommand for LastFailure {
+    type Output = PathBuf;
+
+    fn run(&self, config: &Config, reporter: &impl Reporter) -> TResult<Self::Output> {
+        let logs = CheckLogs::of_config(config).ok_or(Error::LogFolderUnavailable)?;
+
+        let path = logs
+            .most_recent()
+            .map_err(|error| CargoMSRVError::Io {
+                error,
+                source: IoErrorSource::ReadDir(logs.folder().to_path_buf()),
+            })?
+            .ok_or_else(|| Error::NoFailedCheck {
+                folder: logs.folder().to_path_buf(),
+            })?;
+
+        reporter.report_event(FailureLog::new(path.clone()))?;
+
+        if config.sub_command_config().last_failure().pager {
+            let pager = std::env::var("PAGER").unwrap_or_default();
+            page(&pager, &path)?;
+        }
+
+        Ok(path)
+    }
+}
+
+/// Pipe the log to the given pager, which may include arguments, like `less -R`.
+fn page(pager: &str, path: &Path) -> TResult<()> {
+    let mut words = pager.split_whitespace();
+    let (program, args) = match words.next() {
+        Some(program) => (program, words.collect::<Vec<_>>()),
+        None => (DEFAULT_PAGER, Vec::new()),
+    };
+
+    let log = File::open(path).map_err(|error| CargoMSRVError::Io {
+        error,
+        source: IoErrorSource::ReadFile(path.to_path_buf()),
+    })?;
+
+    // the exit status of the pager is not of interest, e.g. when it is quit early
+    Command::new(program)
+        .args(args)
+        .stdin(log)
+        .status()
+        .map_err(|error| CargoMSRVError::Io {
+            error,
+            source: IoErrorSource::SpawnProcess(program.into()),
+        })?;
+
+    Ok(())
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Unable to determine the log folder, in which the logs of failed checks are kept")]
+    LogFolderUnavailable,
+
+    #[error("No failed check was logged in '{}'. Checks are not logged with --no-log", .folder.display())]
+    NoFailedCheck { folder: PathBuf },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::last_failure::LastFailureCmdConfig;
+    use crate::config::{ConfigBuilder, SubCommandConfig};
+    use crate::reporter::TestReporter;
+    use crate::toolchain::ToolchainSpec;
+    use crate::{semver, Action, Event};
+    use test_dir::{DirBuilder, TestDir};
+
+    fn config(scratch_dir: &Path) -> Config<'static> {
+        ConfigBuilder::new(Action::LastFailure, "x86_64-unknown-linux-gnu")
+            .scratch_dir(Some(scratch_dir.to_path_buf()))
+            .sub_command_config(SubCommandConfig::LastFailureConfig(LastFailureCmdConfig {
+                pager: false,
+            }))
+            .build()
+    }
+
+    #[test]
+    fn locates_most_recent_failure() {
+        let tmp = TestDir::temp();
+        let config = config(tmp.root());
+
+        let version = semver::Version::new(1, 61, 0);
+        let toolchain = ToolchainSpec::new(&version, "x86_64-unknown-linux-gnu");
+        let expected = CheckLogs::of_config(&config)
+            .unwrap()
+            .write_failure(&toolchain, "cargo check", b"error[E0658]")
+            .unwrap();
+
+        let reporter = TestReporter::default();
+        let path = LastFailure::default()
+            .run(&config, reporter.reporter())
+            .unwrap();
+
+        assert_eq!(path, expected);
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::from(FailureLog::new(expected))]
+        );
+    }
+
+    #[test]
+    fn no_failed_check() {
+        let tmp = TestDir::temp();
+        let config = config(tmp.root());
+
+        let reporter = TestReporter::default();
+        let result = LastFailure::default().run(&config, reporter.reporter());
+
+        assert!(matches!(
+            result,
+            Err(CargoMSRVError::SubCommandLastFailure(
+                Error::NoFailedCheck { .. }
+            ))
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn pipes_log_to_pager() {
+        let tmp = TestDir::temp();
+        let log = tmp.path("1.61.0.log");
+        std::fs::write(&log, "error[E0658]").unwrap();
+
+        page("cat -u", &log).unwrap();
+    }
+}
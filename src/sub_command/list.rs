@@ -1,9 +1,14 @@
+use crate::config::list::ListMsrvVariant;
 use crate::config::Config;
+use crate::crates_index::{RegistryIndexes, RustVersionHistory};
 use crate::dependency_graph::resolver::{CargoMetadataResolver, DependencyResolver};
+use crate::dependency_graph::DependencyGraph;
 use crate::error::TResult;
-use crate::reporter::event::ListDep;
+use crate::reporter::event::{orphans, ListDep};
 use crate::reporter::Reporter;
 use crate::SubCommand;
+use cargo_metadata::PackageId;
+use std::collections::BTreeMap;
 
 #[derive(Default)]
 pub struct List;
@@ -19,9 +24,49 @@ impl SubCommand for List {
 fn list_msrv(config: &Config, reporter: &impl Reporter) -> TResult<()> {
     let resolver = CargoMetadataResolver::try_from_config(config)?;
     let graph = resolver.resolve()?;
-    let variant = config.sub_command_config().list().variant;
+    let list_config = config.sub_command_config().list();
+    let variant = list_config.variant;
 
-    reporter.report_event(ListDep::new(variant, graph))?;
+    let indexes = RegistryIndexes::new(
+        list_config.crates_index.as_deref(),
+        &list_config.registry_indexes,
+    );
+
+    let history = match variant {
+        ListMsrvVariant::Orphans if !indexes.is_empty() => Some(orphans_history(&graph, &indexes)?),
+        _ => None,
+    };
+
+    let event = ListDep::new(variant, graph);
+    let event = match history {
+        Some(history) => event.with_history(history),
+        None => event,
+    };
+
+    reporter.report_event(event)?;
 
     Ok(())
 }
+
+/// The release history of each orphan, as found in the index of the registry it originates from.
+/// Orphans from registries without a known index are left out, rather than being looked up in the
+/// wrong index.
+fn orphans_history(
+    graph: &DependencyGraph,
+    indexes: &RegistryIndexes,
+) -> TResult<BTreeMap<PackageId, RustVersionHistory>> {
+    let mut history = BTreeMap::new();
+
+    for package in orphans(graph) {
+        let index = match indexes.index_of(package.source.as_ref()) {
+            Some(index) => index,
+            None => continue,
+        };
+
+        if let Some(package_history) = index.rust_version_history(&package.name)? {
+            history.insert(package.id.clone(), package_history);
+        }
+    }
+
+    Ok(history)
+}
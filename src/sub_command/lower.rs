@@ -0,0 +1,358 @@
+//! Estimates what it would take to lower the MSRV of a crate, with `cargo msrv lower`.
+//!
+//! A copy of the crate, the sandbox, is checked with the releases less recent than the MSRV, from
+//! the most recent to the least recent. The `rust-version` of the copy is removed, so cargo
+//! doesn't refuse to build it with these releases. The failures of these checks are analyzed for
+//! the changes which would make the crate compatible. The crate itself is left untouched.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use rust_releases::{Release, ReleaseIndex};
+use toml_edit::{Document, Item};
+
+use crate::channel_manifest::ChannelManifests;
+use crate::check::{Check, CheckChain, ComponentAvailabilityCheck};
+use crate::config::{Config, ConfigBuilder};
+use crate::crates_index::CratesIndex;
+use crate::error::{CargoMSRVError, IoErrorSource, TResult};
+use crate::filter_releases::filter_releases;
+use crate::long_path::Sandbox;
+use crate::manifest::bare_version::BareVersion;
+use crate::manifest::syntax::{requirements, ManifestRequirement};
+use crate::manifest::{CargoManifestParser, TomlParser};
+use crate::outcome::Outcome;
+use crate::reporter::event::{Blocker, LoweringStep, MsrvLowering};
+use crate::reporter::Reporter;
+use crate::semver;
+use crate::sub_command::impact::{
+    create_sandbox, for_each_dependency_table, inherits_from_workspace, isolate_manifest,
+};
+use crate::sub_command::SubCommand;
+
+mod blockers;
+
+use blockers::blockers;
+
+/// Tries the releases less recent than the MSRV, and reports what keeps the crate from
+/// supporting each of them.
+pub struct Lower<'index, C: Check> {
+    release_index: &'index ReleaseIndex,
+    runner: C,
+}
+
+impl<'index, C: Check> Lower<'index, C> {
+    pub fn new(release_index: &'index ReleaseIndex, runner: C) -> Self {
+        Self {
+            release_index,
+            runner,
+        }
+    }
+}
+
+impl<'index, C: Check> SubCommand for Lower<'index, C> {
+    type Output = MsrvLowering;
+
+    fn run(&self, config: &Config, reporter: &impl Reporter) -> TResult<Self::Output> {
+        let manifest = config.context().manifest_path()?;
+
+        let msrv = config
+            .declared_msrv()
+            .cloned()
+            .ok_or_else(|| Error::NoMsrv {
+                manifest: manifest.to_path_buf(),
+            })?;
+
+        let contents = std::fs::read_to_string(manifest).map_err(|error| CargoMSRVError::Io {
+            error,
+            source: IoErrorSource::ReadFile(manifest.to_path_buf()),
+        })?;
+        let mut document = CargoManifestParser::default().parse::<Document>(&contents)?;
+
+        let manifest_info = ManifestInfo {
+            requirements: requirements(&document),
+            optional: optional_dependencies(&mut document),
+        };
+
+        let crate_root = config.context().crate_root_path()?;
+        let sandbox = Sandbox::new(config.sandbox_root(), "cargo-msrv-lower");
+
+        let steps = create_sandbox(crate_root, sandbox.path(), |document| {
+            if inherits_from_workspace(document) {
+                return Err(CargoMSRVError::SubCommandLower(
+                    Error::InheritsFromWorkspace {
+                        manifest: manifest.to_path_buf(),
+                    },
+                ));
+            }
+
+            isolate_manifest(document, crate_root);
+
+            if let Some(package) = document
+                .get_mut("package")
+                .and_then(Item::as_table_like_mut)
+            {
+                package.remove("rust-version");
+            }
+
+            Ok(())
+        })
+        .and_then(|()| self.steps(config, reporter, sandbox.path(), &msrv, &manifest_info))?;
+
+        let lowering = MsrvLowering::new(msrv, steps);
+        reporter.report_event(lowering.clone())?;
+
+        Ok(lowering)
+    }
+}
+
+/// What the manifest of the crate tells about the blockers.
+struct ManifestInfo {
+    /// The manifest syntax which older releases can't read
+    requirements: Vec<ManifestRequirement>,
+    /// The names of the optional dependencies
+    optional: HashSet<String>,
+}
+
+impl<'index, C: Check> Lower<'index, C> {
+    /// Check the sandbox with each release which is tried, from the most recent to the least
+    /// recent.
+    fn steps(
+        &self,
+        config: &Config,
+        reporter: &impl Reporter,
+        sandbox: &Path,
+        msrv: &BareVersion,
+        manifest_info: &ManifestInfo,
+    ) -> TResult<Vec<LoweringStep>> {
+        let cmd_config = config.sub_command_config().lower();
+        let sandbox_config = ConfigBuilder::from_config(config)
+            .crate_path(Some(sandbox))
+            .build();
+
+        let msrv = msrv.to_semver_version();
+        let mut versions = filter_releases(config, self.release_index.releases())
+            .iter()
+            .map(Release::version)
+            .filter(|version| *version < &msrv)
+            .cloned()
+            .collect::<Vec<_>>();
+
+        versions.sort_by(|lhs, rhs| rhs.cmp(lhs));
+        versions.truncate(cmd_config.steps);
+
+        let crates_index = cmd_config.crates_index.as_deref().map(CratesIndex::new);
+
+        // a release which didn't ship a component which the check requires is rejected unchecked
+        let runner = CheckChain::new(&self.runner).layer(ComponentAvailabilityCheck::new(
+            reporter,
+            ChannelManifests::new(config),
+        ));
+
+        let mut steps = Vec::with_capacity(versions.len());
+
+        for version in versions {
+            let toolchain = config.toolchain_spec(&version);
+
+            let outcome = match runner.check(&sandbox_config, &toolchain) {
+                Ok(outcome) => outcome,
+                // less recent releases don't support the target either
+                Err(CargoMSRVError::ToolchainUnavailable(toolchain)) => {
+                    info!(
+                        %toolchain,
+                        "toolchain is not available, so no less recent releases are tried"
+                    );
+                    break;
+                }
+                Err(error) => return Err(error),
+            };
+
+            let blockers = match &outcome {
+                Outcome::Success(_) => Vec::new(),
+                Outcome::Failure(failure) => step_blockers(
+                    &version,
+                    &failure.error_message,
+                    manifest_info,
+                    crates_index.as_ref(),
+                )?,
+            };
+
+            steps.push(LoweringStep::new(version, blockers));
+        }
+
+        Ok(steps)
+    }
+}
+
+/// The blockers of a release, from the output of its failed check, and from the manifest syntax
+/// which its cargo can't read. Each dependency is completed with the release to which it can be
+/// pinned, if a crates.io index is given, and whether it is optional.
+fn step_blockers(
+    version: &semver::Version,
+    output: &str,
+    manifest_info: &ManifestInfo,
+    crates_index: Option<&CratesIndex>,
+) -> TResult<Vec<Blocker>> {
+    let mut found = manifest_info
+        .requirements
+        .iter()
+        .filter(|requirement| &requirement.feature.readable_since() > version)
+        .map(|requirement| Blocker::ManifestSyntax {
+            feature: requirement.feature,
+            key: requirement.key.clone(),
+        })
+        .collect::<Vec<_>>();
+
+    for mut blocker in blockers(output) {
+        if let Blocker::Dependency {
+            name,
+            version: current,
+            pin,
+            optional,
+            ..
+        } = &mut blocker
+        {
+            *optional = manifest_info.optional.contains(name.as_str());
+
+            if let Some(index) = crates_index {
+                *pin = index.newest_supporting(name, version, current.as_ref())?;
+            }
+        }
+
+        // cargo can't read the manifest, so the check fails before anything is built
+        if matches!(blocker, Blocker::Other { .. }) && !found.is_empty() {
+            continue;
+        }
+
+        found.push(blocker);
+    }
+
+    Ok(found)
+}
+
+/// The names of the dependencies of the crate which are optional, i.e. enabled by a feature.
+fn optional_dependencies(document: &mut Document) -> HashSet<String> {
+    let mut optional = HashSet::new();
+
+    for_each_dependency_table(document, |dependencies| {
+        for (name, dependency) in dependencies.iter() {
+            if dependency.get("optional").and_then(Item::as_bool) == Some(true) {
+                optional.insert(name.to_string());
+            }
+        }
+    });
+
+    optional
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("No MSRV is specified in '{}', to lower", .manifest.display())]
+    NoMsrv { manifest: PathBuf },
+
+    #[error("The manifest '{}' inherits from its workspace, which the copy of the crate can't resolve", .manifest.display())]
+    InheritsFromWorkspace { manifest: PathBuf },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::syntax::ManifestFeature;
+
+    const MANIFEST: &str = r#"[package]
+name = "example"
+version = "0.1.0"
+rust-version = "1.60"
+resolver = "2"
+
+[dependencies]
+log = { version = "0.4.20", optional = true }
+serde = "1.0.190"
+
+[target.'cfg(unix)'.dependencies]
+libc = { version = "0.2", optional = true }
+"#;
+
+    fn manifest_info() -> ManifestInfo {
+        let mut document = MANIFEST.parse::<Document>().unwrap();
+
+        ManifestInfo {
+            requirements: requirements(&document),
+            optional: optional_dependencies(&mut document),
+        }
+    }
+
+    #[test]
+    fn optional() {
+        let mut document = MANIFEST.parse::<Document>().unwrap();
+
+        assert_eq!(
+            optional_dependencies(&mut document),
+            ["log", "libc"]
+                .iter()
+                .map(|name| name.to_string())
+                .collect()
+        );
+    }
+
+    #[test]
+    fn optional_dependency() {
+        let output = "error: package `log v0.4.20` cannot be built because it requires rustc 1.60.0 or newer, while the currently active rustc version is 1.59.0";
+
+        let blockers = step_blockers(
+            &semver::Version::new(1, 59, 0),
+            output,
+            &manifest_info(),
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(
+            blockers,
+            vec![Blocker::Dependency {
+                name: "log".to_string(),
+                version: Some(semver::Version::new(0, 4, 20)),
+                requires: Some(semver::Version::new(1, 60, 0)),
+                pin: None,
+                optional: true,
+            }]
+        );
+    }
+
+    #[test]
+    fn unreadable_manifest() {
+        let output = "error: failed to parse manifest at `/work/example/Cargo.toml`";
+
+        let blockers = step_blockers(
+            &semver::Version::new(1, 50, 0),
+            output,
+            &manifest_info(),
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(
+            blockers,
+            vec![Blocker::ManifestSyntax {
+                feature: ManifestFeature::Resolver2,
+                key: "package.resolver".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn readable_manifest() {
+        let output =
+            "error[E0658]: use of unstable library feature 'let_chains'\n --> src/lib.rs:1:1";
+
+        let blockers = step_blockers(
+            &semver::Version::new(1, 59, 0),
+            output,
+            &manifest_info(),
+            None,
+        )
+        .unwrap();
+
+        assert!(matches!(blockers.as_slice(), [Blocker::Code { .. }]));
+    }
+}
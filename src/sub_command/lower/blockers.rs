@@ -0,0 +1,313 @@
+//! Reads what keeps a crate from supporting a release from the output of its failed check: the
+//! dependencies which cargo refused to build, since they require a more recent Rust version, and
+//! the compiler errors, which are attributed to a dependency when they're located in its source.
+
+use crate::manifest::bare_version::BareVersion;
+use crate::reporter::event::Blocker;
+use crate::semver;
+
+/// The blockers which the output of a failed check reveals. When none can be attributed, the
+/// first error is given as is.
+pub(super) fn blockers(output: &str) -> Vec<Blocker> {
+    let lines = output.lines().collect::<Vec<_>>();
+    let mut blockers = Vec::new();
+    let mut unattributed = None;
+
+    for line in &lines {
+        if let Some((name, version, requires)) = required_rustc(line) {
+            add_dependency(&mut blockers, name, version, Some(requires));
+        }
+    }
+
+    for (i, line) in lines.iter().enumerate() {
+        let message = match error_message(line) {
+            Some(message) if !is_summary(message) => message,
+            _ => continue,
+        };
+
+        // the notes of the diagnostic, up to the next diagnostic
+        let notes = lines[i + 1..]
+            .iter()
+            .map(|line| line.trim_start())
+            .take_while(|line| !line.starts_with("error") && !line.starts_with("warning"))
+            .collect::<Vec<_>>();
+
+        // cargo gives the cause of a manifest which it can't parse below the error
+        if let Some(edition) =
+            edition(message).or_else(|| notes.iter().find_map(|note| edition(note)))
+        {
+            add(&mut blockers, Blocker::Edition { edition });
+            continue;
+        }
+
+        let mut location = None;
+        let mut feature = None;
+
+        for note in notes {
+            if let Some(at) = note.strip_prefix("--> ") {
+                location = location.or_else(|| Some(at.trim()));
+            }
+
+            if let Some((_, rest)) = note.split_once("#![feature(") {
+                feature = rest.split(')').next().map(String::from);
+            }
+        }
+
+        match (location, location.and_then(registry_package)) {
+            (_, Some((name, version))) => add_dependency(&mut blockers, name, Some(version), None),
+            (Some(location), None) => add(
+                &mut blockers,
+                Blocker::Code {
+                    message: message.to_string(),
+                    feature,
+                    location: Some(location.to_string()),
+                },
+            ),
+            (None, None) => {
+                unattributed = unattributed.or(Some(message));
+            }
+        }
+    }
+
+    match unattributed {
+        Some(message) if blockers.is_empty() => vec![Blocker::Other {
+            message: message.to_string(),
+        }],
+        _ => blockers,
+    }
+}
+
+fn add(blockers: &mut Vec<Blocker>, blocker: Blocker) {
+    if !blockers.contains(&blocker) {
+        blockers.push(blocker);
+    }
+}
+
+/// Add the dependency, or complete what is known about it, if it was added already.
+fn add_dependency(
+    blockers: &mut Vec<Blocker>,
+    name: &str,
+    version: Option<semver::Version>,
+    requires: Option<semver::Version>,
+) {
+    let existing = blockers.iter_mut().find_map(|blocker| match blocker {
+        Blocker::Dependency {
+            name: existing,
+            version,
+            requires,
+            ..
+        } if existing.as_str() == name => Some((version, requires)),
+        _ => None,
+    });
+
+    match existing {
+        Some((existing_version, existing_requires)) => {
+            *existing_version = existing_version.take().or(version);
+            *existing_requires = existing_requires.take().or(requires);
+        }
+        None => blockers.push(Blocker::Dependency {
+            name: name.to_string(),
+            version,
+            requires,
+            pin: None,
+            optional: false,
+        }),
+    }
+}
+
+/// The message of an error, e.g. `` `let...else` statements are unstable `` for
+/// ``error[E0658]: `let...else` statements are unstable``.
+fn error_message(line: &str) -> Option<&str> {
+    let rest = line.strip_prefix("error")?;
+
+    let rest = match rest.strip_prefix('[') {
+        Some(code) => code.split_once(']')?.1,
+        None => rest,
+    };
+
+    rest.strip_prefix(':').map(str::trim)
+}
+
+/// Whether the error only summarizes the errors before it.
+fn is_summary(message: &str) -> bool {
+    ["could not compile", "aborting due to", "build failed"]
+        .iter()
+        .any(|summary| message.starts_with(summary))
+}
+
+/// The package which cargo refused to build, with the Rust version it requires, e.g. from
+/// ``package `a v0.1.0` cannot be built because it requires rustc 1.60 or newer`` (Rust 1.56 up
+/// to 1.72), or `a@0.1.0 requires rustc 1.60` (later releases).
+fn required_rustc(line: &str) -> Option<(&str, Option<semver::Version>, semver::Version)> {
+    let (package, rest) = line.split_once(" requires rustc ")?;
+
+    let requires = rest
+        .split(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .next()?
+        .trim_end_matches('.')
+        .parse::<BareVersion>()
+        .ok()?
+        .to_semver_version();
+
+    let (name, version) = match package.split_once("package `") {
+        Some((_, package)) => {
+            let mut words = package.split_whitespace();
+            let name = words.next()?;
+            let version = words
+                .next()
+                .map(|version| version.trim_start_matches('v').trim_end_matches('`'))
+                .and_then(|version| version.parse().ok());
+
+            (name, version)
+        }
+        None => {
+            let (name, version) = package.trim().split_once('@')?;
+            (name, version.parse().ok())
+        }
+    };
+
+    Some((name, version, requires))
+}
+
+/// The edition which is not yet known, e.g. from ``feature `edition2021` is required``, or
+/// `edition 2021 is unstable and only available with -Z unstable-options`.
+fn edition(message: &str) -> Option<String> {
+    let year = match message.split_once("feature `edition") {
+        Some((_, rest)) => rest.split('`').next()?,
+        None if message.starts_with("edition ") && message.contains(" is unstable") => {
+            message["edition ".len()..].split_whitespace().next()?
+        }
+        None => return None,
+    };
+
+    (year.len() == 4 && year.chars().all(|c| c.is_ascii_digit())).then(|| year.to_string())
+}
+
+/// The package, and its version, whose source in the cargo registry holds the location, e.g.
+/// `serde` 1.0.190 for `/home/a/.cargo/registry/src/index.crates.io-6f17d22bba15001f/serde-1.0.190/src/de.rs:10:5`.
+fn registry_package(location: &str) -> Option<(&str, semver::Version)> {
+    let start = location
+        .match_indices("registry")
+        .map(|(start, needle)| start + needle.len())
+        .find(|&end| matches!(location.get(end..end + 5), Some("/src/") | Some("\\src\\")))?;
+
+    // the folder of the registry, then the folder of the package
+    let package = location[start + 5..].split(['/', '\\']).nth(1)?;
+
+    package
+        .match_indices('-')
+        .find_map(|(i, _)| Some((&package[..i], package[i + 1..].parse().ok()?)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LET_ELSE: &str = "error[E0658]: `let...else` statements are unstable
+ --> src/lib.rs:3:5
+  |
+3 |     let Some(x) = y else { return };
+  |     ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+  |
+  = note: see issue #87335 <https://github.com/rust-lang/rust/issues/87335> for more information
+  = help: add `#![feature(let_else)]` to the crate attributes to enable
+
+error: aborting due to previous error
+
+error: could not compile `example`";
+
+    const DEPENDENCY: &str = "   Compiling once_cell v1.19.0
+error[E0658]: use of unstable library feature 'const_mut_refs'
+  --> /home/a/.cargo/registry/src/index.crates.io-6f17d22bba15001f/once_cell-1.19.0/src/lib.rs:10:5
+   |
+   = help: add `#![feature(const_mut_refs)]` to the crate attributes to enable
+
+error: could not compile `once_cell` due to previous error";
+
+    #[test]
+    fn code_of_crate() {
+        assert_eq!(
+            blockers(LET_ELSE),
+            vec![Blocker::Code {
+                message: "`let...else` statements are unstable".to_string(),
+                feature: Some("let_else".to_string()),
+                location: Some("src/lib.rs:3:5".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn code_of_dependency() {
+        assert_eq!(
+            blockers(DEPENDENCY),
+            vec![Blocker::Dependency {
+                name: "once_cell".to_string(),
+                version: Some(semver::Version::new(1, 19, 0)),
+                requires: None,
+                pin: None,
+                optional: false,
+            }]
+        );
+    }
+
+    #[yare::parameterized(
+        cargo_1_56 = { "error: package `log v0.4.20` cannot be built because it requires rustc 1.60.0 or newer, while the currently active rustc version is 1.56.0" },
+        later_cargo = { "error: rustc 1.56.0 is not supported by the following packages:\n  log@0.4.20 requires rustc 1.60.0" },
+    )]
+    fn dependency_requires_rustc(output: &str) {
+        assert_eq!(
+            blockers(output),
+            vec![Blocker::Dependency {
+                name: "log".to_string(),
+                version: Some(semver::Version::new(0, 4, 20)),
+                requires: Some(semver::Version::new(1, 60, 0)),
+                pin: None,
+                optional: false,
+            }]
+        );
+    }
+
+    #[test]
+    fn same_dependency_once() {
+        let output = format!(
+            "{}\n{}",
+            DEPENDENCY,
+            DEPENDENCY.replace("const_mut_refs", "const_fn_trait_bound")
+        );
+
+        assert_eq!(blockers(&output).len(), 1);
+    }
+
+    #[yare::parameterized(
+        cargo = { "error: failed to parse manifest at `/work/example/Cargo.toml`\n\nCaused by:\n  feature `edition2021` is required" },
+        rustc = { "error: edition 2021 is unstable and only available with -Z unstable-options" },
+    )]
+    fn unknown_edition(output: &str) {
+        assert_eq!(
+            blockers(output),
+            vec![Blocker::Edition {
+                edition: "2021".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn unattributed_error() {
+        assert_eq!(
+            blockers("error: no matching package named `helper` found"),
+            vec![Blocker::Other {
+                message: "no matching package named `helper` found".to_string()
+            }]
+        );
+    }
+
+    #[yare::parameterized(
+        unix = { "/home/a/.cargo/registry/src/index.crates.io-6f17d22bba15001f/serde_json-1.0.108/src/de.rs:10:5", Some(("serde_json", semver::Version::new(1, 0, 108))) },
+        windows = { r"C:\Users\a\.cargo\registry\src\index.crates.io-6f17d22bba15001f\proc-macro2-1.0.69\src\lib.rs:3:1", Some(("proc-macro2", semver::Version::new(1, 0, 69))) },
+        pre_release = { "/home/a/.cargo/registry/src/github.com-1ecc6299db9ec823/tokio-1.0.0-rc.1/src/lib.rs:1:1", Some(("tokio", semver::Version::parse("1.0.0-rc.1").unwrap())) },
+        crate_source = { "src/lib.rs:3:5", None },
+    )]
+    fn package_of_location(location: &str, expected: Option<(&str, semver::Version)>) {
+        assert_eq!(registry_package(location), expected);
+    }
+}
@@ -0,0 +1,266 @@
+use rust_releases::ReleaseIndex;
+
+use crate::check::Check;
+use crate::config::{Config, ConfigBuilder, FeatureSet};
+use crate::error::{CargoMSRVError, TResult};
+use crate::msrv::MinimumSupportedRustVersion;
+use crate::reporter::event::{MatrixCell, MatrixRow, MsrvMatrix, Progress, ProgressUnit};
+use crate::reporter::Reporter;
+use crate::sub_command::find::search;
+use crate::sub_command::SubCommand;
+
+/// Determines the MSRV for each combination of targets and feature configurations.
+///
+/// Each combination runs the same search as `cargo msrv` (find), with the configured target and
+/// features replaced. A combination for which the search fails does not stop the matrix; the error
+/// is reported in its cell instead. A cancelled matrix does stop.
+pub struct Matrix<'index, C: Check> {
+    release_index: &'index ReleaseIndex,
+    runner: C,
+}
+
+impl<'index, C: Check> Matrix<'index, C> {
+    pub fn new(release_index: &'index ReleaseIndex, runner: C) -> Self {
+        Self {
+            release_index,
+            runner,
+        }
+    }
+}
+
+impl<'index, C: Check> SubCommand for Matrix<'index, C> {
+    type Output = MsrvMatrix;
+
+    fn run(&self, config: &Config, reporter: &impl Reporter) -> TResult<Self::Output> {
+        let cmd_config = config.sub_command_config().matrix();
+
+        let targets = if cmd_config.targets.is_empty() {
+            vec![config.target().clone()]
+        } else {
+            cmd_config.targets.clone()
+        };
+
+        let feature_sets = if cmd_config.feature_sets.is_empty() {
+            vec![FeatureSet::Default]
+        } else {
+            cmd_config.feature_sets.clone()
+        };
+
+        let mut rows = Vec::with_capacity(targets.len());
+        let total = (targets.len() * feature_sets.len()) as u64;
+        let mut current = 0;
+
+        for target in &targets {
+            let mut cells = Vec::with_capacity(feature_sets.len());
+
+            for features in &feature_sets {
+                info!(%target, %features, "finding msrv of matrix configuration");
+
+                current += 1;
+                let unit = ProgressUnit::new(format!("{} ({})", target, features), current, total);
+                reporter.report_event(Progress::start_of_unit(unit.clone()))?;
+
+                let cell_config = ConfigBuilder::from_config(config)
+                    .target(target)
+                    .features(features.clone())
+                    .progress_unit(Some(unit))
+                    .build();
+
+                let cell = match search(
+                    &cell_config,
+                    reporter,
+                    self.release_index,
+                    &self.runner,
+                    None,
+                ) {
+                    Ok(MinimumSupportedRustVersion::Toolchain { toolchain }) => {
+                        MatrixCell::msrv(features.clone(), toolchain.version().clone())
+                    }
+                    Ok(MinimumSupportedRustVersion::NoCompatibleToolchain) => {
+                        MatrixCell::none(features.clone())
+                    }
+                    // A cancelled matrix stops, instead of failing each of the remaining cells
+                    Err(CargoMSRVError::Cancelled) => return Err(CargoMSRVError::Cancelled),
+                    Err(error) => MatrixCell::error(features.clone(), error.to_string()),
+                };
+
+                cells.push(cell);
+            }
+
+            rows.push(MatrixRow::new(target.as_str(), cells));
+        }
+
+        let matrix = MsrvMatrix::new(rows);
+        reporter.report_event(matrix.clone())?;
+
+        Ok(matrix)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::matrix::MatrixCmdConfig;
+    use crate::config::SubCommandConfig;
+    use crate::outcome::Outcome;
+    use crate::reporter::event::Message;
+    use crate::reporter::{FakeTestReporter, TestReporter};
+    use crate::semver;
+    use crate::toolchain::{OwnedToolchainSpec, ToolchainSpec};
+    use crate::{Action, CancellationToken};
+    use rust_releases::Release;
+    use std::iter::FromIterator;
+
+    /// Accepts 1.56 and up with the default features, and only 1.58 and up with all features. The
+    /// wasm target is unavailable for each toolchain.
+    struct FeatureRunner;
+
+    impl Check for FeatureRunner {
+        fn check(&self, config: &Config, toolchain: &ToolchainSpec) -> TResult<Outcome> {
+            if let Some(token) = config.cancellation_token() {
+                token.check()?;
+            }
+
+            let version = toolchain.version();
+
+            if config.target() == "wasm32-unknown-unknown" {
+                return Err(CargoMSRVError::ToolchainUnavailable(
+                    OwnedToolchainSpec::new(version, config.target()),
+                ));
+            }
+
+            let minimum = match config.features() {
+                FeatureSet::AllFeatures => 58,
+                _ => 56,
+            };
+
+            let toolchain = OwnedToolchainSpec::new(version, config.target());
+
+            if version.minor >= minimum {
+                Ok(Outcome::new_success(toolchain))
+            } else {
+                Ok(Outcome::new_failure(toolchain, "f".to_string()))
+            }
+        }
+    }
+
+    fn index() -> ReleaseIndex {
+        ReleaseIndex::from_iter(
+            (54..=59)
+                .rev()
+                .map(|minor| Release::new_stable(semver::Version::new(1, minor, 0))),
+        )
+    }
+
+    fn config(cmd_config: MatrixCmdConfig) -> Config<'static> {
+        ConfigBuilder::new(Action::Matrix, "x86_64-unknown-linux-gnu")
+            .sub_command_config(SubCommandConfig::MatrixConfig(cmd_config))
+            .build()
+    }
+
+    #[test]
+    fn msrv_per_configuration() {
+        let index = index();
+        let config = config(MatrixCmdConfig {
+            targets: vec![
+                "x86_64-unknown-linux-gnu".to_string(),
+                "wasm32-unknown-unknown".to_string(),
+            ],
+            feature_sets: vec![FeatureSet::Default, FeatureSet::AllFeatures],
+        });
+
+        let matrix = Matrix::new(&index, FeatureRunner)
+            .run(&config, &FakeTestReporter::default())
+            .unwrap();
+
+        let native = &matrix.targets()[0];
+        assert_eq!(native.target(), "x86_64-unknown-linux-gnu");
+        assert_eq!(
+            native.configurations()[0].msrv_version(),
+            Some(&semver::Version::new(1, 56, 0))
+        );
+        assert_eq!(
+            native.configurations()[1].msrv_version(),
+            Some(&semver::Version::new(1, 58, 0))
+        );
+
+        let wasm = &matrix.targets()[1];
+        assert_eq!(wasm.target(), "wasm32-unknown-unknown");
+        assert!(wasm
+            .configurations()
+            .iter()
+            .all(|cell| cell.msrv_version().is_none() && cell.error_message().is_some()));
+    }
+
+    #[test]
+    fn defaults_to_configured_target_and_default_features() {
+        let index = index();
+        let config = config(MatrixCmdConfig {
+            targets: vec![],
+            feature_sets: vec![],
+        });
+
+        let matrix = Matrix::new(&index, FeatureRunner)
+            .run(&config, &FakeTestReporter::default())
+            .unwrap();
+
+        assert_eq!(matrix.targets().len(), 1);
+        assert_eq!(matrix.targets()[0].target(), "x86_64-unknown-linux-gnu");
+        assert_eq!(
+            matrix.targets()[0].configurations()[0].features(),
+            &FeatureSet::Default
+        );
+    }
+
+    #[test]
+    fn progress_of_each_configuration() {
+        let index = index();
+        let config = config(MatrixCmdConfig {
+            targets: vec!["x86_64-unknown-linux-gnu".to_string()],
+            feature_sets: vec![FeatureSet::Default, FeatureSet::AllFeatures],
+        });
+        let reporter = TestReporter::default();
+
+        Matrix::new(&index, FeatureRunner)
+            .run(&config, reporter.reporter())
+            .unwrap();
+
+        let progress = reporter
+            .wait_for_events()
+            .into_iter()
+            .filter_map(|event| match event.message() {
+                Message::Progress(progress) => Some(progress.clone()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        let units = progress
+            .iter()
+            .filter(|progress| progress.is_start_of_unit())
+            .filter_map(Progress::unit)
+            .map(|unit| (unit.current(), unit.total()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(units, vec![(1, 2), (2, 2)]);
+        assert!(progress.iter().all(|progress| progress.unit().is_some()));
+    }
+
+    #[test]
+    fn cancelled_matrix_stops() {
+        let index = index();
+        let token = CancellationToken::new();
+        token.cancel();
+
+        let config = config(MatrixCmdConfig {
+            targets: vec![],
+            feature_sets: vec![FeatureSet::Default, FeatureSet::AllFeatures],
+        });
+        let config = ConfigBuilder::from_config(&config)
+            .cancellation_token(token)
+            .build();
+
+        let result = Matrix::new(&index, FeatureRunner).run(&config, &FakeTestReporter::default());
+
+        assert!(matches!(result, Err(CargoMSRVError::Cancelled)));
+    }
+}
@@ -0,0 +1,393 @@
+use std::convert::TryFrom;
+use std::path::{Path, PathBuf};
+
+use rust_releases::{Release, ReleaseIndex};
+use toml_edit::{Document, Item};
+
+use crate::config::policy::PolicyCmdConfig;
+use crate::config::Config;
+use crate::error::{CargoMSRVError, IoErrorSource, TResult};
+use crate::manifest::bare_version::BareVersion;
+use crate::manifest::{CargoManifest, CargoManifestParser, TomlParser};
+use crate::reporter::event::{MsrvSubject, PolicyCheck, PolicyViolation};
+use crate::reporter::Reporter;
+use crate::semver;
+use crate::writer::result_file::ResultFile;
+use crate::SubCommand;
+
+/// The rules which can be declared in a policy file.
+pub const POLICY_RULES: &[&str] = &["max_msrv", "max_minor_bump", "min_supported_releases"];
+
+/// Validates the MSRV of a crate against the rules of the MSRV policy of a team.
+pub struct Policy<'index> {
+    release_index: &'index ReleaseIndex,
+}
+
+impl<'index> Policy<'index> {
+    pub fn new(release_index: &'index ReleaseIndex) -> Self {
+        Self { release_index }
+    }
+}
+
+impl<'index> SubCommand for Policy<'index> {
+    type Output = ();
+
+    fn run(&self, config: &Config, reporter: &impl Reporter) -> TResult<Self::Output> {
+        match config.sub_command_config().policy() {
+            PolicyCmdConfig::Check { policy, result } => {
+                self.check(config, policy, result.as_deref(), reporter)
+            }
+        }
+    }
+}
+
+impl<'index> Policy<'index> {
+    fn check(
+        &self,
+        config: &Config,
+        policy_path: &Path,
+        result_path: Option<&Path>,
+        reporter: &impl Reporter,
+    ) -> TResult<()> {
+        let policy = MsrvPolicy::read(policy_path)?;
+        let msrv = manifest_msrv(config)?;
+        let found_msrv = result_path
+            .map(ResultFile::read)
+            .transpose()?
+            .map(|result| result.msrv().clone());
+
+        let violations = policy.violations(
+            msrv.as_ref(),
+            found_msrv.as_ref(),
+            self.release_index.releases(),
+        );
+        let count = violations.len();
+
+        reporter.report_event(PolicyCheck::new(policy_path, msrv, found_msrv, violations))?;
+
+        if count > 0 {
+            return Err(Error::Violations(count).into());
+        }
+
+        Ok(())
+    }
+}
+
+fn manifest_msrv(config: &Config) -> TResult<Option<BareVersion>> {
+    let cargo_toml = config.context().manifest_path()?;
+
+    let contents = std::fs::read_to_string(cargo_toml).map_err(|error| CargoMSRVError::Io {
+        error,
+        source: IoErrorSource::ReadFile(cargo_toml.to_path_buf()),
+    })?;
+
+    let manifest = CargoManifestParser::default().parse::<Document>(&contents)?;
+    let manifest = CargoManifest::try_from(manifest)?;
+
+    Ok(manifest.minimum_rust_version().cloned())
+}
+
+/// The rules of a policy file, e.g. `msrv-policy.toml`. Rules which are not declared are not
+/// enforced.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct MsrvPolicy {
+    /// The most recent Rust version which may be the MSRV
+    max_msrv: Option<BareVersion>,
+    /// The number of minor versions by which the MSRV found by `find` may move beyond the MSRV of
+    /// the Cargo manifest
+    max_minor_bump: Option<u64>,
+    /// The number of stable minor releases which the MSRV must support, including the latest one
+    min_supported_releases: Option<u64>,
+}
+
+impl MsrvPolicy {
+    fn read(path: &Path) -> TResult<Self> {
+        let contents = std::fs::read_to_string(path).map_err(|error| CargoMSRVError::Io {
+            error,
+            source: IoErrorSource::ReadFile(path.to_path_buf()),
+        })?;
+
+        Self::parse(&contents).map_err(|reason| {
+            Error::InvalidPolicy {
+                path: path.to_path_buf(),
+                reason,
+            }
+            .into()
+        })
+    }
+
+    fn parse(contents: &str) -> Result<Self, String> {
+        let document = contents
+            .parse::<Document>()
+            .map_err(|error| error.to_string())?;
+
+        let mut policy = Self::default();
+
+        for (key, item) in document.as_table().iter() {
+            match key {
+                "max_msrv" => {
+                    let version = item
+                        .as_str()
+                        .ok_or_else(|| "'max_msrv' must be a Rust version".to_string())?;
+                    let version = version
+                        .parse::<BareVersion>()
+                        .map_err(|error| format!("'max_msrv' must be a Rust version: {}", error))?;
+
+                    policy.max_msrv = Some(version);
+                }
+                "max_minor_bump" => policy.max_minor_bump = Some(count(key, item)?),
+                "min_supported_releases" => policy.min_supported_releases = Some(count(key, item)?),
+                unknown => {
+                    return Err(format!(
+                        "unknown rule '{}', expected one of: {}",
+                        unknown,
+                        POLICY_RULES.join(", ")
+                    ))
+                }
+            }
+        }
+
+        Ok(policy)
+    }
+
+    /// The rules violated by the MSRV of the Cargo manifest, and the MSRV found by `find`.
+    fn violations(
+        &self,
+        msrv: Option<&BareVersion>,
+        found_msrv: Option<&semver::Version>,
+        releases: &[Release],
+    ) -> Vec<PolicyViolation> {
+        let mut violations = Vec::new();
+
+        if msrv.is_none() {
+            violations.push(PolicyViolation::MissingMsrv);
+        }
+
+        let msrv = msrv.map(BareVersion::to_semver_version);
+
+        let subjects = msrv
+            .iter()
+            .map(|version| (MsrvSubject::Manifest, version))
+            .chain(found_msrv.map(|version| (MsrvSubject::FindResult, version)))
+            .collect::<Vec<_>>();
+
+        if let Some(max_msrv) = &self.max_msrv {
+            violations.extend(
+                subjects
+                    .iter()
+                    .filter(|(_, version)| !max_msrv.is_at_most(version))
+                    .map(|&(subject, version)| PolicyViolation::MaxMsrv {
+                        subject,
+                        msrv: version.clone(),
+                        max_msrv: max_msrv.clone(),
+                    }),
+            );
+        }
+
+        if let (Some(max_minor_bump), Some(from), Some(to)) =
+            (self.max_minor_bump, msrv.as_ref(), found_msrv)
+        {
+            let bump = to.minor.saturating_sub(from.minor);
+
+            if to.major != from.major || bump > max_minor_bump {
+                violations.push(PolicyViolation::MaxMinorBump {
+                    from: from.clone(),
+                    to: to.clone(),
+                    max_minor_bump,
+                });
+            }
+        }
+
+        if let Some(min_supported_releases) = self.min_supported_releases {
+            violations.extend(subjects.iter().filter_map(|&(subject, version)| {
+                let supported_releases = supported_releases(version, releases);
+
+                (supported_releases < min_supported_releases).then(|| {
+                    PolicyViolation::MinSupportedReleases {
+                        subject,
+                        msrv: version.clone(),
+                        supported_releases,
+                        min_supported_releases,
+                    }
+                })
+            }));
+        }
+
+        violations
+    }
+}
+
+fn count(key: &str, item: &Item) -> Result<u64, String> {
+    item.as_integer()
+        .and_then(|value| u64::try_from(value).ok())
+        .ok_or_else(|| format!("'{}' must be a non-negative integer", key))
+}
+
+/// The number of stable minor releases which are supported by the given MSRV, i.e. which are at
+/// least as recent. Patch releases don't count separately.
+fn supported_releases(msrv: &semver::Version, releases: &[Release]) -> u64 {
+    let mut minors = releases
+        .iter()
+        .map(|release| (release.version().major, release.version().minor))
+        .filter(|&version| version >= (msrv.major, msrv.minor))
+        .collect::<Vec<_>>();
+
+    minors.sort_unstable();
+    minors.dedup();
+
+    minors.len() as u64
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Unable to parse the MSRV policy in '{}': {}", .path.display(), .reason)]
+    InvalidPolicy { path: PathBuf, reason: String },
+
+    #[error("The MSRV violates {0} rule(s) of the MSRV policy")]
+    Violations(usize),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn releases() -> Vec<Release> {
+        (50..=60)
+            .rev()
+            .map(|minor| Release::new_stable(semver::Version::new(1, minor, 0)))
+            .chain(std::iter::once(Release::new_stable(semver::Version::new(
+                1, 58, 1,
+            ))))
+            .collect()
+    }
+
+    fn version(minor: u64) -> semver::Version {
+        semver::Version::new(1, minor, 0)
+    }
+
+    #[test]
+    fn parse_policy() {
+        let policy = MsrvPolicy::parse(
+            r#"
+            # The MSRV policy of the team
+            max_msrv = "1.65"
+            max_minor_bump = 2
+            min_supported_releases = 6
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            policy,
+            MsrvPolicy {
+                max_msrv: Some(BareVersion::TwoComponents(1, 65)),
+                max_minor_bump: Some(2),
+                min_supported_releases: Some(6),
+            }
+        );
+    }
+
+    #[yare::parameterized(
+        unknown_rule = { "max_version = \"1.65\"" },
+        invalid_version = { "max_msrv = \"latest\"" },
+        version_not_a_string = { "max_msrv = 1" },
+        negative_count = { "max_minor_bump = -1" },
+        count_not_an_integer = { "min_supported_releases = \"6\"" },
+        invalid_toml = { "max_msrv =" },
+    )]
+    fn rejected_policy(contents: &str) {
+        assert!(MsrvPolicy::parse(contents).is_err());
+    }
+
+    #[yare::parameterized(
+        newest = { 60, 1 },
+        patch_release_is_not_counted = { 58, 3 },
+        oldest = { 50, 11 },
+        newer_than_index = { 61, 0 },
+    )]
+    fn supported_releases_of_msrv(minor: u64, expected: u64) {
+        assert_eq!(supported_releases(&version(minor), &releases()), expected);
+    }
+
+    #[test]
+    fn satisfied_policy() {
+        let policy = MsrvPolicy::parse(
+            r#"
+            max_msrv = "1.58"
+            max_minor_bump = 2
+            min_supported_releases = 3
+            "#,
+        )
+        .unwrap();
+
+        let violations = policy.violations(
+            Some(&BareVersion::TwoComponents(1, 56)),
+            Some(&version(58)),
+            &releases(),
+        );
+
+        assert!(violations.is_empty());
+    }
+
+    #[test]
+    fn each_subject_is_checked() {
+        let policy = MsrvPolicy::parse(
+            r#"
+            max_msrv = "1.57"
+            max_minor_bump = 1
+            min_supported_releases = 4
+            "#,
+        )
+        .unwrap();
+
+        let violations = policy.violations(
+            Some(&BareVersion::TwoComponents(1, 56)),
+            Some(&version(58)),
+            &releases(),
+        );
+
+        assert_eq!(
+            violations,
+            vec![
+                PolicyViolation::MaxMsrv {
+                    subject: MsrvSubject::FindResult,
+                    msrv: version(58),
+                    max_msrv: BareVersion::TwoComponents(1, 57),
+                },
+                PolicyViolation::MaxMinorBump {
+                    from: version(56),
+                    to: version(58),
+                    max_minor_bump: 1,
+                },
+                PolicyViolation::MinSupportedReleases {
+                    subject: MsrvSubject::FindResult,
+                    msrv: version(58),
+                    supported_releases: 3,
+                    min_supported_releases: 4,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn missing_msrv() {
+        let policy = MsrvPolicy::parse("max_minor_bump = 1").unwrap();
+
+        let violations = policy.violations(None, Some(&version(58)), &releases());
+
+        assert_eq!(violations, vec![PolicyViolation::MissingMsrv]);
+    }
+
+    #[test]
+    fn max_msrv_allows_patch_releases() {
+        let policy = MsrvPolicy::parse(r#"max_msrv = "1.58""#).unwrap();
+
+        let violations = policy.violations(
+            Some(&BareVersion::ThreeComponents(1, 58, 1)),
+            None,
+            &releases(),
+        );
+
+        assert!(violations.is_empty());
+    }
+}
@@ -0,0 +1,38 @@
+use rust_releases::ReleaseIndex;
+
+use crate::config::Config;
+use crate::error::TResult;
+use crate::filter_releases::classify_releases;
+use crate::release_schedule::release_date;
+use crate::reporter::event::{ListedRelease, ReleaseList};
+use crate::reporter::Reporter;
+use crate::sub_command::SubCommand;
+
+/// Lists the releases of the release index, and whether each is included in the search space.
+pub struct Releases<'index> {
+    release_index: &'index ReleaseIndex,
+}
+
+impl<'index> Releases<'index> {
+    pub fn new(release_index: &'index ReleaseIndex) -> Self {
+        Self { release_index }
+    }
+}
+
+impl<'index> SubCommand for Releases<'index> {
+    type Output = ();
+
+    fn run(&self, config: &Config, reporter: &impl Reporter) -> TResult<Self::Output> {
+        let releases = classify_releases(config, self.release_index.releases())
+            .into_iter()
+            .map(|(release, exclusion)| {
+                let version = release.version();
+                ListedRelease::new(version.clone(), release_date(version.minor), exclusion)
+            })
+            .collect();
+
+        reporter.report_event(ReleaseList::new(releases))?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,318 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::config::report::ReportCmdConfig;
+use crate::config::Config;
+use crate::error::{CargoMSRVError, IoErrorSource, TResult};
+use crate::reporter::event::{Compatibility, ConfigurationChange, MsrvResult, ReportDiff};
+use crate::reporter::Reporter;
+use crate::semver;
+use crate::toolchain::OwnedToolchainSpec;
+use crate::SubCommand;
+
+/// Reports on the results of earlier cargo-msrv runs, as written with `--output-format json`.
+#[derive(Default)]
+pub struct Report;
+
+impl SubCommand for Report {
+    type Output = ();
+
+    fn run(&self, config: &Config, reporter: &impl Reporter) -> TResult<Self::Output> {
+        match config.sub_command_config().report() {
+            ReportCmdConfig::Diff { old, new } => report_diff(old, new, reporter),
+        }
+    }
+}
+
+fn report_diff(old: &Path, new: &Path, reporter: &impl Reporter) -> TResult<()> {
+    let old = RunResult::read(old)?;
+    let new = RunResult::read(new)?;
+
+    reporter.report_event(diff(&old, &new))?;
+
+    Ok(())
+}
+
+/// The messages of a single cargo-msrv run which are relevant to compare runs.
+#[derive(Debug)]
+struct RunResult {
+    msrv_result: MsrvResult,
+    compatibility: Vec<Compatibility>,
+}
+
+impl RunResult {
+    fn read(path: &Path) -> TResult<Self> {
+        let contents = std::fs::read_to_string(path).map_err(|error| CargoMSRVError::Io {
+            error,
+            source: IoErrorSource::ReadFile(path.to_path_buf()),
+        })?;
+
+        Self::parse(&contents, path)
+    }
+
+    /// Parse the json output of a run, which consists of one message per line.
+    fn parse(output: &str, path: &Path) -> TResult<Self> {
+        let invalid = |source| Error::InvalidOutput {
+            path: path.to_path_buf(),
+            source,
+        };
+
+        let mut msrv_result = None;
+        let mut compatibility = Vec::new();
+
+        for line in output.lines().filter(|line| !line.trim().is_empty()) {
+            let message = serde_json::from_str::<serde_json::Value>(line).map_err(invalid)?;
+
+            match message["type"].as_str() {
+                Some("msrv_result") => {
+                    msrv_result = Some(serde_json::from_value(message).map_err(invalid)?);
+                }
+                Some("compatibility") => {
+                    compatibility.push(serde_json::from_value(message).map_err(invalid)?);
+                }
+                _ => {}
+            }
+        }
+
+        let msrv_result = msrv_result.ok_or_else(|| Error::NoMsrvResult(path.to_path_buf()))?;
+
+        Ok(Self {
+            msrv_result,
+            compatibility,
+        })
+    }
+
+    /// Whether each checked toolchain was compatible. When a toolchain was checked more than
+    /// once, the last check wins.
+    fn decisions(&self) -> BTreeMap<(&semver::Version, &str), (&OwnedToolchainSpec, bool)> {
+        self.compatibility
+            .iter()
+            .map(|it| {
+                let toolchain = it.toolchain();
+
+                (
+                    (toolchain.version(), toolchain.target()),
+                    (toolchain, it.is_compatible()),
+                )
+            })
+            .collect()
+    }
+
+    /// The configuration with which the run was made, as a flat list of properties.
+    fn configuration(&self) -> BTreeMap<String, String> {
+        let mut properties = BTreeMap::new();
+
+        let fields = match serde_json::to_value(&self.msrv_result) {
+            Ok(serde_json::Value::Object(fields)) => fields,
+            _ => return properties,
+        };
+
+        for (key, value) in fields {
+            match value {
+                // The result itself is not part of the configuration
+                _ if key == "determined" || key == "undetermined" => {}
+                serde_json::Value::Object(fingerprint) if key == "fingerprint" => {
+                    for (name, value) in fingerprint {
+                        insert_property(&mut properties, format!("fingerprint.{}", name), value);
+                    }
+                }
+                value => insert_property(&mut properties, key, value),
+            }
+        }
+
+        properties
+    }
+}
+
+fn insert_property(
+    properties: &mut BTreeMap<String, String>,
+    name: String,
+    value: serde_json::Value,
+) {
+    match value {
+        serde_json::Value::Null => {}
+        serde_json::Value::String(value) => {
+            properties.insert(name, value);
+        }
+        value => {
+            properties.insert(name, value.to_string());
+        }
+    }
+}
+
+fn diff(old: &RunResult, new: &RunResult) -> ReportDiff {
+    let old_decisions = old.decisions();
+    let new_decisions = new.decisions();
+
+    // Only toolchains which were checked in both runs can be compared
+    let changed_decision = |was_compatible: bool| {
+        old_decisions
+            .iter()
+            .filter_map(|(key, (_, old))| Some((new_decisions.get(key)?, *old)))
+            .filter(|((_, new), old)| *old == was_compatible && *new != was_compatible)
+            .map(|((toolchain, _), _)| (*toolchain).clone())
+            .collect::<Vec<_>>()
+    };
+
+    let newly_failing = changed_decision(true);
+    let newly_passing = changed_decision(false);
+
+    let old_configuration = old.configuration();
+    let new_configuration = new.configuration();
+
+    let mut properties = old_configuration
+        .keys()
+        .chain(new_configuration.keys())
+        .collect::<Vec<_>>();
+    properties.sort();
+    properties.dedup();
+
+    let changed_configuration = properties
+        .into_iter()
+        .filter_map(|property| {
+            let old = old_configuration.get(property);
+            let new = new_configuration.get(property);
+
+            (old != new)
+                .then(|| ConfigurationChange::new(property.clone(), old.cloned(), new.cloned()))
+        })
+        .collect();
+
+    ReportDiff::new(
+        old.msrv_result.msrv().cloned(),
+        new.msrv_result.msrv().cloned(),
+        newly_failing,
+        newly_passing,
+        changed_configuration,
+    )
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Unable to parse the cargo-msrv json output in '{}': {}", .path.display(), .source)]
+    InvalidOutput {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+
+    #[error("The cargo-msrv json output in '{}' does not contain an MSRV result", .0.display())]
+    NoMsrvResult(PathBuf),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TARGET: &str = "x86_64-unknown-linux-gnu";
+
+    fn compatibility(minor: u64, compatible: bool) -> String {
+        let report = if compatible {
+            r#""compatible""#
+        } else {
+            r#"{"incompatible":{"error":null}}"#
+        };
+
+        format!(
+            r#"{{"type":"compatibility","toolchain":{{"version":"1.{}.0","target":"{}"}},"decision":{},"compatibility_report":{}}}"#,
+            minor, TARGET, compatible, report
+        )
+    }
+
+    fn msrv_result(minor: u64, check_command: &str) -> String {
+        format!(
+            r#"{{"type":"msrv_result","fingerprint":{{"cargo_msrv_version":"0.15.1","check_command":"{}"}},"determined":{{"version":"1.{}.0","success":true}}}}"#,
+            check_command, minor
+        )
+    }
+
+    fn run(lines: &[String]) -> RunResult {
+        RunResult::parse(&lines.join("\n"), Path::new("run.json")).unwrap()
+    }
+
+    fn toolchain(minor: u64) -> OwnedToolchainSpec {
+        OwnedToolchainSpec::new(&semver::Version::new(1, minor, 0), TARGET)
+    }
+
+    #[test]
+    fn parse_ignores_other_messages() {
+        let output = format!(
+            "{}\n{}\n\n{}",
+            r#"{"type":"meta","instance":"cargo-msrv"}"#,
+            compatibility(56, true),
+            msrv_result(56, "cargo check")
+        );
+
+        let result = RunResult::parse(&output, Path::new("run.json")).unwrap();
+
+        assert_eq!(
+            result.msrv_result.msrv(),
+            Some(&semver::Version::new(1, 56, 0))
+        );
+        assert_eq!(result.compatibility.len(), 1);
+    }
+
+    #[test]
+    fn parse_requires_msrv_result() {
+        let result = RunResult::parse(&compatibility(56, true), Path::new("run.json"));
+
+        assert!(matches!(
+            result,
+            Err(CargoMSRVError::SubCommandReport(Error::NoMsrvResult(_)))
+        ));
+    }
+
+    #[test]
+    fn parse_rejects_invalid_json() {
+        let result = RunResult::parse("not json", Path::new("run.json"));
+
+        assert!(matches!(
+            result,
+            Err(CargoMSRVError::SubCommandReport(
+                Error::InvalidOutput { .. }
+            ))
+        ));
+    }
+
+    #[test]
+    fn diff_of_changed_runs() {
+        let old = run(&[
+            compatibility(60, true),
+            compatibility(56, true),
+            compatibility(55, false),
+            msrv_result(56, "cargo check"),
+        ]);
+        let new = run(&[
+            compatibility(60, true),
+            compatibility(58, true),
+            compatibility(56, false),
+            msrv_result(58, "cargo test"),
+        ]);
+
+        let diff = diff(&old, &new);
+
+        assert_eq!(
+            diff,
+            ReportDiff::new(
+                Some(semver::Version::new(1, 56, 0)),
+                Some(semver::Version::new(1, 58, 0)),
+                vec![toolchain(56)],
+                vec![],
+                vec![ConfigurationChange::new(
+                    "fingerprint.check_command".to_string(),
+                    Some("cargo check".to_string()),
+                    Some("cargo test".to_string()),
+                )],
+            )
+        );
+    }
+
+    #[test]
+    fn diff_of_equal_runs() {
+        let old = run(&[compatibility(56, true), msrv_result(56, "cargo check")]);
+        let new = run(&[compatibility(56, true), msrv_result(56, "cargo check")]);
+
+        let diff = diff(&old, &new);
+
+        assert!(!diff.has_changes());
+    }
+}
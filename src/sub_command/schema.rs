@@ -0,0 +1,130 @@
+use std::fs;
+use std::io;
+use std::io::Write;
+use std::path::Path;
+
+use serde_json::Value;
+
+use crate::config::Config;
+use crate::error::{CargoMSRVError, IoErrorSource, TResult};
+use crate::reporter::event::{AuxiliaryOutput, AuxiliaryOutputItem, Destination};
+use crate::reporter::schema::{event_schema, message_schemas};
+use crate::reporter::Reporter;
+use crate::sub_command::SubCommand;
+
+/// The file to which the schema of all messages is written, with `--out-dir`.
+const EVENT_SCHEMA_FILE: &str = "event.schema.json";
+
+/// Prints the JSON schema of the messages written with `--output-format json`, or writes a
+/// schema file for each type of message.
+#[derive(Default)]
+pub struct Schema;
+
+impl SubCommand for Schema {
+    type Output = ();
+
+    fn run(&self, config: &Config, reporter: &impl Reporter) -> TResult<Self::Output> {
+        match &config.sub_command_config().schema().out_dir {
+            Some(dir) => write_schema_files(dir, reporter),
+            None => {
+                let stdout = io::stdout();
+                print_schema(&mut stdout.lock())
+            }
+        }
+    }
+}
+
+fn print_schema(out: &mut impl Write) -> TResult<()> {
+    let schema = serde_json::to_value(event_schema()).expect("the schema is serializable");
+
+    writeln!(out, "{}", pretty(&schema)).map_err(|error| CargoMSRVError::Io {
+        error,
+        source: IoErrorSource::WriteStdout,
+    })
+}
+
+fn write_schema_files(dir: &Path, reporter: &impl Reporter) -> TResult<()> {
+    fs::create_dir_all(dir).map_err(|error| CargoMSRVError::Io {
+        error,
+        source: IoErrorSource::CreateDir(dir.to_path_buf()),
+    })?;
+
+    let schema = serde_json::to_value(event_schema()).expect("the schema is serializable");
+    write_schema_file(&dir.join(EVENT_SCHEMA_FILE), &schema, None, reporter)?;
+
+    for (kind, schema) in message_schemas() {
+        let path = dir.join(format!("{}.schema.json", kind));
+        write_schema_file(&path, &schema, Some(kind), reporter)?;
+    }
+
+    Ok(())
+}
+
+fn write_schema_file(
+    path: &Path,
+    schema: &Value,
+    message: Option<String>,
+    reporter: &impl Reporter,
+) -> TResult<()> {
+    fs::write(path, format!("{}\n", pretty(schema))).map_err(|error| CargoMSRVError::Io {
+        error,
+        source: IoErrorSource::WriteFile(path.to_path_buf()),
+    })?;
+
+    reporter.report_event(AuxiliaryOutput::new(
+        Destination::File(path.to_path_buf()),
+        AuxiliaryOutputItem::schema_file(message),
+    ))?;
+
+    Ok(())
+}
+
+fn pretty(schema: &Value) -> String {
+    serde_json::to_string_pretty(schema).expect("the schema is serializable")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::event::Message;
+    use crate::reporter::TestReporter;
+    use test_dir::{DirBuilder, TestDir};
+
+    #[test]
+    fn printed_schema() {
+        let mut out = Vec::new();
+        print_schema(&mut out).unwrap();
+
+        let schema: Value = serde_json::from_slice(&out).unwrap();
+
+        assert_eq!(schema["title"], "Event");
+        assert!(schema["definitions"]["OwnedToolchainSpec"].is_object());
+    }
+
+    #[test]
+    fn schema_files() {
+        let tmp = TestDir::temp();
+        let dir = tmp.path("schema");
+        let reporter = TestReporter::default();
+
+        write_schema_files(&dir, reporter.reporter()).unwrap();
+
+        for file in &[
+            EVENT_SCHEMA_FILE,
+            "msrv_result.schema.json",
+            "compatibility.schema.json",
+        ] {
+            let contents = fs::read_to_string(dir.join(file)).unwrap();
+            let schema: Value = serde_json::from_str(&contents).unwrap();
+            assert!(schema["oneOf"].is_array(), "{}", file);
+        }
+
+        let written = reporter
+            .wait_for_events()
+            .iter()
+            .filter(|event| matches!(event.message(), Message::AuxiliaryOutput(_)))
+            .count();
+
+        assert_eq!(written, message_schemas().len() + 1);
+    }
+}
@@ -0,0 +1,470 @@
+//! Serves the release index, installs toolchains, and runs checks, for other cargo-msrv
+//! invocations, with `cargo msrv serve`. See [`crate::server`] for the protocol.
+
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::Path;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread;
+use std::time::Duration;
+
+use rust_releases::{Release, ReleaseIndex};
+
+use crate::cancellation::CancellationToken;
+use crate::command::RustupCommand;
+use crate::config::{required_component, Config, InstallProfile};
+use crate::download::{install_args, is_unavailable_for_target};
+use crate::error::{CargoMSRVError, IoErrorSource, TResult};
+use crate::reporter::event::Serving;
+use crate::reporter::Reporter;
+use crate::server::{InstallRequest, InstallStatus, Request, Response};
+use crate::sub_command::SubCommand;
+use crate::toolchain::{OwnedToolchainSpec, ToolchainSpec};
+
+/// How long to wait before polling for new connections, and for cancellation, again.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Holds the release index in memory, and accepts the requests of cargo-msrv invocations which
+/// are given `--server`, until it is cancelled or interrupted.
+pub struct Serve<'index> {
+    release_index: &'index ReleaseIndex,
+}
+
+impl<'index> Serve<'index> {
+    pub fn new(release_index: &'index ReleaseIndex) -> Self {
+        Self { release_index }
+    }
+}
+
+impl<'index> SubCommand for Serve<'index> {
+    type Output = ();
+
+    fn run(&self, config: &Config, reporter: &impl Reporter) -> TResult<Self::Output> {
+        let address = &config.sub_command_config().serve().address;
+
+        let listener = TcpListener::bind(address).map_err(|error| CargoMSRVError::Io {
+            error,
+            source: IoErrorSource::Listen(address.clone()),
+        })?;
+
+        // the port is chosen by the OS, when given as 0
+        let address = listener
+            .local_addr()
+            .map(|address| address.to_string())
+            .unwrap_or_else(|_| address.clone());
+
+        let releases = self
+            .release_index
+            .releases()
+            .iter()
+            .map(Release::version)
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+
+        info!(%address, releases = releases.len(), "serving");
+        reporter.report_event(Serving::new(address.as_str(), releases.len()))?;
+
+        serve(
+            listener,
+            Arc::new(State::new(releases)),
+            config.cancellation_token(),
+        )
+    }
+}
+
+/// What the server holds in memory, and shares between the connections.
+#[derive(Debug)]
+struct State {
+    releases: Vec<String>,
+    /// The installs of each toolchain, by its spec. Each toolchain is locked for the duration of
+    /// its install, so a request for a toolchain which is being installed waits for the install to
+    /// finish, while other toolchains are installed concurrently.
+    toolchains: Mutex<HashMap<String, Arc<Mutex<Installs>>>>,
+}
+
+/// The outcome of each install of a toolchain, by its arguments. A failed install is not
+/// remembered, so it's retried by the next request.
+type Installs = HashMap<Vec<String>, InstallStatus>;
+
+impl State {
+    fn new(releases: Vec<String>) -> Self {
+        Self {
+            releases,
+            toolchains: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn respond(&self, request: Request) -> Response {
+        match request {
+            Request::ReleaseIndex => Response::ReleaseIndex {
+                releases: self.releases.clone(),
+            },
+            Request::Install(install) => self.install(&install),
+            Request::Check {
+                install,
+                dir,
+                check_command,
+            } => self.check(&install, &dir, &check_command),
+        }
+    }
+
+    /// The lock of the installs of the toolchain with the given spec.
+    fn toolchain(&self, spec: &str) -> Arc<Mutex<Installs>> {
+        let mut toolchains = self
+            .toolchains
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        Arc::clone(toolchains.entry(spec.to_string()).or_default())
+    }
+
+    /// Install the toolchain, unless it was installed already.
+    fn install(&self, request: &InstallRequest) -> Response {
+        let (toolchain, args) = match install_args_of(request) {
+            Ok(parts) => parts,
+            Err(message) => return Response::Error { message },
+        };
+
+        let toolchain = self.toolchain(toolchain.spec());
+        let mut installs = toolchain.lock().unwrap_or_else(PoisonError::into_inner);
+
+        if let Some(&status) = installs.get(&args) {
+            info!(?args, ?status, "toolchain installed before");
+            return Response::Install {
+                status,
+                stderr: None,
+            };
+        }
+
+        info!(?args, "installing toolchain");
+
+        let output = RustupCommand::new()
+            .with_stdout()
+            .with_stderr()
+            .with_args(args.iter())
+            .install();
+
+        let (status, stderr) = match output {
+            Ok(output) if output.exit_status().success() => (InstallStatus::Installed, None),
+            Ok(output) if is_unavailable_for_target(output.stderr()) => (
+                InstallStatus::Unavailable,
+                Some(output.stderr().to_string()),
+            ),
+            Ok(output) => (InstallStatus::Failed, Some(output.stderr().to_string())),
+            Err(error) => (InstallStatus::Failed, Some(error.to_string())),
+        };
+
+        if status != InstallStatus::Failed {
+            installs.insert(args, status);
+        } else {
+            warn!(?args, ?stderr, "rustup failed to install toolchain");
+        }
+
+        Response::Install { status, stderr }
+    }
+
+    /// Install the toolchain, and check the crate in the given directory with the cargo command.
+    /// When the toolchain is not installed, the response to the install is given instead.
+    fn check(&self, install: &InstallRequest, dir: &Path, check_command: &[String]) -> Response {
+        if check_command.first().map(String::as_str) != Some("cargo") {
+            return Response::Error {
+                message: format!(
+                    "unable to check with '{}', only cargo commands are checked by the server",
+                    check_command.join(" ")
+                ),
+            };
+        }
+
+        match self.install(install) {
+            Response::Install {
+                status: InstallStatus::Installed,
+                ..
+            } => {}
+            response => return response,
+        }
+
+        let toolchain = &install.toolchain;
+        let mut cmd = vec![toolchain.spec()];
+        cmd.extend(check_command.iter().map(String::as_str));
+
+        if toolchain.host() != toolchain.target() {
+            cmd.extend(["--target", toolchain.target()]);
+        }
+
+        info!(?cmd, dir = %dir.display(), "checking toolchain");
+
+        let output = RustupCommand::new()
+            .with_args(cmd)
+            .with_dir(dir)
+            .with_stderr()
+            .run();
+
+        match output {
+            Ok(output) if output.exit_status().success() => Response::Check {
+                compatible: true,
+                error: None,
+            },
+            Ok(output) => Response::Check {
+                compatible: false,
+                error: Some(output.stderr().to_string()),
+            },
+            Err(error) => Response::Error {
+                message: format!("unable to run the check: {}", error),
+            },
+        }
+    }
+}
+
+/// The toolchain of the install request, and the arguments to `rustup install` with which it is
+/// installed. The parts of the request are validated, so they can't be mistaken for other
+/// arguments to rustup.
+fn install_args_of(request: &InstallRequest) -> Result<(&OwnedToolchainSpec, Vec<String>), String> {
+    let toolchain = &request.toolchain;
+
+    for platform in [toolchain.target(), toolchain.host()] {
+        if !is_target_triple(platform) {
+            return Err(format!("'{}' is not a valid target", platform));
+        }
+    }
+
+    let profile = request
+        .profile
+        .parse::<InstallProfile>()
+        .map_err(|error| error.to_string())?;
+
+    let components = request
+        .components
+        .iter()
+        .map(|name| {
+            required_component(name).ok_or_else(|| format!("'{}' is not a known component", name))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let spec =
+        ToolchainSpec::new(toolchain.version(), toolchain.target()).with_host(toolchain.host());
+
+    Ok((toolchain, install_args(&spec, profile, &components)))
+}
+
+/// Whether the name looks like a target triple, like `x86_64-unknown-linux-gnu`.
+fn is_target_triple(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with('-')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+/// Accept connections until the token, if any, is cancelled. Each connection is handled on its
+/// own thread, so a client which waits for an install doesn't hold up the others.
+fn serve(
+    listener: TcpListener,
+    state: Arc<State>,
+    cancellation: Option<&CancellationToken>,
+) -> TResult<()> {
+    let listen_error = |error| CargoMSRVError::Io {
+        error,
+        source: IoErrorSource::Listen(
+            listener
+                .local_addr()
+                .map(|address| address.to_string())
+                .unwrap_or_default(),
+        ),
+    };
+
+    // polled, so cancellation is noticed without a connection
+    listener.set_nonblocking(true).map_err(listen_error)?;
+
+    loop {
+        if let Some(token) = cancellation {
+            token.check()?;
+        }
+
+        match listener.accept() {
+            Ok((stream, peer)) => {
+                let state = Arc::clone(&state);
+
+                thread::spawn(move || {
+                    if let Err(error) = handle(stream, &state) {
+                        warn!(%peer, %error, "unable to handle the request");
+                    }
+                });
+            }
+            Err(error) if error.kind() == io::ErrorKind::WouldBlock => {
+                thread::sleep(POLL_INTERVAL);
+            }
+            Err(error) => warn!(%error, "unable to accept a connection"),
+        }
+    }
+}
+
+/// Read the request of the connection, and write the response.
+fn handle(stream: TcpStream, state: &State) -> io::Result<()> {
+    // the connection may inherit the non-blocking mode of the listener
+    stream.set_nonblocking(false)?;
+
+    let mut line = String::new();
+    BufReader::new(&stream).read_line(&mut line)?;
+
+    let response = match serde_json::from_str::<Request>(&line) {
+        Ok(request) => state.respond(request),
+        Err(error) => Response::Error {
+            message: format!("unable to parse the request: {}", error),
+        },
+    };
+
+    let mut line = serde_json::to_string(&response).expect("the response is serializable");
+    line.push('\n');
+
+    (&stream).write_all(line.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::release_index_source::ReleaseIndexSource;
+    use crate::semver;
+    use crate::server::{Client, ServerSource};
+
+    /// Serve the state on a port chosen by the OS, until the returned token is cancelled.
+    fn start(state: State) -> (String, CancellationToken, thread::JoinHandle<TResult<()>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        let token = CancellationToken::new();
+
+        let server = {
+            let token = token.clone();
+            thread::spawn(move || serve(listener, Arc::new(state), Some(&token)))
+        };
+
+        (address, token, server)
+    }
+
+    #[test]
+    fn release_index_of_server() {
+        let state = State::new(vec!["1.60.0".to_string(), "1.56.1".to_string()]);
+        let (address, token, server) = start(state);
+
+        let index = ServerSource::new(&address).fetch().unwrap();
+
+        assert_eq!(
+            index
+                .releases()
+                .iter()
+                .map(Release::version)
+                .collect::<Vec<_>>(),
+            vec![
+                &semver::Version::new(1, 60, 0),
+                &semver::Version::new(1, 56, 1)
+            ]
+        );
+
+        token.cancel();
+        assert!(matches!(
+            server.join().unwrap(),
+            Err(CargoMSRVError::Cancelled)
+        ));
+    }
+
+    fn install_request(target: &str, components: &[&'static str]) -> InstallRequest {
+        let version = semver::Version::new(1, 56, 0);
+        let toolchain = ToolchainSpec::new(&version, target);
+
+        InstallRequest::new(&toolchain, InstallProfile::Minimal, components)
+    }
+
+    /// Remember the install of the toolchain of the request, as if it was installed before.
+    fn installed_before(state: &State, request: &InstallRequest, status: InstallStatus) {
+        let (toolchain, args) = install_args_of(request).unwrap();
+
+        state
+            .toolchain(toolchain.spec())
+            .lock()
+            .unwrap()
+            .insert(args, status);
+    }
+
+    #[test]
+    fn installed_toolchain_is_not_installed_again() {
+        let request = install_request("x86_64-unknown-linux-gnu", &[]);
+        let state = State::new(Vec::new());
+        installed_before(&state, &request, InstallStatus::Unavailable);
+
+        let (address, token, _server) = start(state);
+
+        let response = Client::new(&address)
+            .request(&Request::Install(request))
+            .unwrap();
+
+        assert_eq!(
+            response,
+            Response::Install {
+                status: InstallStatus::Unavailable,
+                stderr: None
+            }
+        );
+
+        token.cancel();
+    }
+
+    #[test]
+    fn toolchains_are_locked_separately() {
+        let installing = install_request("x86_64-unknown-linux-gnu", &[]);
+        let installed = install_request("aarch64-unknown-linux-gnu", &[]);
+
+        let state = State::new(Vec::new());
+        installed_before(&state, &installed, InstallStatus::Installed);
+
+        // as if the other toolchain is being installed
+        let lock = state.toolchain(install_args_of(&installing).unwrap().0.spec());
+        let _installing = lock.lock().unwrap();
+
+        assert_eq!(
+            state.install(&installed),
+            Response::Install {
+                status: InstallStatus::Installed,
+                stderr: None
+            }
+        );
+    }
+
+    #[yare::parameterized(
+        option_as_target = { install_request("--force", &[]) },
+        unknown_component = { install_request("x86_64-unknown-linux-gnu", &["--force"]) },
+    )]
+    fn invalid_install_request(request: InstallRequest) {
+        let state = State::new(Vec::new());
+
+        assert!(matches!(state.install(&request), Response::Error { .. }));
+    }
+
+    #[test]
+    fn only_cargo_commands_are_checked() {
+        let state = State::new(Vec::new());
+        let request = install_request("x86_64-unknown-linux-gnu", &[]);
+        let command = vec!["sh".to_string(), "-c".to_string(), "true".to_string()];
+
+        assert!(matches!(
+            state.check(&request, Path::new("."), &command),
+            Response::Error { .. }
+        ));
+    }
+
+    #[test]
+    fn unreachable_server() {
+        // bound, so nothing else listens on the port, and dropped before the request
+        let address = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .to_string();
+
+        let result = ServerSource::new(&address).fetch();
+
+        assert!(matches!(
+            result,
+            Err(CargoMSRVError::ServerUnreachable { .. })
+        ));
+    }
+}
@@ -1,12 +1,15 @@
+use std::convert::TryFrom;
 use std::io::Write;
+use std::path::{Path, PathBuf};
 
 use rust_releases::semver;
 
 use toml_edit::{table, value, Document, Item, Value};
 
-use crate::error::{IoErrorSource, SetMsrvError};
+use crate::error::{IoErrorSource, SetMsrvError, WorkspaceMemberConflict};
 use crate::manifest::bare_version::BareVersion;
-use crate::manifest::{CargoManifestParser, TomlParser};
+use crate::manifest::workspace;
+use crate::manifest::{CargoManifest, CargoManifestParser, TomlParser};
 use crate::reporter::event::{
     AuxiliaryOutput, AuxiliaryOutputItem, Destination, MsrvKind, SetOutputMessage,
 };
@@ -37,11 +40,14 @@ fn set_msrv(config: &Config, reporter: &impl Reporter) -> TResult<()> {
 
     // Parse the Cargo manifest contents, in particular the MSRV value
     let mut manifest = CargoManifestParser::default().parse::<Document>(&contents)?;
-    check_workspace(&manifest)?;
     let msrv = &config.sub_command_config().set().msrv;
 
     // Set the MSRV
-    set_or_override_msrv(&mut manifest, msrv)?;
+    if workspace::is_workspace(&manifest) {
+        set_workspace_msrv(&mut manifest, cargo_toml, msrv)?;
+    } else {
+        set_or_override_msrv(&mut manifest, msrv)?;
+    }
 
     // Open the Cargo manifest file with write permissions and truncate the current its contents
     let mut file = std::fs::OpenOptions::new()
@@ -73,14 +79,94 @@ fn set_msrv(config: &Config, reporter: &impl Reporter) -> TResult<()> {
     Ok(())
 }
 
-fn check_workspace(manifest: &Document) -> TResult<()> {
-    if manifest.as_table().get("package").is_none()
-        && manifest.as_table().get("workspace").is_some()
-    {
-        Err(CargoMSRVError::WorkspaceFound)
-    } else {
-        Ok(())
+/// Set the MSRV of a workspace, as the `workspace.package.rust-version` field, from which members
+/// may inherit it. A root package is given the same MSRV, unless it inherits it already.
+///
+/// The MSRV is only set if each workspace member which declares its own MSRV declares one which
+/// is at least as recent, so the workspace stays consistent.
+fn set_workspace_msrv(
+    manifest: &mut Document,
+    cargo_toml: &Path,
+    msrv: &BareVersion,
+) -> TResult<()> {
+    let is_rust_version = msrv.to_semver_version() >= RUST_VERSION_SUPPORTED_SINCE;
+
+    if !is_rust_version {
+        // Without a workspace MSRV, a root package may still be given its own MSRV
+        return if workspace::is_virtual(manifest) {
+            Err(SetMsrvError::WorkspaceRequiresRustVersion(msrv.clone()).into())
+        } else {
+            set_or_override_msrv(manifest, msrv)
+        };
+    }
+
+    let root = cargo_toml.parent().unwrap_or_else(|| Path::new(""));
+    let conflicts = member_conflicts(&workspace::member_manifests(root, manifest)?, msrv)?;
+
+    if !conflicts.is_empty() {
+        return Err(SetMsrvError::InconsistentWorkspace {
+            msrv: msrv.clone(),
+            conflicts,
+        }
+        .into());
+    }
+
+    let package = &mut manifest["workspace"]["package"];
+
+    if package.is_none() {
+        // Explicitly create the table, otherwise it would default to an inline table instead
+        *package = table();
+    }
+
+    package["rust-version"] = value(msrv.to_string());
+
+    if !workspace::is_virtual(manifest) && !inherits_rust_version(manifest) {
+        set_or_override_msrv(manifest, msrv)?;
+    }
+
+    Ok(())
+}
+
+/// The workspace members which declare an MSRV which is less recent than the given MSRV.
+/// Members which inherit the MSRV of the workspace, or declare none, don't conflict.
+fn member_conflicts(
+    members: &[PathBuf],
+    msrv: &BareVersion,
+) -> TResult<Vec<WorkspaceMemberConflict>> {
+    let msrv = msrv.to_semver_version();
+    let mut conflicts = Vec::new();
+
+    for member in members {
+        let contents = std::fs::read_to_string(member).map_err(|error| CargoMSRVError::Io {
+            error,
+            source: IoErrorSource::ReadFile(member.to_path_buf()),
+        })?;
+
+        let document = CargoManifestParser::default().parse::<Document>(&contents)?;
+        let manifest = CargoManifest::try_from(document)?;
+
+        if let Some(member_msrv) = manifest.minimum_rust_version() {
+            if member_msrv.to_semver_version() < msrv {
+                conflicts.push(WorkspaceMemberConflict {
+                    manifest: member.to_path_buf(),
+                    msrv: member_msrv.clone(),
+                });
+            }
+        }
     }
+
+    Ok(conflicts)
+}
+
+/// Whether the package inherits its MSRV from the workspace, i.e. `rust-version.workspace = true`.
+fn inherits_rust_version(manifest: &Document) -> bool {
+    manifest
+        .as_table()
+        .get("package")
+        .and_then(|package| package.get("rust-version"))
+        .and_then(|rust_version| rust_version.get("workspace"))
+        .and_then(Item::as_bool)
+        .unwrap_or_default()
 }
 
 /// Override MSRV if it is already set, otherwise, simply set it
@@ -924,3 +1010,149 @@ edition = "2021"
         assert_eq!(new_manifest.minimum_rust_version().unwrap(), &METADATA_MSRV)
     }
 }
+
+#[cfg(test)]
+mod set_workspace_msrv_tests {
+    use test_dir::{DirBuilder, TestDir};
+    use toml_edit::Document;
+
+    use crate::error::{SetMsrvError, WorkspaceMemberConflict};
+    use crate::manifest::bare_version::BareVersion;
+    use crate::manifest::{CargoManifestParser, TomlParser};
+    use crate::sub_command::set::set_workspace_msrv;
+    use crate::CargoMSRVError;
+
+    const WORKSPACE: &str = r#"[workspace]
+members = ["crates/*"]
+"#;
+
+    fn workspace(members: &[(&str, &str)]) -> TestDir {
+        let tmp = TestDir::temp();
+
+        for (name, package) in members {
+            let dir = tmp.path("crates").join(name);
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(
+                dir.join("Cargo.toml"),
+                format!("[package]\nname = \"{}\"\n{}", name, package),
+            )
+            .unwrap();
+        }
+
+        tmp
+    }
+
+    fn parse(contents: &str) -> Document {
+        CargoManifestParser::default()
+            .parse::<Document>(contents)
+            .unwrap()
+    }
+
+    #[test]
+    fn set_for_virtual_workspace() {
+        let tmp = workspace(&[
+            ("inherits", "rust-version.workspace = true\n"),
+            ("newer", "rust-version = \"1.62\"\n"),
+            ("unspecified", ""),
+        ]);
+        let mut manifest = parse(WORKSPACE);
+
+        set_workspace_msrv(
+            &mut manifest,
+            &tmp.path("Cargo.toml"),
+            &BareVersion::TwoComponents(1, 60),
+        )
+        .unwrap();
+
+        assert_eq!(
+            manifest["workspace"]["package"]["rust-version"]
+                .as_str()
+                .unwrap(),
+            "1.60"
+        );
+        assert!(manifest.to_string().contains("[workspace.package]"));
+    }
+
+    #[test]
+    fn conflicting_members_are_reported() {
+        let tmp = workspace(&[
+            ("older", "rust-version = \"1.58\"\n"),
+            ("metadata", "\n[package.metadata]\nmsrv = \"1.40\"\n"),
+            ("same", "rust-version = \"1.60.0\"\n"),
+        ]);
+        let mut manifest = parse(WORKSPACE);
+
+        let error = set_workspace_msrv(
+            &mut manifest,
+            &tmp.path("Cargo.toml"),
+            &BareVersion::TwoComponents(1, 60),
+        )
+        .unwrap_err();
+
+        let conflicts = match error {
+            CargoMSRVError::SetMsrv(SetMsrvError::InconsistentWorkspace { conflicts, .. }) => {
+                conflicts
+            }
+            other => panic!("expected an inconsistent workspace, but got: {}", other),
+        };
+
+        assert_eq!(
+            conflicts,
+            vec![
+                WorkspaceMemberConflict {
+                    manifest: tmp.path("crates/metadata/Cargo.toml"),
+                    msrv: BareVersion::TwoComponents(1, 40),
+                },
+                WorkspaceMemberConflict {
+                    manifest: tmp.path("crates/older/Cargo.toml"),
+                    msrv: BareVersion::TwoComponents(1, 58),
+                },
+            ]
+        );
+
+        // The manifest is left as it was
+        assert_eq!(manifest.to_string(), WORKSPACE);
+    }
+
+    #[yare::parameterized(
+        own_msrv = { "[package]\nname = \"root\"\nrust-version = \"1.56\"\n\n[workspace]\n", Some("1.60") },
+        inherited_msrv = { "[package]\nname = \"root\"\nrust-version.workspace = true\n\n[workspace]\n", None },
+    )]
+    fn set_for_root_package(contents: &str, expected: Option<&str>) {
+        let tmp = workspace(&[]);
+        let mut manifest = parse(contents);
+
+        set_workspace_msrv(
+            &mut manifest,
+            &tmp.path("Cargo.toml"),
+            &BareVersion::TwoComponents(1, 60),
+        )
+        .unwrap();
+
+        assert_eq!(
+            manifest["workspace"]["package"]["rust-version"]
+                .as_str()
+                .unwrap(),
+            "1.60"
+        );
+        assert_eq!(manifest["package"]["rust-version"].as_str(), expected);
+    }
+
+    #[test]
+    fn virtual_workspace_requires_rust_version() {
+        let tmp = workspace(&[]);
+        let mut manifest = parse(WORKSPACE);
+
+        let error = set_workspace_msrv(
+            &mut manifest,
+            &tmp.path("Cargo.toml"),
+            &BareVersion::TwoComponents(1, 40),
+        )
+        .unwrap_err();
+
+        assert!(matches!(
+            error,
+            CargoMSRVError::SetMsrv(SetMsrvError::WorkspaceRequiresRustVersion(_))
+        ));
+    }
+}
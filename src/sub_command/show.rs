@@ -1,13 +1,15 @@
 use std::convert::TryFrom;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use toml_edit::Document;
 
+use crate::clippy_config;
 use crate::config::Config;
 use crate::error::{CargoMSRVError, IoErrorSource, TResult};
 
+use crate::manifest::bare_version::BareVersion;
 use crate::manifest::{CargoManifest, CargoManifestParser, TomlParser};
-use crate::reporter::event::ShowOutputMessage;
+use crate::reporter::event::{ShowOutputMessage, ToolConfigMsrv};
 use crate::reporter::Reporter;
 use crate::SubCommand;
 
@@ -42,6 +44,36 @@ fn show_msrv(config: &Config, reporter: &impl Reporter) -> TResult<()> {
         cargo_toml.to_path_buf(),
     ))?;
 
+    let crate_root = config.context().crate_root_path()?;
+    let sync = config.sub_command_config().show().sync_clippy;
+
+    show_clippy_msrv(crate_root, msrv, sync, reporter)
+}
+
+/// Report the MSRV of each clippy configuration file which specifies one, and whether it agrees
+/// with the MSRV of the Cargo manifest. If `sync` is set, a disagreeing MSRV is overwritten.
+fn show_clippy_msrv(
+    crate_root: &Path,
+    msrv: &BareVersion,
+    sync: bool,
+    reporter: &impl Reporter,
+) -> TResult<()> {
+    for path in clippy_config::find(crate_root) {
+        let version = match clippy_config::read_msrv(&path)? {
+            Some(version) => version,
+            None => continue,
+        };
+
+        let mut event = ToolConfigMsrv::new("clippy", &path, version, msrv.clone(), false);
+
+        if sync && !event.is_consistent() {
+            clippy_config::write_msrv(&path, msrv)?;
+            event = ToolConfigMsrv::new("clippy", &path, msrv.clone(), msrv.clone(), true);
+        }
+
+        reporter.report_event(event)?;
+    }
+
     Ok(())
 }
 
@@ -50,3 +82,59 @@ pub enum Error {
     #[error("MSRV was not specified in Cargo manifest at '{}'", .0.display())]
     NoMSRVInCargoManifest(PathBuf),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::event::Message;
+    use crate::reporter::TestReporter;
+    use test_dir::{DirBuilder, TestDir};
+
+    #[yare::parameterized(
+        consistent = { "1.56", false, true, false, BareVersion::TwoComponents(1, 56) },
+        inconsistent = { "1.60", false, false, false, BareVersion::TwoComponents(1, 60) },
+        synced = { "1.60", true, true, true, BareVersion::ThreeComponents(1, 56, 0) },
+    )]
+    fn clippy_msrv(
+        clippy_msrv: &str,
+        sync: bool,
+        consistent: bool,
+        synced: bool,
+        expected_msrv: BareVersion,
+    ) {
+        let tmp = TestDir::temp();
+        let path = tmp.path("clippy.toml");
+        std::fs::write(&path, format!("msrv = \"{}\"\n", clippy_msrv)).unwrap();
+
+        let reporter = TestReporter::default();
+        let manifest_msrv = BareVersion::ThreeComponents(1, 56, 0);
+        show_clippy_msrv(tmp.root(), &manifest_msrv, sync, reporter.reporter()).unwrap();
+
+        let events = reporter.wait_for_events();
+        assert_eq!(events.len(), 1);
+
+        if let Message::ToolConfigMsrv(it) = events[0].message() {
+            assert_eq!(it.is_consistent(), consistent);
+            assert_eq!(it.synced(), synced);
+        } else {
+            panic!("expected the MSRV of clippy to be reported");
+        }
+
+        assert_eq!(
+            clippy_config::read_msrv(&path).unwrap(),
+            Some(expected_msrv)
+        );
+    }
+
+    #[test]
+    fn clippy_config_without_msrv() {
+        let tmp = TestDir::temp();
+        std::fs::write(tmp.path(".clippy.toml"), "too-many-lines-threshold = 80\n").unwrap();
+
+        let reporter = TestReporter::default();
+        let manifest_msrv = BareVersion::TwoComponents(1, 56);
+        show_clippy_msrv(tmp.root(), &manifest_msrv, true, reporter.reporter()).unwrap();
+
+        assert!(reporter.wait_for_events().is_empty());
+    }
+}
@@ -0,0 +1,606 @@
+//! Validates the MSRV declared by a Cargo manifest before it is used, e.g. on the CI before a
+//! release: the `package.rust-version` and `package.metadata.msrv` keys must be valid versions,
+//! which are neither newer than the most recent stable release, nor older than the edition of
+//! the package, and a version inherited from the workspace must be declared by the workspace.
+
+use std::path::{Path, PathBuf};
+
+use rust_releases::ReleaseIndex;
+use toml_edit::{Document, Item, TableLike};
+
+use crate::cli::rust_releases_opts::Edition;
+use crate::config::Config;
+use crate::error::{CargoMSRVError, IoErrorSource, TResult};
+use crate::manifest::bare_version::BareVersion;
+use crate::manifest::workspace::is_workspace;
+use crate::reporter::event::{Diagnostic, DiagnosticCode, ManifestValidation, Severity, Span};
+use crate::reporter::Reporter;
+use crate::semver;
+use crate::sub_command::SubCommand;
+
+const PACKAGE: &[&str] = &["package"];
+const PACKAGE_METADATA: &[&str] = &["package", "metadata"];
+const WORKSPACE_PACKAGE: &[&str] = &["workspace", "package"];
+const RUST_VERSION: &str = "rust-version";
+
+/// Validates the MSRV declared by the Cargo manifest, and reports each issue as a diagnostic.
+pub struct Validate<'index> {
+    release_index: &'index ReleaseIndex,
+}
+
+impl<'index> Validate<'index> {
+    pub fn new(release_index: &'index ReleaseIndex) -> Self {
+        Self { release_index }
+    }
+}
+
+impl<'index> SubCommand for Validate<'index> {
+    type Output = ();
+
+    fn run(&self, config: &Config, reporter: &impl Reporter) -> TResult<Self::Output> {
+        let manifest_path = config.context().manifest_path()?;
+        let latest = self
+            .release_index
+            .most_recent()
+            .map(|release| release.version().clone());
+
+        let diagnostics = validate(manifest_path, latest.as_ref())?;
+        let validation = ManifestValidation::new(manifest_path, diagnostics);
+        let errors = validation.count(Severity::Error);
+
+        reporter.report_event(validation)?;
+
+        if errors > 0 {
+            return Err(Error::InvalidManifest {
+                path: manifest_path.to_path_buf(),
+                errors,
+            }
+            .into());
+        }
+
+        Ok(())
+    }
+}
+
+/// Validate the MSRV declared by the manifest at the given path, against the most recent stable
+/// release, if known.
+fn validate(manifest_path: &Path, latest: Option<&semver::Version>) -> TResult<Vec<Diagnostic>> {
+    let manifest = Manifest::read(manifest_path)?;
+    let workspace = find_workspace_root(&manifest)?;
+
+    let mut validator = Validator {
+        latest,
+        edition: edition(&manifest, workspace.as_ref()),
+        diagnostics: Vec::new(),
+    };
+
+    let inherits = manifest
+        .get(PACKAGE, RUST_VERSION)
+        .map(inherits_workspace)
+        .unwrap_or_default();
+
+    let rust_version = match manifest.get(PACKAGE, RUST_VERSION) {
+        Some(_) if inherits => validator.inherited(&manifest, workspace.as_ref()),
+        Some(item) => validator.version(&manifest, PACKAGE, RUST_VERSION, item),
+        None => None,
+    };
+
+    let metadata_msrv = manifest
+        .get(PACKAGE_METADATA, "msrv")
+        .and_then(|item| validator.version(&manifest, PACKAGE_METADATA, "msrv", item));
+
+    if let (Some(rust_version), Some(metadata_msrv)) = (&rust_version, &metadata_msrv) {
+        if rust_version.to_semver_version() != metadata_msrv.to_semver_version() {
+            validator.push(manifest.diagnostic(
+                Severity::Warning,
+                DiagnosticCode::ConflictingKeys,
+                format!(
+                    "{} differs from package.rust-version, {}, which takes precedence",
+                    metadata_msrv, rust_version
+                ),
+                PACKAGE_METADATA,
+                "msrv",
+            ));
+        }
+    }
+
+    if let (Some(workspace), false) = (&workspace, inherits) {
+        let workspace_msrv = match workspace.get(WORKSPACE_PACKAGE, RUST_VERSION) {
+            // The root manifest declares the version which its members inherit
+            Some(item) if workspace.path == manifest.path => {
+                validator.version(workspace, WORKSPACE_PACKAGE, RUST_VERSION, item)
+            }
+            Some(item) => item.as_str().and_then(|version| version.parse().ok()),
+            None => None,
+        };
+
+        if let (Some(rust_version), Some(workspace_msrv)) = (&rust_version, &workspace_msrv) {
+            if rust_version.to_semver_version() != workspace_msrv.to_semver_version() {
+                validator.push(manifest.diagnostic(
+                    Severity::Warning,
+                    DiagnosticCode::DiffersFromWorkspace,
+                    format!(
+                        "{} differs from workspace.package.rust-version, {}; use \
+                        `rust-version.workspace = true` to inherit it",
+                        rust_version, workspace_msrv
+                    ),
+                    PACKAGE,
+                    RUST_VERSION,
+                ));
+            }
+        }
+    }
+
+    Ok(validator.diagnostics)
+}
+
+/// Collects the diagnostics of the declared versions.
+struct Validator<'v> {
+    latest: Option<&'v semver::Version>,
+    edition: Option<Edition>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'v> Validator<'v> {
+    fn push(&mut self, diagnostic: Diagnostic) {
+        self.diagnostics.push(diagnostic);
+    }
+
+    /// Validate the version declared by the given key, and return it if it can be parsed.
+    fn version(
+        &mut self,
+        manifest: &Manifest,
+        table: &[&str],
+        key: &str,
+        item: &Item,
+    ) -> Option<BareVersion> {
+        let value = match item.as_str() {
+            Some(value) => value,
+            None => {
+                self.push(manifest.diagnostic(
+                    Severity::Error,
+                    DiagnosticCode::InvalidType,
+                    "expected a version string, like \"1.56\"",
+                    table,
+                    key,
+                ));
+                return None;
+            }
+        };
+
+        let version = match value.parse::<BareVersion>() {
+            Ok(version) => version,
+            Err(error) => {
+                self.push(manifest.diagnostic(
+                    Severity::Error,
+                    DiagnosticCode::InvalidSyntax,
+                    format!("'{}' is not a valid Rust version: {}", value, error),
+                    table,
+                    key,
+                ));
+                return None;
+            }
+        };
+
+        if let Some(latest) = self.latest {
+            if !version.is_at_least(latest) {
+                self.push(manifest.diagnostic(
+                    Severity::Error,
+                    DiagnosticCode::NewerThanStable,
+                    format!(
+                        "{} is newer than the most recent stable Rust release, {}",
+                        version, latest
+                    ),
+                    table,
+                    key,
+                ));
+            }
+        }
+
+        if let Some(edition) = self.edition {
+            let minimum = edition.as_bare_version();
+
+            if !minimum.is_at_least(&version.to_semver_version()) {
+                self.push(manifest.diagnostic(
+                    Severity::Error,
+                    DiagnosticCode::BelowEditionMinimum,
+                    format!(
+                        "{} is older than Rust {}, the first release which supports the {} \
+                        edition of the package",
+                        version, minimum, edition
+                    ),
+                    table,
+                    key,
+                ));
+            }
+        }
+
+        Some(version)
+    }
+
+    /// Validate the version inherited from the workspace with `rust-version.workspace = true`.
+    fn inherited(
+        &mut self,
+        manifest: &Manifest,
+        workspace: Option<&Manifest>,
+    ) -> Option<BareVersion> {
+        let workspace = match workspace {
+            Some(workspace) => workspace,
+            None => {
+                self.push(manifest.diagnostic(
+                    Severity::Error,
+                    DiagnosticCode::MissingWorkspace,
+                    "the MSRV is inherited from the workspace, but the package is not part of a \
+                    workspace",
+                    PACKAGE,
+                    RUST_VERSION,
+                ));
+                return None;
+            }
+        };
+
+        match workspace.get(WORKSPACE_PACKAGE, RUST_VERSION) {
+            Some(item) => self.version(workspace, WORKSPACE_PACKAGE, RUST_VERSION, item),
+            None => {
+                self.push(manifest.diagnostic(
+                    Severity::Error,
+                    DiagnosticCode::MissingWorkspaceKey,
+                    format!(
+                        "the MSRV is inherited from the workspace, but '{}' does not declare \
+                        workspace.package.rust-version",
+                        workspace.path.display()
+                    ),
+                    PACKAGE,
+                    RUST_VERSION,
+                ));
+                None
+            }
+        }
+    }
+}
+
+/// A parsed manifest, with its source, in which the keys of diagnostics are located.
+#[derive(Clone)]
+struct Manifest {
+    path: PathBuf,
+    contents: String,
+    document: Document,
+}
+
+impl Manifest {
+    fn read(path: &Path) -> TResult<Self> {
+        let contents = std::fs::read_to_string(path).map_err(|error| CargoMSRVError::Io {
+            error,
+            source: IoErrorSource::ReadFile(path.to_path_buf()),
+        })?;
+        let document = contents
+            .parse::<Document>()
+            .map_err(CargoMSRVError::ParseToml)?;
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            contents,
+            document,
+        })
+    }
+
+    /// The item of the given key, in the table with the given path.
+    fn get(&self, table: &[&str], key: &str) -> Option<&Item> {
+        let mut current: &dyn TableLike = self.document.as_table();
+
+        for name in table {
+            current = current.get(name)?.as_table_like()?;
+        }
+
+        current.get(key)
+    }
+
+    fn diagnostic(
+        &self,
+        severity: Severity,
+        code: DiagnosticCode,
+        message: impl Into<String>,
+        table: &[&str],
+        key: &str,
+    ) -> Diagnostic {
+        let mut path = table.to_vec();
+        path.push(key);
+
+        Diagnostic::new(
+            severity,
+            code,
+            message,
+            &self.path,
+            path.join("."),
+            locate(&self.contents, table, key),
+        )
+    }
+}
+
+/// Whether the item inherits its value from the workspace, e.g. `rust-version.workspace = true`.
+fn inherits_workspace(item: &Item) -> bool {
+    item.get("workspace")
+        .and_then(Item::as_bool)
+        .unwrap_or_default()
+}
+
+/// The edition of the package, which may be inherited from the workspace.
+fn edition(manifest: &Manifest, workspace: Option<&Manifest>) -> Option<Edition> {
+    let item = manifest.get(PACKAGE, "edition")?;
+
+    let item = if inherits_workspace(item) {
+        workspace?.get(WORKSPACE_PACKAGE, "edition")?
+    } else {
+        item
+    };
+
+    item.as_str()?.parse().ok()
+}
+
+/// The manifest of the workspace root of the package: the manifest itself if it declares a
+/// workspace, or else the first manifest in the parent directories which does, as Cargo looks
+/// for it when `package.workspace` is not given.
+fn find_workspace_root(manifest: &Manifest) -> TResult<Option<Manifest>> {
+    if is_workspace(&manifest.document) {
+        return Ok(Some(manifest.clone()));
+    }
+
+    let dir = match manifest.path.parent() {
+        Some(dir) if dir.as_os_str().is_empty() => Path::new("."),
+        Some(dir) => dir,
+        None => return Ok(None),
+    };
+
+    let dir = dir.canonicalize().map_err(|error| CargoMSRVError::Io {
+        error,
+        source: IoErrorSource::ReadDir(dir.to_path_buf()),
+    })?;
+
+    for ancestor in dir.ancestors().skip(1) {
+        let candidate = ancestor.join("Cargo.toml");
+
+        if candidate.is_file() {
+            let candidate = Manifest::read(&candidate)?;
+
+            if is_workspace(&candidate.document) {
+                return Ok(Some(candidate));
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+/// Locate the given key of the table with the given path in the source of a manifest.
+///
+/// The items parsed by `toml_edit` do not know their position in the source, so the key is
+/// searched for line by line: either below the header of its table, e.g. `rust-version` below
+/// `[package]`, or as a dotted key below the header of a parent table, e.g. `package.rust-version`
+/// at the root of the manifest. The span covers the key and its value.
+fn locate(contents: &str, table: &[&str], key: &str) -> Option<Span> {
+    let mut path = table.to_vec();
+    path.push(key);
+
+    // The path of the current table, or `None` within an array of tables
+    let mut header = Some(Vec::new());
+    let mut offset = 0;
+
+    for (index, line) in contents.split_inclusive('\n').enumerate() {
+        let start = offset;
+        offset += line.len();
+
+        let trimmed = line.trim();
+
+        if trimmed.starts_with("[[") {
+            header = None;
+            continue;
+        }
+
+        if let Some(name) = trimmed.strip_prefix('[') {
+            header = name.split(']').next().map(split_key);
+            continue;
+        }
+
+        let (line_key, _) = match trimmed.split_once('=') {
+            Some(pair) if !trimmed.starts_with('#') => pair,
+            _ => continue,
+        };
+
+        if let Some(header) = &header {
+            let mut full = header.clone();
+            full.extend(split_key(line_key));
+
+            if full.starts_with(&path) {
+                let indent = line.len() - line.trim_start().len();
+
+                return Some(Span {
+                    line: index + 1,
+                    column: indent + 1,
+                    start: start + indent,
+                    end: start + indent + trimmed.len(),
+                });
+            }
+        }
+    }
+
+    None
+}
+
+/// The names of a dotted key, e.g. `package."rust-version"`, without quotes.
+fn split_key(key: &str) -> Vec<&str> {
+    key.split('.')
+        .map(|name| name.trim().trim_matches(|c| c == '"' || c == '\''))
+        .collect()
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("The MSRV declared in '{}' has {} error(s)", .path.display(), .errors)]
+    InvalidManifest { path: PathBuf, errors: usize },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_dir::{DirBuilder, TestDir};
+
+    const LATEST: semver::Version = semver::Version::new(1, 64, 0);
+
+    fn manifest(tmp: &TestDir, dir: &str, contents: &str) -> PathBuf {
+        std::fs::create_dir_all(tmp.path(dir)).unwrap();
+        std::fs::write(tmp.path(dir).join("Cargo.toml"), contents).unwrap();
+
+        tmp.path(dir).join("Cargo.toml")
+    }
+
+    fn codes(manifest: &Path) -> Vec<(Severity, DiagnosticCode, String)> {
+        validate(manifest, Some(&LATEST))
+            .unwrap()
+            .into_iter()
+            .map(|diagnostic| {
+                (
+                    diagnostic.severity(),
+                    diagnostic.code(),
+                    diagnostic.key().to_string(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn valid_manifest() {
+        let tmp = TestDir::temp();
+        let manifest = manifest(
+            &tmp,
+            ".",
+            "[package]\nname = \"a\"\nedition = \"2021\"\nrust-version = \"1.58\"\n",
+        );
+
+        assert!(codes(&manifest).is_empty());
+    }
+
+    #[yare::parameterized(
+        invalid_syntax = { "rust-version = \"1.58.x\"", DiagnosticCode::InvalidSyntax },
+        invalid_type = { "rust-version = 1.58", DiagnosticCode::InvalidType },
+        newer_than_stable = { "rust-version = \"1.65\"", DiagnosticCode::NewerThanStable },
+        below_edition_minimum = { "rust-version = \"1.55\"", DiagnosticCode::BelowEditionMinimum },
+        missing_workspace = { "rust-version.workspace = true", DiagnosticCode::MissingWorkspace },
+    )]
+    fn error(key: &str, expected: DiagnosticCode) {
+        let tmp = TestDir::temp();
+        let manifest = manifest(
+            &tmp,
+            ".",
+            &format!("[package]\nname = \"a\"\nedition = \"2021\"\n{}\n", key),
+        );
+
+        assert_eq!(
+            codes(&manifest),
+            vec![(
+                Severity::Error,
+                expected,
+                "package.rust-version".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn conflicting_keys() {
+        let tmp = TestDir::temp();
+        let manifest = manifest(
+            &tmp,
+            ".",
+            "[package]\nname = \"a\"\nrust-version = \"1.58\"\n\n[package.metadata]\nmsrv = \"1.56\"\n",
+        );
+
+        assert_eq!(
+            codes(&manifest),
+            vec![(
+                Severity::Warning,
+                DiagnosticCode::ConflictingKeys,
+                "package.metadata.msrv".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn inherited_from_workspace() {
+        let tmp = TestDir::temp();
+        let root = manifest(
+            &tmp,
+            ".",
+            "[workspace]\nmembers = [\"crates/*\"]\n\n[workspace.package]\nrust-version = \"1.70\"\nedition = \"2021\"\n",
+        );
+        let a = manifest(
+            &tmp,
+            "crates/a",
+            "[package]\nname = \"a\"\nedition.workspace = true\nrust-version.workspace = true\n",
+        );
+        let b = manifest(
+            &tmp,
+            "crates/b",
+            "[package]\nname = \"b\"\nrust-version = \"1.60\"\n",
+        );
+
+        let diagnostics = validate(&a, Some(&LATEST)).unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code(), DiagnosticCode::NewerThanStable);
+        assert_eq!(diagnostics[0].key(), "workspace.package.rust-version");
+        assert_eq!(
+            diagnostics[0].manifest().canonicalize().unwrap(),
+            root.canonicalize().unwrap()
+        );
+        assert_eq!(diagnostics[0].span().map(|span| span.line), Some(5));
+
+        assert_eq!(
+            codes(&b),
+            vec![(
+                Severity::Warning,
+                DiagnosticCode::DiffersFromWorkspace,
+                "package.rust-version".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn workspace_without_msrv() {
+        let tmp = TestDir::temp();
+        manifest(&tmp, ".", "[workspace]\nmembers = [\"a\"]\n");
+        let a = manifest(
+            &tmp,
+            "a",
+            "[package]\nname = \"a\"\nrust-version.workspace = true\n",
+        );
+
+        assert_eq!(
+            codes(&a),
+            vec![(
+                Severity::Error,
+                DiagnosticCode::MissingWorkspaceKey,
+                "package.rust-version".to_string()
+            )]
+        );
+    }
+
+    #[yare::parameterized(
+        table = { "[package]\nname = \"a\"\nrust-version = \"1.56\"\n", &["package"], "rust-version", Some((3, 1, 21, 42)) },
+        indented = { "[package]\n  rust-version = \"1.56\" \n", &["package"], "rust-version", Some((2, 3, 12, 33)) },
+        dotted = { "package.rust-version = \"1.56\"\n", &["package"], "rust-version", Some((1, 1, 0, 29)) },
+        dotted_in_parent = { "[package]\nmetadata.msrv = \"1.56\"\n", &["package", "metadata"], "msrv", Some((2, 1, 10, 32)) },
+        inherited = { "[package]\nrust-version.workspace = true\n", &["package"], "rust-version", Some((2, 1, 10, 39)) },
+        other_table = { "[dependencies]\nrust-version = \"1.56\"\n", &["package"], "rust-version", None },
+        array_of_tables = { "[package]\n[[bin]]\nrust-version = \"1.56\"\n", &["package"], "rust-version", None },
+        comment = { "[package]\n# rust-version = \"1.56\"\n", &["package"], "rust-version", None },
+    )]
+    fn locate_key(
+        contents: &str,
+        table: &[&str],
+        key: &str,
+        expected: Option<(usize, usize, usize, usize)>,
+    ) {
+        let span =
+            locate(contents, table, key).map(|span| (span.line, span.column, span.start, span.end));
+
+        assert_eq!(span, expected);
+    }
+}
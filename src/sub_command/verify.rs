@@ -1,19 +1,24 @@
 use std::convert::TryFrom;
 use std::path::{Path, PathBuf};
 
-use rust_releases::{Release, ReleaseIndex};
+use rust_releases::{semver, Release, ReleaseIndex};
 
 use toml_edit::Document;
 
-use crate::check::Check;
+use crate::check::{Check, CheckChain};
 use crate::config::Config;
 use crate::error::{CargoMSRVError, IoErrorSource, TResult};
 use crate::manifest::bare_version::BareVersion;
 use crate::manifest::{CargoManifest, CargoManifestParser, TomlParser};
+use crate::msrv::resolve_msrv;
 use crate::outcome::Outcome;
 use crate::reporter::Reporter;
 use crate::sub_command::SubCommand;
-use crate::toolchain::ToolchainSpec;
+use crate::writer::result_file::ResultFile;
+use crate::writer::write_msrv::write_msrv;
+
+pub(crate) use changed_members::verify_changed_members;
+use published_metadata::check_published_metadata;
 
 /// Verifier which determines whether a given Rust version is deemed compatible or not.
 pub struct Verify<'index, C: Check> {
@@ -37,10 +42,24 @@ impl<'index, C: Check> SubCommand for Verify<'index, C> {
     type Output = ();
 
     /// Run the verifier against a Rust version which is obtained from the config.
-    fn run(&self, config: &Config, _reporter: &impl Reporter) -> TResult<Self::Output> {
+    fn run(&self, config: &Config, reporter: &impl Reporter) -> TResult<Self::Output> {
         let rust_version = RustVersion::try_from_config(config)?;
 
-        verify_msrv(config, self.release_index, rust_version, &self.runner)?;
+        if config
+            .sub_command_config()
+            .verify()
+            .check_published_metadata
+        {
+            check_published_metadata(config)?;
+        }
+
+        verify_msrv(
+            config,
+            reporter,
+            self.release_index,
+            rust_version,
+            &self.runner,
+        )?;
 
         Ok(())
     }
@@ -61,17 +80,35 @@ fn parse_manifest(path: &Path) -> TResult<CargoManifest> {
 /// for the (given or specified) `rust_version`.
 fn verify_msrv(
     config: &Config,
+    reporter: &impl Reporter,
     release_index: &ReleaseIndex,
     rust_version: RustVersion,
     runner: &impl Check,
 ) -> TResult<()> {
-    let bare_version = rust_version.version();
-    let version =
-        bare_version.try_to_semver(release_index.releases().iter().map(Release::version))?;
+    let pin_patch = config.sub_command_config().verify().pin_patch;
+
+    // A two component version is verified against its most recent patch release, unless pinned
+    let bare_version = if pin_patch {
+        rust_version.version().pin_patch()
+    } else {
+        rust_version.version().clone()
+    };
+    let version = resolve_msrv(
+        config,
+        reporter,
+        &bare_version,
+        release_index.releases().iter().map(Release::version),
+    )?;
 
-    let toolchain = ToolchainSpec::new(version, config.target());
+    let toolchain = config.toolchain_spec(version);
+
+    // surfaces the Rust version which cargo requires, when it refuses the toolchain
+    let runner = CheckChain::from_config(runner, config, reporter);
 
     match runner.check(config, &toolchain)? {
+        Outcome::Success(_) if config.sub_command_config().verify().write_on_success => {
+            write_if_newer(config, reporter, release_index, &rust_version, version)
+        }
         Outcome::Success(_) => Ok(()),
         Outcome::Failure(_) => Err(CargoMSRVError::SubCommandVerify(Error::VerifyFailed(
             VerifyFailed::from(rust_version),
@@ -79,6 +116,47 @@ fn verify_msrv(
     }
 }
 
+/// Write the verified `version` to the Cargo manifest, if it is newer than the MSRV which is
+/// currently specified in the manifest.
+fn write_if_newer(
+    config: &Config,
+    reporter: &impl Reporter,
+    release_index: &ReleaseIndex,
+    rust_version: &RustVersion,
+    version: &semver::Version,
+) -> TResult<()> {
+    // The version was read from the manifest, so it can't be newer than itself
+    if let RustVersionSource::Manifest(_) = rust_version.source {
+        return Ok(());
+    }
+
+    let path = config.context().manifest_path()?;
+    let manifest = parse_manifest(path)?;
+    let pin_patch = config.sub_command_config().verify().pin_patch;
+
+    // A two component declared version like `1.60` also covers `1.60.2`, unless pinned
+    let declared = manifest.minimum_rust_version().map(|declared| {
+        if pin_patch {
+            declared.pin_patch()
+        } else {
+            declared.clone()
+        }
+    });
+
+    match declared {
+        Some(declared) if declared.is_at_most(version) => {
+            info!(
+                %declared,
+                %version,
+                "not writing the verified version, since the declared MSRV is at least as recent"
+            );
+
+            Ok(())
+        }
+        _ => write_msrv(config, reporter, release_index, version),
+    }
+}
+
 /// Error which can be returned if the verifier deemed the tested Rust version incompatible.
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -86,6 +164,36 @@ pub enum Error {
         "Crate source was found to be incompatible with Rust version '{}' specified {}", .0.rust_version, .0.source
     )]
     VerifyFailed(VerifyFailed),
+
+    #[error(
+        "The MSRV in '{}' was found with check command '{recorded}', which differs from the current check command '{current}'", .path.display()
+    )]
+    CheckCommandMismatch {
+        path: PathBuf,
+        recorded: String,
+        current: String,
+    },
+
+    #[error("Unable to determine the files which changed since '{revision}': {message}")]
+    ChangedFiles { revision: String, message: String },
+
+    #[error("The MSRV of {failed} out of {total} changed workspace members could not be verified")]
+    MembersFailed { failed: usize, total: usize },
+
+    #[error("Unable to fetch the published metadata from '{url}': {message}")]
+    FetchPublishedMetadata { url: String, message: String },
+
+    #[error(
+        "The MSRV in the Cargo manifest ({}) differs from the MSRV of the latest published version of {name}, {version} ({})",
+        .local.as_ref().map_or_else(|| "none".to_string(), ToString::to_string),
+        .published.as_ref().map_or_else(|| "none".to_string(), ToString::to_string)
+    )]
+    PublishedMetadataMismatch {
+        name: String,
+        version: String,
+        published: Option<BareVersion>,
+        local: Option<BareVersion>,
+    },
 }
 
 /// Data structure which contains information about which version failed to verify, and where
@@ -117,11 +225,18 @@ struct RustVersion {
 }
 
 impl RustVersion {
-    /// Obtain the rust-version from one of two sources, in order:
-    /// 1. the rust-version given to the verify subcommand, or
-    /// 2. the rust-version as specified in the Cargo manifest
+    /// Obtain the rust-version from one of three sources, in order:
+    /// 1. the rust-version given to the verify subcommand,
+    /// 2. the MSRV recorded in the result file given to the verify subcommand, or
+    /// 3. the rust-version as specified in the Cargo manifest
     fn try_from_config(config: &Config) -> TResult<Self> {
-        let rust_version = config.sub_command_config().verify().rust_version.as_ref();
+        let verify_config = config.sub_command_config().verify();
+
+        if let Some(path) = verify_config.against_result.as_deref() {
+            return Self::try_from_result_file(config, path);
+        }
+
+        let rust_version = verify_config.rust_version.as_ref();
 
         let (rust_version, source) = match rust_version {
             Some(v) => Ok((v.clone(), RustVersionSource::Arg)),
@@ -142,6 +257,28 @@ impl RustVersion {
         })
     }
 
+    /// Obtain the rust-version from a result file. The MSRV is only comparable when it is verified
+    /// with the same check command as the one it was found with.
+    fn try_from_result_file(config: &Config, path: &Path) -> TResult<Self> {
+        let result = ResultFile::read(path)?;
+        let check_command = config.check_command_string();
+
+        if result.check_command() != check_command {
+            return Err(CargoMSRVError::SubCommandVerify(
+                Error::CheckCommandMismatch {
+                    path: path.to_path_buf(),
+                    recorded: result.check_command().to_string(),
+                    current: check_command,
+                },
+            ));
+        }
+
+        Ok(Self {
+            rust_version: BareVersion::from(result.msrv()),
+            source: RustVersionSource::ResultFile(path.to_path_buf()),
+        })
+    }
+
     /// Get the bare (two- or three component) version specifying the Rust version.
     fn version(&self) -> &BareVersion {
         &self.rust_version
@@ -156,4 +293,167 @@ enum RustVersionSource {
 
     #[error("as MSRV in the Cargo manifest located at '{0}'")]
     Manifest(PathBuf),
+
+    #[error("as MSRV in the result file located at '{0}'")]
+    ResultFile(PathBuf),
+}
+
+mod changed_members;
+mod published_metadata;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::TestRunner;
+    use crate::config::verify::VerifyCmdConfig;
+    use crate::config::{ConfigBuilder, SubCommandConfig};
+    use crate::reporter::FakeTestReporter;
+    use crate::Action;
+    use std::iter::FromIterator;
+    use test_dir::{DirBuilder, FileType, TestDir};
+
+    fn verify_with_write_on_success(manifest: &str, rust_version: BareVersion) -> String {
+        let tmp = TestDir::temp().create("Cargo.toml", FileType::EmptyFile);
+        let manifest_path = tmp.path("Cargo.toml");
+        std::fs::write(&manifest_path, manifest).unwrap();
+
+        let config = ConfigBuilder::new(Action::Verify, "x86_64-unknown-linux-gnu")
+            .crate_path(Some(tmp.root()))
+            .sub_command_config(SubCommandConfig::VerifyConfig(VerifyCmdConfig {
+                rust_version: Some(rust_version),
+                against_result: None,
+                write_on_success: true,
+                pin_patch: false,
+                changed_since: None,
+                check_published_metadata: false,
+            }))
+            .build();
+
+        let index = ReleaseIndex::from_iter(vec![
+            Release::new_stable(semver::Version::new(1, 60, 0)),
+            Release::new_stable(semver::Version::new(1, 56, 0)),
+            Release::new_stable(semver::Version::new(1, 55, 0)),
+        ]);
+        let runner = TestRunner::with_ok(&[
+            semver::Version::new(1, 60, 0),
+            semver::Version::new(1, 55, 0),
+        ]);
+
+        Verify::new(&index, runner)
+            .run(&config, &FakeTestReporter::default())
+            .unwrap();
+
+        std::fs::read_to_string(&manifest_path).unwrap()
+    }
+
+    #[test]
+    fn writes_newer_version() {
+        let manifest = verify_with_write_on_success(
+            "[package]\nrust-version = \"1.56\"\n",
+            BareVersion::TwoComponents(1, 60),
+        );
+
+        assert!(manifest.contains("rust-version = \"1.60.0\""));
+    }
+
+    #[test]
+    fn keeps_more_recent_declared_version() {
+        let manifest = verify_with_write_on_success(
+            "[package]\nrust-version = \"1.56\"\n",
+            BareVersion::TwoComponents(1, 55),
+        );
+
+        assert_eq!(manifest, "[package]\nrust-version = \"1.56\"\n");
+    }
+
+    #[test]
+    fn writes_when_no_version_is_declared() {
+        let manifest =
+            verify_with_write_on_success("[package]\n", BareVersion::TwoComponents(1, 60));
+
+        assert!(manifest.contains("rust-version = \"1.60.0\""));
+    }
+
+    fn verify_against_patch_releases(pin_patch: bool) -> TResult<()> {
+        let config = ConfigBuilder::new(Action::Verify, "x86_64-unknown-linux-gnu")
+            .sub_command_config(SubCommandConfig::VerifyConfig(VerifyCmdConfig {
+                rust_version: Some(BareVersion::TwoComponents(1, 60)),
+                against_result: None,
+                write_on_success: false,
+                pin_patch,
+                changed_since: None,
+                check_published_metadata: false,
+            }))
+            .build();
+
+        let index = ReleaseIndex::from_iter(vec![
+            Release::new_stable(semver::Version::new(1, 60, 2)),
+            Release::new_stable(semver::Version::new(1, 60, 0)),
+        ]);
+        // Only the most recent patch release is compatible
+        let runner = TestRunner::with_ok(&[semver::Version::new(1, 60, 2)]);
+
+        Verify::new(&index, runner).run(&config, &FakeTestReporter::default())
+    }
+
+    fn verify_against_result(recorded_command: &str) -> TResult<()> {
+        let tmp = TestDir::temp();
+        let path = tmp.path("msrv.toml");
+        std::fs::write(
+            &path,
+            format!(
+                "[msrv]\nversion = \"1.56.0\"\n\n[check]\ncommand = \"{}\"\ntarget = \"x86_64-unknown-linux-gnu\"\n",
+                recorded_command
+            ),
+        )
+        .unwrap();
+
+        let config = ConfigBuilder::new(Action::Verify, "x86_64-unknown-linux-gnu")
+            .sub_command_config(SubCommandConfig::VerifyConfig(VerifyCmdConfig {
+                rust_version: None,
+                against_result: Some(path),
+                write_on_success: false,
+                pin_patch: false,
+                changed_since: None,
+                check_published_metadata: false,
+            }))
+            .build();
+
+        let index = ReleaseIndex::from_iter(vec![
+            Release::new_stable(semver::Version::new(1, 56, 1)),
+            Release::new_stable(semver::Version::new(1, 56, 0)),
+        ]);
+        // The recorded version is verified exactly, not against its most recent patch release
+        let runner = TestRunner::with_ok(&[semver::Version::new(1, 56, 0)]);
+
+        Verify::new(&index, runner).run(&config, &FakeTestReporter::default())
+    }
+
+    #[test]
+    fn verifies_recorded_msrv() {
+        assert!(verify_against_result("cargo check").is_ok());
+    }
+
+    #[test]
+    fn rejects_result_of_other_check_command() {
+        assert!(matches!(
+            verify_against_result("cargo check --all-features"),
+            Err(CargoMSRVError::SubCommandVerify(
+                Error::CheckCommandMismatch { .. }
+            ))
+        ));
+    }
+
+    #[test]
+    fn two_components_verifies_latest_patch_release() {
+        assert!(verify_against_patch_releases(false).is_ok());
+    }
+
+    #[test]
+    fn two_components_with_pinned_patch_verifies_first_patch_release() {
+        assert!(matches!(
+            verify_against_patch_releases(true),
+            Err(CargoMSRVError::SubCommandVerify(Error::VerifyFailed(_)))
+        ));
+    }
 }
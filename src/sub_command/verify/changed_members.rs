@@ -0,0 +1,347 @@
+//! Verifies only the members of a workspace which are affected by the changes since a git
+//! revision, with `verify --changed-since <GIT_REF>`.
+//!
+//! A changed file affects the member which contains it. A member is also affected when one of the
+//! members it depends on by path is affected. Files which configure the build of the whole
+//! workspace, like the lockfile, affect every member.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use cargo_metadata::{Metadata, MetadataCommand};
+use rust_releases::ReleaseIndex;
+use toml_edit::Document;
+
+use crate::check::Check;
+use crate::config::discover::DiscoverRun;
+use crate::config::{Config, ConfigBuilder};
+use crate::error::{CargoMSRVError, IoErrorSource, TResult};
+use crate::manifest::{CargoManifestParser, TomlParser};
+use crate::reporter::event::{Progress, ProgressUnit, SkipReason, SkippedMember};
+use crate::reporter::Reporter;
+use crate::sub_command::discover::{declared_msrv, inherits_msrv, project_config, workspace_msrv};
+use crate::sub_command::verify::Error;
+use crate::sub_command::{SubCommand, Verify};
+
+/// The files, relative to the workspace root, which affect every member when they change.
+const WORKSPACE_FILES: &[&str] = &[
+    "Cargo.toml",
+    "Cargo.lock",
+    "rust-toolchain",
+    "rust-toolchain.toml",
+    ".cargo/config",
+    ".cargo/config.toml",
+];
+
+/// Verify the MSRV of each workspace member which is affected by the changes since the revision
+/// given with `--changed-since`, with a runner made by `runner`.
+///
+/// A member which fails does not stop the others; a cancelled run does stop.
+pub(crate) fn verify_changed_members<C: Check>(
+    config: &Config,
+    reporter: &impl Reporter,
+    index: &ReleaseIndex,
+    runner: impl Fn() -> C,
+) -> TResult<()> {
+    let since = config
+        .sub_command_config()
+        .verify()
+        .changed_since
+        .as_deref()
+        .unwrap_or("HEAD");
+
+    let metadata = MetadataCommand::new()
+        .manifest_path(config.context().manifest_path()?)
+        .no_deps()
+        .exec()?;
+
+    let root = metadata.workspace_root.clone().into_std_path_buf();
+    let members = workspace_members(&metadata, &root);
+    let changed = changed_files(&root, since)?;
+    let affected = affected_members(&members, &changed);
+
+    info!(
+        since,
+        changed = changed.len(),
+        affected = affected.len(),
+        "changed workspace members"
+    );
+
+    let workspace = read_manifest(&root.join("Cargo.toml"))?;
+    let mut total = 0;
+    let mut failed = 0;
+    // The affected members are the units of the progress, including those without an MSRV
+    let mut unit = 0;
+
+    for member in &members {
+        let path = if member.dir.as_os_str().is_empty() {
+            PathBuf::from(".")
+        } else {
+            member.dir.clone()
+        };
+
+        if !affected.contains(member.dir.as_path()) {
+            let reason = SkipReason::Unchanged {
+                since: since.to_string(),
+            };
+            reporter.report_event(SkippedMember::new(&member.name, path, reason))?;
+            continue;
+        }
+
+        unit += 1;
+        let progress_unit = ProgressUnit::new(&member.name, unit, affected.len() as u64);
+
+        let crate_path = root.join(&member.dir);
+        let document = read_manifest(&crate_path.join("Cargo.toml"))?;
+
+        let msrv = declared_msrv(&document).or_else(|| {
+            Some(&workspace)
+                .filter(|_| inherits_msrv(&document))
+                .and_then(workspace_msrv)
+        });
+
+        let msrv = match msrv {
+            Some(msrv) => msrv,
+            None => {
+                reporter.report_event(SkippedMember::new(
+                    &member.name,
+                    path,
+                    SkipReason::NoMsrv,
+                ))?;
+                continue;
+            }
+        };
+
+        info!(crate_path = %crate_path.display(), %msrv, "verifying changed workspace member");
+
+        reporter.report_event(Progress::start_of_unit(progress_unit.clone()))?;
+
+        let result = project_config(config, DiscoverRun::Verify, &crate_path, Some(&msrv))
+            .and_then(|member_config| {
+                let member_config = ConfigBuilder::from_config(&member_config)
+                    .progress_unit(Some(progress_unit))
+                    .build();
+
+                Verify::new(index, runner()).run(&member_config, reporter)
+            });
+
+        total += 1;
+
+        match result {
+            Err(CargoMSRVError::Cancelled) => return Err(CargoMSRVError::Cancelled),
+            Err(error) => {
+                warn!(crate_path = %crate_path.display(), %error, "workspace member failed");
+                failed += 1;
+            }
+            Ok(()) => {}
+        }
+    }
+
+    if failed > 0 {
+        Err(CargoMSRVError::SubCommandVerify(Error::MembersFailed {
+            failed,
+            total,
+        }))
+    } else {
+        Ok(())
+    }
+}
+
+/// A member of the workspace, with its directory and the directories of the members it depends on
+/// by path, relative to the workspace root. The directory of a root package is empty.
+#[derive(Debug)]
+struct Member {
+    name: String,
+    dir: PathBuf,
+    dependencies: Vec<PathBuf>,
+}
+
+fn workspace_members(metadata: &Metadata, root: &Path) -> Vec<Member> {
+    let relative = |path: &Path| path.strip_prefix(root).unwrap_or(path).to_path_buf();
+
+    metadata
+        .workspace_packages()
+        .into_iter()
+        .filter_map(|package| {
+            let dir = package.manifest_path.parent()?.as_std_path();
+
+            let dependencies = package
+                .dependencies
+                .iter()
+                .filter_map(|dependency| dependency.path.as_ref())
+                .map(|path| relative(path.as_std_path()))
+                .collect();
+
+            Some(Member {
+                name: package.name.clone(),
+                dir: relative(dir),
+                dependencies,
+            })
+        })
+        .collect()
+}
+
+/// The members which are affected by the changed files, by their directory.
+fn affected_members<'m>(members: &'m [Member], changed: &[PathBuf]) -> HashSet<&'m Path> {
+    let all = || members.iter().map(|member| member.dir.as_path()).collect();
+
+    if changed.iter().any(|file| {
+        WORKSPACE_FILES
+            .iter()
+            .any(|workspace_file| file == Path::new(workspace_file))
+    }) {
+        return all();
+    }
+
+    // A file belongs to the innermost member which contains it, since members may be nested
+    let mut affected = changed
+        .iter()
+        .filter_map(|file| {
+            members
+                .iter()
+                .filter(|member| file.starts_with(&member.dir))
+                .max_by_key(|member| member.dir.components().count())
+        })
+        .map(|member| member.dir.as_path())
+        .collect::<HashSet<_>>();
+
+    loop {
+        let dependents = members
+            .iter()
+            .filter(|member| !affected.contains(member.dir.as_path()))
+            .filter(|member| {
+                member
+                    .dependencies
+                    .iter()
+                    .any(|dependency| affected.contains(dependency.as_path()))
+            })
+            .map(|member| member.dir.as_path())
+            .collect::<Vec<_>>();
+
+        if dependents.is_empty() {
+            return affected;
+        }
+
+        affected.extend(dependents);
+    }
+}
+
+/// The files which changed since the given revision, relative to the workspace root, including
+/// uncommitted and untracked files.
+fn changed_files(root: &Path, since: &str) -> TResult<Vec<PathBuf>> {
+    let mut files = git(
+        root,
+        &["diff", "--name-only", "--relative", "-z", since, "--"],
+        since,
+    )?;
+    files.extend(git(
+        root,
+        &["ls-files", "--others", "--exclude-standard", "-z"],
+        since,
+    )?);
+
+    Ok(files)
+}
+
+/// Run git in the given directory, and return the NUL separated paths it outputs.
+fn git(dir: &Path, args: &[&str], since: &str) -> TResult<Vec<PathBuf>> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .map_err(|error| CargoMSRVError::Io {
+            error,
+            source: IoErrorSource::SpawnProcess("git".into()),
+        })?;
+
+    if !output.status.success() {
+        return Err(CargoMSRVError::SubCommandVerify(Error::ChangedFiles {
+            revision: since.to_string(),
+            message: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        }));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .split('\0')
+        .filter(|path| !path.is_empty())
+        .map(PathBuf::from)
+        .collect())
+}
+
+fn read_manifest(path: &Path) -> TResult<Document> {
+    let contents = std::fs::read_to_string(path).map_err(|error| CargoMSRVError::Io {
+        error,
+        source: IoErrorSource::ReadFile(path.to_path_buf()),
+    })?;
+
+    let document = CargoManifestParser::default().parse::<Document>(&contents)?;
+
+    Ok(document)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn member(dir: &str, dependencies: &[&str]) -> Member {
+        Member {
+            name: dir.rsplit('/').next().unwrap_or(dir).to_string(),
+            dir: PathBuf::from(dir),
+            dependencies: dependencies.iter().map(PathBuf::from).collect(),
+        }
+    }
+
+    fn members() -> Vec<Member> {
+        vec![
+            member("crates/core", &[]),
+            member("crates/core-macros", &[]),
+            member("crates/cli", &["crates/core"]),
+            member("crates/app", &["crates/cli"]),
+            member("crates/extra", &["crates/core-macros"]),
+        ]
+    }
+
+    #[yare::parameterized(
+        member_file = { &["crates/extra/src/lib.rs"], &["crates/extra"] },
+        dependents = { &["crates/core/src/lib.rs"], &["crates/app", "crates/cli", "crates/core"] },
+        sibling_prefix = { &["crates/core-macros/Cargo.toml"], &["crates/core-macros", "crates/extra"] },
+        lockfile = { &["Cargo.lock"], &["crates/app", "crates/cli", "crates/core", "crates/core-macros", "crates/extra"] },
+        toolchain_file = { &["rust-toolchain.toml"], &["crates/app", "crates/cli", "crates/core", "crates/core-macros", "crates/extra"] },
+        outside_members = { &["README.md", "docs/index.md"], &[] },
+        nothing_changed = { &[], &[] },
+    )]
+    fn affected(changed: &[&str], expected: &[&str]) {
+        let members = members();
+        let changed = changed.iter().map(PathBuf::from).collect::<Vec<_>>();
+
+        let mut affected = affected_members(&members, &changed)
+            .into_iter()
+            .map(|dir| dir.to_str().unwrap())
+            .collect::<Vec<_>>();
+        affected.sort_unstable();
+
+        assert_eq!(affected, expected);
+    }
+
+    #[test]
+    fn root_package_contains_other_files() {
+        let members = vec![member("", &[]), member("crates/core", &[])];
+        let changed = vec![PathBuf::from("src/main.rs")];
+
+        let affected = affected_members(&members, &changed);
+
+        assert_eq!(affected, HashSet::from([Path::new("")]));
+    }
+
+    #[test]
+    fn innermost_member_owns_a_file() {
+        let members = vec![member("", &[]), member("crates/core", &[])];
+        let changed = vec![PathBuf::from("crates/core/src/lib.rs")];
+
+        let affected = affected_members(&members, &changed);
+
+        assert_eq!(affected, HashSet::from([Path::new("crates/core")]));
+    }
+}
@@ -0,0 +1,209 @@
+//! Cross-checks the MSRV in the Cargo manifest against the `rust_version` of the latest version
+//! of the crate which is published to crates.io, with `verify --check-published-metadata`, so a
+//! release which went out with a stale, or missing, MSRV doesn't go unnoticed.
+
+use std::convert::TryFrom;
+
+use toml_edit::Document;
+
+use crate::config::Config;
+use crate::error::{CargoMSRVError, IoErrorSource, TResult};
+use crate::manifest::bare_version::BareVersion;
+use crate::manifest::{CargoManifest, CargoManifestParser, TomlParser};
+use crate::sub_command::verify::Error;
+
+const CRATES_IO_API: &str = "https://crates.io/api/v1/crates";
+
+/// crates.io requires a user agent which identifies the client.
+const USER_AGENT: &str = "cargo-msrv (https://github.com/foresterre/cargo-msrv)";
+
+/// Fail when the MSRV in the Cargo manifest differs from the `rust_version` of the latest
+/// published version of the crate.
+pub(crate) fn check_published_metadata(config: &Config) -> TResult<()> {
+    let path = config.context().manifest_path()?;
+    let contents = std::fs::read_to_string(path).map_err(|error| CargoMSRVError::Io {
+        error,
+        source: IoErrorSource::ReadFile(path.to_path_buf()),
+    })?;
+
+    let document = CargoManifestParser::default().parse::<Document>(&contents)?;
+    let name = document
+        .get("package")
+        .and_then(|package| package.get("name"))
+        .and_then(|name| name.as_str())
+        .map(String::from)
+        .ok_or_else(|| {
+            CargoMSRVError::InvalidConfig(format!(
+                "Unable to check the published metadata, since '{}' has no package name",
+                path.display()
+            ))
+        })?;
+    let manifest = CargoManifest::try_from(document)?;
+
+    let url = format!("{}/{}", CRATES_IO_API, name);
+    let published = fetch_published(&url)?;
+
+    let local = manifest.minimum_rust_version();
+
+    if !agrees(local, published.rust_version.as_ref()) {
+        return Err(CargoMSRVError::SubCommandVerify(
+            Error::PublishedMetadataMismatch {
+                name,
+                version: published.version,
+                published: published.rust_version,
+                local: local.cloned(),
+            },
+        ));
+    }
+
+    info!(
+        %name,
+        version = %published.version,
+        "the MSRV in the Cargo manifest agrees with the published metadata"
+    );
+
+    Ok(())
+}
+
+/// The latest version of a crate, as published to crates.io.
+#[derive(Debug, PartialEq)]
+struct Published {
+    version: String,
+    rust_version: Option<BareVersion>,
+}
+
+fn fetch_published(url: &str) -> TResult<Published> {
+    let fetch_error = |message: String| {
+        CargoMSRVError::SubCommandVerify(Error::FetchPublishedMetadata {
+            url: url.to_string(),
+            message,
+        })
+    };
+
+    let response = attohttpc::get(url)
+        .header("User-Agent", USER_AGENT)
+        .send()
+        .map_err(|err| fetch_error(err.to_string()))?;
+
+    match response.status().as_u16() {
+        404 => return Err(fetch_error("the crate is not published".to_string())),
+        _ if !response.is_success() => {
+            return Err(fetch_error(format!(
+                "unexpected status code {}",
+                response.status().as_u16()
+            )))
+        }
+        _ => {}
+    }
+
+    let body = response
+        .text()
+        .map_err(|err| fetch_error(err.to_string()))?;
+
+    parse_published(&body).map_err(fetch_error)
+}
+
+/// The subset of the response of the crates.io API for a crate, which is used here.
+#[derive(serde::Deserialize)]
+struct CrateResponse {
+    #[serde(rename = "crate")]
+    krate: CrateInfo,
+    versions: Vec<VersionInfo>,
+}
+
+#[derive(serde::Deserialize)]
+struct CrateInfo {
+    /// The most recent version, which is not yanked
+    max_version: String,
+}
+
+#[derive(serde::Deserialize)]
+struct VersionInfo {
+    num: String,
+    rust_version: Option<String>,
+}
+
+fn parse_published(body: &str) -> Result<Published, String> {
+    let response = serde_json::from_str::<CrateResponse>(body).map_err(|err| err.to_string())?;
+    let version = response.krate.max_version;
+
+    let info = response
+        .versions
+        .into_iter()
+        .find(|info| info.num == version)
+        .ok_or_else(|| format!("version '{}' is missing from the response", version))?;
+
+    let rust_version = info
+        .rust_version
+        .as_deref()
+        .map(BareVersion::try_from)
+        .transpose()
+        .map_err(|err| err.to_string())?;
+
+    Ok(Published {
+        version,
+        rust_version,
+    })
+}
+
+/// Whether the MSRV of the manifest and the published MSRV are the same, where a two component
+/// version like `1.60` is the same as `1.60.0`, as it is for cargo.
+fn agrees(local: Option<&BareVersion>, published: Option<&BareVersion>) -> bool {
+    local.map(BareVersion::to_semver_version) == published.map(BareVersion::to_semver_version)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RESPONSE: &str = r#"{
+  "crate": { "name": "example", "max_version": "0.2.0", "newest_version": "0.3.0-rc.1" },
+  "versions": [
+    { "num": "0.3.0-rc.1", "rust_version": "1.64", "yanked": false },
+    { "num": "0.2.0", "rust_version": "1.60", "yanked": false },
+    { "num": "0.1.0", "rust_version": null, "yanked": false }
+  ]
+}"#;
+
+    #[test]
+    fn parses_latest_version() {
+        assert_eq!(
+            parse_published(RESPONSE).unwrap(),
+            Published {
+                version: "0.2.0".to_string(),
+                rust_version: Some(BareVersion::TwoComponents(1, 60)),
+            }
+        );
+    }
+
+    #[test]
+    fn missing_rust_version() {
+        let response = RESPONSE.replace("\"0.2.0\", \"rust_version\": \"1.60\"", "\"0.2.0\"");
+
+        assert_eq!(parse_published(&response).unwrap().rust_version, None);
+    }
+
+    #[test]
+    fn latest_version_not_listed() {
+        let response = RESPONSE.replace("\"max_version\": \"0.2.0\"", "\"max_version\": \"0.4.0\"");
+
+        assert!(parse_published(&response).is_err());
+    }
+
+    #[yare::parameterized(
+        same = { Some(BareVersion::TwoComponents(1, 60)), Some(BareVersion::TwoComponents(1, 60)), true },
+        two_and_three_components = { Some(BareVersion::TwoComponents(1, 60)), Some(BareVersion::ThreeComponents(1, 60, 0)), true },
+        other_patch = { Some(BareVersion::TwoComponents(1, 60)), Some(BareVersion::ThreeComponents(1, 60, 1)), false },
+        stale = { Some(BareVersion::TwoComponents(1, 64)), Some(BareVersion::TwoComponents(1, 60)), false },
+        missing_published = { Some(BareVersion::TwoComponents(1, 60)), None, false },
+        missing_local = { None, Some(BareVersion::TwoComponents(1, 60)), false },
+        missing_both = { None, None, true },
+    )]
+    fn published_agrees(
+        local: Option<BareVersion>,
+        published: Option<BareVersion>,
+        expected: bool,
+    ) {
+        assert_eq!(agrees(local.as_ref(), published.as_ref()), expected);
+    }
+}
@@ -0,0 +1,311 @@
+//! Scripted fake checks and release indexes, to simulate a search deterministically, without
+//! rustup or network access.
+//!
+//! Available with the `testing` feature.
+//!
+//! ```
+//! use cargo_msrv::config::{Action, ConfigBuilder, SearchMethod};
+//! use cargo_msrv::testing::{stable_releases, ScriptedCheck};
+//! # use cargo_msrv::{Find, SubCommand};
+//! # use rust_releases::semver;
+//! # use cargo_msrv::reporter::Event;
+//! # use storyteller::{EventSender, Reporter, ReporterError};
+//! # struct Discard;
+//! # impl Reporter for Discard {
+//! #     type Event = Event;
+//! #     type Err = ReporterError<Event>;
+//! #     fn report_event(&self, _: impl Into<Event>) -> Result<(), Self::Err> { Ok(()) }
+//! #     fn disconnect(self) -> Result<(), Self::Err> { Ok(()) }
+//! # }
+//!
+//! // Rust 1.50.0 up to and including Rust 1.56.0
+//! let index = stable_releases(50..=56);
+//! // The check passes from Rust 1.53.0 onwards
+//! let check = ScriptedCheck::from_pattern(&index, "++++---");
+//!
+//! let config = ConfigBuilder::new(Action::Find, "x86_64-unknown-linux-gnu")
+//!     .search_method(SearchMethod::Bisect)
+//!     .build();
+//!
+//! let msrv = Find::new(&index, &check).run(&config, &Discard).unwrap();
+//!
+//! assert_eq!(msrv, semver::Version::new(1, 53, 0));
+//! assert!(check.checked().len() < 7);
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::iter::FromIterator;
+use std::ops::RangeInclusive;
+use std::sync::Mutex;
+
+use rust_releases::{semver, Release, ReleaseIndex};
+
+use crate::check::Check;
+use crate::config::Config;
+use crate::error::{CargoMSRVError, TResult};
+use crate::outcome::Outcome;
+use crate::toolchain::ToolchainSpec;
+
+/// The scripted outcome of a check.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Scripted {
+    /// The check passes
+    Pass,
+    /// The check fails, with the given output
+    Fail(String),
+    /// The toolchain can't be installed, e.g. because it's unavailable for the target
+    Unavailable,
+}
+
+impl Scripted {
+    /// The output of a check which fails, when no output is scripted.
+    const FAILURE_OUTPUT: &'static str = "scripted failure";
+
+    fn fail() -> Self {
+        Self::Fail(Self::FAILURE_OUTPUT.to_string())
+    }
+}
+
+/// A [`Check`] whose outcome for each Rust version is scripted beforehand. The Rust versions
+/// which are checked are recorded, so a test can assert which versions a search method checked.
+pub struct ScriptedCheck {
+    scripted: HashMap<semver::Version, Scripted>,
+    /// The outcome of the Rust versions which are not scripted
+    otherwise: Box<dyn Fn(&semver::Version) -> Scripted + Send + Sync>,
+    checked: Mutex<Vec<semver::Version>>,
+}
+
+impl ScriptedCheck {
+    fn with_otherwise(
+        otherwise: impl Fn(&semver::Version) -> Scripted + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            scripted: HashMap::new(),
+            otherwise: Box::new(otherwise),
+            checked: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// The check passes for each Rust version, unless scripted otherwise.
+    pub fn passing() -> Self {
+        Self::with_otherwise(|_| Scripted::Pass)
+    }
+
+    /// The check fails for each Rust version, unless scripted otherwise.
+    pub fn failing() -> Self {
+        Self::with_otherwise(|_| Scripted::fail())
+    }
+
+    /// The check passes for the given MSRV and more recent Rust versions, and fails for less recent
+    /// Rust versions, unless scripted otherwise.
+    pub fn with_msrv(msrv: semver::Version) -> Self {
+        Self::with_otherwise(move |version| {
+            if version >= &msrv {
+                Scripted::Pass
+            } else {
+                Scripted::fail()
+            }
+        })
+    }
+
+    /// Script the outcome of the releases of the index, from most to least recent, with one
+    /// character per release: `+` passes, `-` fails and `x` is unavailable. The Rust versions
+    /// which are not part of the index fail.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pattern has another length than the index, or holds another character.
+    pub fn from_pattern(index: &ReleaseIndex, pattern: &str) -> Self {
+        let releases = index.releases();
+
+        assert_eq!(
+            releases.len(),
+            pattern.chars().count(),
+            "the pattern '{}' must hold one character per release",
+            pattern
+        );
+
+        releases
+            .iter()
+            .zip(pattern.chars())
+            .fold(Self::failing(), |check, (release, outcome)| {
+                let version = release.version().clone();
+
+                match outcome {
+                    '+' => check.pass(version),
+                    '-' => check.fail(version),
+                    'x' => check.unavailable(version),
+                    other => panic!("unexpected '{}' in the pattern '{}'", other, pattern),
+                }
+            })
+    }
+
+    /// Script the check of the given Rust version to pass.
+    pub fn pass(self, version: semver::Version) -> Self {
+        self.script(version, Scripted::Pass)
+    }
+
+    /// Script the check of the given Rust version to fail.
+    pub fn fail(self, version: semver::Version) -> Self {
+        self.script(version, Scripted::fail())
+    }
+
+    /// Script the check of the given Rust version to fail, with the given output.
+    pub fn fail_with(self, version: semver::Version, output: impl Into<String>) -> Self {
+        self.script(version, Scripted::Fail(output.into()))
+    }
+
+    /// Script the toolchain of the given Rust version to be unavailable.
+    pub fn unavailable(self, version: semver::Version) -> Self {
+        self.script(version, Scripted::Unavailable)
+    }
+
+    pub fn script(mut self, version: semver::Version, outcome: Scripted) -> Self {
+        self.scripted.insert(version, outcome);
+        self
+    }
+
+    /// The Rust versions which were checked so far, in the order in which they were checked.
+    pub fn checked(&self) -> Vec<semver::Version> {
+        self.checked.lock().unwrap().clone()
+    }
+
+    fn outcome(&self, version: &semver::Version) -> Scripted {
+        self.scripted
+            .get(version)
+            .cloned()
+            .unwrap_or_else(|| (self.otherwise)(version))
+    }
+}
+
+impl fmt::Debug for ScriptedCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScriptedCheck")
+            .field("scripted", &self.scripted)
+            .field("checked", &self.checked)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Check for ScriptedCheck {
+    fn check(&self, config: &Config, toolchain: &ToolchainSpec) -> TResult<Outcome> {
+        if let Some(token) = config.cancellation_token() {
+            token.check()?;
+        }
+
+        let version = toolchain.version();
+        self.checked.lock().unwrap().push(version.clone());
+
+        let toolchain = toolchain.to_owned();
+
+        match self.outcome(version) {
+            Scripted::Pass => Ok(Outcome::new_success(toolchain)),
+            Scripted::Fail(output) => Ok(Outcome::new_failure(toolchain, output)),
+            Scripted::Unavailable => Err(CargoMSRVError::ToolchainUnavailable(toolchain)),
+        }
+    }
+}
+
+/// A release index of the given stable Rust versions, ordered from most to least recent.
+pub fn release_index<I: IntoIterator<Item = semver::Version>>(versions: I) -> ReleaseIndex {
+    let mut versions = versions.into_iter().collect::<Vec<_>>();
+    versions.sort_unstable_by(|lhs, rhs| rhs.cmp(lhs));
+    versions.dedup();
+
+    ReleaseIndex::from_iter(versions.into_iter().map(Release::new_stable))
+}
+
+/// A release index of the first patch release of each of the given minor versions of Rust 1,
+/// e.g. `stable_releases(50..=56)` for Rust 1.50.0 up to and including Rust 1.56.0.
+pub fn stable_releases(minors: RangeInclusive<u64>) -> ReleaseIndex {
+    release_index(minors.map(|minor| semver::Version::new(1, minor, 0)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{Action, ConfigBuilder, SearchMethod};
+    use crate::reporter::TestReporter;
+    use crate::{Find, SubCommand};
+
+    fn config(method: SearchMethod) -> Config<'static> {
+        ConfigBuilder::new(Action::Find, "x86_64-unknown-linux-gnu")
+            .search_method(method)
+            .build()
+    }
+
+    fn check(check: &ScriptedCheck, minor: u64) -> TResult<Outcome> {
+        let version = semver::Version::new(1, minor, 0);
+        let toolchain = ToolchainSpec::new(&version, "x86_64-unknown-linux-gnu");
+
+        check.check(&config(SearchMethod::Linear), &toolchain)
+    }
+
+    #[test]
+    fn index_is_ordered_most_recent_first() {
+        let index = release_index(vec![
+            semver::Version::new(1, 54, 0),
+            semver::Version::new(1, 56, 0),
+            semver::Version::new(1, 55, 0),
+        ]);
+
+        let versions = index
+            .releases()
+            .iter()
+            .map(|release| release.version().minor)
+            .collect::<Vec<_>>();
+
+        assert_eq!(versions, vec![56, 55, 54]);
+    }
+
+    #[test]
+    fn scripted_outcomes() {
+        let check = ScriptedCheck::with_msrv(semver::Version::new(1, 55, 0))
+            .fail_with(semver::Version::new(1, 57, 0), "error[E0658]")
+            .unavailable(semver::Version::new(1, 50, 0));
+
+        assert!(check(&check, 56).unwrap().is_success());
+        assert!(check(&check, 55).unwrap().is_success());
+        assert!(!check(&check, 54).unwrap().is_success());
+        assert!(!check(&check, 57).unwrap().is_success());
+        assert!(matches!(
+            check(&check, 50),
+            Err(CargoMSRVError::ToolchainUnavailable(_))
+        ));
+
+        let checked = check
+            .checked()
+            .iter()
+            .map(|version| version.minor)
+            .collect::<Vec<_>>();
+        assert_eq!(checked, vec![56, 55, 54, 57, 50]);
+    }
+
+    #[yare::parameterized(
+        bisect_msrv_in_the_middle = { SearchMethod::Bisect, "+++----", Some(54) },
+        bisect_all_pass = { SearchMethod::Bisect, "+++++++", Some(50) },
+        bisect_none_pass = { SearchMethod::Bisect, "-------", None },
+        linear_msrv_in_the_middle = { SearchMethod::Linear, "+++----", Some(54) },
+        linear_stops_at_first_failure = { SearchMethod::Linear, "++-++++", Some(55) },
+        unavailable_are_skipped = { SearchMethod::Linear, "++++xxx", Some(53) },
+    )]
+    fn search(method: SearchMethod, pattern: &str, expected: Option<u64>) {
+        let index = stable_releases(50..=56);
+        let check = ScriptedCheck::from_pattern(&index, pattern);
+        let reporter = TestReporter::default();
+
+        let msrv = Find::new(&index, &check)
+            .run(&config(method), reporter.reporter())
+            .ok()
+            .map(|msrv| msrv.minor);
+
+        assert_eq!(msrv, expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "one character per release")]
+    fn pattern_of_another_length() {
+        ScriptedCheck::from_pattern(&stable_releases(50..=56), "++");
+    }
+}
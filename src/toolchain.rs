@@ -1,10 +1,14 @@
 use once_cell::sync::OnceCell;
 use rust_releases::semver;
 
+/// A Rust toolchain of a given version, which builds for a given target. The toolchain itself runs
+/// on the host, which is the target, unless another host is given, e.g. to check whether a crate
+/// builds for `wasm32-unknown-unknown` with the `x86_64-unknown-linux-gnu` toolchain.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct ToolchainSpec<'spec> {
     version: &'spec semver::Version,
     target: &'spec str,
+    host: Option<&'spec str>,
     spec: OnceCell<String>,
 }
 
@@ -13,33 +17,63 @@ impl<'spec> ToolchainSpec<'spec> {
         Self {
             version,
             target,
+            host: None,
             spec: OnceCell::new(),
         }
     }
 
+    /// Run the toolchain on the given host, instead of on the target.
+    pub fn with_host(mut self, host: &'spec str) -> Self {
+        self.host = Some(host).filter(|&host| host != self.target);
+        self.spec = OnceCell::new();
+        self
+    }
+
+    /// The name of the toolchain, as known by rustup, e.g. `1.56.0-x86_64-unknown-linux-gnu`.
     pub fn spec(&self) -> &str {
         self.spec
-            .get_or_init(|| make_toolchain_spec(self.version, self.target))
+            .get_or_init(|| make_toolchain_spec(self.version, self.host()))
     }
 
     pub fn version(&self) -> &semver::Version {
         self.version
     }
 
+    pub fn target(&self) -> &str {
+        self.target
+    }
+
+    /// The platform on which the toolchain runs.
+    pub fn host(&self) -> &str {
+        self.host.unwrap_or(self.target)
+    }
+
+    /// Whether the toolchain builds for another target than the one it runs on, in which case the
+    /// standard library of the target must be installed with it.
+    pub fn is_cross_compiling(&self) -> bool {
+        self.host.is_some()
+    }
+
     pub fn to_owned(&self) -> OwnedToolchainSpec {
         OwnedToolchainSpec {
             version: self.version.clone(),
             target: self.target.to_string(),
+            host: self.host.map(String::from),
             spec: self.spec.clone(),
         }
     }
 }
 
-#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[derive(
+    Clone, Debug, Eq, PartialEq, serde::Serialize, serde::Deserialize, schemars::JsonSchema,
+)]
 #[serde(rename_all = "snake_case")]
 pub struct OwnedToolchainSpec {
     version: semver::Version,
     target: String,
+    /// Only present when the toolchain runs on another platform than the target
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    host: Option<String>,
     #[serde(skip)]
     spec: OnceCell<String>,
 }
@@ -49,13 +83,14 @@ impl OwnedToolchainSpec {
         Self {
             version: version.clone(),
             target: target.to_string(),
+            host: None,
             spec: OnceCell::new(),
         }
     }
 
     pub fn spec(&self) -> &str {
         self.spec
-            .get_or_init(|| make_toolchain_spec(&self.version, &self.target))
+            .get_or_init(|| make_toolchain_spec(&self.version, self.host()))
     }
 
     pub fn version(&self) -> &semver::Version {
@@ -65,6 +100,10 @@ impl OwnedToolchainSpec {
     pub fn target(&self) -> &str {
         &self.target
     }
+
+    pub fn host(&self) -> &str {
+        self.host.as_deref().unwrap_or(&self.target)
+    }
 }
 
 impl std::fmt::Display for OwnedToolchainSpec {
@@ -73,6 +112,47 @@ impl std::fmt::Display for OwnedToolchainSpec {
     }
 }
 
-fn make_toolchain_spec(version: &semver::Version, target: &str) -> String {
-    format!("{}-{}", version, target)
+fn make_toolchain_spec(version: &semver::Version, host: &str) -> String {
+    format!("{}-{}", version, host)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[yare::parameterized(
+        native = { None, "1.56.0-x86_64-unknown-linux-gnu", false },
+        same_host = { Some("x86_64-unknown-linux-gnu"), "1.56.0-x86_64-unknown-linux-gnu", false },
+        other_host = { Some("aarch64-apple-darwin"), "1.56.0-aarch64-apple-darwin", true },
+    )]
+    fn spec(host: Option<&str>, expected: &str, cross_compiling: bool) {
+        let version = semver::Version::new(1, 56, 0);
+        let toolchain = ToolchainSpec::new(&version, "x86_64-unknown-linux-gnu");
+        let toolchain = match host {
+            Some(host) => toolchain.with_host(host),
+            None => toolchain,
+        };
+
+        assert_eq!(toolchain.spec(), expected);
+        assert_eq!(toolchain.is_cross_compiling(), cross_compiling);
+        assert_eq!(toolchain.to_owned().spec(), expected);
+        assert_eq!(toolchain.target(), "x86_64-unknown-linux-gnu");
+    }
+
+    #[test]
+    fn host_is_serialized_when_cross_compiling() {
+        let version = semver::Version::new(1, 56, 0);
+        let toolchain = ToolchainSpec::new(&version, "wasm32-unknown-unknown")
+            .with_host("x86_64-unknown-linux-gnu")
+            .to_owned();
+
+        let json = serde_json::to_value(&toolchain).unwrap();
+
+        assert_eq!(json["target"], "wasm32-unknown-unknown");
+        assert_eq!(json["host"], "x86_64-unknown-linux-gnu");
+        assert_eq!(
+            serde_json::from_value::<OwnedToolchainSpec>(json).unwrap(),
+            toolchain
+        );
+    }
 }
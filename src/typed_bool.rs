@@ -13,6 +13,25 @@ impl serde::Serialize for True {
     }
 }
 
+impl schemars::JsonSchema for True {
+    fn is_referenceable() -> bool {
+        false
+    }
+
+    fn schema_name() -> String {
+        "True".to_string()
+    }
+
+    fn json_schema(_gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        schemars::schema::SchemaObject {
+            instance_type: Some(schemars::schema::InstanceType::Boolean.into()),
+            const_value: Some(true.into()),
+            ..Default::default()
+        }
+        .into()
+    }
+}
+
 impl<'de> serde::Deserialize<'de> for True {
     fn deserialize<D>(deserializer: D) -> Result<True, D::Error>
     where
@@ -55,6 +74,25 @@ impl serde::Serialize for False {
     }
 }
 
+impl schemars::JsonSchema for False {
+    fn is_referenceable() -> bool {
+        false
+    }
+
+    fn schema_name() -> String {
+        "False".to_string()
+    }
+
+    fn json_schema(_gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        schemars::schema::SchemaObject {
+            instance_type: Some(schemars::schema::InstanceType::Boolean.into()),
+            const_value: Some(false.into()),
+            ..Default::default()
+        }
+        .into()
+    }
+}
+
 impl<'de> serde::Deserialize<'de> for False {
     fn deserialize<D>(deserializer: D) -> Result<False, D::Error>
     where
@@ -0,0 +1,37 @@
+//! Typed representations of the messages which cargo-msrv prints with `--output-format json`.
+//!
+//! Each line of the json output is a single message, which has a `type` field describing the
+//! kind of message. Messages of type `compatibility` and `msrv_result` can be deserialized into
+//! a [`Compatibility`] and an [`MsrvResult`] respectively.
+//!
+//! ```
+//! use cargo_msrv::types::{Compatibility, MsrvResult};
+//!
+//! let output = r#"{"type":"compatibility","toolchain":{"version":"1.56.0","target":"x86_64-unknown-linux-gnu"},"decision":true,"compatibility_report":"compatible"}
+//! {"type":"msrv_result","determined":{"version":"1.56.0","success":true}}"#;
+//!
+//! for line in output.lines() {
+//!     let message = serde_json::from_str::<serde_json::Value>(line).unwrap();
+//!
+//!     match message["type"].as_str() {
+//!         Some("compatibility") => {
+//!             let compatibility: Compatibility = serde_json::from_value(message).unwrap();
+//!             assert!(compatibility.is_compatible());
+//!         }
+//!         Some("msrv_result") => {
+//!             let result: MsrvResult = serde_json::from_value(message).unwrap();
+//!             assert_eq!(result.msrv().unwrap().to_string(), "1.56.0");
+//!         }
+//!         _ => {}
+//!     }
+//! }
+//! ```
+
+pub use crate::config::SearchMethod;
+pub use crate::fingerprint::Fingerprint;
+pub use crate::manifest::bare_version::BareVersion;
+pub use crate::reporter::event::{
+    CheckTiming, Compatibility, CompatibilityReport, EncodingNote, MsrvResult,
+};
+pub use crate::search_method::{Polarity, SearchSeeds};
+pub use crate::toolchain::OwnedToolchainSpec;
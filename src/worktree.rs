@@ -0,0 +1,317 @@
+//! Temporary git worktrees, in which a crate is checked at a given revision of its repository,
+//! e.g. with `--at-rev v1.2.0`, without touching the working tree of the user.
+//!
+//! The revisions whose worktrees are visited by `cargo msrv history` are listed here as well.
+
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::error::{CargoMSRVError, IoErrorSource, TResult};
+use crate::release_schedule::Date;
+
+/// A worktree of the repository of a crate, with a given revision checked out as detached `HEAD`.
+/// The worktree is removed when it is dropped.
+#[derive(Debug)]
+pub(crate) struct Worktree {
+    revision: String,
+    /// The commit to which the revision resolved
+    commit: String,
+    /// The root of the repository to which the worktree belongs
+    repository: PathBuf,
+    path: PathBuf,
+}
+
+impl Worktree {
+    /// Check out the given revision of the repository which contains `dir`, in a new worktree at
+    /// `path`. A worktree which was left behind at `path` by an earlier, interrupted, run is
+    /// replaced.
+    pub(crate) fn add(dir: &Path, revision: &str, path: PathBuf) -> TResult<Self> {
+        let repository = git(dir, ["rev-parse", "--show-toplevel"], revision)?;
+        let repository = canonicalize(Path::new(&repository))?;
+
+        let commit = format!("{}^{{commit}}", revision);
+        let commit = git(
+            &repository,
+            ["rev-parse", "--verify", commit.as_str()],
+            revision,
+        )?;
+
+        // git resolves a relative path against the repository, instead of the current directory
+        let path = if path.is_absolute() {
+            path
+        } else {
+            current_dir()?.join(path)
+        };
+
+        remove(&repository, &path);
+
+        git(
+            &repository,
+            [
+                OsStr::new("worktree"),
+                OsStr::new("add"),
+                OsStr::new("--detach"),
+                path.as_os_str(),
+                OsStr::new(&commit),
+            ],
+            revision,
+        )?;
+
+        Ok(Self {
+            revision: revision.to_string(),
+            commit,
+            repository,
+            path,
+        })
+    }
+
+    pub(crate) fn revision(&self) -> &str {
+        &self.revision
+    }
+
+    pub(crate) fn commit(&self) -> &str {
+        &self.commit
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The date of the checked out commit, if git can tell.
+    pub(crate) fn date(&self) -> Option<Date> {
+        git(
+            &self.repository,
+            [
+                "log",
+                "-1",
+                "--format=%cd",
+                "--date=short",
+                self.commit.as_str(),
+            ],
+            &self.revision,
+        )
+        .ok()
+        .and_then(|date| date.parse().ok())
+    }
+
+    /// The directory in the worktree which corresponds to the given directory of the repository.
+    pub(crate) fn translate(&self, dir: &Path) -> TResult<PathBuf> {
+        let dir = canonicalize(dir)?;
+
+        dir.strip_prefix(&self.repository)
+            .map(|relative| self.path.join(relative))
+            .map_err(|_| CargoMSRVError::CheckoutRevision {
+                revision: self.revision.clone(),
+                message: format!(
+                    "'{}' is not part of the repository at '{}'",
+                    dir.display(),
+                    self.repository.display()
+                ),
+            })
+    }
+}
+
+impl Drop for Worktree {
+    fn drop(&mut self) {
+        remove(&self.repository, &self.path);
+    }
+}
+
+/// The tags of the repository which contains `dir` which are reachable from `HEAD`, from the least
+/// to the most recently created tag.
+pub(crate) fn tags(dir: &Path) -> TResult<Vec<String>> {
+    list_revisions(
+        dir,
+        &["tag", "--merged", "HEAD", "--sort=creatordate"],
+        "tags",
+    )
+}
+
+/// The commits of the given range of the repository which contains `dir`, like `v1.0.0..HEAD`,
+/// from the least to the most recent commit.
+pub(crate) fn commits(dir: &Path, range: &str) -> TResult<Vec<String>> {
+    list_revisions(dir, &["rev-list", "--reverse", range], range)
+}
+
+fn list_revisions(dir: &Path, args: &[&str], revisions: &str) -> TResult<Vec<String>> {
+    let output = run(dir, args)?.map_err(|message| CargoMSRVError::ListRevisions {
+        revisions: revisions.to_string(),
+        message,
+    })?;
+
+    Ok(output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(String::from)
+        .collect())
+}
+
+/// Run git in the given directory, and return its trimmed output.
+fn git<I, S>(dir: &Path, args: I, revision: &str) -> TResult<String>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<OsStr>,
+{
+    run(dir, args)?.map_err(|message| CargoMSRVError::CheckoutRevision {
+        revision: revision.to_string(),
+        message,
+    })
+}
+
+/// Run git in the given directory. Returns its trimmed output when it succeeds, and the trimmed
+/// error message otherwise.
+fn run<I, S>(dir: &Path, args: I) -> TResult<Result<String, String>>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<OsStr>,
+{
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .map_err(|error| CargoMSRVError::Io {
+            error,
+            source: IoErrorSource::SpawnProcess("git".into()),
+        })?;
+
+    if !output.status.success() {
+        return Ok(Err(String::from_utf8_lossy(&output.stderr)
+            .trim()
+            .to_string()));
+    }
+
+    Ok(Ok(String::from_utf8_lossy(&output.stdout)
+        .trim()
+        .to_string()))
+}
+
+/// Remove the worktree at the given path, if any, and forget about it in the repository. Failures
+/// are ignored, since there may be nothing to remove.
+fn remove(repository: &Path, path: &Path) {
+    let _ = Command::new("git")
+        .arg("-C")
+        .arg(repository)
+        .args(["worktree", "remove", "--force"])
+        .arg(path)
+        .output();
+
+    let _ = std::fs::remove_dir_all(path);
+
+    let _ = Command::new("git")
+        .arg("-C")
+        .arg(repository)
+        .args(["worktree", "prune"])
+        .output();
+}
+
+fn current_dir() -> TResult<PathBuf> {
+    std::env::current_dir().map_err(|error| CargoMSRVError::Io {
+        error,
+        source: IoErrorSource::CurrentDir,
+    })
+}
+
+fn canonicalize(path: &Path) -> TResult<PathBuf> {
+    path.canonicalize().map_err(|error| CargoMSRVError::Io {
+        error,
+        source: IoErrorSource::ReadDir(path.to_path_buf()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_dir::{DirBuilder, TestDir};
+
+    fn commit(repository: &Path, contents: &str) {
+        std::fs::write(repository.join("Cargo.toml"), contents).unwrap();
+
+        for args in [
+            &["add", "Cargo.toml"][..],
+            &[
+                "-c",
+                "user.name=cargo-msrv",
+                "-c",
+                "user.email=cargo-msrv@example.com",
+                "commit",
+                "--quiet",
+                "--message",
+                contents,
+            ][..],
+        ] {
+            git(repository, args, "HEAD").unwrap();
+        }
+    }
+
+    #[test]
+    fn checkout_revision() {
+        let tmp = TestDir::temp();
+        let repository = tmp.path("repository");
+        std::fs::create_dir_all(&repository).unwrap();
+        git(&repository, ["init", "--quiet"], "HEAD").unwrap();
+
+        commit(&repository, "first");
+        git(&repository, ["tag", "v1.0.0"], "HEAD").unwrap();
+        commit(&repository, "second");
+
+        let path = tmp.path("worktree");
+        let worktree = Worktree::add(&repository, "v1.0.0", path.clone()).unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(path.join("Cargo.toml")).unwrap(),
+            "first"
+        );
+        assert_eq!(
+            std::fs::read_to_string(repository.join("Cargo.toml")).unwrap(),
+            "second"
+        );
+        assert_eq!(worktree.translate(&repository).unwrap(), path);
+
+        drop(worktree);
+
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn listed_revisions() {
+        let tmp = TestDir::temp();
+        let repository = tmp.path("repository");
+        std::fs::create_dir_all(&repository).unwrap();
+        git(&repository, ["init", "--quiet"], "HEAD").unwrap();
+
+        commit(&repository, "first");
+        git(&repository, ["tag", "v1.0.0"], "HEAD").unwrap();
+        commit(&repository, "second");
+        commit(&repository, "third");
+
+        assert_eq!(tags(&repository).unwrap(), vec!["v1.0.0".to_string()]);
+        assert_eq!(commits(&repository, "v1.0.0..HEAD").unwrap().len(), 2);
+
+        let worktree = Worktree::add(&repository, "v1.0.0", tmp.path("worktree")).unwrap();
+        assert!(worktree.date().is_some());
+
+        assert!(matches!(
+            commits(&repository, "v9.9.9..HEAD"),
+            Err(CargoMSRVError::ListRevisions { revisions, .. }) if revisions == "v9.9.9..HEAD"
+        ));
+    }
+
+    #[test]
+    fn unknown_revision() {
+        let tmp = TestDir::temp();
+        let repository = tmp.path("repository");
+        std::fs::create_dir_all(&repository).unwrap();
+        git(&repository, ["init", "--quiet"], "HEAD").unwrap();
+        commit(&repository, "first");
+
+        let result = Worktree::add(&repository, "v9.9.9", tmp.path("worktree"));
+
+        assert!(matches!(
+            result,
+            Err(CargoMSRVError::CheckoutRevision { revision, .. }) if revision == "v9.9.9"
+        ));
+    }
+}
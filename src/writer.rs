@@ -1,2 +1,3 @@
+pub mod result_file;
 pub mod toolchain_file;
 pub mod write_msrv;
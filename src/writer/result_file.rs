@@ -0,0 +1,222 @@
+//! A small TOML file which records the MSRV found by cargo-msrv, as written with `--write-result`.
+//!
+//! Contrary to the MSRV written to the Cargo manifest, this file is meant to be committed and
+//! reviewed on its own. The recorded MSRV can be validated with `cargo msrv verify --against-result`.
+
+use crate::error::IoErrorSource;
+use crate::release_schedule;
+use crate::reporter::event::{AuxiliaryOutput, AuxiliaryOutputItem, Destination};
+use crate::reporter::Reporter;
+use crate::{semver, CargoMSRVError, Config, TResult};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+use toml_edit::{table, value, Date, Datetime, Document, Offset, Time};
+
+const HEADER: &str =
+    "# Written by cargo-msrv, verify with `cargo msrv verify --against-result <FILE>`\n";
+
+/// Write the found MSRV, and the configuration with which it was found, to the result file at
+/// `path`.
+pub fn write_result_file(
+    config: &Config,
+    reporter: &impl Reporter,
+    path: &Path,
+    version: &semver::Version,
+) -> TResult<()> {
+    let result = ResultFile::new(
+        version.clone(),
+        config.check_command_string(),
+        config.target().clone(),
+    );
+
+    std::fs::write(path, result.to_toml(SystemTime::now())).map_err(|error| {
+        CargoMSRVError::Io {
+            error,
+            source: IoErrorSource::WriteFile(path.to_path_buf()),
+        }
+    })?;
+
+    reporter.report_event(AuxiliaryOutput::new(
+        Destination::File(path.to_path_buf()),
+        AuxiliaryOutputItem::result_file(),
+    ))?;
+
+    Ok(())
+}
+
+/// The MSRV, and the check configuration with which it was found, as recorded in a result file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ResultFile {
+    msrv: semver::Version,
+    check_command: String,
+    target: String,
+}
+
+impl ResultFile {
+    pub fn new(msrv: semver::Version, check_command: String, target: String) -> Self {
+        Self {
+            msrv,
+            check_command,
+            target,
+        }
+    }
+
+    /// Read a result file, as written by `--write-result`.
+    pub fn read(path: &Path) -> TResult<Self> {
+        let contents = std::fs::read_to_string(path).map_err(|error| CargoMSRVError::Io {
+            error,
+            source: IoErrorSource::ReadFile(path.to_path_buf()),
+        })?;
+
+        Self::parse(&contents).map_err(|reason| CargoMSRVError::InvalidResultFile {
+            path: path.to_path_buf(),
+            reason,
+        })
+    }
+
+    pub fn msrv(&self) -> &semver::Version {
+        &self.msrv
+    }
+
+    pub fn check_command(&self) -> &str {
+        &self.check_command
+    }
+
+    pub fn target(&self) -> &str {
+        &self.target
+    }
+
+    fn parse(contents: &str) -> Result<Self, String> {
+        let document = contents
+            .parse::<Document>()
+            .map_err(|error| error.to_string())?;
+
+        let field = |table: &str, key: &str| {
+            document
+                .get(table)
+                .and_then(|table| table.get(key))
+                .and_then(|item| item.as_str())
+                .ok_or_else(|| format!("expected the key '{}.{}'", table, key))
+        };
+
+        let msrv = field("msrv", "version")?;
+        let msrv = semver::Version::parse(msrv)
+            .map_err(|error| format!("invalid version '{}': {}", msrv, error))?;
+
+        Ok(Self {
+            msrv,
+            check_command: field("check", "command")?.to_string(),
+            target: field("check", "target")?.to_string(),
+        })
+    }
+
+    fn to_toml(&self, found_at: SystemTime) -> String {
+        let mut document = Document::new();
+
+        document["msrv"] = table();
+        document["msrv"]["version"] = value(self.msrv.to_string());
+        document["msrv"]["found_at"] = value(utc_datetime(found_at));
+
+        document["check"] = table();
+        document["check"]["command"] = value(self.check_command.as_str());
+        document["check"]["target"] = value(self.target.as_str());
+
+        format!("{}{}", HEADER, document)
+    }
+}
+
+/// The given time as an offset date-time in UTC, e.g. `2022-10-16T08:30:00Z`.
+fn utc_datetime(time: SystemTime) -> Datetime {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default();
+
+    let date = release_schedule::Date::from_days_since_epoch((secs / 86_400) as i64);
+    let secs_of_day = secs % 86_400;
+
+    Datetime {
+        date: Some(Date {
+            year: date.year() as u16,
+            month: date.month(),
+            day: date.day(),
+        }),
+        time: Some(Time {
+            hour: (secs_of_day / 3600) as u8,
+            minute: (secs_of_day % 3600 / 60) as u8,
+            second: (secs_of_day % 60) as u8,
+            nanosecond: 0,
+        }),
+        offset: Some(Offset::Z),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ConfigBuilder;
+    use crate::reporter::TestReporter;
+    use crate::{Action, Event};
+    use std::time::Duration;
+    use test_dir::{DirBuilder, TestDir};
+
+    #[test]
+    fn to_toml() {
+        let result = ResultFile::new(
+            semver::Version::new(1, 56, 0),
+            "cargo check".to_string(),
+            "x86_64-unknown-linux-gnu".to_string(),
+        );
+
+        let found_at = UNIX_EPOCH + Duration::from_secs(1_665_909_000);
+
+        assert_eq!(
+            result.to_toml(found_at),
+            r#"# Written by cargo-msrv, verify with `cargo msrv verify --against-result <FILE>`
+
+[msrv]
+version = "1.56.0"
+found_at = 2022-10-16T08:30:00Z
+
+[check]
+command = "cargo check"
+target = "x86_64-unknown-linux-gnu"
+"#
+        );
+    }
+
+    #[test]
+    fn write_and_read() {
+        let tmp = TestDir::temp();
+        let path = tmp.path("msrv.toml");
+
+        let config = ConfigBuilder::new(Action::Find, "x86_64-unknown-linux-gnu").build();
+        let reporter = TestReporter::default();
+        let version = semver::Version::new(1, 56, 0);
+
+        write_result_file(&config, reporter.reporter(), &path, &version).unwrap();
+
+        let result = ResultFile::read(&path).unwrap();
+        assert_eq!(result.msrv(), &version);
+        assert_eq!(result.check_command(), "cargo check");
+        assert_eq!(result.target(), "x86_64-unknown-linux-gnu");
+
+        let expected: Vec<Event> =
+            vec![
+                AuxiliaryOutput::new(Destination::File(path), AuxiliaryOutputItem::result_file())
+                    .into(),
+            ];
+
+        phenomenon::contains_at_least_ordered(reporter.wait_for_events(), expected).assert_this();
+    }
+
+    #[yare::parameterized(
+        missing_version = { "[msrv]\n[check]\ncommand = \"cargo check\"\ntarget = \"x\"\n" },
+        invalid_version = { "[msrv]\nversion = \"1.56\"\n[check]\ncommand = \"cargo check\"\ntarget = \"x\"\n" },
+        missing_check = { "[msrv]\nversion = \"1.56.0\"\n" },
+        not_toml = { "msrv =" },
+    )]
+    fn parse_invalid(contents: &str) {
+        assert!(ResultFile::parse(contents).is_err());
+    }
+}
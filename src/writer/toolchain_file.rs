@@ -1,4 +1,5 @@
 use crate::combinators::ThenSome;
+use crate::config::ToolchainFileFormat;
 use crate::error::IoErrorSource;
 use crate::reporter::event::{
     AuxiliaryOutput, AuxiliaryOutputItem, Destination, ToolchainFileKind,
@@ -6,23 +7,33 @@ use crate::reporter::event::{
 use crate::reporter::Reporter;
 use crate::{semver, CargoMSRVError, Config, TResult};
 use std::fmt;
+use std::io;
 use std::path::{Path, PathBuf};
+use toml_edit::{table, value, Document, Item, Value};
 
 const TOOLCHAIN_FILE: &str = "rust-toolchain";
 const TOOLCHAIN_FILE_TOML: &str = "rust-toolchain.toml";
 
-// - consider: replace toolchain file with 'best' rust-toolchain(.toml) format variant available
-// - consider: support old toolchain-file format
-// - consider: do not simply override, also support components, targets, profile
-//     - in reverse: use the values from rust-toolchain file to auto configure config
+// - consider: use the values from rust-toolchain file to auto configure config
 pub fn write_toolchain_file(
     config: &Config,
     reporter: &impl Reporter,
     stable_version: &semver::Version,
 ) -> TResult<()> {
     let path_prefix = config.context().crate_root_path()?;
-    let path = toolchain_file(path_prefix);
-    let content = format_toolchain_file(stable_version);
+    let format = config.toolchain_file_format();
+    let path = toolchain_file(path_prefix, format);
+
+    let (content, kind) = match format {
+        ToolchainFileFormat::Legacy => (
+            format_legacy_toolchain_file(stable_version),
+            ToolchainFileKind::Legacy,
+        ),
+        ToolchainFileFormat::Toml => (
+            update_toolchain_file(&read_existing(&path)?, &path, stable_version)?,
+            ToolchainFileKind::Toml,
+        ),
+    };
 
     std::fs::write(&path, content).map_err(|error| CargoMSRVError::Io {
         error,
@@ -31,14 +42,18 @@ pub fn write_toolchain_file(
 
     reporter.report_event(AuxiliaryOutput::new(
         Destination::File(path),
-        AuxiliaryOutputItem::toolchain_file(ToolchainFileKind::Toml),
+        AuxiliaryOutputItem::toolchain_file(kind),
     ))?;
 
     Ok(())
 }
 
 /// Determine whether we should use a .toml extension or no extension for the rust-toolchain file.
-fn toolchain_file(path: &Path) -> PathBuf {
+///
+/// A toolchain file in the legacy format can't have an extension. A toolchain file in the toml
+/// format is written to the existing toolchain file, if any, since rustup accepts the toml format
+/// without extension as well.
+fn toolchain_file(path: &Path, format: ToolchainFileFormat) -> PathBuf {
     fn without_extension(path: &Path) -> Option<PathBuf> {
         let file = path.join(TOOLCHAIN_FILE);
         ThenSome::then_some(file.exists(), file)
@@ -49,28 +64,79 @@ fn toolchain_file(path: &Path) -> PathBuf {
         ThenSome::then_some(file.exists(), file)
     }
 
-    // Without extension variant has precedence over with extension variant
-    // https://rust-lang.github.io/rustup/overrides.html#the-toolchain-file
-    without_extension(path)
-        .or_else(|| with_extension(path))
-        .unwrap_or_else(|| path.join(TOOLCHAIN_FILE))
+    match format {
+        ToolchainFileFormat::Legacy => path.join(TOOLCHAIN_FILE),
+        // Without extension variant has precedence over with extension variant
+        // https://rust-lang.github.io/rustup/overrides.html#the-toolchain-file
+        ToolchainFileFormat::Toml => without_extension(path)
+            .or_else(|| with_extension(path))
+            .unwrap_or_else(|| path.join(TOOLCHAIN_FILE_TOML)),
+    }
+}
+
+/// The contents of the existing toolchain file, or an empty string if there is none yet.
+fn read_existing(path: &Path) -> TResult<String> {
+    match std::fs::read_to_string(path) {
+        Ok(contents) => Ok(contents),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(String::new()),
+        // A directory can't be written either, which is reported instead
+        Err(_) if path.is_dir() => Ok(String::new()),
+        Err(error) => Err(CargoMSRVError::Io {
+            error,
+            source: IoErrorSource::ReadFile(path.to_path_buf()),
+        }),
+    }
 }
 
-fn format_toolchain_file<D>(channel: &D) -> String
+fn format_legacy_toolchain_file<D>(channel: &D) -> String
 where
     D: fmt::Display,
 {
-    format!(
-        r#"[toolchain]
-channel = "{}"
-"#,
-        channel
-    )
+    format!("{}\n", channel)
+}
+
+/// Set the channel of the toolchain file in the toml format. The other values of an existing
+/// toolchain file, like its components and targets, are preserved. An existing toolchain file in
+/// the legacy format, which holds just the channel, is replaced.
+fn update_toolchain_file<D>(existing: &str, path: &Path, channel: &D) -> TResult<String>
+where
+    D: fmt::Display,
+{
+    let mut document = match existing.parse::<Document>() {
+        Ok(document) => document,
+        Err(_) if is_legacy(existing) => Document::new(),
+        Err(error) => return Err(CargoMSRVError::ParseToml(error)),
+    };
+
+    let toolchain = &mut document["toolchain"];
+
+    match toolchain {
+        // Explicitly create the table, otherwise it would default to an inline table instead
+        Item::None => *toolchain = table(),
+        Item::Table(_) | Item::Value(Value::InlineTable(_)) => {}
+        _ => {
+            return Err(CargoMSRVError::InvalidConfig(format!(
+                "Expected a [toolchain] table in '{}'",
+                path.display()
+            )))
+        }
+    }
+
+    toolchain["channel"] = value(channel.to_string());
+
+    Ok(document.to_string())
+}
+
+/// Whether the contents are those of a toolchain file in the legacy format: a single channel.
+fn is_legacy(contents: &str) -> bool {
+    let contents = contents.trim();
+
+    !contents.contains(char::is_whitespace) && !contents.contains(['=', '['])
 }
 
 #[cfg(test)]
 mod write_toolchain_file_tests {
-    use crate::config::ConfigBuilder;
+    use crate::config::{ConfigBuilder, ToolchainFileFormat};
     use crate::error::IoErrorSource;
     use crate::reporter::event::{
         AuxiliaryOutput, AuxiliaryOutputItem, Destination, ToolchainFileKind,
@@ -91,22 +157,75 @@ mod write_toolchain_file_tests {
         let fake_reporter = FakeTestReporter::default();
         let version = semver::Version::new(1, 22, 44);
 
-        let toolchain_file = tmp.path("rust-toolchain");
-        assert!(!toolchain_file.exists()); // should not exist yet
+        let toolchain_file_toml = tmp.path("rust-toolchain.toml");
+        assert!(!toolchain_file_toml.exists()); // should not exist yet
 
         write_toolchain_file(&config, &fake_reporter, &version).unwrap();
-        assert!(toolchain_file.exists()); // now should exist
+        assert!(toolchain_file_toml.exists()); // now should exist
 
-        let contents = std::fs::read_to_string(&toolchain_file).unwrap();
+        let contents = std::fs::read_to_string(&toolchain_file_toml).unwrap();
         let expected = r#"[toolchain]
 channel = "1.22.44"
 "#;
         assert_eq!(&contents, expected);
 
+        let toolchain_file = tmp.path("rust-toolchain");
+        assert!(!toolchain_file.exists()); // no ext variant should not exist
+    }
+
+    #[test]
+    fn legacy_format() {
+        let tmp = TestDir::temp();
+        let crate_path = tmp.root();
+        let config = ConfigBuilder::new(Action::Find, "")
+            .crate_path(Some(crate_path))
+            .toolchain_file_format(ToolchainFileFormat::Legacy)
+            .build();
+
+        let fake_reporter = FakeTestReporter::default();
+        let version = semver::Version::new(1, 22, 44);
+
+        write_toolchain_file(&config, &fake_reporter, &version).unwrap();
+
+        let contents = std::fs::read_to_string(tmp.path("rust-toolchain")).unwrap();
+        assert_eq!(&contents, "1.22.44\n");
+
         let toolchain_file_toml = tmp.path("rust-toolchain.toml");
         assert!(!toolchain_file_toml.exists()); // toml variant should not exist
     }
 
+    #[test]
+    fn preserves_components_and_targets() {
+        let tmp = TestDir::temp();
+        std::fs::write(
+            tmp.path("rust-toolchain.toml"),
+            r#"[toolchain]
+channel = "stable"
+components = ["rustfmt", "clippy"]
+targets = ["wasm32-unknown-unknown"]
+"#,
+        )
+        .unwrap();
+
+        let crate_path = tmp.root();
+        let config = ConfigBuilder::new(Action::Find, "")
+            .crate_path(Some(crate_path))
+            .build();
+
+        let fake_reporter = FakeTestReporter::default();
+        let version = semver::Version::new(1, 56, 1);
+
+        write_toolchain_file(&config, &fake_reporter, &version).unwrap();
+
+        let contents = std::fs::read_to_string(tmp.path("rust-toolchain.toml")).unwrap();
+        let expected = r#"[toolchain]
+channel = "1.56.1"
+components = ["rustfmt", "clippy"]
+targets = ["wasm32-unknown-unknown"]
+"#;
+        assert_eq!(&contents, expected);
+    }
+
     #[test]
     fn pre_existing_without_extension() {
         let tmp = TestDir::temp().create("rust-toolchain", FileType::EmptyFile);
@@ -218,7 +337,7 @@ channel = "1.55.77"
 
         let events = test_reporter.wait_for_events();
         let expected: Vec<Event> = vec![AuxiliaryOutput::new(
-            Destination::File(tmp.path("rust-toolchain")),
+            Destination::File(tmp.path("rust-toolchain.toml")),
             AuxiliaryOutputItem::toolchain_file(ToolchainFileKind::Toml),
         )
         .into()];
@@ -264,41 +383,53 @@ channel = "1.55.77"
 
 #[cfg(test)]
 mod toolchain_file_tests {
+    use crate::config::ToolchainFileFormat;
     use crate::writer::toolchain_file::toolchain_file;
     use test_dir::{DirBuilder, FileType, TestDir};
 
-    #[test]
-    fn without_extension() {
+    #[yare::parameterized(
+        toml = { ToolchainFileFormat::Toml, "rust-toolchain" },
+        legacy = { ToolchainFileFormat::Legacy, "rust-toolchain" },
+    )]
+    fn without_extension(format: ToolchainFileFormat, expected: &str) {
         let tmp = TestDir::temp().create("rust-toolchain", FileType::EmptyFile);
         let crate_path = tmp.root();
-        let toolchain_file = toolchain_file(crate_path);
+        let toolchain_file = toolchain_file(crate_path, format);
 
-        assert_eq!(toolchain_file, crate_path.join("rust-toolchain"));
+        assert_eq!(toolchain_file, crate_path.join(expected));
     }
 
-    #[test]
-    fn with_extension() {
+    #[yare::parameterized(
+        toml = { ToolchainFileFormat::Toml, "rust-toolchain.toml" },
+        legacy = { ToolchainFileFormat::Legacy, "rust-toolchain" },
+    )]
+    fn with_extension(format: ToolchainFileFormat, expected: &str) {
         let tmp = TestDir::temp().create("rust-toolchain.toml", FileType::EmptyFile);
         let crate_path = tmp.root();
-        let toolchain_file = toolchain_file(crate_path);
+        let toolchain_file = toolchain_file(crate_path, format);
 
-        assert_eq!(toolchain_file, crate_path.join("rust-toolchain.toml"));
+        assert_eq!(toolchain_file, crate_path.join(expected));
     }
 
-    #[test]
-    fn default() {
+    #[yare::parameterized(
+        toml = { ToolchainFileFormat::Toml, "rust-toolchain.toml" },
+        legacy = { ToolchainFileFormat::Legacy, "rust-toolchain" },
+    )]
+    fn default(format: ToolchainFileFormat, expected: &str) {
         let tmp = TestDir::temp();
         let crate_path = tmp.root();
-        let toolchain_file = toolchain_file(crate_path);
+        let toolchain_file = toolchain_file(crate_path, format);
 
-        assert_eq!(toolchain_file, crate_path.join("rust-toolchain"));
+        assert_eq!(toolchain_file, crate_path.join(expected));
     }
 }
 
 #[cfg(test)]
 mod format_toolchain_file_tests {
-    use crate::writer::toolchain_file::format_toolchain_file;
+    use crate::writer::toolchain_file::{format_legacy_toolchain_file, update_toolchain_file};
+    use crate::CargoMSRVError;
     use std::fmt;
+    use std::path::Path;
 
     #[yare::parameterized(
         str_value = { Box::new("1.36.0") },
@@ -306,10 +437,60 @@ mod format_toolchain_file_tests {
         bare_version = { Box::new(crate::manifest::bare_version::BareVersion::ThreeComponents(1, 36, 0))},
     )]
     fn values_which_impl_display(channel: Box<dyn fmt::Display>) {
-        let content = format_toolchain_file(&channel);
+        let content =
+            update_toolchain_file("", Path::new("rust-toolchain.toml"), &channel).unwrap();
         let expected = r#"[toolchain]
 channel = "1.36.0"
 "#;
         assert_eq!(&content, expected);
+
+        let content = format_legacy_toolchain_file(&channel);
+        assert_eq!(&content, "1.36.0\n");
+    }
+
+    #[yare::parameterized(
+        legacy = { "1.30.0\n", "[toolchain]\nchannel = \"1.36.0\"\n" },
+        profile = {
+            "[toolchain]\nchannel = \"nightly\"\nprofile = \"minimal\"\n",
+            "[toolchain]\nchannel = \"1.36.0\"\nprofile = \"minimal\"\n"
+        },
+        without_channel = {
+            "[toolchain]\ncomponents = [\"rustfmt\"]\n",
+            "[toolchain]\ncomponents = [\"rustfmt\"]\nchannel = \"1.36.0\"\n"
+        },
+    )]
+    fn updates_existing(existing: &str, expected: &str) {
+        let version = crate::semver::Version::new(1, 36, 0);
+        let content =
+            update_toolchain_file(existing, Path::new("rust-toolchain.toml"), &version).unwrap();
+
+        assert_eq!(&content, expected);
+    }
+
+    #[test]
+    fn updates_inline_table() {
+        let version = crate::semver::Version::new(1, 36, 0);
+        let existing = "toolchain = { channel = \"stable\", profile = \"minimal\" }\n";
+        let content =
+            update_toolchain_file(existing, Path::new("rust-toolchain.toml"), &version).unwrap();
+
+        let document = content.parse::<toml_edit::Document>().unwrap();
+        assert!(document["toolchain"].is_inline_table());
+        assert_eq!(document["toolchain"]["channel"].as_str(), Some("1.36.0"));
+        assert_eq!(document["toolchain"]["profile"].as_str(), Some("minimal"));
+    }
+
+    #[yare::parameterized(
+        not_a_table = { "toolchain = \"stable\"\n" },
+        invalid_toml = { "[toolchain\nchannel = \"stable\"\n" },
+    )]
+    fn rejects_existing(existing: &str) {
+        let version = crate::semver::Version::new(1, 36, 0);
+        let result = update_toolchain_file(existing, Path::new("rust-toolchain.toml"), &version);
+
+        assert!(matches!(
+            result,
+            Err(CargoMSRVError::InvalidConfig(_)) | Err(CargoMSRVError::ParseToml(_))
+        ));
     }
 }
@@ -1,16 +1,27 @@
+use rust_releases::ReleaseIndex;
+
 use crate::config::set::SetCmdConfig;
 use crate::config::{ConfigBuilder, SubCommandConfig};
+use crate::msrv::newer_patch_release;
 use crate::reporter::Reporter;
 use crate::{semver, Action, Config, Set, SubCommand, TResult};
 
 /// Write the MSRV to the Cargo manifest
 ///
-/// Repurposes the Set MSRV subcommand for this action.
+/// Repurposes the Set MSRV subcommand for this action. With `--prefer-newest-patch`, the most
+/// recent patch release of the minor version of the MSRV in the index is written instead.
 pub fn write_msrv(
     config: &Config,
     reporter: &impl Reporter,
+    index: &ReleaseIndex,
     version: &semver::Version,
 ) -> TResult<()> {
+    let version = if config.prefer_newest_patch() {
+        newer_patch_release(index, version).unwrap_or(version)
+    } else {
+        version
+    };
+
     let config = ConfigBuilder::from_config(config)
         .mode_intent(Action::Set)
         .sub_command_config(SubCommandConfig::SetConfig(SetCmdConfig {
@@ -30,10 +41,23 @@ mod tests {
     use crate::config::ConfigBuilder;
     use crate::reporter::FakeTestReporter;
     use crate::{semver, Action};
+    use rust_releases::{Release, ReleaseIndex};
+    use std::iter::FromIterator;
     use test_dir::{DirBuilder, FileType, TestDir};
 
-    #[test]
-    fn sample() {
+    fn index() -> ReleaseIndex {
+        ReleaseIndex::from_iter(vec![
+            Release::new_stable(semver::Version::new(2, 1, 0)),
+            Release::new_stable(semver::Version::new(2, 0, 7)),
+            Release::new_stable(semver::Version::new(2, 0, 5)),
+        ])
+    }
+
+    #[yare::parameterized(
+        as_found = { false, "2.0.5" },
+        prefer_newest_patch = { true, "2.0.7" },
+    )]
+    fn sample(prefer_newest_patch: bool, expected: &str) {
         let tmp = TestDir::temp().create("Cargo.toml", FileType::EmptyFile);
         let manifest = tmp.path("Cargo.toml");
 
@@ -42,14 +66,18 @@ mod tests {
         let crate_path = tmp.root();
         let config = ConfigBuilder::new(Action::Find, "")
             .crate_path(Some(crate_path))
+            .prefer_newest_patch(prefer_newest_patch)
             .build();
 
         let fake_reporter = FakeTestReporter::default();
         let version = semver::Version::new(2, 0, 5);
 
-        write_msrv(&config, &fake_reporter, &version).unwrap();
+        write_msrv(&config, &fake_reporter, &index(), &version).unwrap();
 
         let content = std::fs::read_to_string(&manifest).unwrap();
-        assert_eq!(content, "[package]\nrust-version = \"2.0.5\"\n");
+        assert_eq!(
+            content,
+            format!("[package]\nrust-version = \"{}\"\n", expected)
+        );
     }
 }